@@ -129,6 +129,7 @@ fn bench_request_processing(c: &mut Criterion) {
             ClientRequest::CallTool(CallToolParams {
                 name: "simple_tool".to_string(),
                 arguments: json!({"input": "test"}),
+                meta: None,
             }),
         ),
         (
@@ -139,6 +140,7 @@ fn bench_request_processing(c: &mut Criterion) {
                     "data": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
                     "operation": "average"
                 }),
+                meta: None,
             }),
         ),
         (
@@ -208,11 +210,15 @@ fn bench_response_generation(c: &mut Criterion) {
                                 name: "tool1".to_string(),
                                 description: Some("First tool".to_string()),
                                 input_schema: json!({"type": "object"}),
+                                output_schema: None,
+                                annotations: None,
                             },
                             ToolInfo {
                                 name: "tool2".to_string(),
                                 description: Some("Second tool".to_string()),
                                 input_schema: json!({"type": "object"}),
+                                output_schema: None,
+                                annotations: None,
                             },
                         ],
                         next_cursor: None,
@@ -233,6 +239,7 @@ fn bench_response_generation(c: &mut Criterion) {
                                 .to_string(),
                         }],
                         is_error: false,
+                        structured_content: None,
                     })
                     .unwrap(),
                 ),
@@ -285,6 +292,7 @@ fn bench_capabilities(c: &mut Criterion) {
         }),
         roots: Some(RootsCapabilities { list_changed: true }),
         sampling: Some(SamplingCapabilities { models: None }),
+        elicitation: None,
         experimental: None,
         logging: None,
     };
@@ -391,6 +399,7 @@ fn bench_batch_operations(c: &mut Criterion) {
                     "data": format!("Batch item {}", i),
                     "index": i
                 }),
+                meta: None,
             })
         })
         .collect();
@@ -413,6 +422,7 @@ fn bench_batch_operations(c: &mut Criterion) {
                 text: format!("Result for batch item {}: processed successfully", i),
             }],
             is_error: false,
+            structured_content: None,
         })
         .collect();
 
@@ -0,0 +1,144 @@
+//! Frame parsing benchmarks for the stdio transport
+//!
+//! Compares decoding a frame through an intermediate `serde_json::Value`
+//! with the zero-copy path in `pmcp::shared::frame_parser`, for large tool
+//! calls and tool results. A counting allocator reports the allocations
+//! each path makes per message before the timings run.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pmcp::shared::frame_parser::parse_frame;
+use pmcp::shared::protocol_helpers::parse_request;
+use pmcp::types::{JSONRPCRequest, JSONRPCResponse};
+use serde_json::{json, Value};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// System allocator that counts allocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations_of<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+/// A `tools/call` request carrying `rows` rows of arguments.
+fn tool_call_frame(rows: usize) -> Vec<u8> {
+    let rows: Vec<Value> = (0..rows)
+        .map(|i| json!({"id": i, "name": format!("row_{}", i), "tags": ["a", "b"], "score": 0.5}))
+        .collect();
+    serde_json::to_vec(&json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {"name": "import", "arguments": {"rows": rows}}
+    }))
+    .unwrap()
+}
+
+/// A tool result carrying `blocks` text blocks.
+fn tool_result_frame(blocks: usize) -> Vec<u8> {
+    let content: Vec<Value> = (0..blocks)
+        .map(|i| json!({"type": "text", "text": format!("line {} of the tool output", i)}))
+        .collect();
+    serde_json::to_vec(&json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {"content": content, "isError": false}
+    }))
+    .unwrap()
+}
+
+/// Decode a request the way the transport did before: bytes to a `Value`
+/// tree, then to the typed request.
+fn parse_request_via_value(frame: &[u8]) {
+    let value: Value = serde_json::from_slice(frame).unwrap();
+    let request: JSONRPCRequest<Value> = serde_json::from_value(value).unwrap();
+    black_box(parse_request(request).unwrap());
+}
+
+fn parse_response_via_value(frame: &[u8]) {
+    let value: Value = serde_json::from_slice(frame).unwrap();
+    let response: JSONRPCResponse = serde_json::from_value(value).unwrap();
+    black_box(response);
+}
+
+fn parse_zero_copy(frame: &[u8]) {
+    black_box(parse_frame(frame).unwrap());
+}
+
+fn report_allocations() {
+    for size in [10, 1_000] {
+        let call = tool_call_frame(size);
+        let result = tool_result_frame(size);
+        println!(
+            "tools/call, {} rows: {} allocations via Value, {} zero-copy",
+            size,
+            allocations_of(|| parse_request_via_value(&call)),
+            allocations_of(|| parse_zero_copy(&call)),
+        );
+        println!(
+            "tool result, {} blocks: {} allocations via Value, {} zero-copy",
+            size,
+            allocations_of(|| parse_response_via_value(&result)),
+            allocations_of(|| parse_zero_copy(&result)),
+        );
+    }
+}
+
+fn bench_tool_call_parsing(c: &mut Criterion) {
+    report_allocations();
+
+    let mut group = c.benchmark_group("frame_parsing_tool_call");
+    for rows in [10, 1_000] {
+        let frame = tool_call_frame(rows);
+        group.throughput(Throughput::Bytes(frame.len() as u64));
+        group.bench_with_input(BenchmarkId::new("via_value", rows), &frame, |b, frame| {
+            b.iter(|| parse_request_via_value(black_box(frame)))
+        });
+        group.bench_with_input(BenchmarkId::new("zero_copy", rows), &frame, |b, frame| {
+            b.iter(|| parse_zero_copy(black_box(frame)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_tool_result_parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frame_parsing_tool_result");
+    for blocks in [10, 1_000] {
+        let frame = tool_result_frame(blocks);
+        group.throughput(Throughput::Bytes(frame.len() as u64));
+        group.bench_with_input(BenchmarkId::new("via_value", blocks), &frame, |b, frame| {
+            b.iter(|| parse_response_via_value(black_box(frame)))
+        });
+        group.bench_with_input(BenchmarkId::new("zero_copy", blocks), &frame, |b, frame| {
+            b.iter(|| parse_zero_copy(black_box(frame)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_tool_call_parsing, bench_tool_result_parsing);
+criterion_main!(benches);
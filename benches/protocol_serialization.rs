@@ -49,6 +49,7 @@ fn bench_request_serialization(c: &mut Criterion) {
                 "format": "markdown"
             }
         }),
+        meta: None,
     });
 
     group.bench_function("call_tool_request", |b| {
@@ -149,6 +150,8 @@ fn bench_response_serialization(c: &mut Criterion) {
                         "limit": {"type": "number"}
                     }
                 }),
+                output_schema: None,
+                annotations: None,
             },
             ToolInfo {
                 name: "analyze".to_string(),
@@ -160,6 +163,8 @@ fn bench_response_serialization(c: &mut Criterion) {
                         "method": {"type": "string"}
                     }
                 }),
+                output_schema: None,
+                annotations: None,
             },
             ToolInfo {
                 name: "generate".to_string(),
@@ -171,6 +176,8 @@ fn bench_response_serialization(c: &mut Criterion) {
                         "variables": {"type": "object"}
                     }
                 }),
+                output_schema: None,
+                annotations: None,
             },
         ],
         next_cursor: None,
@@ -191,6 +198,7 @@ fn bench_response_serialization(c: &mut Criterion) {
             },
         ],
         is_error: false,
+        structured_content: None,
     };
 
     group.bench_function("call_tool_response", |b| {
@@ -274,6 +282,7 @@ fn bench_large_messages(c: &mut Criterion) {
     let large_response = CallToolResult {
         content: large_content,
         is_error: false,
+        structured_content: None,
     };
 
     group.bench_function("large_tool_response_serialize", |b| {
@@ -299,6 +308,8 @@ fn bench_large_messages(c: &mut Criterion) {
                     }
                 }
             }),
+            output_schema: None,
+            annotations: None,
         })
         .collect();
 
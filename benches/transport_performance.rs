@@ -62,6 +62,7 @@ fn bench_message_formatting(c: &mut Criterion) {
             text: format!("This is a long piece of content for item {} that simulates a realistic response from an MCP tool with substantial data.", i),
         }).collect(),
         is_error: false,
+        structured_content: None,
     }).unwrap();
 
     let test_messages = [
@@ -146,6 +147,7 @@ fn bench_protocol_helpers(c: &mut Criterion) {
                     "input": "test data",
                     "options": {"format": "json"}
                 }),
+                meta: None,
             }));
             black_box(request)
         })
@@ -228,6 +230,7 @@ fn bench_concurrent_processing(c: &mut Criterion) {
                     "id": i,
                     "data": format!("Message data for request {}", i)
                 }),
+                meta: None,
             }))
             .unwrap()
         })
@@ -248,6 +251,7 @@ fn bench_concurrent_processing(c: &mut Criterion) {
             ClientRequest::CallTool(CallToolParams {
                 name: format!("tool_{}", i),
                 arguments: serde_json::json!({"id": i}),
+                meta: None,
             })
         })
         .collect();
@@ -35,6 +35,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         sampling: Some(pmcp::types::capabilities::SamplingCapabilities::default()),
         // Client supports roots
         roots: Some(pmcp::types::capabilities::RootsCapabilities::default()),
+        // Client can ask its user for input on the server's behalf
+        elicitation: Some(pmcp::types::capabilities::ElicitationCapabilities::default()),
         // No experimental features
         experimental: None,
     };
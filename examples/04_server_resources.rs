@@ -65,6 +65,7 @@ impl ResourceHandler for FileSystemResources {
                 contents: vec![Content::Text {
                     text: content.clone(),
                 }],
+                meta: None,
             }),
             None => Err(pmcp::Error::protocol(
                 pmcp::ErrorCode::METHOD_NOT_FOUND,
@@ -126,6 +127,7 @@ impl ResourceHandler for TemplateResources {
                 contents: vec![Content::Text {
                     text: format!("Hello, {}! Welcome to MCP resources.", name),
                 }],
+                meta: None,
             })
         } else if uri.starts_with("template://time/") {
             let timezone = uri.strip_prefix("template://time/").unwrap_or("UTC");
@@ -138,6 +140,7 @@ impl ResourceHandler for TemplateResources {
                         chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
                     ),
                 }],
+                meta: None,
             })
         } else {
             Err(pmcp::Error::protocol(
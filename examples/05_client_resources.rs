@@ -118,6 +118,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             data.len()
                         );
                     },
+                    pmcp::types::Content::Audio { data, mime_type } => {
+                        println!(
+                            "   Audio content: {} (data length: {})",
+                            mime_type,
+                            data.len()
+                        );
+                    },
+                    pmcp::types::Content::ResourceLink(link) => {
+                        println!("   Link to: {}", link.uri);
+                    },
+                    pmcp::types::Content::EmbeddedResource { resource } => {
+                        println!("   Embedded resource: {}", resource.uri);
+                    },
                 }
             }
         },
@@ -93,7 +93,10 @@ fn fibonacci(n: u32) -> u32 {
                     match &msg.content {
                         pmcp::types::Content::Text { text } => text,
                         pmcp::types::Content::Image { .. } => "[Image content]",
-                        pmcp::types::Content::Resource { .. } => "[Resource content]",
+                        pmcp::types::Content::Audio { .. } => "[Audio content]",
+                        pmcp::types::Content::Resource { .. }
+                        | pmcp::types::Content::EmbeddedResource { .. } => "[Resource content]",
+                        pmcp::types::Content::ResourceLink(_) => "[Resource link]",
                     }
                 );
             }
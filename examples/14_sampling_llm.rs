@@ -35,7 +35,9 @@ impl SamplingHandler for MockLLM {
                 .map(|m| match &m.content {
                     Content::Text { text } => text.as_str(),
                     Content::Image { .. } => "[image]",
-                    Content::Resource { .. } => "[resource]",
+                    Content::Audio { .. } => "[audio]",
+                    Content::Resource { .. } | Content::EmbeddedResource { .. } => "[resource]",
+                    Content::ResourceLink(_) => "[resource link]",
                 })
                 .unwrap_or("empty")
         );
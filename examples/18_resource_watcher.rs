@@ -146,6 +146,7 @@ impl ResourceHandler for FileSystemResourceHandler {
 
         Ok(ReadResourceResult {
             contents: vec![Content::Text { text: content }],
+            meta: None,
         })
     }
 
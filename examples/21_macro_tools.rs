@@ -0,0 +1,80 @@
+//! Example: Declaring tools with the #[tool] macro
+//!
+//! This example demonstrates:
+//! - Turning functions into tool handlers with `#[tool]`
+//! - Descriptions taken from doc comments
+//! - Input schemas generated from typed parameters
+//! - Optional parameters and access to the request context
+//!
+//! Run with:
+//! ```bash
+//! cargo run --example 21_macro_tools --features macros
+//! ```
+
+use pmcp::types::capabilities::ServerCapabilities;
+use pmcp::{tool, RequestHandlerExtra, Server};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Arithmetic operation to perform
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum Operation {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Serialize)]
+struct CalculatorResult {
+    result: f64,
+    expression: String,
+}
+
+/// Perform an arithmetic operation on two numbers.
+#[tool]
+async fn calculate(operation: Operation, a: f64, b: f64) -> Result<CalculatorResult, String> {
+    let (result, symbol) = match operation {
+        Operation::Add => (a + b, '+'),
+        Operation::Subtract => (a - b, '-'),
+        Operation::Multiply => (a * b, '*'),
+        Operation::Divide if b == 0.0 => return Err("Division by zero".to_string()),
+        Operation::Divide => (a / b, '/'),
+    };
+    Ok(CalculatorResult {
+        result,
+        expression: format!("{} {} {} = {}", a, symbol, b, result),
+    })
+}
+
+/// Greet someone, optionally with a title.
+#[tool(name = "greet")]
+async fn greeting(name: String, title: Option<String>, extra: RequestHandlerExtra) -> String {
+    tracing::info!("Handling request {}", extra.request_id);
+    match title {
+        Some(title) => format!("Hello, {} {}!", title, name),
+        None => format!("Hello, {}!", name),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter("pmcp=info")
+        .init();
+
+    let server = Server::builder()
+        .name("macro-tools-server")
+        .version("1.0.0")
+        .capabilities(ServerCapabilities::tools_only())
+        .tool(CalculateToolHandler::NAME, CalculateToolHandler)
+        .tool(GreetingToolHandler::NAME, GreetingToolHandler)
+        .build()?;
+
+    println!("Server ready! Listening on stdio...");
+    println!("Available tools: calculate, greet");
+
+    server.run_stdio().await?;
+    Ok(())
+}
@@ -28,7 +28,6 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::Arc;
-use tokio::sync::Mutex;
 use tracing::info;
 
 // === Tool Implementations ===
@@ -125,6 +124,8 @@ impl ToolHandler for CalculatorTool {
                 },
                 "required": ["operation", "a", "b"]
             }),
+            output_schema: None,
+            annotations: None,
         })
     }
 }
@@ -272,8 +273,8 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
         .build()
         .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
 
-    // Wrap server in Arc<Mutex<>> for sharing
-    let server = Arc::new(Mutex::new(server));
+    // Wrap server in Arc for sharing
+    let server = Arc::new(server);
 
     // Configure the HTTP server address
     let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 8080);
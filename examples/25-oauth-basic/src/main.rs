@@ -16,7 +16,6 @@ use pmcp::{
 use serde_json::{json, Value};
 use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
 /// A public tool that doesn't require authentication
 struct PublicTool;
@@ -148,8 +147,8 @@ async fn main() -> Result<()> {
 
             #[cfg(feature = "streamable-http")]
             {
-                // Wrap server in Arc<Mutex<>> for HTTP transport
-                let server = Arc::new(Mutex::new(server));
+                // Wrap server in Arc for HTTP transport
+                let server = Arc::new(server);
 
                 // Configure the HTTP server address
                 let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port);
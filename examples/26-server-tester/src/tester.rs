@@ -1139,6 +1139,7 @@ impl ServerTester {
                                     text: format!("{}", result),
                                 }],
                                 is_error: false,
+                                structured_content: None,
                             })
                         } else {
                             Err(pmcp::Error::Internal(
@@ -1413,6 +1414,11 @@ impl ServerTester {
                                     println!("      MIME type: {}", mime_type);
                                     println!("      Data size: {} bytes (base64)", data.len());
                                 },
+                                pmcp::types::Content::Audio { data, mime_type } => {
+                                    println!("      Content type: Audio");
+                                    println!("      MIME type: {}", mime_type);
+                                    println!("      Data size: {} bytes (base64)", data.len());
+                                },
                                 pmcp::types::Content::Resource {
                                     uri,
                                     text,
@@ -1432,6 +1438,14 @@ impl ServerTester {
                                         println!("      Text: {}", preview);
                                     }
                                 },
+                                pmcp::types::Content::ResourceLink(link) => {
+                                    println!("      Content type: Resource Link");
+                                    println!("      URI: {}", link.uri);
+                                },
+                                pmcp::types::Content::EmbeddedResource { resource } => {
+                                    println!("      Content type: Embedded Resource");
+                                    println!("      URI: {}", resource.uri);
+                                },
                             }
                         }
 
@@ -1480,6 +1494,20 @@ impl ServerTester {
                                     ));
                                 }
                             },
+                            pmcp::types::Content::Audio { data, mime_type } => {
+                                if data.is_empty() {
+                                    warnings.push(format!(
+                                        "Resource '{}' has empty audio data",
+                                        resource.name
+                                    ));
+                                }
+                                if !mime_type.starts_with("audio/") {
+                                    warnings.push(format!(
+                                        "Resource '{}' has non-audio MIME type '{}' for audio content",
+                                        resource.name, mime_type
+                                    ));
+                                }
+                            },
                             pmcp::types::Content::Resource {
                                 uri,
                                 text: _,
@@ -1503,6 +1531,13 @@ impl ServerTester {
                                     }
                                 }
                             },
+                            pmcp::types::Content::ResourceLink(_)
+                            | pmcp::types::Content::EmbeddedResource { .. } => {
+                                warnings.push(format!(
+                                    "Resource '{}' returns tool result content instead of resource contents",
+                                    resource.name
+                                ));
+                            },
                         }
                     }
 
@@ -1875,6 +1910,7 @@ impl ServerTester {
                                     text: "Unexpected success".to_string(),
                                 }],
                                 is_error: false,
+                                structured_content: None,
                             })
                         }
                     },
@@ -2301,7 +2337,10 @@ impl ServerTester {
             },
             _ => {
                 // Return empty resource for other transport types
-                Ok(pmcp::types::ReadResourceResult { contents: vec![] })
+                Ok(pmcp::types::ReadResourceResult {
+                    contents: vec![],
+                    meta: None,
+                })
             },
         }
     }
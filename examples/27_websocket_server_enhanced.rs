@@ -25,6 +25,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         max_message_size: Some(10 * 1024 * 1024), // 10MB
         enable_pooling: true,
         enable_broadcast: true, // Enable broadcast mode
+        message_size_limits: Default::default(),
     };
 
     // Create and start the server
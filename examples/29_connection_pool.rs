@@ -74,6 +74,7 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
         auto_scaling: true,
         max_retries: 3,
         retry_delay: Duration::from_secs(1),
+        ..Default::default()
     };
 
     info!("✅ Configuration:");
@@ -117,6 +118,7 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
         LoadBalanceStrategy::LeastConnections,
         LoadBalanceStrategy::WeightedRoundRobin,
         LoadBalanceStrategy::Random,
+        LoadBalanceStrategy::AdaptiveLatency,
     ];
 
     for strategy in strategies {
@@ -266,6 +266,8 @@ impl ToolHandler for CustomValidatedTool {
                     "properties": {}
                 })
             }),
+            output_schema: None,
+            annotations: None,
         })
     }
 }
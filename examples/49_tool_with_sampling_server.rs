@@ -205,11 +205,12 @@ async fn main() -> Result<()> {
     let server = Server::builder()
         .name("sampling-tools-server")
         .version("1.0.0")
-        .capabilities(ServerCapabilities {
+        // No sampling handler is registered here, so advertise the
+        // capabilities verbatim rather than deriving them from handlers
+        .override_capabilities(ServerCapabilities {
             tools: Some(pmcp::ToolCapabilities {
                 list_changed: Some(true),
             }),
-            // In a real implementation, you would also enable sampling capabilities
             sampling: Some(pmcp::SamplingCapabilities {
                 models: Some(vec![
                     "example-llm-model".to_string(),
@@ -1,4 +1,5 @@
 use pmcp::client::Client;
+use pmcp::shared::{MonotonicIdGenerator, RequestIdGenerator};
 use pmcp::types::ClientCapabilities;
 use pmcp::{WasmHttpClient, WasmHttpConfig, WasmWebSocketTransport};
 use serde::Serialize;
@@ -67,6 +68,8 @@ pub struct WasmClient {
     // We use Option to handle both client types
     ws_client: Option<Client<WasmWebSocketTransport>>,
     http_client: Option<WasmHttpClient>,
+    // Ids for requests sent directly over the HTTP client
+    request_ids: MonotonicIdGenerator,
 }
 
 #[wasm_bindgen]
@@ -79,6 +82,7 @@ impl WasmClient {
             connection_type: None,
             ws_client: None,
             http_client: None,
+            request_ids: MonotonicIdGenerator::new(),
         }
     }
 
@@ -112,7 +116,7 @@ impl WasmClient {
 
             // Initialize the connection - wrap in TransportMessage
             let init_request = pmcp::shared::TransportMessage::Request {
-                id: 1i64.into(),
+                id: self.request_ids.next_id(),
                 request: pmcp::types::Request::Client(Box::new(
                     pmcp::types::ClientRequest::Initialize(pmcp::types::InitializeParams {
                         protocol_version: pmcp::LATEST_PROTOCOL_VERSION.to_string(),
@@ -186,7 +190,7 @@ impl WasmClient {
 
                 // Create list tools request as TransportMessage
                 let request = pmcp::shared::TransportMessage::Request {
-                    id: 2i64.into(),
+                    id: self.request_ids.next_id(),
                     request: pmcp::types::Request::Client(Box::new(
                         pmcp::types::ClientRequest::ListTools(pmcp::types::ListToolsRequest {
                             cursor: None,
@@ -243,11 +247,12 @@ impl WasmClient {
 
                 // Create call tool request as TransportMessage
                 let request = pmcp::shared::TransportMessage::Request {
-                    id: 3i64.into(), // TODO: Implement proper request ID tracking
+                    id: self.request_ids.next_id(),
                     request: pmcp::types::Request::Client(Box::new(
                         pmcp::types::ClientRequest::CallTool(pmcp::types::CallToolRequest {
                             name,
                             arguments,
+                            meta: None,
                         }),
                     )),
                 };
@@ -1,5 +1,8 @@
-use pmcp::server::wasm_server::{SimpleTool, WasmMcpServer};
-use pmcp::types::{ClientRequest, Request as McpRequest, ServerCapabilities};
+mod session_store;
+
+use pmcp::server::wasm_server::{SessionHeaders, SimpleTool, WasmMcpServer};
+use pmcp::shared::http_constants::{MCP_PROTOCOL_VERSION, MCP_SESSION_ID};
+use pmcp::types::ServerCapabilities;
 use serde_json::{json, Value};
 use spin_sdk::http::{IntoResponse, Request, Response};
 use spin_sdk::http_component;
@@ -10,12 +13,15 @@ use spin_sdk::http_component;
 /// The actual MCP implementation is shared across all WASM platforms.
 #[http_component]
 fn handle_request(req: Request) -> anyhow::Result<impl IntoResponse> {
+    let header = |name: &str| req.header(name).and_then(|value| value.as_str());
+    let origin = header("origin");
+    let server = create_mcp_server();
+
     // Handle CORS preflight
     if *req.method() == spin_sdk::http::Method::Options {
-        let mut response = Response::new(200, ());
-        response.set_header("access-control-allow-origin", "*");
-        response.set_header("access-control-allow-methods", "POST, OPTIONS");
-        response.set_header("access-control-allow-headers", "Content-Type");
+        let reply = server.preflight(origin);
+        let mut response = Response::new(reply.status, ());
+        set_headers(&mut response, &reply.headers);
         return Ok(response);
     }
 
@@ -34,114 +40,60 @@ fn handle_request(req: Request) -> anyhow::Result<impl IntoResponse> {
         });
         let mut response = Response::new(200, serde_json::to_string_pretty(&info)?);
         response.set_header("content-type", "application/json");
-        response.set_header("access-control-allow-origin", "*");
+        set_headers(&mut response, &server.cors_headers(origin));
+        return Ok(response);
+    }
+
+    let store = session_store::SpinSessionStore::open_default()?;
+
+    // Clients end their session with DELETE
+    if *req.method() == spin_sdk::http::Method::Delete {
+        let status = match header(MCP_SESSION_ID) {
+            Some(session_id) => {
+                futures::executor::block_on(server.end_session(&store, session_id))?;
+                204
+            },
+            None => 400,
+        };
+        let mut response = Response::new(status, ());
+        set_headers(&mut response, &server.cors_headers(origin));
         return Ok(response);
     }
 
     // Only handle POST requests for MCP protocol
     if *req.method() != spin_sdk::http::Method::Post {
-        let mut response = Response::new(405, "Only GET and POST methods are supported");
+        let mut response = Response::new(405, "Only GET, POST, and DELETE methods are supported");
         response.set_header("content-type", "text/plain");
         return Ok(response);
     }
 
-    // Get request body
-    let body_bytes = req.body();
-    let body = std::str::from_utf8(&body_bytes)?;
-
-    // Parse the JSON-RPC request
-    let request_value: Value = serde_json::from_str(body)?;
-
-    // Check if this is a notification (no id field means it's a notification)
-    let maybe_id = request_value.get("id");
-
-    let method = request_value.get("method")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-
-    let params = request_value.get("params")
-        .cloned()
-        .unwrap_or(Value::Null);
-
-    // Handle notifications - they don't require a response
-    if maybe_id.is_none() {
-        match method {
-            "notifications/initialized" => {
-                // Client is telling us it's initialized - return empty 200 OK
-                let response = Response::new(200, ());
-                return Ok(response);
-            },
-            "notifications/cancelled" => {
-                // Cancellation notification - return empty 200 OK
-                let response = Response::new(200, ());
-                return Ok(response);
-            },
-            _ => {
-                // Unknown notification - still return empty 200 OK
-                let response = Response::new(200, ());
-                return Ok(response);
-            }
-        }
-    }
-
-    // Extract request ID for regular requests
-    let id = maybe_id
-        .and_then(|v| serde_json::from_value(v.clone()).ok())
-        .unwrap_or(pmcp::types::RequestId::String("0".to_string()));
-
-    // Build the environment-agnostic MCP server
-    let server = create_mcp_server();
-
-    // Construct ClientRequest based on method
-    let client_request = match method {
-        "initialize" => {
-            let mut init_params = params.clone();
-            if init_params.is_object() && !init_params.get("capabilities").is_some() {
-                if let Some(obj) = init_params.as_object_mut() {
-                    obj.insert("capabilities".to_string(), json!({}));
-                }
-            }
-            let init_params: pmcp::types::InitializeParams = serde_json::from_value(init_params)?;
-            ClientRequest::Initialize(init_params)
-        },
-        "tools/list" => {
-            ClientRequest::ListTools(pmcp::types::ListToolsParams { cursor: None })
-        },
-        "tools/call" => {
-            let call_params: pmcp::types::CallToolParams = serde_json::from_value(params)?;
-            ClientRequest::CallTool(call_params)
-        },
-        _ => {
-            // Return proper JSON-RPC error for unknown methods
-            let error_response = json!({
-                "jsonrpc": "2.0",
-                "id": id,
-                "error": {
-                    "code": -32601,
-                    "message": format!("Method not found: {}", method)
-                }
-            });
-            let mut response = Response::new(200, serde_json::to_string(&error_response)?);
-            response.set_header("content-type", "application/json");
-            response.set_header("access-control-allow-origin", "*");
-            return Ok(response);
-        }
+    // Parse, dispatch, and serialize the JSON-RPC body within the client's session
+    let headers = SessionHeaders {
+        session_id: header(MCP_SESSION_ID),
+        protocol_version: header(MCP_PROTOCOL_VERSION),
+        origin,
     };
+    let body = std::str::from_utf8(req.body())?;
+    let reply = futures::executor::block_on(server.handle_http_session(&store, headers, body));
 
-    // Handle the request with the environment-agnostic server
-    let response = futures::executor::block_on(
-        server.handle_request(id, McpRequest::Client(Box::new(client_request)))
-    );
-
-    // Return the JSON-RPC response
-    let response_json = serde_json::to_string(&response)?;
-    let mut http_response = Response::new(200, response_json);
-    http_response.set_header("content-type", "application/json");
-    http_response.set_header("access-control-allow-origin", "*");
+    let mut http_response = Response::new(reply.status, reply.body);
+    if reply.status != 202 {
+        http_response.set_header("content-type", "application/json");
+    }
+    if let Some(session_id) = &reply.session_id {
+        http_response.set_header(MCP_SESSION_ID, session_id.as_str());
+    }
+    set_headers(&mut http_response, &reply.headers);
 
     Ok(http_response)
 }
 
+fn set_headers(response: &mut Response, headers: &[(&'static str, String)]) {
+    for (name, value) in headers {
+        response.set_header(*name, value.as_str());
+    }
+}
+
 /// Create the shared MCP server with tools
 /// This function contains the "write once" MCP logic that's shared across platforms
 fn create_mcp_server() -> WasmMcpServer {
@@ -282,4 +234,4 @@ fn create_mcp_server() -> WasmMcpServer {
             }))
         )
         .build()
-}
\ No newline at end of file
+}
@@ -0,0 +1,67 @@
+//! MCP session storage backed by Spin's key-value store.
+
+use async_trait::async_trait;
+use pmcp::server::wasm_server::SessionStore;
+use serde::{Deserialize, Serialize};
+use spin_sdk::key_value::Store;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Spin key-value stores have no expiry, so each entry carries its own.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    value: String,
+    expires_at: u64,
+}
+
+/// Keeps sessions in the component's default key-value store, so every
+/// instance of the component sees them.
+pub struct SpinSessionStore(Store);
+
+impl SpinSessionStore {
+    /// Open the default store; the component must list it in
+    /// `key_value_stores` in spin.toml.
+    pub fn open_default() -> anyhow::Result<Self> {
+        Ok(Self(Store::open_default()?))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn kv_error(e: impl std::fmt::Display) -> pmcp::Error {
+    pmcp::Error::internal(format!("Key-value store error: {}", e))
+}
+
+#[async_trait(?Send)]
+impl SessionStore for SpinSessionStore {
+    async fn get(&self, key: &str) -> pmcp::Result<Option<String>> {
+        let Some(bytes) = self.0.get(key).map_err(kv_error)? else {
+            return Ok(None);
+        };
+        let Ok(entry) = serde_json::from_slice::<Entry>(&bytes) else {
+            return Ok(None);
+        };
+        if entry.expires_at <= now() {
+            self.0.delete(key).map_err(kv_error)?;
+            return Ok(None);
+        }
+        Ok(Some(entry.value))
+    }
+
+    async fn put(&self, key: &str, value: String, ttl: Duration) -> pmcp::Result<()> {
+        let entry = Entry {
+            value,
+            expires_at: now().saturating_add(ttl.as_secs()),
+        };
+        let bytes = serde_json::to_vec(&entry).map_err(kv_error)?;
+        self.0.set(key, &bytes).map_err(kv_error)
+    }
+
+    async fn delete(&self, key: &str) -> pmcp::Result<()> {
+        self.0.delete(key).map_err(kv_error)
+    }
+}
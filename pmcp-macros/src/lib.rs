@@ -14,42 +14,22 @@
 //!
 //! ## Tool Definition
 //!
-//! ```rust,ignore
-//! use pmcp_macros::{tool, tool_router};
-//! use serde::{Deserialize, Serialize};
-//! use schemars::JsonSchema;
+//! With the `macros` feature, `pmcp` re-exports `#[tool]`:
 //!
-//! #[derive(Debug, Deserialize, JsonSchema)]
-//! struct CalculateParams {
-//!     a: i32,
-//!     b: i32,
-//!     operation: String,
-//! }
+//! ```rust,ignore
+//! use pmcp::{tool, Server};
 //!
-//! #[derive(Debug, Serialize, JsonSchema)]
-//! struct CalculateResult {
-//!     result: i32,
+//! /// Add two numbers.
+//! #[tool]
+//! async fn add(a: i64, b: i64) -> i64 {
+//!     a + b
 //! }
 //!
-//! #[tool_router]
-//! impl Calculator {
-//!     #[tool(description = "Perform arithmetic operations")]
-//!     async fn calculate(&self, params: CalculateParams) -> Result<CalculateResult, String> {
-//!         let result = match params.operation.as_str() {
-//!             "add" => params.a + params.b,
-//!             "subtract" => params.a - params.b,
-//!             "multiply" => params.a * params.b,
-//!             "divide" => {
-//!                 if params.b == 0 {
-//!                     return Err("Division by zero".to_string());
-//!                 }
-//!                 params.a / params.b
-//!             }
-//!             _ => return Err("Unknown operation".to_string()),
-//!         };
-//!         Ok(CalculateResult { result })
-//!     }
-//! }
+//! let server = Server::builder()
+//!     .name("calculator")
+//!     .version("1.0.0")
+//!     .tool(AddToolHandler::NAME, AddToolHandler)
+//!     .build()?;
 //! ```
 
 use proc_macro::TokenStream;
@@ -59,33 +39,47 @@ mod tool;
 mod tool_router;
 mod utils;
 
-/// Defines a tool handler with automatic schema generation.
+/// Defines a tool handler from a function.
+///
+/// For a function `fn_name`, generates a unit struct `FnNameToolHandler`
+/// implementing `pmcp::ToolHandler`, with a `NAME` constant to register it
+/// under. Each parameter is a tool argument of the same name; the input
+/// schema is generated from the parameter types, which must implement
+/// `Deserialize` and `JsonSchema`. `Option` parameters are optional. A
+/// parameter of type `RequestHandlerExtra` receives the request context
+/// instead.
+///
+/// The return value must implement `Serialize`. Returning
+/// `Err` from a `Result` fails the call, keeping a `pmcp::Error` as is and
+/// turning other errors into internal errors.
+///
+/// Functions with a `self` receiver are left to `#[tool_router]`.
 ///
 /// # Attributes
 ///
 /// - `name` - Optional tool name (defaults to function name)
-/// - `description` - Tool description (required)
-/// - `annotations` - Additional metadata for the tool
+/// - `description` - Tool description (defaults to the doc comment; one of
+///   the two is required)
 ///
 /// # Examples
 ///
 /// ```rust,ignore
-/// #[tool(description = "Add two numbers")]
+/// /// Add two numbers.
+/// #[tool]
 /// async fn add(a: i32, b: i32) -> Result<i32, String> {
-///     Ok(a + b)
+///     a.checked_add(b).ok_or_else(|| "overflow".to_string())
 /// }
 /// ```
 ///
-/// With custom name and annotations:
+/// With a custom name and the request context:
 ///
 /// ```rust,ignore
-/// #[tool(
-///     name = "math_add",
-///     description = "Add two numbers",
-///     annotations(category = "math", complexity = "simple")
-/// )]
-/// async fn add(a: i32, b: i32) -> Result<i32, String> {
-///     Ok(a + b)
+/// #[tool(name = "math_add", description = "Add two numbers")]
+/// async fn add(a: i32, b: Option<i32>, extra: RequestHandlerExtra) -> pmcp::Result<i32> {
+///     if extra.is_cancelled() {
+///         return Err(pmcp::Error::cancelled());
+///     }
+///     Ok(a + b.unwrap_or(0))
 /// }
 /// ```
 #[proc_macro_attribute]
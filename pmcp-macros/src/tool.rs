@@ -3,266 +3,230 @@
 //! This module implements the `#[tool]` attribute macro for defining MCP tools
 //! with automatic schema generation and handler implementation.
 
+use crate::utils::{extract_doc_comment, extract_result_types, is_pmcp_error, to_pascal_case};
+use darling::ast::NestedMeta;
 use darling::FromMeta;
-use proc_macro2::{Ident, TokenStream};
-use quote::quote;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
 use syn::parse::Parser;
-use syn::{parse_quote, FnArg, ItemFn, Pat, PatType, ReturnType, Type, TypePath};
+use syn::{FnArg, ItemFn, Pat, PatType, ReturnType, Type, TypePath};
 
 /// Tool macro arguments
-#[derive(Debug, FromMeta)]
+#[derive(Debug, Default, FromMeta)]
 struct ToolArgs {
     /// Tool name (defaults to function name)
     #[darling(default)]
     name: Option<String>,
 
-    /// Tool description
-    description: String,
-
-    /// Additional annotations
+    /// Tool description (defaults to the doc comment)
     #[darling(default)]
-    annotations: Option<ToolAnnotations>,
+    description: Option<String>,
 }
 
-/// Tool annotations for metadata
-#[derive(Debug, Default, FromMeta)]
-struct ToolAnnotations {
-    #[darling(default)]
-    category: Option<String>,
-
-    #[darling(default)]
-    complexity: Option<String>,
-
-    #[darling(default)]
-    read_only: Option<bool>,
-}
-
-/// Expands the #[tool] attribute macro  
+/// Expands the #[tool] attribute macro
 pub fn expand_tool(args: TokenStream, input: ItemFn) -> syn::Result<TokenStream> {
-    // Parse macro arguments from TokenStream
+    // Methods are collected by #[tool_router]
+    if matches!(input.sig.inputs.first(), Some(FnArg::Receiver(_))) {
+        return Ok(quote!(#input));
+    }
+
     let nested_metas = if args.is_empty() {
         vec![]
     } else {
-        // Parse as key-value pairs
-        let parser = syn::punctuated::Punctuated::<darling::ast::NestedMeta, syn::Token![,]>::parse_terminated;
+        let parser = syn::punctuated::Punctuated::<NestedMeta, syn::Token![,]>::parse_terminated;
         parser
             .parse2(args)
-            .map(|p| p.into_iter().collect::<Vec<_>>())
-            .unwrap_or_default()
+            .map(|p| p.into_iter().collect::<Vec<_>>())?
     };
-
-    let args = ToolArgs::from_list(&nested_metas)
-        .map_err(|e| syn::Error::new_spanned(&input.sig.ident, e.to_string()))?;
+    let args =
+        ToolArgs::from_list(&nested_metas).map_err(|e| syn::Error::new(Span::call_site(), e))?;
+
+    if !input.sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.sig.generics,
+            "#[tool] functions cannot be generic",
+        ));
+    }
 
     let fn_name = &input.sig.ident;
+    let vis = &input.vis;
     let tool_name = args.name.unwrap_or_else(|| fn_name.to_string());
-    let description = args.description;
-
-    // Extract function parameters
-    let params = extract_parameters(&input)?;
+    let description = args
+        .description
+        .or_else(|| extract_doc_comment(&input.attrs))
+        .ok_or_else(|| syn::Error::new(Span::call_site(), "Tool must have a description"))?;
 
-    // Extract return type
-    let return_type = extract_return_type(&input)?;
+    let pascal_name = to_pascal_case(&fn_name.to_string());
+    let handler_name = format_ident!("{}ToolHandler", pascal_name);
+    let args_name = format_ident!("__{}ToolArgs", pascal_name);
 
-    // Generate the wrapper struct and implementation
-    let wrapper_name = Ident::new(
-        &format!("{}ToolHandler", to_pascal_case(&fn_name.to_string())),
-        fn_name.span(),
-    );
+    let params = extract_parameters(&input)?;
+    let fields = params.iter().filter_map(|param| match param {
+        Param::Argument { name, ty } => Some(quote!(#name: #ty)),
+        Param::Extra => None,
+    });
+    let call_args = params.iter().map(|param| match param {
+        Param::Argument { name, .. } => quote!(args.#name),
+        Param::Extra => quote!(extra),
+    });
+
+    let await_token = if input.sig.asyncness.is_some() {
+        quote!(.await)
+    } else {
+        quote!()
+    };
+    let result_conversion = generate_result_conversion(&input.sig.output);
 
-    // Check if function is async
-    let is_async = input.sig.asyncness.is_some();
-    let await_token = if is_async { quote!(.await) } else { quote!() };
+    let handler_doc = format!("Handler for the `{}` tool.", tool_name);
 
-    // Generate parameter extraction code
-    let param_extraction = generate_param_extraction(&params)?;
-    let param_names: Vec<_> = params.iter().map(|p| &p.name).collect();
+    Ok(quote! {
+        #input
 
-    // Generate result conversion
-    let result_conversion = generate_result_conversion(&return_type)?;
+        #[doc(hidden)]
+        #[derive(::pmcp::__private::serde::Deserialize, ::pmcp::__private::schemars::JsonSchema)]
+        #[serde(crate = "::pmcp::__private::serde")]
+        #[schemars(crate = "::pmcp::__private::schemars")]
+        struct #args_name {
+            #(#fields,)*
+        }
 
-    // Build the handler implementation
-    let expanded = quote! {
-        #input
+        #[doc = #handler_doc]
+        #[derive(Debug, Clone, Copy, Default)]
+        #vis struct #handler_name;
 
-        /// Auto-generated tool handler for #tool_name
-        #[derive(Debug, Clone)]
-        pub struct #wrapper_name;
+        impl #handler_name {
+            /// Name the tool is advertised under.
+            pub const NAME: &'static str = #tool_name;
+        }
 
-        #[async_trait::async_trait]
-        impl pmcp::ToolHandler for #wrapper_name {
+        #[::pmcp::async_trait]
+        impl ::pmcp::ToolHandler for #handler_name {
             async fn handle(
                 &self,
-                args: serde_json::Value,
-                _extra: pmcp::RequestHandlerExtra,
-            ) -> pmcp::Result<serde_json::Value> {
-                // Extract parameters from JSON
-                #param_extraction
-
-                // Call the original function
-                let result = #fn_name(#(#param_names),*)#await_token;
-
-                // Convert result to JSON
+                args: ::pmcp::__private::serde_json::Value,
+                extra: ::pmcp::RequestHandlerExtra,
+            ) -> ::pmcp::Result<::pmcp::__private::serde_json::Value> {
+                let _ = &extra;
+                let args = if args.is_null() {
+                    ::pmcp::__private::serde_json::Value::Object(Default::default())
+                } else {
+                    args
+                };
+                let args: #args_name = ::pmcp::__private::serde_json::from_value(args).map_err(|e| {
+                    ::pmcp::Error::invalid_params(format!(
+                        "Invalid arguments for tool '{}': {}",
+                        #tool_name, e
+                    ))
+                })?;
+
+                let result = #fn_name(#(#call_args),*)#await_token;
                 #result_conversion
             }
-        }
 
-        impl #wrapper_name {
-            /// Get tool definition
-            pub fn definition() -> pmcp::types::Tool {
-                pmcp::types::Tool {
+            fn metadata(&self) -> Option<::pmcp::types::ToolInfo> {
+                Some(::pmcp::types::ToolInfo {
                     name: #tool_name.to_string(),
                     description: Some(#description.to_string()),
-                    input_schema: Some(Self::input_schema()),
-                }
-            }
-
-            /// Generate input schema
-            fn input_schema() -> serde_json::Value {
-                // Schema generation requires schemars feature
-                // Returns basic schema for now
-                serde_json::json!({
-                    "type": "object",
-                    "properties": {},
-                    "required": []
+                    input_schema: ::pmcp::server::schema_utils::generate_schema::<#args_name>(),
+                    output_schema: None,
+                    annotations: None,
                 })
             }
         }
-    };
-
-    Ok(expanded)
+    })
 }
 
-/// Parameter information
-struct ParamInfo {
-    name: Ident,
-    ty: Type,
-    optional: bool,
+/// A parameter of a tool function
+enum Param {
+    /// Deserialized from the tool arguments by name
+    Argument { name: Ident, ty: Box<Type> },
+    /// The request's `RequestHandlerExtra`
+    Extra,
 }
 
 /// Extract parameters from function signature
-fn extract_parameters(func: &ItemFn) -> syn::Result<Vec<ParamInfo>> {
+fn extract_parameters(func: &ItemFn) -> syn::Result<Vec<Param>> {
     let mut params = Vec::new();
 
     for arg in &func.sig.inputs {
-        match arg {
-            FnArg::Receiver(_) => {
-                // Skip self parameter
-                continue;
-            },
-            FnArg::Typed(PatType { pat, ty, .. }) => {
-                if let Pat::Ident(pat_ident) = pat.as_ref() {
-                    let name = pat_ident.ident.clone();
-                    let ty = ty.as_ref().clone();
-                    let optional = is_option_type(&ty);
-
-                    params.push(ParamInfo { name, ty, optional });
-                }
-            },
+        let FnArg::Typed(PatType { pat, ty, .. }) = arg else {
+            continue;
+        };
+        if is_request_handler_extra(ty) {
+            params.push(Param::Extra);
+            continue;
         }
+        let Pat::Ident(pat_ident) = pat.as_ref() else {
+            return Err(syn::Error::new_spanned(
+                pat,
+                "#[tool] parameters must be plain identifiers",
+            ));
+        };
+        params.push(Param::Argument {
+            name: pat_ident.ident.clone(),
+            ty: ty.clone(),
+        });
     }
 
     Ok(params)
 }
 
-/// Extract return type information
-fn extract_return_type(func: &ItemFn) -> syn::Result<Type> {
-    match &func.sig.output {
-        ReturnType::Default => Ok(parse_quote!(())),
-        ReturnType::Type(_, ty) => Ok(ty.as_ref().clone()),
-    }
-}
-
-/// Generate parameter extraction code from JSON
-fn generate_param_extraction(params: &[ParamInfo]) -> syn::Result<TokenStream> {
-    let mut extractions = Vec::new();
-
-    for param in params {
-        let name = &param.name;
-        let name_str = name.to_string();
-        let ty = &param.ty;
-
-        if param.optional {
-            extractions.push(quote! {
-                let #name: #ty = args.get(#name_str)
-                    .and_then(|v| serde_json::from_value(v.clone()).ok())
-                    .unwrap_or_default();
-            });
-        } else {
-            extractions.push(quote! {
-                let #name: #ty = args.get(#name_str)
-                    .and_then(|v| serde_json::from_value(v.clone()).ok())
-                    .ok_or_else(|| pmcp::Error::InvalidParams(
-                        format!("Missing required parameter: {}", #name_str)
-                    ))?;
-            });
-        }
-    }
+/// Generate code turning the function's return value into the tool result
+fn generate_result_conversion(output: &ReturnType) -> TokenStream {
+    let to_value = quote! {
+        ::pmcp::__private::serde_json::to_value(value).map_err(|e| {
+            ::pmcp::Error::internal(format!("Failed to serialize tool result: {}", e))
+        })
+    };
 
-    Ok(quote! {
-        #(#extractions)*
-    })
-}
+    let ReturnType::Type(_, ty) = output else {
+        return quote! {
+            let value = result;
+            #to_value
+        };
+    };
 
-/// Generate result conversion code
-fn generate_result_conversion(return_type: &Type) -> syn::Result<TokenStream> {
-    // Check if return type is Result<T, E>
-    if is_result_type(return_type) {
-        Ok(quote! {
-            match result {
-                Ok(value) => {
-                    let json_value = serde_json::to_value(value)
-                        .map_err(|e| pmcp::Error::InternalError(e.to_string()))?;
-                    Ok(json_value)
-                }
-                Err(e) => Err(pmcp::Error::ToolError(e.to_string()))
-            }
-        })
-    } else {
-        Ok(quote! {
-            let json_value = serde_json::to_value(result)
-                .map_err(|e| pmcp::Error::InternalError(e.to_string()))?;
-            Ok(json_value)
-        })
+    match extract_result_types(ty) {
+        Some((_, err_ty)) if !is_pmcp_error(err_ty) => quote! {
+            let value = result.map_err(|e| ::pmcp::Error::internal(e.to_string()))?;
+            #to_value
+        },
+        // `pmcp::Result<T>` or `Result<T, pmcp::Error>`
+        _ if is_result_type(ty) => quote! {
+            let value = result?;
+            #to_value
+        },
+        _ => quote! {
+            let value = result;
+            #to_value
+        },
     }
 }
 
-/// Check if a type is Option<T>
-fn is_option_type(ty: &Type) -> bool {
+/// Check if a type is Result<..>
+fn is_result_type(ty: &Type) -> bool {
     if let Type::Path(TypePath { path, .. }) = ty {
         if let Some(segment) = path.segments.last() {
-            return segment.ident == "Option";
+            return segment.ident == "Result";
         }
     }
     false
 }
 
-/// Check if a type is Result<T, E>
-fn is_result_type(ty: &Type) -> bool {
+/// Check if a type is `RequestHandlerExtra`
+fn is_request_handler_extra(ty: &Type) -> bool {
     if let Type::Path(TypePath { path, .. }) = ty {
         if let Some(segment) = path.segments.last() {
-            return segment.ident == "Result";
+            return segment.ident == "RequestHandlerExtra";
         }
     }
     false
 }
 
-/// Convert snake_case to PascalCase
-fn to_pascal_case(s: &str) -> String {
-    s.split('_')
-        .map(|word| {
-            let mut chars = word.chars();
-            match chars.next() {
-                None => String::new(),
-                Some(first) => first.to_uppercase().chain(chars).collect(),
-            }
-        })
-        .collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use syn::parse_quote;
 
     #[test]
     fn test_to_pascal_case() {
@@ -271,15 +235,6 @@ mod tests {
         assert_eq!(to_pascal_case("simple"), "Simple");
     }
 
-    #[test]
-    fn test_is_option_type() {
-        let opt_type: Type = parse_quote!(Option<String>);
-        assert!(is_option_type(&opt_type));
-
-        let non_opt_type: Type = parse_quote!(String);
-        assert!(!is_option_type(&non_opt_type));
-    }
-
     #[test]
     fn test_is_result_type() {
         let result_type: Type = parse_quote!(Result<String, Error>);
@@ -288,4 +243,29 @@ mod tests {
         let non_result_type: Type = parse_quote!(String);
         assert!(!is_result_type(&non_result_type));
     }
+
+    #[test]
+    fn test_is_request_handler_extra() {
+        assert!(is_request_handler_extra(&parse_quote!(RequestHandlerExtra)));
+        assert!(is_request_handler_extra(&parse_quote!(
+            pmcp::RequestHandlerExtra
+        )));
+        assert!(!is_request_handler_extra(&parse_quote!(String)));
+    }
+
+    #[test]
+    fn test_description_falls_back_to_doc_comment() {
+        let input: ItemFn = parse_quote! {
+            /// Add two numbers.
+            async fn add(a: i32, b: i32) -> i32 { a + b }
+        };
+        let expanded = expand_tool(TokenStream::new(), input).unwrap().to_string();
+        assert!(expanded.contains("AddToolHandler"));
+        assert!(expanded.contains("\"Add two numbers.\""));
+
+        let input: ItemFn = parse_quote! {
+            async fn add(a: i32, b: i32) -> i32 { a + b }
+        };
+        assert!(expand_tool(TokenStream::new(), input).is_err());
+    }
 }
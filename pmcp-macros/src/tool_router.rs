@@ -261,11 +261,6 @@ fn parse_visibility(vis_str: &Option<String>) -> syn::Result<Visibility> {
     }
 }
 
-/// Trait to mark types that have a tool router
-trait ToolRouterInfo {
-    const ROUTER_FIELD: &'static str;
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,9 +3,14 @@
 //! These tests verify that the #[tool] macro correctly generates
 //! tool handlers with proper schema generation and type safety.
 
+// Several tests only check that the generated code compiles.
+#![allow(dead_code)]
+
+use pmcp::{RequestHandlerExtra, ToolHandler};
 use pmcp_macros::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 #[derive(Debug, Deserialize, JsonSchema)]
 struct AddParams {
@@ -54,6 +59,109 @@ fn test_tool_with_result_type() {
     }
 }
 
+fn extra() -> RequestHandlerExtra {
+    RequestHandlerExtra::new("test".to_string(), Default::default())
+}
+
+#[tokio::test]
+async fn test_generated_handler_calls_function() {
+    /// Divide two numbers.
+    ///
+    /// Fails when dividing by zero.
+    #[tool]
+    async fn divide(a: f64, b: f64, extra: RequestHandlerExtra) -> Result<f64, String> {
+        assert!(!extra.is_cancelled());
+        if b == 0.0 {
+            Err("Division by zero".to_string())
+        } else {
+            Ok(a / b)
+        }
+    }
+
+    let handler = DivideToolHandler;
+    assert_eq!(DivideToolHandler::NAME, "divide");
+    let result = handler
+        .handle(json!({"a": 6.0, "b": 3.0}), extra())
+        .await
+        .unwrap();
+    assert_eq!(result, json!(2.0));
+
+    let err = handler
+        .handle(json!({"a": 1.0, "b": 0.0}), extra())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Division by zero"));
+
+    let err = handler
+        .handle(json!({"a": 1.0}), extra())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("missing field `b`"));
+
+    let info = handler.metadata().unwrap();
+    assert_eq!(info.name, "divide");
+    assert_eq!(
+        info.description.as_deref(),
+        Some("Divide two numbers.\n\nFails when dividing by zero.")
+    );
+    assert_eq!(info.input_schema["type"], "object");
+    assert_eq!(info.input_schema["properties"]["a"]["type"], "number");
+    assert_eq!(info.input_schema["required"], json!(["a", "b"]));
+    assert!(info.input_schema["properties"].get("extra").is_none());
+}
+
+#[tokio::test]
+async fn test_generated_handler_serves_tool_calls() {
+    #[derive(Debug, Deserialize, JsonSchema)]
+    enum Unit {
+        Celsius,
+        Fahrenheit,
+    }
+
+    /// Convert a temperature to Celsius.
+    #[tool(name = "to_celsius")]
+    fn convert(value: f64, unit: Option<Unit>) -> pmcp::Result<f64> {
+        match unit {
+            Some(Unit::Fahrenheit) => Ok((value - 32.0) * 5.0 / 9.0),
+            Some(Unit::Celsius) | None => Ok(value),
+        }
+    }
+
+    let (client_transport, server_transport) = pmcp::InMemoryTransport::pair();
+    let server = pmcp::Server::builder()
+        .name("macro-test")
+        .version("1.0.0")
+        .tool(ConvertToolHandler::NAME, ConvertToolHandler)
+        .build()
+        .unwrap();
+    let server = tokio::spawn(server.run(server_transport));
+
+    let mut client = pmcp::Client::new(client_transport);
+    client
+        .initialize(pmcp::ClientCapabilities::default())
+        .await
+        .unwrap();
+    let tools = client.list_tools(None).await.unwrap().tools;
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "to_celsius");
+    assert_eq!(tools[0].input_schema["required"], json!(["value"]));
+
+    let result = client
+        .call_tool(
+            "to_celsius".to_string(),
+            json!({"value": 212.0, "unit": "Fahrenheit"}),
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    let pmcp::types::Content::Text { text } = &result.content[0] else {
+        panic!("Expected text content");
+    };
+    assert_eq!(text, "100.0");
+
+    server.abort();
+}
+
 #[test]
 fn test_tool_with_optional_params() {
     #[derive(Debug, Deserialize, JsonSchema)]
@@ -122,8 +230,11 @@ mod property_tests {
                 x as i64 + y as i64
             }
 
-            // Test would verify the generated handler works with arbitrary inputs
-            prop_assert!(true); // Placeholder
+            let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+            let result = runtime
+                .block_on(AddPropToolHandler.handle(json!({"x": a, "y": b}), extra()))
+                .unwrap();
+            prop_assert_eq!(result, json!(i64::from(a) + i64::from(b)));
         }
     }
 }
@@ -200,12 +200,11 @@ impl OidcDiscoveryClient {
     fn should_retry(&self, error: &Error) -> bool {
         // Use self to check against max_retries (even though we don't strictly need it)
         let _ = self.max_retries;
-        // Check if it's a timeout error
-        if matches!(error, Error::Timeout(_)) {
+        if error.is_retryable() {
             return true;
         }
 
-        // Retry on network errors or specific error patterns
+        // Fall back to message patterns for opaque errors (e.g. from browser fetch)
         let error_str = error.to_string();
         error_str.contains("CORS")
             || error_str.contains("network")
@@ -0,0 +1,246 @@
+//! Automatic reconnection.
+//!
+//! A client configured with [`Client::with_reconnect`] restores a lost
+//! connection by itself. When a request finds the transport disconnected,
+//! or fails because the connection dropped, the client opens a new
+//! transport with the configured connector, retrying with the backoff of a
+//! [`ReconnectManager`], and restores the session the way
+//! [`Client::reconnect`] does: `initialize` is replayed, the server must
+//! still offer the capabilities it advertised before, resource
+//! subscriptions are renewed, and the offline queue is flushed.
+//!
+//! A request that had not been sent yet is then sent on the new
+//! connection. One that was awaiting its response when the connection
+//! dropped is resent or failed with [`Error::RequestInterrupted`],
+//! according to [`PendingRequests`]. Clones of a client share its
+//! connection, so requests that fail together reconnect only once.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::client::auto_reconnect::{AutoReconnect, PendingRequests};
+//! use pmcp::shared::ReconnectConfig;
+//! use pmcp::{Client, ClientCapabilities};
+//! use std::time::Duration;
+//!
+//! # #[cfg(feature = "tcp")]
+//! # async fn example() -> pmcp::Result<()> {
+//! use pmcp::shared::TcpTransport;
+//!
+//! let connect = || TcpTransport::connect("127.0.0.1:7000");
+//! let mut client = Client::new(connect().await?).with_reconnect(
+//!     AutoReconnect::new(connect)
+//!         .with_backoff(ReconnectConfig {
+//!             max_retries: Some(10),
+//!             max_delay: Duration::from_secs(5),
+//!             ..Default::default()
+//!         })
+//!         .with_pending_requests(PendingRequests::Reissue),
+//! );
+//! client.initialize(ClientCapabilities::default()).await?;
+//!
+//! // Survives the server restarting between calls
+//! let tools = client.list_tools(None).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Client::with_reconnect`]: crate::Client::with_reconnect
+//! [`Client::reconnect`]: crate::Client::reconnect
+//! [`Error::RequestInterrupted`]: crate::Error::RequestInterrupted
+
+use crate::error::{Error, Result};
+use crate::shared::reconnect::{ReconnectConfig, ReconnectEvent, ReconnectManager};
+use crate::shared::Transport;
+use crate::types::{ClientRequest, Request};
+use futures::future::BoxFuture;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::{broadcast, Mutex};
+
+/// Opens a new transport to the server.
+type Connector<T> = Arc<dyn Fn() -> BoxFuture<'static, Result<T>> + Send + Sync>;
+
+/// What happens to requests that were awaiting a response when the
+/// connection dropped.
+///
+/// The server may or may not have processed such a request, so resending it
+/// can repeat its effects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PendingRequests {
+    /// Resend requests that are safe to repeat, and fail the rest.
+    ///
+    /// Tool calls may have side effects, so they are resent only if they
+    /// carry an idempotency key (see
+    /// [`Client::call_tool_idempotent`](crate::Client::call_tool_idempotent)).
+    /// All other requests are resent.
+    #[default]
+    ReissueIdempotent,
+
+    /// Resend every request.
+    Reissue,
+
+    /// Fail every request.
+    Fail,
+}
+
+impl PendingRequests {
+    /// Whether `request` is resent on the restored connection.
+    pub fn reissues(self, request: &Request) -> bool {
+        match (self, request) {
+            (Self::Fail, _) => false,
+            (Self::Reissue, _) => true,
+            (Self::ReissueIdempotent, Request::Client(request)) => match &**request {
+                ClientRequest::CallTool(call) => call.idempotency_key().is_some(),
+                _ => true,
+            },
+            (Self::ReissueIdempotent, _) => false,
+        }
+    }
+}
+
+/// Automatic reconnection settings for [`Client::with_reconnect`].
+///
+/// [`Client::with_reconnect`]: crate::Client::with_reconnect
+pub struct AutoReconnect<T> {
+    connect: Connector<T>,
+    backoff: ReconnectConfig,
+    pending: PendingRequests,
+}
+
+impl<T> std::fmt::Debug for AutoReconnect<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AutoReconnect")
+            .field("connect", &"<Fn>")
+            .field("backoff", &self.backoff)
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
+
+impl<T: Transport> AutoReconnect<T> {
+    /// Reconnect with transports opened by `connect`.
+    ///
+    /// `connect` should return a connected transport; it is retried with the
+    /// default [`ReconnectConfig`] backoff until it succeeds or the backoff
+    /// gives up.
+    pub fn new<F, Fut>(connect: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+    {
+        Self {
+            connect: Arc::new(move || Box::pin(connect())),
+            backoff: ReconnectConfig::default(),
+            pending: PendingRequests::default(),
+        }
+    }
+
+    /// Set the backoff between connection attempts and when to give up.
+    pub fn with_backoff(mut self, config: ReconnectConfig) -> Self {
+        self.backoff = config;
+        self
+    }
+
+    /// Set what happens to requests interrupted by the lost connection.
+    pub fn with_pending_requests(mut self, policy: PendingRequests) -> Self {
+        self.pending = policy;
+        self
+    }
+}
+
+/// Reconnection state shared by a client and its clones.
+pub(crate) struct Reconnector<T> {
+    connect: Connector<T>,
+    pub(crate) pending: PendingRequests,
+    manager: ReconnectManager,
+    /// Held while the connection is restored
+    pub(crate) restoring: Mutex<()>,
+    /// Number of times the connection has been restored
+    generation: AtomicU64,
+}
+
+impl<T> std::fmt::Debug for Reconnector<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reconnector")
+            .field("pending", &self.pending)
+            .field("manager", &self.manager)
+            .field("generation", &self.generation())
+            .finish()
+    }
+}
+
+impl<T> Reconnector<T> {
+    pub(crate) fn new(config: AutoReconnect<T>) -> Self {
+        Self {
+            connect: config.connect,
+            pending: config.pending,
+            manager: ReconnectManager::new(config.backoff),
+            restoring: Mutex::new(()),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of times the connection has been restored.
+    ///
+    /// A request that saw the connection fail compares this with the value
+    /// from before it was sent, to skip reconnecting if another request
+    /// already has.
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Record that the session was restored.
+    pub(crate) fn restored(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Open a new transport, retrying with backoff.
+    pub(crate) async fn connect(&self) -> Result<T> {
+        self.manager.on_disconnected().await;
+
+        let connected = parking_lot::Mutex::new(None);
+        self.manager
+            .reconnect_with(|| {
+                let attempt = (self.connect)();
+                let connected = &connected;
+                async move {
+                    *connected.lock() = Some(attempt.await?);
+                    Ok(())
+                }
+            })
+            .await?;
+        connected
+            .into_inner()
+            .ok_or_else(|| Error::internal("Reconnect succeeded without a transport"))
+    }
+
+    /// Subscribe to the reconnection lifecycle events.
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<ReconnectEvent> {
+        self.manager.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CallToolRequest;
+    use serde_json::json;
+
+    #[test]
+    fn test_pending_request_policies() {
+        let call =
+            |request: CallToolRequest| Request::Client(Box::new(ClientRequest::CallTool(request)));
+        let plain = call(CallToolRequest::new("send", json!({})));
+        let keyed = call(CallToolRequest::new("send", json!({})).with_idempotency_key("k"));
+        let ping = Request::Client(Box::new(ClientRequest::Ping));
+
+        let default = PendingRequests::default();
+        assert!(default.reissues(&ping));
+        assert!(default.reissues(&keyed));
+        assert!(!default.reissues(&plain));
+        assert!(PendingRequests::Reissue.reissues(&plain));
+        assert!(!PendingRequests::Fail.reissues(&ping));
+    }
+}
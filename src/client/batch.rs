@@ -0,0 +1,515 @@
+//! Client-side JSON-RPC batching.
+//!
+//! [`RequestBatch`] collects several requests and sends them together,
+//! saving round trips on high-latency transports. When the negotiated
+//! protocol version supports JSON-RPC batches, the requests go out as a
+//! single batch array; otherwise they are pipelined as individual messages.
+//! Either way, responses are correlated by request id and returned in the
+//! order the requests were added. [`Client::send_batch`] does the same for
+//! arbitrary requests, returning their untyped results.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::client::BatchResult;
+//! use pmcp::{Client, ClientCapabilities, StdioTransport};
+//! use serde_json::json;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let mut client = Client::new(StdioTransport::new());
+//! client.initialize(ClientCapabilities::default()).await?;
+//!
+//! let results = client
+//!     .batch()
+//!     .list_tools(None)
+//!     .call_tool("add", json!({ "a": 1, "b": 2 }))
+//!     .send()
+//!     .await?;
+//!
+//! for result in results {
+//!     match result? {
+//!         BatchResult::ListTools(tools) => println!("{} tools", tools.tools.len()),
+//!         BatchResult::CallTool(result) => println!("{}", result.display()),
+//!         _ => {},
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::Client;
+use crate::error::{Error, Result};
+use crate::shared::Transport;
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::{
+    CallToolRequest, CallToolResult, ClientRequest, GetPromptRequest, GetPromptResult,
+    ListPromptsRequest, ListPromptsResult, ListResourcesRequest, ListResourcesResult,
+    ListToolsRequest, ListToolsResult, ReadResourceRequest, ReadResourceResult, Request,
+    TransportMessage,
+};
+use std::collections::HashMap;
+
+/// Protocol versions that allow JSON-RPC batches.
+///
+/// Batching was introduced in 2025-03-26 and removed again in 2025-06-18.
+const BATCH_PROTOCOL_VERSIONS: &[&str] = &["2025-03-26"];
+
+/// Typed result of one request in a batch.
+#[derive(Debug, Clone)]
+pub enum BatchResult {
+    /// Result of `tools/list`
+    ListTools(ListToolsResult),
+    /// Result of `tools/call`
+    CallTool(CallToolResult),
+    /// Result of `resources/list`
+    ListResources(ListResourcesResult),
+    /// Result of `resources/read`
+    ReadResource(ReadResourceResult),
+    /// Result of `prompts/list`
+    ListPrompts(ListPromptsResult),
+    /// Result of `prompts/get`
+    GetPrompt(GetPromptResult),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BatchKind {
+    ListTools,
+    CallTool,
+    ListResources,
+    ReadResource,
+    ListPrompts,
+    GetPrompt,
+}
+
+impl BatchKind {
+    fn parse(self, value: serde_json::Value) -> Result<BatchResult> {
+        let parse_error = |e: serde_json::Error| Error::parse(e.to_string());
+        Ok(match self {
+            Self::ListTools => {
+                BatchResult::ListTools(serde_json::from_value(value).map_err(parse_error)?)
+            },
+            Self::CallTool => {
+                BatchResult::CallTool(serde_json::from_value(value).map_err(parse_error)?)
+            },
+            Self::ListResources => {
+                BatchResult::ListResources(serde_json::from_value(value).map_err(parse_error)?)
+            },
+            Self::ReadResource => {
+                BatchResult::ReadResource(serde_json::from_value(value).map_err(parse_error)?)
+            },
+            Self::ListPrompts => {
+                BatchResult::ListPrompts(serde_json::from_value(value).map_err(parse_error)?)
+            },
+            Self::GetPrompt => {
+                BatchResult::GetPrompt(serde_json::from_value(value).map_err(parse_error)?)
+            },
+        })
+    }
+}
+
+/// Builder for a batch of client requests.
+///
+/// Created with [`Client::batch`].
+#[derive(Debug)]
+pub struct RequestBatch<'a, T: Transport> {
+    client: &'a Client<T>,
+    requests: Vec<(BatchKind, ClientRequest)>,
+}
+
+impl<'a, T: Transport> RequestBatch<'a, T> {
+    pub(crate) fn new(client: &'a Client<T>) -> Self {
+        Self {
+            client,
+            requests: Vec::new(),
+        }
+    }
+
+    /// Add a `tools/list` request.
+    pub fn list_tools(mut self, cursor: Option<String>) -> Self {
+        self.requests.push((
+            BatchKind::ListTools,
+            ClientRequest::ListTools(ListToolsRequest { cursor }),
+        ));
+        self
+    }
+
+    /// Add a `tools/call` request.
+    pub fn call_tool(mut self, name: impl Into<String>, arguments: serde_json::Value) -> Self {
+        self.requests.push((
+            BatchKind::CallTool,
+            ClientRequest::CallTool(CallToolRequest {
+                name: name.into(),
+                arguments,
+                meta: None,
+            }),
+        ));
+        self
+    }
+
+    /// Add a `resources/list` request.
+    pub fn list_resources(mut self, cursor: Option<String>) -> Self {
+        self.requests.push((
+            BatchKind::ListResources,
+            ClientRequest::ListResources(ListResourcesRequest { cursor }),
+        ));
+        self
+    }
+
+    /// Add a `resources/read` request.
+    pub fn read_resource(mut self, uri: impl Into<String>) -> Self {
+        self.requests.push((
+            BatchKind::ReadResource,
+            ClientRequest::ReadResource(ReadResourceRequest::new(uri)),
+        ));
+        self
+    }
+
+    /// Add a `prompts/list` request.
+    pub fn list_prompts(mut self, cursor: Option<String>) -> Self {
+        self.requests.push((
+            BatchKind::ListPrompts,
+            ClientRequest::ListPrompts(ListPromptsRequest { cursor }),
+        ));
+        self
+    }
+
+    /// Add a `prompts/get` request.
+    pub fn get_prompt(
+        mut self,
+        name: impl Into<String>,
+        arguments: HashMap<String, String>,
+    ) -> Self {
+        self.requests.push((
+            BatchKind::GetPrompt,
+            ClientRequest::GetPrompt(GetPromptRequest {
+                name: name.into(),
+                arguments,
+            }),
+        ));
+        self
+    }
+
+    /// Get the number of requests in the batch.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Check if the batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Send the batch and wait for all responses.
+    ///
+    /// Returns one result per request, in the order the requests were added.
+    /// A request the server rejected yields an `Err` in its slot without
+    /// failing the rest of the batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not initialized, the server lacks a
+    /// capability required by one of the requests, or the batch could not be
+    /// sent.
+    pub async fn send(self) -> Result<Vec<Result<BatchResult>>> {
+        let (kinds, requests): (Vec<_>, Vec<_>) = self.requests.into_iter().unzip();
+        let results = self.client.send_batch(requests).await?;
+        Ok(kinds
+            .into_iter()
+            .zip(results)
+            .map(|(kind, result)| result.and_then(|value| kind.parse(value)))
+            .collect())
+    }
+}
+
+/// Method name of a client request.
+fn method_of(request: &ClientRequest) -> Result<String> {
+    serde_json::to_value(request)
+        .ok()
+        .and_then(|value| value.get("method")?.as_str().map(str::to_string))
+        .ok_or_else(|| Error::internal("Failed to determine the method of a request"))
+}
+
+impl<T: Transport> Client<T> {
+    /// Send arbitrary requests together and return their raw results.
+    ///
+    /// This is the untyped counterpart of [`batch`](Self::batch): the
+    /// requests go out as one JSON-RPC batch when the protocol version
+    /// allows it, and are pipelined otherwise. Results come back in request
+    /// order. A request the server rejected yields an `Err` in its slot, and
+    /// if the transport fails after the batch was sent, the requests still
+    /// unanswered get an `Err` while those already answered keep their
+    /// results.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::types::{CallToolRequest, ClientRequest};
+    /// use pmcp::{Client, ClientCapabilities, StdioTransport};
+    /// use serde_json::json;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let results = client
+    ///     .send_batch(vec![
+    ///         ClientRequest::Ping,
+    ///         ClientRequest::CallTool(CallToolRequest {
+    ///             name: "add".to_string(),
+    ///             arguments: json!({ "a": 1, "b": 2 }),
+    ///             meta: None,
+    ///         }),
+    ///     ])
+    ///     .await?;
+    /// for result in results {
+    ///     match result {
+    ///         Ok(value) => println!("{}", value),
+    ///         Err(e) => eprintln!("request failed: {}", e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not initialized, the server lacks a
+    /// capability required by one of the requests, or the batch could not be
+    /// sent.
+    pub async fn send_batch(
+        &self,
+        requests: Vec<ClientRequest>,
+    ) -> Result<Vec<Result<serde_json::Value>>> {
+        self.ensure_initialized()?;
+        for request in &requests {
+            self.assert_method_supported(&method_of(request)?)?;
+        }
+        if requests.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut slots = HashMap::with_capacity(requests.len());
+        let mut messages = Vec::with_capacity(requests.len());
+        for (index, request) in requests.into_iter().enumerate() {
+            let id = self.next_request_id().await;
+            slots.insert(id.clone(), index);
+            messages.push(TransportMessage::Request {
+                id,
+                request: Request::Client(Box::new(request)),
+            });
+        }
+
+        let batching = self
+            .protocol_version
+            .as_deref()
+            .is_some_and(|version| BATCH_PROTOCOL_VERSIONS.contains(&version));
+
+        let mut results: Vec<Option<Result<serde_json::Value>>> = std::iter::repeat_with(|| None)
+            .take(messages.len())
+            .collect();
+        let mut transport = self.transport.write().await;
+        if batching {
+            transport.send_batch(messages).await?;
+        } else {
+            for message in messages {
+                transport.send(message).await?;
+            }
+        }
+
+        while !slots.is_empty() {
+            let response = match transport.receive().await {
+                Ok(TransportMessage::Response(response)) => response,
+                // Notifications and server requests are not part of the batch.
+                Ok(_) => continue,
+                Err(e) => {
+                    for index in slots.into_values() {
+                        results[index] = Some(Err(Error::protocol_msg(format!(
+                            "No response received before the transport failed: {}",
+                            e
+                        ))));
+                    }
+                    break;
+                },
+            };
+            let index = slots.remove(&response.id).ok_or_else(|| {
+                Error::protocol_msg(format!(
+                    "Received response for unknown request id {}",
+                    response.id
+                ))
+            })?;
+            results[index] = Some(match response.payload {
+                ResponsePayload::Result(value) => Ok(value),
+                ResponsePayload::Error(error) => Err(Error::from_jsonrpc_error(error)),
+            });
+        }
+        drop(transport);
+
+        Ok(results.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ClientCapabilities, Implementation, JSONRPCResponse, ServerCapabilities};
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    /// Transport that answers every request, in reverse order, and records
+    /// whether requests arrived as a batch.
+    #[derive(Debug, Default)]
+    struct EchoTransport {
+        queued: Vec<TransportMessage>,
+        batches: Arc<Mutex<Vec<usize>>>,
+    }
+
+    impl EchoTransport {
+        fn answer(&mut self, message: TransportMessage) {
+            let TransportMessage::Request { id, request } = message else {
+                return;
+            };
+            let payload = match request {
+                Request::Client(request) => match *request {
+                    ClientRequest::ListTools(_) => ResponsePayload::Result(json!({ "tools": [] })),
+                    ClientRequest::CallTool(call) if call.name == "silent" => return,
+                    ClientRequest::CallTool(call) if call.name == "fail" => {
+                        ResponsePayload::Error(crate::types::jsonrpc::JSONRPCError {
+                            code: -32602,
+                            message: "bad arguments".to_string(),
+                            data: None,
+                        })
+                    },
+                    ClientRequest::CallTool(call) => ResponsePayload::Result(json!({
+                        "content": [{ "type": "text", "text": call.name }]
+                    })),
+                    _ => ResponsePayload::Result(json!({})),
+                },
+                Request::Server(_) => return,
+            };
+            self.queued.insert(
+                0,
+                TransportMessage::Response(JSONRPCResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    payload,
+                }),
+            );
+        }
+    }
+
+    #[async_trait]
+    impl Transport for EchoTransport {
+        async fn send(&mut self, message: TransportMessage) -> Result<()> {
+            self.batches.lock().unwrap().push(1);
+            self.answer(message);
+            Ok(())
+        }
+
+        async fn send_batch(&mut self, messages: Vec<TransportMessage>) -> Result<()> {
+            self.batches.lock().unwrap().push(messages.len());
+            for message in messages {
+                self.answer(message);
+            }
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<TransportMessage> {
+            // Answer in reverse order to exercise id correlation.
+            self.queued
+                .pop()
+                .ok_or_else(|| Error::protocol_msg("No more responses"))
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn client(protocol_version: &str) -> (Client<EchoTransport>, Arc<Mutex<Vec<usize>>>) {
+        let transport = EchoTransport::default();
+        let batches = transport.batches.clone();
+        let mut client = Client::with_info(
+            transport,
+            Implementation {
+                name: "test".to_string(),
+                version: "1.0.0".to_string(),
+            },
+        );
+        client.capabilities = Some(ClientCapabilities::default());
+        client.server_capabilities = Some(ServerCapabilities::tools_only());
+        client.protocol_version = Some(protocol_version.to_string());
+        client.initialized = true;
+        (client, batches)
+    }
+
+    #[tokio::test]
+    async fn test_batch_results_are_correlated_in_order() {
+        let (client, batches) = client("2025-03-26");
+        let results = client
+            .batch()
+            .call_tool("first", json!({}))
+            .list_tools(None)
+            .call_tool("fail", json!({}))
+            .call_tool("last", json!({}))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(*batches.lock().unwrap(), vec![4]);
+        assert_eq!(results.len(), 4);
+        match &results[0] {
+            Ok(BatchResult::CallTool(result)) => {
+                assert_eq!(result.display().to_string(), "first")
+            },
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(matches!(results[1], Ok(BatchResult::ListTools(_))));
+        assert!(results[2].is_err());
+        match &results[3] {
+            Ok(BatchResult::CallTool(result)) => assert_eq!(result.display().to_string(), "last"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_falls_back_to_pipelining_and_checks_capabilities() {
+        let (client, batches) = client(crate::types::LATEST_PROTOCOL_VERSION);
+        let results = client
+            .batch()
+            .list_tools(None)
+            .call_tool("a", json!({}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(*batches.lock().unwrap(), vec![1, 1]);
+
+        // Prompts are not advertised by the server.
+        let result = client.batch().list_prompts(None).send().await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_keeps_results_when_transport_fails() {
+        let (client, batches) = client("2025-03-26");
+        let call = |name: &str| {
+            ClientRequest::CallTool(CallToolRequest {
+                name: name.to_string(),
+                arguments: json!({}),
+                meta: None,
+            })
+        };
+        let results = client
+            .send_batch(vec![call("first"), call("silent"), ClientRequest::Ping])
+            .await
+            .unwrap();
+
+        assert_eq!(*batches.lock().unwrap(), vec![3]);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap()["content"][0]["text"], "first");
+        let err = results[1].as_ref().unwrap_err();
+        assert!(err.to_string().contains("No response"), "{}", err);
+        assert!(results[2].is_ok());
+    }
+}
@@ -0,0 +1,468 @@
+//! Loading server definitions from `mcpServers` configuration files.
+//!
+//! Host applications commonly list the servers they use in a JSON file such
+//! as `claude_desktop_config.json`, keyed by server name. A server is either
+//! a command launched as a child process and spoken to over stdio, or a URL
+//! reached over streamable HTTP:
+//!
+//! ```json
+//! {
+//!   "mcpServers": {
+//!     "filesystem": {
+//!       "command": "npx",
+//!       "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+//!       "env": {"DEBUG": "1"}
+//!     },
+//!     "search": {
+//!       "url": "https://search.example.com/mcp",
+//!       "headers": {"Authorization": "Bearer token"}
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! [`McpConfig`] parses this format, from JSON or, with the `yaml` feature,
+//! from YAML of the same shape. The top-level key may also be `servers`, and
+//! entries may carry a `cwd`, a `type` (`stdio`, `http`, `streamable-http` or
+//! `sse`) and a `disabled` flag. Servers of type `sse` are connected with
+//! streamable HTTP, which servers offering the older SSE transport
+//! generally also serve.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::client::config::McpConfig;
+//! use pmcp::ClientCapabilities;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let config = McpConfig::load("claude_desktop_config.json")?;
+//!
+//! let mut client = config.connect("filesystem")?;
+//! client.initialize(ClientCapabilities::default()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::multiplex::McpMultiplexClient;
+use crate::error::{Error, Result};
+use crate::shared::child_process::{ChildProcessCommand, ChildProcessTransport};
+#[cfg(feature = "streamable-http")]
+use crate::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+use crate::shared::transport::{Transport, TransportMessage};
+use crate::Client;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Servers defined by a configuration file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct McpConfig {
+    /// Servers by name
+    #[serde(rename = "mcpServers", alias = "servers", default)]
+    pub servers: BTreeMap<String, ServerConfig>,
+}
+
+impl McpConfig {
+    /// Parse a JSON configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `json` is not a valid configuration.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| Error::validation(format!("Invalid MCP configuration: {}", e)))
+    }
+
+    /// Parse a YAML configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `yaml` is not a valid configuration.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        serde_yaml::from_str(yaml)
+            .map_err(|e| Error::validation(format!("Invalid MCP configuration: {}", e)))
+    }
+
+    /// Read a configuration file.
+    ///
+    /// Files ending in `.yaml` or `.yml` are parsed as YAML, others as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid
+    /// configuration, or if it is YAML and the `yaml` feature is disabled.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::validation(format!("Cannot read {}: {}", path.display(), e)))?;
+        let yaml = path
+            .extension()
+            .is_some_and(|extension| extension == "yaml" || extension == "yml");
+        if !yaml {
+            return Self::from_json(&text);
+        }
+        #[cfg(feature = "yaml")]
+        return Self::from_yaml(&text);
+        #[cfg(not(feature = "yaml"))]
+        Err(Error::validation(format!(
+            "Cannot read {}: YAML configurations need the `yaml` feature",
+            path.display()
+        )))
+    }
+
+    /// The server named `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a not-found error if no server has that name.
+    pub fn server(&self, name: &str) -> Result<&ServerConfig> {
+        self.servers
+            .get(name)
+            .ok_or_else(|| Error::not_found(format!("No server named '{}' configured", name)))
+    }
+
+    /// The servers not marked `disabled`, in name order.
+    pub fn enabled_servers(&self) -> impl Iterator<Item = (&str, &ServerConfig)> {
+        self.servers
+            .iter()
+            .filter(|(_, server)| !server.is_disabled())
+            .map(|(name, server)| (name.as_str(), server))
+    }
+
+    /// A client for the server named `name`, not yet initialized.
+    ///
+    /// A stdio server is spawned right away, so this must be called within
+    /// a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no server has that name or it cannot be
+    /// connected.
+    pub fn connect(&self, name: &str) -> Result<Client<ConfiguredTransport>> {
+        Ok(Client::new(self.server(name)?.transport()?))
+    }
+
+    /// A multiplexing client over every enabled server, each added under its
+    /// configured name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a server cannot be connected.
+    pub fn multiplex_client(&self) -> Result<McpMultiplexClient> {
+        self.enabled_servers()
+            .try_fold(McpMultiplexClient::new(), |multiplex, (name, server)| {
+                Ok(multiplex.add_server(name, Client::new(server.transport()?)))
+            })
+    }
+}
+
+/// How to reach one configured server.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawServerConfig")]
+pub enum ServerConfig {
+    /// A command run as a child process, spoken to over stdio
+    Stdio(StdioServerConfig),
+    /// A URL reached over streamable HTTP
+    Http(HttpServerConfig),
+}
+
+impl ServerConfig {
+    /// Whether the entry is marked `disabled`.
+    pub fn is_disabled(&self) -> bool {
+        match self {
+            Self::Stdio(config) => config.disabled,
+            Self::Http(config) => config.disabled,
+        }
+    }
+
+    /// Connect to the server.
+    ///
+    /// A stdio server is spawned right away, so this must be called within
+    /// a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the child process cannot be spawned, or if the
+    /// server is reached over HTTP and the URL is invalid or the
+    /// `streamable-http` feature is disabled.
+    pub fn transport(&self) -> Result<ConfiguredTransport> {
+        match self {
+            Self::Stdio(config) => Ok(ConfiguredTransport::ChildProcess(Box::new(
+                ChildProcessTransport::spawn(config.command())?,
+            ))),
+            #[cfg(feature = "streamable-http")]
+            Self::Http(config) => Ok(ConfiguredTransport::StreamableHttp(config.transport()?)),
+            #[cfg(not(feature = "streamable-http"))]
+            Self::Http(config) => Err(Error::validation(format!(
+                "Cannot connect to {}: HTTP servers need the `streamable-http` feature",
+                config.url
+            ))),
+        }
+    }
+}
+
+/// A server run as a child process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StdioServerConfig {
+    /// Program to run
+    pub command: String,
+    /// Arguments to the program
+    pub args: Vec<String>,
+    /// Environment variables added to the inherited environment
+    pub env: BTreeMap<String, String>,
+    /// Working directory, if not the current one
+    pub cwd: Option<PathBuf>,
+    /// Whether the entry is disabled
+    pub disabled: bool,
+}
+
+impl StdioServerConfig {
+    /// The command spawning the server.
+    pub fn command(&self) -> ChildProcessCommand {
+        let mut command = ChildProcessCommand::new(&self.command).args(&self.args);
+        for (key, value) in &self.env {
+            command = command.env(key, value);
+        }
+        if let Some(cwd) = &self.cwd {
+            command = command.current_dir(cwd);
+        }
+        command
+    }
+}
+
+/// A server reached over HTTP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpServerConfig {
+    /// Endpoint URL
+    pub url: String,
+    /// Headers sent with every request
+    pub headers: BTreeMap<String, String>,
+    /// Whether the entry is disabled
+    pub disabled: bool,
+}
+
+impl HttpServerConfig {
+    /// A streamable HTTP transport to the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the URL is invalid.
+    #[cfg(feature = "streamable-http")]
+    pub fn transport(&self) -> Result<StreamableHttpTransport> {
+        let url = url::Url::parse(&self.url)
+            .map_err(|e| Error::validation(format!("Invalid server URL {}: {}", self.url, e)))?;
+        Ok(StreamableHttpTransport::new(
+            StreamableHttpTransportConfig {
+                url,
+                extra_headers: self
+                    .headers
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect(),
+                auth_provider: None,
+                session_id: None,
+                enable_json_response: false,
+                on_resumption_token: None,
+            },
+        ))
+    }
+}
+
+/// A server entry as written, before deciding how the server is reached.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawServerConfig {
+    #[serde(rename = "type")]
+    transport: Option<String>,
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    cwd: Option<PathBuf>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default)]
+    disabled: bool,
+}
+
+impl TryFrom<RawServerConfig> for ServerConfig {
+    type Error = String;
+
+    fn try_from(raw: RawServerConfig) -> std::result::Result<Self, String> {
+        let stdio = match raw.transport.as_deref() {
+            None => raw.command.is_some(),
+            Some("stdio") => true,
+            Some("http" | "streamable-http" | "streamableHttp" | "sse") => false,
+            Some(other) => return Err(format!("unknown server type `{}`", other)),
+        };
+        if stdio {
+            let command = raw
+                .command
+                .ok_or("a stdio server needs a `command` or a `url`")?;
+            Ok(Self::Stdio(StdioServerConfig {
+                command,
+                args: raw.args,
+                env: raw.env,
+                cwd: raw.cwd,
+                disabled: raw.disabled,
+            }))
+        } else {
+            let url = raw.url.ok_or("an HTTP server needs a `url`")?;
+            Ok(Self::Http(HttpServerConfig {
+                url,
+                headers: raw.headers,
+                disabled: raw.disabled,
+            }))
+        }
+    }
+}
+
+/// The transport to a configured server.
+#[derive(Debug)]
+pub enum ConfiguredTransport {
+    /// A server run as a child process
+    ChildProcess(Box<ChildProcessTransport>),
+    /// A server reached over streamable HTTP
+    #[cfg(feature = "streamable-http")]
+    StreamableHttp(StreamableHttpTransport),
+}
+
+impl ConfiguredTransport {
+    fn inner(&mut self) -> &mut dyn Transport {
+        match self {
+            Self::ChildProcess(transport) => transport.as_mut(),
+            #[cfg(feature = "streamable-http")]
+            Self::StreamableHttp(transport) => transport,
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for ConfiguredTransport {
+    async fn send(&mut self, message: TransportMessage) -> Result<()> {
+        self.inner().send(message).await
+    }
+
+    async fn receive(&mut self) -> Result<TransportMessage> {
+        self.inner().receive().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner().close().await
+    }
+
+    async fn send_batch(&mut self, messages: Vec<TransportMessage>) -> Result<()> {
+        self.inner().send_batch(messages).await
+    }
+
+    async fn receive_batch(&mut self) -> Result<Vec<TransportMessage>> {
+        self.inner().receive_batch().await
+    }
+
+    fn is_connected(&self) -> bool {
+        match self {
+            Self::ChildProcess(transport) => transport.is_connected(),
+            #[cfg(feature = "streamable-http")]
+            Self::StreamableHttp(transport) => transport.is_connected(),
+        }
+    }
+
+    fn transport_type(&self) -> &'static str {
+        match self {
+            Self::ChildProcess(transport) => transport.transport_type(),
+            #[cfg(feature = "streamable-http")]
+            Self::StreamableHttp(transport) => transport.transport_type(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"{
+        "mcpServers": {
+            "echo": {
+                "command": "cat",
+                "args": ["-u"],
+                "env": {"DEBUG": "1"},
+                "cwd": "/"
+            },
+            "search": {
+                "type": "http",
+                "url": "https://search.example.com/mcp",
+                "headers": {"Authorization": "Bearer token"}
+            },
+            "old": {"command": "old-server", "disabled": true}
+        }
+    }"#;
+
+    #[test]
+    fn test_parses_stdio_and_http_servers() {
+        let config = McpConfig::from_json(CONFIG).unwrap();
+
+        let ServerConfig::Stdio(echo) = config.server("echo").unwrap() else {
+            panic!("expected a stdio server");
+        };
+        assert_eq!(echo.command, "cat");
+        assert_eq!(echo.args, ["-u"]);
+        assert_eq!(echo.env["DEBUG"], "1");
+        assert_eq!(echo.cwd.as_deref(), Some(Path::new("/")));
+
+        let ServerConfig::Http(search) = config.server("search").unwrap() else {
+            panic!("expected an HTTP server");
+        };
+        assert_eq!(search.headers["Authorization"], "Bearer token");
+
+        let enabled: Vec<&str> = config.enabled_servers().map(|(name, _)| name).collect();
+        assert_eq!(enabled, ["echo", "search"]);
+        assert!(config.server("missing").is_err());
+
+        let vscode = McpConfig::from_json(r#"{"servers": {"a": {"url": "http://a/mcp"}}}"#);
+        assert!(matches!(
+            vscode.unwrap().server("a").unwrap(),
+            ServerConfig::Http(_)
+        ));
+        assert!(McpConfig::from_json(r#"{"mcpServers": {"a": {"args": []}}}"#).is_err());
+        assert!(
+            McpConfig::from_json(r#"{"mcpServers": {"a": {"type": "ws", "url": "x"}}}"#).is_err()
+        );
+    }
+
+    #[cfg(all(unix, feature = "yaml"))]
+    #[tokio::test]
+    async fn test_yaml_config_connects_stdio_server() {
+        let config = McpConfig::from_yaml(
+            "mcpServers:\n  echo:\n    command: cat\n    env:\n      DEBUG: \"1\"\n",
+        )
+        .unwrap();
+        assert_eq!(config, {
+            let mut json = McpConfig::from_json(CONFIG).unwrap();
+            json.servers.retain(|name, _| name == "echo");
+            if let Some(ServerConfig::Stdio(echo)) = json.servers.get_mut("echo") {
+                echo.args.clear();
+                echo.cwd = None;
+            }
+            json
+        });
+
+        let mut transport = config.server("echo").unwrap().transport().unwrap();
+        assert_eq!(transport.transport_type(), "child-process");
+        let ping = TransportMessage::Notification(crate::types::Notification::Client(
+            crate::types::ClientNotification::Initialized,
+        ));
+        transport.send(ping).await.unwrap();
+        assert!(matches!(
+            transport.receive().await.unwrap(),
+            TransportMessage::Notification(_)
+        ));
+        transport.close().await.unwrap();
+
+        assert!(config.connect("echo").is_ok());
+        assert_eq!(config.multiplex_client().unwrap().server_names(), ["echo"]);
+    }
+}
@@ -0,0 +1,57 @@
+//! Answering servers' requests for user input.
+//!
+//! A server may send `elicitation/create` mid-operation to ask the user for
+//! data matching a JSON Schema. Register an [`ElicitationHandler`] with
+//! [`Client::on_elicitation`](crate::Client::on_elicitation) to present the
+//! request and return the user's answer, and declare the `elicitation`
+//! capability when initializing so the server knows it may ask.
+//!
+//! Requests arriving while the client waits for one of its own responses are
+//! answered in place. Without a handler they are rejected with
+//! "method not found".
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use pmcp::client::elicitation::ElicitationHandler;
+//! use pmcp::types::elicitation::{ElicitRequestParams, ElicitResult};
+//! use pmcp::{Client, StdioTransport};
+//!
+//! struct Terminal;
+//!
+//! #[async_trait]
+//! impl ElicitationHandler for Terminal {
+//!     async fn elicit(&self, params: ElicitRequestParams) -> pmcp::Result<ElicitResult> {
+//!         eprintln!("{}", params.message);
+//!         let mut answer = String::new();
+//!         std::io::stdin().read_line(&mut answer)?;
+//!         match answer.trim() {
+//!             "" => Ok(ElicitResult::cancel()),
+//!             "no" => Ok(ElicitResult::decline()),
+//!             value => {
+//!                 let mut content = serde_json::Map::new();
+//!                 content.insert("value".to_string(), value.into());
+//!                 Ok(ElicitResult::accept(content))
+//!             },
+//!         }
+//!     }
+//! }
+//!
+//! let client = Client::new(StdioTransport::new()).on_elicitation(Terminal);
+//! ```
+
+use crate::error::Result;
+use crate::types::elicitation::{ElicitRequestParams, ElicitResult};
+use async_trait::async_trait;
+
+/// Collects input from the user when a server asks for it.
+#[async_trait]
+pub trait ElicitationHandler: Send + Sync {
+    /// Present `params` to the user and return their answer.
+    ///
+    /// Errors are sent back to the server as a JSON-RPC error; a user who
+    /// simply refuses should be reported with [`ElicitResult::decline`] or
+    /// [`ElicitResult::cancel`] instead.
+    async fn elicit(&self, params: ElicitRequestParams) -> Result<ElicitResult>;
+}
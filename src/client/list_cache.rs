@@ -0,0 +1,262 @@
+//! Cache for list results.
+//!
+//! Tool, prompt and resource lists rarely change, yet clients often list them
+//! before every call. With a list cache enabled, [`Client::list_tools`] and
+//! the other list methods answer repeated requests from memory. Cached lists
+//! are dropped when the server sends the matching
+//! `notifications/*/list_changed`, and expire after a TTL so servers that
+//! never send change notifications are still picked up eventually.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::client::list_cache::{ListCacheConfig, ListKind};
+//! use pmcp::{ClientBuilder, ClientCapabilities, StdioTransport};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let mut client = ClientBuilder::new(StdioTransport::new())
+//!     .list_cache(ListCacheConfig::default().with_ttl(Duration::from_secs(60)))
+//!     .build();
+//! client.initialize(ClientCapabilities::default()).await?;
+//!
+//! let tools = client.list_tools(None).await?;
+//! // Answered from the cache
+//! let again = client.list_tools(None).await?;
+//!
+//! // Force the next call to ask the server
+//! client.invalidate_list_cache(ListKind::Tools);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Client::list_tools`]: crate::Client::list_tools
+
+use crate::types::ServerNotification;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Default time a cached list is reused.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// Settings for the list cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListCacheConfig {
+    ttl: Option<Duration>,
+}
+
+impl Default for ListCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Some(DEFAULT_TTL),
+        }
+    }
+}
+
+impl ListCacheConfig {
+    /// Set how long a cached list is reused before asking the server again.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Keep cached lists until the server reports a change.
+    ///
+    /// Only use this with servers that send `list_changed` notifications.
+    pub fn without_ttl(mut self) -> Self {
+        self.ttl = None;
+        self
+    }
+
+    /// Time a cached list is reused, or `None` if it never expires.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+}
+
+/// A list the client can cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListKind {
+    /// `tools/list`
+    Tools,
+    /// `prompts/list`
+    Prompts,
+    /// `resources/list`
+    Resources,
+    /// `resources/templates/list`
+    ResourceTemplates,
+}
+
+impl ListKind {
+    /// Lists made stale by a server notification.
+    pub(crate) fn changed_by(notification: &ServerNotification) -> &'static [ListKind] {
+        match notification {
+            ServerNotification::ToolsChanged => &[ListKind::Tools],
+            ServerNotification::PromptsChanged => &[ListKind::Prompts],
+            ServerNotification::ResourcesChanged => {
+                &[ListKind::Resources, ListKind::ResourceTemplates]
+            },
+            _ => &[],
+        }
+    }
+}
+
+/// Counters describing how the list cache has been used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListCacheStats {
+    /// List requests answered from the cache.
+    pub hits: u64,
+    /// List requests sent to the server.
+    pub misses: u64,
+    /// Times cached lists were dropped, by notification or manually.
+    pub invalidations: u64,
+    /// Pages currently cached.
+    pub entries: usize,
+}
+
+/// A cached page of a list.
+#[derive(Debug)]
+struct Entry {
+    result: Value,
+    stored_at: u64,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    entries: HashMap<(ListKind, Option<String>), Entry>,
+    stats: ListCacheStats,
+}
+
+/// List results keyed by list and cursor.
+#[derive(Debug)]
+pub(crate) struct ListCache {
+    config: ListCacheConfig,
+    state: Mutex<State>,
+}
+
+impl ListCache {
+    pub(crate) fn new(config: ListCacheConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Look up a cached page, dropping it if it has expired.
+    pub(crate) fn get(&self, kind: ListKind, cursor: Option<&str>) -> Option<Value> {
+        let mut state = self.state.lock();
+        let key = (kind, cursor.map(str::to_string));
+        let fresh = state.entries.get(&key).map(|entry| !self.is_expired(entry));
+
+        match fresh {
+            Some(true) => {
+                state.stats.hits += 1;
+                state.entries.get(&key).map(|entry| entry.result.clone())
+            },
+            Some(false) => {
+                state.entries.remove(&key);
+                state.stats.misses += 1;
+                None
+            },
+            None => {
+                state.stats.misses += 1;
+                None
+            },
+        }
+    }
+
+    /// Store a page fetched from the server.
+    pub(crate) fn insert(&self, kind: ListKind, cursor: Option<String>, result: Value) {
+        self.state.lock().entries.insert(
+            (kind, cursor),
+            Entry {
+                result,
+                stored_at: crate::shared::runtime::timestamp_millis(),
+            },
+        );
+    }
+
+    /// Drop every cached page of a list.
+    pub(crate) fn invalidate(&self, kind: ListKind) {
+        let mut state = self.state.lock();
+        state.entries.retain(|(cached, _), _| *cached != kind);
+        state.stats.invalidations += 1;
+    }
+
+    /// Drop all cached pages.
+    pub(crate) fn clear(&self) {
+        let mut state = self.state.lock();
+        state.entries.clear();
+        state.stats.invalidations += 1;
+    }
+
+    pub(crate) fn stats(&self) -> ListCacheStats {
+        let state = self.state.lock();
+        ListCacheStats {
+            entries: state.entries.len(),
+            ..state.stats
+        }
+    }
+
+    fn is_expired(&self, entry: &Entry) -> bool {
+        self.config.ttl.is_some_and(|ttl| {
+            let ttl_ms = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+            crate::shared::runtime::timestamp_millis().saturating_sub(entry.stored_at) >= ttl_ms
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_hits_misses_and_invalidation() {
+        let cache = ListCache::new(ListCacheConfig::default());
+        assert!(cache.get(ListKind::Tools, None).is_none());
+
+        cache.insert(ListKind::Tools, None, json!({ "tools": [] }));
+        cache.insert(
+            ListKind::Tools,
+            Some("2".to_string()),
+            json!({ "tools": [] }),
+        );
+        cache.insert(ListKind::Prompts, None, json!({ "prompts": [] }));
+        assert_eq!(
+            cache.get(ListKind::Tools, None),
+            Some(json!({ "tools": [] }))
+        );
+        assert!(cache.get(ListKind::Tools, Some("2")).is_some());
+
+        for kind in ListKind::changed_by(&ServerNotification::ToolsChanged) {
+            cache.invalidate(*kind);
+        }
+        assert!(cache.get(ListKind::Tools, Some("2")).is_none());
+        assert!(cache.get(ListKind::Prompts, None).is_some());
+
+        assert_eq!(
+            cache.stats(),
+            ListCacheStats {
+                hits: 3,
+                misses: 2,
+                invalidations: 1,
+                entries: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let cache = ListCache::new(ListCacheConfig::default().with_ttl(Duration::ZERO));
+        cache.insert(ListKind::Resources, None, json!({ "resources": [] }));
+        assert!(cache.get(ListKind::Resources, None).is_none());
+        assert_eq!(cache.stats().entries, 0);
+
+        let cache = ListCache::new(ListCacheConfig::default().without_ttl());
+        cache.insert(ListKind::Resources, None, json!({ "resources": [] }));
+        assert!(cache.get(ListKind::Resources, None).is_some());
+    }
+}
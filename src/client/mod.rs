@@ -1,23 +1,27 @@
 //! MCP client implementation.
 
 use crate::error::{Error, Result};
-use crate::shared::{Protocol, ProtocolOptions, Transport};
+use crate::shared::{Protocol, ProtocolOptions, RequestIdGenerator, RequestOptions, Transport};
 use crate::types::{
     CallToolRequest, CallToolResult, CancelledNotification, ClientCapabilities, ClientNotification,
     ClientRequest, CompleteRequest, CompleteResult, CreateMessageRequest, CreateMessageResult,
     GetPromptRequest, GetPromptResult, Implementation, InitializeRequest, InitializeResult,
     ListPromptsRequest, ListPromptsResult, ListResourceTemplatesRequest,
     ListResourceTemplatesResult, ListResourcesRequest, ListResourcesResult, ListToolsRequest,
-    ListToolsResult, LoggingLevel, Notification, ProgressNotification, ReadResourceRequest,
-    ReadResourceResult, Request, RequestId, ServerCapabilities, SubscribeRequest,
-    UnsubscribeRequest,
+    ListToolsResult, LoggingLevel, Notification, ProgressNotification, ProgressToken, PromptInfo,
+    ReadResourceRequest, ReadResourceResult, Request, RequestId, ResourceInfo, ResourceTemplate,
+    ServerCapabilities, SubscribeRequest, ToolInfo, UnsubscribeRequest,
 };
-use std::collections::HashMap;
+use futures::{Stream, TryStreamExt};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use uuid::Uuid;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::types::Content;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::sync::{mpsc, oneshot, RwLock};
+#[cfg(not(target_arch = "wasm32"))]
+use uuid::Uuid;
 
 #[cfg(target_arch = "wasm32")]
 use futures_channel::{mpsc, oneshot};
@@ -26,7 +30,198 @@ use futures_locks::RwLock;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod auth;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod auto_reconnect;
+pub mod batch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod config;
+pub mod elicitation;
+pub mod list_cache;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod multiplex;
+pub mod notifications;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod oauth;
+pub mod offline;
 pub mod transport;
+mod typed;
+
+pub use batch::{BatchResult, RequestBatch};
+pub use list_cache::{ListCacheConfig, ListCacheStats, ListKind};
+pub use notifications::{NotificationStream, Subscription};
+pub use offline::{OfflineQueueConfig, QueuedResponse};
+
+/// Chunk size, in bytes of the transmitted representation, used by
+/// [`Client::read_resource_stream`].
+pub const RESOURCE_STREAM_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// Progress token of an in-flight request and the callback its updates go to.
+type ProgressRoute<'a> = (
+    &'a ProgressToken,
+    &'a (dyn Fn(&ProgressNotification) + Sync),
+);
+
+/// Callback receiving the server's log messages.
+type LogHandler = Arc<dyn Fn(&crate::types::protocol::LogMessageParams) + Send + Sync>;
+
+/// Decode the raw bytes carried by a resource content item.
+#[cfg(not(target_arch = "wasm32"))]
+fn content_bytes(content: &Content) -> Result<Vec<u8>> {
+    use crate::types::ResourceContents;
+    use base64::Engine;
+
+    match content {
+        Content::Text { text }
+        | Content::Resource {
+            text: Some(text), ..
+        }
+        | Content::EmbeddedResource {
+            resource: ResourceContents {
+                text: Some(text), ..
+            },
+        } => Ok(text.as_bytes().to_vec()),
+        Content::Image { data, .. }
+        | Content::Audio { data, .. }
+        | Content::EmbeddedResource {
+            resource: ResourceContents {
+                blob: Some(data), ..
+            },
+        } => base64::prelude::BASE64_STANDARD
+            .decode(data)
+            .map_err(|e| Error::parse(format!("Invalid base64 resource data: {}", e))),
+        Content::Resource { .. } | Content::ResourceLink(_) | Content::EmbeddedResource { .. } => {
+            Ok(Vec::new())
+        },
+    }
+}
+
+/// Follow `next_cursor` across pages, yielding the items of each page.
+///
+/// Fails if the server hands back the cursor it was just given, which would
+/// otherwise loop forever.
+fn paginate<'a, I, F, Fut>(fetch: F) -> impl Stream<Item = Result<I>> + 'a
+where
+    I: 'a,
+    F: Fn(Option<String>) -> Fut + 'a,
+    Fut: std::future::Future<Output = Result<(Vec<I>, Option<String>)>> + 'a,
+{
+    let pages = futures::stream::try_unfold(Some(None), move |cursor: Option<Option<String>>| {
+        let page = cursor.map(|cursor| (cursor.clone(), fetch(cursor)));
+        async move {
+            let Some((cursor, page)) = page else {
+                return Ok(None);
+            };
+            let (items, next_cursor) = page.await?;
+            if next_cursor.is_some() && next_cursor == cursor {
+                return Err(Error::protocol_msg(format!(
+                    "Server returned the same pagination cursor twice: {}",
+                    next_cursor.unwrap_or_default()
+                )));
+            }
+            let items = futures::stream::iter(items.into_iter().map(Ok));
+            Ok(Some((items, next_cursor.map(Some))))
+        }
+    });
+    pages.try_flatten()
+}
+
+/// Ask for progress on a request, returning the token it will arrive under.
+///
+/// Only tool calls carry request metadata, so other requests get `None`.
+fn request_progress(request: &mut Request, request_id: &RequestId) -> Option<ProgressToken> {
+    let Request::Client(client_request) = request else {
+        return None;
+    };
+    let ClientRequest::CallTool(call) = client_request.as_mut() else {
+        return None;
+    };
+    if let Some(token) = call.progress_token() {
+        return Some(token);
+    }
+    let token = match request_id {
+        RequestId::Number(n) => ProgressToken::Number(*n),
+        RequestId::String(s) => ProgressToken::String(s.clone()),
+    };
+    *call = call.clone().with_progress_token(token.clone());
+    Some(token)
+}
+
+/// Pass a progress notification to the request's progress callback.
+fn report_progress(options: &RequestOptions, progress: &ProgressNotification) {
+    if let Some(callback) = &options.on_progress {
+        // Progress is reported as a percentage
+        callback(
+            progress.progress.clamp(0.0, 100.0).round() as u64,
+            Some(100),
+        );
+    }
+}
+
+/// Server capability a client request method depends on.
+fn required_capability(method: &str) -> Option<&'static str> {
+    match method {
+        "tools/list" | "tools/call" => Some("tools"),
+        "prompts/list" | "prompts/get" => Some("prompts"),
+        "resources/subscribe" | "resources/unsubscribe" => Some("resources.subscribe"),
+        "resources/list" | "resources/templates/list" | "resources/read" => Some("resources"),
+        "logging/setLevel" => Some("logging"),
+        "completion/complete" => Some("completions"),
+        "sampling/createMessage" => Some("sampling"),
+        _ => None,
+    }
+}
+
+/// Capabilities advertised in `before` that are missing from `after`.
+fn capability_regressions(
+    before: &ServerCapabilities,
+    after: &ServerCapabilities,
+) -> Vec<&'static str> {
+    let subscribes = |caps: &ServerCapabilities| {
+        caps.resources
+            .as_ref()
+            .and_then(|r| r.subscribe)
+            .unwrap_or(false)
+    };
+    [
+        ("tools", before.tools.is_some(), after.tools.is_some()),
+        ("prompts", before.prompts.is_some(), after.prompts.is_some()),
+        (
+            "resources",
+            before.resources.is_some(),
+            after.resources.is_some(),
+        ),
+        (
+            "resource subscriptions",
+            subscribes(before),
+            subscribes(after),
+        ),
+        ("logging", before.logging.is_some(), after.logging.is_some()),
+        (
+            "completions",
+            before.completions.is_some(),
+            after.completions.is_some(),
+        ),
+        (
+            "sampling",
+            before.sampling.is_some(),
+            after.sampling.is_some(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, had, has)| *had && !*has)
+    .map(|(name, _, _)| name)
+    .collect()
+}
+
+/// Decoded bytes of one chunked resource read.
+#[cfg(not(target_arch = "wasm32"))]
+struct FetchedChunk {
+    bytes: Vec<u8>,
+    /// Offset of the next chunk, or `None` after the last one.
+    next: Option<u64>,
+    /// Expected size of the decoded resource, if known.
+    total: Option<u64>,
+}
 
 /// MCP client for connecting to servers.
 pub struct Client<T: Transport> {
@@ -35,11 +230,35 @@ pub struct Client<T: Transport> {
     capabilities: Option<ClientCapabilities>,
     server_capabilities: Option<ServerCapabilities>,
     server_version: Option<Implementation>,
+    protocol_version: Option<String>,
     instructions: Option<String>,
     initialized: bool,
     info: Implementation,
     notification_tx: Option<mpsc::Sender<Notification>>,
     active_requests: Arc<RwLock<HashMap<RequestId, oneshot::Sender<()>>>>,
+    /// Resource reads tagged with an entity tag, reused on `notModified` replies
+    resource_cache: Arc<RwLock<HashMap<String, ReadResourceResult>>>,
+    /// Subscribed resource URIs, restored by [`Client::reconnect`]
+    subscriptions: Arc<RwLock<HashSet<String>>>,
+    /// Requests held while disconnected, flushed by [`Client::reconnect`]
+    offline_queue: Option<Arc<offline::OfflineQueue>>,
+    /// Cached list results, when enabled with [`Client::with_list_cache`]
+    list_cache: Option<Arc<list_cache::ListCache>>,
+    /// Input schemas from the last `tools/list`, by tool name
+    tool_schemas: Arc<RwLock<HashMap<String, serde_json::Value>>>,
+    /// Output schemas from the last `tools/list`, by tool name
+    output_schemas: Arc<RwLock<HashMap<String, serde_json::Value>>>,
+    /// Whether tool results are checked against their output schema
+    validate_tool_output: bool,
+    /// Receives `notifications/message` from the server
+    log_handler: Option<LogHandler>,
+    /// Callbacks and streams subscribed to server notifications
+    notifications: Arc<notifications::NotificationRegistry>,
+    /// Answers the server's `elicitation/create` requests
+    elicitation_handler: Option<Arc<dyn elicitation::ElicitationHandler>>,
+    /// Restores lost connections, when enabled with [`Client::with_reconnect`]
+    #[cfg(not(target_arch = "wasm32"))]
+    auto_reconnect: Option<Arc<auto_reconnect::Reconnector<T>>>,
 }
 
 impl<T: Transport> std::fmt::Debug for Client<T> {
@@ -103,11 +322,24 @@ impl<T: Transport> Client<T> {
             capabilities: None,
             server_capabilities: None,
             server_version: None,
+            protocol_version: None,
             instructions: None,
             initialized: false,
             info: client_info,
             notification_tx: None,
             active_requests: Arc::new(RwLock::new(HashMap::new())),
+            resource_cache: Arc::new(RwLock::new(HashMap::new())),
+            subscriptions: Arc::new(RwLock::new(HashSet::new())),
+            offline_queue: None,
+            list_cache: None,
+            tool_schemas: Arc::new(RwLock::new(HashMap::new())),
+            output_schemas: Arc::new(RwLock::new(HashMap::new())),
+            validate_tool_output: false,
+            log_handler: None,
+            notifications: Arc::default(),
+            elicitation_handler: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            auto_reconnect: None,
         }
     }
 
@@ -126,6 +358,7 @@ impl<T: Transport> Client<T> {
     ///         "notifications/progress".to_string(),
     ///         "notifications/message".to_string(),
     ///     ],
+    ///     ..Default::default()
     /// };
     ///
     /// let transport = StdioTransport::new();
@@ -147,11 +380,24 @@ impl<T: Transport> Client<T> {
             capabilities: None,
             server_capabilities: None,
             server_version: None,
+            protocol_version: None,
             instructions: None,
             initialized: false,
             info: client_info,
             notification_tx: None,
             active_requests: Arc::new(RwLock::new(HashMap::new())),
+            resource_cache: Arc::new(RwLock::new(HashMap::new())),
+            subscriptions: Arc::new(RwLock::new(HashSet::new())),
+            offline_queue: None,
+            list_cache: None,
+            tool_schemas: Arc::new(RwLock::new(HashMap::new())),
+            output_schemas: Arc::new(RwLock::new(HashMap::new())),
+            validate_tool_output: false,
+            log_handler: None,
+            notifications: Arc::default(),
+            elicitation_handler: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            auto_reconnect: None,
         }
     }
 
@@ -195,35 +441,38 @@ impl<T: Transport> Client<T> {
         }
 
         self.capabilities = Some(capabilities.clone());
+        let init_result = self.handshake(capabilities).await?;
+
+        self.server_capabilities = Some(init_result.capabilities.clone());
+        self.server_version = Some(init_result.server_info.clone());
+        self.protocol_version = Some(init_result.protocol_version.as_str().to_string());
+        self.instructions.clone_from(&init_result.instructions);
+        self.initialized = true;
+
+        Ok(init_result)
+    }
 
+    /// Exchange `initialize` and send `notifications/initialized`.
+    async fn handshake(&self, capabilities: ClientCapabilities) -> Result<InitializeResult> {
+        let versions = self.protocol.read().await.options().versions.clone();
         // Send initialize request
         let request = Request::Client(Box::new(ClientRequest::Initialize(InitializeRequest {
-            protocol_version: crate::types::LATEST_PROTOCOL_VERSION.to_string(),
+            protocol_version: versions.latest().to_string(),
             capabilities,
             client_info: self.info.clone(),
         })));
 
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
-        let response = self.send_request(request_id, request).await?;
+        let request_id = self.next_request_id().await;
+        let response = self
+            .send_once(request_id, request, &RequestOptions::default())
+            .await?;
 
         // Parse initialize result
         match response.payload {
             crate::types::jsonrpc::ResponsePayload::Result(result) => {
                 if let Ok(init_result) = serde_json::from_value::<InitializeResult>(result) {
-                    // Validate protocol version
-                    if !crate::types::SUPPORTED_PROTOCOL_VERSIONS
-                        .contains(&init_result.protocol_version.as_str())
-                    {
-                        return Err(Error::protocol_msg(format!(
-                            "Server protocol version {} not supported",
-                            init_result.protocol_version
-                        )));
-                    }
-
-                    self.server_capabilities = Some(init_result.capabilities.clone());
-                    self.server_version = Some(init_result.server_info.clone());
-                    self.instructions.clone_from(&init_result.instructions);
-                    self.initialized = true;
+                    let version = versions.accept(init_result.protocol_version.as_str())?;
+                    self.protocol.write().await.set_negotiated_version(version);
 
                     // Send initialized notification
                     self.send_notification(Notification::Client(ClientNotification::Initialized))
@@ -240,6 +489,397 @@ impl<T: Transport> Client<T> {
         }
     }
 
+    /// Restore the session over a new transport after the connection was lost.
+    ///
+    /// Redoes the initialize handshake with the capabilities originally
+    /// passed to [`initialize`](Self::initialize), checks that the server
+    /// still offers every capability it advertised before, and subscribes
+    /// again to all subscribed resources. Requests queued with
+    /// [`send_or_queue`](Self::send_or_queue) are then sent in order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, ClientCapabilities, StdioTransport};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// // ... the connection drops ...
+    /// client.reconnect(StdioTransport::new()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The client was never initialized
+    /// - The handshake fails
+    /// - The server no longer advertises a capability it had before
+    ///   ([`Error::UnsupportedCapability`])
+    /// - A resource subscription cannot be re-established
+    /// - The connection drops again while flushing queued requests; the
+    ///   unsent requests stay queued
+    pub async fn reconnect(&mut self, transport: T) -> Result<InitializeResult> {
+        let capabilities = match (&self.capabilities, self.initialized) {
+            (Some(capabilities), true) => capabilities.clone(),
+            _ => return Err(Error::InvalidState("Client not initialized".into())),
+        };
+        let previous = self.server_capabilities.clone().unwrap_or_default();
+
+        *self.transport.write().await = transport;
+        self.active_requests.write().await.clear();
+        self.clear_list_cache();
+        self.initialized = false;
+
+        let result = self.initialize(capabilities).await?;
+        self.restore_session(&previous, &result).await?;
+
+        Ok(result)
+    }
+
+    /// Bring a newly initialized session back to the state of the lost one.
+    ///
+    /// Checks that the server still offers the `previous` capabilities,
+    /// renews resource subscriptions, and flushes the offline queue.
+    async fn restore_session(
+        &self,
+        previous: &ServerCapabilities,
+        result: &InitializeResult,
+    ) -> Result<()> {
+        let missing = capability_regressions(previous, &result.capabilities);
+        if !missing.is_empty() {
+            return Err(Error::capability(format!(
+                "Server no longer supports {} after reconnect",
+                missing.join(", ")
+            )));
+        }
+
+        let uris: Vec<String> = self.subscriptions.read().await.iter().cloned().collect();
+        for uri in uris {
+            let request =
+                Request::Client(Box::new(ClientRequest::Subscribe(SubscribeRequest { uri })));
+            let request_id = self.next_request_id().await;
+            let response = self
+                .send_once(request_id, request, &RequestOptions::default())
+                .await?;
+            if let crate::types::jsonrpc::ResponsePayload::Error(error) = response.payload {
+                return Err(Error::from_jsonrpc_error(error));
+            }
+        }
+
+        self.flush_offline_queue().await
+    }
+
+    /// Reconnect automatically when the connection is lost.
+    ///
+    /// See the [`auto_reconnect`] module. Lifecycle events can be observed
+    /// with [`reconnect_events`](Self::reconnect_events).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_reconnect(mut self, config: auto_reconnect::AutoReconnect<T>) -> Self {
+        self.auto_reconnect = Some(Arc::new(auto_reconnect::Reconnector::new(config)));
+        self
+    }
+
+    /// Subscribe to the events of automatic reconnection, or `None` if it is
+    /// not enabled.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reconnect_events(
+        &self,
+    ) -> Option<tokio::sync::broadcast::Receiver<crate::shared::ReconnectEvent>> {
+        self.auto_reconnect
+            .as_ref()
+            .map(|reconnector| reconnector.subscribe())
+    }
+
+    /// Enable queueing of requests while the transport is disconnected.
+    ///
+    /// See [`send_or_queue`](Self::send_or_queue).
+    pub fn with_offline_queue(mut self, config: OfflineQueueConfig) -> Self {
+        self.offline_queue = Some(Arc::new(offline::OfflineQueue::new(config)));
+        self
+    }
+
+    /// Cache the results of the list methods.
+    ///
+    /// See the [`list_cache`] module.
+    pub fn with_list_cache(mut self, config: ListCacheConfig) -> Self {
+        self.list_cache = Some(Arc::new(list_cache::ListCache::new(config)));
+        self
+    }
+
+    /// Usage counters of the list cache, or `None` if it is not enabled.
+    pub fn list_cache_stats(&self) -> Option<ListCacheStats> {
+        self.list_cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// Drop the cached pages of one list, so the next call asks the server.
+    pub fn invalidate_list_cache(&self, kind: ListKind) {
+        if let Some(cache) = &self.list_cache {
+            cache.invalidate(kind);
+        }
+    }
+
+    /// Drop every cached list.
+    pub fn clear_list_cache(&self) {
+        if let Some(cache) = &self.list_cache {
+            cache.clear();
+        }
+    }
+
+    /// Check tool results against the output schema the tool advertises.
+    ///
+    /// Output schemas are learned from [`list_tools`](Self::list_tools).
+    /// When enabled, a successful result from a tool with an output schema
+    /// must carry `structuredContent` matching it, or the call fails with a
+    /// validation error listing each mismatch. With the `validation` feature
+    /// the full schema is enforced, otherwise only required properties are
+    /// checked.
+    pub fn with_output_validation(mut self, enabled: bool) -> Self {
+        self.validate_tool_output = enabled;
+        self
+    }
+
+    /// Pass the server's log messages (`notifications/message`) to
+    /// `handler`.
+    ///
+    /// Messages arrive while the client waits for a response. Use
+    /// [`set_logging_level`](Self::set_logging_level) to choose which the
+    /// server sends.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::{Client, StdioTransport};
+    ///
+    /// let client = Client::new(StdioTransport::new()).on_log(|message| {
+    ///     eprintln!(
+    ///         "[{:?}] {}: {}",
+    ///         message.level,
+    ///         message.logger.as_deref().unwrap_or("server"),
+    ///         message.message
+    ///     );
+    /// });
+    /// ```
+    pub fn on_log(
+        mut self,
+        handler: impl Fn(&crate::types::protocol::LogMessageParams) + Send + Sync + 'static,
+    ) -> Self {
+        self.log_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Answer the server's `elicitation/create` requests with `handler`.
+    ///
+    /// Also declare the `elicitation` capability when initializing; see
+    /// [`elicitation`] for an example.
+    pub fn on_elicitation(
+        mut self,
+        handler: impl elicitation::ElicitationHandler + 'static,
+    ) -> Self {
+        self.elicitation_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Call `callback` with each progress notification under `token`.
+    ///
+    /// For progress on requests sent without
+    /// [`RequestOptions::on_progress`](crate::shared::RequestOptions), such as
+    /// a tool call made with an explicit progress token. The callback stays
+    /// registered until the returned [`Subscription`] is dropped. See
+    /// [`notifications`] for when notifications are delivered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::types::ProgressToken;
+    /// use pmcp::{Client, StdioTransport};
+    ///
+    /// let client = Client::new(StdioTransport::new());
+    /// let subscription = client.on_progress(ProgressToken::String("import".into()), |update| {
+    ///     println!("{}%", update.progress);
+    /// });
+    /// // ... call a tool with the progress token "import" ...
+    /// drop(subscription);
+    /// ```
+    pub fn on_progress(
+        &self,
+        token: ProgressToken,
+        callback: impl Fn(&ProgressNotification) + Send + Sync + 'static,
+    ) -> Subscription {
+        self.notifications
+            .subscribe(notifications::progress_handler(token, callback))
+    }
+
+    /// Call `callback` when the server reports that the resource at `uri`
+    /// changed.
+    ///
+    /// The server only reports changes to resources the client subscribed
+    /// to with [`subscribe_resource`](Self::subscribe_resource). The callback
+    /// stays registered until the returned [`Subscription`] is dropped.
+    pub fn on_resource_updated(
+        &self,
+        uri: impl Into<String>,
+        callback: impl Fn(&crate::types::protocol::ResourceUpdatedParams) + Send + Sync + 'static,
+    ) -> Subscription {
+        self.notifications
+            .subscribe(notifications::resource_updated_handler(
+                uri.into(),
+                callback,
+            ))
+    }
+
+    /// Call `callback` when the server reports that its list of tools
+    /// changed.
+    ///
+    /// The callback stays registered until the returned [`Subscription`] is
+    /// dropped.
+    pub fn on_tools_changed(&self, callback: impl Fn() + Send + Sync + 'static) -> Subscription {
+        self.notifications
+            .subscribe(notifications::tools_changed_handler(callback))
+    }
+
+    /// A stream of every notification from the server.
+    ///
+    /// The stream buffers up to `capacity` notifications. When it is full,
+    /// the client stops reading from the transport until the stream is read,
+    /// so a slow consumer slows the client down rather than losing
+    /// notifications. Read it from a task other than the one making
+    /// requests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use pmcp::{Client, ClientCapabilities, StdioTransport};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// let mut notifications = client.notification_stream(64);
+    /// tokio::spawn(async move {
+    ///     while let Some(notification) = notifications.next().await {
+    ///         println!("{:?}", notification);
+    ///     }
+    /// });
+    /// client.initialize(ClientCapabilities::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn notification_stream(&self, capacity: usize) -> NotificationStream {
+        self.notifications.stream(capacity)
+    }
+
+    /// Number of requests waiting in the offline queue.
+    pub fn queued_requests(&self) -> usize {
+        self.offline_queue.as_ref().map_or(0, |queue| queue.len())
+    }
+
+    /// Send a request now, or queue it until the connection is restored.
+    ///
+    /// If the transport is connected the request is sent immediately and the
+    /// returned [`QueuedResponse`] is already resolved. If the transport is
+    /// disconnected, or the send fails because the connection was lost, the
+    /// request is queued and the response resolves after a successful
+    /// [`reconnect`](Self::reconnect).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The offline queue is not enabled
+    /// - The client was never initialized
+    /// - The request is an `initialize` request
+    /// - The queue is full
+    pub async fn send_or_queue(&self, request: ClientRequest) -> Result<QueuedResponse> {
+        let Some(queue) = &self.offline_queue else {
+            return Err(Error::invalid_state("Offline queue is not enabled"));
+        };
+        if self.capabilities.is_none() {
+            return Err(Error::invalid_state("Client not initialized"));
+        }
+        if matches!(request, ClientRequest::Initialize(_)) {
+            return Err(Error::invalid_state("Initialize requests cannot be queued"));
+        }
+
+        if self.initialized && self.transport.read().await.is_connected() {
+            let request_id = self.next_request_id().await;
+            match self
+                .send_request(request_id, Request::Client(Box::new(request.clone())))
+                .await
+            {
+                Err(e) if e.is_fatal_for_connection() => {
+                    tracing::debug!("Connection lost, queueing request: {}", e);
+                },
+                result => return Ok(QueuedResponse::ready(result)),
+            }
+        }
+
+        queue.push(request)
+    }
+
+    /// Send the requests queued while disconnected, oldest first.
+    async fn flush_offline_queue(&self) -> Result<()> {
+        let Some(queue) = &self.offline_queue else {
+            return Ok(());
+        };
+        while let Some(entry) = queue.pop() {
+            let request_id = self.next_request_id().await;
+            let request = Request::Client(Box::new(entry.request.clone()));
+            match self
+                .send_once(request_id, request, &RequestOptions::default())
+                .await
+            {
+                Err(e) if e.is_fatal_for_connection() => {
+                    queue.requeue(entry);
+                    return Err(e);
+                },
+                result => entry.complete(result),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reconnect using a [`ReconnectManager`] and restore the session.
+    ///
+    /// `connect` is retried according to the manager's backoff policy until
+    /// it yields a transport, which is then passed to
+    /// [`reconnect`](Self::reconnect).
+    ///
+    /// [`ReconnectManager`]: crate::shared::ReconnectManager
+    ///
+    /// # Errors
+    ///
+    /// Returns the last connection error once the manager gives up, or any
+    /// error from [`reconnect`](Self::reconnect).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn reconnect_with<F, Fut>(
+        &mut self,
+        manager: &crate::shared::ReconnectManager,
+        connect: F,
+    ) -> Result<InitializeResult>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let connected = parking_lot::Mutex::new(None);
+        manager
+            .reconnect_with(|| {
+                let attempt = connect();
+                let connected = &connected;
+                async move {
+                    *connected.lock() = Some(attempt.await?);
+                    Ok(())
+                }
+            })
+            .await?;
+
+        let transport = connected
+            .into_inner()
+            .ok_or_else(|| Error::internal("Reconnect succeeded without a transport"))?;
+        self.reconnect(transport).await
+    }
+
     /// Get server capabilities after initialization.
     pub fn get_server_capabilities(&self) -> Option<&ServerCapabilities> {
         self.server_capabilities.as_ref()
@@ -250,6 +890,28 @@ impl<T: Transport> Client<T> {
         self.server_version.as_ref()
     }
 
+    /// Start a batch of requests sent together in a single round trip.
+    ///
+    /// See [`RequestBatch`] for details.
+    pub fn batch(&self) -> RequestBatch<'_, T> {
+        RequestBatch::new(self)
+    }
+
+    /// Get the protocol version negotiated during initialization.
+    pub fn get_protocol_version(&self) -> Option<&str> {
+        self.protocol_version.as_deref()
+    }
+
+    /// Whether the protocol version negotiated during initialization has
+    /// `feature`.
+    ///
+    /// False before initialization.
+    pub fn supports_feature(&self, feature: crate::shared::ProtocolFeature) -> bool {
+        self.protocol_version
+            .as_deref()
+            .is_some_and(|version| crate::shared::NegotiatedVersion::new(version).supports(feature))
+    }
+
     /// Get server instructions after initialization.
     pub fn get_instructions(&self) -> Option<&str> {
         self.instructions.as_deref()
@@ -259,7 +921,7 @@ impl<T: Transport> Client<T> {
     pub async fn ping(&self) -> Result<()> {
         self.ensure_initialized()?;
         let request = Request::Client(Box::new(ClientRequest::Ping));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
+        let request_id = self.next_request_id().await;
         let response = self.send_request(request_id, request).await?;
 
         match response.payload {
@@ -270,13 +932,91 @@ impl<T: Transport> Client<T> {
         }
     }
 
+    /// Ping the server while the client is idle to detect a dead connection.
+    ///
+    /// Every `config.interval()` the server is pinged, unless requests are in
+    /// flight; those carry their own timeouts. Once
+    /// `config.failure_threshold()` pings in a row fail or go unanswered, the
+    /// transport is closed and `on_timeout` runs, typically to
+    /// [`reconnect`](Self::reconnect) with a fresh transport. Dropping the
+    /// returned handle stops the pings. See [`crate::shared::keepalive`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::shared::keepalive::KeepAliveConfig;
+    /// use pmcp::{Client, ClientCapabilities, StdioTransport};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let (dead_tx, dead_rx) = tokio::sync::oneshot::channel();
+    /// let _keep_alive = client.start_keep_alive(KeepAliveConfig::default(), move || {
+    ///     let _ = dead_tx.send(());
+    /// })?;
+    ///
+    /// // Wait for the connection to die, then start over
+    /// let _ = dead_rx.await;
+    /// client.reconnect(StdioTransport::new()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not initialized.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start_keep_alive(
+        &self,
+        config: crate::shared::KeepAliveConfig,
+        on_timeout: impl FnOnce() + Send + 'static,
+    ) -> Result<crate::shared::KeepAliveHandle>
+    where
+        T: 'static,
+    {
+        self.ensure_initialized()?;
+        let ping = {
+            let client = self.clone();
+            let timeout = config.timeout();
+            move || {
+                let client = client.clone();
+                async move {
+                    if !client.active_requests.read().await.is_empty() {
+                        return Ok(());
+                    }
+                    let options = RequestOptions::default().with_timeout(timeout);
+                    client
+                        .request_with_options(ClientRequest::Ping, &options)
+                        .await
+                        .map(drop)
+                }
+            }
+        };
+        let transport = self.transport.clone();
+        let close = move || async move {
+            if let Err(e) = transport.write().await.close().await {
+                tracing::debug!("Failed to close dead transport: {}", e);
+            }
+            on_timeout();
+        };
+
+        // The ping request times out on its own first, so it stops tracking
+        // itself and tells the server before the schedule gives up on it
+        Ok(crate::shared::keepalive::spawn_keep_alive(
+            config.with_timeout(config.timeout() * 2),
+            ping,
+            close,
+        ))
+    }
+
     /// Set the logging level on the server.
     pub async fn set_logging_level(&self, level: LoggingLevel) -> Result<()> {
         self.ensure_initialized()?;
         self.assert_capability("logging", "logging/setLevel")?;
 
         let request = Request::Client(Box::new(ClientRequest::SetLoggingLevel { level }));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
+        let request_id = self.next_request_id().await;
         let response = self.send_request(request_id, request).await?;
 
         match response.payload {
@@ -320,44 +1060,83 @@ impl<T: Transport> Client<T> {
         self.ensure_initialized()?;
         self.assert_capability("tools", "tools/list")?;
 
-        let request = Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
-            cursor,
-        })));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
-        let response = self.send_request(request_id, request).await?;
-
-        match response.payload {
-            crate::types::jsonrpc::ResponsePayload::Result(result) => {
-                serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
-            },
-            crate::types::jsonrpc::ResponsePayload::Error(error) => {
-                Err(Error::from_jsonrpc_error(error))
-            },
+        let result = self
+            .list_request(
+                ListKind::Tools,
+                cursor.clone(),
+                ClientRequest::ListTools(ListToolsRequest { cursor }),
+            )
+            .await?;
+        let result: ListToolsResult =
+            serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))?;
+        let mut schemas = self.tool_schemas.write().await;
+        let mut output_schemas = self.output_schemas.write().await;
+        for tool in &result.tools {
+            schemas.insert(tool.name.clone(), tool.input_schema.clone());
+            match &tool.output_schema {
+                Some(schema) => output_schemas.insert(tool.name.clone(), schema.clone()),
+                None => output_schemas.remove(&tool.name),
+            };
         }
+        Ok(result)
     }
 
-    /// Call a tool.
-    ///
-    /// Invokes a server-provided tool with the specified name and arguments.
-    /// The server must have declared the tool via the tools capability during initialization.
+    /// Stream all tools, following pagination cursors.
     ///
-    /// # Arguments
-    ///
-    /// * `name` - The name of the tool to call
-    /// * `arguments` - JSON value containing the tool's arguments
+    /// Pages are requested as the stream is polled, starting from the first
+    /// page.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
+    /// use futures::TryStreamExt;
     /// use pmcp::{Client, StdioTransport, ClientCapabilities};
-    /// use serde_json::json;
     ///
     /// # async fn example() -> pmcp::Result<()> {
-    /// let transport = StdioTransport::new();
-    /// let mut client = Client::new(transport);
+    /// let mut client = Client::new(StdioTransport::new());
     /// client.initialize(ClientCapabilities::default()).await?;
     ///
-    /// // Call a simple tool with no arguments
+    /// let mut tools = std::pin::pin!(client.list_tools_stream());
+    /// while let Some(tool) = tools.try_next().await? {
+    ///     println!("Tool: {}", tool.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_tools_stream(&self) -> impl Stream<Item = Result<ToolInfo>> + '_ {
+        paginate(move |cursor| async move {
+            let page = self.list_tools(cursor).await?;
+            Ok((page.tools, page.next_cursor))
+        })
+    }
+
+    /// List all tools, following pagination cursors.
+    pub async fn list_tools_all(&self) -> Result<Vec<ToolInfo>> {
+        self.list_tools_stream().try_collect().await
+    }
+
+    /// Call a tool.
+    ///
+    /// Invokes a server-provided tool with the specified name and arguments.
+    /// The server must have declared the tool via the tools capability during initialization.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the tool to call
+    /// * `arguments` - JSON value containing the tool's arguments
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    /// use serde_json::json;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let transport = StdioTransport::new();
+    /// let mut client = Client::new(transport);
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// // Call a simple tool with no arguments
     /// let result = client.call_tool(
     ///     "list_files".to_string(),
     ///     json!({})
@@ -397,20 +1176,141 @@ impl<T: Transport> Client<T> {
         &self,
         name: String,
         arguments: serde_json::Value,
+    ) -> Result<CallToolResult> {
+        self.call_tool_with_options(
+            CallToolRequest::new(name, arguments),
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Call a tool with a timeout overriding the configured `tools/call` timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    /// use serde_json::json;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let result = client
+    ///     .call_tool_with_timeout(
+    ///         "build_index".to_string(),
+    ///         json!({}),
+    ///         Duration::from_secs(600),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_tool_with_timeout(
+        &self,
+        name: String,
+        arguments: serde_json::Value,
+        timeout: std::time::Duration,
+    ) -> Result<CallToolResult> {
+        let options = RequestOptions::default().with_timeout(timeout);
+        self.call_tool_with_options(CallToolRequest::new(name, arguments), &options)
+            .await
+    }
+
+    /// Call a tool at most once, even if the request is delivered again.
+    ///
+    /// The call carries `idempotency_key` in its `_meta`. A server with
+    /// idempotency enabled answers any repeat of the key with the original
+    /// result instead of running the tool again, which makes retries and
+    /// replays after a reconnect safe for side-effecting tools. Use a fresh
+    /// key, such as a UUID, for every logical operation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    /// use serde_json::json;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let key = uuid::Uuid::new_v4().to_string();
+    /// let result = client
+    ///     .call_tool_idempotent("send_email".to_string(), json!({"to": "a@b.c"}), key)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_tool_idempotent(
+        &self,
+        name: String,
+        arguments: serde_json::Value,
+        idempotency_key: String,
+    ) -> Result<CallToolResult> {
+        let request = CallToolRequest::new(name, arguments).with_idempotency_key(idempotency_key);
+        self.call_tool_with_options(request, &RequestOptions::default())
+            .await
+    }
+
+    /// Call a tool with per-request options.
+    ///
+    /// Options set the timeout, a progress callback, whether progress
+    /// restarts the timeout, and a maximum total duration. Asking for
+    /// progress adds a progress token to the call unless it already has one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::shared::RequestOptions;
+    /// use pmcp::types::CallToolRequest;
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    /// use serde_json::json;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// // Give up after a minute of silence or an hour in total
+    /// let options = RequestOptions::default()
+    ///     .with_timeout(Duration::from_secs(60))
+    ///     .with_reset_timeout_on_progress(true)
+    ///     .with_max_total_timeout(Duration::from_secs(3600))
+    ///     .with_progress(|percent, _| println!("{}% done", percent));
+    /// let result = client
+    ///     .call_tool_with_options(CallToolRequest::new("reindex", json!({})), &options)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_tool_with_options(
+        &self,
+        request: CallToolRequest,
+        options: &RequestOptions,
     ) -> Result<CallToolResult> {
         self.ensure_initialized()?;
         self.assert_capability("tools", "tools/call")?;
 
-        let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest {
-            name,
-            arguments,
-        })));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
-        let response = self.send_request(request_id, request).await?;
+        let name = request.name.clone();
+        let request = Request::Client(Box::new(ClientRequest::CallTool(request)));
+        let request_id = self.next_request_id().await;
+        let response = self
+            .send_request_with_options(request_id, request, options)
+            .await?;
 
         match response.payload {
             crate::types::jsonrpc::ResponsePayload::Result(result) => {
-                serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
+                let result: CallToolResult =
+                    serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))?;
+                if self.validate_tool_output {
+                    let schema = self.output_schemas.read().await.get(&name).cloned();
+                    if let Some(schema) = schema {
+                        typed::check_output(&name, &schema, &result)?;
+                    }
+                }
+                Ok(result)
             },
             crate::types::jsonrpc::ResponsePayload::Error(error) => {
                 Err(Error::from_jsonrpc_error(error))
@@ -468,20 +1368,30 @@ impl<T: Transport> Client<T> {
         self.ensure_initialized()?;
         self.assert_capability("prompts", "prompts/list")?;
 
-        let request = Request::Client(Box::new(ClientRequest::ListPrompts(ListPromptsRequest {
-            cursor,
-        })));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
-        let response = self.send_request(request_id, request).await?;
+        let result = self
+            .list_request(
+                ListKind::Prompts,
+                cursor.clone(),
+                ClientRequest::ListPrompts(ListPromptsRequest { cursor }),
+            )
+            .await?;
+        serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
+    }
 
-        match response.payload {
-            crate::types::jsonrpc::ResponsePayload::Result(result) => {
-                serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
-            },
-            crate::types::jsonrpc::ResponsePayload::Error(error) => {
-                Err(Error::from_jsonrpc_error(error))
-            },
-        }
+    /// Stream all prompts, following pagination cursors.
+    ///
+    /// Pages are requested as the stream is polled, starting from the first
+    /// page.
+    pub fn list_prompts_stream(&self) -> impl Stream<Item = Result<PromptInfo>> + '_ {
+        paginate(move |cursor| async move {
+            let page = self.list_prompts(cursor).await?;
+            Ok((page.prompts, page.next_cursor))
+        })
+    }
+
+    /// List all prompts, following pagination cursors.
+    pub async fn list_prompts_all(&self) -> Result<Vec<PromptInfo>> {
+        self.list_prompts_stream().try_collect().await
     }
 
     /// Get a prompt.
@@ -552,7 +1462,7 @@ impl<T: Transport> Client<T> {
             name,
             arguments,
         })));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
+        let request_id = self.next_request_id().await;
         let response = self.send_request(request_id, request).await?;
 
         match response.payload {
@@ -609,20 +1519,30 @@ impl<T: Transport> Client<T> {
         self.ensure_initialized()?;
         self.assert_capability("resources", "resources/list")?;
 
-        let request = Request::Client(Box::new(ClientRequest::ListResources(
-            ListResourcesRequest { cursor },
-        )));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
-        let response = self.send_request(request_id, request).await?;
+        let result = self
+            .list_request(
+                ListKind::Resources,
+                cursor.clone(),
+                ClientRequest::ListResources(ListResourcesRequest { cursor }),
+            )
+            .await?;
+        serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
+    }
 
-        match response.payload {
-            crate::types::jsonrpc::ResponsePayload::Result(result) => {
-                serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
-            },
-            crate::types::jsonrpc::ResponsePayload::Error(error) => {
-                Err(Error::from_jsonrpc_error(error))
-            },
-        }
+    /// Stream all resources, following pagination cursors.
+    ///
+    /// Pages are requested as the stream is polled, starting from the first
+    /// page.
+    pub fn list_resources_stream(&self) -> impl Stream<Item = Result<ResourceInfo>> + '_ {
+        paginate(move |cursor| async move {
+            let page = self.list_resources(cursor).await?;
+            Ok((page.resources, page.next_cursor))
+        })
+    }
+
+    /// List all resources, following pagination cursors.
+    pub async fn list_resources_all(&self) -> Result<Vec<ResourceInfo>> {
+        self.list_resources_stream().try_collect().await
     }
 
     /// List resource templates.
@@ -669,20 +1589,32 @@ impl<T: Transport> Client<T> {
         self.ensure_initialized()?;
         self.assert_capability("resources", "resources/templates/list")?;
 
-        let request = Request::Client(Box::new(ClientRequest::ListResourceTemplates(
-            ListResourceTemplatesRequest { cursor },
-        )));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
-        let response = self.send_request(request_id, request).await?;
+        let result = self
+            .list_request(
+                ListKind::ResourceTemplates,
+                cursor.clone(),
+                ClientRequest::ListResourceTemplates(ListResourceTemplatesRequest { cursor }),
+            )
+            .await?;
+        serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
+    }
 
-        match response.payload {
-            crate::types::jsonrpc::ResponsePayload::Result(result) => {
-                serde_json::from_value(result).map_err(|e| Error::parse(e.to_string()))
-            },
-            crate::types::jsonrpc::ResponsePayload::Error(error) => {
-                Err(Error::from_jsonrpc_error(error))
-            },
-        }
+    /// Stream all resource templates, following pagination cursors.
+    ///
+    /// Pages are requested as the stream is polled, starting from the first
+    /// page.
+    pub fn list_resource_templates_stream(
+        &self,
+    ) -> impl Stream<Item = Result<ResourceTemplate>> + '_ {
+        paginate(move |cursor| async move {
+            let page = self.list_resource_templates(cursor).await?;
+            Ok((page.resource_templates, page.next_cursor))
+        })
+    }
+
+    /// List all resource templates, following pagination cursors.
+    pub async fn list_resource_templates_all(&self) -> Result<Vec<ResourceTemplate>> {
+        self.list_resource_templates_stream().try_collect().await
     }
 
     /// Read a resource.
@@ -729,14 +1661,247 @@ impl<T: Transport> Client<T> {
     /// - The resource URI doesn't exist
     /// - Access to the resource is denied
     /// - Network or protocol errors occur
+    ///
+    /// # Caching
+    ///
+    /// When the server tags a result with an entity tag, the client keeps a
+    /// copy and sends the tag with the next read of the same URI. If the
+    /// server replies that the resource is unchanged, the cached copy is
+    /// returned instead. Use [`clear_resource_cache`](Self::clear_resource_cache)
+    /// to drop cached copies.
     pub async fn read_resource(&self, uri: String) -> Result<ReadResourceResult> {
         self.ensure_initialized()?;
         self.assert_capability("resources", "resources/read")?;
 
-        let request = Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceRequest {
-            uri,
-        })));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
+        let cached = self.resource_cache.read().await.get(&uri).cloned();
+        let mut params = ReadResourceRequest::new(uri.clone());
+        if let Some(etag) = cached.as_ref().and_then(ReadResourceResult::etag) {
+            params = params.with_if_none_match(etag);
+        }
+
+        let result = self.send_read_resource(params).await?;
+
+        if result.is_not_modified() {
+            return cached.ok_or_else(|| {
+                Error::protocol(
+                    crate::ErrorCode::INTERNAL_ERROR,
+                    format!(
+                        "Server reported '{}' as unchanged but no copy is cached",
+                        uri
+                    ),
+                )
+            });
+        }
+
+        if result.etag().is_some() {
+            self.resource_cache
+                .write()
+                .await
+                .insert(uri, result.clone());
+        } else if cached.is_some() {
+            self.resource_cache.write().await.remove(&uri);
+        }
+        Ok(result)
+    }
+
+    /// Drop all resource contents cached for conditional reads.
+    pub async fn clear_resource_cache(&self) {
+        self.resource_cache.write().await.clear();
+    }
+
+    /// Stream the contents of a resource.
+    ///
+    /// The resource is fetched in chunks of [`RESOURCE_STREAM_CHUNK_SIZE`]
+    /// using chunked `resources/read` requests, so large resources can be
+    /// piped to disk or a parser without holding them in memory. Text
+    /// contents are yielded as UTF-8 bytes and binary contents are decoded
+    /// from base64. Servers without chunked read support return the whole
+    /// resource in the first chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let mut reader = client.read_resource_stream("file://large.log".to_string());
+    /// let mut file = tokio::fs::File::create("large.log").await?;
+    /// tokio::io::copy(&mut reader, &mut file).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_resource_stream(&self, uri: String) -> impl tokio::io::AsyncRead + Send + '_ {
+        let chunks = futures::stream::try_unfold(Some(0u64), move |offset| {
+            let uri = uri.clone();
+            async move {
+                let Some(offset) = offset else {
+                    return Ok(None);
+                };
+                let chunk = self
+                    .read_resource_chunk(uri, offset, RESOURCE_STREAM_CHUNK_SIZE)
+                    .await
+                    .map_err(std::io::Error::other)?;
+                Ok::<_, std::io::Error>(Some((std::io::Cursor::new(chunk.bytes), chunk.next)))
+            }
+        });
+        tokio_util::io::StreamReader::new(Box::pin(chunks))
+    }
+
+    /// Download a resource to a file.
+    ///
+    /// The resource is fetched with chunked reads (see
+    /// [`read_resource_stream`](Self::read_resource_stream)), binary contents
+    /// are decoded from base64, and the bytes are written to a temporary file
+    /// next to `path` that is renamed into place once the download completes.
+    /// A failed download never leaves a partial file at `path`.
+    ///
+    /// Returns the number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let size = client
+    ///     .download_resource("file://images/logo.png".to_string(), "logo.png")
+    ///     .await?;
+    /// println!("Downloaded {} bytes", size);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn download_resource(
+        &self,
+        uri: String,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<u64> {
+        self.download_resource_with_progress(uri, path, |_, _| {})
+            .await
+    }
+
+    /// Download a resource to a file, reporting progress after each chunk.
+    ///
+    /// `progress` is called with the number of bytes written so far and the
+    /// expected total size, if the server reported one. Binary sizes are
+    /// estimated from the base64 length and may overshoot by up to two bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// client
+    ///     .download_resource_with_progress(
+    ///         "file://data/export.csv".to_string(),
+    ///         "export.csv",
+    ///         |written, total| match total {
+    ///             Some(total) => println!("{}/{} bytes", written, total),
+    ///             None => println!("{} bytes", written),
+    ///         },
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn download_resource_with_progress<F>(
+        &self,
+        uri: String,
+        path: impl AsRef<std::path::Path>,
+        mut progress: F,
+    ) -> Result<u64>
+    where
+        F: FnMut(u64, Option<u64>) + Send,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| {
+                Error::invalid_params(format!("Invalid download path '{}'", path.display()))
+            })?
+            .to_string_lossy();
+        let temp_path = path.with_file_name(format!(".{}.{}.part", file_name, Uuid::new_v4()));
+
+        let download = async {
+            let mut file = tokio::fs::File::create(&temp_path).await?;
+            let mut written = 0u64;
+            let mut offset = Some(0u64);
+            while let Some(current) = offset {
+                let chunk = self
+                    .read_resource_chunk(uri.clone(), current, RESOURCE_STREAM_CHUNK_SIZE)
+                    .await?;
+                file.write_all(&chunk.bytes).await?;
+                written += chunk.bytes.len() as u64;
+                progress(written, chunk.total);
+                offset = chunk.next;
+            }
+            file.sync_all().await?;
+            drop(file);
+            tokio::fs::rename(&temp_path, path).await?;
+            Ok(written)
+        };
+
+        let result: Result<u64> = download.await;
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+        }
+        result
+    }
+
+    /// Read one chunk of a resource and decode its bytes.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn read_resource_chunk(
+        &self,
+        uri: String,
+        offset: u64,
+        length: u64,
+    ) -> Result<FetchedChunk> {
+        self.ensure_initialized()?;
+        self.assert_capability("resources", "resources/read")?;
+
+        let params = ReadResourceRequest::new(uri).with_chunk(offset, length);
+        let result = self.send_read_resource(params).await?;
+
+        let mut bytes = Vec::new();
+        for content in &result.contents {
+            bytes.extend(content_bytes(content)?);
+        }
+
+        let chunk = result.chunk();
+        let next = chunk.as_ref().and_then(|chunk| {
+            let next = chunk.offset + chunk.length;
+            (chunk.length > 0 && chunk.total.is_some_and(|total| next < total)).then_some(next)
+        });
+        let binary = matches!(
+            result.contents.first(),
+            Some(Content::Image { .. } | Content::Audio { .. })
+        );
+        let total = match chunk.and_then(|chunk| chunk.total) {
+            // Chunk totals count base64 characters for binary contents.
+            Some(total) if binary => Some(total / 4 * 3),
+            Some(total) => Some(total),
+            None => Some(bytes.len() as u64),
+        };
+        Ok(FetchedChunk { bytes, next, total })
+    }
+
+    async fn send_read_resource(&self, params: ReadResourceRequest) -> Result<ReadResourceResult> {
+        let request = Request::Client(Box::new(ClientRequest::ReadResource(params)));
+        let request_id = self.next_request_id().await;
         let response = self.send_request(request_id, request).await?;
 
         match response.payload {
@@ -786,27 +1951,19 @@ impl<T: Transport> Client<T> {
     /// - Network or protocol errors occur
     pub async fn subscribe_resource(&self, uri: String) -> Result<()> {
         self.ensure_initialized()?;
-        self.assert_capability("resources", "resources/subscribe")?;
-
-        // Check if server supports subscriptions
-        if let Some(resources) = &self
-            .server_capabilities
-            .as_ref()
-            .and_then(|c| c.resources.as_ref())
-        {
-            if !resources.subscribe.unwrap_or(false) {
-                return Err(Error::capability(
-                    "Server does not support resource subscriptions",
-                ));
-            }
-        }
+        self.assert_capability("resources.subscribe", "resources/subscribe")?;
 
-        let request = Request::Client(Box::new(ClientRequest::Subscribe(SubscribeRequest { uri })));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
+        let request = Request::Client(Box::new(ClientRequest::Subscribe(SubscribeRequest {
+            uri: uri.clone(),
+        })));
+        let request_id = self.next_request_id().await;
         let response = self.send_request(request_id, request).await?;
 
         match response.payload {
-            crate::types::jsonrpc::ResponsePayload::Result(_) => Ok(()),
+            crate::types::jsonrpc::ResponsePayload::Result(_) => {
+                self.subscriptions.write().await.insert(uri);
+                Ok(())
+            },
             crate::types::jsonrpc::ResponsePayload::Error(error) => {
                 Err(Error::from_jsonrpc_error(error))
             },
@@ -850,16 +2007,19 @@ impl<T: Transport> Client<T> {
     /// - Network or protocol errors occur
     pub async fn unsubscribe_resource(&self, uri: String) -> Result<()> {
         self.ensure_initialized()?;
-        self.assert_capability("resources", "resources/unsubscribe")?;
+        self.assert_capability("resources.subscribe", "resources/unsubscribe")?;
 
         let request = Request::Client(Box::new(ClientRequest::Unsubscribe(UnsubscribeRequest {
-            uri,
+            uri: uri.clone(),
         })));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
+        let request_id = self.next_request_id().await;
         let response = self.send_request(request_id, request).await?;
 
         match response.payload {
-            crate::types::jsonrpc::ResponsePayload::Result(_) => Ok(()),
+            crate::types::jsonrpc::ResponsePayload::Result(_) => {
+                self.subscriptions.write().await.remove(&uri);
+                Ok(())
+            },
             crate::types::jsonrpc::ResponsePayload::Error(error) => {
                 Err(Error::from_jsonrpc_error(error))
             },
@@ -916,7 +2076,7 @@ impl<T: Transport> Client<T> {
         self.assert_capability("completions", "completion/complete")?;
 
         let request = Request::Client(Box::new(ClientRequest::Complete(params)));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
+        let request_id = self.next_request_id().await;
         let response = self.send_request(request_id, request).await?;
 
         match response.payload {
@@ -997,7 +2157,7 @@ impl<T: Transport> Client<T> {
         self.assert_capability("sampling", "sampling/createMessage")?;
 
         let request = Request::Client(Box::new(ClientRequest::CreateMessage(params)));
-        let request_id = RequestId::String(Uuid::new_v4().to_string());
+        let request_id = self.next_request_id().await;
         let response = self.send_request(request_id, request).await?;
 
         match response.payload {
@@ -1155,10 +2315,45 @@ impl<T: Transport> Client<T> {
     /// Returns an error if:
     /// - Network or protocol errors occur while sending the cancellation
     pub async fn cancel_request(&self, request_id: &RequestId) -> Result<()> {
-        // Send cancellation notification
+        self.cancel_request_with_reason(request_id, "User requested cancellation")
+            .await
+    }
+
+    /// Cancel a request, telling the server why.
+    ///
+    /// Same as [`cancel_request`](Self::cancel_request), with `reason` sent in
+    /// the `notifications/cancelled` notification. The server cancels the
+    /// handler's cancellation token, so handlers that check it stop early.
+    /// Requests that time out are cancelled this way automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities, RequestId};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let request_id = RequestId::String("long-operation-123".to_string());
+    /// client
+    ///     .cancel_request_with_reason(&request_id, "No longer needed")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification cannot be sent.
+    pub async fn cancel_request_with_reason(
+        &self,
+        request_id: &RequestId,
+        reason: impl Into<String>,
+    ) -> Result<()> {
         self.send_notification(Notification::Cancelled(CancelledNotification {
             request_id: request_id.clone(),
-            reason: Some("User requested cancellation".to_string()),
+            reason: Some(reason.into()),
         }))
         .await?;
 
@@ -1220,33 +2415,55 @@ impl<T: Transport> Client<T> {
         }
     }
 
-    /// Assert that the server has a specific capability.
-    fn assert_capability(&self, capability: &str, method: &str) -> Result<()> {
-        let has_capability = match capability {
-            "tools" => self
-                .server_capabilities
-                .as_ref()
-                .is_some_and(|c| c.tools.is_some()),
-            "prompts" => self
-                .server_capabilities
-                .as_ref()
-                .is_some_and(|c| c.prompts.is_some()),
-            "resources" => self
-                .server_capabilities
-                .as_ref()
-                .is_some_and(|c| c.resources.is_some()),
-            "logging" => self
-                .server_capabilities
-                .as_ref()
-                .is_some_and(|c| c.logging.is_some()),
-            "completions" => self
-                .server_capabilities
+    /// Check whether the server advertised the capability a method needs.
+    ///
+    /// Methods that need no capability (such as `ping`) are always
+    /// supported. Returns `false` before initialization.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, ClientCapabilities, StdioTransport};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// if client.supports("resources/subscribe") {
+    ///     client.subscribe_resource("file:///config.json".to_string()).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn supports(&self, method: &str) -> bool {
+        self.initialized
+            && required_capability(method).is_none_or(|capability| self.has_capability(capability))
+    }
+
+    /// Whether the server advertised a capability.
+    fn has_capability(&self, capability: &str) -> bool {
+        let Some(caps) = self.server_capabilities.as_ref() else {
+            return false;
+        };
+        match capability {
+            "tools" => caps.tools.is_some(),
+            "prompts" => caps.prompts.is_some(),
+            "resources" => caps.resources.is_some(),
+            "resources.subscribe" => caps
+                .resources
                 .as_ref()
-                .is_some_and(|c| c.completions.is_some()),
+                .and_then(|r| r.subscribe)
+                .unwrap_or(false),
+            "logging" => caps.logging.is_some(),
+            "completions" => caps.completions.is_some(),
+            "sampling" => caps.sampling.is_some(),
             _ => false,
-        };
+        }
+    }
 
-        if has_capability {
+    /// Assert that the server has a specific capability.
+    fn assert_capability(&self, capability: &str, method: &str) -> Result<()> {
+        if self.has_capability(capability) {
             Ok(())
         } else {
             Err(Error::capability(format!(
@@ -1256,11 +2473,360 @@ impl<T: Transport> Client<T> {
         }
     }
 
+    /// Assert that the server supports a method before sending it.
+    fn assert_method_supported(&self, method: &str) -> Result<()> {
+        match required_capability(method) {
+            Some(capability) if self.initialized => self.assert_capability(capability, method),
+            _ => Ok(()),
+        }
+    }
+
+    /// Send a list request, answering it from the list cache when enabled.
+    async fn list_request(
+        &self,
+        kind: ListKind,
+        cursor: Option<String>,
+        request: ClientRequest,
+    ) -> Result<serde_json::Value> {
+        if let Some(cached) = self
+            .list_cache
+            .as_ref()
+            .and_then(|cache| cache.get(kind, cursor.as_deref()))
+        {
+            return Ok(cached);
+        }
+
+        let request_id = self.next_request_id().await;
+        let response = self
+            .send_request(request_id, Request::Client(Box::new(request)))
+            .await?;
+
+        match response.payload {
+            crate::types::jsonrpc::ResponsePayload::Result(result) => {
+                if let Some(cache) = &self.list_cache {
+                    cache.insert(kind, cursor, result.clone());
+                }
+                Ok(result)
+            },
+            crate::types::jsonrpc::ResponsePayload::Error(error) => {
+                Err(Error::from_jsonrpc_error(error))
+            },
+        }
+    }
+
+    /// Apply a notification received while waiting for a response, and
+    /// deliver it to its subscribers.
+    async fn handle_server_notification(&self, notification: Notification) {
+        let notification = match notification {
+            Notification::Server(notification) => notification,
+            Notification::Progress(progress) => {
+                crate::types::ServerNotification::Progress(progress)
+            },
+            _ => return,
+        };
+        if let Some(cache) = &self.list_cache {
+            for kind in ListKind::changed_by(&notification) {
+                cache.invalidate(*kind);
+            }
+        }
+        if let (crate::types::ServerNotification::LogMessage(message), Some(handler)) =
+            (&notification, &self.log_handler)
+        {
+            handler(message);
+        }
+        self.notifications.dispatch(notification).await;
+    }
+
+    /// Generate the id for the next outgoing request.
+    async fn next_request_id(&self) -> RequestId {
+        self.protocol.read().await.next_request_id()
+    }
+
     /// Send a request and wait for response.
+    ///
+    /// Uses the timeout configured for the request's method in
+    /// [`ProtocolOptions`].
     async fn send_request(
         &self,
         request_id: RequestId,
         request: Request,
+    ) -> Result<crate::types::JSONRPCResponse> {
+        self.send_request_with_options(request_id, request, &RequestOptions::default())
+            .await
+    }
+
+    /// Send any client request with per-request options.
+    ///
+    /// For requests without a dedicated `*_with_options` method. Returns the
+    /// raw result; JSON-RPC errors are returned as errors. Progress can only
+    /// be requested for tool calls.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::shared::RequestOptions;
+    /// use pmcp::types::{ClientRequest, ReadResourceRequest};
+    /// use pmcp::{Client, StdioTransport, ClientCapabilities};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    ///
+    /// let request = ClientRequest::ReadResource(ReadResourceRequest::new("file://huge.csv"));
+    /// let options = RequestOptions::default().with_timeout(Duration::from_secs(120));
+    /// let result = client.request_with_options(request, &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn request_with_options(
+        &self,
+        request: ClientRequest,
+        options: &RequestOptions,
+    ) -> Result<serde_json::Value> {
+        self.ensure_initialized()?;
+        let request_id = self.next_request_id().await;
+        let response = self
+            .send_request_with_options(request_id, Request::Client(Box::new(request)), options)
+            .await?;
+
+        match response.payload {
+            crate::types::jsonrpc::ResponsePayload::Result(result) => Ok(result),
+            crate::types::jsonrpc::ResponsePayload::Error(error) => {
+                Err(Error::from_jsonrpc_error(error))
+            },
+        }
+    }
+
+    /// Send a request with per-request options and wait for response.
+    ///
+    /// With automatic reconnection enabled, a lost connection is restored
+    /// first.
+    async fn send_request_with_options(
+        &self,
+        request_id: RequestId,
+        request: Request,
+        options: &RequestOptions,
+    ) -> Result<crate::types::JSONRPCResponse> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(reconnector) = self.auto_reconnect.as_deref().filter(|_| self.initialized) {
+            return self
+                .send_reconnecting(reconnector, request_id, request, options)
+                .await;
+        }
+
+        self.send_once(request_id, request, options).await
+    }
+
+    /// Send a request, restoring the connection if it is or gets lost.
+    ///
+    /// Whether a request that was awaiting its response is resent is up to
+    /// the reconnector's [`PendingRequests`](auto_reconnect::PendingRequests)
+    /// policy.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn send_reconnecting(
+        &self,
+        reconnector: &auto_reconnect::Reconnector<T>,
+        request_id: RequestId,
+        request: Request,
+        options: &RequestOptions,
+    ) -> Result<crate::types::JSONRPCResponse> {
+        let generation = reconnector.generation();
+        if !self.transport.read().await.is_connected() {
+            // Nothing was sent yet, so the request is safe to send afterwards
+            self.recover(reconnector, generation).await?;
+            return self.send_once(request_id, request, options).await;
+        }
+
+        match self
+            .send_once(request_id.clone(), request.clone(), options)
+            .await
+        {
+            Err(e) if e.is_fatal_for_connection() => {
+                tracing::debug!("Connection lost, reconnecting: {}", e);
+                let restored = self.recover(reconnector, generation).await;
+                if !reconnector.pending.reissues(&request) {
+                    if let Err(e) = restored {
+                        tracing::warn!("Failed to restore the connection: {}", e);
+                    }
+                    return Err(Error::RequestInterrupted(
+                        crate::shared::request_method(&request).to_string(),
+                    ));
+                }
+                restored?;
+                self.send_once(request_id, request, options).await
+            },
+            result => result,
+        }
+    }
+
+    /// Open a new connection and restore the session on it.
+    ///
+    /// Does nothing if the connection was already restored since
+    /// `generation`, by another request of this client or its clones.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn recover(
+        &self,
+        reconnector: &auto_reconnect::Reconnector<T>,
+        generation: u64,
+    ) -> Result<()> {
+        let _restoring = reconnector.restoring.lock().await;
+        if reconnector.generation() != generation {
+            return Ok(());
+        }
+        let capabilities = self
+            .capabilities
+            .clone()
+            .ok_or_else(|| Error::invalid_state("Client not initialized"))?;
+        let previous = self.server_capabilities.clone().unwrap_or_default();
+
+        let transport = reconnector.connect().await?;
+        *self.transport.write().await = transport;
+        self.active_requests.write().await.clear();
+        self.clear_list_cache();
+
+        let result = self.handshake(capabilities).await?;
+        self.restore_session(&previous, &result).await?;
+        reconnector.restored();
+        Ok(())
+    }
+
+    /// Send a request once, without reconnecting.
+    async fn send_once(
+        &self,
+        request_id: RequestId,
+        request: Request,
+        options: &RequestOptions,
+    ) -> Result<crate::types::JSONRPCResponse> {
+        #[cfg(feature = "opentelemetry")]
+        {
+            use crate::shared::telemetry::{RequestSpan, Side};
+            use opentelemetry::context::FutureExt as _;
+
+            let mut request = request;
+            let transport = self.transport.read().await.transport_type();
+            let span = RequestSpan::start(
+                Side::Client,
+                crate::shared::request_method(&request),
+                &request_id,
+                transport,
+                &opentelemetry::Context::current(),
+            );
+            span.inject_into(&mut request);
+            let result = self
+                .deliver_request(request_id, request, options)
+                .with_context(span.context().clone())
+                .await;
+            span.finish(result.as_ref());
+            result
+        }
+
+        #[cfg(not(feature = "opentelemetry"))]
+        self.deliver_request(request_id, request, options).await
+    }
+
+    /// Deliver a request and wait for its response.
+    ///
+    /// The timeout restarts on progress if the options ask for it, but never
+    /// extends past the maximum total timeout.
+    async fn deliver_request(
+        &self,
+        request_id: RequestId,
+        mut request: Request,
+        options: &RequestOptions,
+    ) -> Result<crate::types::JSONRPCResponse> {
+        self.assert_method_supported(crate::shared::request_method(&request))?;
+
+        let timeout = self
+            .protocol
+            .read()
+            .await
+            .request_timeout(crate::shared::request_method(&request), options);
+        let progress_token = if options.wants_progress() {
+            request_progress(&mut request, &request_id)
+        } else {
+            None
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let started = tokio::time::Instant::now();
+            let max_total = options.max_total_timeout.map(|max| started + max);
+            let cap = |at: tokio::time::Instant| max_total.map_or(at, |max| at.min(max));
+            let deadline = parking_lot::Mutex::new(cap(started + timeout));
+
+            let on_progress = |progress: &ProgressNotification| {
+                report_progress(options, progress);
+                if options.reset_timeout_on_progress {
+                    *deadline.lock() = cap(tokio::time::Instant::now() + timeout);
+                }
+            };
+            let exchange = self.exchange_request(
+                request_id.clone(),
+                request,
+                progress_token
+                    .as_ref()
+                    .map(|token| (token, &on_progress as _)),
+            );
+            let expired = async {
+                loop {
+                    let at = *deadline.lock();
+                    tokio::time::sleep_until(at).await;
+                    if *deadline.lock() <= tokio::time::Instant::now() {
+                        break;
+                    }
+                }
+            };
+
+            tokio::select! {
+                result = exchange => result,
+                () = expired => {
+                    self.active_requests.write().await.remove(&request_id);
+                    // Let the server stop working on a request nobody awaits
+                    if let Err(e) = self
+                        .cancel_request_with_reason(&request_id, "Request timed out")
+                        .await
+                    {
+                        tracing::debug!("Failed to cancel timed out request: {}", e);
+                    }
+                    let limit = match options.max_total_timeout {
+                        Some(max) if started.elapsed() >= max => max,
+                        _ => timeout,
+                    };
+                    Err(Error::timeout(
+                        u64::try_from(limit.as_millis()).unwrap_or(u64::MAX),
+                    ))
+                },
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            // Timers are not available to the client on wasm; rely on the
+            // transport's own timeouts.
+            let _ = timeout;
+            let on_progress = |progress: &ProgressNotification| report_progress(options, progress);
+            self.exchange_request(
+                request_id,
+                request,
+                progress_token
+                    .as_ref()
+                    .map(|token| (token, &on_progress as _)),
+            )
+            .await
+        }
+    }
+
+    /// Send a request through the transport and receive its response.
+    ///
+    /// Progress notifications under `progress`'s token are passed to its
+    /// callback; every notification is applied to the client and delivered
+    /// to its subscribers.
+    async fn exchange_request(
+        &self,
+        request_id: RequestId,
+        request: Request,
+        progress: Option<ProgressRoute<'_>>,
     ) -> Result<crate::types::JSONRPCResponse> {
         // Track request for cancellation
         let (cancel_tx, _cancel_rx) = oneshot::channel();
@@ -1278,8 +2844,31 @@ impl<T: Transport> Client<T> {
         self.transport.write().await.send(message).await?;
 
         // Wait for response (this would be implemented with proper response routing)
-        // For now, receive next message and assume it's our response
-        let response_message = self.transport.write().await.receive().await?;
+        // For now, receive messages until a response arrives and assume it's ours;
+        // notifications sent in the meantime are applied along the way
+        let response_message = loop {
+            let message = self.transport.write().await.receive().await?;
+            match message {
+                crate::types::TransportMessage::Request {
+                    id,
+                    request: Request::Server(request),
+                } => self.answer_server_request(id, request).await?,
+                crate::types::TransportMessage::Notification(notification) => {
+                    if let (
+                        Notification::Progress(update)
+                        | Notification::Server(crate::types::ServerNotification::Progress(update)),
+                        Some((token, on_progress)),
+                    ) = (&notification, progress)
+                    {
+                        if &update.progress_token == token {
+                            on_progress(update);
+                        }
+                    }
+                    self.handle_server_notification(notification).await;
+                },
+                message => break message,
+            }
+        };
 
         // Remove from active requests
         self.active_requests.write().await.remove(&request_id);
@@ -1292,6 +2881,36 @@ impl<T: Transport> Client<T> {
         }
     }
 
+    /// Answer a request the server sent while we wait for a response.
+    async fn answer_server_request(
+        &self,
+        id: RequestId,
+        request: Box<crate::types::ServerRequest>,
+    ) -> Result<()> {
+        let response = match (*request, &self.elicitation_handler) {
+            (crate::types::ServerRequest::Elicit(params), Some(handler)) => {
+                match handler.elicit(*params).await.and_then(|result| {
+                    serde_json::to_value(result).map_err(|e| Error::internal(e.to_string()))
+                }) {
+                    Ok(result) => crate::types::JSONRPCResponse::success(id, result),
+                    Err(e) => crate::types::JSONRPCResponse::error(id, e.into()),
+                }
+            },
+            (request, _) => crate::types::JSONRPCResponse::error(
+                id,
+                Error::method_not_found(crate::shared::protocol_helpers::request_method(
+                    &Request::Server(Box::new(request)),
+                ))
+                .into(),
+            ),
+        };
+        self.transport
+            .write()
+            .await
+            .send(crate::types::TransportMessage::Response(response))
+            .await
+    }
+
     /// Send a notification.
     async fn send_notification(&self, notification: Notification) -> Result<()> {
         let message = crate::types::TransportMessage::Notification(notification);
@@ -1335,6 +2954,13 @@ impl<T: Transport> Client<T> {
 pub struct ClientBuilder<T: Transport> {
     transport: T,
     options: ProtocolOptions,
+    id_generator: Option<Arc<dyn RequestIdGenerator>>,
+    offline_queue: Option<OfflineQueueConfig>,
+    list_cache: Option<ListCacheConfig>,
+    log_handler: Option<LogHandler>,
+    elicitation_handler: Option<Arc<dyn elicitation::ElicitationHandler>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    reconnect: Option<auto_reconnect::AutoReconnect<T>>,
 }
 
 impl<T: Transport> std::fmt::Debug for ClientBuilder<T> {
@@ -1342,6 +2968,11 @@ impl<T: Transport> std::fmt::Debug for ClientBuilder<T> {
         f.debug_struct("ClientBuilder")
             .field("transport", &"<Transport>")
             .field("options", &self.options)
+            .field("id_generator", &self.id_generator)
+            .field("offline_queue", &self.offline_queue)
+            .field("list_cache", &self.list_cache)
+            .field("log_handler", &self.log_handler.is_some())
+            .field("elicitation_handler", &self.elicitation_handler.is_some())
             .finish()
     }
 }
@@ -1352,6 +2983,13 @@ impl<T: Transport> ClientBuilder<T> {
         Self {
             transport,
             options: ProtocolOptions::default(),
+            id_generator: None,
+            offline_queue: None,
+            list_cache: None,
+            log_handler: None,
+            elicitation_handler: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            reconnect: None,
         }
     }
 
@@ -1367,16 +3005,134 @@ impl<T: Transport> ClientBuilder<T> {
         self
     }
 
+    /// Set the generator for outgoing request ids.
+    ///
+    /// Defaults to random UUIDs.
+    pub fn request_id_generator(mut self, generator: impl RequestIdGenerator + 'static) -> Self {
+        self.id_generator = Some(Arc::new(generator));
+        self
+    }
+
+    /// Set the timeout for requests without a method-specific timeout.
+    pub fn default_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.options.default_timeout = timeout;
+        self
+    }
+
+    /// Set the timeout for a specific method, e.g. `tools/call`.
+    pub fn method_timeout(
+        mut self,
+        method: impl Into<String>,
+        timeout: std::time::Duration,
+    ) -> Self {
+        self.options.method_timeouts.insert(method.into(), timeout);
+        self
+    }
+
+    /// Queue requests submitted with [`Client::send_or_queue`] while the
+    /// transport is disconnected.
+    pub fn offline_queue(mut self, config: OfflineQueueConfig) -> Self {
+        self.offline_queue = Some(config);
+        self
+    }
+
+    /// Cache list results, invalidated by the server's `list_changed`
+    /// notifications.
+    pub fn list_cache(mut self, config: ListCacheConfig) -> Self {
+        self.list_cache = Some(config);
+        self
+    }
+
+    /// Pass the server's log messages to `handler`; see [`Client::on_log`].
+    pub fn on_log(
+        mut self,
+        handler: impl Fn(&crate::types::protocol::LogMessageParams) + Send + Sync + 'static,
+    ) -> Self {
+        self.log_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Answer the server's `elicitation/create` requests with `handler`;
+    /// see [`Client::on_elicitation`].
+    pub fn on_elicitation(
+        mut self,
+        handler: impl elicitation::ElicitationHandler + 'static,
+    ) -> Self {
+        self.elicitation_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Reconnect automatically when the connection is lost; see
+    /// [`Client::with_reconnect`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reconnect(mut self, config: auto_reconnect::AutoReconnect<T>) -> Self {
+        self.reconnect = Some(config);
+        self
+    }
+
     /// Build the client.
     pub fn build(self) -> Client<T> {
-        Client::with_options(
+        let mut protocol = Protocol::new(self.options.clone());
+        if let Some(generator) = self.id_generator {
+            protocol.set_id_generator(generator);
+        }
+
+        let mut client = Client::with_options(
             self.transport,
             Implementation {
                 name: "pmcp-client".to_string(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
             },
             self.options,
-        )
+        );
+        client.protocol = Arc::new(RwLock::new(protocol));
+        client.log_handler = self.log_handler;
+        client.elicitation_handler = self.elicitation_handler;
+        if let Some(config) = self.list_cache {
+            client = client.with_list_cache(config);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(config) = self.reconnect {
+            client = client.with_reconnect(config);
+        }
+        match self.offline_queue {
+            Some(config) => client.with_offline_queue(config),
+            None => client,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Transport + Clone + 'static> Client<crate::shared::connection_pool::PooledTransport<T>> {
+    /// Create a client that spreads its requests over a connection pool.
+    ///
+    /// Each request goes to the connection chosen by the pool's
+    /// [`LoadBalanceStrategy`](crate::shared::connection_pool::LoadBalanceStrategy),
+    /// skipping unhealthy ones, and [`initialize`](Self::initialize) is
+    /// replayed on every connection before its first request. A request
+    /// whose connection fails before answering is resent on another one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::shared::connection_pool::{ConnectionPool, ConnectionPoolConfig};
+    /// use pmcp::shared::Transport;
+    /// use pmcp::{Client, ClientCapabilities};
+    ///
+    /// # async fn example<T: Transport + Clone + 'static>(
+    /// #     connect: impl Fn() -> pmcp::Result<T> + Send + Sync + 'static,
+    /// # ) -> pmcp::Result<()> {
+    /// let mut pool = ConnectionPool::new(ConnectionPoolConfig::default());
+    /// pool.start(connect).await?;
+    ///
+    /// let mut client = Client::with_pool(pool);
+    /// client.initialize(ClientCapabilities::default()).await?;
+    /// let tools = client.list_tools(None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_pool(pool: crate::shared::connection_pool::ConnectionPool<T>) -> Self {
+        Self::new(crate::shared::connection_pool::PooledTransport::new(pool))
     }
 }
 
@@ -1388,11 +3144,24 @@ impl<T: Transport> Clone for Client<T> {
             capabilities: self.capabilities.clone(),
             server_capabilities: self.server_capabilities.clone(),
             server_version: self.server_version.clone(),
+            protocol_version: self.protocol_version.clone(),
             instructions: self.instructions.clone(),
             initialized: self.initialized,
             info: self.info.clone(),
             notification_tx: self.notification_tx.clone(),
             active_requests: self.active_requests.clone(),
+            resource_cache: self.resource_cache.clone(),
+            subscriptions: self.subscriptions.clone(),
+            offline_queue: self.offline_queue.clone(),
+            list_cache: self.list_cache.clone(),
+            tool_schemas: self.tool_schemas.clone(),
+            output_schemas: self.output_schemas.clone(),
+            validate_tool_output: self.validate_tool_output,
+            log_handler: self.log_handler.clone(),
+            notifications: self.notifications.clone(),
+            elicitation_handler: self.elicitation_handler.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            auto_reconnect: self.auto_reconnect.clone(),
         }
     }
 }
@@ -1415,6 +3184,9 @@ mod tests {
     struct MockTransport {
         responses: Arc<Mutex<Vec<TransportMessage>>>,
         sent_messages: Arc<Mutex<Vec<TransportMessage>>>,
+        connected: Arc<std::sync::atomic::AtomicBool>,
+        /// Makes `receive` fail as if the connection dropped
+        drop_on_receive: Arc<std::sync::atomic::AtomicBool>,
     }
 
     impl MockTransport {
@@ -1422,6 +3194,8 @@ mod tests {
             Self {
                 responses: Arc::new(Mutex::new(Vec::new())),
                 sent_messages: Arc::new(Mutex::new(Vec::new())),
+                connected: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+                drop_on_receive: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             }
         }
 
@@ -1429,6 +3203,8 @@ mod tests {
             Self {
                 responses: Arc::new(Mutex::new(responses)),
                 sent_messages: Arc::new(Mutex::new(Vec::new())),
+                connected: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+                drop_on_receive: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             }
         }
 
@@ -1446,6 +3222,12 @@ mod tests {
         }
 
         async fn receive(&mut self) -> Result<TransportMessage> {
+            if self
+                .drop_on_receive
+                .load(std::sync::atomic::Ordering::SeqCst)
+            {
+                return Err(crate::error::TransportError::ConnectionClosed.into());
+            }
             self.responses
                 .lock()
                 .unwrap()
@@ -1456,6 +3238,10 @@ mod tests {
         async fn close(&mut self) -> Result<()> {
             Ok(())
         }
+
+        fn is_connected(&self) -> bool {
+            self.connected.load(std::sync::atomic::Ordering::SeqCst)
+        }
     }
 
     #[test]
@@ -1814,4 +3600,1007 @@ mod tests {
         let contents = result.unwrap();
         assert_eq!(contents.contents.len(), 1);
     }
+
+    fn init_response_with(id: i64, capabilities: serde_json::Value) -> TransportMessage {
+        TransportMessage::Response(JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::from(id),
+            payload: ResponsePayload::Result(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": capabilities,
+                "serverInfo": {
+                    "name": "test-server",
+                    "version": "1.0.0"
+                }
+            })),
+        })
+    }
+
+    fn empty_response(id: i64) -> TransportMessage {
+        TransportMessage::Response(JSONRPCResponse::success(RequestId::from(id), json!({})))
+    }
+
+    #[tokio::test]
+    async fn test_capability_checks_fail_fast() {
+        let transport = MockTransport::with_responses(vec![init_response_with(
+            1,
+            json!({"tools": {}, "resources": {}, "sampling": {}}),
+        )]);
+        let sent = transport.sent_messages.clone();
+        let mut client = Client::new(transport);
+        assert!(!client.supports("ping"));
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+
+        assert!(client.supports("ping"));
+        assert!(client.supports("tools/call"));
+        assert!(client.supports("sampling/createMessage"));
+        assert!(client.supports("resources/read"));
+        assert!(!client.supports("resources/subscribe"));
+        assert!(!client.supports("prompts/list"));
+
+        let sent_before = sent.lock().unwrap().len();
+        let err = client
+            .subscribe_resource("test://x".to_string())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::UnsupportedCapability(ref m) if m.contains("resources.subscribe"))
+        );
+        let err = client.list_prompts(None).await.unwrap_err();
+        assert!(matches!(err, Error::UnsupportedCapability(ref m) if m.contains("prompts")));
+        assert_eq!(sent.lock().unwrap().len(), sent_before);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_restores_session() {
+        let capabilities = json!({"tools": {}, "resources": {"subscribe": true}});
+        let transport = MockTransport::with_responses(vec![
+            empty_response(2),
+            init_response_with(1, capabilities.clone()),
+        ]);
+        let mut client = Client::new(transport);
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+        client
+            .subscribe_resource("test://watched".to_string())
+            .await
+            .unwrap();
+
+        let replacement = MockTransport::with_responses(vec![
+            empty_response(4),
+            init_response_with(3, capabilities),
+        ]);
+        let sent = replacement.sent_messages.clone();
+        client.reconnect(replacement).await.unwrap();
+
+        let resubscribed = sent.lock().unwrap().iter().any(|message| {
+            matches!(
+                message,
+                TransportMessage::Request { request: Request::Client(request), .. }
+                    if matches!(&**request, ClientRequest::Subscribe(s) if s.uri == "test://watched")
+            )
+        });
+        assert!(resubscribed);
+    }
+
+    #[tokio::test]
+    async fn test_offline_requests_flush_on_reconnect() {
+        let transport = MockTransport::with_responses(vec![init_response_with(1, json!({}))]);
+        let connected = transport.connected.clone();
+        let mut client = Client::new(transport).with_offline_queue(OfflineQueueConfig::default());
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+
+        connected.store(false, std::sync::atomic::Ordering::SeqCst);
+        let pending = client.send_or_queue(ClientRequest::Ping).await.unwrap();
+        assert!(pending.is_queued());
+        assert_eq!(client.queued_requests(), 1);
+
+        let replacement = MockTransport::with_responses(vec![
+            empty_response(3),
+            init_response_with(2, json!({})),
+        ]);
+        let sent = replacement.sent_messages.clone();
+        client.reconnect(replacement).await.unwrap();
+
+        assert_eq!(client.queued_requests(), 0);
+        assert_eq!(pending.await.unwrap().id, RequestId::from(3i64));
+        let pinged = sent.lock().unwrap().iter().any(|message| {
+            matches!(
+                message,
+                TransportMessage::Request { request: Request::Client(request), .. }
+                    if matches!(&**request, ClientRequest::Ping)
+            )
+        });
+        assert!(pinged);
+    }
+
+    #[tokio::test]
+    async fn test_send_or_queue_requires_opt_in() {
+        let transport = MockTransport::with_responses(vec![init_response_with(1, json!({}))]);
+        let mut client = Client::new(transport);
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+        assert!(client.send_or_queue(ClientRequest::Ping).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_detects_capability_regression() {
+        let transport = MockTransport::with_responses(vec![init_response_with(
+            1,
+            json!({"tools": {}, "prompts": {}}),
+        )]);
+        let mut client = Client::new(transport);
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+
+        let replacement =
+            MockTransport::with_responses(vec![init_response_with(2, json!({"tools": {}}))]);
+        let err = client.reconnect(replacement).await.unwrap_err();
+        assert!(matches!(err, Error::UnsupportedCapability(ref m) if m.contains("prompts")));
+    }
+
+    /// Automatic reconnection handing out `replacements`, last first.
+    fn reconnect_to(
+        replacements: Vec<MockTransport>,
+    ) -> auto_reconnect::AutoReconnect<MockTransport> {
+        let replacements = Arc::new(Mutex::new(replacements));
+        auto_reconnect::AutoReconnect::new(move || {
+            let next = replacements.lock().unwrap().pop();
+            async move { next.ok_or_else(|| Error::internal("No more transports")) }
+        })
+        .with_backoff(crate::shared::ReconnectConfig {
+            initial_delay: std::time::Duration::from_millis(1),
+            max_retries: Some(1),
+            ..Default::default()
+        })
+    }
+
+    fn sent_methods(sent: &Mutex<Vec<TransportMessage>>) -> Vec<String> {
+        sent.lock()
+            .unwrap()
+            .iter()
+            .filter_map(|message| match message {
+                TransportMessage::Request { request, .. } => {
+                    Some(crate::shared::request_method(request).to_string())
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_auto_reconnect_restores_session_before_sending() {
+        let capabilities = json!({"tools": {}, "resources": {"subscribe": true}});
+        let transport = MockTransport::with_responses(vec![
+            empty_response(2),
+            init_response_with(1, capabilities.clone()),
+        ]);
+        let connected = transport.connected.clone();
+        let replacement = MockTransport::with_responses(vec![
+            empty_response(3),
+            empty_response(5),
+            init_response_with(4, capabilities),
+        ]);
+        let sent = replacement.sent_messages.clone();
+        let mut client = Client::new(transport).with_reconnect(reconnect_to(vec![replacement]));
+        let mut events = client.reconnect_events().unwrap();
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+        client
+            .subscribe_resource("test://watched".to_string())
+            .await
+            .unwrap();
+
+        connected.store(false, std::sync::atomic::Ordering::SeqCst);
+        client.ping().await.unwrap();
+
+        assert_eq!(
+            sent_methods(&sent),
+            ["initialize", "resources/subscribe", "ping"]
+        );
+        assert_eq!(
+            events.recv().await.unwrap(),
+            crate::shared::ReconnectEvent::Disconnected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_auto_reconnect_pending_requests() {
+        let tool_result = || {
+            TransportMessage::Response(JSONRPCResponse::success(
+                RequestId::from(2i64),
+                json!({"content": []}),
+            ))
+        };
+        let connect = |replacement: MockTransport| async {
+            let transport =
+                MockTransport::with_responses(vec![init_response_with(1, json!({"tools": {}}))]);
+            let dropped = transport.drop_on_receive.clone();
+            let mut client = Client::new(transport).with_reconnect(reconnect_to(vec![replacement]));
+            client
+                .initialize(ClientCapabilities::default())
+                .await
+                .unwrap();
+            dropped.store(true, std::sync::atomic::Ordering::SeqCst);
+            client
+        };
+
+        // A plain tool call may have run, so it is not resent
+        let replacement =
+            MockTransport::with_responses(vec![init_response_with(3, json!({"tools": {}}))]);
+        let sent = replacement.sent_messages.clone();
+        let client = connect(replacement).await;
+        let err = client
+            .call_tool("send".to_string(), json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::RequestInterrupted(ref m) if m == "tools/call"));
+        assert_eq!(sent_methods(&sent), ["initialize"]);
+
+        // One with an idempotency key is
+        let replacement = MockTransport::with_responses(vec![
+            tool_result(),
+            init_response_with(3, json!({"tools": {}})),
+        ]);
+        let sent = replacement.sent_messages.clone();
+        let client = connect(replacement).await;
+        client
+            .call_tool_idempotent("send".to_string(), json!({}), "key".to_string())
+            .await
+            .unwrap();
+        assert_eq!(sent_methods(&sent), ["initialize", "tools/call"]);
+    }
+
+    #[tokio::test]
+    async fn test_read_resource_stream() {
+        use tokio::io::AsyncReadExt;
+
+        let init_response = TransportMessage::Response(JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::from(1i64),
+            payload: ResponsePayload::Result(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": { "resources": {} },
+                "serverInfo": { "name": "test-server", "version": "1.0.0" }
+            })),
+        });
+        let chunk = |id: i64, text: &str, offset: u64| {
+            TransportMessage::Response(JSONRPCResponse {
+                jsonrpc: "2.0".to_string(),
+                id: RequestId::from(id),
+                payload: ResponsePayload::Result(json!({
+                    "contents": [{ "type": "text", "text": text }],
+                    "_meta": { "chunk": { "offset": offset, "length": text.len(), "total": 11 } }
+                })),
+            })
+        };
+
+        let transport = MockTransport::with_responses(vec![
+            chunk(3, "world", 6),
+            chunk(2, "hello ", 0),
+            init_response,
+        ]);
+        let sent = transport.sent_messages.clone();
+        let mut client = Client::new(transport);
+        let _ = client
+            .initialize(ClientCapabilities {
+                resources: Some(ResourceCapabilities::default()),
+                ..Default::default()
+            })
+            .await;
+
+        let mut body = Vec::new();
+        client
+            .read_resource_stream("doc://big".to_string())
+            .read_to_end(&mut body)
+            .await
+            .unwrap();
+        assert_eq!(body, b"hello world");
+
+        let offsets: Vec<u64> = sent
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|message| match message {
+                TransportMessage::Request {
+                    request: Request::Client(request),
+                    ..
+                } => match request.as_ref() {
+                    ClientRequest::ReadResource(params) => params.chunk().map(|c| c.offset),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(offsets, vec![0, 6]);
+    }
+
+    fn binary_chunk_response(id: i64, data: &str, offset: u64, total: u64) -> TransportMessage {
+        TransportMessage::Response(JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::from(id),
+            payload: ResponsePayload::Result(json!({
+                "contents": [{ "type": "image", "data": data, "mimeType": "image/png" }],
+                "_meta": { "chunk": { "offset": offset, "length": data.len(), "total": total } }
+            })),
+        })
+    }
+
+    async fn resource_client(responses: Vec<TransportMessage>) -> Client<MockTransport> {
+        let init_response = TransportMessage::Response(JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::from(1i64),
+            payload: ResponsePayload::Result(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": { "resources": {} },
+                "serverInfo": { "name": "test-server", "version": "1.0.0" }
+            })),
+        });
+        let mut responses = responses;
+        responses.push(init_response);
+        let mut client = Client::new(MockTransport::with_responses(responses));
+        let _ = client
+            .initialize(ClientCapabilities {
+                resources: Some(ResourceCapabilities::default()),
+                ..Default::default()
+            })
+            .await;
+        client
+    }
+
+    #[derive(Debug)]
+    struct StallingTransport;
+
+    #[async_trait]
+    impl Transport for StallingTransport {
+        async fn send(&mut self, _message: TransportMessage) -> Result<()> {
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<TransportMessage> {
+            std::future::pending().await
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_timeouts_per_method_and_per_request() {
+        let mut client = ClientBuilder::new(StallingTransport)
+            .default_timeout(std::time::Duration::from_secs(60))
+            .method_timeout("ping", std::time::Duration::from_millis(10))
+            .build();
+        client.initialized = true;
+        client.server_capabilities = Some(ServerCapabilities::tools_only());
+
+        let err = client.ping().await.unwrap_err();
+        assert!(matches!(err, Error::Timeout(10)));
+
+        let err = client
+            .call_tool_with_timeout(
+                "slow".to_string(),
+                json!({}),
+                std::time::Duration::from_millis(20),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Timeout(20)));
+        assert!(client.active_requests.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_keep_alive_gives_up_on_unanswered_pings() {
+        let config = crate::shared::KeepAliveConfig::default()
+            .with_interval(std::time::Duration::from_millis(10))
+            .with_timeout(std::time::Duration::from_millis(10))
+            .with_failure_threshold(2);
+        let mut client = Client::new(StallingTransport);
+        assert!(client.start_keep_alive(config, || {}).is_err());
+        client.initialized = true;
+
+        let (dead_tx, dead_rx) = oneshot::channel();
+        let keep_alive = client
+            .start_keep_alive(config, move || {
+                let _ = dead_tx.send(());
+            })
+            .unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), dead_rx)
+            .await
+            .expect("keep-alive did not give up")
+            .unwrap();
+        assert!(keep_alive.timed_out());
+        assert_eq!(keep_alive.failures(), 2);
+        assert!(client.active_requests.read().await.is_empty());
+    }
+
+    /// Answers each request after a series of delayed progress notifications.
+    #[derive(Debug)]
+    struct ProgressingTransport {
+        delay: std::time::Duration,
+        updates: usize,
+        pending: std::collections::VecDeque<TransportMessage>,
+    }
+
+    #[async_trait]
+    impl Transport for ProgressingTransport {
+        async fn send(&mut self, message: TransportMessage) -> Result<()> {
+            if let TransportMessage::Request {
+                id,
+                request: Request::Client(request),
+            } = message
+            {
+                let ClientRequest::CallTool(call) = *request else {
+                    return Ok(());
+                };
+                // Progress nobody asked for must not reset the timeout
+                let token = call
+                    .progress_token()
+                    .unwrap_or_else(|| ProgressToken::String("unrequested".to_string()));
+                for step in 1..=self.updates {
+                    self.pending
+                        .push_back(TransportMessage::Notification(Notification::Progress(
+                            ProgressNotification {
+                                progress_token: token.clone(),
+                                progress: (step * 100 / (self.updates + 1)) as f64,
+                                message: None,
+                            },
+                        )));
+                }
+                self.pending
+                    .push_back(TransportMessage::Response(JSONRPCResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id,
+                        payload: ResponsePayload::Result(json!({ "content": [] })),
+                    }));
+            }
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<TransportMessage> {
+            tokio::time::sleep(self.delay).await;
+            match self.pending.pop_front() {
+                Some(message) => Ok(message),
+                None => std::future::pending().await,
+            }
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_progress_resets_timeout_up_to_max_total() {
+        let client = || {
+            let mut client = Client::new(ProgressingTransport {
+                delay: std::time::Duration::from_millis(40),
+                updates: 5,
+                pending: std::collections::VecDeque::new(),
+            });
+            client.initialized = true;
+            client.server_capabilities = Some(ServerCapabilities::tools_only());
+            client
+        };
+        let call = || CallToolRequest::new("slow", json!({}));
+        let timeout = std::time::Duration::from_millis(100);
+
+        // Six 40ms gaps outlast the 100ms timeout unless progress resets it
+        let err = client()
+            .call_tool_with_options(call(), &RequestOptions::default().with_timeout(timeout))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Timeout(100)));
+
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let seen = updates.clone();
+        let options = RequestOptions::default()
+            .with_timeout(timeout)
+            .with_reset_timeout_on_progress(true)
+            .with_progress(move |progress, total| {
+                seen.lock().unwrap().push((progress, total));
+            });
+        client()
+            .call_tool_with_options(call(), &options)
+            .await
+            .unwrap();
+        assert_eq!(updates.lock().unwrap().len(), 5);
+        assert_eq!(updates.lock().unwrap()[0], (16, Some(100)));
+
+        let options = options.with_max_total_timeout(std::time::Duration::from_millis(150));
+        let err = client()
+            .call_tool_with_options(call(), &options)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Timeout(150)));
+    }
+
+    #[tokio::test]
+    async fn test_download_resource_decodes_binary_chunks() {
+        // "hello world!" as base64, split into two 8-character chunks.
+        let client = resource_client(vec![
+            binary_chunk_response(3, "d29ybGQh", 8, 16),
+            binary_chunk_response(2, "aGVsbG8g", 0, 16),
+        ])
+        .await;
+
+        let dir = std::env::temp_dir().join(format!("pmcp-download-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("out.bin");
+
+        let mut reports = Vec::new();
+        let written = client
+            .download_resource_with_progress("img://x".to_string(), &path, |done, total| {
+                reports.push((done, total))
+            })
+            .await
+            .unwrap();
+        let contents = std::fs::read(&path).unwrap();
+        let entries = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(written, 12);
+        assert_eq!(contents, b"hello world!");
+        assert_eq!(reports, vec![(6, Some(12)), (12, Some(12))]);
+        assert_eq!(entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_download_resource_failure_leaves_no_file() {
+        let client = resource_client(vec![
+            binary_chunk_response(3, "!!!!", 8, 16),
+            binary_chunk_response(2, "aGVsbG8g", 0, 16),
+        ])
+        .await;
+
+        let dir = std::env::temp_dir().join(format!("pmcp-download-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("out.bin");
+
+        let result = client.download_resource("img://x".to_string(), &path).await;
+        let entries = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(entries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_read_resource_not_modified_uses_cache() {
+        let init_response = TransportMessage::Response(JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::from(1i64),
+            payload: ResponsePayload::Result(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": { "resources": {} },
+                "serverInfo": { "name": "test-server", "version": "1.0.0" }
+            })),
+        });
+        let first_read = TransportMessage::Response(JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::from(2i64),
+            payload: ResponsePayload::Result(json!({
+                "contents": [{ "type": "text", "text": "large document" }],
+                "_meta": { "etag": "\"v1\"" }
+            })),
+        });
+        let second_read = TransportMessage::Response(JSONRPCResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::from(3i64),
+            payload: ResponsePayload::Result(json!({
+                "contents": [],
+                "_meta": { "etag": "\"v1\"", "notModified": true }
+            })),
+        });
+
+        let transport = MockTransport::with_responses(vec![second_read, first_read, init_response]);
+        let sent = transport.sent_messages.clone();
+        let mut client = Client::new(transport);
+        let _ = client
+            .initialize(ClientCapabilities {
+                resources: Some(ResourceCapabilities::default()),
+                ..Default::default()
+            })
+            .await;
+
+        let first = client.read_resource("doc://big".to_string()).await.unwrap();
+        let second = client.read_resource("doc://big".to_string()).await.unwrap();
+        assert_eq!(second.contents.len(), 1);
+        assert_eq!(second.etag(), first.etag());
+
+        let last_request = sent.lock().unwrap().last().cloned().unwrap();
+        match last_request {
+            TransportMessage::Request {
+                request: Request::Client(request),
+                ..
+            } => match *request {
+                ClientRequest::ReadResource(params) => {
+                    assert_eq!(params.if_none_match(), Some("\"v1\""));
+                },
+                other => panic!("unexpected request: {:?}", other),
+            },
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_on_log_receives_log_messages() {
+        let response = |id: i64, result: serde_json::Value| {
+            TransportMessage::Response(JSONRPCResponse {
+                jsonrpc: "2.0".to_string(),
+                id: RequestId::from(id),
+                payload: ResponsePayload::Result(result),
+            })
+        };
+        let transport = MockTransport::with_responses(vec![
+            response(2, json!({})),
+            TransportMessage::Notification(Notification::Server(
+                crate::types::ServerNotification::LogMessage(
+                    crate::types::protocol::LogMessageParams {
+                        level: crate::types::protocol::LogLevel::Warning,
+                        logger: Some("db".to_string()),
+                        message: "slow query".to_string(),
+                        data: None,
+                    },
+                ),
+            )),
+            response(
+                1,
+                json!({
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": { "logging": {} },
+                    "serverInfo": { "name": "test-server", "version": "1.0.0" }
+                }),
+            ),
+        ]);
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let mut client = ClientBuilder::new(transport)
+            .on_log({
+                let received = received.clone();
+                move |message| received.lock().unwrap().push(message.message.clone())
+            })
+            .build();
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+
+        client
+            .set_logging_level(LoggingLevel::Warning)
+            .await
+            .unwrap();
+        assert_eq!(*received.lock().unwrap(), ["slow query"]);
+    }
+
+    #[tokio::test]
+    async fn test_on_elicitation_answers_server_requests() {
+        struct Approve;
+
+        #[async_trait]
+        impl elicitation::ElicitationHandler for Approve {
+            async fn elicit(
+                &self,
+                params: crate::types::elicitation::ElicitRequestParams,
+            ) -> Result<crate::types::elicitation::ElicitResult> {
+                let mut content = serde_json::Map::new();
+                content.insert("answer".to_string(), json!(params.message));
+                Ok(crate::types::elicitation::ElicitResult::accept(content))
+            }
+        }
+
+        let response = |id: i64, result: serde_json::Value| {
+            TransportMessage::Response(JSONRPCResponse {
+                jsonrpc: "2.0".to_string(),
+                id: RequestId::from(id),
+                payload: ResponsePayload::Result(result),
+            })
+        };
+        let server_request =
+            |id: &str, request: crate::types::ServerRequest| TransportMessage::Request {
+                id: RequestId::from(id.to_string()),
+                request: Request::Server(Box::new(request)),
+            };
+        let transport = MockTransport::with_responses(vec![
+            response(2, json!({})),
+            server_request("roots", crate::types::ServerRequest::ListRoots),
+            server_request(
+                "ask",
+                crate::types::ServerRequest::Elicit(Box::new(
+                    crate::types::elicitation::ElicitRequestParams::new("yes", json!({})),
+                )),
+            ),
+            response(
+                1,
+                json!({
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": {},
+                    "serverInfo": { "name": "test-server", "version": "1.0.0" }
+                }),
+            ),
+        ]);
+        let sent = transport.sent_messages.clone();
+        let mut client = ClientBuilder::new(transport)
+            .on_elicitation(Approve)
+            .build();
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+        client.ping().await.unwrap();
+
+        let answers: Vec<_> = sent
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|message| match message {
+                TransportMessage::Response(response) => Some(response.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0].id, RequestId::from("ask".to_string()));
+        assert_eq!(
+            answers[0].result(),
+            Some(&json!({"action": "accept", "content": {"answer": "yes"}}))
+        );
+        assert_eq!(answers[1].id, RequestId::from("roots".to_string()));
+        assert_eq!(
+            answers[1].get_error().map(|e| e.code),
+            Some(crate::error::ErrorCode::METHOD_NOT_FOUND.as_i32())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notification_subscriptions() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let notification =
+            |notification| TransportMessage::Notification(Notification::Server(notification));
+        let transport = MockTransport::with_responses(vec![
+            TransportMessage::Response(JSONRPCResponse {
+                jsonrpc: "2.0".to_string(),
+                id: RequestId::from(2i64),
+                payload: ResponsePayload::Result(json!({})),
+            }),
+            notification(crate::types::ServerNotification::ToolsChanged),
+            notification(crate::types::ServerNotification::ResourceUpdated(
+                crate::types::protocol::ResourceUpdatedParams {
+                    uri: "file:///b".to_string(),
+                },
+            )),
+            notification(crate::types::ServerNotification::ResourceUpdated(
+                crate::types::protocol::ResourceUpdatedParams {
+                    uri: "file:///a".to_string(),
+                },
+            )),
+            TransportMessage::Notification(Notification::Progress(ProgressNotification {
+                progress_token: ProgressToken::String("job".to_string()),
+                progress: 50.0,
+                message: None,
+            })),
+            TransportMessage::Response(JSONRPCResponse {
+                jsonrpc: "2.0".to_string(),
+                id: RequestId::from(1i64),
+                payload: ResponsePayload::Result(json!({
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": {},
+                    "serverInfo": { "name": "test-server", "version": "1.0.0" }
+                })),
+            }),
+        ]);
+        let mut client = Client::new(transport);
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+
+        let progress = Arc::new(AtomicUsize::new(0));
+        let updated = Arc::new(AtomicUsize::new(0));
+        let tools_changed = Arc::new(AtomicUsize::new(0));
+        let count = |counter: &Arc<AtomicUsize>| {
+            let counter = counter.clone();
+            move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        };
+        let on_progress = count(&progress);
+        let _progress =
+            client.on_progress(ProgressToken::String("job".to_string()), move |update| {
+                assert_eq!(update.progress, 50.0);
+                on_progress();
+            });
+        let on_updated = count(&updated);
+        let _updated = client.on_resource_updated("file:///a", move |_| on_updated());
+        client.on_tools_changed(count(&tools_changed)).detach();
+        // Dropped right away, so never called
+        let _ = client.on_tools_changed(|| panic!("unsubscribed callback called"));
+        let mut stream = client.notification_stream(8);
+
+        client.ping().await.unwrap();
+        assert_eq!(progress.load(Ordering::SeqCst), 1);
+        assert_eq!(updated.load(Ordering::SeqCst), 1);
+        assert_eq!(tools_changed.load(Ordering::SeqCst), 1);
+
+        let received: Vec<_> = (&mut stream).take(4).collect().await;
+        assert!(matches!(
+            received[0],
+            crate::types::ServerNotification::Progress(_)
+        ));
+        assert!(matches!(
+            received[3],
+            crate::types::ServerNotification::ToolsChanged
+        ));
+        drop(client);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_cache_invalidated_by_list_changed() {
+        let response = |id: i64, result: serde_json::Value| {
+            TransportMessage::Response(JSONRPCResponse {
+                jsonrpc: "2.0".to_string(),
+                id: RequestId::from(id),
+                payload: ResponsePayload::Result(result),
+            })
+        };
+        let tools = |name: &str| json!({ "tools": [{ "name": name, "inputSchema": {} }] });
+
+        let transport = MockTransport::with_responses(vec![
+            response(4, tools("second")),
+            response(3, json!({})),
+            TransportMessage::Notification(Notification::Server(
+                crate::types::ServerNotification::ToolsChanged,
+            )),
+            response(2, tools("first")),
+            response(
+                1,
+                json!({
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": { "tools": { "listChanged": true } },
+                    "serverInfo": { "name": "test-server", "version": "1.0.0" }
+                }),
+            ),
+        ]);
+        let sent = transport.sent_messages.clone();
+        let mut client = ClientBuilder::new(transport)
+            .list_cache(ListCacheConfig::default())
+            .build();
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            client.list_tools(None).await.unwrap().tools[0].name,
+            "first"
+        );
+        assert_eq!(
+            client.list_tools(None).await.unwrap().tools[0].name,
+            "first"
+        );
+        let requests_sent = sent.lock().unwrap().len();
+
+        // The change notification arrives while waiting for the ping response
+        client.ping().await.unwrap();
+        assert_eq!(
+            client.list_tools(None).await.unwrap().tools[0].name,
+            "second"
+        );
+        assert_eq!(sent.lock().unwrap().len(), requests_sent + 2);
+
+        let stats = client.list_cache_stats().unwrap();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.invalidations, 1);
+        assert_eq!(stats.entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_all_follows_cursors() {
+        let response = |id: i64, result: serde_json::Value| {
+            TransportMessage::Response(JSONRPCResponse {
+                jsonrpc: "2.0".to_string(),
+                id: RequestId::from(id),
+                payload: ResponsePayload::Result(result),
+            })
+        };
+        let page = |names: &[&str], next: Option<&str>| {
+            let prompts: Vec<_> = names.iter().map(|name| json!({ "name": name })).collect();
+            json!({ "prompts": prompts, "nextCursor": next })
+        };
+
+        let transport = MockTransport::with_responses(vec![
+            response(6, page(&["d"], Some("2"))),
+            response(5, page(&["d"], Some("2"))),
+            response(4, page(&["c"], None)),
+            response(3, page(&["b"], Some("2"))),
+            response(2, page(&["a"], Some("1"))),
+            response(
+                1,
+                json!({
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": { "prompts": {} },
+                    "serverInfo": { "name": "test-server", "version": "1.0.0" }
+                }),
+            ),
+        ]);
+        let sent = transport.sent_messages.clone();
+        let mut client = Client::new(transport);
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+
+        let names: Vec<_> = client
+            .list_prompts_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|prompt| prompt.name)
+            .collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+
+        let cursors: Vec<_> = sent
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|message| match message {
+                TransportMessage::Request {
+                    request: Request::Client(request),
+                    ..
+                } => match request.as_ref() {
+                    ClientRequest::ListPrompts(params) => Some(params.cursor.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            cursors,
+            vec![None, Some("1".to_string()), Some("2".to_string())]
+        );
+
+        // A server repeating the cursor it was given fails instead of looping
+        let mut prompts = std::pin::pin!(client.list_prompts_stream());
+        assert!(prompts.try_next().await.unwrap().is_some());
+        let err = prompts.try_next().await.unwrap_err();
+        assert!(err.to_string().contains("same pagination cursor"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_request_with_reason_notifies_server() {
+        let transport = MockTransport::new();
+        let sent = transport.sent_messages.clone();
+        let client = Client::new(transport);
+
+        client
+            .cancel_request_with_reason(&RequestId::from(3i64), "No longer needed")
+            .await
+            .unwrap();
+
+        let last = sent.lock().unwrap().last().cloned();
+        match last {
+            Some(TransportMessage::Notification(Notification::Cancelled(cancelled))) => {
+                assert_eq!(cancelled.request_id, RequestId::from(3i64));
+                assert_eq!(cancelled.reason.as_deref(), Some("No longer needed"));
+            },
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,535 @@
+//! One client for several servers.
+//!
+//! [`McpMultiplexClient`] holds a [`Client`] per server, over any mix of
+//! transports, and presents their tools, prompts and resources as one
+//! catalog. Calls are routed to the server that offers the named item,
+//! under the name that server knows it by.
+//!
+//! When several servers offer the same name, [`ConflictPolicy`] decides what
+//! the catalog shows. Renamed tools and prompts are exposed as
+//! `server.name`, and renamed resource URIs as `server+uri`, the same scheme
+//! [`ServerBuilder::mount`](crate::ServerBuilder::mount) uses.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::client::multiplex::{ConflictPolicy, McpMultiplexClient};
+//! use pmcp::{Client, ClientCapabilities};
+//! use serde_json::json;
+//!
+//! # #[cfg(feature = "tcp")]
+//! # async fn example() -> pmcp::Result<()> {
+//! use pmcp::shared::TcpTransport;
+//!
+//! let mut servers = McpMultiplexClient::new()
+//!     .with_conflict_policy(ConflictPolicy::PrefixAll)
+//!     .add_server("files", Client::new(TcpTransport::connect("127.0.0.1:7000").await?))
+//!     .add_server("search", Client::new(TcpTransport::connect("127.0.0.1:7001").await?));
+//! servers.initialize(ClientCapabilities::default()).await?;
+//!
+//! for tool in servers.list_tools().await? {
+//!     println!("{}", tool.name); // e.g. "search.query"
+//! }
+//! let result = servers.call_tool("search.query", json!({"q": "mcp"})).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::shared::Transport;
+use crate::types::{
+    CallToolResult, ClientCapabilities, GetPromptResult, PromptInfo, ReadResourceResult,
+    ResourceInfo, ServerCapabilities, ToolInfo,
+};
+use crate::Client;
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// What the combined catalog shows when several servers offer the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Expose every offer of a contested name under its server's prefix;
+    /// names offered once keep their own.
+    #[default]
+    PrefixConflicts,
+
+    /// Expose every name under its server's prefix.
+    PrefixAll,
+
+    /// Expose the offer of the server added first, and hide the others.
+    FirstWins,
+
+    /// Fail listing with an error naming the servers in conflict.
+    Reject,
+}
+
+/// A client to one of the servers, whatever its transport.
+#[async_trait]
+trait Upstream: Send + Sync {
+    fn server_capabilities(&self) -> Option<&ServerCapabilities>;
+    async fn initialize(&mut self, capabilities: ClientCapabilities) -> Result<()>;
+    async fn list_tools(&self) -> Result<Vec<ToolInfo>>;
+    async fn call_tool(&self, name: String, arguments: Value) -> Result<CallToolResult>;
+    async fn list_prompts(&self) -> Result<Vec<PromptInfo>>;
+    async fn get_prompt(
+        &self,
+        name: String,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult>;
+    async fn list_resources(&self) -> Result<Vec<ResourceInfo>>;
+    async fn read_resource(&self, uri: String) -> Result<ReadResourceResult>;
+}
+
+#[async_trait]
+impl<T: Transport> Upstream for Client<T> {
+    fn server_capabilities(&self) -> Option<&ServerCapabilities> {
+        self.get_server_capabilities()
+    }
+
+    async fn initialize(&mut self, capabilities: ClientCapabilities) -> Result<()> {
+        Client::initialize(self, capabilities).await.map(|_| ())
+    }
+
+    async fn list_tools(&self) -> Result<Vec<ToolInfo>> {
+        self.list_tools_all().await
+    }
+
+    async fn call_tool(&self, name: String, arguments: Value) -> Result<CallToolResult> {
+        Client::call_tool(self, name, arguments).await
+    }
+
+    async fn list_prompts(&self) -> Result<Vec<PromptInfo>> {
+        self.list_prompts_all().await
+    }
+
+    async fn get_prompt(
+        &self,
+        name: String,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult> {
+        Client::get_prompt(self, name, arguments).await
+    }
+
+    async fn list_resources(&self) -> Result<Vec<ResourceInfo>> {
+        self.list_resources_all().await
+    }
+
+    async fn read_resource(&self, uri: String) -> Result<ReadResourceResult> {
+        Client::read_resource(self, uri).await
+    }
+}
+
+/// The kinds of items in the catalog.
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Tool,
+    Prompt,
+    Resource,
+}
+
+impl Kind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Tool => "Tool",
+            Self::Prompt => "Prompt",
+            Self::Resource => "Resource",
+        }
+    }
+
+    /// Whether the server offers items of this kind.
+    fn offered_by(self, capabilities: Option<&ServerCapabilities>) -> bool {
+        capabilities.is_some_and(|capabilities| match self {
+            Self::Tool => capabilities.tools.is_some(),
+            Self::Prompt => capabilities.prompts.is_some(),
+            Self::Resource => capabilities.resources.is_some(),
+        })
+    }
+
+    /// The name `name` of `server` is exposed under when renamed.
+    fn prefixed(self, server: &str, name: &str) -> String {
+        match self {
+            Self::Tool | Self::Prompt => format!("{}.{}", server, name),
+            Self::Resource => format!("{}+{}", server, name),
+        }
+    }
+}
+
+/// Which server serves an exposed name, and its name there.
+#[derive(Debug, Clone)]
+struct Route {
+    server: usize,
+    name: String,
+}
+
+/// Routes of the last listed catalog, by exposed name.
+#[derive(Debug, Default)]
+struct Routes {
+    tools: HashMap<String, Route>,
+    prompts: HashMap<String, Route>,
+    resources: HashMap<String, Route>,
+}
+
+impl Routes {
+    fn of(&mut self, kind: Kind) -> &mut HashMap<String, Route> {
+        match kind {
+            Kind::Tool => &mut self.tools,
+            Kind::Prompt => &mut self.prompts,
+            Kind::Resource => &mut self.resources,
+        }
+    }
+}
+
+/// A client for several MCP servers at once.
+///
+/// See the [module documentation](self).
+pub struct McpMultiplexClient {
+    servers: Vec<(String, Box<dyn Upstream>)>,
+    policy: ConflictPolicy,
+    routes: RwLock<Routes>,
+}
+
+impl std::fmt::Debug for McpMultiplexClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpMultiplexClient")
+            .field("servers", &self.server_names())
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl Default for McpMultiplexClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl McpMultiplexClient {
+    /// Create a multiplexer without servers.
+    pub fn new() -> Self {
+        Self {
+            servers: Vec::new(),
+            policy: ConflictPolicy::default(),
+            routes: RwLock::new(Routes::default()),
+        }
+    }
+
+    /// Set how conflicting names are resolved.
+    pub fn with_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Add the server at the other end of `client` under `name`.
+    ///
+    /// `name` is the prefix of the server's renamed items. The client may
+    /// already be initialized; otherwise [`initialize`](Self::initialize)
+    /// initializes it.
+    pub fn add_server<T: Transport + 'static>(
+        mut self,
+        name: impl Into<String>,
+        client: Client<T>,
+    ) -> Self {
+        self.servers.push((name.into(), Box::new(client)));
+        self
+    }
+
+    /// Names of the servers, in the order they were added.
+    pub fn server_names(&self) -> Vec<&str> {
+        self.servers.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Initialize the clients that are not initialized yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first initialization error, naming its server.
+    pub async fn initialize(&mut self, capabilities: ClientCapabilities) -> Result<()> {
+        for (name, server) in &mut self.servers {
+            if server.server_capabilities().is_none() {
+                server
+                    .initialize(capabilities.clone())
+                    .await
+                    .map_err(|e| Error::internal(format!("Server '{}': {}", name, e)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// List the tools of every server, as named in the combined catalog.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a server fails to list its tools, or if the
+    /// policy is [`ConflictPolicy::Reject`] and two servers offer a tool of
+    /// the same name.
+    pub async fn list_tools(&self) -> Result<Vec<ToolInfo>> {
+        let listed = self.list(Kind::Tool, |server| server.list_tools()).await?;
+        self.merge(Kind::Tool, listed, |tool| &mut tool.name)
+    }
+
+    /// Call the tool exposed as `name` on the server that offers it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no server offers the tool, or the call fails.
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<CallToolResult> {
+        let (server, name) = self
+            .route(Kind::Tool, name, || async {
+                self.list_tools().await.map(|_| ())
+            })
+            .await?;
+        server.call_tool(name, arguments).await
+    }
+
+    /// List the prompts of every server, as named in the combined catalog.
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`list_tools`](Self::list_tools) does.
+    pub async fn list_prompts(&self) -> Result<Vec<PromptInfo>> {
+        let listed = self
+            .list(Kind::Prompt, |server| server.list_prompts())
+            .await?;
+        self.merge(Kind::Prompt, listed, |prompt| &mut prompt.name)
+    }
+
+    /// Get the prompt exposed as `name` from the server that offers it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no server offers the prompt, or getting it fails.
+    pub async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult> {
+        let (server, name) = self
+            .route(Kind::Prompt, name, || async {
+                self.list_prompts().await.map(|_| ())
+            })
+            .await?;
+        server.get_prompt(name, arguments).await
+    }
+
+    /// List the resources of every server, with URIs as in the combined
+    /// catalog.
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`list_tools`](Self::list_tools) does.
+    pub async fn list_resources(&self) -> Result<Vec<ResourceInfo>> {
+        let listed = self
+            .list(Kind::Resource, |server| server.list_resources())
+            .await?;
+        self.merge(Kind::Resource, listed, |resource| &mut resource.uri)
+    }
+
+    /// Read the resource exposed as `uri` from the server that offers it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no server offers the resource, or reading it
+    /// fails.
+    pub async fn read_resource(&self, uri: &str) -> Result<ReadResourceResult> {
+        let (server, uri) = self
+            .route(Kind::Resource, uri, || async {
+                self.list_resources().await.map(|_| ())
+            })
+            .await?;
+        server.read_resource(uri).await
+    }
+
+    /// List the items of `kind` of every server offering them, concurrently.
+    async fn list<'a, I, F, Fut>(&'a self, kind: Kind, list: F) -> Result<Vec<(usize, Vec<I>)>>
+    where
+        F: Fn(&'a dyn Upstream) -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<I>>>,
+    {
+        try_join_all(
+            self.servers
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, server))| kind.offered_by(server.server_capabilities()))
+                .map(|(index, (name, server))| {
+                    let listed = list(server.as_ref());
+                    async move {
+                        listed
+                            .await
+                            .map(|items| (index, items))
+                            .map_err(|e| Error::internal(format!("Server '{}': {}", name, e)))
+                    }
+                }),
+        )
+        .await
+    }
+
+    /// Name the listed items by the conflict policy and record their routes.
+    fn merge<I>(
+        &self,
+        kind: Kind,
+        mut listed: Vec<(usize, Vec<I>)>,
+        key: fn(&mut I) -> &mut String,
+    ) -> Result<Vec<I>> {
+        let mut offered_by: HashMap<String, Vec<usize>> = HashMap::new();
+        for (server, items) in &mut listed {
+            for item in items {
+                let servers = offered_by.entry(key(item).clone()).or_default();
+                if !servers.contains(server) {
+                    servers.push(*server);
+                }
+            }
+        }
+
+        let mut merged = Vec::new();
+        let mut routes = HashMap::new();
+        for (server, items) in listed {
+            let server_name = &self.servers[server].0;
+            for mut item in items {
+                let name = key(&mut item).clone();
+                let contested = offered_by[&name].len() > 1;
+                let exposed = match self.policy {
+                    ConflictPolicy::PrefixAll => kind.prefixed(server_name, &name),
+                    ConflictPolicy::PrefixConflicts if contested => {
+                        kind.prefixed(server_name, &name)
+                    },
+                    ConflictPolicy::FirstWins if routes.contains_key(&name) => continue,
+                    ConflictPolicy::Reject if contested => {
+                        let servers: Vec<&str> = offered_by[&name]
+                            .iter()
+                            .map(|&server| self.servers[server].0.as_str())
+                            .collect();
+                        return Err(Error::validation(format!(
+                            "{} '{}' is offered by several servers: {}",
+                            kind.label(),
+                            name,
+                            servers.join(", ")
+                        )));
+                    },
+                    _ => name.clone(),
+                };
+                *key(&mut item) = exposed.clone();
+                routes.insert(exposed, Route { server, name });
+                merged.push(item);
+            }
+        }
+
+        *self.routes.write().of(kind) = routes;
+        Ok(merged)
+    }
+
+    /// The server offering the item exposed as `name`, and its name there.
+    ///
+    /// Names missing from the last listing are looked up again after
+    /// `refresh`, so items can be used without listing them first.
+    async fn route<F, Fut>(
+        &self,
+        kind: Kind,
+        name: &str,
+        refresh: F,
+    ) -> Result<(&dyn Upstream, String)>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<()>>,
+    {
+        let lookup = || self.routes.write().of(kind).get(name).cloned();
+        let route = match lookup() {
+            Some(route) => route,
+            None => {
+                refresh().await?;
+                lookup().ok_or_else(|| {
+                    Error::not_found(format!("{} '{}' not found", kind.label(), name))
+                })?
+            },
+        };
+        Ok((self.servers[route.server].1.as_ref(), route.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::simple_tool::SyncTool;
+    use crate::shared::InMemoryTransport;
+    use crate::Server;
+    use serde_json::json;
+
+    /// A client to a server with a `whoami` tool and a tool of its own.
+    fn client(name: &'static str, own_tool: &'static str) -> Client<InMemoryTransport> {
+        let (client_transport, server_transport) = InMemoryTransport::pair();
+        let server = Server::builder()
+            .name(name)
+            .version("1.0.0")
+            .tool("whoami", SyncTool::new("whoami", move |_| Ok(json!(name))))
+            .tool(own_tool, SyncTool::new(own_tool, move |_| Ok(json!(name))))
+            .build()
+            .unwrap();
+        tokio::spawn(server.run(server_transport));
+        Client::new(client_transport)
+    }
+
+    async fn multiplexer(policy: ConflictPolicy) -> McpMultiplexClient {
+        let mut servers = McpMultiplexClient::new()
+            .with_conflict_policy(policy)
+            .add_server("fs", client("fs", "read_file"))
+            .add_server("git", client("git", "commit"));
+        servers
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+        servers
+    }
+
+    async fn tool_names(servers: &McpMultiplexClient) -> Vec<String> {
+        let mut names: Vec<String> = servers
+            .list_tools()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|tool| tool.name)
+            .collect();
+        names.sort();
+        names
+    }
+
+    async fn answer(servers: &McpMultiplexClient, tool: &str) -> String {
+        let result = servers.call_tool(tool, json!({})).await.unwrap();
+        serde_json::to_string(&result).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_tools_are_prefixed_and_routed() {
+        let servers = multiplexer(ConflictPolicy::PrefixConflicts).await;
+        assert_eq!(
+            tool_names(&servers).await,
+            ["commit", "fs.whoami", "git.whoami", "read_file"]
+        );
+        assert!(answer(&servers, "git.whoami").await.contains("git"));
+        assert!(answer(&servers, "read_file").await.contains("fs"));
+        assert!(servers.call_tool("whoami", json!({})).await.is_err());
+
+        // Routes are looked up without listing first
+        let servers = multiplexer(ConflictPolicy::PrefixAll).await;
+        assert!(answer(&servers, "git.commit").await.contains("git"));
+        assert_eq!(
+            tool_names(&servers).await,
+            ["fs.read_file", "fs.whoami", "git.commit", "git.whoami"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_first_wins_and_reject_policies() {
+        let servers = multiplexer(ConflictPolicy::FirstWins).await;
+        assert_eq!(
+            tool_names(&servers).await,
+            ["commit", "read_file", "whoami"]
+        );
+        assert!(answer(&servers, "whoami").await.contains("fs"));
+
+        let servers = multiplexer(ConflictPolicy::Reject).await;
+        let err = servers.list_tools().await.unwrap_err();
+        assert!(err.to_string().contains("fs, git"));
+    }
+}
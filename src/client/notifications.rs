@@ -0,0 +1,178 @@
+//! Subscriptions to server notifications.
+//!
+//! [`Client::on_progress`](super::Client::on_progress),
+//! [`on_resource_updated`](super::Client::on_resource_updated) and
+//! [`on_tools_changed`](super::Client::on_tools_changed) register callbacks
+//! for one kind of notification, and
+//! [`notification_stream`](super::Client::notification_stream) delivers every
+//! notification as a [`Stream`].
+//!
+//! The client reads notifications from the transport while it waits for a
+//! response, and passes each to the matching callbacks and to every stream
+//! before reading on. A stream buffers a bounded number of notifications;
+//! once its buffer is full the client waits for it to be read, so consume
+//! streams from a task of their own.
+
+use crate::types::protocol::ResourceUpdatedParams;
+use crate::types::{ProgressNotification, ProgressToken, ServerNotification};
+use futures::channel::mpsc;
+use futures::{SinkExt, Stream};
+use parking_lot::Mutex;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::task::{Context, Poll};
+
+/// Callback receiving every notification, filtering for its own kind.
+type Handler = Arc<dyn Fn(&ServerNotification) + Send + Sync>;
+
+/// The callbacks and streams notifications are delivered to.
+#[derive(Default)]
+pub(crate) struct NotificationRegistry {
+    next_id: AtomicU64,
+    handlers: Mutex<Vec<(u64, Handler)>>,
+    streams: Mutex<Vec<mpsc::Sender<ServerNotification>>>,
+}
+
+impl std::fmt::Debug for NotificationRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotificationRegistry")
+            .field("handlers", &self.handlers.lock().len())
+            .field("streams", &self.streams.lock().len())
+            .finish()
+    }
+}
+
+impl NotificationRegistry {
+    /// Register `handler` until the returned subscription is dropped.
+    pub(crate) fn subscribe(
+        self: &Arc<Self>,
+        handler: impl Fn(&ServerNotification) + Send + Sync + 'static,
+    ) -> Subscription {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.handlers.lock().push((id, Arc::new(handler)));
+        Subscription {
+            registry: Arc::downgrade(self),
+            id,
+        }
+    }
+
+    /// A stream of every notification, buffering up to `capacity`.
+    pub(crate) fn stream(&self, capacity: usize) -> NotificationStream {
+        // The channel holds one more message per sender than its buffer
+        let (sender, receiver) = mpsc::channel(capacity.saturating_sub(1));
+        self.streams.lock().push(sender);
+        NotificationStream { receiver }
+    }
+
+    /// Deliver `notification` to the callbacks, then to the streams, waiting
+    /// for room in each.
+    pub(crate) async fn dispatch(&self, notification: ServerNotification) {
+        let handlers: Vec<Handler> = self
+            .handlers
+            .lock()
+            .iter()
+            .map(|(_, handler)| handler.clone())
+            .collect();
+        for handler in handlers {
+            handler(&notification);
+        }
+
+        let streams = self.streams.lock().clone();
+        if streams.is_empty() {
+            return;
+        }
+        for mut stream in streams {
+            // A dropped stream just misses the notification
+            let _ = stream.send(notification.clone()).await;
+        }
+        self.streams.lock().retain(|stream| !stream.is_closed());
+    }
+}
+
+/// A registered notification callback.
+///
+/// Dropping the subscription removes the callback; use
+/// [`detach`](Self::detach) to keep it for the life of the client.
+#[must_use = "the callback is removed when the subscription is dropped"]
+#[derive(Debug)]
+pub struct Subscription {
+    registry: Weak<NotificationRegistry>,
+    id: u64,
+}
+
+impl Subscription {
+    /// Keep the callback registered for as long as the client exists.
+    pub fn detach(mut self) {
+        self.registry = Weak::new();
+    }
+
+    /// Remove the callback.
+    pub fn unsubscribe(self) {
+        // Dropping removes the callback
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(registry) = self.registry.upgrade() {
+            registry.handlers.lock().retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+/// Every notification the client receives, in order.
+///
+/// Progress notifications arrive as [`ServerNotification::Progress`]. The
+/// stream ends when the client and all its clones are dropped.
+#[derive(Debug)]
+pub struct NotificationStream {
+    receiver: mpsc::Receiver<ServerNotification>,
+}
+
+impl Stream for NotificationStream {
+    type Item = ServerNotification;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+/// A handler passing progress under `token` to `callback`.
+pub(crate) fn progress_handler(
+    token: ProgressToken,
+    callback: impl Fn(&ProgressNotification) + Send + Sync + 'static,
+) -> impl Fn(&ServerNotification) + Send + Sync + 'static {
+    move |notification| {
+        if let ServerNotification::Progress(progress) = notification {
+            if progress.progress_token == token {
+                callback(progress);
+            }
+        }
+    }
+}
+
+/// A handler passing updates of the resource at `uri` to `callback`.
+pub(crate) fn resource_updated_handler(
+    uri: String,
+    callback: impl Fn(&ResourceUpdatedParams) + Send + Sync + 'static,
+) -> impl Fn(&ServerNotification) + Send + Sync + 'static {
+    move |notification| {
+        if let ServerNotification::ResourceUpdated(params) = notification {
+            if params.uri == uri {
+                callback(params);
+            }
+        }
+    }
+}
+
+/// A handler calling `callback` when the server's tools change.
+pub(crate) fn tools_changed_handler(
+    callback: impl Fn() + Send + Sync + 'static,
+) -> impl Fn(&ServerNotification) + Send + Sync + 'static {
+    move |notification| {
+        if matches!(notification, ServerNotification::ToolsChanged) {
+            callback();
+        }
+    }
+}
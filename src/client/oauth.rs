@@ -0,0 +1,1065 @@
+//! OAuth 2.1 authorization for clients of protected MCP servers.
+//!
+//! [`OAuthClientProvider`] walks through the flow the MCP authorization
+//! specification describes:
+//!
+//! 1. Discover the authorization server from the MCP server's protected
+//!    resource metadata (RFC 9728), then fetch that server's metadata
+//!    (RFC 8414, falling back to `OpenID Connect` discovery).
+//! 2. Register a client dynamically (RFC 7591) unless a client ID is
+//!    configured.
+//! 3. Send the user to the authorization endpoint with a PKCE challenge and
+//!    exchange the returned code for tokens.
+//! 4. Refresh the access token when it expires or the server rejects it.
+//!
+//! Tokens are kept in a [`TokenStorage`] so they survive restarts, and the
+//! user-facing part of the flow (opening a browser, receiving the redirect)
+//! is delegated to an [`AuthorizationHandler`].
+//!
+//! With the `streamable-http` feature the provider plugs into
+//! [`StreamableHttpTransportConfig::auth_provider`], which retries a request
+//! once after the provider handles a `401 Unauthorized`.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use async_trait::async_trait;
+//! use pmcp::client::oauth::{
+//!     AuthorizationHandler, AuthorizationResponse, OAuthClientConfig, OAuthClientProvider,
+//! };
+//! use url::Url;
+//!
+//! #[derive(Debug)]
+//! struct PromptUser;
+//!
+//! #[async_trait]
+//! impl AuthorizationHandler for PromptUser {
+//!     async fn authorize(&self, authorization_url: Url) -> pmcp::Result<AuthorizationResponse> {
+//!         println!("Open {} and paste the URL you are redirected to:", authorization_url);
+//!         let mut line = String::new();
+//!         std::io::stdin().read_line(&mut line)?;
+//!         let redirect = Url::parse(line.trim())
+//!             .map_err(|e| pmcp::Error::validation(e.to_string()))?;
+//!         AuthorizationResponse::from_redirect_url(&redirect)
+//!     }
+//! }
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let config = OAuthClientConfig::new(
+//!     Url::parse("https://mcp.example.com/mcp").unwrap(),
+//!     "http://localhost:8765/callback",
+//! )
+//! .with_scopes(["mcp:tools"]);
+//!
+//! let provider = OAuthClientProvider::new(config, PromptUser);
+//! let tokens = provider.authorize().await?;
+//! println!("Access token expires at {:?}", tokens.expires_at);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`StreamableHttpTransportConfig::auth_provider`]: crate::shared::streamable_http::StreamableHttpTransportConfig::auth_provider
+
+use crate::client::auth::TokenResponse;
+use crate::error::{Error, ErrorCode, Result};
+pub use crate::server::auth::oauth2::ProtectedResourceMetadata;
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Debug;
+use std::sync::Arc;
+use url::Url;
+use uuid::Uuid;
+
+/// Access tokens are treated as expired this many seconds early, so a
+/// request never leaves with a token that lapses in flight.
+const EXPIRY_MARGIN_SECS: u64 = 30;
+
+/// Well-known path of authorization server metadata (RFC 8414).
+const AUTHORIZATION_SERVER_PATH: &str = "/.well-known/oauth-authorization-server";
+
+/// Well-known path of `OpenID Connect` discovery.
+const OPENID_CONFIGURATION_PATH: &str = "/.well-known/openid-configuration";
+
+/// OAuth 2.0 authorization server metadata (RFC 8414).
+///
+/// Unlike [`OidcDiscoveryMetadata`](crate::server::auth::oauth2::OidcDiscoveryMetadata),
+/// only the fields a client needs are required.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizationServerMetadata {
+    /// Issuer identifier.
+    pub issuer: String,
+
+    /// Authorization endpoint URL.
+    pub authorization_endpoint: String,
+
+    /// Token endpoint URL.
+    pub token_endpoint: String,
+
+    /// Dynamic client registration endpoint URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registration_endpoint: Option<String>,
+
+    /// Supported scopes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes_supported: Vec<String>,
+
+    /// Supported PKCE code challenge methods.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub code_challenge_methods_supported: Vec<String>,
+}
+
+/// A PKCE code verifier and its `S256` challenge (RFC 7636).
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::client::oauth::PkceChallenge;
+///
+/// let pkce = PkceChallenge::new();
+/// assert_eq!(pkce.method(), "S256");
+/// assert_eq!(pkce.verifier().len(), 43);
+/// assert_ne!(pkce.verifier(), pkce.challenge());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PkceChallenge {
+    verifier: String,
+    challenge: String,
+}
+
+impl Default for PkceChallenge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PkceChallenge {
+    /// Generate a random verifier and derive its challenge.
+    pub fn new() -> Self {
+        // Two v4 UUIDs give 32 bytes from the OS random number generator
+        let mut random = [0u8; 32];
+        random[..16].copy_from_slice(Uuid::new_v4().as_bytes());
+        random[16..].copy_from_slice(Uuid::new_v4().as_bytes());
+
+        let verifier = URL_SAFE_NO_PAD.encode(random);
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+        Self {
+            verifier,
+            challenge,
+        }
+    }
+
+    /// The secret sent with the token request.
+    pub fn verifier(&self) -> &str {
+        &self.verifier
+    }
+
+    /// The hashed verifier sent with the authorization request.
+    pub fn challenge(&self) -> &str {
+        &self.challenge
+    }
+
+    /// The challenge method.
+    pub fn method(&self) -> &'static str {
+        "S256"
+    }
+}
+
+/// Tokens held by a client, with the access token's absolute expiry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuthTokens {
+    /// Access token.
+    pub access_token: String,
+
+    /// Refresh token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+
+    /// Unix time, in seconds, at which the access token expires.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+
+    /// Granted scopes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl OAuthTokens {
+    /// Convert a token endpoint response, anchoring its lifetime at now.
+    pub fn from_response(response: TokenResponse) -> Self {
+        Self {
+            access_token: response.access_token,
+            refresh_token: response.refresh_token,
+            expires_at: response.expires_in.map(|secs| now() + secs),
+            scope: response.scope,
+        }
+    }
+
+    /// Whether the access token has expired or is about to.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|at| now() + EXPIRY_MARGIN_SECS >= at)
+    }
+}
+
+/// Persistent storage for a client's tokens.
+///
+/// Implement this to keep tokens in a keychain, file or database; the
+/// provider saves every token it obtains and clears tokens the server no
+/// longer accepts.
+#[async_trait]
+pub trait TokenStorage: Send + Sync + Debug {
+    /// Load the stored tokens, if any.
+    async fn load(&self) -> Result<Option<OAuthTokens>>;
+
+    /// Store newly obtained tokens.
+    async fn save(&self, tokens: &OAuthTokens) -> Result<()>;
+
+    /// Forget the stored tokens.
+    async fn clear(&self) -> Result<()>;
+}
+
+/// Token storage that lasts as long as the process.
+#[derive(Debug, Default)]
+pub struct InMemoryTokenStorage {
+    tokens: parking_lot::Mutex<Option<OAuthTokens>>,
+}
+
+impl InMemoryTokenStorage {
+    /// Create empty storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create storage holding `tokens`.
+    pub fn with_tokens(tokens: OAuthTokens) -> Self {
+        Self {
+            tokens: parking_lot::Mutex::new(Some(tokens)),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenStorage for InMemoryTokenStorage {
+    async fn load(&self) -> Result<Option<OAuthTokens>> {
+        Ok(self.tokens.lock().clone())
+    }
+
+    async fn save(&self, tokens: &OAuthTokens) -> Result<()> {
+        *self.tokens.lock() = Some(tokens.clone());
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<()> {
+        *self.tokens.lock() = None;
+        Ok(())
+    }
+}
+
+/// The parameters the authorization server redirected back with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationResponse {
+    /// Authorization code.
+    pub code: String,
+
+    /// State parameter, which must match the one sent.
+    pub state: Option<String>,
+}
+
+impl AuthorizationResponse {
+    /// Read the response from the URL the user was redirected to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::client::oauth::AuthorizationResponse;
+    /// use url::Url;
+    ///
+    /// let redirect = Url::parse("http://localhost/callback?code=abc&state=xyz").unwrap();
+    /// let response = AuthorizationResponse::from_redirect_url(&redirect).unwrap();
+    /// assert_eq!(response.code, "abc");
+    /// assert_eq!(response.state.as_deref(), Some("xyz"));
+    ///
+    /// let denied = Url::parse("http://localhost/callback?error=access_denied").unwrap();
+    /// assert!(AuthorizationResponse::from_redirect_url(&denied).is_err());
+    /// ```
+    pub fn from_redirect_url(url: &Url) -> Result<Self> {
+        let mut code = None;
+        let mut state = None;
+        let mut error = None;
+        let mut description = None;
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "code" => code = Some(value.into_owned()),
+                "state" => state = Some(value.into_owned()),
+                "error" => error = Some(value.into_owned()),
+                "error_description" => description = Some(value.into_owned()),
+                _ => {},
+            }
+        }
+
+        if let Some(error) = error {
+            return Err(Error::authentication(match description {
+                Some(description) => format!("Authorization failed: {} ({})", error, description),
+                None => format!("Authorization failed: {}", error),
+            }));
+        }
+        let code = code.ok_or_else(|| {
+            Error::authentication("Authorization redirect is missing the code parameter")
+        })?;
+        Ok(Self { code, state })
+    }
+}
+
+/// The user-facing step of the authorization code flow.
+#[async_trait]
+pub trait AuthorizationHandler: Send + Sync + Debug {
+    /// Send the user to `authorization_url` (for example by opening a
+    /// browser) and return what the authorization server redirected back
+    /// with.
+    async fn authorize(&self, authorization_url: Url) -> Result<AuthorizationResponse>;
+}
+
+/// Configuration for [`OAuthClientProvider`].
+#[derive(Debug, Clone)]
+pub struct OAuthClientConfig {
+    /// URL of the MCP server, also sent as the token's resource indicator.
+    pub server_url: Url,
+
+    /// Redirect URI registered for the client.
+    pub redirect_uri: String,
+
+    /// Pre-registered client ID; without one the client is registered
+    /// dynamically.
+    pub client_id: Option<String>,
+
+    /// Client secret for confidential clients.
+    pub client_secret: Option<String>,
+
+    /// Scopes to request.
+    pub scopes: Vec<String>,
+
+    /// Name sent with dynamic client registration.
+    pub client_name: String,
+}
+
+impl OAuthClientConfig {
+    /// Create a configuration for a public client of `server_url`.
+    pub fn new(server_url: Url, redirect_uri: impl Into<String>) -> Self {
+        Self {
+            server_url,
+            redirect_uri: redirect_uri.into(),
+            client_id: None,
+            client_secret: None,
+            scopes: Vec::new(),
+            client_name: "pmcp".to_string(),
+        }
+    }
+
+    /// Use a pre-registered client.
+    pub fn with_client(
+        mut self,
+        client_id: impl Into<String>,
+        client_secret: Option<String>,
+    ) -> Self {
+        self.client_id = Some(client_id.into());
+        self.client_secret = client_secret;
+        self
+    }
+
+    /// Set the scopes to request.
+    pub fn with_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the name sent with dynamic client registration.
+    pub fn with_client_name(mut self, name: impl Into<String>) -> Self {
+        self.client_name = name.into();
+        self
+    }
+}
+
+/// Client credentials, configured or obtained by registration.
+#[derive(Debug, Clone, Deserialize)]
+struct ClientCredentials {
+    client_id: String,
+    #[serde(default)]
+    client_secret: Option<String>,
+}
+
+/// What the provider has learned so far.
+#[derive(Debug, Default)]
+struct Session {
+    server: Option<AuthorizationServerMetadata>,
+    client: Option<ClientCredentials>,
+    tokens: Option<OAuthTokens>,
+    loaded: bool,
+}
+
+/// Authorizes requests to an MCP server with OAuth 2.1 and PKCE.
+///
+/// Tokens are obtained on first use, refreshed when they expire, and the
+/// whole flow is run again if the refresh token is rejected. Concurrent
+/// callers wait for a single flow rather than starting their own.
+#[derive(Debug)]
+pub struct OAuthClientProvider {
+    config: OAuthClientConfig,
+    handler: Arc<dyn AuthorizationHandler>,
+    storage: Arc<dyn TokenStorage>,
+    http: reqwest::Client,
+    session: tokio::sync::Mutex<Session>,
+}
+
+impl OAuthClientProvider {
+    /// Create a provider that keeps tokens in memory.
+    pub fn new(config: OAuthClientConfig, handler: impl AuthorizationHandler + 'static) -> Self {
+        Self {
+            config,
+            handler: Arc::new(handler),
+            storage: Arc::new(InMemoryTokenStorage::new()),
+            http: reqwest::Client::new(),
+            session: tokio::sync::Mutex::new(Session::default()),
+        }
+    }
+
+    /// Keep tokens in `storage` instead of memory.
+    pub fn with_storage(mut self, storage: Arc<dyn TokenStorage>) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// The current tokens, if any.
+    pub async fn tokens(&self) -> Result<Option<OAuthTokens>> {
+        let mut session = self.session.lock().await;
+        self.stored_tokens(&mut session).await
+    }
+
+    /// Run the authorization code flow now, replacing any current tokens.
+    pub async fn authorize(&self) -> Result<OAuthTokens> {
+        let mut session = self.session.lock().await;
+        self.run_authorization(&mut session, None).await
+    }
+
+    /// A valid access token, refreshing or authorizing as needed.
+    pub async fn access_token(&self) -> Result<String> {
+        let mut session = self.session.lock().await;
+        if let Some(tokens) = self.stored_tokens(&mut session).await? {
+            if !tokens.is_expired() {
+                return Ok(tokens.access_token);
+            }
+            if let Some(refreshed) = self.try_refresh(&mut session, &tokens, None).await? {
+                return Ok(refreshed.access_token);
+            }
+        }
+        Ok(self
+            .run_authorization(&mut session, None)
+            .await?
+            .access_token)
+    }
+
+    /// Replace the tokens the server rejected.
+    ///
+    /// `www_authenticate` is the rejection's `WWW-Authenticate` header; its
+    /// `resource_metadata` parameter, if present, is used for discovery.
+    pub async fn reauthorize(&self, www_authenticate: Option<&str>) -> Result<OAuthTokens> {
+        let hint = www_authenticate.and_then(resource_metadata_url);
+        let mut session = self.session.lock().await;
+        if let Some(tokens) = self.stored_tokens(&mut session).await? {
+            if let Some(refreshed) = self
+                .try_refresh(&mut session, &tokens, hint.as_deref())
+                .await?
+            {
+                return Ok(refreshed);
+            }
+        }
+        self.run_authorization(&mut session, hint.as_deref()).await
+    }
+
+    /// The resource indicator (RFC 8707) tokens are requested for.
+    fn resource(&self) -> &str {
+        self.config.server_url.as_str().trim_end_matches('/')
+    }
+
+    async fn stored_tokens(&self, session: &mut Session) -> Result<Option<OAuthTokens>> {
+        if !session.loaded {
+            session.tokens = self.storage.load().await?;
+            session.loaded = true;
+        }
+        Ok(session.tokens.clone())
+    }
+
+    async fn store_tokens(
+        &self,
+        session: &mut Session,
+        tokens: OAuthTokens,
+    ) -> Result<OAuthTokens> {
+        self.storage.save(&tokens).await?;
+        session.tokens = Some(tokens.clone());
+        session.loaded = true;
+        Ok(tokens)
+    }
+
+    /// Refresh `tokens`, returning `None` if they cannot be refreshed.
+    async fn try_refresh(
+        &self,
+        session: &mut Session,
+        tokens: &OAuthTokens,
+        hint: Option<&str>,
+    ) -> Result<Option<OAuthTokens>> {
+        let Some(refresh_token) = tokens.refresh_token.as_deref() else {
+            return Ok(None);
+        };
+
+        let server = self.server_metadata(session, hint).await?;
+        let client = self.client_credentials(session, &server).await?;
+        let scope = self.config.scopes.join(" ");
+        let mut params = vec![
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ];
+        if !scope.is_empty() {
+            params.push(("scope", &scope));
+        }
+
+        match self.request_tokens(&server, &client, params).await {
+            Ok(mut refreshed) => {
+                // Servers that don't rotate refresh tokens omit them
+                if refreshed.refresh_token.is_none() {
+                    refreshed.refresh_token = Some(refresh_token.to_string());
+                }
+                self.store_tokens(session, refreshed).await.map(Some)
+            },
+            Err(e) => {
+                tracing::warn!("Token refresh failed, authorizing again: {}", e);
+                self.storage.clear().await?;
+                session.tokens = None;
+                Ok(None)
+            },
+        }
+    }
+
+    async fn run_authorization(
+        &self,
+        session: &mut Session,
+        hint: Option<&str>,
+    ) -> Result<OAuthTokens> {
+        let server = self.server_metadata(session, hint).await?;
+        let client = self.client_credentials(session, &server).await?;
+        let pkce = PkceChallenge::new();
+        let state = Uuid::new_v4().to_string();
+
+        let mut url = Url::parse(&server.authorization_endpoint).map_err(|e| {
+            Error::protocol(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Invalid authorization endpoint: {}", e),
+            )
+        })?;
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("response_type", "code")
+                .append_pair("client_id", &client.client_id)
+                .append_pair("redirect_uri", &self.config.redirect_uri)
+                .append_pair("code_challenge", pkce.challenge())
+                .append_pair("code_challenge_method", pkce.method())
+                .append_pair("state", &state)
+                .append_pair("resource", self.resource());
+            if !self.config.scopes.is_empty() {
+                query.append_pair("scope", &self.config.scopes.join(" "));
+            }
+        }
+
+        let response = self.handler.authorize(url).await?;
+        if response.state.as_deref() != Some(state.as_str()) {
+            return Err(Error::authentication(
+                "Authorization response state does not match the request",
+            ));
+        }
+
+        let tokens = self
+            .request_tokens(
+                &server,
+                &client,
+                vec![
+                    ("grant_type", "authorization_code"),
+                    ("code", &response.code),
+                    ("redirect_uri", &self.config.redirect_uri),
+                    ("code_verifier", pkce.verifier()),
+                ],
+            )
+            .await?;
+        self.store_tokens(session, tokens).await
+    }
+
+    /// Post a token request, authenticating as `client`.
+    async fn request_tokens(
+        &self,
+        server: &AuthorizationServerMetadata,
+        client: &ClientCredentials,
+        mut params: Vec<(&str, &str)>,
+    ) -> Result<OAuthTokens> {
+        params.push(("client_id", &client.client_id));
+        params.push(("resource", self.resource()));
+
+        let mut request = self
+            .http
+            .post(&server.token_endpoint)
+            .header("Accept", "application/json")
+            .form(&params);
+        if let Some(secret) = &client.client_secret {
+            request = request.basic_auth(&client.client_id, Some(secret));
+        }
+
+        let response = request.send().await.map_err(|e| {
+            Error::protocol(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to reach token endpoint: {}", e),
+            )
+        })?;
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(Error::authentication(format!(
+                "Token request failed: {}",
+                error_text
+            )));
+        }
+
+        let response = response.json::<TokenResponse>().await.map_err(|e| {
+            Error::protocol(
+                ErrorCode::PARSE_ERROR,
+                format!("Failed to parse token response: {}", e),
+            )
+        })?;
+        Ok(OAuthTokens::from_response(response))
+    }
+
+    /// The authorization server's metadata, discovered on first use.
+    async fn server_metadata(
+        &self,
+        session: &mut Session,
+        hint: Option<&str>,
+    ) -> Result<AuthorizationServerMetadata> {
+        if let Some(server) = &session.server {
+            return Ok(server.clone());
+        }
+
+        let issuer = match self.resource_metadata(hint).await {
+            Ok(metadata) => metadata
+                .authorization_servers
+                .into_iter()
+                .next()
+                .ok_or_else(|| {
+                    Error::authentication("Protected resource lists no authorization servers")
+                })?,
+            Err(e) => {
+                // Servers predating RFC 9728 act as their own authorization server
+                tracing::debug!(
+                    "No protected resource metadata ({}), using server origin",
+                    e
+                );
+                self.config.server_url.origin().ascii_serialization()
+            },
+        };
+
+        let server = self.authorization_server_metadata(&issuer).await?;
+        if !server.code_challenge_methods_supported.is_empty()
+            && !server
+                .code_challenge_methods_supported
+                .iter()
+                .any(|method| method == "S256")
+        {
+            return Err(Error::authentication(
+                "Authorization server does not support S256 PKCE",
+            ));
+        }
+
+        session.server = Some(server.clone());
+        Ok(server)
+    }
+
+    async fn resource_metadata(&self, hint: Option<&str>) -> Result<ProtectedResourceMetadata> {
+        match hint {
+            Some(url) => self.get_json(url).await,
+            None => {
+                let origin = self.config.server_url.origin().ascii_serialization();
+                let mut last_error = None;
+                for path in ProtectedResourceMetadata::new(self.resource()).well_known_paths() {
+                    match self.get_json(&format!("{}{}", origin, path)).await {
+                        Ok(metadata) => return Ok(metadata),
+                        Err(e) => last_error = Some(e),
+                    }
+                }
+                Err(last_error.unwrap_or_else(|| {
+                    Error::protocol(
+                        ErrorCode::INTERNAL_ERROR,
+                        "Failed to fetch protected resource metadata",
+                    )
+                }))
+            },
+        }
+    }
+
+    async fn authorization_server_metadata(
+        &self,
+        issuer: &str,
+    ) -> Result<AuthorizationServerMetadata> {
+        let issuer_url = Url::parse(issuer).map_err(|e| {
+            Error::protocol(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Invalid authorization server URL: {}", e),
+            )
+        })?;
+        let origin = issuer_url.origin().ascii_serialization();
+        let path = issuer_url.path().trim_end_matches('/');
+
+        // RFC 8414 inserts the well-known path before the issuer's path;
+        // OpenID Connect appends it
+        let candidates = [
+            format!("{}{}{}", origin, AUTHORIZATION_SERVER_PATH, path),
+            format!("{}{}{}", origin, path, OPENID_CONFIGURATION_PATH),
+        ];
+
+        let mut last_error = None;
+        for url in &candidates {
+            match self.get_json(url).await {
+                Ok(metadata) => return Ok(metadata),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            Error::protocol(
+                ErrorCode::INTERNAL_ERROR,
+                "Failed to discover authorization server",
+            )
+        }))
+    }
+
+    /// The configured client, or one registered with the server.
+    async fn client_credentials(
+        &self,
+        session: &mut Session,
+        server: &AuthorizationServerMetadata,
+    ) -> Result<ClientCredentials> {
+        if let Some(client_id) = &self.config.client_id {
+            return Ok(ClientCredentials {
+                client_id: client_id.clone(),
+                client_secret: self.config.client_secret.clone(),
+            });
+        }
+        if let Some(client) = &session.client {
+            return Ok(client.clone());
+        }
+
+        let endpoint = server.registration_endpoint.as_deref().ok_or_else(|| {
+            Error::authentication(
+                "No client ID configured and the server does not support dynamic registration",
+            )
+        })?;
+        let registration = serde_json::json!({
+            "client_name": self.config.client_name,
+            "redirect_uris": [self.config.redirect_uri],
+            "grant_types": ["authorization_code", "refresh_token"],
+            "response_types": ["code"],
+            "token_endpoint_auth_method": "none",
+        });
+
+        let response = self
+            .http
+            .post(endpoint)
+            .json(&registration)
+            .send()
+            .await
+            .map_err(|e| {
+                Error::protocol(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to register client: {}", e),
+                )
+            })?;
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(Error::authentication(format!(
+                "Client registration failed: {}",
+                error_text
+            )));
+        }
+
+        let client = response.json::<ClientCredentials>().await.map_err(|e| {
+            Error::protocol(
+                ErrorCode::PARSE_ERROR,
+                format!("Failed to parse registration response: {}", e),
+            )
+        })?;
+        session.client = Some(client.clone());
+        Ok(client)
+    }
+
+    async fn get_json<D: DeserializeOwned>(&self, url: &str) -> Result<D> {
+        let response = self
+            .http
+            .get(url)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .map_err(|e| {
+                Error::protocol(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to fetch {}: {}", url, e),
+                )
+            })?;
+        if !response.status().is_success() {
+            return Err(Error::protocol(
+                ErrorCode::INTERNAL_ERROR,
+                format!("{} returned status: {}", url, response.status()),
+            ));
+        }
+        response.json::<D>().await.map_err(|e| {
+            Error::protocol(
+                ErrorCode::PARSE_ERROR,
+                format!("Failed to parse {}: {}", url, e),
+            )
+        })
+    }
+}
+
+#[cfg(feature = "streamable-http")]
+#[async_trait]
+impl crate::shared::streamable_http::AuthProvider for OAuthClientProvider {
+    async fn get_access_token(&self) -> Result<String> {
+        self.access_token().await
+    }
+
+    async fn handle_unauthorized(&self, www_authenticate: Option<&str>) -> Result<bool> {
+        self.reauthorize(www_authenticate).await?;
+        Ok(true)
+    }
+}
+
+/// Extract the `resource_metadata` parameter of a Bearer challenge.
+fn resource_metadata_url(www_authenticate: &str) -> Option<String> {
+    let (_, rest) = www_authenticate.split_once("resource_metadata=")?;
+    let value = match rest.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next()?,
+        None => rest.split([',', ' ']).next()?,
+    };
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    /// Plays the user: approves every request, echoing its state.
+    #[derive(Debug, Default, Clone)]
+    struct ApproveAll {
+        urls: Arc<parking_lot::Mutex<Vec<Url>>>,
+    }
+
+    #[async_trait]
+    impl AuthorizationHandler for ApproveAll {
+        async fn authorize(&self, authorization_url: Url) -> Result<AuthorizationResponse> {
+            let state = authorization_url
+                .query_pairs()
+                .find(|(key, _)| key == "state")
+                .map(|(_, value)| value.into_owned());
+            self.urls.lock().push(authorization_url);
+            Ok(AuthorizationResponse {
+                code: "the-code".to_string(),
+                state,
+            })
+        }
+    }
+
+    #[test]
+    fn test_resource_metadata_url_from_challenge() {
+        assert_eq!(
+            resource_metadata_url(
+                r#"Bearer error="invalid_token", resource_metadata="https://mcp.example.com/.well-known/oauth-protected-resource""#
+            )
+            .as_deref(),
+            Some("https://mcp.example.com/.well-known/oauth-protected-resource")
+        );
+        assert_eq!(
+            resource_metadata_url("Bearer resource_metadata=https://a.example/rm, scope=x")
+                .as_deref(),
+            Some("https://a.example/rm")
+        );
+        assert_eq!(resource_metadata_url(r#"Bearer realm="mcp""#), None);
+    }
+
+    #[test]
+    fn test_tokens_expire_early() {
+        let tokens = OAuthTokens::from_response(TokenResponse {
+            access_token: "a".to_string(),
+            token_type: "Bearer".to_string(),
+            expires_in: Some(EXPIRY_MARGIN_SECS / 2),
+            refresh_token: None,
+            scope: None,
+        });
+        assert!(tokens.is_expired());
+
+        let forever = OAuthTokens {
+            expires_at: None,
+            ..tokens
+        };
+        assert!(!forever.is_expired());
+    }
+
+    #[tokio::test]
+    async fn test_discovers_registers_authorizes_and_refreshes() {
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+
+        let _resource = server
+            .mock("GET", "/.well-known/oauth-protected-resource/mcp")
+            .with_body(
+                serde_json::json!({
+                    "resource": format!("{}/mcp", base),
+                    "authorization_servers": [format!("{}/auth", base)],
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let _metadata = server
+            .mock("GET", "/.well-known/oauth-authorization-server/auth")
+            .with_body(
+                serde_json::json!({
+                    "issuer": format!("{}/auth", base),
+                    "authorization_endpoint": format!("{}/auth/authorize", base),
+                    "token_endpoint": format!("{}/auth/token", base),
+                    "registration_endpoint": format!("{}/auth/register", base),
+                    "code_challenge_methods_supported": ["S256"],
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let register = server
+            .mock("POST", "/auth/register")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "redirect_uris": ["http://localhost/callback"],
+            })))
+            .with_status(201)
+            .with_body(r#"{"client_id": "registered"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let exchange = server
+            .mock("POST", "/auth/token")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("grant_type".into(), "authorization_code".into()),
+                Matcher::UrlEncoded("code".into(), "the-code".into()),
+                Matcher::UrlEncoded("client_id".into(), "registered".into()),
+                Matcher::UrlEncoded("resource".into(), format!("{}/mcp", base)),
+                Matcher::Regex("code_verifier=".into()),
+            ]))
+            .with_body(
+                r#"{"access_token": "first", "token_type": "Bearer", "expires_in": 3600, "refresh_token": "r1"}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let refresh = server
+            .mock("POST", "/auth/token")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("grant_type".into(), "refresh_token".into()),
+                Matcher::UrlEncoded("refresh_token".into(), "r1".into()),
+            ]))
+            .with_body(r#"{"access_token": "second", "token_type": "Bearer"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let storage = Arc::new(InMemoryTokenStorage::new());
+        let config = OAuthClientConfig::new(
+            Url::parse(&format!("{}/mcp", base)).unwrap(),
+            "http://localhost/callback",
+        )
+        .with_scopes(["mcp:tools"]);
+        let user = ApproveAll::default();
+        let provider = OAuthClientProvider::new(config, user.clone()).with_storage(storage.clone());
+
+        assert_eq!(provider.access_token().await.unwrap(), "first");
+        // The token is cached rather than requested again
+        assert_eq!(provider.access_token().await.unwrap(), "first");
+
+        let refreshed = provider
+            .reauthorize(Some(r#"Bearer error="invalid_token""#))
+            .await
+            .unwrap();
+        assert_eq!(refreshed.access_token, "second");
+        assert_eq!(refreshed.refresh_token.as_deref(), Some("r1"));
+        assert_eq!(storage.load().await.unwrap(), Some(refreshed));
+
+        register.assert_async().await;
+        exchange.assert_async().await;
+        refresh.assert_async().await;
+
+        // Refreshing didn't send the user through authorization again
+        let urls = user.urls.lock();
+        assert_eq!(urls.len(), 1);
+        let query: std::collections::HashMap<_, _> = urls[0].query_pairs().into_owned().collect();
+        assert_eq!(query["client_id"], "registered");
+        assert_eq!(query["code_challenge_method"], "S256");
+        assert_eq!(query["scope"], "mcp:tools");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_mismatched_state() {
+        #[derive(Debug)]
+        struct WrongState;
+
+        #[async_trait]
+        impl AuthorizationHandler for WrongState {
+            async fn authorize(&self, _url: Url) -> Result<AuthorizationResponse> {
+                Ok(AuthorizationResponse {
+                    code: "the-code".to_string(),
+                    state: Some("forged".to_string()),
+                })
+            }
+        }
+
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+        let _metadata = server
+            .mock("GET", AUTHORIZATION_SERVER_PATH)
+            .with_body(
+                serde_json::json!({
+                    "issuer": base,
+                    "authorization_endpoint": format!("{}/authorize", base),
+                    "token_endpoint": format!("{}/token", base),
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let token = server.mock("POST", "/token").expect(0).create_async().await;
+
+        // No protected resource metadata: the server's origin is the issuer
+        let config = OAuthClientConfig::new(
+            Url::parse(&format!("{}/mcp", base)).unwrap(),
+            "http://localhost/callback",
+        )
+        .with_client("preregistered", None);
+        let provider = OAuthClientProvider::new(config, WrongState);
+
+        let err = provider.authorize().await.unwrap_err();
+        assert!(err.to_string().contains("state"));
+        token.assert_async().await;
+    }
+}
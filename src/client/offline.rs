@@ -0,0 +1,291 @@
+//! Offline request queue.
+//!
+//! Clients on intermittent connections (laptops, mobile browsers) can opt in
+//! to queueing requests while the transport is disconnected instead of
+//! failing them. Requests submitted with [`Client::send_or_queue`] are held
+//! in a bounded queue and sent once [`Client::reconnect`] has restored the
+//! session. Requests that wait longer than the configured expiry fail with a
+//! timeout error.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::client::offline::OfflineQueueConfig;
+//! use pmcp::types::{CallToolRequest, ClientRequest};
+//! use pmcp::{ClientBuilder, ClientCapabilities, StdioTransport};
+//! use serde_json::json;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let mut client = ClientBuilder::new(StdioTransport::new())
+//!     .offline_queue(
+//!         OfflineQueueConfig::default()
+//!             .with_capacity(20)
+//!             .with_expiry(Duration::from_secs(60)),
+//!     )
+//!     .build();
+//! client.initialize(ClientCapabilities::default()).await?;
+//!
+//! // Sent now if connected, otherwise held until `reconnect` succeeds
+//! let pending = client
+//!     .send_or_queue(ClientRequest::CallTool(CallToolRequest::new(
+//!         "save_note",
+//!         json!({ "text": "hello" }),
+//!     )))
+//!     .await?;
+//!
+//! client.reconnect(StdioTransport::new()).await?;
+//! let response = pending.await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Client::send_or_queue`]: crate::Client::send_or_queue
+//! [`Client::reconnect`]: crate::Client::reconnect
+
+use crate::error::{Error, Result};
+use crate::types::{ClientRequest, JSONRPCResponse};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::sync::oneshot;
+
+#[cfg(target_arch = "wasm32")]
+use futures_channel::oneshot;
+
+/// Default maximum number of queued requests.
+pub const DEFAULT_CAPACITY: usize = 100;
+
+/// Default time a request may wait in the queue.
+pub const DEFAULT_EXPIRY: Duration = Duration::from_secs(300);
+
+/// Limits for the offline request queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OfflineQueueConfig {
+    capacity: usize,
+    expiry: Duration,
+}
+
+impl Default for OfflineQueueConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            expiry: DEFAULT_EXPIRY,
+        }
+    }
+}
+
+impl OfflineQueueConfig {
+    /// Set the maximum number of queued requests.
+    ///
+    /// Requests submitted while the queue is full are rejected.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set how long a request may wait before it fails with a timeout.
+    pub fn with_expiry(mut self, expiry: Duration) -> Self {
+        self.expiry = expiry;
+        self
+    }
+
+    /// Maximum number of queued requests.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Maximum time a request may wait in the queue.
+    pub fn expiry(&self) -> Duration {
+        self.expiry
+    }
+}
+
+/// A request waiting for the connection to come back.
+#[derive(Debug)]
+pub(crate) struct QueuedRequest {
+    pub(crate) request: ClientRequest,
+    queued_at: u64,
+    reply: oneshot::Sender<Result<JSONRPCResponse>>,
+}
+
+impl QueuedRequest {
+    /// Deliver the outcome to the caller awaiting the [`QueuedResponse`].
+    pub(crate) fn complete(self, result: Result<JSONRPCResponse>) {
+        let _ = self.reply.send(result);
+    }
+}
+
+/// Bounded FIFO of requests submitted while disconnected.
+#[derive(Debug)]
+pub(crate) struct OfflineQueue {
+    config: OfflineQueueConfig,
+    entries: Mutex<VecDeque<QueuedRequest>>,
+}
+
+impl OfflineQueue {
+    pub(crate) fn new(config: OfflineQueueConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Number of requests waiting.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Queue a request, returning the future that resolves once it is sent.
+    pub(crate) fn push(&self, request: ClientRequest) -> Result<QueuedResponse> {
+        let mut entries = self.entries.lock();
+        self.expire(&mut entries);
+        if entries.len() >= self.config.capacity {
+            return Err(Error::invalid_state(format!(
+                "Offline queue is full ({} requests)",
+                self.config.capacity
+            )));
+        }
+
+        let (reply, receiver) = oneshot::channel();
+        entries.push_back(QueuedRequest {
+            request,
+            queued_at: crate::shared::runtime::timestamp_millis(),
+            reply,
+        });
+        Ok(QueuedResponse::pending(receiver))
+    }
+
+    /// Take the oldest request that has not expired.
+    pub(crate) fn pop(&self) -> Option<QueuedRequest> {
+        let mut entries = self.entries.lock();
+        self.expire(&mut entries);
+        entries.pop_front()
+    }
+
+    /// Put a request back at the front after a failed send.
+    pub(crate) fn requeue(&self, entry: QueuedRequest) {
+        self.entries.lock().push_front(entry);
+    }
+
+    /// Fail requests that have waited longer than the expiry.
+    fn expire(&self, entries: &mut VecDeque<QueuedRequest>) {
+        let expiry_ms = u64::try_from(self.config.expiry.as_millis()).unwrap_or(u64::MAX);
+        let now = crate::shared::runtime::timestamp_millis();
+        while entries
+            .front()
+            .is_some_and(|entry| now.saturating_sub(entry.queued_at) >= expiry_ms)
+        {
+            if let Some(entry) = entries.pop_front() {
+                tracing::debug!("Dropping expired offline request");
+                entry.complete(Err(Error::timeout(expiry_ms)));
+            }
+        }
+    }
+}
+
+/// Response to a request submitted with [`Client::send_or_queue`].
+///
+/// Resolves immediately if the request was sent right away, or once the
+/// request has been flushed after a reconnect. Fails with a timeout if the
+/// request expired in the queue, and with [`Error::Cancelled`] if the client
+/// was dropped first.
+///
+/// [`Client::send_or_queue`]: crate::Client::send_or_queue
+#[derive(Debug)]
+pub struct QueuedResponse {
+    state: ResponseState,
+}
+
+#[derive(Debug)]
+enum ResponseState {
+    Ready(Option<Result<JSONRPCResponse>>),
+    Pending(oneshot::Receiver<Result<JSONRPCResponse>>),
+}
+
+impl QueuedResponse {
+    pub(crate) fn ready(result: Result<JSONRPCResponse>) -> Self {
+        Self {
+            state: ResponseState::Ready(Some(result)),
+        }
+    }
+
+    fn pending(receiver: oneshot::Receiver<Result<JSONRPCResponse>>) -> Self {
+        Self {
+            state: ResponseState::Pending(receiver),
+        }
+    }
+
+    /// Whether the request is still waiting in the queue.
+    pub fn is_queued(&self) -> bool {
+        matches!(self.state, ResponseState::Pending(_))
+    }
+}
+
+impl Future for QueuedResponse {
+    type Output = Result<JSONRPCResponse>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut self.state {
+            ResponseState::Ready(result) => Poll::Ready(
+                result
+                    .take()
+                    .unwrap_or_else(|| Err(Error::invalid_state("Response already taken"))),
+            ),
+            ResponseState::Pending(receiver) => Pin::new(receiver)
+                .poll(cx)
+                .map(|result| result.unwrap_or_else(|_| Err(Error::cancelled()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RequestId;
+    use serde_json::json;
+
+    fn ping() -> ClientRequest {
+        ClientRequest::Ping
+    }
+
+    #[tokio::test]
+    async fn test_queue_is_bounded_and_fifo() {
+        let queue = OfflineQueue::new(OfflineQueueConfig::default().with_capacity(2));
+        let first = queue.push(ping()).unwrap();
+        let _second = queue.push(ping()).unwrap();
+        assert!(first.is_queued());
+
+        let err = queue.push(ping()).unwrap_err();
+        assert!(err.to_string().contains("full"), "{}", err);
+        assert_eq!(queue.len(), 2);
+
+        let entry = queue.pop().unwrap();
+        entry.complete(Ok(JSONRPCResponse::success(
+            RequestId::from(1i64),
+            json!({}),
+        )));
+        assert_eq!(first.await.unwrap().id, RequestId::from(1i64));
+
+        let entry = queue.pop().unwrap();
+        queue.requeue(entry);
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_requests_time_out() {
+        let queue = OfflineQueue::new(OfflineQueueConfig::default().with_expiry(Duration::ZERO));
+        let pending = queue.push(ping()).unwrap();
+        assert!(queue.pop().is_none());
+        assert!(matches!(pending.await, Err(Error::Timeout(0))));
+
+        let dropped = queue.push(ping()).unwrap();
+        drop(queue);
+        assert!(matches!(dropped.await, Err(Error::Cancelled)));
+    }
+}
@@ -0,0 +1,281 @@
+//! Typed tool calls, the client-side counterpart of
+//! [`TypedToolWithOutput`](crate::server::typed_tool::TypedToolWithOutput).
+
+use super::Client;
+use crate::error::{Error, Result};
+use crate::shared::{RequestOptions, Transport};
+use crate::types::{CallToolRequest, CallToolResult, Content};
+use crate::utils::validation::schema_violations;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Longest excerpt of a mismatched result quoted in error messages.
+const RESULT_EXCERPT_LEN: usize = 200;
+
+impl<T: Transport> Client<T> {
+    /// Call a tool with a typed input and decode its result into `O`.
+    ///
+    /// `input` is serialized to the tool's arguments. If the tool's input
+    /// schema is known from a previous [`list_tools`](Self::list_tools), the
+    /// arguments are checked against it before the request is sent; with the
+    /// `validation` feature the full schema is enforced, otherwise only
+    /// required properties are checked.
+    ///
+    /// The result is decoded from the tool's `structuredContent`, falling back
+    /// to a single text content item holding JSON for servers that do not
+    /// send structured content.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Client, ClientCapabilities, StdioTransport};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Serialize)]
+    /// struct AddInput {
+    ///     a: f64,
+    ///     b: f64,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct AddOutput {
+    ///     sum: f64,
+    /// }
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let mut client = Client::new(StdioTransport::new());
+    /// client.initialize(ClientCapabilities::default()).await?;
+    /// client.list_tools(None).await?; // caches input schemas for validation
+    ///
+    /// let output: AddOutput = client
+    ///     .call_tool_typed("add", &AddInput { a: 1.0, b: 2.0 })
+    ///     .await?;
+    /// assert_eq!(output.sum, 3.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `input` cannot be serialized or does not match the tool's schema
+    /// - The tool call fails or the tool reports an error
+    /// - The result cannot be decoded into `O`
+    pub async fn call_tool_typed<I, O>(&self, name: &str, input: &I) -> Result<O>
+    where
+        I: Serialize + ?Sized,
+        O: DeserializeOwned,
+    {
+        let arguments = serde_json::to_value(input).map_err(|e| {
+            Error::validation(format!("Cannot serialize input for tool '{}': {}", name, e))
+        })?;
+
+        let schema = self.tool_schemas.read().await.get(name).cloned();
+        if let Some(schema) = schema {
+            check_input(name, &schema, &arguments)?;
+        }
+
+        let result = self
+            .call_tool_with_options(
+                CallToolRequest::new(name, arguments),
+                &RequestOptions::default(),
+            )
+            .await?;
+        decode_output(name, result)
+    }
+}
+
+/// Check tool arguments against the tool's input schema.
+fn check_input(name: &str, schema: &Value, arguments: &Value) -> Result<()> {
+    let problems = schema_violations(schema, arguments);
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::validation(format!(
+            "Input for tool '{}' does not match its schema: {}",
+            name,
+            problems.join("; ")
+        )))
+    }
+}
+
+/// Check a tool result's structured content against the tool's output schema.
+///
+/// Error results are not checked; they carry no structured content.
+pub(super) fn check_output(name: &str, schema: &Value, result: &CallToolResult) -> Result<()> {
+    if result.is_error {
+        return Ok(());
+    }
+    let Some(structured) = &result.structured_content else {
+        return Err(Error::validation(format!(
+            "Tool '{}' declares an output schema but returned no structured content",
+            name
+        )));
+    };
+
+    let problems = schema_violations(schema, structured);
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::validation(format!(
+            "Output of tool '{}' does not match its output schema: {} (result: {})",
+            name,
+            problems.join("; "),
+            excerpt(structured)
+        )))
+    }
+}
+
+/// Decode a tool result into the caller's output type.
+fn decode_output<O: DeserializeOwned>(name: &str, result: CallToolResult) -> Result<O> {
+    if result.is_error {
+        return Err(Error::internal(format!(
+            "Tool '{}' failed: {}",
+            name,
+            result.display()
+        )));
+    }
+
+    let value = match result.structured_content {
+        Some(value) => value,
+        None => match result.content.as_slice() {
+            [Content::Text { text }] => serde_json::from_str(text).map_err(|e| {
+                Error::parse(format!(
+                    "Tool '{}' returned no structured content and its text is not JSON: {}",
+                    name, e
+                ))
+            })?,
+            _ => {
+                return Err(Error::parse(format!(
+                    "Tool '{}' returned no structured content",
+                    name
+                )))
+            },
+        },
+    };
+
+    serde_json::from_value(value.clone()).map_err(|e| {
+        Error::parse(format!(
+            "Result of tool '{}' does not match {}: {} (result: {})",
+            name,
+            std::any::type_name::<O>(),
+            e,
+            excerpt(&value)
+        ))
+    })
+}
+
+/// Compact JSON rendering of a value, truncated for error messages.
+fn excerpt(value: &Value) -> String {
+    let text = value.to_string();
+    match text.char_indices().nth(RESULT_EXCERPT_LEN) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Sum {
+        sum: i64,
+    }
+
+    fn result(content: Vec<Content>, structured: Option<Value>) -> CallToolResult {
+        CallToolResult {
+            content,
+            is_error: false,
+            structured_content: structured,
+        }
+    }
+
+    #[test]
+    fn test_check_input_reports_missing_required() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"a": {"type": "number"}, "b": {"type": "number"}},
+            "required": ["a", "b"]
+        });
+        assert!(check_input("add", &schema, &json!({"a": 1, "b": 2})).is_ok());
+
+        let err = check_input("add", &schema, &json!({"a": 1})).unwrap_err();
+        assert!(err.to_string().contains("'b'"), "{}", err);
+        assert!(err.to_string().contains("'add'"), "{}", err);
+    }
+
+    #[test]
+    fn test_check_output_reports_mismatches() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"sum": {"type": "integer"}},
+            "required": ["sum"]
+        });
+        assert!(check_output("add", &schema, &result(vec![], Some(json!({"sum": 3})))).is_ok());
+
+        let err =
+            check_output("add", &schema, &result(vec![], Some(json!({"total": 3})))).unwrap_err();
+        assert!(err.to_string().contains("'sum'"), "{}", err);
+        assert!(err.to_string().contains(r#"{"total":3}"#), "{}", err);
+
+        let err = check_output("add", &schema, &result(vec![], None)).unwrap_err();
+        assert!(err.to_string().contains("no structured content"), "{}", err);
+
+        let failed = CallToolResult {
+            is_error: true,
+            ..result(vec![], None)
+        };
+        assert!(check_output("add", &schema, &failed).is_ok());
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn test_check_output_enforces_types() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"sum": {"type": "integer"}},
+            "required": ["sum"]
+        });
+        let err =
+            check_output("add", &schema, &result(vec![], Some(json!({"sum": "3"})))).unwrap_err();
+        assert!(err.to_string().contains("/sum"), "{}", err);
+    }
+
+    #[test]
+    fn test_decode_output_sources() {
+        let structured = result(vec![], Some(json!({"sum": 3})));
+        assert_eq!(
+            decode_output::<Sum>("add", structured).unwrap(),
+            Sum { sum: 3 }
+        );
+
+        let text = result(
+            vec![Content::Text {
+                text: r#"{"sum": 4}"#.to_string(),
+            }],
+            None,
+        );
+        assert_eq!(decode_output::<Sum>("add", text).unwrap(), Sum { sum: 4 });
+
+        let mismatch = result(vec![], Some(json!({"total": 3})));
+        let err = decode_output::<Sum>("add", mismatch).unwrap_err();
+        assert!(err.to_string().contains("missing field `sum`"), "{}", err);
+        assert!(err.to_string().contains(r#"{"total":3}"#), "{}", err);
+
+        let failed = CallToolResult {
+            is_error: true,
+            ..result(
+                vec![Content::Text {
+                    text: "boom".to_string(),
+                }],
+                None,
+            )
+        };
+        let err = decode_output::<Sum>("add", failed).unwrap_err();
+        assert!(err.to_string().contains("boom"), "{}", err);
+    }
+}
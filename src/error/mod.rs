@@ -2,6 +2,30 @@
 //!
 //! This module provides a comprehensive error type that covers all possible
 //! failure modes in the MCP protocol.
+//!
+//! Underlying errors from I/O, HTTP, and WebSocket libraries are kept as the
+//! error [`source`](std::error::Error::source), and [`Error::is_retryable`] and
+//! [`Error::is_fatal_for_connection`] classify failures so callers can decide
+//! whether to retry or reconnect without inspecting messages.
+//! [`Error::category`] groups failures into an [`ErrorCategory`] that
+//! reconnection logic uses to avoid retrying failures that cannot clear up
+//! on their own, such as rejected credentials.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::Error;
+//! use std::io;
+//!
+//! let err = Error::from(io::Error::new(io::ErrorKind::ConnectionReset, "reset by peer"));
+//! assert!(err.is_retryable());
+//! assert!(err.is_fatal_for_connection());
+//! assert_eq!(err.io_error().map(io::Error::kind), Some(io::ErrorKind::ConnectionReset));
+//!
+//! let err = Error::validation("missing field");
+//! assert!(!err.is_retryable());
+//! assert!(!err.is_fatal_for_connection());
+//! ```
 
 pub mod recovery;
 
@@ -49,6 +73,15 @@ pub enum Error {
     #[error("Capability not supported: {0}")]
     UnsupportedCapability(String),
 
+    /// A feature is missing from the protocol version agreed on
+    #[error("{feature} needs protocol version {} or later, but {negotiated} was negotiated", .feature.since())]
+    UnsupportedProtocolFeature {
+        /// The feature used
+        feature: crate::shared::protocol::ProtocolFeature,
+        /// The version agreed on
+        negotiated: String,
+    },
+
     /// Internal errors
     #[error("Internal error: {0}")]
     Internal(String),
@@ -73,11 +106,42 @@ pub enum Error {
     #[error("Circuit breaker is open")]
     CircuitBreakerOpen,
 
+    /// The connection was lost while a request awaited its response, and the
+    /// request was not resent (holds the request's method)
+    #[error("Connection lost before {0} was answered")]
+    RequestInterrupted(String),
+
     /// Other errors
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+/// Broad cause of a failure, used to decide whether reconnecting can help.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Network trouble that may clear up, such as resets, refusals, and timeouts.
+    TransientNetwork,
+    /// The peer sent or expected something that is not valid MCP.
+    ProtocolViolation,
+    /// Credentials were missing, rejected, or insufficient.
+    AuthFailure,
+    /// The peer closed the connection or went away.
+    PeerGone,
+    /// A failure that says nothing about the connection, such as an
+    /// application error returned by a tool.
+    Other,
+}
+
+impl ErrorCategory {
+    /// Whether re-establishing the connection may resolve the failure.
+    ///
+    /// Authentication failures and protocol violations recur on a fresh
+    /// connection, so reconnecting after them only loops.
+    pub fn is_recoverable(self) -> bool {
+        !matches!(self, Self::AuthFailure | Self::ProtocolViolation)
+    }
+}
+
 /// JSON-RPC error code for custom errors.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ErrorCode(pub i32);
@@ -135,7 +199,7 @@ impl std::hash::Hash for ErrorCode {
 pub enum TransportError {
     /// IO error
     #[error("IO error: {0}")]
-    Io(String),
+    Io(#[source] std::io::Error),
 
     /// Connection closed
     #[error("Connection closed")]
@@ -161,6 +225,10 @@ pub enum TransportError {
     #[error("Send error: {0}")]
     Send(String),
 
+    /// The server ended the session; a new one must be initialized
+    #[error("Session expired or terminated by the server")]
+    SessionExpired,
+
     /// WebSocket error (when feature enabled)
     #[cfg(feature = "websocket")]
     #[error("WebSocket error: {0}")]
@@ -170,20 +238,170 @@ pub enum TransportError {
     #[cfg(feature = "http")]
     #[error("HTTP error: {0}")]
     Http(String),
+
+    /// HTTP client error
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("HTTP request failed: {0}")]
+    Reqwest(#[from] reqwest::Error),
 }
 
 impl From<std::io::Error> for TransportError {
     fn from(err: std::io::Error) -> Self {
-        Self::Io(err.to_string())
+        Self::Io(err)
     }
 }
 
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
-        Self::Transport(TransportError::Io(err.to_string()))
+        Self::Transport(TransportError::Io(err))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Transport(TransportError::Reqwest(err))
     }
 }
 
+impl TransportError {
+    /// Broad cause of this failure.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::Io(err) => io_kind_category(err.kind()),
+            Self::ConnectionClosed | Self::Send(_) | Self::SessionExpired => {
+                ErrorCategory::PeerGone
+            },
+            Self::InvalidMessage(_) | Self::Serialization(_) | Self::Deserialization(_) => {
+                ErrorCategory::ProtocolViolation
+            },
+            Self::Request(_) => ErrorCategory::TransientNetwork,
+            #[cfg(feature = "websocket")]
+            Self::WebSocket(err) => {
+                use tokio_tungstenite::tungstenite::Error as WsError;
+                match err {
+                    WsError::Io(err) => io_kind_category(err.kind()),
+                    WsError::ConnectionClosed | WsError::AlreadyClosed => ErrorCategory::PeerGone,
+                    WsError::Tls(_) => ErrorCategory::TransientNetwork,
+                    WsError::Http(response) => http_status_category(response.status().as_u16()),
+                    _ => ErrorCategory::ProtocolViolation,
+                }
+            },
+            #[cfg(feature = "http")]
+            Self::Http(_) => ErrorCategory::TransientNetwork,
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Reqwest(err) => match err.status() {
+                Some(status) => http_status_category(status.as_u16()),
+                None if err.is_decode() => ErrorCategory::ProtocolViolation,
+                None => ErrorCategory::TransientNetwork,
+            },
+        }
+    }
+
+    /// Whether retrying the failed operation may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Io(err) => io_kind_is_transient(err.kind()),
+            Self::ConnectionClosed | Self::Send(_) | Self::Request(_) => true,
+            // Retrying only succeeds after initializing a new session
+            Self::InvalidMessage(_)
+            | Self::Serialization(_)
+            | Self::Deserialization(_)
+            | Self::SessionExpired => false,
+            #[cfg(feature = "websocket")]
+            Self::WebSocket(err) => {
+                use tokio_tungstenite::tungstenite::Error as WsError;
+                match err {
+                    WsError::Io(err) => io_kind_is_transient(err.kind()),
+                    WsError::ConnectionClosed | WsError::AlreadyClosed => true,
+                    _ => false,
+                }
+            },
+            #[cfg(feature = "http")]
+            Self::Http(_) => true,
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Reqwest(err) => {
+                err.is_timeout()
+                    || err.is_connect()
+                    || err
+                        .status()
+                        .is_some_and(|s| s.is_server_error() || s.as_u16() == 429)
+            },
+        }
+    }
+
+    /// Whether the connection is unusable and must be re-established.
+    pub fn is_fatal_for_connection(&self) -> bool {
+        match self {
+            Self::Io(err) => !matches!(
+                err.kind(),
+                std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::WouldBlock
+                    | std::io::ErrorKind::TimedOut
+            ),
+            Self::ConnectionClosed | Self::Send(_) | Self::SessionExpired => true,
+            Self::InvalidMessage(_)
+            | Self::Serialization(_)
+            | Self::Deserialization(_)
+            | Self::Request(_) => false,
+            #[cfg(feature = "websocket")]
+            Self::WebSocket(err) => {
+                use tokio_tungstenite::tungstenite::Error as WsError;
+                matches!(
+                    err,
+                    WsError::Io(_)
+                        | WsError::ConnectionClosed
+                        | WsError::AlreadyClosed
+                        | WsError::Protocol(_)
+                        | WsError::Tls(_)
+                )
+            },
+            #[cfg(feature = "http")]
+            Self::Http(_) => false,
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Reqwest(err) => err.is_connect(),
+        }
+    }
+}
+
+/// Category of an I/O failure.
+fn io_kind_category(kind: std::io::ErrorKind) -> ErrorCategory {
+    use std::io::ErrorKind;
+    match kind {
+        ErrorKind::BrokenPipe | ErrorKind::UnexpectedEof => ErrorCategory::PeerGone,
+        ErrorKind::InvalidData => ErrorCategory::ProtocolViolation,
+        _ => ErrorCategory::TransientNetwork,
+    }
+}
+
+/// Category of a failed HTTP status.
+#[cfg(any(feature = "websocket", not(target_arch = "wasm32")))]
+fn http_status_category(status: u16) -> ErrorCategory {
+    match status {
+        401 | 403 | 407 => ErrorCategory::AuthFailure,
+        404 | 410 => ErrorCategory::PeerGone,
+        408 | 429 | 500..=599 => ErrorCategory::TransientNetwork,
+        _ => ErrorCategory::ProtocolViolation,
+    }
+}
+
+/// Whether an I/O error kind indicates a condition that may clear on retry.
+fn io_kind_is_transient(kind: std::io::ErrorKind) -> bool {
+    use std::io::ErrorKind;
+    matches!(
+        kind,
+        ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::ConnectionRefused
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+            | ErrorKind::TimedOut
+            | ErrorKind::Interrupted
+            | ErrorKind::WouldBlock
+            | ErrorKind::UnexpectedEof
+    )
+}
+
 impl Error {
     /// Create a new internal error.
     pub fn internal(message: impl Into<String>) -> Self {
@@ -205,6 +423,7 @@ impl Error {
             Self::Protocol { code, .. } => Some(*code),
             Self::Timeout(_) => Some(ErrorCode::REQUEST_TIMEOUT),
             Self::Authentication(_) => Some(ErrorCode::AUTHENTICATION_REQUIRED),
+            Self::UnsupportedProtocolFeature { .. } => Some(ErrorCode::UNSUPPORTED_CAPABILITY),
             Self::RateLimited => Some(ErrorCode::RATE_LIMITED),
             Self::CircuitBreakerOpen => Some(ErrorCode::CIRCUIT_BREAKER_OPEN),
             _ => None,
@@ -300,6 +519,118 @@ impl Error {
             data: None,
         }
     }
+
+    /// Broad cause of this failure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::{Error, ErrorCategory};
+    ///
+    /// let err = Error::authentication("token expired");
+    /// assert_eq!(err.category(), ErrorCategory::AuthFailure);
+    /// assert!(!err.category().is_recoverable());
+    /// ```
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::Transport(err) => err.category(),
+            Self::Authentication(_) => ErrorCategory::AuthFailure,
+            Self::Timeout(_) | Self::RateLimited => ErrorCategory::TransientNetwork,
+            Self::RequestInterrupted(_) => ErrorCategory::PeerGone,
+            Self::Serialization(_) => ErrorCategory::ProtocolViolation,
+            Self::Protocol { code, .. } => match *code {
+                ErrorCode::AUTHENTICATION_REQUIRED | ErrorCode::PERMISSION_DENIED => {
+                    ErrorCategory::AuthFailure
+                },
+                ErrorCode::PARSE_ERROR | ErrorCode::INVALID_REQUEST => {
+                    ErrorCategory::ProtocolViolation
+                },
+                ErrorCode::REQUEST_TIMEOUT | ErrorCode::RATE_LIMITED => {
+                    ErrorCategory::TransientNetwork
+                },
+                _ => ErrorCategory::Other,
+            },
+            Self::Other(_) => self
+                .io_error()
+                .map_or(ErrorCategory::Other, |err| io_kind_category(err.kind())),
+            _ => ErrorCategory::Other,
+        }
+    }
+
+    /// Whether retrying the failed operation may succeed.
+    ///
+    /// True for timeouts, rate limiting, and transient transport failures
+    /// such as connection resets. Errors in the request itself (validation,
+    /// invalid params, unsupported capabilities) and authentication failures
+    /// are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Timeout(_) | Self::RateLimited => true,
+            Self::Protocol { code, .. } => {
+                *code == ErrorCode::REQUEST_TIMEOUT || *code == ErrorCode::RATE_LIMITED
+            },
+            Self::Transport(err) => err.is_retryable(),
+            Self::Other(_) => self
+                .io_error()
+                .is_some_and(|err| io_kind_is_transient(err.kind())),
+            _ => false,
+        }
+    }
+
+    /// Whether the connection that produced this error is unusable.
+    ///
+    /// A fatal error means in-flight and future requests on the connection
+    /// will fail until it is re-established.
+    pub fn is_fatal_for_connection(&self) -> bool {
+        match self {
+            Self::Transport(err) => err.is_fatal_for_connection(),
+            Self::Other(_) => self.io_error().is_some(),
+            _ => false,
+        }
+    }
+
+    /// Find an error of type `E` in this error's source chain.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::Error;
+    /// use std::io;
+    ///
+    /// let err = Error::from(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"));
+    /// let io_err = err.find_source::<io::Error>().unwrap();
+    /// assert_eq!(io_err.kind(), io::ErrorKind::BrokenPipe);
+    /// ```
+    pub fn find_source<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        if let Self::Other(err) = self {
+            return err.chain().find_map(|e| e.downcast_ref::<E>());
+        }
+        let mut current: Option<&(dyn std::error::Error + 'static)> = Some(self);
+        while let Some(err) = current {
+            if let Some(found) = err.downcast_ref::<E>() {
+                return Some(found);
+            }
+            current = err.source();
+        }
+        None
+    }
+
+    /// The underlying I/O error, if any.
+    pub fn io_error(&self) -> Option<&std::io::Error> {
+        self.find_source()
+    }
+
+    /// The underlying HTTP client error, if any.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reqwest_error(&self) -> Option<&reqwest::Error> {
+        self.find_source()
+    }
+
+    /// The underlying WebSocket error, if any.
+    #[cfg(feature = "websocket")]
+    pub fn websocket_error(&self) -> Option<&tokio_tungstenite::tungstenite::Error> {
+        self.find_source()
+    }
 }
 
 #[cfg(test)]
@@ -315,6 +646,79 @@ mod tests {
         assert!(matches!(err, Error::Protocol { .. }));
     }
 
+    #[test]
+    fn test_retryability_classification() {
+        use std::io;
+
+        let reset = Error::from(io::Error::new(io::ErrorKind::ConnectionReset, "reset"));
+        assert!(reset.is_retryable());
+        assert!(reset.is_fatal_for_connection());
+
+        let denied = Error::from(io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+        assert!(!denied.is_retryable());
+        assert!(denied.is_fatal_for_connection());
+
+        assert!(Error::Timeout(100).is_retryable());
+        assert!(!Error::Timeout(100).is_fatal_for_connection());
+        assert!(Error::RateLimited.is_retryable());
+        assert!(Error::protocol(ErrorCode::RATE_LIMITED, "slow down").is_retryable());
+        assert!(Error::Transport(TransportError::ConnectionClosed).is_fatal_for_connection());
+
+        assert!(!Error::authentication("bad token").is_retryable());
+        assert!(!Error::invalid_params("missing").is_retryable());
+        assert!(!Error::Transport(TransportError::InvalidMessage("x".into())).is_retryable());
+    }
+
+    #[test]
+    fn test_error_categories() {
+        use std::io;
+
+        let reset = Error::from(io::Error::new(io::ErrorKind::ConnectionReset, "reset"));
+        assert_eq!(reset.category(), ErrorCategory::TransientNetwork);
+        let eof = Error::from(io::Error::new(io::ErrorKind::UnexpectedEof, "eof"));
+        assert_eq!(eof.category(), ErrorCategory::PeerGone);
+        assert_eq!(
+            Error::Transport(TransportError::ConnectionClosed).category(),
+            ErrorCategory::PeerGone
+        );
+        assert_eq!(
+            Error::Transport(TransportError::InvalidMessage("x".into())).category(),
+            ErrorCategory::ProtocolViolation
+        );
+        assert_eq!(
+            Error::protocol(ErrorCode::PERMISSION_DENIED, "no").category(),
+            ErrorCategory::AuthFailure
+        );
+        assert_eq!(Error::internal("boom").category(), ErrorCategory::Other);
+
+        assert!(ErrorCategory::TransientNetwork.is_recoverable());
+        assert!(ErrorCategory::PeerGone.is_recoverable());
+        assert!(!ErrorCategory::AuthFailure.is_recoverable());
+        assert!(!ErrorCategory::ProtocolViolation.is_recoverable());
+    }
+
+    #[test]
+    fn test_sources_are_preserved() {
+        use std::error::Error as _;
+        use std::io;
+
+        let err = Error::from(io::Error::new(io::ErrorKind::UnexpectedEof, "eof"));
+        assert!(err.source().is_some());
+        assert_eq!(
+            err.io_error().map(io::Error::kind),
+            Some(io::ErrorKind::UnexpectedEof)
+        );
+
+        let wrapped = Error::Other(anyhow::Error::new(io::Error::new(
+            io::ErrorKind::ConnectionAborted,
+            "aborted",
+        )));
+        assert!(wrapped.io_error().is_some());
+        assert!(wrapped.is_retryable());
+
+        assert!(Error::internal("opaque").io_error().is_none());
+    }
+
     #[test]
     fn test_error_codes() {
         assert_eq!(ErrorCode::PARSE_ERROR.as_i32(), -32700);
@@ -417,6 +417,9 @@ pub struct RecoveryPolicy {
 
     /// Whether to log recovery attempts.
     log_attempts: bool,
+
+    /// Whether to stop recovery for errors that are not retryable.
+    fail_fast_on_non_retryable: bool,
 }
 
 impl Default for RecoveryPolicy {
@@ -447,6 +450,7 @@ impl Default for RecoveryPolicy {
             strategies,
             default_strategy: RecoveryStrategy::FailFast,
             log_attempts: true,
+            fail_fast_on_non_retryable: false,
         }
     }
 }
@@ -458,6 +462,7 @@ impl RecoveryPolicy {
             strategies: HashMap::new(),
             default_strategy,
             log_attempts: true,
+            fail_fast_on_non_retryable: false,
         }
     }
 
@@ -472,6 +477,20 @@ impl RecoveryPolicy {
             .get(error_code)
             .unwrap_or(&self.default_strategy)
     }
+
+    /// Stop recovering as soon as an error is not retryable.
+    ///
+    /// When enabled, errors for which [`Error::is_retryable`] is false fail
+    /// fast regardless of the configured strategy, and retry loops end at the
+    /// first such error instead of exhausting their attempts.
+    pub fn set_fail_fast_on_non_retryable(&mut self, enabled: bool) {
+        self.fail_fast_on_non_retryable = enabled;
+    }
+
+    /// Whether recovery should give up on this error.
+    fn gives_up_on(&self, error: &Error) -> bool {
+        self.fail_fast_on_non_retryable && !error.is_retryable()
+    }
 }
 
 /// Error recovery handler trait.
@@ -1105,6 +1124,9 @@ impl AdvancedRecoveryExecutor {
                 Err(e) => {
                     last_error = e;
                     self.coordinator.metrics.record_failure(jittered_delay);
+                    if self.policy.gives_up_on(&last_error) {
+                        break;
+                    }
 
                     if self.policy.log_attempts {
                         warn!("Adaptive retry attempt {} failed: {}", attempt, last_error);
@@ -1253,6 +1275,9 @@ impl AdvancedRecoveryExecutor {
                 Err(e) => {
                     last_error = e;
                     self.coordinator.metrics.record_failure(delay);
+                    if self.policy.gives_up_on(&last_error) {
+                        break;
+                    }
 
                     if self.policy.log_attempts {
                         warn!("Retry attempt {} failed: {}", attempt, last_error);
@@ -1325,6 +1350,7 @@ impl RecoveryExecutor {
                 }
                 Ok(result)
             },
+            Err(error) if self.policy.gives_up_on(&error) => Err(error),
             Err(error) => {
                 let error_code = error.error_code().unwrap_or(ErrorCode::INTERNAL_ERROR);
                 let strategy = self.policy.get_strategy(&error_code);
@@ -1458,6 +1484,9 @@ impl RecoveryExecutor {
                 Ok(result) => return Ok(result),
                 Err(e) => {
                     last_error = e;
+                    if self.policy.gives_up_on(&last_error) {
+                        break;
+                    }
                     if self.policy.log_attempts {
                         warn!("Retry attempt {} failed: {}", attempt, last_error);
                     }
@@ -1498,6 +1527,9 @@ impl RecoveryExecutor {
                 Ok(result) => return Ok(result),
                 Err(e) => {
                     last_error = e;
+                    if self.policy.gives_up_on(&last_error) {
+                        break;
+                    }
                     if self.policy.log_attempts {
                         warn!(
                             "Exponential retry attempt {} failed: {}",
@@ -1668,6 +1700,39 @@ mod tests {
         assert_eq!(attempt_count.load(Ordering::Relaxed), 3);
     }
 
+    #[tokio::test]
+    async fn test_non_retryable_errors_fail_fast() {
+        let mut policy = RecoveryPolicy::default();
+        policy.set_fail_fast_on_non_retryable(true);
+        let executor = RecoveryExecutor::new(policy);
+
+        let attempt_count = Arc::new(AtomicU32::new(0));
+        let attempt_count_clone = attempt_count.clone();
+
+        let result = executor
+            .retry_fixed(Error::Timeout(10), 3, Duration::from_millis(1), || {
+                let count = attempt_count_clone.fetch_add(1, Ordering::Relaxed);
+                async move {
+                    if count == 0 {
+                        Err(Error::Timeout(10))
+                    } else {
+                        Err(Error::authentication("token revoked"))
+                    }
+                }
+            })
+            .await;
+
+        assert!(matches!(result, Err(Error::Authentication(_))));
+        assert_eq!(attempt_count.load(Ordering::Relaxed), 2);
+
+        let result = executor
+            .execute_with_recovery("op", || async {
+                Err(Error::invalid_params("missing argument"))
+            })
+            .await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_circuit_breaker() {
         let config = CircuitBreakerConfig {
@@ -87,25 +87,30 @@ pub mod simd;
 
 // Re-export commonly used types
 pub use client::{Client, ClientBuilder};
-pub use error::{Error, ErrorCode, Result};
+pub use error::{Error, ErrorCategory, ErrorCode, Result};
 #[cfg(not(target_arch = "wasm32"))]
 pub use server::cancellation::RequestHandlerExtra;
+#[cfg(all(not(target_arch = "wasm32"), feature = "templates"))]
+pub use server::simple_prompt::TemplatePrompt;
 #[cfg(not(target_arch = "wasm32"))]
 pub use server::{
     auth,
+    http_tool::HttpTool,
+    prompt_builder::PromptResultBuilder,
+    prompt_template::PromptTemplate,
     simple_prompt::{SimplePrompt, SyncPrompt},
     simple_resources::{DynamicResourceHandler, ResourceCollection, StaticResource},
     simple_tool::{SimpleTool, SyncTool},
     typed_tool::{SimpleToolExt, SyncToolExt, TypedSyncTool, TypedTool, TypedToolWithOutput},
     PromptHandler, ResourceHandler, SamplingHandler, Server, ServerBuilder, ToolHandler,
 };
-#[cfg(not(target_arch = "wasm32"))]
-pub use shared::StdioTransport;
 pub use shared::{
     batch::{BatchRequest, BatchResponse},
     uri_template::UriTemplate,
     AuthMiddleware, LoggingMiddleware, Middleware, MiddlewareChain, RetryMiddleware, Transport,
 };
+#[cfg(not(target_arch = "wasm32"))]
+pub use shared::{ChildProcessCommand, ChildProcessTransport, InMemoryTransport, StdioTransport};
 
 #[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
 pub use shared::{WebSocketConfig, WebSocketTransport};
@@ -118,6 +123,9 @@ pub use shared::{WasmHttpClient, WasmHttpConfig, WasmHttpTransport};
 
 #[cfg(all(feature = "http", not(target_arch = "wasm32")))]
 pub use shared::{HttpConfig, HttpTransport};
+
+#[cfg(all(feature = "tcp", not(target_arch = "wasm32")))]
+pub use shared::TcpTransport;
 pub use types::{
     AuthInfo, AuthScheme, CallToolRequest, CallToolResult, ClientCapabilities, ClientNotification,
     ClientRequest, CompleteRequest, CompleteResult, CompletionArgument, CompletionReference,
@@ -148,6 +156,7 @@ pub use types::{
 ///         text: "Operation completed successfully".to_string(),
 ///     }],
 ///     is_error: false,
+///     structured_content: None,
 /// };
 ///
 /// assert_eq!(result.content.len(), 1);
@@ -165,6 +174,7 @@ pub use types::{
 ///         text: "Tool execution failed: Invalid input parameter".to_string(),
 ///     }],
 ///     is_error: true,
+///     structured_content: None,
 /// };
 ///
 /// assert!(error_result.is_error);
@@ -183,6 +193,7 @@ pub use types::{
 ///         mime_type: Some("text/plain".to_string()),
 ///     }],
 ///     is_error: false,
+///     structured_content: None,
 /// };
 ///
 /// match &resource_result.content[0] {
@@ -205,6 +216,7 @@ pub use types::{
 ///         text: "Hello, MCP!".to_string(),
 ///     }],
 ///     is_error: false,
+///     structured_content: None,
 /// };
 ///
 /// // Serialize to JSON
@@ -217,11 +229,26 @@ pub use types::{
 /// ```
 pub use types::CallToolResult as ToolResult;
 #[cfg(not(target_arch = "wasm32"))]
-pub use utils::{BatchingConfig, DebouncingConfig, MessageBatcher, MessageDebouncer};
+pub use utils::{
+    BatchingConfig, DebouncingConfig, MessageBatcher, MessageDebouncer, WriteBatchingConfig,
+};
 
 // Re-export async_trait for convenience
 pub use async_trait::async_trait;
 
+/// Declare a tool from a function; see [`pmcp_macros::tool`].
+#[cfg(all(not(target_arch = "wasm32"), feature = "macros"))]
+pub use pmcp_macros::tool;
+
+/// Dependencies used by code generated in `pmcp-macros`. Not public API.
+#[cfg(all(not(target_arch = "wasm32"), feature = "macros"))]
+#[doc(hidden)]
+pub mod __private {
+    pub use schemars;
+    pub use serde;
+    pub use serde_json;
+}
+
 /// Protocol version constants
 ///
 /// # Examples
@@ -0,0 +1,232 @@
+//! actix-web integration for the streamable HTTP server.
+//!
+//! A [`StreamableHttpServer`] started on its own owns its listener.
+//! [`ActixMcp`] serves the same endpoints from an existing actix-web
+//! application instead: JSON-RPC over `POST`, SSE streams over `GET`,
+//! session termination over `DELETE`, and the OAuth metadata routes when
+//! configured. They share the application's workers, middleware and
+//! configuration, while requests are handled by the server's own router
+//! (see [`StreamableHttpServer::into_router`]), so they behave exactly as on
+//! a standalone server.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use actix_web::{web, App, HttpServer};
+//! use pmcp::server::actix::ActixMcp;
+//! use pmcp::server::streamable_http_server::StreamableHttpServer;
+//! use pmcp::Server;
+//! use std::sync::Arc;
+//!
+//! #[actix_web::main]
+//! async fn main() -> std::io::Result<()> {
+//!     let server = Server::builder()
+//!         .name("actix-server")
+//!         .version("1.0.0")
+//!         .build()
+//!         .expect("valid server");
+//!     let mcp = ActixMcp::new(StreamableHttpServer::new(
+//!         ([0, 0, 0, 0], 0).into(),
+//!         Arc::new(server),
+//!     ));
+//!
+//!     HttpServer::new(move || {
+//!         App::new()
+//!             .route("/health", web::get().to(|| async { "ok" }))
+//!             .service(mcp.scope("/mcp"))
+//!     })
+//!     .bind(("0.0.0.0", 8080))?
+//!     .run()
+//!     .await
+//! }
+//! ```
+
+use super::streamable_http_server::{McpService, StreamableHttpServer};
+use crate::shared::http_constants::TEXT_EVENT_STREAM;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse};
+use tower_service::Service;
+
+/// Serves a [`StreamableHttpServer`] from an actix-web application.
+///
+/// Cloning is cheap; clones serve the same server, so one can be moved
+/// into each worker's application factory.
+#[derive(Debug, Clone)]
+pub struct ActixMcp {
+    service: McpService,
+}
+
+impl ActixMcp {
+    /// Serve the endpoints of `server`.
+    ///
+    /// The address `server` was created with is not used.
+    pub fn new(server: StreamableHttpServer) -> Self {
+        Self {
+            service: server.into_service(),
+        }
+    }
+
+    /// A scope serving the MCP endpoint at `path`.
+    ///
+    /// The OAuth metadata routes, when configured, are served below `path`.
+    pub fn scope(&self, path: &str) -> actix_web::Scope {
+        web::scope(path)
+            .app_data(web::Data::new(self.clone()))
+            .default_service(web::to(serve))
+    }
+
+    /// Answer `request`, for use from a handler of your own.
+    ///
+    /// The part of the path not matched by the handler's route is the path
+    /// on the MCP server, so route the handler with a tail match (e.g.
+    /// `/mcp{tail:.*}`) or serve it from a scope.
+    pub async fn handle(&self, request: HttpRequest, body: web::Bytes) -> HttpResponse {
+        let tail = request.match_info().unprocessed().trim_start_matches('/');
+        let uri = match request.query_string() {
+            "" => format!("/{}", tail),
+            query => format!("/{}?{}", tail, query),
+        };
+        let mut forwarded = axum::http::Request::builder()
+            .method(request.method().as_str())
+            .uri(uri);
+        for (name, value) in request.headers() {
+            forwarded = forwarded.header(name.as_str(), value.as_bytes());
+        }
+        // HTTP/2 requests name their host in the URI, not a `Host` header
+        if !request
+            .headers()
+            .contains_key(actix_web::http::header::HOST)
+        {
+            forwarded = forwarded.header(
+                axum::http::header::HOST,
+                request.connection_info().host().to_string(),
+            );
+        }
+        let mut forwarded = match forwarded.body(axum::body::Body::from(body)) {
+            Ok(forwarded) => forwarded,
+            Err(e) => return HttpResponse::BadRequest().body(e.to_string()),
+        };
+        if let Some(peer) = request.peer_addr() {
+            forwarded
+                .extensions_mut()
+                .insert(axum::extract::ConnectInfo(peer));
+        }
+
+        let mut service = self.service.clone();
+        let ready = std::future::poll_fn(|cx| {
+            Service::<axum::http::Request<axum::body::Body>>::poll_ready(&mut service, cx)
+        });
+        let response = match ready.await {
+            Ok(()) => service.call(forwarded).await,
+            Err(e) => Err(e),
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(never) => match never {},
+        };
+
+        let (parts, body) = response.into_parts();
+        let mut reply = HttpResponse::build(
+            StatusCode::from_u16(parts.status.as_u16())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        );
+        for (name, value) in &parts.headers {
+            reply.append_header((name.as_str(), value.as_bytes()));
+        }
+
+        let streamed = parts
+            .headers
+            .get(axum::http::header::CONTENT_TYPE)
+            .is_some_and(|content_type| {
+                content_type
+                    .as_bytes()
+                    .starts_with(TEXT_EVENT_STREAM.as_bytes())
+            });
+        if streamed {
+            return reply.streaming(body.into_data_stream());
+        }
+        match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(bytes) => reply.body(bytes),
+            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        }
+    }
+}
+
+async fn serve(mcp: web::Data<ActixMcp>, request: HttpRequest, body: web::Bytes) -> HttpResponse {
+    mcp.handle(request, body).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Server;
+    use actix_web::{test, App};
+    use std::sync::Arc;
+
+    fn mcp() -> ActixMcp {
+        let server = Server::builder()
+            .name("actix")
+            .version("1.0.0")
+            .build()
+            .unwrap();
+        ActixMcp::new(StreamableHttpServer::new(
+            ([127, 0, 0, 1], 0).into(),
+            Arc::new(server),
+        ))
+    }
+
+    #[actix_web::test]
+    async fn test_serves_streamable_http_from_a_scope() {
+        let app = test::init_service(App::new().service(mcp().scope("/mcp"))).await;
+
+        let initialize = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": crate::LATEST_PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {"name": "test-client", "version": "1.0.0"},
+            },
+        });
+        let request = test::TestRequest::post()
+            .uri("/mcp")
+            .insert_header(("Content-Type", "application/json"))
+            .insert_header(("Accept", "application/json, text/event-stream"))
+            .set_json(&initialize)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let session = response
+            .headers()
+            .get("mcp-session-id")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["result"]["serverInfo"]["name"], "actix");
+
+        let request = test::TestRequest::get()
+            .uri("/mcp")
+            .insert_header(("Accept", "text/event-stream"))
+            .insert_header(("mcp-session-id", session.as_str()))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response
+            .headers()
+            .get("content-type")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("text/event-stream"));
+
+        let request = test::TestRequest::delete()
+            .uri("/mcp")
+            .insert_header(("mcp-session-id", session.as_str()))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+    }
+}
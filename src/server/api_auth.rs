@@ -0,0 +1,45 @@
+//! Authentication for outgoing HTTP API calls made by generated tools.
+
+/// Authentication applied to outgoing API calls.
+#[derive(Debug, Clone, Default)]
+pub enum ApiAuth {
+    /// No authentication.
+    #[default]
+    None,
+    /// `Authorization: Bearer <token>` header.
+    Bearer(String),
+    /// HTTP basic authentication.
+    Basic {
+        /// Username
+        username: String,
+        /// Optional password
+        password: Option<String>,
+    },
+    /// API key sent in a custom header.
+    Header {
+        /// Header name
+        name: String,
+        /// Header value
+        value: String,
+    },
+    /// API key sent as a query parameter.
+    Query {
+        /// Query parameter name
+        name: String,
+        /// Query parameter value
+        value: String,
+    },
+}
+
+impl ApiAuth {
+    /// Apply this authentication to a request builder.
+    pub(crate) fn apply(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            Self::None => builder,
+            Self::Bearer(token) => builder.bearer_auth(token),
+            Self::Basic { username, password } => builder.basic_auth(username, password.as_ref()),
+            Self::Header { name, value } => builder.header(name.as_str(), value.as_str()),
+            Self::Query { name, value } => builder.query(&[(name, value)]),
+        }
+    }
+}
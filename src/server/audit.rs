@@ -0,0 +1,239 @@
+//! Audit logging of client requests.
+//!
+//! A server with an [`AuditLogger`] records every request it answers: the
+//! method, who sent it, how long it took, whether it succeeded, and its
+//! params and result. Payloads larger than the configured limit are
+//! truncated so a single large resource read can't flood the log.
+//!
+//! Two loggers ship with the SDK: [`JsonLinesAuditLogger`] appends one JSON
+//! object per line to a file, and [`TracingAuditLogger`] emits a `tracing`
+//! event per request. Implement [`AuditLogger`] to ship entries elsewhere.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::audit::JsonLinesAuditLogger;
+//! use pmcp::Server;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let server = Server::builder()
+//!     .name("payments")
+//!     .version("1.0.0")
+//!     .audit_logger(JsonLinesAuditLogger::open("audit.jsonl").await?)
+//!     .audit_payload_limit(1024)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Default maximum serialized size of a recorded payload, in bytes.
+pub const DEFAULT_PAYLOAD_LIMIT: usize = 4096;
+
+/// How a request ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum AuditOutcome {
+    /// The request succeeded.
+    Success,
+    /// The request failed with a JSON-RPC error.
+    Error {
+        /// JSON-RPC error code.
+        code: i32,
+        /// Error message.
+        message: String,
+    },
+}
+
+/// One audited request and its response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// When the request arrived.
+    pub timestamp: DateTime<Utc>,
+    /// JSON-RPC request ID.
+    pub request_id: String,
+    /// Request method, e.g. `tools/call`.
+    pub method: String,
+    /// Subject the server's auth provider authenticated from the request's
+    /// `Authorization` header, or the client's address or connection when
+    /// the request carried no credentials.
+    pub principal: String,
+    /// Time spent handling the request, in milliseconds.
+    pub duration_ms: u64,
+    /// How the request ended.
+    pub outcome: AuditOutcome,
+    /// Request params, possibly truncated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+    /// Result of a successful request, possibly truncated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+}
+
+impl AuditEntry {
+    pub(crate) fn duration_ms(duration: Duration) -> u64 {
+        u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+    }
+}
+
+/// Destination for audit entries.
+#[async_trait]
+pub trait AuditLogger: Send + Sync {
+    /// Record one entry.
+    ///
+    /// Errors are logged and otherwise ignored; they never fail the request.
+    async fn record(&self, entry: &AuditEntry) -> Result<()>;
+}
+
+/// Replace `value` with a truncated string if it serializes to more than
+/// `limit` bytes.
+pub(crate) fn truncate_payload(value: Value, limit: usize) -> Value {
+    let serialized = value.to_string();
+    if serialized.len() <= limit {
+        return value;
+    }
+    let mut end = limit;
+    while !serialized.is_char_boundary(end) {
+        end -= 1;
+    }
+    Value::String(format!(
+        "{}... ({} bytes truncated)",
+        &serialized[..end],
+        serialized.len() - end
+    ))
+}
+
+/// Audit logger appending entries to a file as JSON lines.
+#[derive(Debug)]
+pub struct JsonLinesAuditLogger {
+    file: Mutex<tokio::fs::File>,
+}
+
+impl JsonLinesAuditLogger {
+    /// Open `path` for appending, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be opened.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+#[async_trait]
+impl AuditLogger for JsonLinesAuditLogger {
+    async fn record(&self, entry: &AuditEntry) -> Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        // One write per entry keeps lines whole between concurrent requests
+        let mut file = self.file.lock().await;
+        file.write_all(&line).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+/// Audit logger emitting a `tracing` event per entry, under the
+/// `pmcp::audit` target.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TracingAuditLogger;
+
+impl TracingAuditLogger {
+    /// Create the logger.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl AuditLogger for TracingAuditLogger {
+    async fn record(&self, entry: &AuditEntry) -> Result<()> {
+        let params = entry.params.as_ref().map(Value::to_string);
+        match &entry.outcome {
+            AuditOutcome::Success => tracing::info!(
+                target: "pmcp::audit",
+                request_id = %entry.request_id,
+                method = %entry.method,
+                principal = %entry.principal,
+                duration_ms = entry.duration_ms,
+                params = params.as_deref(),
+                "request succeeded"
+            ),
+            AuditOutcome::Error { code, message } => tracing::warn!(
+                target: "pmcp::audit",
+                request_id = %entry.request_id,
+                method = %entry.method,
+                principal = %entry.principal,
+                duration_ms = entry.duration_ms,
+                params = params.as_deref(),
+                code,
+                error = %message,
+                "request failed"
+            ),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_truncate_payload() {
+        let small = json!({"a": 1});
+        assert_eq!(truncate_payload(small.clone(), 64), small);
+
+        let truncated = truncate_payload(json!({"text": "é".repeat(100)}), 20);
+        let text = truncated.as_str().unwrap();
+        assert!(text.starts_with("{\"text\":\""));
+        assert!(text.ends_with("bytes truncated)"));
+    }
+
+    #[tokio::test]
+    async fn test_json_lines_logger_appends_entries() {
+        let path = std::env::temp_dir().join(format!("pmcp-audit-{}.jsonl", uuid::Uuid::new_v4()));
+        let logger = JsonLinesAuditLogger::open(&path).await.unwrap();
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            request_id: "1".to_string(),
+            method: "tools/call".to_string(),
+            principal: "alice".to_string(),
+            duration_ms: 3,
+            outcome: AuditOutcome::Error {
+                code: -32602,
+                message: "bad".to_string(),
+            },
+            params: Some(json!({"name": "delete"})),
+            result: None,
+        };
+        logger.record(&entry).await.unwrap();
+        logger.record(&entry).await.unwrap();
+
+        let contents = tokio::fs::read_to_string(&path).await.unwrap();
+        let _ = tokio::fs::remove_file(&path).await;
+        let lines: Vec<AuditEntry> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, [entry.clone(), entry]);
+    }
+}
@@ -0,0 +1,700 @@
+//! JWT validation against an issuer's JSON Web Key Set.
+//!
+//! [`JwksTokenValidator`] checks access tokens issued by a standard identity
+//! provider without calling it for every request: signing keys are fetched
+//! from the issuer's JWKS endpoint, cached, and refreshed when they expire
+//! or a token names a key that isn't cached yet (as happens after a key
+//! rotation).
+
+use super::traits::{AuthContext, TokenValidator};
+use crate::error::{Error, ErrorCode, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use parking_lot::{Mutex, RwLock};
+use ring::signature::{
+    RsaPublicKeyComponents, UnparsedPublicKey, ECDSA_P256_SHA256_FIXED, RSA_PKCS1_2048_8192_SHA256,
+};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Default time fetched keys are trusted before being fetched again.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Default clock skew tolerated when checking `exp` and `nbf`.
+pub const DEFAULT_LEEWAY: Duration = Duration::from_secs(60);
+
+/// Minimum time between fetches triggered by unknown key IDs, so tokens
+/// naming made-up keys can't be used to hammer the issuer.
+const MIN_REFETCH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Signature algorithms accepted in tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Rs256,
+    Es256,
+}
+
+impl Algorithm {
+    fn parse(alg: &str) -> Result<Self> {
+        match alg {
+            "RS256" => Ok(Self::Rs256),
+            "ES256" => Ok(Self::Es256),
+            other => Err(Error::authentication(format!(
+                "Unsupported token algorithm: {}",
+                other
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Rs256 => "RS256",
+            Self::Es256 => "ES256",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Header {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+/// A JSON Web Key (RFC 7517); only the members used for RSA and EC
+/// signature keys are read.
+#[derive(Debug, Clone, Deserialize)]
+struct Jwk {
+    kty: String,
+    #[serde(default)]
+    kid: Option<String>,
+    #[serde(default)]
+    alg: Option<String>,
+    #[serde(default, rename = "use")]
+    usage: Option<String>,
+    #[serde(default)]
+    n: Option<String>,
+    #[serde(default)]
+    e: Option<String>,
+    #[serde(default)]
+    crv: Option<String>,
+    #[serde(default)]
+    x: Option<String>,
+    #[serde(default)]
+    y: Option<String>,
+}
+
+impl Jwk {
+    /// Whether this key can verify a token with `kid` signed with `alg`.
+    fn matches(&self, kid: Option<&str>, alg: Algorithm) -> bool {
+        if kid.is_some() && self.kid.as_deref() != kid {
+            return false;
+        }
+        if self.usage.as_deref().is_some_and(|usage| usage != "sig")
+            || self.alg.as_deref().is_some_and(|a| a != alg.name())
+        {
+            return false;
+        }
+        match alg {
+            Algorithm::Rs256 => self.kty == "RSA",
+            Algorithm::Es256 => self.kty == "EC" && self.crv.as_deref() == Some("P-256"),
+        }
+    }
+
+    fn verify(&self, alg: Algorithm, message: &[u8], signature: &[u8]) -> Result<()> {
+        let verified = match alg {
+            Algorithm::Rs256 => {
+                let components = RsaPublicKeyComponents {
+                    n: decode_member(self.n.as_deref())?,
+                    e: decode_member(self.e.as_deref())?,
+                };
+                components.verify(&RSA_PKCS1_2048_8192_SHA256, message, signature)
+            },
+            Algorithm::Es256 => {
+                // Uncompressed SEC1 point: 0x04 || x || y
+                let mut point = vec![0x04];
+                point.extend(decode_member(self.x.as_deref())?);
+                point.extend(decode_member(self.y.as_deref())?);
+                UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, point).verify(message, signature)
+            },
+        };
+        verified.map_err(|_| Error::authentication("Invalid token signature"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Debug)]
+struct KeyCache {
+    keys: Vec<Jwk>,
+    fetched_at: Instant,
+}
+
+/// Validates JWT access tokens signed with an issuer's published keys.
+///
+/// Tokens must be signed with RS256 or ES256, name the configured issuer,
+/// be addressed to one of the configured audiences, and be within their
+/// `nbf`/`exp` window. Scopes are read from the `scope` claim
+/// (space-separated) and the `scp` claim (string or array) unless other
+/// claims are configured.
+///
+/// The JWKS URL is discovered from the issuer's `OpenID Connect` or OAuth
+/// authorization server metadata unless set explicitly.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use pmcp::server::auth::{JwksTokenValidator, ProxyProvider, TokenValidator};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// # async fn example() -> pmcp::Result<()> {
+/// let validator = JwksTokenValidator::new("https://auth.example.com", "https://mcp.example.com")
+///     .cache_ttl(Duration::from_secs(600));
+///
+/// let auth = validator.validate("eyJhbGciOiJSUzI1NiIs...").await?;
+/// println!("{} may use {:?}", auth.subject, auth.scopes);
+///
+/// // Or check the Authorization header of incoming requests
+/// let provider = ProxyProvider::with_upstream("https://auth.example.com")
+///     .with_validator(Arc::new(validator));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct JwksTokenValidator {
+    issuer: String,
+    audiences: Vec<String>,
+    jwks_uri: Mutex<Option<String>>,
+    cache_ttl: Duration,
+    leeway: Duration,
+    scope_claims: Vec<String>,
+    client: reqwest::Client,
+    cache: RwLock<Option<KeyCache>>,
+}
+
+impl JwksTokenValidator {
+    /// Create a validator for tokens from `issuer` intended for `audience`.
+    pub fn new(issuer: impl Into<String>, audience: impl Into<String>) -> Self {
+        Self {
+            issuer: issuer.into(),
+            audiences: vec![audience.into()],
+            jwks_uri: Mutex::new(None),
+            cache_ttl: DEFAULT_CACHE_TTL,
+            leeway: DEFAULT_LEEWAY,
+            scope_claims: vec!["scope".to_string(), "scp".to_string()],
+            client: reqwest::Client::new(),
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Also accept tokens intended for `audience`.
+    pub fn audience(mut self, audience: impl Into<String>) -> Self {
+        self.audiences.push(audience.into());
+        self
+    }
+
+    /// Fetch keys from `jwks_uri` instead of discovering it.
+    pub fn jwks_uri(self, jwks_uri: impl Into<String>) -> Self {
+        *self.jwks_uri.lock() = Some(jwks_uri.into());
+        self
+    }
+
+    /// Set how long fetched keys are trusted.
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Set the clock skew tolerated when checking `exp` and `nbf`.
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    /// Set the claims scopes are read from.
+    pub fn scope_claims<S, I>(mut self, claims: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scope_claims = claims.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The key to verify a token with `kid` signed with `alg`.
+    async fn key_for(&self, kid: Option<&str>, alg: Algorithm) -> Result<Jwk> {
+        let stale = {
+            let cache = self.cache.read();
+            match cache.as_ref() {
+                Some(cache) if cache.fetched_at.elapsed() < self.cache_ttl => {
+                    if let Some(key) = cache.keys.iter().find(|key| key.matches(kid, alg)) {
+                        return Ok(key.clone());
+                    }
+                    if cache.fetched_at.elapsed() < MIN_REFETCH_INTERVAL {
+                        return Err(unknown_key(kid));
+                    }
+                    None
+                },
+                Some(cache) => cache.keys.iter().find(|key| key.matches(kid, alg)).cloned(),
+                None => None,
+            }
+        };
+
+        let keys = match self.fetch_keys().await {
+            Ok(keys) => keys,
+            Err(e) => {
+                // Keep validating with expired keys while the issuer is unreachable
+                return match stale {
+                    Some(key) => {
+                        tracing::warn!("Failed to refresh JWKS, using cached keys: {}", e);
+                        Ok(key)
+                    },
+                    None => Err(e),
+                };
+            },
+        };
+
+        let key = keys.iter().find(|key| key.matches(kid, alg)).cloned();
+        *self.cache.write() = Some(KeyCache {
+            keys,
+            fetched_at: Instant::now(),
+        });
+        key.ok_or_else(|| unknown_key(kid))
+    }
+
+    async fn fetch_keys(&self) -> Result<Vec<Jwk>> {
+        let jwks_uri = self.resolve_jwks_uri().await?;
+        tracing::debug!("Fetching JWKS from {}", jwks_uri);
+        Ok(self.get_json::<JwkSet>(&jwks_uri).await?.keys)
+    }
+
+    async fn resolve_jwks_uri(&self) -> Result<String> {
+        if let Some(uri) = self.jwks_uri.lock().clone() {
+            return Ok(uri);
+        }
+
+        let issuer = self.issuer.trim_end_matches('/');
+        let mut last_error = None;
+        for path in [
+            "/.well-known/openid-configuration",
+            "/.well-known/oauth-authorization-server",
+        ] {
+            let metadata = match self
+                .get_json::<serde_json::Value>(&format!("{}{}", issuer, path))
+                .await
+            {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    last_error = Some(e);
+                    continue;
+                },
+            };
+            if let Some(uri) = metadata.get("jwks_uri").and_then(|v| v.as_str()) {
+                *self.jwks_uri.lock() = Some(uri.to_string());
+                return Ok(uri.to_string());
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            Error::protocol(
+                ErrorCode::INTERNAL_ERROR,
+                "Issuer metadata does not include a jwks_uri",
+            )
+        }))
+    }
+
+    async fn get_json<D: DeserializeOwned>(&self, url: &str) -> Result<D> {
+        let response = self
+            .client
+            .get(url)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .map_err(|e| {
+                Error::protocol(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to fetch {}: {}", url, e),
+                )
+            })?;
+        if !response.status().is_success() {
+            return Err(Error::protocol(
+                ErrorCode::INTERNAL_ERROR,
+                format!("{} returned status: {}", url, response.status()),
+            ));
+        }
+        response.json::<D>().await.map_err(|e| {
+            Error::protocol(
+                ErrorCode::PARSE_ERROR,
+                format!("Failed to parse {}: {}", url, e),
+            )
+        })
+    }
+
+    fn check_claims(&self, claims: &serde_json::Map<String, serde_json::Value>) -> Result<()> {
+        if claims.get("iss").and_then(|v| v.as_str()) != Some(self.issuer.as_str()) {
+            return Err(Error::authentication("Token issuer is not trusted"));
+        }
+
+        let addressed = match claims.get("aud") {
+            Some(serde_json::Value::String(aud)) => self.audiences.contains(aud),
+            Some(serde_json::Value::Array(auds)) => auds
+                .iter()
+                .filter_map(|aud| aud.as_str())
+                .any(|aud| self.audiences.iter().any(|a| a == aud)),
+            _ => false,
+        };
+        if !addressed {
+            return Err(Error::authentication(
+                "Token is not intended for this server",
+            ));
+        }
+
+        let now = now();
+        let leeway = self.leeway.as_secs();
+        let exp = claims
+            .get("exp")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| Error::authentication("Token has no expiry"))?;
+        if now > exp.saturating_add(leeway) {
+            return Err(Error::authentication("Token expired"));
+        }
+        if let Some(nbf) = claims.get("nbf").and_then(|v| v.as_u64()) {
+            if now.saturating_add(leeway) < nbf {
+                return Err(Error::authentication("Token is not valid yet"));
+            }
+        }
+        Ok(())
+    }
+
+    fn auth_context(
+        &self,
+        token: &str,
+        claims: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<AuthContext> {
+        let subject = claims
+            .get("sub")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::authentication("Token has no subject"))?
+            .to_string();
+
+        let mut scopes: Vec<String> = Vec::new();
+        for claim in &self.scope_claims {
+            let granted: Vec<&str> = match claims.get(claim) {
+                Some(serde_json::Value::String(s)) => s.split_whitespace().collect(),
+                Some(serde_json::Value::Array(items)) => {
+                    items.iter().filter_map(|v| v.as_str()).collect()
+                },
+                _ => continue,
+            };
+            for scope in granted {
+                if !scopes.iter().any(|s| s == scope) {
+                    scopes.push(scope.to_string());
+                }
+            }
+        }
+
+        let client_id = ["client_id", "azp"]
+            .iter()
+            .find_map(|claim| claims.get(*claim).and_then(|v| v.as_str()))
+            .map(str::to_string);
+        let expires_at = claims.get("exp").and_then(|v| v.as_u64());
+
+        Ok(AuthContext {
+            subject,
+            scopes,
+            claims: claims.into_iter().collect::<HashMap<_, _>>(),
+            token: Some(token.to_string()),
+            client_id,
+            expires_at,
+        })
+    }
+}
+
+#[async_trait]
+impl TokenValidator for JwksTokenValidator {
+    async fn validate(&self, token: &str) -> Result<AuthContext> {
+        // The signature covers the encoded header and payload
+        let Some((signing_input, signature)) = token.rsplit_once('.') else {
+            return Err(Error::authentication("Malformed token"));
+        };
+        let Some((header, payload)) = signing_input.split_once('.') else {
+            return Err(Error::authentication("Malformed token"));
+        };
+
+        let header: Header = decode_json(header)?;
+        let alg = Algorithm::parse(&header.alg)?;
+        let signature = decode_part(signature)?;
+        let key = self.key_for(header.kid.as_deref(), alg).await?;
+        key.verify(alg, signing_input.as_bytes(), &signature)?;
+
+        let claims: serde_json::Map<String, serde_json::Value> = decode_json(payload)?;
+        self.check_claims(&claims)?;
+        self.auth_context(token, claims)
+    }
+}
+
+fn unknown_key(kid: Option<&str>) -> Error {
+    Error::authentication(match kid {
+        Some(kid) => format!("No signing key with ID {}", kid),
+        None => "No signing key for token".to_string(),
+    })
+}
+
+fn decode_part(part: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|_| Error::authentication("Malformed token"))
+}
+
+fn decode_json<D: DeserializeOwned>(part: &str) -> Result<D> {
+    serde_json::from_slice(&decode_part(part)?)
+        .map_err(|_| Error::authentication("Malformed token"))
+}
+
+fn decode_member(member: Option<&str>) -> Result<Vec<u8>> {
+    member
+        .and_then(|m| URL_SAFE_NO_PAD.decode(m).ok())
+        .ok_or_else(|| Error::protocol(ErrorCode::INTERNAL_ERROR, "Malformed key in issuer's JWKS"))
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair, RsaKeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+    use serde_json::json;
+
+    const ISSUER: &str = "https://auth.example.com";
+    const AUDIENCE: &str = "https://mcp.example.com";
+
+    enum Signer {
+        Rsa(RsaKeyPair),
+        Ec(EcdsaKeyPair),
+    }
+
+    impl Signer {
+        fn rsa() -> Self {
+            let der = include_bytes!("../../../tests/fixtures/jwt/rsa.pk8");
+            Self::Rsa(RsaKeyPair::from_pkcs8(der).unwrap())
+        }
+
+        fn ec() -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 =
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+            Self::Ec(
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+                    .unwrap(),
+            )
+        }
+
+        fn jwk(&self, kid: &str) -> serde_json::Value {
+            match self {
+                Self::Rsa(key) => {
+                    let public: RsaPublicKeyComponents<Vec<u8>> = key.public().into();
+                    json!({
+                        "kty": "RSA",
+                        "kid": kid,
+                        "use": "sig",
+                        "n": URL_SAFE_NO_PAD.encode(public.n),
+                        "e": URL_SAFE_NO_PAD.encode(public.e),
+                    })
+                },
+                Self::Ec(key) => {
+                    let point = key.public_key().as_ref();
+                    json!({
+                        "kty": "EC",
+                        "kid": kid,
+                        "crv": "P-256",
+                        "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+                        "y": URL_SAFE_NO_PAD.encode(&point[33..]),
+                    })
+                },
+            }
+        }
+
+        fn sign(&self, kid: &str, claims: &serde_json::Value) -> String {
+            let alg = match self {
+                Self::Rsa(_) => "RS256",
+                Self::Ec(_) => "ES256",
+            };
+            let header = json!({"alg": alg, "typ": "JWT", "kid": kid});
+            let input = format!(
+                "{}.{}",
+                URL_SAFE_NO_PAD.encode(header.to_string()),
+                URL_SAFE_NO_PAD.encode(claims.to_string())
+            );
+            let rng = SystemRandom::new();
+            let signature = match self {
+                Self::Rsa(key) => {
+                    let mut signature = vec![0; key.public().modulus_len()];
+                    key.sign(
+                        &ring::signature::RSA_PKCS1_SHA256,
+                        &rng,
+                        input.as_bytes(),
+                        &mut signature,
+                    )
+                    .unwrap();
+                    signature
+                },
+                Self::Ec(key) => key.sign(&rng, input.as_bytes()).unwrap().as_ref().to_vec(),
+            };
+            format!("{}.{}", input, URL_SAFE_NO_PAD.encode(signature))
+        }
+    }
+
+    fn claims() -> serde_json::Value {
+        json!({
+            "iss": ISSUER,
+            "aud": [AUDIENCE, "other"],
+            "sub": "user-1",
+            "azp": "client-1",
+            "exp": now() + 600,
+            "scope": "tools:read tools:call",
+            "scp": ["tools:call", "admin"],
+        })
+    }
+
+    async fn serve_jwks(
+        server: &mut mockito::ServerGuard,
+        keys: Vec<serde_json::Value>,
+    ) -> mockito::Mock {
+        server
+            .mock("GET", "/jwks")
+            .with_body(json!({ "keys": keys }).to_string())
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_validates_rs256_and_es256_tokens() {
+        let rsa = Signer::rsa();
+        let ec = Signer::ec();
+        let mut server = mockito::Server::new_async().await;
+        let _discovery = server
+            .mock("GET", "/.well-known/openid-configuration")
+            .with_body(json!({ "jwks_uri": format!("{}/jwks", server.url()) }).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+        let jwks = serve_jwks(&mut server, vec![rsa.jwk("r1"), ec.jwk("e1")])
+            .await
+            .expect(1);
+
+        let mut claims = claims();
+        claims["iss"] = json!(server.url());
+        let validator = JwksTokenValidator::new(server.url(), AUDIENCE);
+
+        let auth = validator.validate(&rsa.sign("r1", &claims)).await.unwrap();
+        assert_eq!(auth.subject, "user-1");
+        assert_eq!(auth.scopes, ["tools:read", "tools:call", "admin"]);
+        assert_eq!(auth.client_id.as_deref(), Some("client-1"));
+        assert_eq!(auth.claims["sub"], "user-1");
+
+        // Served from the cache
+        let auth = validator.validate(&ec.sign("e1", &claims)).await.unwrap();
+        assert!(auth.has_scope("admin"));
+        jwks.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_tokens() {
+        let rsa = Signer::rsa();
+        let mut server = mockito::Server::new_async().await;
+        let _jwks = serve_jwks(&mut server, vec![rsa.jwk("r1")]).await;
+        let validator = JwksTokenValidator::new(ISSUER, AUDIENCE)
+            .jwks_uri(format!("{}/jwks", server.url()))
+            .leeway(Duration::ZERO);
+
+        let mut wrong_audience = claims();
+        wrong_audience["aud"] = json!("https://elsewhere.example.com");
+        let mut wrong_issuer = claims();
+        wrong_issuer["iss"] = json!("https://evil.example.com");
+        let mut expired = claims();
+        expired["exp"] = json!(now() - 10);
+        let mut not_yet = claims();
+        not_yet["nbf"] = json!(now() + 600);
+
+        for (claims, reason) in [
+            (wrong_audience, "not intended"),
+            (wrong_issuer, "issuer"),
+            (expired, "expired"),
+            (not_yet, "not valid yet"),
+        ] {
+            let err = validator
+                .validate(&rsa.sign("r1", &claims))
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains(reason), "{}: {}", reason, err);
+        }
+
+        // A valid signature over different claims
+        let token = rsa.sign("r1", &claims());
+        let forged = rsa.sign("r1", &json!({"sub": "admin"}));
+        let (forged_input, _) = forged.rsplit_once('.').unwrap();
+        let (_, signature) = token.rsplit_once('.').unwrap();
+        let err = validator
+            .validate(&format!("{}.{}", forged_input, signature))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("signature"));
+
+        let unsigned = format!(
+            "{}.{}.",
+            URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#),
+            URL_SAFE_NO_PAD.encode(claims().to_string())
+        );
+        assert!(validator.validate(&unsigned).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refetches_keys_after_rotation() {
+        let old = Signer::ec();
+        let new = Signer::ec();
+        let mut server = mockito::Server::new_async().await;
+        let first = serve_jwks(&mut server, vec![old.jwk("old")])
+            .await
+            .expect(1);
+        let validator =
+            JwksTokenValidator::new(ISSUER, AUDIENCE).jwks_uri(format!("{}/jwks", server.url()));
+
+        validator
+            .validate(&old.sign("old", &claims()))
+            .await
+            .unwrap();
+        first.assert_async().await;
+        first.remove_async().await;
+
+        let rotated = serve_jwks(&mut server, vec![new.jwk("new")])
+            .await
+            .expect(1);
+        // Pretend the keys were fetched long enough ago to allow a refetch
+        if let Some(cache) = validator.cache.write().as_mut() {
+            cache.fetched_at -= MIN_REFETCH_INTERVAL;
+        }
+        validator
+            .validate(&new.sign("new", &claims()))
+            .await
+            .unwrap();
+
+        // Unknown keys right after a fetch don't trigger another one
+        let err = validator
+            .validate(&new.sign("bogus", &claims()))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("bogus"));
+        rotated.assert_async().await;
+    }
+}
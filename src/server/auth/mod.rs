@@ -1,5 +1,6 @@
 //! Server-side authentication providers and middleware.
 
+pub mod jwks;
 pub mod middleware;
 pub mod oauth2;
 pub mod proxy;
@@ -10,15 +11,28 @@ pub use traits::{
     AuthContext, AuthProvider, ScopeBasedAuthorizer, SessionManager, TokenValidator, ToolAuthorizer,
 };
 
+pub use jwks::JwksTokenValidator;
+
 // Re-export proxy providers
 pub use proxy::{NoOpAuthProvider, OptionalAuthProvider, ProxyProvider, ProxyProviderConfig};
 
 // Keep existing OAuth2 exports for compatibility
 pub use oauth2::{
-    AccessToken, AuthorizationCode, AuthorizationRequest, GrantType, InMemoryOAuthProvider,
-    OAuthClient, OAuthError, OAuthMetadata, OAuthProvider, ProxyOAuthProvider, ResponseType,
-    RevocationRequest, TokenInfo, TokenRequest, TokenType,
+    AccessToken, AuthorizationCode, AuthorizationRequest, ClientRegistrationRequest,
+    ClientRegistrationResponse, ClientStore, GrantType, InMemoryClientStore, InMemoryOAuthProvider,
+    OAuthClient, OAuthError, OAuthMetadata, OAuthProvider, ProtectedResourceMetadata,
+    ProxyOAuthProvider, ResponseType, RevocationRequest, TokenInfo, TokenRequest, TokenType,
 };
 
+tokio::task_local! {
+    /// `Authorization` header of the HTTP request being handled
+    pub(crate) static AUTHORIZATION: String;
+}
+
+/// `Authorization` header of the HTTP request being handled, if any.
+pub(crate) fn current_authorization() -> Option<String> {
+    AUTHORIZATION.try_with(Clone::clone).ok()
+}
+
 // Note: AuthContext from traits replaces the one from middleware
 // We'll need to update middleware to use the new AuthContext from traits
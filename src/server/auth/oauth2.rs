@@ -166,6 +166,17 @@ pub struct OAuthError {
     pub error_uri: Option<String>,
 }
 
+impl OAuthError {
+    /// Create an error with a code and description.
+    pub fn new(error: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            error: error.into(),
+            error_description: Some(description.into()),
+            error_uri: None,
+        }
+    }
+}
+
 /// `OpenID Connect Discovery` metadata.
 /// Represents the well-known configuration for OAuth 2.0/OIDC servers.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -218,6 +229,154 @@ pub struct OidcDiscoveryMetadata {
 /// OAuth 2.0 server metadata (alias for backward compatibility).
 pub type OAuthMetadata = OidcDiscoveryMetadata;
 
+/// Well-known path protected resource metadata is served under (RFC 9728).
+pub const PROTECTED_RESOURCE_METADATA_PATH: &str = "/.well-known/oauth-protected-resource";
+
+/// OAuth 2.0 protected resource metadata (RFC 9728).
+///
+/// MCP servers publish this document so clients can discover which
+/// authorization servers issue tokens for them.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::server::auth::ProtectedResourceMetadata;
+///
+/// let metadata = ProtectedResourceMetadata::new("https://mcp.example.com/mcp")
+///     .authorization_server("https://auth.example.com")
+///     .scopes_supported(["mcp:tools", "mcp:resources"])
+///     .resource_name("Example MCP server");
+///
+/// assert_eq!(
+///     metadata.metadata_url().as_deref(),
+///     Some("https://mcp.example.com/.well-known/oauth-protected-resource/mcp")
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProtectedResourceMetadata {
+    /// Resource identifier.
+    pub resource: String,
+
+    /// Issuer URLs of the authorization servers for this resource.
+    #[serde(default)]
+    pub authorization_servers: Vec<String>,
+
+    /// Scopes the resource understands.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes_supported: Option<Vec<String>>,
+
+    /// Ways the resource accepts bearer tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bearer_methods_supported: Option<Vec<String>>,
+
+    /// Human-readable name of the resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_name: Option<String>,
+
+    /// URL of the resource's documentation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_documentation: Option<String>,
+}
+
+impl ProtectedResourceMetadata {
+    /// Create metadata for `resource` that accepts tokens in the
+    /// `Authorization` header.
+    pub fn new(resource: impl Into<String>) -> Self {
+        Self {
+            resource: resource.into(),
+            authorization_servers: Vec::new(),
+            scopes_supported: None,
+            bearer_methods_supported: Some(vec!["header".to_string()]),
+            resource_name: None,
+            resource_documentation: None,
+        }
+    }
+
+    /// Add an authorization server by its issuer URL.
+    pub fn authorization_server(mut self, issuer: impl Into<String>) -> Self {
+        self.authorization_servers.push(issuer.into());
+        self
+    }
+
+    /// Set the scopes the resource understands.
+    pub fn scopes_supported<S, I>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes_supported = Some(scopes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the human-readable name of the resource.
+    pub fn resource_name(mut self, name: impl Into<String>) -> Self {
+        self.resource_name = Some(name.into());
+        self
+    }
+
+    /// Set the URL of the resource's documentation.
+    pub fn resource_documentation(mut self, url: impl Into<String>) -> Self {
+        self.resource_documentation = Some(url.into());
+        self
+    }
+
+    /// Paths the document is served at: the well-known path with the
+    /// resource's path appended, as RFC 9728 specifies, and the bare
+    /// well-known path for clients that only look at the origin.
+    pub fn well_known_paths(&self) -> Vec<String> {
+        let mut paths = Vec::with_capacity(2);
+        if let Some(path) = self.resource_path() {
+            paths.push(format!("{}{}", PROTECTED_RESOURCE_METADATA_PATH, path));
+        }
+        paths.push(PROTECTED_RESOURCE_METADATA_PATH.to_string());
+        paths
+    }
+
+    /// URL of this document, as advertised in the `resource_metadata`
+    /// parameter of `WWW-Authenticate` challenges. `None` if the resource
+    /// isn't an absolute URL.
+    pub fn metadata_url(&self) -> Option<String> {
+        let url = url::Url::parse(&self.resource).ok()?;
+        Some(format!(
+            "{}{}{}",
+            url.origin().ascii_serialization(),
+            PROTECTED_RESOURCE_METADATA_PATH,
+            self.resource_path().unwrap_or("")
+        ))
+    }
+
+    /// The resource's path, if it has a non-root one.
+    fn resource_path(&self) -> Option<&str> {
+        let after_scheme = self.resource.split_once("://")?.1;
+        let path = after_scheme.find('/').map(|i| &after_scheme[i..])?;
+        let path = path.split(['?', '#']).next()?.trim_end_matches('/');
+        (!path.is_empty()).then_some(path)
+    }
+}
+
+/// Routes serving `metadata` at its well-known paths.
+#[cfg(feature = "streamable-http")]
+pub(crate) fn protected_resource_routes(metadata: &ProtectedResourceMetadata) -> axum::Router {
+    use axum::http::header::ACCESS_CONTROL_ALLOW_ORIGIN;
+
+    let document = Arc::new(metadata.clone());
+    let mut router = axum::Router::new();
+    for path in metadata.well_known_paths() {
+        let document = document.clone();
+        router = router.route(
+            &path,
+            axum::routing::get(move || async move {
+                // Browser-based clients fetch this cross-origin
+                (
+                    [(ACCESS_CONTROL_ALLOW_ORIGIN, "*")],
+                    axum::Json(document.as_ref().clone()),
+                )
+            }),
+        );
+    }
+    router
+}
+
 /// OAuth 2.0 authorization request.
 #[derive(Debug, Clone, Deserialize)]
 pub struct AuthorizationRequest {
@@ -309,6 +468,253 @@ pub struct RevocationRequest {
     pub client_secret: Option<String>,
 }
 
+/// Client metadata sent to the registration endpoint (RFC 7591).
+///
+/// Omitted grant and response types default to the authorization code
+/// flow, and an omitted auth method to `client_secret_basic`, as the RFC
+/// specifies.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClientRegistrationRequest {
+    /// Redirect URIs.
+    #[serde(default)]
+    pub redirect_uris: Vec<String>,
+
+    /// How the client authenticates at the token endpoint (`none` for
+    /// public clients).
+    #[serde(default = "default_token_endpoint_auth_method")]
+    pub token_endpoint_auth_method: String,
+
+    /// Grant types the client will use.
+    #[serde(default = "default_grant_types")]
+    pub grant_types: Vec<GrantType>,
+
+    /// Response types the client will use.
+    #[serde(default = "default_response_types")]
+    pub response_types: Vec<ResponseType>,
+
+    /// Human-readable client name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_name: Option<String>,
+
+    /// Space-separated scopes the client may request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+
+    /// Other metadata (`client_uri`, `logo_uri`, `contacts`, ...), kept as is.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+fn default_token_endpoint_auth_method() -> String {
+    "client_secret_basic".to_string()
+}
+
+fn default_grant_types() -> Vec<GrantType> {
+    vec![GrantType::AuthorizationCode]
+}
+
+fn default_response_types() -> Vec<ResponseType> {
+    vec![ResponseType::Code]
+}
+
+impl ClientRegistrationRequest {
+    /// Check the metadata against what the authorization server supports.
+    ///
+    /// Errors carry the RFC 7591 codes `invalid_redirect_uri` and
+    /// `invalid_client_metadata`.
+    pub fn validate(&self, metadata: &OAuthMetadata) -> std::result::Result<(), OAuthError> {
+        let uses_redirects = self.grant_types.contains(&GrantType::AuthorizationCode)
+            || self.response_types.contains(&ResponseType::Token);
+        if uses_redirects && self.redirect_uris.is_empty() {
+            return Err(OAuthError::new(
+                "invalid_redirect_uri",
+                "redirect_uris is required for redirect-based flows",
+            ));
+        }
+        for uri in &self.redirect_uris {
+            validate_redirect_uri(uri)?;
+        }
+
+        if !metadata
+            .token_endpoint_auth_methods_supported
+            .contains(&self.token_endpoint_auth_method)
+        {
+            return Err(OAuthError::new(
+                "invalid_client_metadata",
+                format!(
+                    "Unsupported token_endpoint_auth_method: {}",
+                    self.token_endpoint_auth_method
+                ),
+            ));
+        }
+        if let Some(grant) = self
+            .grant_types
+            .iter()
+            .find(|g| !metadata.grant_types_supported.contains(g))
+        {
+            return Err(OAuthError::new(
+                "invalid_client_metadata",
+                format!("Unsupported grant type: {:?}", grant),
+            ));
+        }
+        if self.grant_types.contains(&GrantType::AuthorizationCode)
+            != self.response_types.contains(&ResponseType::Code)
+        {
+            return Err(OAuthError::new(
+                "invalid_client_metadata",
+                "The authorization_code grant and the code response type must be used together",
+            ));
+        }
+        if !metadata.scopes_supported.is_empty() {
+            if let Some(scope) = self
+                .scopes()
+                .into_iter()
+                .find(|s| !metadata.scopes_supported.contains(s))
+            {
+                return Err(OAuthError::new(
+                    "invalid_client_metadata",
+                    format!("Unsupported scope: {}", scope),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the client is public, i.e. gets no secret.
+    pub fn is_public(&self) -> bool {
+        self.token_endpoint_auth_method == "none"
+    }
+
+    /// The requested scopes, split.
+    pub fn scopes(&self) -> Vec<String> {
+        self.scope
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(String::from)
+            .collect()
+    }
+
+    /// The client record to store for this registration; the provider
+    /// assigns its ID and secret.
+    fn into_client(self, default_scopes: &[String]) -> OAuthClient {
+        let scopes = match self.scopes() {
+            scopes if scopes.is_empty() => default_scopes.to_vec(),
+            scopes => scopes,
+        };
+        let mut metadata = self.extra;
+        metadata.insert(
+            "token_endpoint_auth_method".to_string(),
+            self.token_endpoint_auth_method.into(),
+        );
+        OAuthClient {
+            client_id: String::new(),
+            client_secret: None,
+            client_name: self.client_name.unwrap_or_default(),
+            redirect_uris: self.redirect_uris,
+            grant_types: self.grant_types,
+            response_types: self.response_types,
+            scopes,
+            metadata,
+        }
+    }
+}
+
+/// Redirect URIs must be absolute, fragment-free, and use plain HTTP only
+/// for loopback hosts (RFC 8252 native apps); custom schemes are allowed.
+fn validate_redirect_uri(uri: &str) -> std::result::Result<(), OAuthError> {
+    let invalid = |reason: &str| {
+        OAuthError::new(
+            "invalid_redirect_uri",
+            format!("Invalid redirect URI {}: {}", uri, reason),
+        )
+    };
+    let url = url::Url::parse(uri).map_err(|_| invalid("not an absolute URI"))?;
+    if url.fragment().is_some() {
+        return Err(invalid("fragments are not allowed"));
+    }
+    if url.scheme() == "http" {
+        let loopback = matches!(
+            url.host(),
+            Some(url::Host::Domain("localhost"))
+                | Some(url::Host::Ipv4(std::net::Ipv4Addr::LOCALHOST))
+                | Some(url::Host::Ipv6(std::net::Ipv6Addr::LOCALHOST))
+        );
+        if !loopback {
+            return Err(invalid("http is only allowed for loopback hosts"));
+        }
+    }
+    Ok(())
+}
+
+/// Registration endpoint response (RFC 7591 section 3.2.1).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientRegistrationResponse {
+    /// Issued client identifier.
+    pub client_id: String,
+
+    /// Issued client secret (confidential clients only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+
+    /// When the client ID was issued, in seconds since the epoch.
+    pub client_id_issued_at: u64,
+
+    /// When the secret expires, in seconds since the epoch; `0` means never.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret_expires_at: Option<u64>,
+
+    /// The registered metadata, with defaults filled in.
+    #[serde(flatten)]
+    pub metadata: ClientRegistrationRequest,
+}
+
+/// Storage for registered clients, so registrations can outlive the
+/// process or be shared between server instances.
+#[async_trait]
+pub trait ClientStore: Send + Sync {
+    /// Insert or replace a client.
+    async fn put_client(&self, client: &OAuthClient) -> Result<()>;
+
+    /// Look up a client by ID.
+    async fn get_client(&self, client_id: &str) -> Result<Option<OAuthClient>>;
+
+    /// Remove a client, returning whether it existed.
+    async fn remove_client(&self, client_id: &str) -> Result<bool>;
+}
+
+/// Client store keeping clients in process memory.
+#[derive(Debug, Default)]
+pub struct InMemoryClientStore {
+    clients: RwLock<HashMap<String, OAuthClient>>,
+}
+
+impl InMemoryClientStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ClientStore for InMemoryClientStore {
+    async fn put_client(&self, client: &OAuthClient) -> Result<()> {
+        self.clients
+            .write()
+            .await
+            .insert(client.client_id.clone(), client.clone());
+        Ok(())
+    }
+
+    async fn get_client(&self, client_id: &str) -> Result<Option<OAuthClient>> {
+        Ok(self.clients.read().await.get(client_id).cloned())
+    }
+
+    async fn remove_client(&self, client_id: &str) -> Result<bool> {
+        Ok(self.clients.write().await.remove(client_id).is_some())
+    }
+}
+
 /// OAuth 2.0 server provider trait.
 #[async_trait]
 pub trait OAuthProvider: Send + Sync {
@@ -355,6 +761,43 @@ pub trait OAuthProvider: Send + Sync {
     /// Get server metadata.
     async fn metadata(&self) -> Result<OAuthMetadata>;
 
+    /// Register a client from RFC 7591 metadata.
+    ///
+    /// The default implementation validates the metadata against
+    /// [`metadata`](Self::metadata) and stores the client with
+    /// [`register_client`](Self::register_client).
+    async fn register_dynamic_client(
+        &self,
+        request: ClientRegistrationRequest,
+    ) -> Result<ClientRegistrationResponse> {
+        let metadata = self.metadata().await?;
+        request.validate(&metadata).map_err(|e| {
+            Error::protocol(
+                ErrorCode::INVALID_PARAMS,
+                e.error_description.unwrap_or(e.error),
+            )
+        })?;
+
+        let client = self
+            .register_client(request.clone().into_client(&metadata.scopes_supported))
+            .await?;
+        Ok(ClientRegistrationResponse {
+            client_secret_expires_at: client.client_secret.as_ref().map(|_| 0),
+            client_id: client.client_id,
+            client_secret: client.client_secret,
+            client_id_issued_at: unix_now(),
+            metadata: request,
+        })
+    }
+
+    /// Replace a confidential client's secret, invalidating the old one.
+    async fn rotate_client_secret(&self, _client_id: &str) -> Result<OAuthClient> {
+        Err(Error::protocol(
+            ErrorCode::METHOD_NOT_FOUND,
+            "Client secret rotation not implemented for this provider",
+        ))
+    }
+
     /// Discover OIDC configuration from well-known endpoint.
     /// Returns the discovery metadata if successful.
     /// Implementations should handle retries for network failures.
@@ -368,14 +811,21 @@ pub trait OAuthProvider: Send + Sync {
     }
 }
 
+/// Seconds since the Unix epoch.
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
 /// In-memory OAuth 2.0 provider implementation.
-#[derive(Debug)]
 pub struct InMemoryOAuthProvider {
     /// Base URL for endpoints.
     base_url: String,
 
     /// Registered clients.
-    clients: Arc<RwLock<HashMap<String, OAuthClient>>>,
+    clients: Arc<dyn ClientStore>,
 
     /// Active authorization codes.
     codes: Arc<RwLock<HashMap<String, AuthorizationCode>>>,
@@ -396,12 +846,24 @@ pub struct InMemoryOAuthProvider {
     supported_scopes: Vec<String>,
 }
 
+impl std::fmt::Debug for InMemoryOAuthProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemoryOAuthProvider")
+            .field("base_url", &self.base_url)
+            .field("clients", &"ClientStore { ... }")
+            .field("token_expiration", &self.token_expiration)
+            .field("code_expiration", &self.code_expiration)
+            .field("supported_scopes", &self.supported_scopes)
+            .finish_non_exhaustive()
+    }
+}
+
 impl InMemoryOAuthProvider {
     /// Create a new in-memory OAuth provider.
     pub fn new(base_url: impl Into<String>) -> Self {
         Self {
             base_url: base_url.into(),
-            clients: Arc::new(RwLock::new(HashMap::new())),
+            clients: Arc::new(InMemoryClientStore::new()),
             codes: Arc::new(RwLock::new(HashMap::new())),
             tokens: Arc::new(RwLock::new(HashMap::new())),
             refresh_tokens: Arc::new(RwLock::new(HashMap::new())),
@@ -411,6 +873,12 @@ impl InMemoryOAuthProvider {
         }
     }
 
+    /// Keep registered clients in `store` instead of process memory.
+    pub fn with_client_store(mut self, store: Arc<dyn ClientStore>) -> Self {
+        self.clients = store;
+        self
+    }
+
     /// Generate a secure random token.
     fn generate_token() -> String {
         Uuid::new_v4().to_string()
@@ -418,10 +886,7 @@ impl InMemoryOAuthProvider {
 
     /// Get current timestamp.
     fn now() -> u64 {
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
+        unix_now()
     }
 
     /// Verify PKCE code challenge.
@@ -450,20 +915,39 @@ impl OAuthProvider for InMemoryOAuthProvider {
         if client.client_id.is_empty() {
             client.client_id = Self::generate_token();
         }
-        if client.client_secret.is_none() {
+        let public = client
+            .metadata
+            .get("token_endpoint_auth_method")
+            .and_then(|m| m.as_str())
+            == Some("none");
+        if client.client_secret.is_none() && !public {
             client.client_secret = Some(Self::generate_token());
         }
 
-        // Store client
-        let mut clients = self.clients.write().await;
-        clients.insert(client.client_id.clone(), client.clone());
-
+        self.clients.put_client(&client).await?;
         Ok(client)
     }
 
     async fn get_client(&self, client_id: &str) -> Result<Option<OAuthClient>> {
-        let clients = self.clients.read().await;
-        Ok(clients.get(client_id).cloned())
+        self.clients.get_client(client_id).await
+    }
+
+    async fn rotate_client_secret(&self, client_id: &str) -> Result<OAuthClient> {
+        let mut client = self
+            .clients
+            .get_client(client_id)
+            .await?
+            .ok_or_else(|| Error::protocol(ErrorCode::INVALID_REQUEST, "Invalid client_id"))?;
+        if client.client_secret.is_none() {
+            return Err(Error::protocol(
+                ErrorCode::INVALID_REQUEST,
+                "Public clients have no secret to rotate",
+            ));
+        }
+
+        client.client_secret = Some(Self::generate_token());
+        self.clients.put_client(&client).await?;
+        Ok(client)
     }
 
     async fn validate_authorization(&self, request: &AuthorizationRequest) -> Result<()> {
@@ -722,12 +1206,67 @@ impl OAuthProvider for InMemoryOAuthProvider {
             token_endpoint_auth_methods_supported: vec![
                 "client_secret_basic".to_string(),
                 "client_secret_post".to_string(),
+                "none".to_string(),
             ],
             code_challenge_methods_supported: vec!["plain".to_string(), "S256".to_string()],
         })
     }
 }
 
+/// Path the client registration endpoint is served at, matching the
+/// `registration_endpoint` advertised by [`InMemoryOAuthProvider`].
+pub const CLIENT_REGISTRATION_PATH: &str = "/oauth2/register";
+
+/// Routes serving an RFC 7591 registration endpoint for `provider` at
+/// [`CLIENT_REGISTRATION_PATH`].
+///
+/// Rejected registrations get a `400` with an [`OAuthError`] body, as the
+/// RFC requires.
+#[cfg(feature = "streamable-http")]
+pub fn client_registration_routes(provider: Arc<dyn OAuthProvider>) -> axum::Router {
+    axum::Router::new().route(
+        CLIENT_REGISTRATION_PATH,
+        axum::routing::post(move |body: axum::body::Bytes| {
+            let provider = provider.clone();
+            async move { handle_client_registration(provider.as_ref(), &body).await }
+        }),
+    )
+}
+
+#[cfg(feature = "streamable-http")]
+async fn handle_client_registration(
+    provider: &dyn OAuthProvider,
+    body: &[u8],
+) -> axum::response::Response {
+    use axum::http::{header::CACHE_CONTROL, StatusCode};
+    use axum::response::IntoResponse;
+
+    let rejected = |error: OAuthError| (StatusCode::BAD_REQUEST, axum::Json(error)).into_response();
+
+    let request: ClientRegistrationRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return rejected(OAuthError::new("invalid_client_metadata", e.to_string())),
+    };
+    // Validate here as well so the specific RFC 7591 error code survives
+    let metadata = match provider.metadata().await {
+        Ok(metadata) => metadata,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    if let Err(error) = request.validate(&metadata) {
+        return rejected(error);
+    }
+
+    match provider.register_dynamic_client(request).await {
+        Ok(response) => (
+            StatusCode::CREATED,
+            [(CACHE_CONTROL, "no-store")],
+            axum::Json(response),
+        )
+            .into_response(),
+        Err(e) => rejected(OAuthError::new("invalid_client_metadata", e.to_string())),
+    }
+}
+
 /// Proxy OAuth provider that delegates to an upstream OAuth server.
 #[derive(Debug)]
 pub struct ProxyOAuthProvider {
@@ -755,6 +1294,123 @@ impl ProxyOAuthProvider {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_protected_resource_well_known_paths() {
+        let metadata = ProtectedResourceMetadata::new("https://mcp.example.com/tenant/mcp/");
+        assert_eq!(
+            metadata.well_known_paths(),
+            [
+                "/.well-known/oauth-protected-resource/tenant/mcp",
+                "/.well-known/oauth-protected-resource",
+            ]
+        );
+
+        let root = ProtectedResourceMetadata::new("https://mcp.example.com");
+        assert_eq!(root.well_known_paths(), [PROTECTED_RESOURCE_METADATA_PATH]);
+        assert_eq!(
+            root.metadata_url().as_deref(),
+            Some("https://mcp.example.com/.well-known/oauth-protected-resource")
+        );
+        assert_eq!(
+            ProtectedResourceMetadata::new("not a url").metadata_url(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_client_registration() {
+        let provider = InMemoryOAuthProvider::new("http://localhost:8080");
+
+        let request: ClientRegistrationRequest = serde_json::from_value(serde_json::json!({
+            "redirect_uris": ["http://127.0.0.1:3000/callback"],
+            "client_name": "Inspector",
+            "scope": "read",
+            "logo_uri": "https://example.com/logo.png",
+        }))
+        .unwrap();
+        let response = provider.register_dynamic_client(request).await.unwrap();
+        assert_eq!(response.client_secret_expires_at, Some(0));
+        assert_eq!(
+            response.metadata.grant_types,
+            [GrantType::AuthorizationCode]
+        );
+
+        let client = provider
+            .get_client(&response.client_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(client.client_secret, response.client_secret);
+        assert_eq!(client.scopes, ["read"]);
+        assert_eq!(client.metadata["logo_uri"], "https://example.com/logo.png");
+
+        let rotated = provider
+            .rotate_client_secret(&response.client_id)
+            .await
+            .unwrap();
+        assert!(rotated.client_secret.is_some());
+        assert_ne!(rotated.client_secret, response.client_secret);
+
+        // Public clients get no secret, so there is nothing to rotate
+        let public: ClientRegistrationRequest = serde_json::from_value(serde_json::json!({
+            "redirect_uris": ["myapp://callback"],
+            "token_endpoint_auth_method": "none",
+        }))
+        .unwrap();
+        let response = provider.register_dynamic_client(public).await.unwrap();
+        assert!(response.client_secret.is_none());
+        assert!(provider
+            .rotate_client_secret(&response.client_id)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_client_registration_validation() {
+        let metadata = InMemoryOAuthProvider::new("http://localhost:8080")
+            .metadata()
+            .await
+            .unwrap();
+        let error_for = |value: serde_json::Value| {
+            serde_json::from_value::<ClientRegistrationRequest>(value)
+                .unwrap()
+                .validate(&metadata)
+                .unwrap_err()
+                .error
+        };
+
+        assert_eq!(error_for(serde_json::json!({})), "invalid_redirect_uri");
+        assert_eq!(
+            error_for(serde_json::json!({"redirect_uris": ["http://example.com/cb"]})),
+            "invalid_redirect_uri"
+        );
+        assert_eq!(
+            error_for(serde_json::json!({"redirect_uris": ["https://example.com/cb#frag"]})),
+            "invalid_redirect_uri"
+        );
+        assert_eq!(
+            error_for(serde_json::json!({
+                "redirect_uris": ["https://example.com/cb"],
+                "scope": "admin",
+            })),
+            "invalid_client_metadata"
+        );
+        assert_eq!(
+            error_for(serde_json::json!({
+                "redirect_uris": ["https://example.com/cb"],
+                "token_endpoint_auth_method": "private_key_jwt",
+            })),
+            "invalid_client_metadata"
+        );
+        assert_eq!(
+            error_for(serde_json::json!({
+                "redirect_uris": ["https://example.com/cb"],
+                "response_types": ["token"],
+            })),
+            "invalid_client_metadata"
+        );
+    }
+
     #[tokio::test]
     async fn test_oauth_flow() {
         let provider = InMemoryOAuthProvider::new("http://localhost:8080");
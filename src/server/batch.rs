@@ -49,7 +49,12 @@ mod tests {
     use super::*;
     use crate::server::ServerBuilder;
     use crate::shared::batch::BatchRequest;
-    use crate::types::{JSONRPCRequest, RequestId};
+    use crate::shared::{Transport, TransportMessage};
+    use crate::types::jsonrpc::ResponsePayload;
+    use crate::types::{
+        CallToolRequest, ClientCapabilities, ClientNotification, ClientRequest, Implementation,
+        InitializeRequest, JSONRPCRequest, Notification, Request, RequestId,
+    };
     use serde_json::json;
 
     #[tokio::test]
@@ -175,4 +180,133 @@ mod tests {
             crate::types::jsonrpc::ResponsePayload::Result(_) => panic!("Expected error response"),
         }
     }
+
+    /// Transport exchanging whole frames, driven by the test as the client.
+    #[derive(Debug)]
+    struct FrameTransport {
+        incoming: tokio::sync::mpsc::UnboundedReceiver<Vec<TransportMessage>>,
+        outgoing: tokio::sync::mpsc::UnboundedSender<Vec<TransportMessage>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for FrameTransport {
+        async fn send(&mut self, message: TransportMessage) -> Result<()> {
+            let _ = self.outgoing.send(vec![message]);
+            Ok(())
+        }
+
+        async fn send_batch(&mut self, messages: Vec<TransportMessage>) -> Result<()> {
+            let _ = self.outgoing.send(messages);
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<TransportMessage> {
+            Err(crate::Error::protocol_msg("Frames are received whole"))
+        }
+
+        async fn receive_batch(&mut self) -> Result<Vec<TransportMessage>> {
+            self.incoming
+                .recv()
+                .await
+                .ok_or_else(|| crate::Error::protocol_msg("Client closed"))
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Tool that waits until `peers` calls are running at once.
+    struct Rendezvous(Arc<tokio::sync::Barrier>);
+
+    #[async_trait::async_trait]
+    impl crate::ToolHandler for Rendezvous {
+        async fn handle(
+            &self,
+            args: serde_json::Value,
+            _extra: crate::RequestHandlerExtra,
+        ) -> Result<serde_json::Value> {
+            self.0.wait().await;
+            Ok(args)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transport_batch_runs_concurrently_and_replies_in_order() {
+        let server = ServerBuilder::new()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("meet", Rendezvous(Arc::new(tokio::sync::Barrier::new(3))))
+            .build()
+            .unwrap();
+        let (to_server, incoming) = tokio::sync::mpsc::unbounded_channel();
+        let (outgoing, mut from_server) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(server.run(FrameTransport { incoming, outgoing }));
+
+        let request = |id: i64, request: ClientRequest| TransportMessage::Request {
+            id: RequestId::from(id),
+            request: Request::Client(Box::new(request)),
+        };
+        let call = |id: i64| {
+            request(
+                id,
+                ClientRequest::CallTool(CallToolRequest::new("meet", json!({ "n": id }))),
+            )
+        };
+        async fn next(
+            rx: &mut tokio::sync::mpsc::UnboundedReceiver<Vec<TransportMessage>>,
+        ) -> Vec<TransportMessage> {
+            tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+                .await
+                .expect("server did not answer")
+                .unwrap()
+        }
+
+        to_server
+            .send(vec![request(
+                1,
+                ClientRequest::Initialize(InitializeRequest {
+                    protocol_version: "2025-03-26".to_string(),
+                    capabilities: ClientCapabilities::default(),
+                    client_info: Implementation {
+                        name: "test-client".to_string(),
+                        version: "1.0.0".to_string(),
+                    },
+                }),
+            )])
+            .unwrap();
+        assert_eq!(next(&mut from_server).await.len(), 1);
+
+        // Each call waits for the other two, so they only finish if the
+        // batch runs them concurrently. The notification gets no reply.
+        to_server
+            .send(vec![
+                call(4),
+                TransportMessage::Notification(Notification::Client(
+                    ClientNotification::Initialized,
+                )),
+                call(2),
+                call(3),
+            ])
+            .unwrap();
+        let replies = next(&mut from_server).await;
+        let ids: Vec<_> = replies
+            .iter()
+            .map(|reply| match reply {
+                TransportMessage::Response(response) => {
+                    assert!(matches!(response.payload, ResponsePayload::Result(_)));
+                    response.id.clone()
+                },
+                other => panic!("expected a response, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                RequestId::from(4i64),
+                RequestId::from(2i64),
+                RequestId::from(3i64)
+            ]
+        );
+    }
 }
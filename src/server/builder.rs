@@ -3,8 +3,9 @@
 use crate::error::{Error, Result};
 use crate::server::auth::{AuthProvider, ToolAuthorizer};
 use crate::server::core::ServerCore;
+use crate::server::resource_templates::ResourceTemplateRegistry;
 use crate::server::{PromptHandler, ResourceHandler, SamplingHandler, ToolHandler};
-use crate::types::{Implementation, ServerCapabilities};
+use crate::types::{Implementation, ResourceTemplate, ServerCapabilities};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -49,9 +50,11 @@ pub struct ServerCoreBuilder {
     tools: HashMap<String, Arc<dyn ToolHandler>>,
     prompts: HashMap<String, Arc<dyn PromptHandler>>,
     resources: Option<Arc<dyn ResourceHandler>>,
+    resource_templates: Vec<ResourceTemplate>,
     sampling: Option<Arc<dyn SamplingHandler>>,
     auth_provider: Option<Arc<dyn AuthProvider>>,
     tool_authorizer: Option<Arc<dyn ToolAuthorizer>>,
+    validate_tool_output: bool,
 }
 
 impl Default for ServerCoreBuilder {
@@ -70,9 +73,11 @@ impl ServerCoreBuilder {
             tools: HashMap::new(),
             prompts: HashMap::new(),
             resources: None,
+            resource_templates: Vec::new(),
             sampling: None,
             auth_provider: None,
             tool_authorizer: None,
+            validate_tool_output: false,
         }
     }
 
@@ -198,6 +203,35 @@ impl ServerCoreBuilder {
         self
     }
 
+    /// Register a parameterized resource template.
+    ///
+    /// Templates are advertised through `resources/templates/list`; reading
+    /// the expanded URIs is still served by the resource handler.
+    pub fn resource_template(
+        mut self,
+        uri_template: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        mime_type: Option<String>,
+    ) -> Self {
+        self.resource_templates.push(ResourceTemplate {
+            uri_template: uri_template.into(),
+            name: name.into(),
+            description: Some(description.into()),
+            mime_type,
+        });
+
+        // Update capabilities to include resources
+        if self.capabilities.resources.is_none() {
+            self.capabilities.resources = Some(crate::types::ResourceCapabilities {
+                subscribe: None,
+                list_changed: None,
+            });
+        }
+
+        self
+    }
+
     /// Set the sampling handler.
     ///
     /// Sampling provides LLM capabilities for message generation.
@@ -258,6 +292,15 @@ impl ServerCoreBuilder {
         self
     }
 
+    /// Check tool results against the output schema each tool advertises.
+    ///
+    /// A result that does not match is replaced by an internal error listing
+    /// each mismatch.
+    pub fn validate_tool_output(mut self, enabled: bool) -> Self {
+        self.validate_tool_output = enabled;
+        self
+    }
+
     /// Build the `ServerCore` instance.
     ///
     /// Returns an error if required fields (name, version) are not set.
@@ -270,6 +313,17 @@ impl ServerCoreBuilder {
             .version
             .ok_or_else(|| Error::validation("Server version is required"))?;
 
+        for (prompt_name, prompt) in &self.prompts {
+            prompt.validate().map_err(|e| {
+                Error::validation(format!("Invalid prompt '{}': {}", prompt_name, e))
+            })?;
+        }
+
+        let mut resource_templates = ResourceTemplateRegistry::new();
+        for template in self.resource_templates {
+            resource_templates.register(template)?;
+        }
+
         let info = Implementation { name, version };
 
         Ok(ServerCore::new(
@@ -281,7 +335,9 @@ impl ServerCoreBuilder {
             self.sampling,
             self.auth_provider,
             self.tool_authorizer,
-        ))
+        )
+        .with_resource_templates(resource_templates)
+        .with_output_validation(self.validate_tool_output))
     }
 }
 
@@ -2,6 +2,7 @@
 
 use crate::error::Result;
 use crate::types::protocol::{CancelledNotification, Notification};
+use crate::types::RequestId;
 use std::collections::HashMap;
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
@@ -9,7 +10,34 @@ use tokio::sync::RwLock;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio_util::sync::CancellationToken;
 
+tokio::task_local! {
+    /// Session of the request being handled, for transports whose clients
+    /// share one server
+    pub(crate) static SESSION_ID: String;
+}
+
+/// Key of a request's cancellation token.
+///
+/// Clients number their requests independently, so a request ID is only
+/// unique within the session that sent it.
+pub(crate) fn request_key(session_id: Option<&str>, request_id: &RequestId) -> String {
+    match session_id {
+        Some(session_id) => format!("{}/{}", session_id, request_id),
+        None => request_id.to_string(),
+    }
+}
+
+/// Key of the cancellation token of a request handled in this task.
+pub(crate) fn current_request_key(request_id: &RequestId) -> String {
+    SESSION_ID
+        .try_with(|session_id| request_key(Some(session_id), request_id))
+        .unwrap_or_else(|_| request_key(None, request_id))
+}
+
 /// Manages cancellation tokens for requests.
+///
+/// Clones share the same set of tokens.
+#[derive(Clone)]
 pub struct CancellationManager {
     tokens: Arc<RwLock<HashMap<String, CancellationToken>>>,
     notification_sender: Option<Arc<dyn Fn(Notification) + Send + Sync>>,
@@ -63,6 +91,20 @@ impl CancellationManager {
         Ok(())
     }
 
+    /// Cancel a request because the peer asked to, without notifying it back.
+    ///
+    /// Returns whether the request was still in flight.
+    pub async fn cancel_from_peer(&self, request_id: &str) -> bool {
+        let token = self.tokens.write().await.remove(request_id);
+        match token {
+            Some(token) => {
+                token.cancel();
+                true
+            },
+            None => false,
+        }
+    }
+
     /// Remove a completed request's token.
     pub async fn remove_token(&self, request_id: &str) {
         let mut tokens = self.tokens.write().await;
@@ -124,6 +166,12 @@ pub struct RequestHandlerExtra {
     pub auth_info: Option<crate::types::auth::AuthInfo>,
     /// Validated authentication context (if auth is enabled)
     pub auth_context: Option<crate::server::auth::AuthContext>,
+    /// Progress reporter, if the caller asked for progress
+    pub progress: Option<crate::server::progress::ProgressReporter>,
+    /// Channel for requests back to the connected client
+    pub client_requester: Option<crate::server::client_requests::ClientRequester>,
+    /// Logger forwarding messages to the connected client
+    pub logger: Option<crate::server::logging::ServerLogger>,
 }
 
 impl RequestHandlerExtra {
@@ -135,6 +183,9 @@ impl RequestHandlerExtra {
             session_id: None,
             auth_info: None,
             auth_context: None,
+            progress: None,
+            client_requester: None,
+            logger: None,
         }
     }
 
@@ -159,6 +210,161 @@ impl RequestHandlerExtra {
         self
     }
 
+    /// Set the progress reporter.
+    pub fn with_progress_reporter(
+        mut self,
+        progress: Option<crate::server::progress::ProgressReporter>,
+    ) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Get the progress reporter, if the caller asked for progress.
+    pub fn progress_reporter(&self) -> Option<&crate::server::progress::ProgressReporter> {
+        self.progress.as_ref()
+    }
+
+    /// Report progress to the caller.
+    ///
+    /// Sends `notifications/progress` with the caller's progress token; does
+    /// nothing if the caller did not ask for progress. Updates are rate
+    /// limited, see [`progress`](crate::server::progress).
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the server is no longer running.
+    pub async fn report_progress(
+        &self,
+        progress: f64,
+        total: Option<f64>,
+        message: Option<String>,
+    ) -> Result<()> {
+        if let Some(reporter) = &self.progress {
+            reporter.report(progress, total, message).await?;
+        }
+        Ok(())
+    }
+
+    /// Set the channel for requests back to the connected client.
+    pub fn with_client_requester(
+        mut self,
+        client_requester: Option<crate::server::client_requests::ClientRequester>,
+    ) -> Self {
+        self.client_requester = client_requester;
+        self
+    }
+
+    /// Set the logger forwarding messages to the client.
+    pub fn with_logger(mut self, logger: Option<crate::server::logging::ServerLogger>) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Whether the connected client can sample messages for this handler.
+    pub fn supports_sampling(&self) -> bool {
+        self.client_requester
+            .as_ref()
+            .is_some_and(crate::server::client_requests::ClientRequester::supports_sampling)
+    }
+
+    /// Ask the connected client to sample a message from its LLM.
+    ///
+    /// See [`client_requests`](crate::server::client_requests).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handler is not running on a connected server,
+    /// the client does not support sampling, or the request fails.
+    pub async fn create_message(
+        &self,
+        params: crate::types::CreateMessageParams,
+    ) -> Result<crate::types::CreateMessageResult> {
+        let requester = self.client_requester.as_ref().ok_or_else(|| {
+            crate::error::Error::invalid_state("Handler is not connected to a client")
+        })?;
+        requester.create_message(params).await
+    }
+
+    /// Whether the connected client can ask its user for input.
+    pub fn supports_elicitation(&self) -> bool {
+        self.client_requester
+            .as_ref()
+            .is_some_and(crate::server::client_requests::ClientRequester::supports_elicitation)
+    }
+
+    /// Ask the user, through the connected client, for input matching
+    /// `requested_schema`.
+    ///
+    /// The result says whether the user accepted, declined or cancelled;
+    /// the submitted data is in [`ElicitResult::accepted`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::RequestHandlerExtra;
+    /// use serde_json::json;
+    ///
+    /// async fn confirm(extra: &RequestHandlerExtra) -> pmcp::Result<bool> {
+    ///     let result = extra
+    ///         .elicit(
+    ///             json!({
+    ///                 "type": "object",
+    ///                 "properties": {"confirm": {"type": "boolean"}},
+    ///                 "required": ["confirm"]
+    ///             }),
+    ///             "Delete 12 files?",
+    ///         )
+    ///         .await?;
+    ///     Ok(result
+    ///         .accepted()
+    ///         .and_then(|content| content["confirm"].as_bool())
+    ///         .unwrap_or(false))
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handler is not running on a connected server,
+    /// the client does not support elicitation, or the request fails.
+    ///
+    /// [`ElicitResult::accepted`]: crate::types::elicitation::ElicitResult::accepted
+    pub async fn elicit(
+        &self,
+        requested_schema: serde_json::Value,
+        message: impl Into<String>,
+    ) -> Result<crate::types::elicitation::ElicitResult> {
+        let requester = self.client_requester.as_ref().ok_or_else(|| {
+            crate::error::Error::invalid_state("Handler is not connected to a client")
+        })?;
+        requester
+            .elicit(crate::types::elicitation::ElicitRequestParams::new(
+                message,
+                requested_schema,
+            ))
+            .await
+    }
+
+    /// Whether the connected client can list its filesystem roots.
+    pub fn supports_roots(&self) -> bool {
+        self.client_requester
+            .as_ref()
+            .is_some_and(crate::server::client_requests::ClientRequester::supports_roots)
+    }
+
+    /// Ask the connected client for the filesystem roots the server may
+    /// operate on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handler is not running on a connected server,
+    /// the client does not support roots, or the request fails.
+    pub async fn list_roots(&self) -> Result<crate::server::roots::ListRootsResult> {
+        let requester = self.client_requester.as_ref().ok_or_else(|| {
+            crate::error::Error::invalid_state("Handler is not connected to a client")
+        })?;
+        requester.list_roots().await
+    }
+
     /// Get the auth context if available.
     pub fn auth_context(&self) -> Option<&crate::server::auth::AuthContext> {
         self.auth_context.as_ref()
@@ -243,6 +449,22 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_cancel_from_peer_does_not_notify() {
+        let mut manager = CancellationManager::new();
+        let sent = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let sent_clone = sent.clone();
+        manager.set_notification_sender(Arc::new(move |_| {
+            sent_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+        }));
+
+        let token = manager.create_token("7".to_string()).await;
+        assert!(manager.clone().cancel_from_peer("7").await);
+        assert!(token.is_cancelled());
+        assert!(!manager.cancel_from_peer("7").await);
+        assert!(!sent.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
     #[tokio::test]
     async fn test_remove_token() {
         let manager = CancellationManager::new();
@@ -0,0 +1,449 @@
+//! Requests from the server to the connected client.
+//!
+//! MCP lets servers call back into the client, most notably
+//! `sampling/createMessage` to have the client's LLM generate a message. A
+//! [`ClientRequester`] sends such requests over the server's transport and
+//! matches the client's responses to them, so tool handlers can await the
+//! result mid-call through [`RequestHandlerExtra::create_message`].
+//!
+//! Servers can also ask the user for structured input with
+//! `elicitation/create`, through [`RequestHandlerExtra::elicit`], and ask for
+//! the client's filesystem roots with `roots/list`, through
+//! [`RequestHandlerExtra::list_roots`]. Outside a handler, take a requester
+//! from [`Server::client_requester`](crate::Server::client_requester) before
+//! running the server.
+//!
+//! The requester also records what the client declared at initialization;
+//! sampling, elicitation and roots requests to a client without the
+//! matching capability fail immediately instead of waiting for an error
+//! response.
+//!
+//! On the streamable HTTP server, each session has a requester of its own,
+//! which handlers of the session's requests get through their
+//! [`RequestHandlerExtra`]. Its requests go out on the SSE stream the client
+//! opened with `GET`, and fail if there is none; the client POSTs its
+//! responses back. Stateless servers cannot make requests to the client.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use pmcp::types::{Content, CreateMessageParams, Role, SamplingMessage};
+//! use pmcp::{RequestHandlerExtra, ToolHandler};
+//! use serde_json::{json, Value};
+//!
+//! struct Summarize;
+//!
+//! #[async_trait]
+//! impl ToolHandler for Summarize {
+//!     async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> pmcp::Result<Value> {
+//!         let text = args["text"].as_str().unwrap_or_default();
+//!         let reply = extra
+//!             .create_message(CreateMessageParams::new(vec![SamplingMessage {
+//!                 role: Role::User,
+//!                 content: Content::Text {
+//!                     text: format!("Summarize in one sentence: {}", text),
+//!                 },
+//!             }]))
+//!             .await?;
+//!         Ok(json!({"summary": reply.content, "model": reply.model}))
+//!     }
+//! }
+//! ```
+//!
+//! [`RequestHandlerExtra`]: crate::server::cancellation::RequestHandlerExtra
+//! [`RequestHandlerExtra::create_message`]: crate::server::cancellation::RequestHandlerExtra::create_message
+//! [`RequestHandlerExtra::elicit`]: crate::server::cancellation::RequestHandlerExtra::elicit
+//! [`RequestHandlerExtra::list_roots`]: crate::server::cancellation::RequestHandlerExtra::list_roots
+
+use crate::error::{Error, Result, TransportError};
+use crate::server::roots::ListRootsResult;
+use crate::shared::protocol::{NegotiatedVersion, ProtocolFeature};
+use crate::shared::{OutgoingQueue, TransportMessage};
+use crate::types::elicitation::{ElicitRequestParams, ElicitResult};
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::{
+    ClientCapabilities, CreateMessageParams, CreateMessageResult, JSONRPCResponse, Request,
+    RequestId, ServerRequest,
+};
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Default time to wait for the client to answer a request.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+tokio::task_local! {
+    /// Requester of the HTTP session whose request is being handled
+    pub(crate) static SESSION_REQUESTER: ClientRequester;
+}
+
+/// Requester of the HTTP session whose request is being handled, if any.
+pub(crate) fn session_requester() -> Option<ClientRequester> {
+    SESSION_REQUESTER.try_with(Clone::clone).ok()
+}
+
+/// Sends a message to the client.
+type Sink = Arc<dyn Fn(TransportMessage) -> Result<()> + Send + Sync>;
+
+/// Sends requests to the connected client and awaits their responses.
+///
+/// Clones share the same connection.
+#[derive(Clone)]
+pub struct ClientRequester {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    pending: Mutex<HashMap<RequestId, oneshot::Sender<Result<Value>>>>,
+    /// Where requests go, set when the server starts
+    sink: Mutex<Option<Sink>>,
+    /// Capabilities the client declared at initialization
+    capabilities: Mutex<Option<ClientCapabilities>>,
+    /// Version agreed on at initialization
+    version: Mutex<Option<NegotiatedVersion>>,
+    next_id: AtomicU64,
+    timeout: Duration,
+}
+
+impl fmt::Debug for ClientRequester {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientRequester")
+            .field("pending", &self.inner.pending.lock().len())
+            .field("timeout", &self.inner.timeout)
+            .finish()
+    }
+}
+
+impl Default for ClientRequester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientRequester {
+    /// Create a requester that is not yet connected.
+    pub fn new() -> Self {
+        Self::with_timeout(DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    /// Create a requester that gives up on responses after `timeout`.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                pending: Mutex::new(HashMap::new()),
+                sink: Mutex::new(None),
+                capabilities: Mutex::new(None),
+                version: Mutex::new(None),
+                next_id: AtomicU64::new(1),
+                timeout,
+            }),
+        }
+    }
+
+    /// Send requests through `outgoing` from now on.
+    pub(crate) fn attach(&self, outgoing: Arc<OutgoingQueue>) {
+        self.attach_sink(move |message| {
+            outgoing.push(None, message);
+            Ok(())
+        });
+    }
+
+    /// Send requests through `sink` from now on.
+    pub(crate) fn attach_sink(
+        &self,
+        sink: impl Fn(TransportMessage) -> Result<()> + Send + Sync + 'static,
+    ) {
+        *self.inner.sink.lock() = Some(Arc::new(sink));
+    }
+
+    /// Record the capabilities the client declared at initialization.
+    pub(crate) fn set_client_capabilities(&self, capabilities: ClientCapabilities) {
+        *self.inner.capabilities.lock() = Some(capabilities);
+    }
+
+    /// Record the version agreed on at initialization.
+    pub(crate) fn set_protocol_version(&self, version: NegotiatedVersion) {
+        *self.inner.version.lock() = Some(version);
+    }
+
+    /// The version agreed on with the client, once initialized.
+    pub fn protocol_version(&self) -> Option<NegotiatedVersion> {
+        self.inner.version.lock().clone()
+    }
+
+    /// Whether the connected client declared the `sampling` capability.
+    pub fn supports_sampling(&self) -> bool {
+        self.inner
+            .capabilities
+            .lock()
+            .as_ref()
+            .is_some_and(|c| c.sampling.is_some())
+    }
+
+    /// Whether the connected client declared the `elicitation` capability.
+    pub fn supports_elicitation(&self) -> bool {
+        self.inner
+            .capabilities
+            .lock()
+            .as_ref()
+            .is_some_and(ClientCapabilities::supports_elicitation)
+    }
+
+    /// Whether the connected client declared the `roots` capability.
+    pub fn supports_roots(&self) -> bool {
+        self.inner
+            .capabilities
+            .lock()
+            .as_ref()
+            .is_some_and(|c| c.roots.is_some())
+    }
+
+    /// Whether any request is waiting for a response.
+    pub fn has_pending(&self) -> bool {
+        !self.inner.pending.lock().is_empty()
+    }
+
+    /// Ask the client to sample a message from its LLM.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client does not support sampling, rejects the
+    /// request, or does not answer within the timeout.
+    pub async fn create_message(&self, params: CreateMessageParams) -> Result<CreateMessageResult> {
+        if !self.supports_sampling() {
+            return Err(Error::invalid_state(
+                "Client did not declare the sampling capability",
+            ));
+        }
+        let value = self
+            .request(ServerRequest::CreateMessage(Box::new(params)))
+            .await?;
+        serde_json::from_value(value)
+            .map_err(|e| Error::parse(format!("Invalid sampling result from client: {}", e)))
+    }
+
+    /// Ask the client to collect input from its user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client does not support elicitation, rejects
+    /// the request, or does not answer within the timeout. The error is
+    /// [`Error::UnsupportedProtocolFeature`] if the version agreed on
+    /// predates elicitation.
+    pub async fn elicit(&self, params: ElicitRequestParams) -> Result<ElicitResult> {
+        if let Some(version) = self.protocol_version() {
+            version.require(ProtocolFeature::Elicitation)?;
+        }
+        if !self.supports_elicitation() {
+            return Err(Error::invalid_state(
+                "Client did not declare the elicitation capability",
+            ));
+        }
+        let value = self
+            .request(ServerRequest::Elicit(Box::new(params)))
+            .await?;
+        serde_json::from_value(value)
+            .map_err(|e| Error::parse(format!("Invalid elicitation result from client: {}", e)))
+    }
+
+    /// Ask the client for the filesystem roots the server may operate on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client does not support roots, rejects the
+    /// request, or does not answer within the timeout.
+    pub async fn list_roots(&self) -> Result<ListRootsResult> {
+        if !self.supports_roots() {
+            return Err(Error::invalid_state(
+                "Client did not declare the roots capability",
+            ));
+        }
+        let value = self.request(ServerRequest::ListRoots).await?;
+        serde_json::from_value(value)
+            .map_err(|e| Error::parse(format!("Invalid roots result from client: {}", e)))
+    }
+
+    /// Send a request to the client and wait for its result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server is not running, the client answers
+    /// with an error, or no answer arrives within the timeout.
+    pub async fn request(&self, request: ServerRequest) -> Result<Value> {
+        let sink = self
+            .inner
+            .sink
+            .lock()
+            .clone()
+            .ok_or_else(|| Error::invalid_state("Server is not connected to a client"))?;
+
+        let id = RequestId::String(format!(
+            "server-{}",
+            self.inner.next_id.fetch_add(1, Ordering::Relaxed)
+        ));
+        let (tx, rx) = oneshot::channel();
+        self.inner.pending.lock().insert(id.clone(), tx);
+        let sent = sink(TransportMessage::Request {
+            id: id.clone(),
+            request: Request::Server(Box::new(request)),
+        });
+        if let Err(e) = sent {
+            self.inner.pending.lock().remove(&id);
+            return Err(e);
+        }
+
+        let outcome = tokio::time::timeout(self.inner.timeout, rx).await;
+        self.inner.pending.lock().remove(&id);
+        match outcome {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(Error::Transport(TransportError::ConnectionClosed)),
+            Err(_) => Err(Error::timeout(
+                u64::try_from(self.inner.timeout.as_millis()).unwrap_or(u64::MAX),
+            )),
+        }
+    }
+
+    /// Complete the request a client response answers.
+    ///
+    /// Returns `false` if no request is waiting for this response.
+    pub(crate) fn resolve(&self, response: JSONRPCResponse) -> bool {
+        let Some(tx) = self.inner.pending.lock().remove(&response.id) else {
+            return false;
+        };
+        let result = match response.payload {
+            ResponsePayload::Result(value) => Ok(value),
+            ResponsePayload::Error(error) => Err(Error::from_jsonrpc_error(error)),
+        };
+        let _ = tx.send(result);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::capabilities::SamplingCapabilities;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_request_round_trip() {
+        let requester = ClientRequester::new();
+        assert!(requester.request(ServerRequest::ListRoots).await.is_err());
+
+        let outgoing = Arc::new(OutgoingQueue::new());
+        requester.attach(outgoing.clone());
+        let call = tokio::spawn({
+            let requester = requester.clone();
+            async move { requester.request(ServerRequest::ListRoots).await }
+        });
+
+        let (_, message) = outgoing.next().await;
+        let TransportMessage::Request { id, .. } = message else {
+            panic!("Expected request, got {:?}", message);
+        };
+        assert!(requester.has_pending());
+        assert!(requester.resolve(JSONRPCResponse::success(id, json!({"roots": []}))));
+        assert_eq!(call.await.unwrap().unwrap(), json!({"roots": []}));
+        assert!(!requester.has_pending());
+        assert!(!requester.resolve(JSONRPCResponse::success(RequestId::from(99i64), json!({}))));
+    }
+
+    #[tokio::test]
+    async fn test_create_message_requires_sampling_capability() {
+        let requester = ClientRequester::new();
+        requester.attach(Arc::new(OutgoingQueue::new()));
+        requester.set_client_capabilities(ClientCapabilities::default());
+        assert!(!requester.supports_sampling());
+        let err = requester
+            .create_message(CreateMessageParams::new(vec![]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("sampling"), "{}", err);
+
+        requester.set_client_capabilities(ClientCapabilities {
+            sampling: Some(SamplingCapabilities::default()),
+            ..Default::default()
+        });
+        assert!(requester.supports_sampling());
+    }
+
+    #[tokio::test]
+    async fn test_elicit_round_trip() {
+        let requester = ClientRequester::new();
+        let outgoing = Arc::new(OutgoingQueue::new());
+        requester.attach(outgoing.clone());
+        let params = ElicitRequestParams::new("Your name?", json!({"type": "object"}));
+        assert!(requester.elicit(params.clone()).await.is_err());
+
+        requester.set_client_capabilities(ClientCapabilities {
+            elicitation: Some(Default::default()),
+            ..Default::default()
+        });
+        let call = tokio::spawn({
+            let requester = requester.clone();
+            async move { requester.elicit(params).await }
+        });
+        let (_, message) = outgoing.next().await;
+        let TransportMessage::Request { id, request } = message else {
+            panic!("Expected request, got {:?}", message);
+        };
+        assert_eq!(
+            crate::shared::protocol_helpers::request_method(&request),
+            "elicitation/create"
+        );
+        requester.resolve(JSONRPCResponse::success(id, json!({"action": "decline"})));
+        assert_eq!(call.await.unwrap().unwrap(), ElicitResult::decline());
+
+        // Clients on older protocol versions cannot be asked
+        requester.set_protocol_version(NegotiatedVersion::new("2025-03-26"));
+        let params = ElicitRequestParams::new("Your name?", json!({"type": "object"}));
+        let err = requester.elicit(params).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnsupportedProtocolFeature {
+                feature: ProtocolFeature::Elicitation,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_roots_round_trip() {
+        let requester = ClientRequester::new();
+        let outgoing = Arc::new(OutgoingQueue::new());
+        requester.attach(outgoing.clone());
+        requester.set_client_capabilities(ClientCapabilities::default());
+        assert!(!requester.supports_roots());
+        assert!(requester.list_roots().await.is_err());
+
+        requester.set_client_capabilities(ClientCapabilities {
+            roots: Some(Default::default()),
+            ..Default::default()
+        });
+        let call = tokio::spawn({
+            let requester = requester.clone();
+            async move { requester.list_roots().await }
+        });
+        let (_, message) = outgoing.next().await;
+        let TransportMessage::Request { id, request } = message else {
+            panic!("Expected request, got {:?}", message);
+        };
+        assert_eq!(
+            crate::shared::protocol_helpers::request_method(&request),
+            "roots/list"
+        );
+        requester.resolve(JSONRPCResponse::success(
+            id,
+            json!({"roots": [{"uri": "file:///work", "name": "work"}]}),
+        ));
+        let roots = call.await.unwrap().unwrap().roots;
+        assert_eq!(roots[0].uri, "file:///work");
+        assert_eq!(roots[0].name.as_deref(), Some("work"));
+    }
+}
@@ -0,0 +1,298 @@
+//! Schema-driven coercion of tool arguments.
+//!
+//! Some hosts send numbers and booleans as strings, or leave out fields the
+//! tool's schema gives a default for. [`ArgumentCoercion`], enabled with
+//! [`ServerBuilder::coerce_arguments`](crate::ServerBuilder::coerce_arguments),
+//! rewrites the arguments of every `tools/call` against the tool's input
+//! schema before the arguments are validated and the handler runs:
+//!
+//! - strings become integers, numbers or booleans where the schema expects
+//!   one and the string parses as it (`"42"` to `42`, `"true"` to `true`);
+//! - missing properties with a `default` in the schema are filled in;
+//! - properties the schema does not declare are removed, unless it allows
+//!   them with `additionalProperties`.
+//!
+//! Values that cannot be coerced are left as they are, for validation or the
+//! handler to reject. Each step can be turned off on its own.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::coercion::ArgumentCoercion;
+//! use pmcp::Server;
+//!
+//! # fn main() -> pmcp::Result<()> {
+//! let server = Server::builder()
+//!     .name("lenient")
+//!     .version("1.0.0")
+//!     // Convert types and fill defaults, but keep unknown fields
+//!     .coerce_arguments(ArgumentCoercion::new().strip_unknown(false))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde_json::{Map, Number, Value};
+
+/// Which coercions to apply to tool arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgumentCoercion {
+    coerce_types: bool,
+    fill_defaults: bool,
+    strip_unknown: bool,
+}
+
+impl Default for ArgumentCoercion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArgumentCoercion {
+    /// Apply every coercion.
+    pub fn new() -> Self {
+        Self {
+            coerce_types: true,
+            fill_defaults: true,
+            strip_unknown: true,
+        }
+    }
+
+    /// Whether strings are converted to the integer, number or boolean the
+    /// schema expects.
+    pub fn coerce_types(mut self, enabled: bool) -> Self {
+        self.coerce_types = enabled;
+        self
+    }
+
+    /// Whether missing properties are set to their schema default.
+    pub fn fill_defaults(mut self, enabled: bool) -> Self {
+        self.fill_defaults = enabled;
+        self
+    }
+
+    /// Whether properties the schema does not declare are removed.
+    pub fn strip_unknown(mut self, enabled: bool) -> Self {
+        self.strip_unknown = enabled;
+        self
+    }
+
+    /// Rewrite `arguments` in place against the input `schema`.
+    ///
+    /// Missing arguments are treated as an empty object when the schema
+    /// declares defaults to fill in.
+    pub fn apply(&self, schema: &Value, arguments: &mut Value) {
+        if arguments.is_null() && self.fill_defaults {
+            let mut filled = Value::Object(Map::new());
+            self.coerce(schema, schema, &mut filled);
+            if filled.as_object().is_some_and(|map| !map.is_empty()) {
+                *arguments = filled;
+            }
+            return;
+        }
+        self.coerce(schema, schema, arguments);
+    }
+
+    fn coerce(&self, root: &Value, schema: &Value, value: &mut Value) {
+        let schema = resolve(root, schema);
+
+        if let Some(Value::Array(all)) = schema.get("allOf") {
+            for sub in all {
+                self.coerce(root, sub, value);
+            }
+        }
+        // Nullable wrappers have a single branch worth coercing against
+        for key in ["anyOf", "oneOf"] {
+            if let Some(Value::Array(branches)) = schema.get(key) {
+                let mut non_null = branches.iter().filter(|branch| !is_null_schema(branch));
+                if let (Some(branch), None) = (non_null.next(), non_null.next()) {
+                    if !value.is_null() {
+                        self.coerce(root, branch, value);
+                    }
+                }
+            }
+        }
+
+        let types = schema_types(schema);
+        match value {
+            Value::String(s) if self.coerce_types && !types.contains(&"string") => {
+                if let Some(coerced) = types.iter().find_map(|ty| parse_as(ty, s)) {
+                    *value = coerced;
+                }
+            },
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items").filter(|items| items.is_object()) {
+                    for item in items {
+                        self.coerce(root, item_schema, item);
+                    }
+                }
+            },
+            Value::Object(map) => self.coerce_object(root, schema, map),
+            _ => {},
+        }
+    }
+
+    fn coerce_object(&self, root: &Value, schema: &Value, map: &mut Map<String, Value>) {
+        let Some(Value::Object(properties)) = schema.get("properties") else {
+            return;
+        };
+
+        if self.strip_unknown {
+            let allows_extra = matches!(
+                schema.get("additionalProperties"),
+                Some(Value::Bool(true) | Value::Object(_))
+            );
+            if !allows_extra {
+                map.retain(|name, _| properties.contains_key(name));
+            }
+        }
+
+        for (name, property) in properties {
+            match map.get_mut(name) {
+                Some(value) => self.coerce(root, property, value),
+                None if self.fill_defaults => {
+                    if let Some(default) = resolve(root, property).get("default") {
+                        map.insert(name.clone(), default.clone());
+                    }
+                },
+                None => {},
+            }
+        }
+    }
+}
+
+/// Follow a local `$ref` such as `#/$defs/Address`.
+fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    let mut schema = schema;
+    // Bounded, in case of a reference cycle
+    for _ in 0..16 {
+        let Some(target) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| root.pointer(pointer))
+        else {
+            break;
+        };
+        schema = target;
+    }
+    schema
+}
+
+fn is_null_schema(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+/// The types a schema allows, from a single `type` or a list of them.
+fn schema_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse `s` as a value of JSON schema type `ty`.
+fn parse_as(ty: &str, s: &str) -> Option<Value> {
+    let s = s.trim();
+    match ty {
+        "integer" => s
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| s.parse::<u64>().map(Value::from))
+            .ok(),
+        "number" => s.parse::<i64>().map(Value::from).ok().or_else(|| {
+            s.parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number)
+        }),
+        "boolean" => match s.to_ascii_lowercase().as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "count": {"type": "integer"},
+                "ratio": {"type": "number"},
+                "verbose": {"type": "boolean", "default": false},
+                "limit": {"type": ["integer", "null"], "default": 10},
+                "name": {"type": "string"},
+                "tags": {"type": "array", "items": {"type": "integer"}},
+                "address": {"anyOf": [{"$ref": "#/$defs/Address"}, {"type": "null"}]}
+            },
+            "$defs": {
+                "Address": {
+                    "type": "object",
+                    "properties": {"zip": {"type": "integer"}},
+                    "additionalProperties": true
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_coerces_fills_and_strips() {
+        let mut args = json!({
+            "count": "42",
+            "ratio": "0.5",
+            "name": "007",
+            "tags": ["1", 2, "x"],
+            "address": {"zip": "12345", "street": "Main"},
+            "extra": true
+        });
+        ArgumentCoercion::new().apply(&schema(), &mut args);
+        assert_eq!(
+            args,
+            json!({
+                "count": 42,
+                "ratio": 0.5,
+                "verbose": false,
+                "limit": 10,
+                "name": "007",
+                "tags": [1, 2, "x"],
+                "address": {"zip": 12345, "street": "Main"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_disabled_steps_leave_arguments_alone() {
+        let mut args = json!({"count": "42", "verbose": "TRUE", "extra": 1});
+        ArgumentCoercion::new()
+            .fill_defaults(false)
+            .strip_unknown(false)
+            .apply(&schema(), &mut args);
+        assert_eq!(args, json!({"count": 42, "verbose": true, "extra": 1}));
+
+        let mut args = json!({"count": "forty-two"});
+        ArgumentCoercion::new()
+            .coerce_types(false)
+            .apply(&schema(), &mut args);
+        assert_eq!(args["count"], "forty-two");
+        assert_eq!(args["limit"], 10);
+    }
+
+    #[test]
+    fn test_missing_arguments_get_defaults() {
+        let mut args = Value::Null;
+        ArgumentCoercion::new().apply(&schema(), &mut args);
+        assert_eq!(args, json!({"verbose": false, "limit": 10}));
+
+        let mut args = Value::Null;
+        ArgumentCoercion::new().apply(&json!({"type": "object"}), &mut args);
+        assert_eq!(args, Value::Null);
+    }
+}
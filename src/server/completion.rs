@@ -0,0 +1,220 @@
+//! Completion providers answering `completion/complete` requests.
+//!
+//! Register a [`CompletionHandler`] with
+//! [`ServerBuilder::completion_provider`](crate::ServerBuilder::completion_provider)
+//! to suggest values for prompt arguments (`ref/prompt`) and resource
+//! template variables (`ref/resource`). [`CompletionRouter`] dispatches each
+//! request to a completer registered for that prompt argument or template
+//! variable.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::server::completion::{prefix_matches, CompletionRouter};
+//! use pmcp::Server;
+//!
+//! let completions = CompletionRouter::new()
+//!     .prompt_argument("review", "language", prefix_matches(["python", "rust", "ruby"]))
+//!     .resource_variable("notes://{id}", "id", |partial: &str| {
+//!         (1..=3)
+//!             .map(|n| format!("note-{}", n))
+//!             .filter(|id| id.starts_with(partial))
+//!             .collect()
+//!     });
+//!
+//! let server = Server::builder()
+//!     .name("completing-server")
+//!     .version("1.0.0")
+//!     .completion_provider(completions)
+//!     .build()?;
+//! # Ok::<(), pmcp::Error>(())
+//! ```
+
+use crate::types::{CompleteRequest, CompletionReference, CompletionResult};
+use crate::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use super::cancellation::RequestHandlerExtra;
+
+/// Maximum number of values in a completion result, per the MCP spec.
+pub const MAX_COMPLETION_VALUES: usize = 100;
+
+/// Handler for `completion/complete` requests.
+#[async_trait]
+pub trait CompletionHandler: Send + Sync {
+    /// Suggest values for the argument in `request`.
+    async fn complete(
+        &self,
+        request: CompleteRequest,
+        extra: RequestHandlerExtra,
+    ) -> Result<CompletionResult>;
+}
+
+/// Build a completion result from all matching values.
+///
+/// Keeps the first [`MAX_COMPLETION_VALUES`] values and reports the total.
+pub fn completion_result(values: Vec<String>) -> CompletionResult {
+    let total = values.len();
+    CompletionResult {
+        values: values.into_iter().take(MAX_COMPLETION_VALUES).collect(),
+        total: Some(total),
+        has_more: total > MAX_COMPLETION_VALUES,
+    }
+}
+
+/// A completer suggesting the given values that start with the typed prefix.
+pub fn prefix_matches<I, S>(values: I) -> impl Fn(&str) -> Vec<String> + Send + Sync + 'static
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let values: Vec<String> = values.into_iter().map(Into::into).collect();
+    move |partial: &str| {
+        values
+            .iter()
+            .filter(|value| value.starts_with(partial))
+            .cloned()
+            .collect()
+    }
+}
+
+type Completer = Arc<dyn Fn(&str) -> Vec<String> + Send + Sync>;
+
+/// Routes completion requests to completers by prompt argument or resource
+/// template variable.
+///
+/// A completer receives the value typed so far and returns the matching
+/// suggestions. Requests without a registered completer get no suggestions.
+#[derive(Default)]
+pub struct CompletionRouter {
+    prompts: HashMap<(String, String), Completer>,
+    resources: HashMap<(String, String), Completer>,
+}
+
+impl fmt::Debug for CompletionRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompletionRouter")
+            .field("prompts", &self.prompts.keys().collect::<Vec<_>>())
+            .field("resources", &self.resources.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl CompletionRouter {
+    /// Create a router with no completers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Complete `argument` of the prompt named `prompt`.
+    pub fn prompt_argument(
+        mut self,
+        prompt: impl Into<String>,
+        argument: impl Into<String>,
+        completer: impl Fn(&str) -> Vec<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.prompts
+            .insert((prompt.into(), argument.into()), Arc::new(completer));
+        self
+    }
+
+    /// Complete `variable` of the resource template `uri_template`.
+    pub fn resource_variable(
+        mut self,
+        uri_template: impl Into<String>,
+        variable: impl Into<String>,
+        completer: impl Fn(&str) -> Vec<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.resources
+            .insert((uri_template.into(), variable.into()), Arc::new(completer));
+        self
+    }
+}
+
+#[async_trait]
+impl CompletionHandler for CompletionRouter {
+    async fn complete(
+        &self,
+        request: CompleteRequest,
+        _extra: RequestHandlerExtra,
+    ) -> Result<CompletionResult> {
+        let (completers, reference) = match request.r#ref {
+            CompletionReference::Prompt { name } => (&self.prompts, name),
+            CompletionReference::Resource { uri } => (&self.resources, uri),
+        };
+        let values = completers
+            .get(&(reference, request.argument.name))
+            .map(|completer| completer(&request.argument.value))
+            .unwrap_or_default();
+        Ok(completion_result(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CompletionArgument;
+    use tokio_util::sync::CancellationToken;
+
+    fn request(r#ref: CompletionReference, name: &str, value: &str) -> CompleteRequest {
+        CompleteRequest {
+            r#ref,
+            argument: CompletionArgument {
+                name: name.to_string(),
+                value: value.to_string(),
+            },
+        }
+    }
+
+    fn extra() -> RequestHandlerExtra {
+        RequestHandlerExtra::new("test".to_string(), CancellationToken::new())
+    }
+
+    #[tokio::test]
+    async fn test_router_dispatches_by_reference() {
+        let router = CompletionRouter::new()
+            .prompt_argument(
+                "review",
+                "language",
+                prefix_matches(["python", "rust", "ruby"]),
+            )
+            .resource_variable("notes://{id}", "id", prefix_matches(["a1", "b2"]));
+
+        let prompt = CompletionReference::Prompt {
+            name: "review".to_string(),
+        };
+        let result = router
+            .complete(request(prompt.clone(), "language", "r"), extra())
+            .await
+            .unwrap();
+        assert_eq!(result.values, vec!["rust", "ruby"]);
+        assert_eq!(result.total, Some(2));
+
+        let result = router
+            .complete(request(prompt, "other", ""), extra())
+            .await
+            .unwrap();
+        assert!(result.values.is_empty());
+
+        let resource = CompletionReference::Resource {
+            uri: "notes://{id}".to_string(),
+        };
+        let result = router
+            .complete(request(resource, "id", "b"), extra())
+            .await
+            .unwrap();
+        assert_eq!(result.values, vec!["b2"]);
+    }
+
+    #[test]
+    fn test_completion_result_caps_values() {
+        let values: Vec<String> = (0..150).map(|i| i.to_string()).collect();
+        let result = completion_result(values);
+        assert_eq!(result.values.len(), MAX_COMPLETION_VALUES);
+        assert_eq!(result.total, Some(150));
+        assert!(result.has_more);
+    }
+}
@@ -0,0 +1,316 @@
+//! Composing servers from mounted sub-servers.
+//!
+//! [`ServerBuilder::mount`](crate::ServerBuilder::mount) adds the tools,
+//! prompts and resources of a built [`Server`](crate::Server) to the server
+//! being built, under a namespace. Tools and prompts are exposed as
+//! `namespace.name`, e.g. `fs.read_file`, and resource URIs as
+//! `namespace+uri`, e.g. `fs+file:///notes.txt`, which is still a valid URI.
+//! Requests for a namespaced name are routed to the mounted handler with its
+//! original name.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::simple_tool::SyncTool;
+//! use pmcp::Server;
+//! use serde_json::json;
+//!
+//! # fn example() -> pmcp::Result<()> {
+//! let fs = Server::builder()
+//!     .name("fs")
+//!     .version("1.0.0")
+//!     .tool("read_file", SyncTool::new("read_file", |_| Ok(json!("..."))))
+//!     .build()?;
+//! let git = Server::builder()
+//!     .name("git")
+//!     .version("1.0.0")
+//!     .tool("commit", SyncTool::new("commit", |_| Ok(json!("done"))))
+//!     .build()?;
+//!
+//! // Serves the tools `fs.read_file` and `git.commit`
+//! let server = Server::builder()
+//!     .name("workspace")
+//!     .version("1.0.0")
+//!     .mount("fs", fs)
+//!     .mount("git", git)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::cancellation::RequestHandlerExtra;
+use super::resource_chunks::ResourceStream;
+use super::{PromptHandler, ResourceHandler, ToolHandler};
+use crate::error::{Error, Result};
+use crate::types::{
+    GetPromptResult, ListResourcesResult, PromptInfo, ReadResourceResult, ToolInfo,
+};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The name a tool or prompt is exposed under.
+pub(crate) fn namespaced(namespace: &str, name: &str) -> String {
+    format!("{}.{}", namespace, name)
+}
+
+/// The URI a resource is exposed under.
+fn namespaced_uri(namespace: &str, uri: &str) -> String {
+    format!("{}+{}", namespace, uri)
+}
+
+/// A mounted tool, advertised under its namespaced name.
+pub(crate) struct MountedTool {
+    name: String,
+    handler: Arc<dyn ToolHandler>,
+}
+
+impl MountedTool {
+    pub(crate) fn new(name: String, handler: Arc<dyn ToolHandler>) -> Self {
+        Self { name, handler }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for MountedTool {
+    async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> Result<Value> {
+        self.handler.handle(args, extra).await
+    }
+
+    fn metadata(&self) -> Option<ToolInfo> {
+        self.handler.metadata().map(|info| ToolInfo {
+            name: self.name.clone(),
+            ..info
+        })
+    }
+}
+
+/// A mounted prompt, advertised under its namespaced name.
+pub(crate) struct MountedPrompt {
+    name: String,
+    handler: Arc<dyn PromptHandler>,
+}
+
+impl MountedPrompt {
+    pub(crate) fn new(name: String, handler: Arc<dyn PromptHandler>) -> Self {
+        Self { name, handler }
+    }
+}
+
+#[async_trait]
+impl PromptHandler for MountedPrompt {
+    async fn handle(
+        &self,
+        args: HashMap<String, String>,
+        extra: RequestHandlerExtra,
+    ) -> Result<GetPromptResult> {
+        self.handler.handle(args, extra).await
+    }
+
+    fn metadata(&self) -> Option<PromptInfo> {
+        self.handler.metadata().map(|info| PromptInfo {
+            name: self.name.clone(),
+            ..info
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.handler.validate()
+    }
+}
+
+/// Routes resource requests to the mounted resource handlers by namespace.
+///
+/// URIs without a mounted namespace go to the composed server's own
+/// handler, if it has one. Listing collects every page of every handler.
+pub(crate) struct MountedResources {
+    mounts: Vec<(String, Arc<dyn ResourceHandler>)>,
+    fallback: Option<Arc<dyn ResourceHandler>>,
+}
+
+impl MountedResources {
+    pub(crate) fn new(
+        mounts: Vec<(String, Arc<dyn ResourceHandler>)>,
+        fallback: Option<Arc<dyn ResourceHandler>>,
+    ) -> Self {
+        Self { mounts, fallback }
+    }
+
+    /// The handler serving `uri` and the URI it knows the resource by.
+    fn route<'a>(&self, uri: &'a str) -> Option<(&Arc<dyn ResourceHandler>, &'a str)> {
+        self.mounts
+            .iter()
+            .find_map(|(namespace, handler)| {
+                uri.strip_prefix(namespace.as_str())
+                    .and_then(|rest| rest.strip_prefix('+'))
+                    .map(|uri| (handler, uri))
+            })
+            .or_else(|| self.fallback.as_ref().map(|handler| (handler, uri)))
+    }
+}
+
+/// Every page of `handler`'s resources.
+async fn list_all(
+    handler: &Arc<dyn ResourceHandler>,
+    extra: &RequestHandlerExtra,
+) -> Result<ListResourcesResult> {
+    let mut page = handler.list(None, extra.clone()).await?;
+    while let Some(cursor) = page.next_cursor.take() {
+        let next = handler.list(Some(cursor), extra.clone()).await?;
+        page.resources.extend(next.resources);
+        page.next_cursor = next.next_cursor;
+    }
+    Ok(page)
+}
+
+#[async_trait]
+impl ResourceHandler for MountedResources {
+    async fn read(&self, uri: &str, extra: RequestHandlerExtra) -> Result<ReadResourceResult> {
+        match self.route(uri) {
+            Some((handler, uri)) => handler.read(uri, extra).await,
+            None => Err(Error::not_found(format!("Resource '{}' not found", uri))),
+        }
+    }
+
+    async fn open_stream(
+        &self,
+        uri: &str,
+        extra: RequestHandlerExtra,
+    ) -> Result<Option<ResourceStream>> {
+        match self.route(uri) {
+            Some((handler, uri)) => handler.open_stream(uri, extra).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn list(
+        &self,
+        _cursor: Option<String>,
+        extra: RequestHandlerExtra,
+    ) -> Result<ListResourcesResult> {
+        let mut resources = match &self.fallback {
+            Some(fallback) => list_all(fallback, &extra).await?.resources,
+            None => Vec::new(),
+        };
+        for (namespace, handler) in &self.mounts {
+            resources.extend(list_all(handler, &extra).await?.resources.into_iter().map(
+                |mut resource| {
+                    resource.uri = namespaced_uri(namespace, &resource.uri);
+                    resource
+                },
+            ));
+        }
+        Ok(ListResourcesResult {
+            resources,
+            next_cursor: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::simple_prompt::SyncPrompt;
+    use crate::server::simple_resources::{ResourceCollection, StaticResource};
+    use crate::server::simple_tool::SyncTool;
+    use crate::shared::InMemoryTransport;
+    use crate::{Client, ClientCapabilities, Server};
+    use serde_json::json;
+
+    fn sub_server(name: &str) -> Server {
+        let label = name.to_string();
+        Server::builder()
+            .name(name)
+            .version("1.0.0")
+            .tool(
+                "whoami",
+                SyncTool::new("whoami", move |_| Ok(json!(label.clone())))
+                    .with_description("Name of the sub-server"),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mounted_servers_are_namespaced_and_routed() {
+        let docs = Server::builder()
+            .name("docs")
+            .version("1.0.0")
+            .prompt(
+                "summarize",
+                SyncPrompt::new("summarize", |_| {
+                    Ok(GetPromptResult {
+                        description: None,
+                        messages: vec![],
+                    })
+                }),
+            )
+            .resources(
+                ResourceCollection::new()
+                    .add_resource(StaticResource::new_text("file:///readme", "hello")),
+            )
+            .build()
+            .unwrap();
+        let server = Server::builder()
+            .name("composed")
+            .version("1.0.0")
+            .mount("fs", sub_server("fs"))
+            .mount("git", sub_server("git"))
+            .mount("docs", docs)
+            .build()
+            .unwrap();
+
+        let (client_transport, server_transport) = InMemoryTransport::pair();
+        tokio::spawn(server.run(server_transport));
+        let mut client = Client::new(client_transport);
+        let init = client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+        let capabilities = init.capabilities;
+        assert!(capabilities.tools.is_some());
+        assert!(capabilities.prompts.is_some());
+        assert!(capabilities.resources.is_some());
+
+        let mut tools: Vec<String> = client
+            .list_tools(None)
+            .await
+            .unwrap()
+            .tools
+            .into_iter()
+            .map(|tool| tool.name)
+            .collect();
+        tools.sort();
+        assert_eq!(tools, ["fs.whoami", "git.whoami"]);
+
+        let result = client
+            .call_tool("git.whoami".to_string(), json!({}))
+            .await
+            .unwrap();
+        assert!(serde_json::to_string(&result).unwrap().contains("git"));
+        assert!(client
+            .call_tool("whoami".to_string(), json!({}))
+            .await
+            .is_err());
+
+        let prompts = client.list_prompts(None).await.unwrap().prompts;
+        assert_eq!(prompts[0].name, "docs.summarize");
+        client
+            .get_prompt("docs.summarize".to_string(), HashMap::new())
+            .await
+            .unwrap();
+
+        let resources = client.list_resources(None).await.unwrap().resources;
+        assert_eq!(resources[0].uri, "docs+file:///readme");
+        let read = client
+            .read_resource("docs+file:///readme".to_string())
+            .await
+            .unwrap();
+        assert!(serde_json::to_string(&read).unwrap().contains("hello"));
+        assert!(client
+            .read_resource("file:///readme".to_string())
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Concurrent request handling.
+//!
+//! By default a server runs one request at a time per connection: while a
+//! handler runs, the next message is not read. A [`ConcurrencyConfig`]
+//! installed with
+//! [`ServerBuilder::concurrency`](crate::ServerBuilder::concurrency) lets up
+//! to [`max_in_flight`](ConcurrencyConfig::max_in_flight) requests run at
+//! once, so a slow tool no longer holds up fast ones. Once that many are
+//! running, the server stops reading from the transport until one finishes,
+//! pushing back on the client.
+//!
+//! Individual tools can be capped further with
+//! [`tool_limit`](ConcurrencyConfig::tool_limit); calls beyond the cap wait
+//! for a running one to finish. Replies and notifications still leave in the
+//! order they were queued.
+//!
+//! While requests run, receiving is interrupted to send what they queue, so
+//! the transport's `receive` must be cancel-safe, as those of
+//! [`StdioTransport`](crate::shared::StdioTransport) and
+//! `TcpTransport` are.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::concurrency::ConcurrencyConfig;
+//! use pmcp::Server;
+//!
+//! let server = Server::builder()
+//!     .name("search")
+//!     .version("1.0.0")
+//!     .concurrency(
+//!         ConcurrencyConfig::new(16)
+//!             // The index only copes with two rebuilds at a time
+//!             .tool_limit("rebuild_index", 2),
+//!     )
+//!     .build()?;
+//! # Ok::<(), pmcp::Error>(())
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// How many requests a server runs at once per connection.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyConfig {
+    max_in_flight: usize,
+    tool_limits: HashMap<String, usize>,
+}
+
+impl Default for ConcurrencyConfig {
+    /// One request at a time.
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl ConcurrencyConfig {
+    /// Run up to `max_in_flight` requests at once; zero is treated as one.
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            max_in_flight: max_in_flight.max(1),
+            tool_limits: HashMap::new(),
+        }
+    }
+
+    /// Run at most `limit` calls of the tool `name` at once; zero is
+    /// treated as one.
+    pub fn tool_limit(mut self, name: impl Into<String>, limit: usize) -> Self {
+        self.tool_limits.insert(name.into(), limit.max(1));
+        self
+    }
+
+    /// Maximum number of requests running at once.
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+
+    /// Maximum number of concurrent calls of the tool `name`, if capped.
+    pub fn limit_for_tool(&self, name: &str) -> Option<usize> {
+        self.tool_limits.get(name).copied()
+    }
+
+    /// Whether more than one request may run at once.
+    pub(crate) fn is_concurrent(&self) -> bool {
+        self.max_in_flight > 1
+    }
+}
+
+/// Permits for tools with a concurrency cap.
+#[derive(Debug, Default)]
+pub(crate) struct ToolPermits {
+    semaphores: HashMap<String, Arc<Semaphore>>,
+}
+
+impl ToolPermits {
+    pub(crate) fn new(config: &ConcurrencyConfig) -> Self {
+        Self {
+            semaphores: config
+                .tool_limits
+                .iter()
+                .map(|(name, limit)| (name.clone(), Arc::new(Semaphore::new(*limit))))
+                .collect(),
+        }
+    }
+
+    /// Wait for a turn to call the tool `name`, if it is capped.
+    pub(crate) async fn acquire(&self, name: &str) -> Option<OwnedSemaphorePermit> {
+        let semaphore = self.semaphores.get(name)?;
+        // The semaphores are never closed.
+        semaphore.clone().acquire_owned().await.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tool_permits_cap_only_limited_tools() {
+        let config = ConcurrencyConfig::new(0).tool_limit("slow", 1);
+        assert_eq!(config.max_in_flight(), 1);
+        assert!(!config.is_concurrent());
+        let permits = ToolPermits::new(&config);
+
+        assert!(permits.acquire("fast").await.is_none());
+        let held = permits.acquire("slow").await.unwrap();
+        let waiting = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            permits.acquire("slow"),
+        );
+        assert!(waiting.await.is_err());
+        drop(held);
+        assert!(permits.acquire("slow").await.is_some());
+    }
+}
@@ -26,6 +26,8 @@ use super::auth::{AuthProvider, ToolAuthorizer};
 #[cfg(not(target_arch = "wasm32"))]
 use super::cancellation::{CancellationManager, RequestHandlerExtra};
 #[cfg(not(target_arch = "wasm32"))]
+use super::resource_templates::ResourceTemplateRegistry;
+#[cfg(not(target_arch = "wasm32"))]
 use super::roots::RootsManager;
 #[cfg(not(target_arch = "wasm32"))]
 use super::subscriptions::SubscriptionManager;
@@ -101,6 +103,9 @@ pub struct ServerCore {
     /// Resource handler (optional)
     resources: Option<Arc<dyn ResourceHandler>>,
 
+    /// Registered resource templates
+    resource_templates: Arc<ResourceTemplateRegistry>,
+
     /// Sampling handler (optional)
     sampling: Option<Arc<dyn SamplingHandler>>,
 
@@ -124,6 +129,9 @@ pub struct ServerCore {
 
     /// Tool authorizer for fine-grained access control (optional)
     tool_authorizer: Option<Arc<dyn ToolAuthorizer>>,
+
+    /// Whether tool results are checked against their output schema
+    validate_tool_output: bool,
 }
 
 impl ServerCore {
@@ -145,6 +153,7 @@ impl ServerCore {
             tools,
             prompts,
             resources,
+            resource_templates: Arc::new(ResourceTemplateRegistry::new()),
             sampling,
             client_capabilities: Arc::new(RwLock::new(None)),
             initialized: Arc::new(RwLock::new(false)),
@@ -153,9 +162,22 @@ impl ServerCore {
             subscription_manager: Arc::new(RwLock::new(SubscriptionManager::new())),
             auth_provider,
             tool_authorizer,
+            validate_tool_output: false,
         }
     }
 
+    /// Set the resource templates advertised by this server.
+    pub fn with_resource_templates(mut self, templates: ResourceTemplateRegistry) -> Self {
+        self.resource_templates = Arc::new(templates);
+        self
+    }
+
+    /// Check tool results against the output schema each tool advertises.
+    pub fn with_output_validation(mut self, enabled: bool) -> Self {
+        self.validate_tool_output = enabled;
+        self
+    }
+
     /// Check if the server is initialized.
     pub async fn is_initialized(&self) -> bool {
         *self.initialized.read().await
@@ -204,6 +226,8 @@ impl ServerCore {
                         name: name.clone(),
                         description: None,
                         input_schema: serde_json::json!({}),
+                        output_schema: None,
+                        annotations: None,
                     }
                 }
             })
@@ -236,16 +260,25 @@ impl ServerCore {
             session_id: None,
             auth_info: None,
             auth_context: None,
+            progress: None,
+            client_requester: None,
+            logger: None,
         };
 
         // Execute the tool
         let result = handler.handle(req.arguments.clone(), extra).await?;
+        if self.validate_tool_output {
+            if let Some(info) = handler.metadata() {
+                crate::server::tool_validation::check_tool_output(&req.name, &info, &result)?;
+            }
+        }
 
         Ok(CallToolResult {
             content: vec![Content::Text {
                 text: serde_json::to_string_pretty(&result)?,
             }],
             is_error: false,
+            structured_content: result.is_object().then_some(result),
         })
     }
 
@@ -294,6 +327,9 @@ impl ServerCore {
             session_id: None,
             auth_info: None,
             auth_context: None,
+            progress: None,
+            client_requester: None,
+            logger: None,
         };
 
         handler.handle(req.arguments.clone(), extra).await
@@ -316,6 +352,9 @@ impl ServerCore {
                     session_id: None,
                     auth_info: None,
                     auth_context: None,
+                    progress: None,
+                    client_requester: None,
+                    logger: None,
                 };
                 handler.list(req.cursor.clone(), extra).await
             },
@@ -342,9 +381,12 @@ impl ServerCore {
             session_id: None,
             auth_info: None,
             auth_context: None,
+            progress: None,
+            client_requester: None,
+            logger: None,
         };
 
-        handler.read(&req.uri, extra).await
+        super::resource_chunks::read_resource(handler.as_ref(), req, extra).await
     }
 
     /// Handle list resource templates request.
@@ -353,7 +395,7 @@ impl ServerCore {
         _req: &ListResourceTemplatesRequest,
     ) -> Result<ListResourceTemplatesResult> {
         Ok(ListResourceTemplatesResult {
-            resource_templates: vec![],
+            resource_templates: self.resource_templates.templates(),
             next_cursor: None,
         })
     }
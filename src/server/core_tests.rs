@@ -126,6 +126,7 @@ mod tests {
                     contents: vec![Content::Text {
                         text: "Resource content".to_string(),
                     }],
+                    meta: None,
                 })
             } else {
                 Err(Error::internal(format!("Resource not found: {}", uri)))
@@ -357,6 +358,7 @@ mod tests {
                 "a": 5,
                 "b": 3
             }),
+            meta: None,
         })));
 
         let response = server.handle_request(RequestId::from(2i64), request).await;
@@ -387,6 +389,7 @@ mod tests {
         let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolParams {
             name: "nonexistent".to_string(),
             arguments: json!({}),
+            meta: None,
         })));
 
         let response = server.handle_request(RequestId::from(2i64), request).await;
@@ -419,6 +422,7 @@ mod tests {
         let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolParams {
             name: "failing_tool".to_string(),
             arguments: json!({}),
+            meta: None,
         })));
 
         let response = server.handle_request(RequestId::from(2i64), request).await;
@@ -524,6 +528,7 @@ mod tests {
         let read_request =
             Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceParams {
                 uri: "test://resource1".to_string(),
+                meta: None,
             })));
 
         let read_response = server
@@ -560,6 +565,7 @@ mod tests {
         // Read non-existent resource
         let request = Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceParams {
             uri: "test://nonexistent".to_string(),
+            meta: None,
         })));
 
         let response = server.handle_request(RequestId::from(2i64), request).await;
@@ -644,6 +650,7 @@ mod tests {
                 let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolParams {
                     name: "concurrent_tool".to_string(),
                     arguments: json!({ "id": i }),
+                    meta: None,
                 })));
                 server_clone
                     .handle_request(RequestId::from(i as i64), request)
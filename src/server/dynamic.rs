@@ -526,6 +526,8 @@ mod tests {
             name: "dynamic_test".to_string(),
             description: Some("Dynamic test tool".to_string()),
             input_schema: json!({}),
+            output_schema: None,
+            annotations: None,
         };
 
         manager
@@ -562,6 +564,8 @@ mod tests {
                     name: "tool1".to_string(),
                     description: Some("Tool 1".to_string()),
                     input_schema: json!({}),
+                    output_schema: None,
+                    annotations: None,
                 },
             )
             .prompt(
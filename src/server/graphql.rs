@@ -0,0 +1,803 @@
+//! GraphQL endpoint adapter exposing queries and mutations as tools.
+//!
+//! Given an introspection result (or a live endpoint to introspect), the
+//! generator creates one [`ToolHandler`] per whitelisted root field. Field
+//! arguments become tool arguments with JSON schemas derived from their
+//! GraphQL types, the operation document is built automatically, and the
+//! tool returns the field's value from the `data` object.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::graphql::GraphQlToolGenerator;
+//! use pmcp::server::api_auth::ApiAuth;
+//! use pmcp::Server;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let tools = GraphQlToolGenerator::new("https://api.example.com/graphql")
+//!     .auth(ApiAuth::Bearer("secret-token".to_string()))
+//!     .query("user")
+//!     .mutation_with_selection("createUser", "{ id name }")
+//!     .introspect()
+//!     .await?
+//!     .generate()?;
+//!
+//! let mut builder = Server::builder().name("graph").version("1.0.0");
+//! for tool in tools {
+//!     builder = builder.tool(tool.name().to_string(), tool);
+//! }
+//! let server = builder.build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::types::ToolInfo;
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::api_auth::ApiAuth;
+use super::cancellation::RequestHandlerExtra;
+use super::ToolHandler;
+
+/// Introspection query used to discover the schema.
+pub const INTROSPECTION_QUERY: &str = r"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    types {
+      kind name description
+      fields(includeDeprecated: true) {
+        name description
+        args { name description defaultValue type { ...TypeRef } }
+        type { ...TypeRef }
+      }
+      inputFields { name description defaultValue type { ...TypeRef } }
+      enumValues(includeDeprecated: true) { name }
+    }
+  }
+}
+fragment TypeRef on __Type {
+  kind name
+  ofType { kind name ofType { kind name ofType { kind name ofType { kind name } } } }
+}";
+
+/// Maximum nesting followed when expanding input object schemas.
+const MAX_INPUT_DEPTH: usize = 16;
+
+/// Kind of root operation a tool executes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    /// A field on the query root type.
+    Query,
+    /// A field on the mutation root type.
+    Mutation,
+}
+
+impl OperationKind {
+    fn keyword(self) -> &'static str {
+        match self {
+            Self::Query => "query",
+            Self::Mutation => "mutation",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FieldSelection {
+    kind: OperationKind,
+    field: String,
+    selection: Option<String>,
+}
+
+/// Generator that builds tools from a GraphQL schema.
+#[derive(Debug, Clone)]
+pub struct GraphQlToolGenerator {
+    endpoint: String,
+    schema: Option<Value>,
+    auth: ApiAuth,
+    default_headers: Vec<(String, String)>,
+    timeout: Duration,
+    selection_depth: usize,
+    fields: Vec<FieldSelection>,
+}
+
+impl GraphQlToolGenerator {
+    /// Create a generator for the given endpoint URL.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            schema: None,
+            auth: ApiAuth::None,
+            default_headers: Vec::new(),
+            timeout: Duration::from_secs(30),
+            selection_depth: 2,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Use a pre-fetched introspection result instead of querying the endpoint.
+    ///
+    /// Accepts either the full response (`{"data": {"__schema": ...}}`) or
+    /// just the `data` object.
+    pub fn schema(mut self, introspection: Value) -> Self {
+        self.schema = Some(introspection);
+        self
+    }
+
+    /// Set the authentication used for requests.
+    pub fn auth(mut self, auth: ApiAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Add a header sent with every request.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the request timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set how deep automatically generated selection sets descend into objects.
+    pub fn selection_depth(mut self, depth: usize) -> Self {
+        self.selection_depth = depth.max(1);
+        self
+    }
+
+    /// Expose a query root field with an automatically generated selection set.
+    pub fn query(self, field: impl Into<String>) -> Self {
+        self.select(OperationKind::Query, field.into(), None)
+    }
+
+    /// Expose a query root field with an explicit selection set (e.g. `"{ id name }"`).
+    pub fn query_with_selection(
+        self,
+        field: impl Into<String>,
+        selection: impl Into<String>,
+    ) -> Self {
+        self.select(OperationKind::Query, field.into(), Some(selection.into()))
+    }
+
+    /// Expose a mutation root field with an automatically generated selection set.
+    pub fn mutation(self, field: impl Into<String>) -> Self {
+        self.select(OperationKind::Mutation, field.into(), None)
+    }
+
+    /// Expose a mutation root field with an explicit selection set.
+    pub fn mutation_with_selection(
+        self,
+        field: impl Into<String>,
+        selection: impl Into<String>,
+    ) -> Self {
+        self.select(
+            OperationKind::Mutation,
+            field.into(),
+            Some(selection.into()),
+        )
+    }
+
+    fn select(mut self, kind: OperationKind, field: String, selection: Option<String>) -> Self {
+        self.fields.push(FieldSelection {
+            kind,
+            field,
+            selection,
+        });
+        self
+    }
+
+    fn client(&self) -> Result<reqwest::Client> {
+        reqwest::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .map_err(|e| Error::internal(format!("Failed to create HTTP client: {}", e)))
+    }
+
+    /// Fetch the schema from the endpoint using the introspection query.
+    pub async fn introspect(mut self) -> Result<Self> {
+        let shared = SharedConfig {
+            client: self.client()?,
+            endpoint: self.endpoint.clone(),
+            auth: self.auth.clone(),
+            default_headers: self.default_headers.clone(),
+        };
+        let data = shared.execute(INTROSPECTION_QUERY, &Map::new()).await?;
+        self.schema = Some(data);
+        Ok(self)
+    }
+
+    /// Generate tools for the whitelisted fields.
+    pub fn generate(&self) -> Result<Vec<GraphQlTool>> {
+        let schema = self.schema.as_ref().ok_or_else(|| {
+            Error::invalid_state("No GraphQL schema: call schema() or introspect() first")
+        })?;
+        let index = SchemaIndex::new(schema)?;
+        let shared = Arc::new(SharedConfig {
+            client: self.client()?,
+            endpoint: self.endpoint.clone(),
+            auth: self.auth.clone(),
+            default_headers: self.default_headers.clone(),
+        });
+
+        let mut names = HashSet::new();
+        self.fields
+            .iter()
+            .map(|selection| {
+                if !names.insert(selection.field.as_str()) {
+                    return Err(Error::validation(format!(
+                        "Duplicate GraphQL tool name: {}",
+                        selection.field
+                    )));
+                }
+                self.build_tool(&index, selection, &shared)
+            })
+            .collect()
+    }
+
+    fn build_tool(
+        &self,
+        index: &SchemaIndex<'_>,
+        selection: &FieldSelection,
+        shared: &Arc<SharedConfig>,
+    ) -> Result<GraphQlTool> {
+        let root = match selection.kind {
+            OperationKind::Query => index.query_type,
+            OperationKind::Mutation => index.mutation_type.ok_or_else(|| {
+                Error::validation("GraphQL schema does not define a mutation type")
+            })?,
+        };
+        let field = index.field(root, &selection.field).ok_or_else(|| {
+            Error::validation(format!(
+                "Field '{}' not found on {} type '{}'",
+                selection.field,
+                selection.kind.keyword(),
+                root
+            ))
+        })?;
+
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        let mut variables = Vec::new();
+        let mut arguments = Vec::new();
+        for arg in field["args"].as_array().into_iter().flatten() {
+            let name = arg["name"].as_str().unwrap_or_default();
+            let mut schema = index.input_schema(&arg["type"], 0)?;
+            if let (Some(obj), Some(desc)) = (schema.as_object_mut(), arg["description"].as_str()) {
+                obj.insert("description".to_string(), json!(desc));
+            }
+            properties.insert(name.to_string(), schema);
+            if is_non_null(&arg["type"]) && arg["defaultValue"].is_null() {
+                required.push(name.to_string());
+            }
+            variables.push(format!("${}: {}", name, type_signature(&arg["type"])?));
+            arguments.push(format!("{0}: ${0}", name));
+        }
+
+        let (selection_set, output_schema) = match &selection.selection {
+            Some(explicit) => (format!(" {}", explicit.trim()), json!({})),
+            None => {
+                let (set, schema) = index.selection(&field["type"], self.selection_depth)?;
+                (set.map(|s| format!(" {}", s)).unwrap_or_default(), schema)
+            },
+        };
+
+        let document = format!(
+            "{kind} {name}{vars} {{ {field}{args}{selection} }}",
+            kind = selection.kind.keyword(),
+            name = operation_name(&selection.field),
+            vars = if variables.is_empty() {
+                String::new()
+            } else {
+                format!("({})", variables.join(", "))
+            },
+            field = selection.field,
+            args = if arguments.is_empty() {
+                String::new()
+            } else {
+                format!("({})", arguments.join(", "))
+            },
+            selection = selection_set,
+        );
+
+        Ok(GraphQlTool {
+            name: selection.field.clone(),
+            kind: selection.kind,
+            description: field["description"].as_str().map(str::to_string),
+            input_schema: json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            }),
+            output_schema,
+            document,
+            shared: shared.clone(),
+        })
+    }
+}
+
+/// Build a GraphQL operation name from a field name.
+fn operation_name(field: &str) -> String {
+    let mut chars = field.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+fn is_non_null(type_ref: &Value) -> bool {
+    type_ref["kind"] == "NON_NULL"
+}
+
+/// Render a type reference as GraphQL type syntax (e.g. `[ID!]!`).
+fn type_signature(type_ref: &Value) -> Result<String> {
+    match type_ref["kind"].as_str() {
+        Some("NON_NULL") => Ok(format!("{}!", type_signature(&type_ref["ofType"])?)),
+        Some("LIST") => Ok(format!("[{}]", type_signature(&type_ref["ofType"])?)),
+        _ => type_ref["name"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| Error::validation("Malformed GraphQL type reference")),
+    }
+}
+
+/// Strip `NON_NULL` and `LIST` wrappers to get the named type.
+fn named_type(type_ref: &Value) -> &str {
+    let mut current = type_ref;
+    while matches!(current["kind"].as_str(), Some("NON_NULL" | "LIST")) {
+        current = &current["ofType"];
+    }
+    current["name"].as_str().unwrap_or_default()
+}
+
+/// Lookup table over the `types` array of an introspection result.
+struct SchemaIndex<'a> {
+    types: HashMap<&'a str, &'a Value>,
+    query_type: &'a str,
+    mutation_type: Option<&'a str>,
+}
+
+impl<'a> SchemaIndex<'a> {
+    fn new(introspection: &'a Value) -> Result<Self> {
+        let schema = introspection
+            .get("data")
+            .unwrap_or(introspection)
+            .get("__schema")
+            .ok_or_else(|| Error::validation("Introspection result has no '__schema'"))?;
+        let types = schema["types"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|t| t["name"].as_str().map(|name| (name, t)))
+            .collect();
+
+        Ok(Self {
+            types,
+            query_type: schema["queryType"]["name"]
+                .as_str()
+                .ok_or_else(|| Error::validation("GraphQL schema has no query type"))?,
+            mutation_type: schema["mutationType"]["name"].as_str(),
+        })
+    }
+
+    fn field(&self, type_name: &str, field: &str) -> Option<&'a Value> {
+        self.types.get(type_name)?["fields"]
+            .as_array()?
+            .iter()
+            .find(|f| f["name"] == field)
+    }
+
+    /// JSON schema for an input type reference.
+    fn input_schema(&self, type_ref: &Value, depth: usize) -> Result<Value> {
+        if depth > MAX_INPUT_DEPTH {
+            return Ok(json!({"type": "object"}));
+        }
+        match type_ref["kind"].as_str() {
+            Some("NON_NULL") => self.input_schema(&type_ref["ofType"], depth),
+            Some("LIST") => Ok(json!({
+                "type": "array",
+                "items": self.input_schema(&type_ref["ofType"], depth + 1)?,
+            })),
+            _ => {
+                let name = type_ref["name"].as_str().unwrap_or_default();
+                let Some(definition) = self.types.get(name) else {
+                    return Ok(scalar_schema(name));
+                };
+                match definition["kind"].as_str() {
+                    Some("ENUM") => Ok(json!({
+                        "type": "string",
+                        "enum": definition["enumValues"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .map(|v| v["name"].clone())
+                            .collect::<Vec<_>>(),
+                    })),
+                    Some("INPUT_OBJECT") => {
+                        let mut properties = Map::new();
+                        let mut required = Vec::new();
+                        for field in definition["inputFields"].as_array().into_iter().flatten() {
+                            let field_name = field["name"].as_str().unwrap_or_default();
+                            properties.insert(
+                                field_name.to_string(),
+                                self.input_schema(&field["type"], depth + 1)?,
+                            );
+                            if is_non_null(&field["type"]) && field["defaultValue"].is_null() {
+                                required.push(field_name.to_string());
+                            }
+                        }
+                        Ok(json!({
+                            "type": "object",
+                            "properties": properties,
+                            "required": required,
+                        }))
+                    },
+                    _ => Ok(scalar_schema(name)),
+                }
+            },
+        }
+    }
+
+    /// Generate a selection set and matching output schema for a type reference.
+    ///
+    /// Returns `None` for the selection set when the type is a leaf (scalar or enum).
+    fn selection(&self, type_ref: &Value, depth: usize) -> Result<(Option<String>, Value)> {
+        match type_ref["kind"].as_str() {
+            Some("NON_NULL") => self.selection(&type_ref["ofType"], depth),
+            Some("LIST") => {
+                let (set, items) = self.selection(&type_ref["ofType"], depth)?;
+                Ok((set, json!({"type": "array", "items": items})))
+            },
+            _ => {
+                let name = named_type(type_ref);
+                let Some(definition) = self.types.get(name) else {
+                    return Ok((None, scalar_schema(name)));
+                };
+                match definition["kind"].as_str() {
+                    Some("OBJECT" | "INTERFACE") => self.object_selection(definition, depth),
+                    Some("UNION") => Ok((
+                        Some("{ __typename }".to_string()),
+                        json!({"type": "object", "properties": {"__typename": {"type": "string"}}}),
+                    )),
+                    Some("ENUM") => Ok((None, self.input_schema(type_ref, 0)?)),
+                    _ => Ok((None, scalar_schema(name))),
+                }
+            },
+        }
+    }
+
+    fn object_selection(
+        &self,
+        definition: &Value,
+        depth: usize,
+    ) -> Result<(Option<String>, Value)> {
+        let mut parts = Vec::new();
+        let mut properties = Map::new();
+        for field in definition["fields"].as_array().into_iter().flatten() {
+            let takes_required_args = field["args"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .any(|a| is_non_null(&a["type"]) && a["defaultValue"].is_null());
+            if takes_required_args {
+                continue;
+            }
+            let name = field["name"].as_str().unwrap_or_default();
+            let is_leaf = self
+                .types
+                .get(named_type(&field["type"]))
+                .is_none_or(|t| matches!(t["kind"].as_str(), Some("SCALAR" | "ENUM")));
+            if !is_leaf && depth <= 1 {
+                continue;
+            }
+            let (set, schema) = self.selection(&field["type"], depth - 1)?;
+            parts.push(match set {
+                Some(set) => format!("{} {}", name, set),
+                None => name.to_string(),
+            });
+            properties.insert(name.to_string(), schema);
+        }
+
+        if parts.is_empty() {
+            parts.push("__typename".to_string());
+            properties.insert("__typename".to_string(), json!({"type": "string"}));
+        }
+        Ok((
+            Some(format!("{{ {} }}", parts.join(" "))),
+            json!({"type": "object", "properties": properties}),
+        ))
+    }
+}
+
+/// JSON schema for a built-in or custom scalar.
+fn scalar_schema(name: &str) -> Value {
+    match name {
+        "Int" => json!({"type": "integer"}),
+        "Float" => json!({"type": "number"}),
+        "Boolean" => json!({"type": "boolean"}),
+        "String" | "ID" => json!({"type": "string"}),
+        _ => json!({}),
+    }
+}
+
+/// Connection settings shared by all tools from one generator.
+#[derive(Debug)]
+struct SharedConfig {
+    client: reqwest::Client,
+    endpoint: String,
+    auth: ApiAuth,
+    default_headers: Vec<(String, String)>,
+}
+
+impl SharedConfig {
+    /// Execute a GraphQL document and return its `data` object.
+    async fn execute(&self, document: &str, variables: &Map<String, Value>) -> Result<Value> {
+        let mut builder = self.client.post(&self.endpoint);
+        for (name, value) in &self.default_headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        let response = self
+            .auth
+            .apply(builder)
+            .json(&json!({ "query": document, "variables": variables }))
+            .send()
+            .await
+            .map_err(|e| Error::internal(format!("GraphQL request failed: {}", e)))?;
+
+        let status = response.status();
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| Error::internal(format!("Invalid GraphQL response: {}", e)))?;
+
+        if let Some(errors) = body.get("errors").filter(|e| !e.is_null()) {
+            let message = errors[0]["message"]
+                .as_str()
+                .unwrap_or("GraphQL request returned errors")
+                .to_string();
+            return Err(Error::Protocol {
+                code: ErrorCode::INTERNAL_ERROR,
+                message,
+                data: Some(json!({ "errors": errors, "data": body["data"] })),
+            });
+        }
+        if !status.is_success() {
+            return Err(Error::internal(format!(
+                "GraphQL endpoint returned HTTP {}",
+                status
+            )));
+        }
+        Ok(body["data"].clone())
+    }
+}
+
+/// A tool executing a single GraphQL root field.
+#[derive(Debug, Clone)]
+pub struct GraphQlTool {
+    name: String,
+    kind: OperationKind,
+    description: Option<String>,
+    input_schema: Value,
+    output_schema: Value,
+    document: String,
+    shared: Arc<SharedConfig>,
+}
+
+impl GraphQlTool {
+    /// Get the tool name (the root field name).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the operation kind.
+    pub fn kind(&self) -> OperationKind {
+        self.kind
+    }
+
+    /// Get the GraphQL document sent for each call.
+    pub fn document(&self) -> &str {
+        &self.document
+    }
+
+    /// Get the input schema derived from the field arguments.
+    pub fn input_schema(&self) -> &Value {
+        &self.input_schema
+    }
+
+    /// Get the JSON schema of the returned value.
+    ///
+    /// Empty when an explicit selection set was supplied.
+    pub fn output_schema(&self) -> &Value {
+        &self.output_schema
+    }
+}
+
+#[async_trait]
+impl ToolHandler for GraphQlTool {
+    async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> Result<Value> {
+        let variables = match args {
+            Value::Object(map) => map,
+            Value::Null => Map::new(),
+            _ => return Err(Error::invalid_params("Tool arguments must be an object")),
+        };
+
+        let data = tokio::select! {
+            data = self.shared.execute(&self.document, &variables) => data?,
+            () = extra.cancellation_token.cancelled() => return Err(Error::cancelled()),
+        };
+        Ok(data.get(&self.name).cloned().unwrap_or(Value::Null))
+    }
+
+    fn metadata(&self) -> Option<ToolInfo> {
+        Some(ToolInfo {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            input_schema: self.input_schema.clone(),
+            output_schema: Some(self.output_schema.clone()),
+            annotations: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_util::sync::CancellationToken;
+
+    fn named(kind: &str, name: &str) -> Value {
+        json!({"kind": kind, "name": name, "ofType": null})
+    }
+
+    fn non_null(inner: Value) -> Value {
+        json!({"kind": "NON_NULL", "name": null, "ofType": inner})
+    }
+
+    fn introspection() -> Value {
+        json!({"data": {"__schema": {
+            "queryType": {"name": "Query"},
+            "mutationType": {"name": "Mutation"},
+            "types": [
+                {"kind": "OBJECT", "name": "Query", "fields": [
+                    {"name": "user", "description": "Fetch a user",
+                     "args": [{"name": "id", "type": non_null(named("SCALAR", "ID")), "defaultValue": null}],
+                     "type": named("OBJECT", "User")}
+                ]},
+                {"kind": "OBJECT", "name": "Mutation", "fields": [
+                    {"name": "createUser", "args": [
+                        {"name": "input", "type": non_null(named("INPUT_OBJECT", "UserInput")), "defaultValue": null}
+                     ],
+                     "type": non_null(named("OBJECT", "User"))}
+                ]},
+                {"kind": "OBJECT", "name": "User", "fields": [
+                    {"name": "id", "args": [], "type": non_null(named("SCALAR", "ID"))},
+                    {"name": "role", "args": [], "type": named("ENUM", "Role")},
+                    {"name": "friends", "args": [],
+                     "type": {"kind": "LIST", "name": null, "ofType": named("OBJECT", "User")}}
+                ]},
+                {"kind": "ENUM", "name": "Role", "enumValues": [{"name": "ADMIN"}, {"name": "USER"}]},
+                {"kind": "INPUT_OBJECT", "name": "UserInput", "inputFields": [
+                    {"name": "name", "type": non_null(named("SCALAR", "String")), "defaultValue": null},
+                    {"name": "age", "type": named("SCALAR", "Int"), "defaultValue": null}
+                ]},
+                {"kind": "SCALAR", "name": "ID"},
+                {"kind": "SCALAR", "name": "String"},
+                {"kind": "SCALAR", "name": "Int"}
+            ]
+        }}})
+    }
+
+    #[test]
+    fn test_generates_query_document_and_schema() {
+        let tools = GraphQlToolGenerator::new("http://localhost/graphql")
+            .schema(introspection())
+            .query("user")
+            .generate()
+            .unwrap();
+
+        let tool = &tools[0];
+        assert_eq!(
+            tool.document(),
+            "query User($id: ID!) { user(id: $id) { id role friends { id role } } }"
+        );
+        assert_eq!(tool.input_schema()["required"], json!(["id"]));
+        assert_eq!(tool.input_schema()["properties"]["id"]["type"], "string");
+        assert_eq!(
+            tool.output_schema()["properties"]["role"]["enum"],
+            json!(["ADMIN", "USER"])
+        );
+        assert_eq!(
+            tool.metadata().unwrap().output_schema.as_ref(),
+            Some(tool.output_schema())
+        );
+    }
+
+    #[test]
+    fn test_mutation_input_object_schema() {
+        let tools = GraphQlToolGenerator::new("http://localhost/graphql")
+            .schema(introspection())
+            .mutation_with_selection("createUser", "{ id }")
+            .generate()
+            .unwrap();
+
+        let schema = &tools[0].input_schema()["properties"]["input"];
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["name"]));
+        assert_eq!(schema["properties"]["age"]["type"], "integer");
+        assert!(tools[0]
+            .document()
+            .starts_with("mutation CreateUser($input: UserInput!)"));
+    }
+
+    #[test]
+    fn test_unknown_field_is_error() {
+        let err = GraphQlToolGenerator::new("http://localhost/graphql")
+            .schema(introspection())
+            .query("missing")
+            .generate()
+            .unwrap_err();
+        assert!(err.to_string().contains("missing"));
+    }
+
+    #[test]
+    fn test_requires_schema() {
+        let err = GraphQlToolGenerator::new("http://localhost/graphql")
+            .query("user")
+            .generate()
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidState(_)));
+    }
+
+    #[tokio::test]
+    async fn test_executes_and_maps_result() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/graphql")
+            .match_body(mockito::Matcher::PartialJson(
+                json!({"variables": {"id": "1"}}),
+            ))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data":{"user":{"id":"1","role":"ADMIN"}}}"#)
+            .create_async()
+            .await;
+
+        let tools = GraphQlToolGenerator::new(format!("{}/graphql", server.url()))
+            .schema(introspection())
+            .query_with_selection("user", "{ id role }")
+            .generate()
+            .unwrap();
+
+        let extra = RequestHandlerExtra::new("1".to_string(), CancellationToken::new());
+        let result = tools[0].handle(json!({"id": "1"}), extra).await.unwrap();
+        assert_eq!(result, json!({"id": "1", "role": "ADMIN"}));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_graphql_errors_become_protocol_errors() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data":null,"errors":[{"message":"not allowed"}]}"#)
+            .create_async()
+            .await;
+
+        let tools = GraphQlToolGenerator::new(server.url())
+            .schema(introspection())
+            .query("user")
+            .generate()
+            .unwrap();
+
+        let extra = RequestHandlerExtra::new("1".to_string(), CancellationToken::new());
+        let err = tools[0]
+            .handle(json!({"id": "1"}), extra)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not allowed"));
+    }
+}
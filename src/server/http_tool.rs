@@ -0,0 +1,540 @@
+//! Declarative HTTP tools.
+//!
+//! [`HttpTool`] calls a REST endpoint described by a URL template, HTTP method,
+//! headers, authentication and a body mapping, so simple "call this endpoint"
+//! tools need no handler code. Arguments are validated against the tool's input
+//! schema before the request is sent, and transient failures (connection
+//! errors, HTTP 429 and 5xx) of idempotent requests are retried with
+//! exponential backoff.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::http_tool::{BodyMapping, HttpTool};
+//! use pmcp::server::api_auth::ApiAuth;
+//! use pmcp::Server;
+//! use serde_json::json;
+//! use std::time::Duration;
+//!
+//! # fn example() -> pmcp::Result<()> {
+//! let create_issue = HttpTool::new(
+//!     "create_issue",
+//!     "POST",
+//!     "https://api.example.com/repos/{owner}/{repo}/issues",
+//! )?
+//! .with_description("Create an issue")
+//! .with_schema(json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "owner": {"type": "string"},
+//!         "repo": {"type": "string"},
+//!         "title": {"type": "string"},
+//!         "body": {"type": "string"}
+//!     },
+//!     "required": ["owner", "repo", "title"]
+//! }))?
+//! .with_auth(ApiAuth::Bearer("token".to_string()))
+//! .with_body(BodyMapping::Remaining);
+//!
+//! let get_issue = HttpTool::new(
+//!     "get_issue",
+//!     "GET",
+//!     "https://api.example.com/repos/{owner}/{repo}/issues/{number}",
+//! )?
+//! .with_retries(3, Duration::from_millis(200));
+//!
+//! let server = Server::builder()
+//!     .name("issues")
+//!     .version("1.0.0")
+//!     .tool("create_issue", create_issue)
+//!     .tool("get_issue", get_issue)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::shared::uri_template::UriTemplate;
+use crate::types::ToolInfo;
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use super::api_auth::ApiAuth;
+use super::cancellation::RequestHandlerExtra;
+use super::ToolHandler;
+
+/// How tool arguments are mapped onto the request body.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BodyMapping {
+    /// Send no body.
+    #[default]
+    None,
+    /// Send all arguments not consumed by the URL template as a JSON object.
+    Remaining,
+    /// Send the value of a single argument as the JSON body.
+    Argument(String),
+    /// Send the listed arguments as a JSON object.
+    Fields(Vec<String>),
+}
+
+/// A tool that performs a configured HTTP request.
+#[derive(Clone)]
+pub struct HttpTool {
+    name: String,
+    description: Option<String>,
+    method: reqwest::Method,
+    url: UriTemplate,
+    headers: Vec<(String, String)>,
+    auth: ApiAuth,
+    body: BodyMapping,
+    input_schema: Value,
+    #[cfg(feature = "validation")]
+    validator: Option<std::sync::Arc<jsonschema::Validator>>,
+    max_retries: u32,
+    retry_delay: Duration,
+    timeout: Duration,
+    client: reqwest::Client,
+}
+
+impl fmt::Debug for HttpTool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpTool")
+            .field("name", &self.name)
+            .field("method", &self.method)
+            .field("url", &self.url.to_string())
+            .field("body", &self.body)
+            .field("max_retries", &self.max_retries)
+            .finish()
+    }
+}
+
+impl HttpTool {
+    /// Create a new HTTP tool.
+    ///
+    /// `url` is an RFC 6570 URI template; its variables are filled from the
+    /// tool arguments. The default input schema declares every template
+    /// variable as a string, and requires those of path and simple
+    /// expansions; query expansions such as `{?verbose}` stay optional.
+    pub fn new(name: impl Into<String>, method: impl AsRef<str>, url: &str) -> Result<Self> {
+        let method = reqwest::Method::from_bytes(method.as_ref().to_uppercase().as_bytes())
+            .map_err(|_| Error::validation(format!("Invalid HTTP method: {}", method.as_ref())))?;
+        let url = UriTemplate::new(url)?;
+
+        let properties: Map<String, Value> = url
+            .variables()
+            .iter()
+            .map(|v| (v.clone(), json!({"type": "string"})))
+            .collect();
+
+        let required = url.path_variables();
+        let tool = Self {
+            name: name.into(),
+            description: None,
+            method,
+            url,
+            headers: Vec::new(),
+            auth: ApiAuth::None,
+            body: BodyMapping::None,
+            input_schema: Value::Null,
+            #[cfg(feature = "validation")]
+            validator: None,
+            max_retries: 0,
+            retry_delay: Duration::from_millis(100),
+            timeout: Duration::from_secs(30),
+            client: reqwest::Client::new(),
+        };
+        tool.with_schema(json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        }))
+    }
+
+    /// Set the description for this tool.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the input schema used for validation and tool metadata.
+    ///
+    /// Fails if the schema is not a valid JSON Schema.
+    pub fn with_schema(mut self, schema: Value) -> Result<Self> {
+        #[cfg(feature = "validation")]
+        {
+            let validator = jsonschema::validator_for(&schema)
+                .map_err(|e| Error::validation(format!("Invalid input schema: {}", e)))?;
+            self.validator = Some(std::sync::Arc::new(validator));
+        }
+        self.input_schema = schema;
+        Ok(self)
+    }
+
+    /// Add a header sent with every request.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the authentication for requests.
+    pub fn with_auth(mut self, auth: ApiAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Set how arguments map onto the request body.
+    pub fn with_body(mut self, body: BodyMapping) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// Retry transient failures up to `max_retries` times with exponential backoff.
+    ///
+    /// Only idempotent methods (GET, HEAD, PUT, DELETE, OPTIONS) are retried,
+    /// so the side effects of a POST or PATCH run at most once.
+    pub fn with_retries(mut self, max_retries: u32, initial_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_delay = initial_delay;
+        self
+    }
+
+    /// Set the per-attempt request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Use a custom HTTP client (e.g. with proxies or custom TLS).
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Validate arguments against the input schema.
+    fn validate(&self, args: &Map<String, Value>) -> Result<()> {
+        let missing: Vec<&str> = self.input_schema["required"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .filter(|name| !args.contains_key(*name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(Error::invalid_params(format!(
+                "Missing required arguments: {}",
+                missing.join(", ")
+            )));
+        }
+
+        #[cfg(feature = "validation")]
+        if let Some(validator) = &self.validator {
+            let instance = Value::Object(args.clone());
+            let errors: Vec<String> = validator
+                .iter_errors(&instance)
+                .map(|e| format!("{}: {}", e.instance_path, e))
+                .collect();
+            if !errors.is_empty() {
+                return Err(Error::invalid_params(format!(
+                    "Invalid arguments: {}",
+                    errors.join("; ")
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Expand the URL template from the arguments.
+    fn build_url(&self, args: &Map<String, Value>) -> Result<String> {
+        let vars: HashMap<String, String> = self
+            .url
+            .variables()
+            .into_iter()
+            .filter_map(|name| {
+                let value = match args.get(&name)? {
+                    Value::String(s) => s.clone(),
+                    Value::Null => return None,
+                    other => other.to_string(),
+                };
+                Some((name, value))
+            })
+            .collect();
+        self.url.expand_with_map(&vars)
+    }
+
+    /// Build the request body from the arguments.
+    fn build_body(&self, args: &Map<String, Value>) -> Option<Value> {
+        match &self.body {
+            BodyMapping::None => None,
+            BodyMapping::Remaining => {
+                let consumed = self.url.variables();
+                Some(Value::Object(
+                    args.iter()
+                        .filter(|(k, _)| !consumed.contains(k))
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect(),
+                ))
+            },
+            BodyMapping::Argument(name) => args.get(name).cloned(),
+            BodyMapping::Fields(fields) => Some(Value::Object(
+                fields
+                    .iter()
+                    .filter_map(|f| args.get(f).map(|v| (f.clone(), v.clone())))
+                    .collect(),
+            )),
+        }
+    }
+
+    /// Send one attempt; returns the status and parsed body.
+    async fn send_once(&self, url: &str, body: Option<&Value>) -> Result<(u16, Value)> {
+        let mut builder = self
+            .client
+            .request(self.method.clone(), url)
+            .timeout(self.timeout);
+        for (name, value) in &self.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        if let Some(body) = body {
+            builder = builder.json(body);
+        }
+
+        let response = self
+            .auth
+            .apply(builder)
+            .send()
+            .await
+            .map_err(|e| Error::internal(format!("HTTP request failed: {}", e)))?;
+        let status = response.status().as_u16();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| Error::internal(format!("Failed to read response body: {}", e)))?;
+        Ok((
+            status,
+            serde_json::from_str(&text).unwrap_or(Value::String(text)),
+        ))
+    }
+
+    async fn execute(&self, url: &str, body: Option<&Value>) -> Result<Value> {
+        let max_retries = if self.method.is_idempotent() {
+            self.max_retries
+        } else {
+            0
+        };
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+        loop {
+            let outcome = self.send_once(url, body).await;
+            let retryable = match &outcome {
+                Ok((status, _)) => *status == 429 || *status >= 500,
+                Err(_) => true,
+            };
+            if retryable && attempt < max_retries {
+                attempt += 1;
+                tracing::debug!(
+                    "HTTP tool '{}' retrying (attempt {}/{})",
+                    self.name,
+                    attempt,
+                    max_retries
+                );
+                crate::runtime::sleep(delay).await;
+                delay = delay.saturating_mul(2);
+                continue;
+            }
+
+            let (status, body) = outcome?;
+            if !(200..300).contains(&status) {
+                return Err(Error::Protocol {
+                    code: ErrorCode::INTERNAL_ERROR,
+                    message: format!("{} {} returned HTTP {}", self.method, url, status),
+                    data: Some(json!({ "status": status, "body": body })),
+                });
+            }
+            return Ok(json!({ "status": status, "body": body }));
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for HttpTool {
+    async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> Result<Value> {
+        let args = match args {
+            Value::Object(map) => map,
+            Value::Null => Map::new(),
+            _ => return Err(Error::invalid_params("Tool arguments must be an object")),
+        };
+        self.validate(&args)?;
+
+        let url = self.build_url(&args)?;
+        let body = self.build_body(&args);
+
+        tokio::select! {
+            result = self.execute(&url, body.as_ref()) => result,
+            () = extra.cancellation_token.cancelled() => Err(Error::cancelled()),
+        }
+    }
+
+    fn metadata(&self) -> Option<ToolInfo> {
+        Some(ToolInfo {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            input_schema: self.input_schema.clone(),
+            output_schema: None,
+            annotations: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_util::sync::CancellationToken;
+
+    fn extra() -> RequestHandlerExtra {
+        RequestHandlerExtra::new("test".to_string(), CancellationToken::new())
+    }
+
+    #[test]
+    fn test_default_schema_from_template() {
+        let tool =
+            HttpTool::new("get_user", "get", "https://api.test/users/{id}{?verbose}").unwrap();
+        let schema = tool.metadata().unwrap().input_schema;
+        assert_eq!(schema["required"], json!(["id"]));
+        assert_eq!(schema["properties"]["id"]["type"], "string");
+        assert_eq!(schema["properties"]["verbose"]["type"], "string");
+    }
+
+    #[test]
+    fn test_invalid_method() {
+        assert!(HttpTool::new("bad", "NOT A METHOD", "https://api.test").is_err());
+    }
+
+    #[test]
+    fn test_body_mappings() {
+        let tool = HttpTool::new("t", "POST", "https://api.test/{id}").unwrap();
+        let args = json!({"id": "1", "title": "x", "extra": 2});
+        let args = args.as_object().unwrap();
+
+        assert_eq!(tool.build_body(args), None);
+        assert_eq!(
+            tool.clone()
+                .with_body(BodyMapping::Remaining)
+                .build_body(args),
+            Some(json!({"title": "x", "extra": 2}))
+        );
+        assert_eq!(
+            tool.clone()
+                .with_body(BodyMapping::Argument("title".into()))
+                .build_body(args),
+            Some(json!("x"))
+        );
+        assert_eq!(
+            tool.with_body(BodyMapping::Fields(vec!["extra".into()]))
+                .build_body(args),
+            Some(json!({"extra": 2}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_required_argument() {
+        let tool = HttpTool::new("t", "GET", "https://api.test/{id}").unwrap();
+        let err = tool.handle(json!({}), extra()).await.unwrap_err();
+        assert!(err.to_string().contains("id"));
+    }
+
+    #[cfg(feature = "validation")]
+    #[tokio::test]
+    async fn test_schema_validation() {
+        let tool = HttpTool::new("t", "GET", "https://api.test/{id}")
+            .unwrap()
+            .with_schema(json!({
+                "type": "object",
+                "properties": {"id": {"type": "integer"}},
+                "required": ["id"]
+            }))
+            .unwrap();
+        let err = tool
+            .handle(json!({"id": "abc"}), extra())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid arguments"));
+
+        let err = HttpTool::new("t", "GET", "https://api.test")
+            .unwrap()
+            .with_schema(json!({"type": "no-such-type"}))
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid input schema"));
+    }
+
+    #[tokio::test]
+    async fn test_request_with_auth_and_body() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/repos/acme/issues")
+            .match_header("x-api-key", "secret")
+            .match_body(mockito::Matcher::Json(json!({"title": "bug"})))
+            .with_status(201)
+            .with_body(r#"{"number": 7}"#)
+            .create_async()
+            .await;
+
+        let tool = HttpTool::new(
+            "create",
+            "POST",
+            &format!("{}/repos/{{owner}}/issues", server.url()),
+        )
+        .unwrap()
+        .with_auth(ApiAuth::Header {
+            name: "x-api-key".into(),
+            value: "secret".into(),
+        })
+        .with_body(BodyMapping::Remaining);
+
+        let result = tool
+            .handle(json!({"owner": "acme", "title": "bug"}), extra())
+            .await
+            .unwrap();
+        assert_eq!(result["status"], 201);
+        assert_eq!(result["body"]["number"], 7);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let failing = server
+            .mock("GET", "/flaky")
+            .with_status(503)
+            .expect(3)
+            .create_async()
+            .await;
+
+        let tool = HttpTool::new("flaky", "GET", &format!("{}/flaky", server.url()))
+            .unwrap()
+            .with_retries(2, Duration::from_millis(1));
+
+        let err = tool.handle(json!({}), extra()).await.unwrap_err();
+        assert!(err.to_string().contains("503"));
+        failing.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_non_idempotent_methods() {
+        let mut server = mockito::Server::new_async().await;
+        let failing = server
+            .mock("POST", "/orders")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let tool = HttpTool::new("order", "POST", &format!("{}/orders", server.url()))
+            .unwrap()
+            .with_retries(2, Duration::from_millis(1));
+
+        assert!(tool.handle(json!({}), extra()).await.is_err());
+        failing.assert_async().await;
+    }
+}
@@ -0,0 +1,274 @@
+//! Duplicate suppression for tool calls.
+//!
+//! Transports that deliver at least once (reconnect replay, message queues)
+//! can hand the server the same `tools/call` more than once. Clients guard
+//! side-effecting calls by attaching an idempotency key to the request's
+//! `_meta` (see [`CallToolRequest::with_idempotency_key`]). A server with an
+//! [`IdempotencyCache`] runs the tool once per key and answers duplicates
+//! with the cached result.
+//!
+//! Only successful results are cached, so a call that failed can be retried
+//! with the same key. Reusing a key for a different tool or different
+//! arguments is rejected with `INVALID_PARAMS`.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::idempotency::IdempotencyCache;
+//! use pmcp::Server;
+//! use std::time::Duration;
+//!
+//! let server = Server::builder()
+//!     .name("payments")
+//!     .version("1.0.0")
+//!     .idempotency(
+//!         IdempotencyCache::new()
+//!             .with_ttl(Duration::from_secs(3600))
+//!             .with_capacity(10_000),
+//!     )
+//!     .build()?;
+//! # Ok::<(), pmcp::Error>(())
+//! ```
+//!
+//! [`CallToolRequest::with_idempotency_key`]: crate::types::CallToolRequest::with_idempotency_key
+
+use crate::error::{Error, Result};
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+/// Default time a result stays cached.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(600);
+
+/// Default maximum number of cached keys.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+#[derive(Debug)]
+struct Entry {
+    /// The call the key was first used for
+    fingerprint: Value,
+    created: Instant,
+    /// Set once the call succeeds; concurrent duplicates wait on it
+    result: OnceCell<Value>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    entries: HashMap<String, Arc<Entry>>,
+    /// Keys in insertion order, oldest first
+    order: VecDeque<String>,
+}
+
+/// Remembers the results of recent calls by idempotency key.
+///
+/// Entries expire after a TTL, and the oldest entries are evicted once the
+/// cache is full.
+#[derive(Debug)]
+pub struct IdempotencyCache {
+    ttl: Duration,
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+impl Default for IdempotencyCache {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_TTL,
+            capacity: DEFAULT_CAPACITY,
+            state: Mutex::new(State::default()),
+        }
+    }
+}
+
+impl IdempotencyCache {
+    /// Create a cache with the default TTL and capacity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long results stay cached.
+    ///
+    /// Redeliveries arriving later than this run the tool again.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set the maximum number of cached keys.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Number of cached keys, including calls still in flight.
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Whether no keys are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget all cached results.
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.entries.clear();
+        state.order.clear();
+    }
+
+    /// Run `call` unless a call with the same key already succeeded.
+    ///
+    /// `fingerprint` identifies the call (typically the tool name and
+    /// arguments). Duplicates that arrive while the first call is running
+    /// wait for its result.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `call`, or `INVALID_PARAMS` if the key was
+    /// already used with a different fingerprint.
+    pub async fn run<F>(&self, key: &str, fingerprint: Value, call: F) -> Result<Value>
+    where
+        F: Future<Output = Result<Value>>,
+    {
+        let entry = self.entry(key, fingerprint)?;
+        if entry.result.initialized() {
+            tracing::debug!("Replaying cached result for idempotency key '{}'", key);
+        }
+        entry.result.get_or_try_init(|| call).await.cloned()
+    }
+
+    fn entry(&self, key: &str, fingerprint: Value) -> Result<Arc<Entry>> {
+        let mut state = self.state.lock();
+        let now = Instant::now();
+
+        while let Some(oldest) = state.order.front() {
+            let expired = state
+                .entries
+                .get(oldest)
+                .is_none_or(|entry| now.duration_since(entry.created) >= self.ttl);
+            if !expired {
+                break;
+            }
+            if let Some(oldest) = state.order.pop_front() {
+                state.entries.remove(&oldest);
+            }
+        }
+
+        if let Some(entry) = state.entries.get(key) {
+            if entry.fingerprint != fingerprint {
+                return Err(Error::invalid_params(format!(
+                    "Idempotency key '{}' was already used for a different call",
+                    key
+                )));
+            }
+            return Ok(Arc::clone(entry));
+        }
+
+        while state.entries.len() >= self.capacity {
+            let Some(oldest) = state.order.pop_front() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+
+        let entry = Arc::new(Entry {
+            fingerprint,
+            created: now,
+            result: OnceCell::new(),
+        });
+        state.entries.insert(key.to_string(), Arc::clone(&entry));
+        state.order.push_back(key.to_string());
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn counted(runs: &AtomicUsize, result: Result<Value>) -> Result<Value> {
+        runs.fetch_add(1, Ordering::SeqCst);
+        result
+    }
+
+    #[tokio::test]
+    async fn test_duplicates_replay_successful_results() {
+        let cache = IdempotencyCache::new();
+        let runs = AtomicUsize::new(0);
+
+        for _ in 0..3 {
+            let result = cache
+                .run("k1", json!("send"), counted(&runs, Ok(json!({"id": 7}))))
+                .await
+                .unwrap();
+            assert_eq!(result, json!({"id": 7}));
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        let err = cache
+            .run("k1", json!("other"), counted(&runs, Ok(json!(null))))
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::INVALID_PARAMS));
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_not_cached() {
+        let cache = IdempotencyCache::new();
+        let runs = AtomicUsize::new(0);
+
+        let failed = cache
+            .run(
+                "k1",
+                json!("send"),
+                counted(&runs, Err(Error::internal("down"))),
+            )
+            .await;
+        assert!(failed.is_err());
+        let retried = cache
+            .run("k1", json!("send"), counted(&runs, Ok(json!(1))))
+            .await;
+        assert_eq!(retried.unwrap(), json!(1));
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expiry_and_capacity() {
+        let cache = IdempotencyCache::new()
+            .with_ttl(Duration::from_millis(20))
+            .with_capacity(2);
+        let runs = AtomicUsize::new(0);
+
+        for key in ["a", "b", "c"] {
+            cache
+                .run(key, json!(key), counted(&runs, Ok(json!(key))))
+                .await
+                .unwrap();
+        }
+        assert_eq!(cache.len(), 2);
+
+        // "a" was evicted to make room, so it runs again
+        cache
+            .run("a", json!("a"), counted(&runs, Ok(json!("a"))))
+            .await
+            .unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 4);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        cache
+            .run("c", json!("c"), counted(&runs, Ok(json!("c"))))
+            .await
+            .unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 5);
+        assert_eq!(cache.len(), 1);
+    }
+}
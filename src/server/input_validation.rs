@@ -0,0 +1,429 @@
+//! Declarative validation of tool arguments.
+//!
+//! [`ArgumentRules`] attach constraints to the fields of a tool's input:
+//! bounds, patterns, allowed values, and fields required on their own or
+//! when another field has a given value. Registered with
+//! [`ServerBuilder::tool_rules`](crate::ServerBuilder::tool_rules), they are
+//! checked on every `tools/call` before the handler runs; a call breaking
+//! any of them fails with `INVALID_PARAMS`, and the error data lists every
+//! violation, not just the first. The constraints are also merged into the
+//! tool's input schema in `tools/list`, so clients can see them.
+//!
+//! Fields are named by their path in the arguments object, with nested
+//! fields separated by dots (`address.zip`).
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::input_validation::ArgumentRules;
+//! use pmcp::{Server, SyncTool};
+//! use serde_json::json;
+//!
+//! # fn main() -> pmcp::Result<()> {
+//! let rules = ArgumentRules::new()
+//!     .required("amount")
+//!     .min("amount", 0.01)
+//!     .max("amount", 10_000.0)
+//!     .one_of("currency", [json!("EUR"), json!("USD")])
+//!     .pattern("reference", r"^[A-Z]{3}-\d{6}$")?
+//!     .required_if("iban", "method", json!("transfer"));
+//!
+//! let server = Server::builder()
+//!     .name("payments")
+//!     .version("1.0.0")
+//!     .tool("pay", SyncTool::new("pay", Ok))
+//!     .tool_rules("pay", rules)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, ErrorCode, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// A constraint on one argument field.
+#[derive(Debug, Clone)]
+pub enum Constraint {
+    /// The field must be present.
+    Required,
+    /// A numeric field must be at least this value.
+    Minimum(f64),
+    /// A numeric field must be at most this value.
+    Maximum(f64),
+    /// A string field must match this pattern.
+    Pattern(Regex),
+    /// The field must equal one of these values.
+    OneOf(Vec<Value>),
+    /// The field must be present when `field` equals `equals`.
+    RequiredIf {
+        /// Path of the field the requirement depends on.
+        field: String,
+        /// Value of that field that makes this one required.
+        equals: Value,
+    },
+}
+
+impl Constraint {
+    /// Short name of the constraint, as reported in violations.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Required => "required",
+            Self::Minimum(_) => "minimum",
+            Self::Maximum(_) => "maximum",
+            Self::Pattern(_) => "pattern",
+            Self::OneOf(_) => "enum",
+            Self::RequiredIf { .. } => "requiredIf",
+        }
+    }
+}
+
+/// One broken constraint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    /// Path of the offending field.
+    pub field: String,
+    /// Name of the broken constraint, e.g. `minimum`.
+    pub constraint: String,
+    /// Human-readable description.
+    pub message: String,
+}
+
+/// Constraints on the arguments of one tool.
+#[derive(Debug, Clone, Default)]
+pub struct ArgumentRules {
+    rules: Vec<(String, Constraint)>,
+}
+
+/// Look up a dotted path in `value`.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |value, key| value.as_object()?.get(key))
+        .filter(|value| !value.is_null())
+}
+
+impl ArgumentRules {
+    /// Create an empty rule set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a constraint on `field`.
+    pub fn rule(mut self, field: impl Into<String>, constraint: Constraint) -> Self {
+        self.rules.push((field.into(), constraint));
+        self
+    }
+
+    /// Require `field`.
+    pub fn required(self, field: impl Into<String>) -> Self {
+        self.rule(field, Constraint::Required)
+    }
+
+    /// Require a numeric `field` to be at least `min`.
+    pub fn min(self, field: impl Into<String>, min: f64) -> Self {
+        self.rule(field, Constraint::Minimum(min))
+    }
+
+    /// Require a numeric `field` to be at most `max`.
+    pub fn max(self, field: impl Into<String>, max: f64) -> Self {
+        self.rule(field, Constraint::Maximum(max))
+    }
+
+    /// Require a string `field` to match `pattern`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `pattern` is not a valid regex.
+    pub fn pattern(self, field: impl Into<String>, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| Error::validation(format!("Invalid pattern '{}': {}", pattern, e)))?;
+        Ok(self.rule(field, Constraint::Pattern(regex)))
+    }
+
+    /// Require `field` to equal one of `allowed`.
+    pub fn one_of(
+        self,
+        field: impl Into<String>,
+        allowed: impl IntoIterator<Item = Value>,
+    ) -> Self {
+        self.rule(field, Constraint::OneOf(allowed.into_iter().collect()))
+    }
+
+    /// Require `field` whenever `other` equals `equals`.
+    pub fn required_if(
+        self,
+        field: impl Into<String>,
+        other: impl Into<String>,
+        equals: Value,
+    ) -> Self {
+        self.rule(
+            field,
+            Constraint::RequiredIf {
+                field: other.into(),
+                equals,
+            },
+        )
+    }
+
+    /// Whether no constraints are configured.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Every constraint `arguments` breaks, in the order they were added.
+    pub fn check(&self, arguments: &Value) -> Vec<Violation> {
+        self.rules
+            .iter()
+            .filter_map(|(field, constraint)| {
+                let message = Self::check_one(arguments, field, constraint)?;
+                Some(Violation {
+                    field: field.clone(),
+                    constraint: constraint.name().to_string(),
+                    message,
+                })
+            })
+            .collect()
+    }
+
+    fn check_one(arguments: &Value, field: &str, constraint: &Constraint) -> Option<String> {
+        let value = lookup(arguments, field);
+        match (constraint, value) {
+            (Constraint::Required, None) => Some(format!("'{}' is required", field)),
+            (
+                Constraint::RequiredIf {
+                    field: other,
+                    equals,
+                },
+                None,
+            ) => (lookup(arguments, other) == Some(equals))
+                .then(|| format!("'{}' is required when '{}' is {}", field, other, equals)),
+            (Constraint::Minimum(min), Some(value)) => match value.as_f64() {
+                Some(n) if n >= *min => None,
+                Some(_) => Some(format!("'{}' must be at least {}", field, min)),
+                None => Some(format!("'{}' must be a number", field)),
+            },
+            (Constraint::Maximum(max), Some(value)) => match value.as_f64() {
+                Some(n) if n <= *max => None,
+                Some(_) => Some(format!("'{}' must be at most {}", field, max)),
+                None => Some(format!("'{}' must be a number", field)),
+            },
+            (Constraint::Pattern(regex), Some(value)) => match value.as_str() {
+                Some(s) if regex.is_match(s) => None,
+                Some(_) => Some(format!("'{}' must match {}", field, regex.as_str())),
+                None => Some(format!("'{}' must be a string", field)),
+            },
+            (Constraint::OneOf(allowed), Some(value)) => (!allowed.contains(value)).then(|| {
+                let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+                format!("'{}' must be one of {}", field, allowed.join(", "))
+            }),
+            _ => None,
+        }
+    }
+
+    /// Check `arguments` against every constraint of `tool`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `INVALID_PARAMS` listing every violation in its data.
+    pub fn validate(&self, tool: &str, arguments: &Value) -> Result<()> {
+        let violations = self.check(arguments);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let summary: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+        Err(Error::Protocol {
+            code: ErrorCode::INVALID_PARAMS,
+            message: format!(
+                "Invalid arguments for tool '{}': {}",
+                tool,
+                summary.join("; ")
+            ),
+            data: Some(json!({ "violations": violations })),
+        })
+    }
+
+    /// Merge the constraints into a JSON Schema for the arguments.
+    ///
+    /// Bounds, patterns, and allowed values become `minimum`, `maximum`,
+    /// `pattern`, and `enum` on the field's schema; required fields are
+    /// added to their parent's `required`; conditional requirements become
+    /// `if`/`then` clauses under `allOf`.
+    pub fn apply_to_schema(&self, schema: &mut Value) {
+        for (field, constraint) in &self.rules {
+            let (parent, name) = match field.rsplit_once('.') {
+                Some((parent, name)) => (Some(parent), name),
+                None => (None, field.as_str()),
+            };
+            let Some(object) = Self::object_schema(schema, parent) else {
+                continue;
+            };
+            match constraint {
+                Constraint::Required => Self::push_required(object, name),
+                Constraint::Minimum(min) => {
+                    Self::property(object, name).insert("minimum".into(), json!(min));
+                },
+                Constraint::Maximum(max) => {
+                    Self::property(object, name).insert("maximum".into(), json!(max));
+                },
+                Constraint::Pattern(regex) => {
+                    Self::property(object, name).insert("pattern".into(), json!(regex.as_str()));
+                },
+                Constraint::OneOf(allowed) => {
+                    Self::property(object, name).insert("enum".into(), json!(allowed));
+                },
+                Constraint::RequiredIf {
+                    field: other,
+                    equals,
+                } => {
+                    // Only sibling conditions can be expressed on the parent
+                    let other = match (parent, other.rsplit_once('.')) {
+                        (Some(parent), Some((other_parent, other))) if parent == other_parent => {
+                            other
+                        },
+                        (None, None) => other.as_str(),
+                        _ => continue,
+                    };
+                    let clause = json!({
+                        "if": {
+                            "properties": { other: { "const": equals } },
+                            "required": [other],
+                        },
+                        "then": { "required": [name] },
+                    });
+                    if let Value::Array(all_of) = object
+                        .entry("allOf")
+                        .or_insert_with(|| Value::Array(Vec::new()))
+                    {
+                        all_of.push(clause);
+                    }
+                },
+            }
+        }
+    }
+
+    /// The object schema at a dotted path of properties, created as needed.
+    fn object_schema<'a>(
+        schema: &'a mut Value,
+        path: Option<&str>,
+    ) -> Option<&'a mut Map<String, Value>> {
+        let mut object = schema.as_object_mut()?;
+        for key in path.into_iter().flat_map(|path| path.split('.')) {
+            object = Self::property(object, key);
+        }
+        Some(object)
+    }
+
+    /// The schema of property `name`, created as needed.
+    fn property<'a>(object: &'a mut Map<String, Value>, name: &str) -> &'a mut Map<String, Value> {
+        let properties = object
+            .entry("properties")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !properties.is_object() {
+            *properties = Value::Object(Map::new());
+        }
+        let property = properties
+            .as_object_mut()
+            .expect("properties is an object")
+            .entry(name)
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !property.is_object() {
+            *property = Value::Object(Map::new());
+        }
+        property.as_object_mut().expect("property is an object")
+    }
+
+    fn push_required(object: &mut Map<String, Value>, name: &str) {
+        let required = object
+            .entry("required")
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(required) = required {
+            if !required.iter().any(|r| r == name) {
+                required.push(json!(name));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> ArgumentRules {
+        ArgumentRules::new()
+            .required("amount")
+            .min("amount", 1.0)
+            .max("amount", 100.0)
+            .one_of("currency", [json!("EUR"), json!("USD")])
+            .pattern("account.reference", r"^[A-Z]{3}-\d+$")
+            .unwrap()
+            .required_if("iban", "method", json!("transfer"))
+    }
+
+    #[test]
+    fn test_reports_every_violation() {
+        let violations = rules().check(&json!({
+            "amount": 500,
+            "currency": "GBP",
+            "account": {"reference": "abc"},
+            "method": "transfer",
+        }));
+        let broken: Vec<_> = violations
+            .iter()
+            .map(|v| (v.field.as_str(), v.constraint.as_str()))
+            .collect();
+        assert_eq!(
+            broken,
+            [
+                ("amount", "maximum"),
+                ("currency", "enum"),
+                ("account.reference", "pattern"),
+                ("iban", "requiredIf"),
+            ]
+        );
+
+        assert_eq!(rules().check(&json!({})).len(), 1);
+        assert!(rules()
+            .check(&json!({"amount": 5, "method": "card"}))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_validate_returns_invalid_params() {
+        let err = rules()
+            .validate("pay", &json!({"amount": "lots"}))
+            .unwrap_err();
+        let Error::Protocol { code, data, .. } = err else {
+            panic!("expected a protocol error");
+        };
+        assert_eq!(code, ErrorCode::INVALID_PARAMS);
+        assert_eq!(
+            data.unwrap()["violations"][0]["message"],
+            "'amount' must be a number"
+        );
+        assert!(ArgumentRules::new().pattern("x", "(").is_err());
+    }
+
+    #[test]
+    fn test_apply_to_schema() {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {"amount": {"type": "number"}},
+        });
+        rules().apply_to_schema(&mut schema);
+        assert_eq!(
+            schema["properties"]["amount"],
+            json!({"type": "number", "minimum": 1.0, "maximum": 100.0})
+        );
+        assert_eq!(schema["required"], json!(["amount"]));
+        assert_eq!(
+            schema["properties"]["currency"]["enum"],
+            json!(["EUR", "USD"])
+        );
+        assert_eq!(
+            schema["properties"]["account"]["properties"]["reference"]["pattern"],
+            r"^[A-Z]{3}-\d+$"
+        );
+        assert_eq!(schema["allOf"][0]["then"], json!({"required": ["iban"]}));
+    }
+}
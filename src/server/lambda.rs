@@ -0,0 +1,352 @@
+//! AWS Lambda adapter for API Gateway.
+//!
+//! [`LambdaAdapter`] runs a [`Server`] as a Lambda function behind API
+//! Gateway, speaking the POST side of the streamable HTTP transport. It
+//! accepts proxy events of both REST APIs (payload format 1.0) and HTTP APIs
+//! (payload format 2.0), and answers with a proxy response both accept.
+//!
+//! Lambda keeps no connection between invocations, and successive requests
+//! of a client may reach different instances, so the adapter is stateless:
+//! it issues no `Mcp-Session-Id`, ignores one sent by the client, and the
+//! server answers requests whether or not that instance saw the client's
+//! `initialize`. Server-to-client streams are not available, so `GET`
+//! requests are refused with `405 Method Not Allowed`, as the transport
+//! allows.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::lambda::LambdaAdapter;
+//! use pmcp::server::simple_tool::SyncTool;
+//! use pmcp::Server;
+//!
+//! #[tokio::main]
+//! async fn main() -> pmcp::Result<()> {
+//!     let server = Server::builder()
+//!         .name("lambda-server")
+//!         .version("1.0.0")
+//!         .tool("echo", SyncTool::new("echo", Ok))
+//!         .build()?;
+//!
+//!     LambdaAdapter::new(server).run().await
+//! }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::shared::frame_parser::parse_frame_batch;
+use crate::shared::TransportMessage;
+use crate::Server;
+use base64::Engine;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+
+/// Headers added to every response, so browser-based clients can call the
+/// function across origins.
+const CORS_HEADERS: [(&str, &str); 4] = [
+    ("Access-Control-Allow-Origin", "*"),
+    ("Access-Control-Allow-Methods", "POST, OPTIONS"),
+    (
+        "Access-Control-Allow-Headers",
+        "Content-Type, Accept, mcp-session-id, mcp-protocol-version",
+    ),
+    ("Access-Control-Expose-Headers", "mcp-protocol-version"),
+];
+
+/// The parts of an API Gateway proxy event the adapter uses.
+#[derive(Debug)]
+struct ApiGatewayRequest {
+    method: String,
+    /// Header values by lowercase name
+    headers: Map<String, Value>,
+    body: Vec<u8>,
+}
+
+impl ApiGatewayRequest {
+    /// Read a REST API (1.0) or HTTP API (2.0) proxy event.
+    fn from_event(event: &Value) -> Result<Self> {
+        let method = if event["version"] == "2.0" {
+            &event["requestContext"]["http"]["method"]
+        } else {
+            &event["httpMethod"]
+        };
+        let method = method
+            .as_str()
+            .ok_or_else(|| Error::validation("Not an API Gateway proxy event"))?
+            .to_ascii_uppercase();
+
+        let headers = event["headers"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.clone()))
+            .collect();
+
+        let body = event["body"].as_str().unwrap_or_default();
+        let body = if event["isBase64Encoded"] == true {
+            base64::engine::general_purpose::STANDARD
+                .decode(body)
+                .map_err(|e| Error::validation(format!("Invalid base64 body: {}", e)))?
+        } else {
+            body.as_bytes().to_vec()
+        };
+
+        Ok(Self {
+            method,
+            headers,
+            body,
+        })
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(Value::as_str)
+    }
+}
+
+/// An API Gateway proxy response, in the shape both payload formats accept.
+fn response(status: u16, headers: &[(&str, &str)], body: String) -> Value {
+    let headers: Map<String, Value> = CORS_HEADERS
+        .iter()
+        .chain(headers)
+        .map(|(name, value)| ((*name).to_string(), Value::from(*value)))
+        .collect();
+    json!({
+        "statusCode": status,
+        "headers": headers,
+        "body": body,
+        "isBase64Encoded": false,
+    })
+}
+
+/// A JSON-RPC error response not tied to a request.
+fn error_response(status: u16, code: crate::ErrorCode, message: &str) -> Value {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "error": {"code": code.as_i32(), "message": message},
+        "id": null,
+    });
+    response(
+        status,
+        &[("Content-Type", "application/json")],
+        body.to_string(),
+    )
+}
+
+/// Runs a [`Server`] as an AWS Lambda function behind API Gateway.
+///
+/// See the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct LambdaAdapter {
+    server: Arc<Server>,
+}
+
+impl LambdaAdapter {
+    /// Serve `server`.
+    pub fn new(server: Server) -> Self {
+        Self {
+            server: Arc::new(server),
+        }
+    }
+
+    /// Answer an API Gateway proxy event with a proxy response.
+    ///
+    /// Requests the transport rejects, such as a malformed body, are
+    /// answered with the matching HTTP status rather than an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `event` is not an API Gateway proxy event.
+    pub async fn handle_event(&self, event: Value) -> Result<Value> {
+        let request = ApiGatewayRequest::from_event(&event)?;
+        Ok(match request.method.as_str() {
+            "POST" => self.handle_post(&request).await,
+            "OPTIONS" => response(204, &[], String::new()),
+            _ => response(405, &[("Allow", "POST, OPTIONS")], String::new()),
+        })
+    }
+
+    async fn handle_post(&self, request: &ApiGatewayRequest) -> Value {
+        let content_type = request.header("content-type").unwrap_or_default();
+        if !content_type.contains("application/json") {
+            return error_response(
+                415,
+                crate::ErrorCode::PARSE_ERROR,
+                "Content-Type must be application/json",
+            );
+        }
+        if let Some(accept) = request.header("accept") {
+            if !accept.contains("application/json")
+                && !accept.contains("text/event-stream")
+                && !accept.contains("*/*")
+            {
+                return error_response(
+                    406,
+                    crate::ErrorCode::PARSE_ERROR,
+                    "Accept header must include application/json",
+                );
+            }
+        }
+
+        let batch = request.body.trim_ascii_start().starts_with(b"[");
+        let messages = match parse_frame_batch(&request.body) {
+            Ok(messages) => messages,
+            Err(e) => {
+                return error_response(
+                    400,
+                    crate::ErrorCode::PARSE_ERROR,
+                    &format!("Invalid JSON: {}", e),
+                )
+            },
+        };
+
+        let mut responses = Vec::new();
+        for message in messages {
+            // Notifications and responses need no answer, and nothing
+            // outlives the invocation for them to affect
+            if let TransportMessage::Request { id, request } = message {
+                responses.push(self.server.handle_request(id, request).await);
+            }
+        }
+
+        let body = match (batch, responses.as_slice()) {
+            (_, []) => return response(202, &[], String::new()),
+            (false, [single]) => serde_json::to_string(single),
+            (_, all) => serde_json::to_string(all),
+        };
+        match body {
+            Ok(body) => response(
+                200,
+                &[
+                    ("Content-Type", "application/json"),
+                    ("mcp-protocol-version", crate::DEFAULT_PROTOCOL_VERSION),
+                ],
+                body,
+            ),
+            Err(e) => error_response(500, crate::ErrorCode::INTERNAL_ERROR, &e.to_string()),
+        }
+    }
+
+    /// Serve invocations from the Lambda runtime until it shuts down.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Lambda runtime fails.
+    pub async fn run(self) -> Result<()> {
+        lambda_runtime::run(lambda_runtime::service_fn(
+            |event: lambda_runtime::LambdaEvent<Value>| {
+                let adapter = self.clone();
+                async move {
+                    adapter
+                        .handle_event(event.payload)
+                        .await
+                        .map_err(lambda_runtime::Error::from)
+                }
+            },
+        ))
+        .await
+        .map_err(|e| Error::internal(format!("Lambda runtime failed: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::simple_tool::SyncTool;
+
+    fn adapter() -> LambdaAdapter {
+        LambdaAdapter::new(
+            Server::builder()
+                .name("lambda")
+                .version("1.0.0")
+                .tool("echo", SyncTool::new("echo", Ok))
+                .build()
+                .unwrap(),
+        )
+    }
+
+    fn rest_event(method: &str, body: &str) -> Value {
+        json!({
+            "httpMethod": method,
+            "path": "/mcp",
+            "headers": {"Content-Type": "application/json", "Accept": "application/json"},
+            "body": body,
+            "isBase64Encoded": false,
+        })
+    }
+
+    fn http_api_event(method: &str, body: &str) -> Value {
+        json!({
+            "version": "2.0",
+            "rawPath": "/mcp",
+            "requestContext": {"http": {"method": method, "path": "/mcp"}},
+            "headers": {"content-type": "application/json"},
+            "body": base64::engine::general_purpose::STANDARD.encode(body),
+            "isBase64Encoded": true,
+        })
+    }
+
+    fn body(response: &Value) -> Value {
+        serde_json::from_str(response["body"].as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rest_and_http_api_events_are_served_statelessly() {
+        let adapter = adapter();
+
+        // No initialize needed, and no session issued
+        let call = r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"echo","arguments":{"text":"hi"}}}"#;
+        let response = adapter
+            .handle_event(rest_event("POST", call))
+            .await
+            .unwrap();
+        assert_eq!(response["statusCode"], 200);
+        assert!(response["headers"].get("mcp-session-id").is_none());
+        assert_eq!(body(&response)["id"], 1);
+        assert!(body(&response)["result"].to_string().contains("hi"));
+
+        let batch = r#"[{"jsonrpc":"2.0","id":"a","method":"tools/list"},{"jsonrpc":"2.0","method":"notifications/initialized"}]"#;
+        let response = adapter
+            .handle_event(http_api_event("POST", batch))
+            .await
+            .unwrap();
+        assert_eq!(response["statusCode"], 200);
+        assert_eq!(body(&response)[0]["result"]["tools"][0]["name"], "echo");
+
+        let note = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        let response = adapter
+            .handle_event(rest_event("POST", note))
+            .await
+            .unwrap();
+        assert_eq!(response["statusCode"], 202);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_requests_are_shaped_as_http_errors() {
+        let adapter = adapter();
+
+        let response = adapter.handle_event(rest_event("GET", "")).await.unwrap();
+        assert_eq!(response["statusCode"], 405);
+        let response = adapter
+            .handle_event(http_api_event("OPTIONS", ""))
+            .await
+            .unwrap();
+        assert_eq!(response["statusCode"], 204);
+        assert_eq!(response["headers"]["Access-Control-Allow-Origin"], "*");
+
+        let response = adapter
+            .handle_event(rest_event("POST", "{not json"))
+            .await
+            .unwrap();
+        assert_eq!(response["statusCode"], 400);
+        assert_eq!(body(&response)["error"]["code"], -32700);
+
+        let mut event = rest_event("POST", "{}");
+        event["headers"] = json!({"Content-Type": "text/plain"});
+        let response = adapter.handle_event(event).await.unwrap();
+        assert_eq!(response["statusCode"], 415);
+
+        assert!(adapter
+            .handle_event(json!({"source": "aws.events"}))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,542 @@
+//! Log messages forwarded to the client.
+//!
+//! Handlers send `notifications/message` through a [`ServerLogger`], taken
+//! from [`Server::logger`](crate::Server::logger) or the handler's
+//! [`RequestHandlerExtra`]. The client picks the minimum level it wants with
+//! `logging/setLevel`; less severe messages are dropped on the server.
+//! Until the client sets a level, [`DEFAULT_LOG_LEVEL`] applies.
+//!
+//! A server emitting log messages should declare the `logging` capability.
+//!
+//! Servers speaking over stdio must keep their own logs off stdout, which
+//! carries the protocol. [`init_stdio_logging`] sends `tracing` records to
+//! stderr instead, and [`StdioLogging::forward_to`] also mirrors warnings and
+//! errors to the client through a [`ServerLogger`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use pmcp::{RequestHandlerExtra, ToolHandler};
+//! use serde_json::{json, Value};
+//!
+//! struct Migrate;
+//!
+//! #[async_trait]
+//! impl ToolHandler for Migrate {
+//!     async fn handle(&self, _args: Value, extra: RequestHandlerExtra) -> pmcp::Result<Value> {
+//!         if let Some(logger) = &extra.logger {
+//!             logger.info("Applying 3 migrations").await?;
+//!             logger
+//!                 .debug_with("Migration applied", json!({"version": 42}))
+//!                 .await?;
+//!         }
+//!         Ok(json!({"applied": 3}))
+//!     }
+//! }
+//! ```
+//!
+//! [`RequestHandlerExtra`]: crate::server::cancellation::RequestHandlerExtra
+
+use crate::error::{Error, Result, TransportError};
+use crate::types::protocol::{LogLevel, LogMessageParams};
+use crate::types::{LoggingLevel, Notification, ServerNotification};
+use parking_lot::RwLock;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Level below which messages are dropped until the client sets one.
+pub const DEFAULT_LOG_LEVEL: LoggingLevel = LoggingLevel::Info;
+
+/// Sends log messages to the connected client, filtered by the level it
+/// asked for.
+///
+/// Clones share the level and connection; [`named`](Self::named) clones
+/// tag their messages with a logger name.
+#[derive(Clone)]
+pub struct ServerLogger {
+    inner: Arc<Inner>,
+    name: Option<String>,
+}
+
+struct Inner {
+    level: RwLock<LoggingLevel>,
+    tx: RwLock<Option<mpsc::Sender<Notification>>>,
+}
+
+impl fmt::Debug for ServerLogger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerLogger")
+            .field("name", &self.name)
+            .field("level", &self.level())
+            .field("connected", &self.inner.tx.read().is_some())
+            .finish()
+    }
+}
+
+impl Default for ServerLogger {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_LEVEL)
+    }
+}
+
+/// Severity of a level the client can request.
+fn threshold_rank(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Warning => 2,
+        LoggingLevel::Error => 3,
+        LoggingLevel::Critical => 4,
+    }
+}
+
+/// Severity of a message level.
+fn message_rank(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Debug => 0,
+        LogLevel::Info => 1,
+        LogLevel::Warning => 2,
+        LogLevel::Error => 3,
+    }
+}
+
+impl ServerLogger {
+    /// Create a logger, not yet connected, sending messages at `level` and
+    /// above.
+    pub fn new(level: LoggingLevel) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                level: RwLock::new(level),
+                tx: RwLock::new(None),
+            }),
+            name: None,
+        }
+    }
+
+    /// Route messages through `tx` once the server is running.
+    pub(crate) fn attach(&self, tx: mpsc::Sender<Notification>) {
+        *self.inner.tx.write() = Some(tx);
+    }
+
+    /// A logger sharing this one's level and connection whose messages are
+    /// tagged with `name`.
+    pub fn named(&self, name: impl Into<String>) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            name: Some(name.into()),
+        }
+    }
+
+    /// Minimum level currently sent.
+    pub fn level(&self) -> LoggingLevel {
+        *self.inner.level.read()
+    }
+
+    /// Set the minimum level sent, as `logging/setLevel` does.
+    pub fn set_level(&self, level: LoggingLevel) {
+        *self.inner.level.write() = level;
+    }
+
+    /// Whether messages at `level` would be sent.
+    pub fn enabled(&self, level: LogLevel) -> bool {
+        message_rank(level) >= threshold_rank(self.level())
+    }
+
+    /// Send a message at `level` with optional structured `data`.
+    ///
+    /// Messages below the client's level, and messages logged while the
+    /// server isn't running, are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the server has stopped.
+    pub async fn log(
+        &self,
+        level: LogLevel,
+        message: impl Into<String>,
+        data: Option<Value>,
+    ) -> Result<()> {
+        if !self.enabled(level) {
+            return Ok(());
+        }
+        let Some(tx) = self.inner.tx.read().clone() else {
+            return Ok(());
+        };
+        let notification = ServerNotification::LogMessage(LogMessageParams {
+            level,
+            logger: self.name.clone(),
+            message: message.into(),
+            data,
+        });
+        tx.send(Notification::Server(notification))
+            .await
+            .map_err(|_| Error::Transport(TransportError::ConnectionClosed))
+    }
+
+    /// Send a message without waiting, tagged with `logger` instead of this
+    /// logger's name.
+    ///
+    /// The message is dropped if the notification queue is full.
+    fn try_log(&self, level: LogLevel, logger: String, message: String, data: Option<Value>) {
+        if !self.enabled(level) {
+            return;
+        }
+        let Some(tx) = self.inner.tx.read().clone() else {
+            return;
+        };
+        let notification = ServerNotification::LogMessage(LogMessageParams {
+            level,
+            logger: Some(logger),
+            message,
+            data,
+        });
+        let _ = tx.try_send(Notification::Server(notification));
+    }
+
+    /// Send a debug message.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the server has stopped.
+    pub async fn debug(&self, message: impl Into<String>) -> Result<()> {
+        self.log(LogLevel::Debug, message, None).await
+    }
+
+    /// Send a debug message with structured data.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the server has stopped.
+    pub async fn debug_with(&self, message: impl Into<String>, data: Value) -> Result<()> {
+        self.log(LogLevel::Debug, message, Some(data)).await
+    }
+
+    /// Send an informational message.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the server has stopped.
+    pub async fn info(&self, message: impl Into<String>) -> Result<()> {
+        self.log(LogLevel::Info, message, None).await
+    }
+
+    /// Send a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the server has stopped.
+    pub async fn warning(&self, message: impl Into<String>) -> Result<()> {
+        self.log(LogLevel::Warning, message, None).await
+    }
+
+    /// Send an error message.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the server has stopped.
+    pub async fn error(&self, message: impl Into<String>) -> Result<()> {
+        self.log(LogLevel::Error, message, None).await
+    }
+}
+
+/// A `tracing` layer mirroring records to the client as
+/// `notifications/message`.
+///
+/// Records at [`WARN`](tracing::Level::WARN) and above are forwarded unless
+/// [`with_min_level`](Self::with_min_level) says otherwise. A record's
+/// target becomes the message's logger name and its fields other than the
+/// message become the message data. The client's `logging/setLevel` still
+/// applies, and records are dropped rather than waited on when the
+/// connection is busy.
+#[derive(Debug, Clone)]
+pub struct ClientLogLayer {
+    logger: ServerLogger,
+    min_level: tracing::Level,
+}
+
+impl ClientLogLayer {
+    /// Forward warnings and errors through `logger`.
+    pub fn new(logger: ServerLogger) -> Self {
+        Self {
+            logger,
+            min_level: tracing::Level::WARN,
+        }
+    }
+
+    /// Forward records at `level` and above.
+    pub fn with_min_level(mut self, level: tracing::Level) -> Self {
+        self.min_level = level;
+        self
+    }
+}
+
+impl<S: tracing::Subscriber> Layer<S> for ClientLogLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        // More verbose levels compare greater
+        if *metadata.level() > self.min_level {
+            return;
+        }
+        let level = match *metadata.level() {
+            tracing::Level::ERROR => LogLevel::Error,
+            tracing::Level::WARN => LogLevel::Warning,
+            tracing::Level::INFO => LogLevel::Info,
+            _ => LogLevel::Debug,
+        };
+        let mut fields = FieldVisitor::default();
+        event.record(&mut fields);
+        let data = (!fields.data.is_empty()).then_some(Value::Object(fields.data));
+        self.logger
+            .try_log(level, metadata.target().to_string(), fields.message, data);
+    }
+}
+
+/// Collects a record's message and its other fields as JSON.
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    data: Map<String, Value>,
+}
+
+impl FieldVisitor {
+    fn record_value(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = match value {
+                Value::String(message) => message,
+                other => other.to_string(),
+            };
+        } else {
+            self.data.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_value(field, Value::String(format!("{:?}", value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_value(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_value(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record_value(field, Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record_value(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record_value(field, Value::from(value));
+    }
+}
+
+/// Logging setup for servers speaking over stdio.
+///
+/// Records go to stderr, filtered by `RUST_LOG` or, when it is unset, by the
+/// [default filter](Self::default_filter). Use [`init_stdio_logging`] for
+/// the defaults.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use pmcp::server::logging::StdioLogging;
+/// use pmcp::Server;
+///
+/// # fn example() -> pmcp::Result<()> {
+/// let server = Server::builder()
+///     .name("stdio-server")
+///     .version("1.0.0")
+///     .build()?;
+///
+/// // Also mirror warnings and errors to the client
+/// StdioLogging::new()
+///     .default_filter("info,hyper=warn")
+///     .forward_to(server.logger())
+///     .init()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StdioLogging {
+    default_filter: String,
+    forward: Option<ClientLogLayer>,
+}
+
+impl Default for StdioLogging {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StdioLogging {
+    /// Log at `info` and above to stderr, without forwarding.
+    pub fn new() -> Self {
+        Self {
+            default_filter: "info".to_string(),
+            forward: None,
+        }
+    }
+
+    /// Filter directives used when `RUST_LOG` is unset, such as
+    /// `info,my_server=debug`.
+    pub fn default_filter(mut self, directives: impl Into<String>) -> Self {
+        self.default_filter = directives.into();
+        self
+    }
+
+    /// Also mirror warnings and errors to the client through `logger`.
+    ///
+    /// See [`ClientLogLayer`].
+    pub fn forward_to(self, logger: ServerLogger) -> Self {
+        self.forward_with(ClientLogLayer::new(logger))
+    }
+
+    /// Also mirror records to the client through `layer`.
+    pub fn forward_with(mut self, layer: ClientLogLayer) -> Self {
+        self.forward = Some(layer);
+        self
+    }
+
+    /// Install the setup as the global `tracing` subscriber.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the filter directives are invalid or a global
+    /// subscriber is already installed.
+    pub fn init(self) -> Result<()> {
+        let filter = match EnvFilter::try_from_default_env() {
+            Ok(filter) => filter,
+            Err(_) => EnvFilter::try_new(&self.default_filter).map_err(|e| {
+                Error::validation(format!(
+                    "Invalid log filter '{}': {}",
+                    self.default_filter, e
+                ))
+            })?,
+        };
+        let stderr = tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_ansi(false);
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(stderr)
+            .with(self.forward)
+            .try_init()
+            .map_err(|e| Error::internal(format!("Failed to initialize logging: {}", e)))
+    }
+}
+
+/// Send `tracing` records to stderr, keeping stdout free for the protocol.
+///
+/// Filters by `RUST_LOG`, or at `info` and above when it is unset. See
+/// [`StdioLogging`] to change the default or mirror records to the client.
+///
+/// # Errors
+///
+/// Returns an error if a global subscriber is already installed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use pmcp::Server;
+///
+/// #[tokio::main]
+/// async fn main() -> pmcp::Result<()> {
+///     pmcp::server::init_stdio_logging()?;
+///     Server::builder()
+///         .name("stdio-server")
+///         .version("1.0.0")
+///         .build()?
+///         .run_stdio()
+///         .await
+/// }
+/// ```
+pub fn init_stdio_logging() -> Result<()> {
+    StdioLogging::new().init()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_messages_below_level_are_dropped() {
+        let logger = ServerLogger::default();
+        let (tx, mut rx) = mpsc::channel(8);
+        logger.attach(tx);
+        let db = logger.named("db");
+
+        db.debug("hidden").await.unwrap();
+        db.info("shown").await.unwrap();
+        logger.set_level(LoggingLevel::Error);
+        db.warning("hidden").await.unwrap();
+        db.error("shown").await.unwrap();
+        logger.set_level(LoggingLevel::Critical);
+        assert!(!db.enabled(LogLevel::Error));
+
+        let mut received = Vec::new();
+        while let Ok(Notification::Server(ServerNotification::LogMessage(params))) = rx.try_recv() {
+            assert_eq!(params.logger.as_deref(), Some("db"));
+            received.push((params.level, params.message));
+        }
+        assert_eq!(
+            received,
+            [
+                (LogLevel::Info, "shown".to_string()),
+                (LogLevel::Error, "shown".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_layer_forwards_warnings_to_client() {
+        let logger = ServerLogger::default();
+        let (tx, mut rx) = mpsc::channel(8);
+        logger.attach(tx);
+        let subscriber = tracing_subscriber::registry().with(ClientLogLayer::new(logger));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("not forwarded");
+            tracing::warn!(user = "ann", free_mb = 12, "disk almost full");
+            tracing::error!(target: "db", "connection lost");
+        });
+
+        let Ok(Notification::Server(ServerNotification::LogMessage(warning))) = rx.try_recv()
+        else {
+            panic!("expected a log message");
+        };
+        assert_eq!(warning.level, LogLevel::Warning);
+        assert_eq!(warning.message, "disk almost full");
+        assert_eq!(warning.logger.as_deref(), Some(module_path!()));
+        assert_eq!(
+            warning.data,
+            Some(serde_json::json!({"user": "ann", "free_mb": 12}))
+        );
+
+        let Ok(Notification::Server(ServerNotification::LogMessage(error))) = rx.try_recv() else {
+            panic!("expected a log message");
+        };
+        assert_eq!(error.level, LogLevel::Error);
+        assert_eq!(error.logger.as_deref(), Some("db"));
+        assert_eq!(error.data, None);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unattached_logger_drops_messages() {
+        let logger = ServerLogger::default();
+        assert!(logger.error("nowhere to go").await.is_ok());
+    }
+}
@@ -0,0 +1,288 @@
+//! Prometheus metrics for servers.
+//!
+//! A [`ServerMetrics`] installed on a server with
+//! [`ServerBuilder::metrics`](crate::ServerBuilder::metrics) counts requests
+//! by method, failures by error code, and the tool calls currently running.
+//! Hand the same instance to the HTTP server
+//! ([`StreamableHttpServerConfig::metrics`](crate::server::streamable_http_server::StreamableHttpServerConfig::metrics)
+//! or [`SseServerBuilder::metrics`](crate::server::transport::SseServerBuilder::metrics))
+//! to serve them at `/metrics` in the Prometheus text format, along with
+//! the sessions open on that server and the size of its event store.
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `mcp_requests_total` | counter | `method` |
+//! | `mcp_request_errors_total` | counter | `code` |
+//! | `mcp_request_duration_seconds` | summary (sum and count) | |
+//! | `mcp_tool_calls_in_flight` | gauge | |
+//! | `mcp_active_sessions` | gauge | |
+//! | `mcp_event_store_events` | gauge | |
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::metrics::ServerMetrics;
+//! use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
+//! use pmcp::Server;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let metrics = Arc::new(ServerMetrics::new());
+//! let server = Server::builder()
+//!     .name("search")
+//!     .version("1.0.0")
+//!     .metrics(metrics.clone())
+//!     .build()?;
+//!
+//! let config = StreamableHttpServerConfig {
+//!     metrics: Some(metrics),
+//!     ..Default::default()
+//! };
+//! let http = StreamableHttpServer::with_config(
+//!     "127.0.0.1:8080".parse().unwrap(),
+//!     Arc::new(server),
+//!     config,
+//! );
+//! http.start().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::shared::PerformanceMetrics;
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::JSONRPCResponse;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Path the metrics are served at.
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Content type of the Prometheus text exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+const REQUESTS: &str = "mcp_requests_total";
+const ERRORS: &str = "mcp_request_errors_total";
+const TOOL_CALLS_IN_FLIGHT: &str = "mcp_tool_calls_in_flight";
+const ACTIVE_SESSIONS: &str = "mcp_active_sessions";
+const EVENT_STORE_EVENTS: &str = "mcp_event_store_events";
+
+/// Metric families kept in [`PerformanceMetrics`], with their help text and
+/// type.
+const FAMILIES: &[(&str, &str, &str)] = &[
+    (REQUESTS, "Requests handled, by method.", "counter"),
+    (
+        ERRORS,
+        "Requests that failed, by JSON-RPC error code.",
+        "counter",
+    ),
+    (
+        TOOL_CALLS_IN_FLIGHT,
+        "Tool calls currently running.",
+        "gauge",
+    ),
+    (ACTIVE_SESSIONS, "Sessions currently open.", "gauge"),
+    (EVENT_STORE_EVENTS, "Events held for resumption.", "gauge"),
+];
+
+/// Request and session metrics of a server.
+///
+/// Totals are kept in the middleware's [`PerformanceMetrics`]; labelled
+/// series live in its custom metrics, keyed by their Prometheus series name.
+#[derive(Debug)]
+pub struct ServerMetrics {
+    metrics: Arc<PerformanceMetrics>,
+}
+
+impl Default for ServerMetrics {
+    fn default() -> Self {
+        Self::with_metrics(Arc::new(PerformanceMetrics::new()))
+    }
+}
+
+impl ServerMetrics {
+    /// Create empty metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record into existing performance metrics, e.g. those of a
+    /// middleware context.
+    pub fn with_metrics(metrics: Arc<PerformanceMetrics>) -> Self {
+        for gauge in [TOOL_CALLS_IN_FLIGHT, ACTIVE_SESSIONS] {
+            metrics.add(gauge, 0.0);
+        }
+        Self { metrics }
+    }
+
+    /// The underlying performance metrics.
+    pub fn performance(&self) -> &Arc<PerformanceMetrics> {
+        &self.metrics
+    }
+
+    /// Count a request to `method` that is starting; finish the returned
+    /// guard with its response.
+    pub(crate) fn start(&self, method: &'static str) -> InFlight<'_> {
+        let tool_call = method == "tools/call";
+        if tool_call {
+            self.metrics.add(TOOL_CALLS_IN_FLIGHT, 1.0);
+        }
+        InFlight {
+            metrics: self,
+            method,
+            tool_call,
+        }
+    }
+
+    /// Count a newly opened session.
+    pub fn session_opened(&self) {
+        self.metrics.add(ACTIVE_SESSIONS, 1.0);
+    }
+
+    /// Count a closed session.
+    pub fn session_closed(&self) {
+        self.metrics.add(ACTIVE_SESSIONS, -1.0);
+    }
+
+    /// Set the number of events held by the event store.
+    pub fn set_event_store_events(&self, events: usize) {
+        self.metrics
+            .record(EVENT_STORE_EVENTS.to_string(), events as f64);
+    }
+
+    /// Render the metrics in the Prometheus text format.
+    pub fn encode(&self) -> String {
+        let series = self.metrics.custom_metrics();
+        let mut out = String::new();
+        for (family, help, kind) in FAMILIES {
+            let mut samples = series
+                .iter()
+                .filter(|(name, _)| {
+                    name.strip_prefix(family)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('{'))
+                })
+                .peekable();
+            if samples.peek().is_none() {
+                continue;
+            }
+            let _ = writeln!(out, "# HELP {} {}", family, help);
+            let _ = writeln!(out, "# TYPE {} {}", family, kind);
+            for (name, value) in samples {
+                let _ = writeln!(out, "{} {}", name, value);
+            }
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP mcp_request_duration_seconds Time spent handling requests."
+        );
+        let _ = writeln!(out, "# TYPE mcp_request_duration_seconds summary");
+        let _ = writeln!(
+            out,
+            "mcp_request_duration_seconds_sum {}",
+            self.metrics.total_time().as_secs_f64()
+        );
+        let _ = writeln!(
+            out,
+            "mcp_request_duration_seconds_count {}",
+            self.metrics.request_count()
+        );
+        out
+    }
+}
+
+/// A request being counted; tool calls stay in flight until it is dropped.
+pub(crate) struct InFlight<'a> {
+    metrics: &'a ServerMetrics,
+    method: &'static str,
+    tool_call: bool,
+}
+
+impl InFlight<'_> {
+    /// Record the outcome of the request.
+    pub(crate) fn finish(self, response: &JSONRPCResponse, duration: Duration) {
+        let metrics = &self.metrics.metrics;
+        metrics.inc_requests();
+        metrics.add_time(duration);
+        metrics.add(&format!("{}{{method=\"{}\"}}", REQUESTS, self.method), 1.0);
+        if let ResponsePayload::Error(error) = &response.payload {
+            metrics.inc_errors();
+            metrics.add(&format!("{}{{code=\"{}\"}}", ERRORS, error.code), 1.0);
+        }
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        if self.tool_call {
+            self.metrics.metrics.add(TOOL_CALLS_IN_FLIGHT, -1.0);
+        }
+    }
+}
+
+/// Routes serving `metrics` at [`METRICS_PATH`], refreshing the event store
+/// size from `event_store` on each scrape.
+#[cfg(feature = "streamable-http")]
+pub(crate) fn metrics_routes(
+    metrics: Arc<ServerMetrics>,
+    event_store: Option<Arc<dyn crate::server::streamable_http_server::EventStore>>,
+) -> axum::Router {
+    use axum::http::header;
+
+    axum::Router::new().route(
+        METRICS_PATH,
+        axum::routing::get(move || async move {
+            if let Some(store) = &event_store {
+                match store.event_count().await {
+                    Ok(Some(events)) => metrics.set_event_store_events(events),
+                    Ok(None) => {},
+                    Err(e) => tracing::warn!("Failed to read event store size: {}", e),
+                }
+            }
+            ([(header::CONTENT_TYPE, CONTENT_TYPE)], metrics.encode())
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::jsonrpc::JSONRPCError;
+    use crate::types::RequestId;
+
+    #[test]
+    fn test_encode_reports_requests_errors_and_gauges() {
+        let metrics = ServerMetrics::new();
+
+        let call = metrics.start("tools/call");
+        assert!(metrics.encode().contains("mcp_tool_calls_in_flight 1\n"));
+        call.finish(
+            &JSONRPCResponse::error(
+                RequestId::from(1i64),
+                JSONRPCError {
+                    code: -32602,
+                    message: "bad".to_string(),
+                    data: None,
+                },
+            ),
+            Duration::from_millis(500),
+        );
+        metrics.start("tools/list").finish(
+            &JSONRPCResponse::success(RequestId::from(2i64), serde_json::json!({})),
+            Duration::from_millis(500),
+        );
+        metrics.session_opened();
+        metrics.set_event_store_events(3);
+
+        let text = metrics.encode();
+        assert!(text.contains("# TYPE mcp_requests_total counter\n"));
+        assert!(text.contains("mcp_requests_total{method=\"tools/call\"} 1\n"));
+        assert!(text.contains("mcp_requests_total{method=\"tools/list\"} 1\n"));
+        assert!(text.contains("mcp_request_errors_total{code=\"-32602\"} 1\n"));
+        assert!(text.contains("mcp_tool_calls_in_flight 0\n"));
+        assert!(text.contains("mcp_active_sessions 1\n"));
+        assert!(text.contains("mcp_event_store_events 3\n"));
+        assert!(text.contains("mcp_request_duration_seconds_sum 1\n"));
+        assert!(text.contains("mcp_request_duration_seconds_count 2\n"));
+    }
+}
@@ -3,14 +3,18 @@
 #[cfg(not(target_arch = "wasm32"))]
 use crate::error::{Error, Result};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::shared::{Protocol, ProtocolOptions, TransportMessage};
+use crate::shared::timing::{self, DispatchMetrics, Phase, RequestTimer, TimingSnapshot};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::shared::{OutgoingQueue, Protocol, ProtocolOptions, TransportMessage};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::types::{
-    CallToolRequest, CallToolResult, ClientCapabilities, ClientRequest, GetPromptRequest,
-    Implementation, InitializeResult, JSONRPCResponse, ListPromptsRequest, ListPromptsResult,
-    ListResourceTemplatesRequest, ListResourceTemplatesResult, ListResourcesRequest,
-    ListResourcesResult, ListToolsRequest, ListToolsResult, Notification, ProtocolVersion,
-    ReadResourceRequest, Request, RequestId, ServerCapabilities, ServerNotification,
+    CallToolRequest, CallToolResult, CancelledNotification, ClientCapabilities, ClientNotification,
+    ClientRequest, CompleteRequest, CompleteResult, CompletionReference, CompletionResult,
+    GetPromptRequest, Implementation, InitializeResult, JSONRPCResponse, ListPromptsRequest,
+    ListPromptsResult, ListResourceTemplatesRequest, ListResourceTemplatesResult,
+    ListResourcesRequest, ListResourcesResult, ListToolsRequest, ListToolsResult, Notification,
+    ProtocolVersion, ReadResourceRequest, Request, RequestId, ServerCapabilities,
+    ServerNotification, SubscribeRequest, UnsubscribeRequest,
 };
 #[cfg(not(target_arch = "wasm32"))]
 use async_trait::async_trait;
@@ -29,18 +33,36 @@ use tokio::sync::mpsc;
 // Core modules (currently native-only due to dependencies)
 #[cfg(not(target_arch = "wasm32"))]
 pub mod adapters;
+/// Authentication for outgoing HTTP API calls.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod api_auth;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod builder;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod core;
 
 // Native-only modules (require tokio, threading, etc.)
+/// Audit logging of client requests.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod audit;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod auth;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod batch;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod cancellation;
+/// Completion providers for `completion/complete` requests.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod completion;
+/// Composing servers from mounted sub-servers.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod compose;
+/// Builders for prompt results with embedded resources and images.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod prompt_builder;
+/// Prompts rendered from templates with `{{argument}}` placeholders.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod prompt_template;
 /// Simple prompt implementations with metadata support.
 #[cfg(not(target_arch = "wasm32"))]
 pub mod simple_prompt;
@@ -63,6 +85,10 @@ pub mod validation;
 #[cfg(feature = "schema-generation")]
 pub mod schema_utils;
 
+/// Build-time validation of tool registrations.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tool_validation;
+
 /// Standard error codes for validation with client elicitation support.
 #[cfg(not(target_arch = "wasm32"))]
 pub mod error_codes;
@@ -82,20 +108,81 @@ pub mod cancellation {
     #[derive(Debug, Clone, Default)]
     pub struct RequestHandlerExtra;
 }
+/// actix-web integration for the streamable HTTP server.
+#[cfg(all(not(target_arch = "wasm32"), feature = "actix"))]
+pub mod actix;
+/// Requests from the server to the connected client.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod client_requests;
+/// Schema-driven coercion of tool arguments.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod coercion;
+/// Concurrent request handling with per-tool caps.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod concurrency;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod dynamic;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod elicitation;
+#[cfg(all(not(target_arch = "wasm32"), feature = "graphql"))]
+pub mod graphql;
+/// Declarative HTTP tools with schema validation and retries.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod http_tool;
+/// Duplicate suppression for tool calls carrying idempotency keys.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod idempotency;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod input_validation;
+/// AWS Lambda adapter for API Gateway.
+#[cfg(all(not(target_arch = "wasm32"), feature = "lambda"))]
+pub mod lambda;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod logging;
+#[cfg(all(not(target_arch = "wasm32"), feature = "prometheus"))]
+pub mod metrics;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod notification_debouncer;
+#[cfg(all(not(target_arch = "wasm32"), feature = "openapi"))]
+pub mod openapi;
+/// DNS rebinding protection for the HTTP servers.
+#[cfg(all(not(target_arch = "wasm32"), feature = "streamable-http"))]
+pub mod origin;
+/// Progress notifications from long-running handlers.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod progress;
+/// Forwarding proxy with hooks for logging and fault injection.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod proxy;
+/// Per-principal rate limiting of client requests.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod rate_limit;
+/// Caching and conditional reads for resource handlers.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod resource_cache;
+/// Chunked reads of large resources.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod resource_chunks;
+/// Registry of parameterized resource templates.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod resource_templates;
 #[cfg(all(not(target_arch = "wasm32"), feature = "resource-watcher"))]
 pub mod resource_watcher;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod roots;
+/// Per-method hooks run around request handling.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod router;
+/// Pluggable session storage for the streamable HTTP server.
+#[cfg(all(not(target_arch = "wasm32"), feature = "streamable-http"))]
+pub mod session_backend;
 #[cfg(all(not(target_arch = "wasm32"), feature = "streamable-http"))]
 pub mod streamable_http_server;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod subscriptions;
+/// Runtime registration of tools.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tool_registry;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod transport;
 
@@ -109,6 +196,9 @@ pub mod wasm_server;
 #[cfg(all(test, target_arch = "wasm32"))]
 mod wasm_server_tests;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use logging::init_stdio_logging;
+
 // WASM-compatible protocol handler trait
 #[cfg(target_arch = "wasm32")]
 pub use wasi_protocol::ProtocolHandler;
@@ -168,6 +258,15 @@ pub trait PromptHandler: Send + Sync {
     fn metadata(&self) -> Option<crate::types::PromptInfo> {
         None
     }
+
+    /// Validate the prompt configuration.
+    ///
+    /// Called once when the server is built so that malformed prompts (for
+    /// example templates that fail to compile) are reported at startup rather
+    /// than on the first `prompts/get` request.
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Handler for resource access.
@@ -181,6 +280,18 @@ pub trait ResourceHandler: Send + Sync {
         extra: cancellation::RequestHandlerExtra,
     ) -> Result<crate::types::ReadResourceResult>;
 
+    /// Open the resource at `uri` for streaming, if it is served that way.
+    ///
+    /// A returned stream is used instead of [`read`](Self::read), and chunked
+    /// reads take only their range from it; see [`resource_chunks`].
+    async fn open_stream(
+        &self,
+        _uri: &str,
+        _extra: cancellation::RequestHandlerExtra,
+    ) -> Result<Option<resource_chunks::ResourceStream>> {
+        Ok(None)
+    }
+
     /// List available resources.
     async fn list(
         &self,
@@ -235,9 +346,12 @@ pub trait SamplingHandler: Send + Sync {
 pub struct Server {
     info: Implementation,
     capabilities: ServerCapabilities,
-    tools: HashMap<String, Arc<dyn ToolHandler>>,
+    tools: tool_registry::ToolRegistry,
     prompts: HashMap<String, Arc<dyn PromptHandler>>,
     resources: Option<Arc<dyn ResourceHandler>>,
+    /// Registered resource templates
+    resource_templates: Arc<resource_templates::ResourceTemplateRegistry>,
+    completion: Option<Arc<dyn completion::CompletionHandler>>,
     sampling: Option<Arc<dyn SamplingHandler>>,
     client_capabilities: Arc<RwLock<Option<ClientCapabilities>>>,
     initialized: Arc<RwLock<bool>>,
@@ -255,6 +369,77 @@ pub struct Server {
     auth_provider: Option<Arc<dyn auth::AuthProvider>>,
     /// Tool authorizer for fine-grained access control
     tool_authorizer: Option<Arc<dyn auth::ToolAuthorizer>>,
+    /// Aggregated per-phase request timings
+    dispatch_metrics: Arc<DispatchMetrics>,
+    /// Whether results carry a `_meta` timing breakdown
+    timing_meta: bool,
+    /// Limits on the shape of incoming request params
+    request_guard: Option<crate::utils::validation::RequestGuard>,
+    /// Whether tool results are checked against their output schema
+    validate_tool_output: bool,
+    /// Constraints on tool arguments, by tool name
+    tool_rules: HashMap<String, input_validation::ArgumentRules>,
+    /// How tool arguments are rewritten against their input schema
+    argument_coercion: Option<coercion::ArgumentCoercion>,
+    /// Protocol versions offered to clients
+    version_negotiator: crate::shared::VersionNegotiator,
+    /// Results of recent tool calls by idempotency key
+    idempotency: Option<idempotency::IdempotencyCache>,
+    /// Per-principal request limits
+    rate_limiter: Option<rate_limit::PrincipalRateLimiter>,
+    /// Where request/response pairs are recorded
+    audit_logger: Option<Arc<dyn audit::AuditLogger>>,
+    /// Maximum serialized size of audited payloads
+    audit_payload_limit: usize,
+    /// Prometheus request metrics
+    #[cfg(feature = "prometheus")]
+    metrics: Option<Arc<metrics::ServerMetrics>>,
+    /// Log messages forwarded to the client
+    logger: logging::ServerLogger,
+    /// Hooks run around individual methods
+    router: router::MethodRouter,
+    /// Requests back to the connected client, e.g. sampling
+    client_requests: client_requests::ClientRequester,
+    /// How long the client may stay silent before the connection is dropped
+    keep_alive: Option<crate::shared::KeepAliveConfig>,
+    /// Called when the client stayed silent past the keep-alive
+    on_keep_alive_timeout: Option<Arc<dyn Fn() + Send + Sync>>,
+    /// How many requests run at once
+    concurrency: concurrency::ConcurrencyConfig,
+    /// Turns for tools with a concurrency cap
+    tool_permits: concurrency::ToolPermits,
+    /// Transport the server runs on, recorded on request spans
+    #[cfg(feature = "opentelemetry")]
+    transport_type: std::sync::OnceLock<&'static str>,
+    /// Clients of the WebSocket server passed to [`Server::run_websocket`]
+    #[cfg(feature = "websocket")]
+    websocket_clients: std::sync::OnceLock<transport::ConnectedClients>,
+    /// File-system watcher for subscribed `file://` resources
+    #[cfg(feature = "resource-watcher")]
+    resource_watcher: Option<resource_watcher::ResourceWatcher>,
+    /// Change events from `resource_watcher`, taken when the server runs
+    #[cfg(feature = "resource-watcher")]
+    resource_events: Option<mpsc::Receiver<ServerNotification>>,
+}
+
+/// Subscriber ID for the single client connected to a running server.
+#[cfg(not(target_arch = "wasm32"))]
+const CLIENT_SUBSCRIBER: &str = "client";
+
+#[cfg(feature = "websocket")]
+tokio::task_local! {
+    /// Subscriber ID of the WebSocket client whose request is being handled
+    static SUBSCRIBER: String;
+}
+
+/// Subscriber ID of the client whose request is being handled.
+#[cfg(not(target_arch = "wasm32"))]
+fn current_subscriber() -> String {
+    #[cfg(feature = "websocket")]
+    if let Ok(subscriber) = SUBSCRIBER.try_with(Clone::clone) {
+        return subscriber;
+    }
+    CLIENT_SUBSCRIBER.to_string()
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -263,7 +448,7 @@ impl std::fmt::Debug for Server {
         f.debug_struct("Server")
             .field("info", &self.info)
             .field("capabilities", &self.capabilities)
-            .field("tools", &self.tools.keys().collect::<Vec<_>>())
+            .field("tools", &self.tools.names())
             .field("prompts", &self.prompts.keys().collect::<Vec<_>>())
             .field("resources", &self.resources.is_some())
             .field("sampling", &self.sampling.is_some())
@@ -276,7 +461,15 @@ impl std::fmt::Debug for Server {
 impl Server {
     /// Check if a tool exists
     pub fn has_tool(&self, name: &str) -> bool {
-        self.tools.contains_key(name)
+        self.tools.contains(name)
+    }
+
+    /// The server's tool registry.
+    ///
+    /// Clone the registry before running the server to register, unregister,
+    /// or replace tools while clients are connected. See [`tool_registry`].
+    pub fn tools(&self) -> &tool_registry::ToolRegistry {
+        &self.tools
     }
 
     /// Check if a prompt exists
@@ -320,6 +513,85 @@ impl Server {
         }
     }
 
+    /// Send a notification to every connected client.
+    ///
+    /// When serving several clients with [`run_websocket`](Self::run_websocket)
+    /// each of them receives it; otherwise it goes to the single connected
+    /// client, as with [`send_notification`](Self::send_notification).
+    ///
+    /// Returns the number of clients the notification was sent to.
+    pub async fn broadcast_notification(&self, notification: ServerNotification) -> usize {
+        #[cfg(feature = "websocket")]
+        if let Some(clients) = self.websocket_clients.get() {
+            return clients
+                .broadcast(TransportMessage::Notification(Notification::Server(
+                    notification,
+                )))
+                .await;
+        }
+        match &self.notification_tx {
+            Some(tx) if tx.send(Notification::Server(notification)).await.is_ok() => 1,
+            _ => 0,
+        }
+    }
+
+    /// Send a notification to one client of a multi-client WebSocket server.
+    ///
+    /// Client IDs are assigned by the
+    /// [`EnhancedWebSocketServer`](transport::EnhancedWebSocketServer) when
+    /// clients connect.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server is not running with
+    /// [`run_websocket`](Self::run_websocket) or the client is not connected.
+    #[cfg(feature = "websocket")]
+    pub async fn notify_client(
+        &self,
+        client_id: transport::ClientId,
+        notification: ServerNotification,
+    ) -> Result<()> {
+        let clients = self
+            .websocket_clients
+            .get()
+            .ok_or_else(|| Error::invalid_state("Server is not serving WebSocket clients"))?;
+        clients
+            .send_to(
+                client_id,
+                TransportMessage::Notification(Notification::Server(notification)),
+            )
+            .await
+    }
+
+    /// Logger sending `notifications/message` to the client.
+    ///
+    /// Messages below the level the client set with `logging/setLevel` are
+    /// dropped; see [`logging`]. The logger can be taken before the server
+    /// runs and used from anywhere; handlers also find it in their
+    /// [`RequestHandlerExtra`](cancellation::RequestHandlerExtra).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::Server;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder()
+    ///     .name("indexer")
+    ///     .version("1.0.0")
+    ///     .build()?;
+    ///
+    /// let logger = server.logger().named("watcher");
+    /// tokio::spawn(async move {
+    ///     let _ = logger.warning("Index is stale").await;
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn logger(&self) -> logging::ServerLogger {
+        self.logger.clone()
+    }
+
     /// Get client capabilities.
     ///
     /// Returns the capabilities that the client declared during initialization.
@@ -384,6 +656,31 @@ impl Server {
     pub async fn is_initialized(&self) -> bool {
         *self.initialized.read().await
     }
+
+    /// Aggregated timing breakdown of the requests handled so far.
+    ///
+    /// Splits latency into parsing, SDK middleware, handler, and
+    /// serialization time so slow handlers can be told apart from SDK
+    /// overhead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::Server;
+    ///
+    /// # fn example(server: &Server) {
+    /// let snapshot = server.timing_snapshot();
+    /// println!(
+    ///     "{} requests, mean handler time {:?}",
+    ///     snapshot.requests,
+    ///     snapshot.handler.mean(snapshot.requests)
+    /// );
+    /// # }
+    /// ```
+    pub fn timing_snapshot(&self) -> TimingSnapshot {
+        self.dispatch_metrics.snapshot()
+    }
+
     /// Create a new server builder.
     ///
     /// Returns a `ServerBuilder` for configuring and constructing a new MCP server.
@@ -465,6 +762,24 @@ impl Server {
         self.run(transport).await
     }
 
+    /// Run the server over HTTP with Server-Sent Events.
+    ///
+    /// Serves clients that speak the classic MCP SSE transport (`GET` event
+    /// stream plus `POST` message endpoint). The transport is bound first if
+    /// it is not already. See [`transport::SseServerTransport`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be bound or the server
+    /// encounters an unrecoverable error.
+    #[cfg(feature = "streamable-http")]
+    pub async fn run_sse(self, mut transport: transport::SseServerTransport) -> Result<()> {
+        if !transport.is_bound() {
+            transport.bind().await?;
+        }
+        self.run(transport).await
+    }
+
     /// Run the server with a custom transport.
     ///
     /// Starts the server using a custom transport implementation.
@@ -513,53 +828,284 @@ impl Server {
     /// - Communication with the client fails
     /// - The server encounters an unrecoverable error
     pub async fn run<T: crate::shared::Transport + 'static>(mut self, transport: T) -> Result<()> {
+        #[cfg(feature = "opentelemetry")]
+        let _ = self.transport_type.set(transport.transport_type());
         let (notification_tx, notification_rx) = mpsc::channel(100);
-        self.notification_tx = Some(notification_tx);
+        self.attach_notifications(notification_tx).await?;
 
         let server = Arc::new(self);
         let transport = Arc::new(RwLock::new(transport));
         let protocol = Arc::new(RwLock::new(Protocol::new(ProtocolOptions::default())));
+        let outgoing = Arc::new(OutgoingQueue::new());
 
-        Self::spawn_notification_handler(transport.clone(), notification_rx);
-        Self::spawn_message_handler(server.clone(), transport.clone(), protocol);
+        Self::spawn_notification_handler(outgoing.clone(), notification_rx);
+        Self::spawn_message_handler(server.clone(), transport.clone(), protocol, outgoing);
 
         // Keep the main task alive
         Self::run_main_loop().await
     }
 
+    /// Serve every client of a multi-client WebSocket server.
+    ///
+    /// Each request is answered on the connection it arrived on, and
+    /// resource subscriptions are tracked per client, so
+    /// [`notify_resource_updated`](Self::notify_resource_updated) reaches
+    /// only the subscribers. Keep a clone of the `Arc` to push notifications
+    /// with [`broadcast_notification`](Self::broadcast_notification) and
+    /// [`notify_client`](Self::notify_client) while the server runs.
+    ///
+    /// All clients share this server's state, including the capabilities
+    /// declared by the latest `initialize`. Cancellation notifications are
+    /// ignored, since request IDs are only unique per client.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::server::transport::{EnhancedWebSocketConfig, EnhancedWebSocketServer};
+    /// use pmcp::{Server, ServerNotification};
+    /// use std::sync::Arc;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let server = Arc::new(
+    ///     Server::builder()
+    ///         .name("shared-server")
+    ///         .version("1.0.0")
+    ///         .build()?,
+    /// );
+    /// let transport = EnhancedWebSocketServer::new(EnhancedWebSocketConfig::default());
+    /// tokio::spawn(server.clone().run_websocket(transport));
+    ///
+    /// // Later, tell every connected client the tool list changed
+    /// server
+    ///     .broadcast_notification(ServerNotification::ToolsChanged)
+    ///     .await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WebSocket server cannot bind its address, or
+    /// if this server is already serving WebSocket clients.
+    #[cfg(feature = "websocket")]
+    pub async fn run_websocket(
+        self: Arc<Self>,
+        mut transport: transport::EnhancedWebSocketServer,
+    ) -> Result<()> {
+        if !transport.is_listening() {
+            transport.start().await?;
+        }
+        let clients = transport.connected_clients();
+        #[cfg(feature = "opentelemetry")]
+        let _ = self.transport_type.set("websocket");
+        if self.websocket_clients.set(clients.clone()).is_err() {
+            return Err(Error::invalid_state(
+                "Server is already serving WebSocket clients",
+            ));
+        }
+
+        // Tool list changes concern every client
+        let (notification_tx, mut notification_rx) = mpsc::channel(100);
+        self.tools.attach(notification_tx);
+        tokio::spawn({
+            let clients = clients.clone();
+            async move {
+                while let Some(notification) = notification_rx.recv().await {
+                    clients
+                        .broadcast(TransportMessage::Notification(notification))
+                        .await;
+                }
+            }
+        });
+
+        loop {
+            let (client_id, message) = transport.receive_from_any().await?;
+            match message {
+                TransportMessage::Request { id, request } => {
+                    let server = self.clone();
+                    let clients = clients.clone();
+                    tokio::spawn(async move {
+                        let handling = SUBSCRIBER
+                            .scope(client_id.to_string(), server.handle_request(id, request));
+                        let response = cancellation::SESSION_ID
+                            .scope(client_id.to_string(), handling)
+                            .await;
+                        if let Err(e) = clients
+                            .send_to(client_id, TransportMessage::Response(response))
+                            .await
+                        {
+                            Self::log_debug(&format!(
+                                "Client {} left before its answer: {}",
+                                client_id, e
+                            ))
+                            .await;
+                        }
+                    });
+                },
+                _ => {
+                    Self::log_debug(&format!(
+                        "Ignoring non-request message from client {}",
+                        client_id
+                    ))
+                    .await;
+                },
+            }
+        }
+    }
+
+    /// Route server-initiated notifications through `notification_tx`.
+    ///
+    /// Starts the resource watcher, if configured, so file changes reach
+    /// clients subscribed to the affected resources.
+    async fn attach_notifications(
+        &mut self,
+        notification_tx: mpsc::Sender<Notification>,
+    ) -> Result<()> {
+        self.tools.attach(notification_tx.clone());
+        self.logger.attach(notification_tx.clone());
+        self.subscription_manager
+            .write()
+            .await
+            .set_notification_sender({
+                let tx = notification_tx.clone();
+                move |notification| {
+                    let _ = tx.try_send(Notification::Server(notification));
+                }
+            });
+        self.notification_tx = Some(notification_tx);
+
+        #[cfg(feature = "resource-watcher")]
+        if let (Some(watcher), Some(mut events)) =
+            (&mut self.resource_watcher, self.resource_events.take())
+        {
+            watcher.start().await?;
+            let subscriptions = self.subscription_manager.clone();
+            tokio::spawn(async move {
+                while let Some(event) = events.recv().await {
+                    if let ServerNotification::ResourceUpdated(params) = event {
+                        let _ = subscriptions
+                            .read()
+                            .await
+                            .notify_resource_updated(params.uri)
+                            .await;
+                    }
+                }
+            });
+        }
+        Ok(())
+    }
+
     /// Spawn task to handle outgoing notifications.
     fn spawn_notification_handler(
-        transport: Arc<RwLock<impl crate::shared::Transport + 'static>>,
+        outgoing: Arc<OutgoingQueue>,
         mut notification_rx: mpsc::Receiver<Notification>,
     ) {
         tokio::spawn(async move {
             while let Some(notification) = notification_rx.recv().await {
-                if let Err(e) =
-                    Self::send_notification_through_transport(&transport, notification).await
-                {
-                    Self::log_error(&format!("Failed to send notification: {}", e)).await;
-                }
+                outgoing.push(None, TransportMessage::Notification(notification));
             }
         });
     }
 
     /// Spawn task to handle incoming messages.
+    ///
+    /// Requests run in tasks of their own, by default one at a time: while a
+    /// handler runs, the loop sends what it queues instead of reading the
+    /// transport. Only while a handler waits on a request to the client, such
+    /// as sampling, does the loop read again so the client's answer can
+    /// arrive. With a [`concurrency`] limit above one, the loop keeps reading
+    /// until that many requests run, interrupting the read whenever there is
+    /// something to send. An idle loop also interrupts its read to send, so
+    /// notifications and requests made outside handlers go out right away.
+    /// The requests of a JSON-RPC batch run concurrently, and their replies
+    /// go out together as one batch.
     fn spawn_message_handler(
         server: Arc<Self>,
         transport: Arc<RwLock<impl crate::shared::Transport + 'static>>,
-        _protocol: Arc<RwLock<Protocol>>,
+        protocol: Arc<RwLock<Protocol>>,
+        outgoing: Arc<OutgoingQueue>,
     ) {
+        server.client_requests.attach(outgoing.clone());
         tokio::spawn(async move {
+            let mut handlers = tokio::task::JoinSet::new();
+            let mut batch_replies = Vec::new();
+            let limit = server.concurrency.max_in_flight();
             loop {
-                let message = match Self::receive_message_from_transport(&transport).await {
-                    Ok(msg) => msg,
+                while let Some(joined) = handlers.try_join_next() {
+                    batch_replies.extend(joined.ok().flatten());
+                }
+                if let Err(e) =
+                    Self::flush_outgoing(&transport, &outgoing, &mut batch_replies).await
+                {
+                    Self::log_error(&format!("Transport send error: {}", e)).await;
+                    break;
+                }
+
+                if handlers.len() >= limit && !server.client_requests.has_pending() {
+                    tokio::select! {
+                        joined = handlers.join_next() => {
+                            batch_replies.extend(joined.and_then(|joined| joined.ok()).flatten());
+                        },
+                        () = outgoing.ready() => {},
+                    }
+                    continue;
+                }
+
+                let timer = RequestTimer::new();
+                let receive = timer.scope(server.receive_unless_silent(&transport));
+                let received = if !handlers.is_empty() && !server.concurrency.is_concurrent() {
+                    receive.await
+                } else {
+                    tokio::select! {
+                        received = receive => received,
+                        joined = handlers.join_next(), if !handlers.is_empty() => {
+                            batch_replies.extend(joined.and_then(|joined| joined.ok()).flatten());
+                            continue;
+                        },
+                        () = outgoing.ready() => continue,
+                    }
+                };
+                let mut messages = match received {
+                    Ok(Some(messages)) => messages,
+                    Ok(None) => {
+                        Self::log_warning("Client went silent; closing the connection").await;
+                        if let Err(e) = transport.write().await.close().await {
+                            Self::log_debug(&format!("Transport close error: {}", e)).await;
+                        }
+                        if let Some(callback) = &server.on_keep_alive_timeout {
+                            callback();
+                        }
+                        break;
+                    },
                     Err(e) => {
                         Self::log_error(&format!("Transport receive error: {}", e)).await;
                         break;
                     },
                 };
 
-                if let Err(e) = Self::handle_transport_message(&server, &transport, message).await {
+                let handled = if messages.len() == 1 {
+                    let message = messages.remove(0);
+                    Self::handle_transport_message(
+                        &server,
+                        &protocol,
+                        &outgoing,
+                        &mut handlers,
+                        timer,
+                        message,
+                    )
+                    .await
+                } else {
+                    Self::handle_transport_batch(
+                        &server,
+                        &protocol,
+                        &outgoing,
+                        &mut handlers,
+                        timer,
+                        messages,
+                    )
+                    .await
+                };
+                if let Err(e) = handled {
                     Self::log_error(&format!("Message handling error: {}", e)).await;
                     break;
                 }
@@ -567,35 +1113,162 @@ impl Server {
         });
     }
 
-    /// Send a notification through the transport.
-    async fn send_notification_through_transport(
+    /// Write queued messages to the transport, highest priority first.
+    ///
+    /// Responses go out ahead of notifications, so a burst of notifications
+    /// cannot delay replies on a slow transport. Replies to batches go first
+    /// of all.
+    async fn flush_outgoing(
         transport: &Arc<RwLock<impl crate::shared::Transport>>,
-        notification: Notification,
+        outgoing: &OutgoingQueue,
+        batch_replies: &mut Vec<Vec<TransportMessage>>,
     ) -> Result<()> {
         let mut t = transport.write().await;
-        t.send(TransportMessage::Notification(notification)).await
+        for replies in batch_replies.drain(..) {
+            t.send_batch(replies).await?;
+        }
+        while let Some((_, message)) = outgoing.pop() {
+            t.send(message).await?;
+        }
+        Ok(())
     }
 
-    /// Receive a message from the transport.
+    /// Receive the messages of the next frame from the transport.
     async fn receive_message_from_transport(
         transport: &Arc<RwLock<impl crate::shared::Transport>>,
-    ) -> Result<TransportMessage> {
+    ) -> Result<Vec<TransportMessage>> {
         let mut t = transport.write().await;
-        t.receive().await
+        t.receive_batch().await
+    }
+
+    /// Receive the messages of the next frame, or `None` if the client stays
+    /// silent past the keep-alive.
+    ///
+    /// The pending receive is only dropped once the connection is given up,
+    /// so a message arriving mid-read is never lost.
+    async fn receive_unless_silent(
+        &self,
+        transport: &Arc<RwLock<impl crate::shared::Transport>>,
+    ) -> Result<Option<Vec<TransportMessage>>> {
+        let receive = Self::receive_message_from_transport(transport);
+        let Some(config) = self.keep_alive else {
+            return receive.await.map(Some);
+        };
+        tokio::pin!(receive);
+
+        let window = config.interval() + config.timeout();
+        let mut silent = 0;
+        loop {
+            tokio::select! {
+                message = &mut receive => return message.map(Some),
+                () = tokio::time::sleep(window) => {
+                    silent += 1;
+                    if silent >= config.failure_threshold() {
+                        return Ok(None);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Handle the messages of a JSON-RPC batch.
+    ///
+    /// Its requests run concurrently in one task, which replies with a batch
+    /// of responses in request order. Responses and notifications in the
+    /// batch are handled as if they had arrived alone.
+    async fn handle_transport_batch(
+        server: &Arc<Self>,
+        protocol: &Arc<RwLock<Protocol>>,
+        outgoing: &Arc<OutgoingQueue>,
+        handlers: &mut tokio::task::JoinSet<Option<Vec<TransportMessage>>>,
+        timer: RequestTimer,
+        messages: Vec<TransportMessage>,
+    ) -> Result<()> {
+        let mut requests = Vec::with_capacity(messages.len());
+        for message in messages {
+            let TransportMessage::Request { id, request } = message else {
+                Self::handle_transport_message(
+                    server,
+                    protocol,
+                    outgoing,
+                    handlers,
+                    timer.clone(),
+                    message,
+                )
+                .await?;
+                continue;
+            };
+            let rejection = protocol.write().await.observe_peer_request(&id).err();
+            if let Some(e) = &rejection {
+                Self::log_warning(&e.to_string()).await;
+            }
+            requests.push((id, request, rejection));
+        }
+        if requests.is_empty() {
+            return Ok(());
+        }
+
+        let server = server.clone();
+        handlers.spawn(async move {
+            let server = &server;
+            let replies = requests
+                .into_iter()
+                .map(|(id, request, rejection)| async move {
+                    match rejection {
+                        Some(e) => JSONRPCResponse::error(id, e.into()),
+                        None => server.handle_request(id, request).await,
+                    }
+                });
+            let responses = timer.scope(futures::future::join_all(replies)).await;
+            Some(
+                responses
+                    .into_iter()
+                    .map(TransportMessage::Response)
+                    .collect(),
+            )
+        });
+        Ok(())
     }
 
     /// Handle a transport message.
     async fn handle_transport_message(
         server: &Arc<Self>,
-        transport: &Arc<RwLock<impl crate::shared::Transport>>,
+        protocol: &Arc<RwLock<Protocol>>,
+        outgoing: &Arc<OutgoingQueue>,
+        handlers: &mut tokio::task::JoinSet<Option<Vec<TransportMessage>>>,
+        timer: RequestTimer,
         message: TransportMessage,
     ) -> Result<()> {
         match message {
             TransportMessage::Request { id, request } => {
-                Self::handle_request_message(server, transport, id, request).await
+                if let Err(e) = protocol.write().await.observe_peer_request(&id) {
+                    Self::log_warning(&e.to_string()).await;
+                    let response = JSONRPCResponse::error(id, e.into());
+                    outgoing.push(None, TransportMessage::Response(response));
+                    return Ok(());
+                }
+                let server = server.clone();
+                let outgoing = outgoing.clone();
+                handlers.spawn(async move {
+                    let response = timer.scope(server.handle_request(id, request)).await;
+                    outgoing.push(None, TransportMessage::Response(response));
+                    None
+                });
+                Ok(())
             },
-            TransportMessage::Response(_) => {
-                Self::log_warning("Server received unexpected response message").await;
+            TransportMessage::Response(response) => {
+                if !server.client_requests.resolve(response) {
+                    Self::log_warning("Server received unexpected response message").await;
+                }
+                Ok(())
+            },
+            TransportMessage::Notification(
+                Notification::Cancelled(cancelled)
+                | Notification::Client(ClientNotification::Cancelled(cancelled)),
+            ) => {
+                if !server.handle_cancelled(&cancelled).await {
+                    Self::log_debug("Cancelled request is no longer in flight").await;
+                }
                 Ok(())
             },
             TransportMessage::Notification(_) => {
@@ -605,18 +1278,6 @@ impl Server {
         }
     }
 
-    /// Handle a request message.
-    async fn handle_request_message(
-        server: &Arc<Self>,
-        transport: &Arc<RwLock<impl crate::shared::Transport>>,
-        id: RequestId,
-        request: Request,
-    ) -> Result<()> {
-        let response = server.handle_request(id, request).await;
-        let mut t = transport.write().await;
-        t.send(TransportMessage::Response(response)).await
-    }
-
     /// Log an error message.
     async fn log_error(message: &str) {
         crate::log(crate::types::protocol::LogLevel::Error, message, None).await;
@@ -640,6 +1301,106 @@ impl Server {
     }
 
     async fn handle_request(&self, id: RequestId, request: Request) -> JSONRPCResponse {
+        // Each request gets its own timer; parse time recorded by the caller
+        // is claimed by the first request dispatched from that message.
+        let timer = RequestTimer::new();
+        if let Some(outer) = RequestTimer::current() {
+            timer.record(Phase::Parse, outer.take(Phase::Parse));
+        }
+
+        // Handlers look up this token, so a `notifications/cancelled` for
+        // the request reaches them while it runs
+        let cancellation_key = cancellation::current_request_key(&id);
+        self.cancellation_manager
+            .create_token(cancellation_key.clone())
+            .await;
+
+        let audited = self.audit_logger.as_ref().map(|_| {
+            (
+                chrono::Utc::now(),
+                id.to_string(),
+                serde_json::to_value(&request),
+            )
+        });
+        #[cfg(feature = "opentelemetry")]
+        let span = crate::shared::telemetry::RequestSpan::start(
+            crate::shared::telemetry::Side::Server,
+            crate::shared::request_method(&request),
+            &id,
+            self.transport_type.get().copied().unwrap_or("unknown"),
+            &crate::shared::telemetry::request_context(&request),
+        );
+        #[cfg(feature = "prometheus")]
+        let in_flight = self
+            .metrics
+            .as_ref()
+            .map(|metrics| metrics.start(crate::shared::request_method(&request)));
+        let started = std::time::Instant::now();
+        let dispatch = timer.scope(self.dispatch_request(id, request));
+        #[cfg(feature = "opentelemetry")]
+        let dispatch =
+            opentelemetry::context::FutureExt::with_context(dispatch, span.context().clone());
+        let mut response = dispatch.await;
+        #[cfg(feature = "opentelemetry")]
+        span.finish(Ok(&response));
+        #[cfg(feature = "prometheus")]
+        if let Some(in_flight) = in_flight {
+            in_flight.finish(&response, started.elapsed());
+        }
+        if let (Some(logger), Some((timestamp, request_id, request))) =
+            (&self.audit_logger, audited)
+        {
+            let entry = self
+                .audit_entry(timestamp, request_id, request, &response, started.elapsed())
+                .await;
+            if let Err(e) = logger.record(&entry).await {
+                tracing::error!("Failed to record audit entry: {}", e);
+            }
+        }
+        self.cancellation_manager
+            .remove_token(&cancellation_key)
+            .await;
+        let timings = timer.finish(started.elapsed());
+        self.dispatch_metrics.record(&timings);
+
+        if self.timing_meta {
+            if let crate::types::jsonrpc::ResponsePayload::Result(result) = &mut response.payload {
+                timings.attach_to(result);
+            }
+        }
+        response
+    }
+
+    async fn dispatch_request(&self, id: RequestId, mut request: Request) -> JSONRPCResponse {
+        if let (Some(guard), Request::Client(client_request)) = (&self.request_guard, &request) {
+            if let Err(e) = Self::check_params(guard, client_request) {
+                return JSONRPCResponse::error(id, e.into());
+            }
+        }
+        if let (Some(limiter), Request::Client(client_request)) = (&self.rate_limiter, &request) {
+            if let Err(e) = self.check_rate_limit(limiter, client_request).await {
+                return JSONRPCResponse::error(id, e.into());
+            }
+        }
+        if let (Some(coercion), Request::Client(client_request)) =
+            (&self.argument_coercion, &mut request)
+        {
+            if let ClientRequest::CallTool(call) = client_request.as_mut() {
+                if let Some(info) = self.tools.get(&call.name).and_then(|h| h.metadata()) {
+                    coercion.apply(&info.input_schema, &mut call.arguments);
+                }
+            }
+        }
+        if let Request::Client(client_request) = &request {
+            if let ClientRequest::CallTool(call) = client_request.as_ref() {
+                if let Some(rules) = self.tool_rules.get(&call.name) {
+                    if let Err(e) = rules.validate(&call.name, &call.arguments) {
+                        return JSONRPCResponse::error(id, e.into());
+                    }
+                }
+            }
+        }
+
         match request {
             Request::Client(ref boxed_req)
                 if matches!(**boxed_req, ClientRequest::Initialize(_)) =>
@@ -649,10 +1410,16 @@ impl Server {
                 };
                 // Store client capabilities
                 *self.client_capabilities.write().await = Some(init_req.capabilities.clone());
+                let requester = self.current_client_requester();
+                requester.set_client_capabilities(init_req.capabilities.clone());
+                let version = self
+                    .version_negotiator
+                    .respond_to(&init_req.protocol_version);
+                requester.set_protocol_version(version.clone());
                 *self.initialized.write().await = true;
 
                 let result = InitializeResult {
-                    protocol_version: ProtocolVersion("2024-11-05".to_string()),
+                    protocol_version: ProtocolVersion(version.as_str().to_string()),
                     capabilities: self.capabilities.clone(),
                     server_info: self.info.clone(),
                     instructions: None,
@@ -661,7 +1428,7 @@ impl Server {
                     jsonrpc: "2.0".to_string(),
                     id: id.clone(),
                     payload: crate::types::jsonrpc::ResponsePayload::Result(
-                        serde_json::to_value(result).unwrap(),
+                        timing::to_value(result).unwrap(),
                     ),
                 }
             },
@@ -680,28 +1447,165 @@ impl Server {
         }
     }
 
+    /// Take a token for the request's caller from the limiter.
+    async fn check_rate_limit(
+        &self,
+        limiter: &rate_limit::PrincipalRateLimiter,
+        request: &ClientRequest,
+    ) -> Result<()> {
+        let Some(group) = rate_limit::MethodGroup::of(request) else {
+            return Ok(());
+        };
+        limiter.check(&self.current_principal().await?, group)
+    }
+
+    /// Who sent the request being handled: the subject authenticated from
+    /// its `Authorization` header, else the client's address or connection.
+    ///
+    /// Requests without credentials are not given the provider's subject,
+    /// so placeholders such as [`NoOpAuthProvider`](auth::NoOpAuthProvider)'s
+    /// don't lump all clients together.
+    async fn current_principal(&self) -> Result<String> {
+        let subject = match (&self.auth_provider, auth::current_authorization()) {
+            (Some(provider), Some(authorization)) => provider
+                .validate_request(Some(&authorization))
+                .await?
+                .map(|ctx| ctx.subject),
+            _ => None,
+        };
+        Ok(subject
+            .or_else(|| rate_limit::current_client_addr().map(|addr| addr.to_string()))
+            .unwrap_or_else(current_subscriber))
+    }
+
+    /// Describe a handled request for the audit log.
+    async fn audit_entry(
+        &self,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        request_id: String,
+        request: serde_json::Result<Value>,
+        response: &JSONRPCResponse,
+        duration: std::time::Duration,
+    ) -> audit::AuditEntry {
+        let limit = self.audit_payload_limit;
+        let mut request = request.unwrap_or_default();
+        let method = request
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let params = request
+            .get_mut("params")
+            .map(|params| audit::truncate_payload(params.take(), limit));
+        let (outcome, result) = match &response.payload {
+            crate::types::jsonrpc::ResponsePayload::Result(result) => (
+                audit::AuditOutcome::Success,
+                Some(audit::truncate_payload(result.clone(), limit)),
+            ),
+            crate::types::jsonrpc::ResponsePayload::Error(error) => (
+                audit::AuditOutcome::Error {
+                    code: error.code,
+                    message: error.message.clone(),
+                },
+                None,
+            ),
+        };
+        audit::AuditEntry {
+            timestamp,
+            request_id,
+            method,
+            principal: self
+                .current_principal()
+                .await
+                .unwrap_or_else(|_| "unauthenticated".to_string()),
+            duration_ms: audit::AuditEntry::duration_ms(duration),
+            outcome,
+            params,
+            result,
+        }
+    }
+
+    /// Check a request's params against the installed guard.
+    fn check_params(
+        guard: &crate::utils::validation::RequestGuard,
+        request: &ClientRequest,
+    ) -> Result<()> {
+        let value = serde_json::to_value(request)?;
+        match value.get("params") {
+            Some(params) => guard.check(params),
+            None => Ok(()),
+        }
+    }
+
     async fn handle_client_request(
         &self,
         id: RequestId,
         request: ClientRequest,
     ) -> JSONRPCResponse {
-        let result = self.process_client_request(id.clone(), request).await;
+        let result = if self.router.is_empty() {
+            self.process_client_request(id.clone(), request).await
+        } else {
+            self.route_client_request(id.clone(), request).await
+        };
         Self::create_response(id, result)
     }
 
-    /// Process a client request and return the result.
-    async fn process_client_request(
+    /// Process a client request wrapped in the hooks registered for its method.
+    async fn route_client_request(
         &self,
         request_id: RequestId,
         request: ClientRequest,
     ) -> Result<serde_json::Value> {
-        match request {
-            ClientRequest::Initialize(_) => {
-                // Already handled above
-                unreachable!("Initialize should be handled separately")
+        let value = serde_json::to_value(&request)?;
+        let method = value
+            .get("method")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        if !self.router.routes(&method) {
+            return self.process_client_request(request_id, request).await;
+        }
+
+        let params = value
+            .get("params")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+        let hook_request = router::HookRequest {
+            method,
+            request_id: request_id.clone(),
+            params: params.clone(),
+        };
+        self.router
+            .run(hook_request, |routed| async move {
+                let request = if routed.params == params {
+                    request
+                } else {
+                    serde_json::from_value(serde_json::json!({
+                        "method": routed.method,
+                        "params": routed.params,
+                    }))
+                    .map_err(|e| {
+                        Error::invalid_params(format!("Invalid params after hooks: {}", e))
+                    })?
+                };
+                self.process_client_request(request_id, request).await
+            })
+            .await
+    }
+
+    /// Process a client request and return the result.
+    async fn process_client_request(
+        &self,
+        request_id: RequestId,
+        request: ClientRequest,
+    ) -> Result<serde_json::Value> {
+        match request {
+            ClientRequest::Initialize(_) => {
+                // Already handled above
+                unreachable!("Initialize should be handled separately")
             },
             ClientRequest::ListTools(req) => self.handle_list_tools(req),
-            ClientRequest::CallTool(req) => self.handle_call_tool(request_id, req).await,
+            ClientRequest::CallTool(req) => self.call_tool_once(request_id, req).await,
             ClientRequest::ListPrompts(req) => self.handle_list_prompts(req),
             ClientRequest::GetPrompt(req) => self.handle_get_prompt(request_id, req).await,
             ClientRequest::ListResources(req) => self.handle_list_resources(request_id, req).await,
@@ -709,11 +1613,14 @@ impl Server {
             ClientRequest::ListResourceTemplates(req) => {
                 Self::handle_list_resource_templates(self, req)
             },
-            ClientRequest::Subscribe(_)
-            | ClientRequest::Unsubscribe(_)
-            | ClientRequest::Complete(_)
-            | ClientRequest::SetLoggingLevel { level: _ }
-            | ClientRequest::Ping => Ok(serde_json::json!({})),
+            ClientRequest::Complete(req) => self.handle_complete(request_id, req).await,
+            ClientRequest::Subscribe(req) => self.handle_subscribe(req).await,
+            ClientRequest::Unsubscribe(req) => self.handle_unsubscribe(req).await,
+            ClientRequest::SetLoggingLevel { level } => {
+                self.logger.set_level(level);
+                Ok(serde_json::json!({}))
+            },
+            ClientRequest::Ping => Ok(serde_json::json!({})),
             ClientRequest::CreateMessage(req) => self.handle_create_message(request_id, req).await,
             ClientRequest::ElicitInputResponse(response) => {
                 // Handle elicitation response if we have a manager
@@ -747,31 +1654,64 @@ impl Server {
         }
     }
 
-    fn handle_list_tools(&self, _req: ListToolsRequest) -> Result<Value> {
-        let tools = self
-            .tools
-            .iter()
-            .map(|(name, handler)| {
-                // Try to get metadata from the handler, otherwise use defaults
-                handler
-                    .metadata()
-                    .unwrap_or_else(|| crate::types::ToolInfo {
-                        name: name.clone(),
-                        description: None,
-                        input_schema: serde_json::json!({
-                            "type": "object",
-                            "properties": {}
-                        }),
-                    })
-            })
-            .collect::<Vec<_>>();
+    async fn handle_subscribe(&self, req: SubscribeRequest) -> Result<Value> {
+        #[cfg(feature = "resource-watcher")]
+        if let Some(watcher) = &self.resource_watcher {
+            let info = crate::types::ResourceInfo {
+                uri: req.uri.clone(),
+                name: req.uri.clone(),
+                description: None,
+                mime_type: None,
+            };
+            watcher.add_resource(req.uri.clone(), info).await?;
+        }
+        self.subscription_manager
+            .read()
+            .await
+            .subscribe(req.uri, current_subscriber())
+            .await?;
+        Ok(serde_json::json!({}))
+    }
+
+    async fn handle_unsubscribe(&self, req: UnsubscribeRequest) -> Result<Value> {
+        let subscriptions = self.subscription_manager.read().await;
+        subscriptions
+            .unsubscribe(req.uri.clone(), current_subscriber())
+            .await?;
+        #[cfg(feature = "resource-watcher")]
+        if let Some(watcher) = &self.resource_watcher {
+            if !subscriptions.has_subscribers(&req.uri).await {
+                watcher.remove_resource(&req.uri).await?;
+            }
+        }
+        Ok(serde_json::json!({}))
+    }
 
-        Ok(serde_json::to_value(ListToolsResult {
+    fn handle_list_tools(&self, _req: ListToolsRequest) -> Result<Value> {
+        let mut tools = self.tools.list();
+        for tool in &mut tools {
+            if let Some(rules) = self.tool_rules.get(&tool.name) {
+                rules.apply_to_schema(&mut tool.input_schema);
+            }
+        }
+        Ok(timing::to_value(ListToolsResult {
             tools,
             next_cursor: None,
         })?)
     }
 
+    /// Run a tool call, replaying the cached result for a repeated idempotency key.
+    async fn call_tool_once(&self, request_id: RequestId, req: CallToolRequest) -> Result<Value> {
+        let (Some(cache), Some(key)) = (&self.idempotency, req.idempotency_key()) else {
+            return self.handle_call_tool(request_id, req).await;
+        };
+        let key = key.to_string();
+        let fingerprint = serde_json::json!({"name": &req.name, "arguments": &req.arguments});
+        cache
+            .run(&key, fingerprint, self.handle_call_tool(request_id, req))
+            .await
+    }
+
     async fn handle_call_tool(&self, request_id: RequestId, req: CallToolRequest) -> Result<Value> {
         let handler = self
             .tools
@@ -780,16 +1720,16 @@ impl Server {
 
         let cancellation_token = self
             .cancellation_manager
-            .get_token(&request_id.to_string())
+            .get_token(&cancellation::current_request_key(&request_id))
             .await
             .unwrap_or_else(tokio_util::sync::CancellationToken::new);
 
         // Validate authentication if auth provider is configured
         let auth_context = if let Some(auth_provider) = &self.auth_provider {
-            // For now, we don't have access to HTTP headers in this context
-            // In a real implementation, this would come from the transport layer
-            // For the OAuth example, we'll use NoOpAuthProvider which validates without headers
-            auth_provider.validate_request(None).await?
+            // HTTP transports pass the request's Authorization header along
+            auth_provider
+                .validate_request(auth::current_authorization().as_deref())
+                .await?
         } else {
             None
         };
@@ -804,18 +1744,34 @@ impl Server {
             }
         }
 
+        let progress = match (req.progress_token(), &self.notification_tx) {
+            (Some(token), Some(tx)) => Some(progress::ProgressReporter::new(token, tx.clone())),
+            _ => None,
+        };
         let extra = crate::server::cancellation::RequestHandlerExtra::new(
             request_id.to_string(),
             cancellation_token,
         )
-        .with_auth_context(auth_context);
-
-        let result = handler.handle(req.arguments, extra).await?;
-        Ok(serde_json::to_value(CallToolResult {
+        .with_logger(Some(self.logger.clone()))
+        .with_auth_context(auth_context)
+        .with_progress_reporter(progress)
+        .with_client_requester(Some(self.current_client_requester()));
+
+        let permit = self.tool_permits.acquire(&req.name).await;
+        let result = timing::time(Phase::Handler, handler.handle(req.arguments, extra)).await;
+        drop(permit);
+        let result = result?;
+        if self.validate_tool_output {
+            if let Some(info) = handler.metadata() {
+                tool_validation::check_tool_output(&req.name, &info, &result)?;
+            }
+        }
+        Ok(timing::to_value(CallToolResult {
             content: vec![crate::types::Content::Text {
                 text: result.to_string(),
             }],
             is_error: false,
+            structured_content: result.is_object().then_some(result),
         })?)
     }
 
@@ -830,7 +1786,7 @@ impl Server {
             })
             .collect::<Vec<_>>();
 
-        Ok(serde_json::to_value(ListPromptsResult {
+        Ok(timing::to_value(ListPromptsResult {
             prompts,
             next_cursor: None,
         })?)
@@ -848,15 +1804,16 @@ impl Server {
 
         let cancellation_token = self
             .cancellation_manager
-            .get_token(&request_id.to_string())
+            .get_token(&cancellation::current_request_key(&request_id))
             .await
             .unwrap_or_else(tokio_util::sync::CancellationToken::new);
         let extra = crate::server::cancellation::RequestHandlerExtra::new(
             request_id.to_string(),
             cancellation_token,
-        );
-        let result = handler.handle(req.arguments, extra).await?;
-        Ok(serde_json::to_value(result)?)
+        )
+        .with_logger(Some(self.logger.clone()));
+        let result = timing::time(Phase::Handler, handler.handle(req.arguments, extra)).await?;
+        Ok(timing::to_value(result)?)
     }
 
     async fn handle_list_resources(
@@ -867,17 +1824,18 @@ impl Server {
         if let Some(handler) = &self.resources {
             let cancellation_token = self
                 .cancellation_manager
-                .get_token(&request_id.to_string())
+                .get_token(&cancellation::current_request_key(&request_id))
                 .await
                 .unwrap_or_else(tokio_util::sync::CancellationToken::new);
             let extra = crate::server::cancellation::RequestHandlerExtra::new(
                 request_id.to_string(),
                 cancellation_token,
-            );
-            let result = handler.list(req.cursor, extra).await?;
-            Ok(serde_json::to_value(result)?)
+            )
+            .with_logger(Some(self.logger.clone()));
+            let result = timing::time(Phase::Handler, handler.list(req.cursor, extra)).await?;
+            Ok(timing::to_value(result)?)
         } else {
-            Ok(serde_json::to_value(ListResourcesResult {
+            Ok(timing::to_value(ListResourcesResult {
                 resources: vec![],
                 next_cursor: None,
             })?)
@@ -896,25 +1854,58 @@ impl Server {
 
         let cancellation_token = self
             .cancellation_manager
-            .get_token(&request_id.to_string())
+            .get_token(&cancellation::current_request_key(&request_id))
             .await
             .unwrap_or_else(tokio_util::sync::CancellationToken::new);
         let extra = crate::server::cancellation::RequestHandlerExtra::new(
             request_id.to_string(),
             cancellation_token,
-        );
-        let result = handler.read(&req.uri, extra).await?;
-        Ok(serde_json::to_value(result)?)
+        )
+        .with_logger(Some(self.logger.clone()));
+        let result = timing::time(
+            Phase::Handler,
+            resource_chunks::read_resource(handler.as_ref(), &req, extra),
+        )
+        .await?;
+        Ok(timing::to_value(result)?)
     }
 
-    #[allow(clippy::unused_self)]
     fn handle_list_resource_templates(&self, _req: ListResourceTemplatesRequest) -> Result<Value> {
-        Ok(serde_json::to_value(ListResourceTemplatesResult {
-            resource_templates: vec![],
+        Ok(timing::to_value(ListResourceTemplatesResult {
+            resource_templates: self.resource_templates.templates(),
             next_cursor: None,
         })?)
     }
 
+    async fn handle_complete(&self, request_id: RequestId, req: CompleteRequest) -> Result<Value> {
+        if let Some(handler) = &self.completion {
+            let cancellation_token = self
+                .cancellation_manager
+                .get_token(&cancellation::current_request_key(&request_id))
+                .await
+                .unwrap_or_else(tokio_util::sync::CancellationToken::new);
+            let extra = crate::server::cancellation::RequestHandlerExtra::new(
+                request_id.to_string(),
+                cancellation_token,
+            )
+            .with_logger(Some(self.logger.clone()));
+            let completion = timing::time(Phase::Handler, handler.complete(req, extra)).await?;
+            return Ok(timing::to_value(CompleteResult { completion })?);
+        }
+
+        let completion = match &req.r#ref {
+            CompletionReference::Resource { uri } => {
+                self.resource_templates.complete(uri, &req.argument)?
+            },
+            CompletionReference::Prompt { .. } => CompletionResult {
+                values: vec![],
+                total: None,
+                has_more: false,
+            },
+        };
+        Ok(timing::to_value(CompleteResult { completion })?)
+    }
+
     async fn handle_create_message(
         &self,
         request_id: RequestId,
@@ -927,15 +1918,16 @@ impl Server {
 
         let cancellation_token = self
             .cancellation_manager
-            .get_token(&request_id.to_string())
+            .get_token(&cancellation::current_request_key(&request_id))
             .await
             .unwrap_or_else(tokio_util::sync::CancellationToken::new);
         let extra = crate::server::cancellation::RequestHandlerExtra::new(
             request_id.to_string(),
             cancellation_token,
-        );
-        let result = handler.create_message(req, extra).await?;
-        Ok(serde_json::to_value(result)?)
+        )
+        .with_logger(Some(self.logger.clone()));
+        let result = timing::time(Phase::Handler, handler.create_message(req, extra)).await?;
+        Ok(timing::to_value(result)?)
     }
 
     /// Register a root directory or URI that the server has access to.
@@ -1029,6 +2021,78 @@ impl Server {
         roots_manager.get_roots().await
     }
 
+    /// A handle sending requests to the connected client.
+    ///
+    /// Running the server consumes it, so take the requester first to make
+    /// requests from outside a handler. Requests fail until the server runs,
+    /// and capability checks apply once the client has initialized. Sessions
+    /// of the streamable HTTP server have requesters of their own, only
+    /// reachable from handlers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Server, StdioTransport};
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder()
+    ///     .name("file-server")
+    ///     .version("1.0.0")
+    ///     .build()?;
+    /// let requester = server.client_requester();
+    /// tokio::spawn(server.run(StdioTransport::new()));
+    ///
+    /// // Once the client has initialized
+    /// let roots = requester.list_roots().await?;
+    /// println!("Client offers {} roots", roots.roots.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn client_requester(&self) -> client_requests::ClientRequester {
+        self.client_requests.clone()
+    }
+
+    /// Requester for the client whose request is being handled: that of its
+    /// HTTP session, if any, or else the server's.
+    fn current_client_requester(&self) -> client_requests::ClientRequester {
+        client_requests::session_requester().unwrap_or_else(|| self.client_requests.clone())
+    }
+
+    /// The protocol version agreed on with the client, once initialized.
+    pub fn negotiated_version(&self) -> Option<crate::shared::NegotiatedVersion> {
+        self.client_requests.protocol_version()
+    }
+
+    /// Ask the connected client for its filesystem roots (`roots/list`).
+    ///
+    /// See [`client_requester`](Self::client_requester) to ask while the
+    /// server runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server is not connected, the client does not
+    /// support roots, or the request fails.
+    pub async fn request_roots(&self) -> Result<roots::ListRootsResult> {
+        self.client_requests.list_roots().await
+    }
+
+    /// Ask the connected client to sample a message from its LLM
+    /// (`sampling/createMessage`).
+    ///
+    /// See [`client_requester`](Self::client_requester) to ask while the
+    /// server runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server is not connected, the client does not
+    /// support sampling, or the request fails.
+    pub async fn create_message(
+        &self,
+        params: crate::types::CreateMessageParams,
+    ) -> Result<crate::types::CreateMessageResult> {
+        self.client_requests.create_message(params).await
+    }
+
     /// Subscribe a client to resource updates.
     ///
     /// This method allows the server to track which clients are interested
@@ -1116,6 +2180,30 @@ impl Server {
             .await
     }
 
+    /// Apply a `notifications/cancelled` sent by the client.
+    ///
+    /// Cancels the [`cancellation_token`](cancellation::RequestHandlerExtra::cancellation_token)
+    /// of the named request's handler. [`run`](Self::run) calls this for
+    /// every cancellation it receives; custom transports and tests can call
+    /// it directly. Returns whether the request was still in flight.
+    ///
+    /// [`run`](Self::run) reads the transport only between requests and
+    /// while a handler waits on the client, so over a single connection a
+    /// cancellation reaches handlers that are waiting on the client, such
+    /// as for sampling.
+    pub async fn handle_cancelled(&self, notification: &CancelledNotification) -> bool {
+        if let Some(reason) = &notification.reason {
+            tracing::debug!(
+                "Client cancelled request {}: {}",
+                notification.request_id,
+                reason
+            );
+        }
+        self.cancellation_manager
+            .cancel_from_peer(&notification.request_id.to_string())
+            .await
+    }
+
     /// Unsubscribe a client from resource updates.
     ///
     /// This method removes a client's subscription to a specific resource,
@@ -1163,7 +2251,34 @@ impl Server {
     /// # Returns
     ///
     /// The number of subscribers that were notified.
+    ///
+    /// When serving several clients with [`run_websocket`](Self::run_websocket)
+    /// only the subscribed clients are notified, and subscriptions of
+    /// clients that have disconnected are dropped.
     pub async fn notify_resource_updated(&self, uri: String) -> Result<usize> {
+        #[cfg(feature = "websocket")]
+        if let Some(clients) = self.websocket_clients.get() {
+            let subscriptions = self.subscription_manager.read().await;
+            let mut notified = 0;
+            for subscriber in subscriptions.get_subscribers(&uri).await {
+                let notification = TransportMessage::Notification(Notification::Server(
+                    ServerNotification::ResourceUpdated(
+                        crate::types::protocol::ResourceUpdatedParams { uri: uri.clone() },
+                    ),
+                ));
+                let delivered = match subscriber.parse() {
+                    Ok(client_id) => clients.send_to(client_id, notification).await.is_ok(),
+                    Err(_) => false,
+                };
+                if delivered {
+                    notified += 1;
+                } else {
+                    subscriptions.unsubscribe_all(&subscriber).await?;
+                }
+            }
+            return Ok(notified);
+        }
+
         let mut subscription_manager = self.subscription_manager.write().await;
         if let Some(tx) = &self.notification_tx {
             subscription_manager.set_notification_sender({
@@ -1183,9 +2298,12 @@ pub struct ServerBuilder {
     name: Option<String>,
     version: Option<String>,
     capabilities: ServerCapabilities,
+    /// Advertise `capabilities` verbatim instead of deriving and checking them
+    capabilities_overridden: bool,
     tools: HashMap<String, Arc<dyn ToolHandler>>,
     prompts: HashMap<String, Arc<dyn PromptHandler>>,
     resources: Option<Arc<dyn ResourceHandler>>,
+    completion: Option<Arc<dyn completion::CompletionHandler>>,
     sampling: Option<Arc<dyn SamplingHandler>>,
     /// Cancellation manager for request cancellation
     cancellation_manager: cancellation::CancellationManager,
@@ -1197,6 +2315,52 @@ pub struct ServerBuilder {
     tool_authorizer: Option<Arc<dyn auth::ToolAuthorizer>>,
     /// Tool protection requirements to be applied at build time
     tool_protections: HashMap<String, Vec<String>>,
+    /// Tool names registered more than once
+    duplicate_tools: Vec<String>,
+    /// Resource templates to be registered at build time
+    resource_templates: Vec<crate::types::ResourceTemplate>,
+    /// Completion values for resource template variables
+    resource_template_completions: Vec<(String, String, Vec<String>)>,
+    /// Router for reads of templated resources
+    resource_template_router: Option<resource_templates::ResourceTemplateRouter>,
+    /// Whether results carry a `_meta` timing breakdown
+    timing_meta: bool,
+    /// Limits on the shape of incoming request params
+    request_guard: Option<crate::utils::validation::RequestGuard>,
+    /// Whether tool results are checked against their output schema
+    validate_tool_output: bool,
+    /// Constraints on tool arguments, by tool name
+    tool_rules: HashMap<String, input_validation::ArgumentRules>,
+    /// How tool arguments are rewritten against their input schema
+    argument_coercion: Option<coercion::ArgumentCoercion>,
+    /// Protocol versions offered to clients
+    version_negotiator: crate::shared::VersionNegotiator,
+    /// Results of recent tool calls by idempotency key
+    idempotency: Option<idempotency::IdempotencyCache>,
+    /// Per-principal request limits
+    rate_limiter: Option<rate_limit::PrincipalRateLimiter>,
+    /// Where request/response pairs are recorded
+    audit_logger: Option<Arc<dyn audit::AuditLogger>>,
+    /// Maximum serialized size of audited payloads
+    audit_payload_limit: usize,
+    /// Prometheus request metrics
+    #[cfg(feature = "prometheus")]
+    metrics: Option<Arc<metrics::ServerMetrics>>,
+    /// Hooks run around individual methods
+    router: router::MethodRouter,
+    /// Advertise tools as changeable at runtime
+    dynamic_tools: bool,
+    /// How long the client may stay silent before the connection is dropped
+    keep_alive: Option<crate::shared::KeepAliveConfig>,
+    /// Called when the client stayed silent past the keep-alive
+    on_keep_alive_timeout: Option<Arc<dyn Fn() + Send + Sync>>,
+    /// How many requests run at once
+    concurrency: concurrency::ConcurrencyConfig,
+    /// Watcher pushing updates for subscribed `file://` resources
+    #[cfg(feature = "resource-watcher")]
+    resource_watcher: Option<resource_watcher::ResourceWatcherBuilder>,
+    /// Resource handlers of mounted servers, by namespace
+    mounted_resources: Vec<(String, Arc<dyn ResourceHandler>)>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -1241,15 +2405,41 @@ impl ServerBuilder {
             name: None,
             version: None,
             capabilities: ServerCapabilities::default(),
+            capabilities_overridden: false,
             tools: HashMap::new(),
             prompts: HashMap::new(),
             resources: None,
+            completion: None,
             sampling: None,
             cancellation_manager: cancellation::CancellationManager::new(),
             roots_manager: roots::RootsManager::new(),
             auth_provider: None,
             tool_authorizer: None,
             tool_protections: HashMap::new(),
+            duplicate_tools: Vec::new(),
+            resource_templates: Vec::new(),
+            resource_template_completions: Vec::new(),
+            resource_template_router: None,
+            timing_meta: false,
+            request_guard: None,
+            validate_tool_output: false,
+            tool_rules: HashMap::new(),
+            argument_coercion: None,
+            version_negotiator: crate::shared::VersionNegotiator::default(),
+            idempotency: None,
+            rate_limiter: None,
+            audit_logger: None,
+            audit_payload_limit: audit::DEFAULT_PAYLOAD_LIMIT,
+            #[cfg(feature = "prometheus")]
+            metrics: None,
+            router: router::MethodRouter::new(),
+            dynamic_tools: false,
+            keep_alive: None,
+            on_keep_alive_timeout: None,
+            concurrency: concurrency::ConcurrencyConfig::default(),
+            #[cfg(feature = "resource-watcher")]
+            resource_watcher: None,
+            mounted_resources: Vec::new(),
         }
     }
 
@@ -1308,6 +2498,13 @@ impl ServerBuilder {
     /// Configures the capabilities that this server supports.
     /// Capabilities inform clients about which MCP features are available.
     ///
+    /// Capabilities for registered tools, prompts, resources, and completions
+    /// are added automatically by `build()`, so this is only needed to set
+    /// options such as `list_changed`. Declaring a capability without a
+    /// handler to back it is a build error; use
+    /// [`override_capabilities`](Self::override_capabilities) to advertise
+    /// capabilities verbatim.
+    ///
     /// # Arguments
     ///
     /// * `capabilities` - The server capabilities to advertise
@@ -1336,587 +2533,517 @@ impl ServerBuilder {
         self
     }
 
-    /// Add a tool handler.
+    /// Advertise exactly these capabilities.
     ///
-    /// Registers a tool that clients can call via the tools/call method.
-    /// Tools are the primary way servers provide functionality to clients.
+    /// Skips deriving capabilities from registered handlers and checking that
+    /// declared capabilities are backed by one. Useful for proxies and test
+    /// doubles that serve requests outside the builder's handlers.
     ///
-    /// # Arguments
+    /// # Examples
     ///
-    /// * `name` - The name of the tool (used by clients to call it)
-    /// * `handler` - The handler implementation for this tool
+    /// ```rust,no_run
+    /// use pmcp::{Server, ServerCapabilities};
+    ///
+    /// let server = Server::builder()
+    ///     .name("proxy")
+    ///     .version("1.0.0")
+    ///     .override_capabilities(ServerCapabilities::tools_only())
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn override_capabilities(mut self, capabilities: ServerCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self.capabilities_overridden = true;
+        self
+    }
+
+    /// Include a per-request timing breakdown in every result's `_meta`.
+    ///
+    /// Results gain a `pmcp/timing` entry with the microseconds spent
+    /// parsing, in SDK middleware, in the handler, and serializing. Intended
+    /// for debugging; the same figures are always aggregated in
+    /// [`Server::timing_snapshot`].
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use pmcp::{Server, ToolHandler};
-    /// use async_trait::async_trait;
-    /// use serde_json::Value;
+    /// use pmcp::Server;
     ///
-    /// struct FileListTool;
+    /// let server = Server::builder()
+    ///     .name("debug-server")
+    ///     .version("1.0.0")
+    ///     .timing_meta(true)
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn timing_meta(mut self, enabled: bool) -> Self {
+        self.timing_meta = enabled;
+        self
+    }
+
+    /// Reject requests whose params exceed the guard's limits.
     ///
-    /// #[async_trait]
-    /// impl ToolHandler for FileListTool {
-    ///     async fn handle(&self, args: Value, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<Value> {
-    ///         let path = args["path"].as_str().unwrap_or(".");
-    ///         // List files in path...
-    ///         Ok(serde_json::json!({"files": ["file1.txt", "file2.txt"]}))
-    ///     }
-    /// }
+    /// Params are checked for nesting depth, string length, and array size
+    /// before any handler sees them; violations are answered with an
+    /// `INVALID_PARAMS` error. Recommended for servers exposed to untrusted
+    /// clients.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::utils::validation::RequestGuard;
+    /// use pmcp::Server;
     ///
     /// let server = Server::builder()
-    ///     .name("file-server")
+    ///     .name("public-server")
     ///     .version("1.0.0")
-    ///     .tool("list_files", FileListTool{})
+    ///     .request_guard(
+    ///         RequestGuard::new()
+    ///             .with_max_depth(16)
+    ///             .with_max_string_length(64 * 1024)
+    ///             .with_max_array_length(1_000),
+    ///     )
     ///     .build()?;
     /// # Ok::<(), pmcp::Error>(())
     /// ```
-    pub fn tool(mut self, name: impl Into<String>, handler: impl ToolHandler + 'static) -> Self {
-        self.tools.insert(name.into(), Arc::new(handler));
+    pub fn request_guard(mut self, guard: crate::utils::validation::RequestGuard) -> Self {
+        self.request_guard = Some(guard);
         self
     }
 
-    /// Add a type-safe tool handler with automatic schema generation.
+    /// Check tool results against the output schema each tool advertises.
     ///
-    /// This method provides first-class support for creating tools with:
-    /// - Automatic JSON schema generation from Rust types
-    /// - Compile-time type safety
-    /// - Runtime validation
-    /// - Field descriptions from doc comments
+    /// A result that does not match is replaced by an internal error listing
+    /// each mismatch, so a tool cannot silently break its advertised
+    /// contract. With the `validation` feature the full schema is enforced,
+    /// otherwise only required properties are checked.
     ///
-    /// # Example
-    /// ```no_run
-    /// # #[cfg(feature = "schema-generation")]
-    /// # {
-    /// use pmcp::ServerBuilder;
-    /// use schemars::JsonSchema;
-    /// use serde::{Deserialize, Serialize};
+    /// # Examples
     ///
-    /// #[derive(Debug, Deserialize, Serialize, JsonSchema)]
-    /// struct EchoArgs {
-    ///     /// The message to echo
-    ///     message: String,
-    ///     /// Optional prefix
-    ///     prefix: Option<String>,
-    /// }
+    /// ```rust,no_run
+    /// use pmcp::Server;
     ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), pmcp::Error> {
-    /// let server = ServerBuilder::new()
-    ///     .name("example")
-    ///     .tool_typed("echo", |args: EchoArgs, _| {
-    ///         Box::pin(async move {
-    ///             let message = match args.prefix {
-    ///                 Some(p) => format!("{}: {}", p, args.message),
-    ///                 None => args.message,
-    ///             };
-    ///             Ok(serde_json::json!({ "message": message }))
-    ///         })
-    ///     })
-    ///     .build();
+    /// let server = Server::builder()
+    ///     .name("strict-server")
+    ///     .version("1.0.0")
+    ///     .validate_tool_output(true)
+    ///     .build()?;
     /// # Ok::<(), pmcp::Error>(())
-    /// # }
-    /// # }
     /// ```
-    #[cfg(feature = "schema-generation")]
-    pub fn tool_typed<T, F, Fut>(mut self, name: impl Into<String>, handler: F) -> Self
-    where
-        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
-        F: Fn(T, crate::RequestHandlerExtra) -> Fut + Send + Sync + 'static,
-        Fut: std::future::Future<Output = crate::Result<serde_json::Value>> + Send + 'static,
-    {
-        use crate::server::typed_tool::TypedTool;
-        use std::pin::Pin;
-
-        let name_str = name.into();
-
-        // Wrap the handler to return Pin<Box<dyn Future>>
-        let wrapped_handler = move |args: T,
-                                    extra: crate::RequestHandlerExtra|
-              -> Pin<
-            Box<dyn std::future::Future<Output = crate::Result<serde_json::Value>> + Send>,
-        > { Box::pin(handler(args, extra)) };
-
-        let tool = TypedTool::new(name_str.clone(), wrapped_handler);
-        self.tools.insert(name_str, Arc::new(tool));
+    pub fn validate_tool_output(mut self, enabled: bool) -> Self {
+        self.validate_tool_output = enabled;
         self
     }
 
-    /// Add a type-safe tool handler with automatic schema generation and description.
+    /// Check the arguments of calls to `tool` against `rules` before its
+    /// handler runs.
     ///
-    /// This is a convenience overload that allows setting a description directly
-    /// without needing to chain `.with_description()`.
+    /// Calls breaking any rule fail with `INVALID_PARAMS` listing every
+    /// violation, and the rules are merged into the tool's advertised input
+    /// schema. See [`input_validation`].
     ///
-    /// # Example
-    /// ```no_run
-    /// # #[cfg(feature = "schema-generation")]
-    /// # {
-    /// use pmcp::ServerBuilder;
-    /// use schemars::JsonSchema;
-    /// use serde::{Deserialize, Serialize};
+    /// # Examples
     ///
-    /// #[derive(Debug, Deserialize, Serialize, JsonSchema)]
-    /// struct EchoArgs {
-    ///     /// The message to echo
-    ///     message: String,
-    ///     /// Optional prefix
-    ///     prefix: Option<String>,
-    /// }
+    /// ```rust,no_run
+    /// use pmcp::server::input_validation::ArgumentRules;
+    /// use pmcp::Server;
     ///
-    /// let server = ServerBuilder::new()
-    ///     .name("example")
-    ///     .tool_typed_with_description(
-    ///         "echo",
-    ///         "Echoes back a message with an optional prefix",
-    ///         |args: EchoArgs, _| {
-    ///             Box::pin(async move {
-    ///                 let message = match args.prefix {
-    ///                     Some(p) => format!("{}: {}", p, args.message),
-    ///                     None => args.message,
-    ///                 };
-    ///                 Ok(serde_json::json!({ "message": message }))
-    ///             })
-    ///         }
-    ///     );
-    /// # }
+    /// let server = Server::builder()
+    ///     .name("weather")
+    ///     .version("1.0.0")
+    ///     .tool_rules(
+    ///         "forecast",
+    ///         ArgumentRules::new().required("city").min("days", 1.0).max("days", 14.0),
+    ///     )
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
     /// ```
-    #[cfg(feature = "schema-generation")]
-    pub fn tool_typed_with_description<T, F, Fut>(
+    pub fn tool_rules(
         mut self,
-        name: impl Into<String>,
-        description: impl Into<String>,
-        handler: F,
-    ) -> Self
-    where
-        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
-        F: Fn(T, crate::RequestHandlerExtra) -> Fut + Send + Sync + 'static,
-        Fut: std::future::Future<Output = crate::Result<serde_json::Value>> + Send + 'static,
-    {
-        use crate::server::typed_tool::TypedTool;
-        use std::pin::Pin;
-
-        let name_str = name.into();
-
-        // Wrap the handler to return Pin<Box<dyn Future>>
-        let wrapped_handler = move |args: T,
-                                    extra: crate::RequestHandlerExtra|
-              -> Pin<
-            Box<dyn std::future::Future<Output = crate::Result<serde_json::Value>> + Send>,
-        > { Box::pin(handler(args, extra)) };
+        tool: impl Into<String>,
+        rules: input_validation::ArgumentRules,
+    ) -> Self {
+        self.tool_rules.insert(tool.into(), rules);
+        self
+    }
 
-        let tool = TypedTool::new(name_str.clone(), wrapped_handler).with_description(description);
-        self.tools.insert(name_str, Arc::new(tool));
+    /// Rewrite the arguments of every tool call against the tool's input
+    /// schema before they are checked and passed to the handler.
+    ///
+    /// Strings are converted to the numbers and booleans the schema expects,
+    /// schema defaults fill missing fields, and undeclared fields are
+    /// dropped, as configured by `coercion`. See [`coercion`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::server::coercion::ArgumentCoercion;
+    /// use pmcp::Server;
+    ///
+    /// let server = Server::builder()
+    ///     .name("lenient")
+    ///     .version("1.0.0")
+    ///     .coerce_arguments(ArgumentCoercion::new())
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn coerce_arguments(mut self, coercion: coercion::ArgumentCoercion) -> Self {
+        self.argument_coercion = Some(coercion);
         self
     }
 
-    /// Add a synchronous type-safe tool handler with automatic schema generation.
+    /// Set the protocol versions the server speaks.
     ///
-    /// Similar to `tool_typed` but for synchronous handlers.
+    /// A client proposing a version outside `versions` is answered with the
+    /// newest of them. Defaults to
+    /// [`SUPPORTED_PROTOCOL_VERSIONS`](crate::SUPPORTED_PROTOCOL_VERSIONS).
     ///
-    /// # Example
-    /// ```no_run
-    /// # #[cfg(feature = "schema-generation")]
-    /// # {
-    /// use pmcp::ServerBuilder;
-    /// use schemars::JsonSchema;
-    /// use serde::{Deserialize, Serialize};
+    /// # Examples
     ///
-    /// #[derive(Debug, Deserialize, Serialize, JsonSchema)]
-    /// struct MathArgs {
-    ///     /// First number
-    ///     a: f64,
-    ///     /// Second number
-    ///     b: f64,
-    ///     /// Operation to perform
-    ///     op: String,
-    /// }
+    /// ```rust,no_run
+    /// use pmcp::shared::VersionNegotiator;
+    /// use pmcp::Server;
     ///
-    /// # fn main() -> Result<(), pmcp::Error> {
-    /// let server = ServerBuilder::new()
-    ///     .name("example")
-    ///     .tool_typed_sync("calculator", |args: MathArgs, _| {
-    ///         let result = match args.op.as_str() {
-    ///             "add" => args.a + args.b,
-    ///             "subtract" => args.a - args.b,
-    ///             "multiply" => args.a * args.b,
-    ///             "divide" => args.a / args.b,
-    ///             _ => return Err(pmcp::Error::Validation("Unknown operation".into())),
-    ///         };
-    ///         Ok(serde_json::json!({ "result": result }))
-    ///     })
-    ///     .build();
+    /// let server = Server::builder()
+    ///     .name("legacy")
+    ///     .version("1.0.0")
+    ///     .protocol_versions(VersionNegotiator::new(["2025-03-26", "2024-11-05"]))
+    ///     .build()?;
     /// # Ok::<(), pmcp::Error>(())
-    /// # }
-    /// # }
     /// ```
-    #[cfg(feature = "schema-generation")]
-    pub fn tool_typed_sync<T, F>(mut self, name: impl Into<String>, handler: F) -> Self
-    where
-        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
-        F: Fn(T, crate::RequestHandlerExtra) -> crate::Result<serde_json::Value>
-            + Send
-            + Sync
-            + 'static,
-    {
-        use crate::server::typed_tool::TypedSyncTool;
-        let name_str = name.into();
-        let tool = TypedSyncTool::new(name_str.clone(), handler);
-        self.tools.insert(name_str, Arc::new(tool));
+    pub fn protocol_versions(mut self, versions: crate::shared::VersionNegotiator) -> Self {
+        self.version_negotiator = versions;
         self
     }
 
-    /// Add a synchronous type-safe tool handler with automatic schema generation and description.
+    /// Run tool calls at most once per idempotency key.
     ///
-    /// This is a convenience overload that allows setting a description directly
-    /// without needing to chain `.with_description()`.
+    /// Calls carrying an idempotency key in their `_meta` are answered from
+    /// `cache` when the key was seen before, so redelivered requests do not
+    /// repeat a tool's side effects. See [`idempotency`] for details.
     ///
-    /// # Example
-    /// ```no_run
-    /// # #[cfg(feature = "schema-generation")]
-    /// # {
-    /// use pmcp::ServerBuilder;
-    /// use schemars::JsonSchema;
-    /// use serde::{Deserialize, Serialize};
+    /// # Examples
     ///
-    /// #[derive(Debug, Deserialize, Serialize, JsonSchema)]
-    /// struct MathArgs {
-    ///     /// First number
-    ///     a: f64,
-    ///     /// Second number
-    ///     b: f64,
-    ///     /// Operation to perform
-    ///     op: String,
-    /// }
+    /// ```rust,no_run
+    /// use pmcp::server::idempotency::IdempotencyCache;
+    /// use pmcp::Server;
     ///
-    /// let server = ServerBuilder::new()
-    ///     .name("example")
-    ///     .tool_typed_sync_with_description(
-    ///         "calculator",
-    ///         "Performs synchronous mathematical operations",
-    ///         |args: MathArgs, _| {
-    ///             let result = match args.op.as_str() {
-    ///                 "add" => args.a + args.b,
-    ///                 "subtract" => args.a - args.b,
-    ///                 "multiply" => args.a * args.b,
-    ///                 "divide" => args.a / args.b,
-    ///                 _ => return Err(pmcp::Error::Validation("Unknown operation".into())),
-    ///             };
-    ///             Ok(serde_json::json!({ "result": result }))
-    ///         }
-    ///     );
-    /// # }
+    /// let server = Server::builder()
+    ///     .name("orders")
+    ///     .version("1.0.0")
+    ///     .idempotency(IdempotencyCache::new())
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
     /// ```
-    #[cfg(feature = "schema-generation")]
-    pub fn tool_typed_sync_with_description<T, F>(
-        mut self,
-        name: impl Into<String>,
-        description: impl Into<String>,
-        handler: F,
-    ) -> Self
-    where
-        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
-        F: Fn(T, crate::RequestHandlerExtra) -> crate::Result<serde_json::Value>
-            + Send
-            + Sync
-            + 'static,
-    {
-        use crate::server::typed_tool::TypedSyncTool;
-        let name_str = name.into();
-        let tool = TypedSyncTool::new(name_str.clone(), handler).with_description(description);
-        self.tools.insert(name_str, Arc::new(tool));
+    pub fn idempotency(mut self, cache: idempotency::IdempotencyCache) -> Self {
+        self.idempotency = Some(cache);
         self
     }
 
-    /// Add a type-safe tool handler with both input and output typing.
+    /// Limit how often each caller may send requests.
     ///
-    /// This method provides full type safety for both input and output types,
-    /// which is useful for testing, documentation, and API contracts.
-    /// Note that output schemas are not part of the MCP protocol but can be
-    /// valuable for development and integration testing.
+    /// Callers are told apart by the subject the auth provider authenticated
+    /// from the request's `Authorization` header, or by their address or
+    /// connection when there is none. Requests over
+    /// the limit fail with `RATE_LIMITED`. See [`rate_limit`] for details.
     ///
-    /// # Type Parameters
+    /// # Examples
     ///
-    /// * `TIn` - Input type that implements `JsonSchema`, `Deserialize`, `Send`, `Sync`
-    /// * `TOut` - Output type that implements `JsonSchema`, `Serialize`, `Send`, `Sync`
+    /// ```rust,no_run
+    /// use pmcp::server::rate_limit::{PrincipalRateLimiter, TokenBucket};
+    /// use pmcp::Server;
     ///
-    /// # Example
-    /// ```no_run
-    /// # #[cfg(feature = "schema-generation")]
-    /// # {
-    /// use pmcp::{ServerBuilder, TypedToolWithOutput};
-    /// use schemars::JsonSchema;
-    /// use serde::{Deserialize, Serialize};
+    /// let server = Server::builder()
+    ///     .name("search")
+    ///     .version("1.0.0")
+    ///     .rate_limit(PrincipalRateLimiter::new().tools(TokenBucket::new(5, 1.0)))
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn rate_limit(mut self, limiter: rate_limit::PrincipalRateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Record every request and its outcome with `logger`.
     ///
-    /// #[derive(JsonSchema, Deserialize)]
-    /// struct MathInput { a: f64, b: f64, op: String }
+    /// See [`audit`] for the recorded fields and the bundled loggers.
     ///
-    /// #[derive(JsonSchema, Serialize)]
-    /// struct MathOutput { result: f64, operation: String }
+    /// # Examples
     ///
-    /// let server = ServerBuilder::new()
-    ///     .name("example")
-    ///     .tool_typed_with_output::<MathInput, MathOutput>("math", |args, _| {
-    ///         Box::pin(async move {
-    ///             let result = match args.op.as_str() {
-    ///                 "add" => args.a + args.b,
-    ///                 "subtract" => args.a - args.b,
-    ///                 _ => return Err(pmcp::Error::Validation("Unknown operation".into())),
-    ///             };
-    ///             Ok(MathOutput {
-    ///                 result,
-    ///                 operation: args.op,
-    ///             })
-    ///         })
-    ///     });
-    /// # }
+    /// ```rust,no_run
+    /// use pmcp::server::audit::TracingAuditLogger;
+    /// use pmcp::Server;
+    ///
+    /// let server = Server::builder()
+    ///     .name("payments")
+    ///     .version("1.0.0")
+    ///     .audit_logger(TracingAuditLogger::new())
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
     /// ```
-    #[cfg(feature = "schema-generation")]
-    pub fn tool_typed_with_output<TIn, TOut>(
-        mut self,
-        name: impl Into<String>,
-        handler: impl Fn(
-                TIn,
-                crate::RequestHandlerExtra,
-            )
-                -> std::pin::Pin<Box<dyn std::future::Future<Output = crate::Result<TOut>> + Send>>
-            + Send
-            + Sync
-            + 'static,
-    ) -> Self
-    where
-        TIn: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
-        TOut: serde::Serialize + schemars::JsonSchema + Send + Sync + 'static,
-    {
-        use crate::server::typed_tool::TypedToolWithOutput;
+    pub fn audit_logger(mut self, logger: impl audit::AuditLogger + 'static) -> Self {
+        self.audit_logger = Some(Arc::new(logger));
+        self
+    }
 
-        let name_str = name.into();
-        let tool = TypedToolWithOutput::new(name_str.clone(), handler);
-        self.tools.insert(name_str, Arc::new(tool));
+    /// Truncate audited params and results that serialize to more than
+    /// `bytes` (default [`audit::DEFAULT_PAYLOAD_LIMIT`]).
+    pub fn audit_payload_limit(mut self, bytes: usize) -> Self {
+        self.audit_payload_limit = bytes;
         self
     }
 
-    /// Add a type-safe tool handler with both input and output typing and description.
+    /// Count requests, errors, and running tool calls in `metrics`.
     ///
-    /// This is a convenience overload that allows setting a description directly
-    /// without needing to chain `.with_description()`.
+    /// Pass the same instance to the HTTP server to serve them at
+    /// `/metrics`; see [`metrics`] for the reported series.
     ///
-    /// # Example
-    /// ```no_run
-    /// # #[cfg(feature = "schema-generation")]
-    /// # {
-    /// use pmcp::ServerBuilder;
-    /// use schemars::JsonSchema;
-    /// use serde::{Deserialize, Serialize};
+    /// # Examples
     ///
-    /// #[derive(JsonSchema, Deserialize)]
-    /// struct MathInput { a: f64, b: f64, op: String }
+    /// ```rust,no_run
+    /// use pmcp::server::metrics::ServerMetrics;
+    /// use pmcp::Server;
+    /// use std::sync::Arc;
     ///
-    /// #[derive(JsonSchema, Serialize)]
-    /// struct MathOutput { result: f64, operation: String }
-    ///
-    /// let server = ServerBuilder::new()
-    ///     .name("example")
-    ///     .tool_typed_with_output_and_description::<MathInput, MathOutput>(
-    ///         "math",
-    ///         "Performs basic mathematical operations on two numbers",
-    ///         |args, _| {
-    ///             Box::pin(async move {
-    ///                 let result = match args.op.as_str() {
-    ///                     "add" => args.a + args.b,
-    ///                     "subtract" => args.a - args.b,
-    ///                     _ => return Err(pmcp::Error::Validation("Unknown operation".into())),
-    ///                 };
-    ///                 Ok(MathOutput { result, operation: args.op })
-    ///             })
-    ///         }
-    ///     );
-    /// # }
+    /// let metrics = Arc::new(ServerMetrics::new());
+    /// let server = Server::builder()
+    ///     .name("search")
+    ///     .version("1.0.0")
+    ///     .metrics(metrics.clone())
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
     /// ```
-    #[cfg(feature = "schema-generation")]
-    pub fn tool_typed_with_output_and_description<TIn, TOut>(
-        mut self,
-        name: impl Into<String>,
-        description: impl Into<String>,
-        handler: impl Fn(
-                TIn,
-                crate::RequestHandlerExtra,
-            )
-                -> std::pin::Pin<Box<dyn std::future::Future<Output = crate::Result<TOut>> + Send>>
-            + Send
-            + Sync
-            + 'static,
-    ) -> Self
-    where
-        TIn: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
-        TOut: serde::Serialize + schemars::JsonSchema + Send + Sync + 'static,
-    {
-        use crate::server::typed_tool::TypedToolWithOutput;
-
-        let name_str = name.into();
-        let tool =
-            TypedToolWithOutput::new(name_str.clone(), handler).with_description(description);
-        self.tools.insert(name_str, Arc::new(tool));
+    #[cfg(feature = "prometheus")]
+    pub fn metrics(mut self, metrics: Arc<metrics::ServerMetrics>) -> Self {
+        self.metrics = Some(metrics);
         self
     }
 
-    /// Add a prompt handler.
-    ///
-    /// Registers a prompt that clients can retrieve via the prompts/get method.
-    /// Prompts provide templates that clients can use for various tasks.
-    ///
-    /// # Arguments
+    /// Drop connections whose client has gone silent.
     ///
-    /// * `name` - The name of the prompt (used by clients to retrieve it)
-    /// * `handler` - The handler implementation for this prompt
+    /// A server reads its transport one message at a time, so it cannot ping
+    /// the client while waiting for the next message. Instead it expects to
+    /// hear from the client, at least through the client's own keep-alive
+    /// pings (see [`Client::start_keep_alive`]), once every interval plus
+    /// the ping timeout. After `failure_threshold` such windows in a row
+    /// without a message, the transport is closed and the callback set with
+    /// [`on_keep_alive_timeout`](Self::on_keep_alive_timeout) runs. Time
+    /// spent running a handler does not count as silence.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use pmcp::{Server, PromptHandler, GetPromptResult, PromptMessage, MessageContent};
-    /// use async_trait::async_trait;
-    /// use std::collections::HashMap;
-    ///
-    /// struct CodeReviewPrompt;
-    ///
-    /// #[async_trait]
-    /// impl PromptHandler for CodeReviewPrompt {
-    ///     async fn handle(&self, args: HashMap<String, String>, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<GetPromptResult> {
-    ///         let language = args.get("language").map(|s| s.as_str()).unwrap_or("unknown");
-    ///         Ok(GetPromptResult {
-    ///             description: Some(format!("Code review prompt for {}", language)),
-    ///             messages: vec![PromptMessage {
-    ///                 role: pmcp::Role::User,
-    ///                 content: pmcp::Content::Text {
-    ///                     text: format!("Please review this {} code:", language),
-    ///                 },
-    ///             }],
-    ///         })
-    ///     }
-    /// }
+    /// use pmcp::shared::keepalive::KeepAliveConfig;
+    /// use pmcp::Server;
+    /// use std::time::Duration;
     ///
     /// let server = Server::builder()
-    ///     .name("code-server")
+    ///     .name("watched-server")
     ///     .version("1.0.0")
-    ///     .prompt("code_review", CodeReviewPrompt{})
+    ///     .keep_alive(KeepAliveConfig::default().with_interval(Duration::from_secs(60)))
+    ///     .on_keep_alive_timeout(|| eprintln!("client went away"))
     ///     .build()?;
     /// # Ok::<(), pmcp::Error>(())
     /// ```
-    pub fn prompt(
-        mut self,
-        name: impl Into<String>,
-        handler: impl PromptHandler + 'static,
-    ) -> Self {
-        self.prompts.insert(name.into(), Arc::new(handler));
+    ///
+    /// [`Client::start_keep_alive`]: crate::Client::start_keep_alive
+    pub fn keep_alive(mut self, config: crate::shared::KeepAliveConfig) -> Self {
+        self.keep_alive = Some(config);
         self
     }
 
-    /// Set the resource handler.
-    ///
-    /// Registers a resource handler that provides access to server resources.
-    /// Resources allow clients to read files, configurations, or other data.
-    ///
-    /// # Arguments
+    /// Run `callback` when the keep-alive drops a silent connection.
+    pub fn on_keep_alive_timeout(mut self, callback: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_keep_alive_timeout = Some(Arc::new(callback));
+        self
+    }
+
+    /// Run several requests at once, optionally capping individual tools.
     ///
-    /// * `handler` - The resource handler implementation
+    /// Without this, requests run one at a time. See [`concurrency`] for
+    /// details.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use pmcp::{Server, ResourceHandler, ReadResourceResult, ListResourcesResult, ResourceInfo};
-    /// use async_trait::async_trait;
-    ///
-    /// struct FileResourceHandler;
-    ///
-    /// #[async_trait]
-    /// impl ResourceHandler for FileResourceHandler {
-    ///     async fn read(&self, uri: &str, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<ReadResourceResult> {
-    ///         // Read file content...
-    ///         Ok(ReadResourceResult {
-    ///             contents: vec![pmcp::Content::Text {
-    ///                 text: "File content here".to_string(),
-    ///             }],
-    ///         })
-    ///     }
-    ///
-    ///     async fn list(&self, _cursor: Option<String>, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<ListResourcesResult> {
-    ///         Ok(ListResourcesResult {
-    ///             resources: vec![pmcp::ResourceInfo {
-    ///                 uri: "file://example.txt".to_string(),
-    ///                 name: "example.txt".to_string(),
-    ///                 description: Some("Example file".to_string()),
-    ///                 mime_type: Some("text/plain".to_string()),
-    ///             }],
-    ///             next_cursor: None,
-    ///         })
-    ///     }
-    /// }
+    /// use pmcp::server::concurrency::ConcurrencyConfig;
+    /// use pmcp::Server;
     ///
     /// let server = Server::builder()
-    ///     .name("file-server")
+    ///     .name("search")
     ///     .version("1.0.0")
-    ///     .resources(FileResourceHandler{})
+    ///     .concurrency(ConcurrencyConfig::new(8).tool_limit("crawl", 1))
     ///     .build()?;
     /// # Ok::<(), pmcp::Error>(())
     /// ```
-    pub fn resources(mut self, handler: impl ResourceHandler + 'static) -> Self {
-        self.resources = Some(Arc::new(handler));
+    pub fn concurrency(mut self, config: concurrency::ConcurrencyConfig) -> Self {
+        self.concurrency = config;
         self
     }
 
-    /// Set the sampling handler.
+    /// Run hooks around individual methods.
     ///
-    /// Registers a sampling handler that provides LLM functionality.
-    /// This allows the server to act as a language model provider.
+    /// Replaces any router set before. See [`router`] for details.
+    pub fn router(mut self, router: router::MethodRouter) -> Self {
+        self.router = router;
+        self
+    }
+
+    /// Register a hook for a method, e.g. `tools/call` or `resources/*`.
     ///
-    /// # Arguments
+    /// Shorthand for adding the hook to the [`router`](Self::router).
+    pub fn method_hook(mut self, method: &str, hook: impl router::MethodHook + 'static) -> Self {
+        self.router = self.router.on(method, hook);
+        self
+    }
+
+    /// Advertise that tools may be added or removed at runtime.
     ///
-    /// * `handler` - The sampling handler implementation
+    /// The `tools` capability is declared with `listChanged` even if no tool
+    /// is registered at build time, so clients know to expect
+    /// `notifications/tools/list_changed` from the [`Server::tools`]
+    /// registry.
+    pub fn dynamic_tools(mut self) -> Self {
+        self.dynamic_tools = true;
+        self
+    }
+
+    /// Push `notifications/resources/updated` when watched files change.
+    ///
+    /// When a client subscribes to a `file://` resource under the watcher's
+    /// base directory, the server starts tracking that file and notifies the
+    /// client after each debounced change. URIs are matched as
+    /// `file://` followed by the changed path, so the resource handler
+    /// (for example a [`ResourceCollection`](simple_resources::ResourceCollection)
+    /// or [`DynamicResourceHandler`](simple_resources::DynamicResourceHandler))
+    /// should use absolute paths in its URIs. The `resources` capability is
+    /// advertised with `subscribe`.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use pmcp::{Server, SamplingHandler, CreateMessageParams, CreateMessageResult};
-    /// use async_trait::async_trait;
-    ///
-    /// struct MockLLM;
-    ///
-    /// #[async_trait]
-    /// impl SamplingHandler for MockLLM {
-    ///     async fn create_message(&self, params: CreateMessageParams, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<CreateMessageResult> {
-    ///         // Process the messages and generate a response
-    ///         Ok(CreateMessageResult {
-    ///             content: pmcp::MessageContent::Text {
-    ///                 text: "Generated response".to_string(),
-    ///             },
-    ///             model: "mock-llm-v1".to_string(),
-    ///             usage: Some(pmcp::TokenUsage {
-    ///                 input_tokens: 10,
-    ///                 output_tokens: 5,
-    ///                 total_tokens: 15,
-    ///             }),
-    ///             stop_reason: Some("end_of_text".to_string()),
-    ///         })
-    ///     }
-    /// }
+    /// use pmcp::server::resource_watcher::ResourceWatcherBuilder;
+    /// use pmcp::{ResourceCollection, Server};
+    /// use std::time::Duration;
     ///
     /// let server = Server::builder()
-    ///     .name("llm-server")
+    ///     .name("docs")
     ///     .version("1.0.0")
-    ///     .sampling(MockLLM{})
+    ///     .resources(ResourceCollection::new())
+    ///     .watch_resources(
+    ///         ResourceWatcherBuilder::new()
+    ///             .base_dir("/srv/docs")
+    ///             .debounce(Duration::from_millis(200)),
+    ///     )
     ///     .build()?;
     /// # Ok::<(), pmcp::Error>(())
     /// ```
-    pub fn sampling(mut self, handler: impl SamplingHandler + 'static) -> Self {
-        self.sampling = Some(Arc::new(handler));
-        // Enable sampling capability
-        self.capabilities.sampling = Some(crate::types::SamplingCapabilities::default());
+    #[cfg(feature = "resource-watcher")]
+    pub fn watch_resources(mut self, watcher: resource_watcher::ResourceWatcherBuilder) -> Self {
+        self.resource_watcher = Some(watcher);
         self
     }
 
-    /// Build the server.
+    fn insert_tool(&mut self, name: String, handler: Arc<dyn ToolHandler>) {
+        if self.tools.contains_key(&name) && !self.duplicate_tools.contains(&name) {
+            self.duplicate_tools.push(name.clone());
+        }
+        self.tools.insert(name, handler);
+    }
+
+    /// Check the registered tools for problems.
+    ///
+    /// Reports duplicate tool names, missing descriptions (as warnings), and
+    /// input schemas that are not valid JSON Schema. `build()` runs the same
+    /// checks, logging warnings and failing on errors.
+    pub fn validate_tools(&self) -> Vec<tool_validation::ToolDiagnostic> {
+        let mut names: Vec<&String> = self.tools.keys().collect();
+        names.sort();
+        tool_validation::validate_tools(
+            names
+                .into_iter()
+                .map(|name| (name.as_str(), self.tools[name].metadata())),
+            &self.duplicate_tools,
+        )
+    }
+
+    /// Capabilities to advertise, derived from the registered handlers.
+    fn resolve_capabilities(&self) -> Result<ServerCapabilities> {
+        let mut capabilities = self.capabilities.clone();
+        if self.capabilities_overridden {
+            return Ok(capabilities);
+        }
+
+        let has_tools = !self.tools.is_empty() || self.dynamic_tools;
+        let has_prompts = !self.prompts.is_empty();
+        let has_resources = self.resources.is_some()
+            || !self.resource_templates.is_empty()
+            || !self.mounted_resources.is_empty();
+        let has_completions = has_prompts
+            || !self.resource_template_completions.is_empty()
+            || self.completion.is_some();
+
+        let unbacked: Vec<&str> = [
+            ("tools", capabilities.tools.is_some(), has_tools),
+            ("prompts", capabilities.prompts.is_some(), has_prompts),
+            ("resources", capabilities.resources.is_some(), has_resources),
+            (
+                "completions",
+                capabilities.completions.is_some(),
+                has_completions,
+            ),
+            (
+                "sampling",
+                capabilities.sampling.is_some(),
+                self.sampling.is_some(),
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, declared, backed)| *declared && !*backed)
+        .map(|(name, _, _)| name)
+        .collect();
+        if !unbacked.is_empty() {
+            return Err(crate::Error::validation(format!(
+                "Capabilities declared without a handler: {}. Register handlers or use \
+                 override_capabilities() to advertise them anyway",
+                unbacked.join(", ")
+            )));
+        }
+
+        if has_tools {
+            let tools = capabilities.tools.get_or_insert_with(Default::default);
+            if self.dynamic_tools {
+                tools.list_changed.get_or_insert(true);
+            }
+        }
+        if has_prompts {
+            capabilities.prompts.get_or_insert_with(Default::default);
+        }
+        if has_resources {
+            capabilities.resources.get_or_insert_with(Default::default);
+        }
+        #[cfg(feature = "resource-watcher")]
+        if let (Some(resources), Some(_)) = (&mut capabilities.resources, &self.resource_watcher) {
+            resources.subscribe.get_or_insert(true);
+        }
+        if !self.resource_template_completions.is_empty() || self.completion.is_some() {
+            capabilities
+                .completions
+                .get_or_insert_with(Default::default);
+        }
+        Ok(capabilities)
+    }
+
+    /// Add a tool handler.
     ///
-    /// Constructs the final Server instance from the configured builder.
-    /// This validates that required fields (name and version) are set.
+    /// Registers a tool that clients can call via the tools/call method.
+    /// Tools are the primary way servers provide functionality to clients.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the tool (used by clients to call it)
+    /// * `handler` - The handler implementation for this tool
     ///
     /// # Examples
     ///
@@ -1925,135 +3052,102 @@ impl ServerBuilder {
     /// use async_trait::async_trait;
     /// use serde_json::Value;
     ///
-    /// struct PingTool;
+    /// struct FileListTool;
     ///
     /// #[async_trait]
-    /// impl ToolHandler for PingTool {
-    ///     async fn handle(&self, _args: Value, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<Value> {
-    ///         Ok(serde_json::json!({"response": "pong"}))
+    /// impl ToolHandler for FileListTool {
+    ///     async fn handle(&self, args: Value, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<Value> {
+    ///         let path = args["path"].as_str().unwrap_or(".");
+    ///         // List files in path...
+    ///         Ok(serde_json::json!({"files": ["file1.txt", "file2.txt"]}))
     ///     }
     /// }
     ///
     /// let server = Server::builder()
-    ///     .name("ping-server")
-    ///     .version("1.0.0")
-    ///     .tool("ping", PingTool{})
-    ///     .build()?;
-    ///
-    /// // Server is now ready to run
-    /// // server.run_stdio().await?;
-    /// # Ok::<(), pmcp::Error>(())
-    /// ```
-    /// Set the authentication provider.
-    ///
-    /// Configures an authentication provider that will validate incoming requests.
-    /// When set, the server will use this provider to authenticate requests before
-    /// processing them.
-    ///
-    /// # Arguments
-    ///
-    /// * `provider` - The authentication provider implementation
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use pmcp::{Server, auth::ProxyProvider};
-    ///
-    /// let auth_provider = ProxyProvider::with_upstream("https://oauth.example.com");
-    ///
-    /// let server = Server::builder()
-    ///     .name("secure-server")
+    ///     .name("file-server")
     ///     .version("1.0.0")
-    ///     .auth_provider(auth_provider)
+    ///     .tool("list_files", FileListTool{})
     ///     .build()?;
     /// # Ok::<(), pmcp::Error>(())
     /// ```
-    pub fn auth_provider(mut self, provider: impl auth::AuthProvider + 'static) -> Self {
-        self.auth_provider = Some(Arc::new(provider));
+    pub fn tool(mut self, name: impl Into<String>, handler: impl ToolHandler + 'static) -> Self {
+        self.insert_tool(name.into(), Arc::new(handler));
         self
     }
 
-    /// Set the tool authorizer.
+    /// Add a type-safe tool handler with automatic schema generation.
     ///
-    /// Configures a tool authorizer for fine-grained access control.
-    /// The authorizer determines which tools authenticated users can access
-    /// based on their authentication context.
+    /// This method provides first-class support for creating tools with:
+    /// - Automatic JSON schema generation from Rust types
+    /// - Compile-time type safety
+    /// - Runtime validation
+    /// - Field descriptions from doc comments
     ///
-    /// # Arguments
-    ///
-    /// * `authorizer` - The tool authorization implementation
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use pmcp::{Server, auth::ScopeBasedAuthorizer};
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "schema-generation")]
+    /// # {
+    /// use pmcp::ServerBuilder;
+    /// use schemars::JsonSchema;
+    /// use serde::{Deserialize, Serialize};
     ///
-    /// let authorizer = ScopeBasedAuthorizer::new()
-    ///     .require_scopes("sensitive_tool", vec!["admin".to_string()])
-    ///     .default_scopes(vec!["read".to_string()]);
+    /// #[derive(Debug, Deserialize, Serialize, JsonSchema)]
+    /// struct EchoArgs {
+    ///     /// The message to echo
+    ///     message: String,
+    ///     /// Optional prefix
+    ///     prefix: Option<String>,
+    /// }
     ///
-    /// let server = Server::builder()
-    ///     .name("secure-server")
-    ///     .version("1.0.0")
-    ///     .tool_authorizer(authorizer)
-    ///     .build()?;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), pmcp::Error> {
+    /// let server = ServerBuilder::new()
+    ///     .name("example")
+    ///     .tool_typed("echo", |args: EchoArgs, _| {
+    ///         Box::pin(async move {
+    ///             let message = match args.prefix {
+    ///                 Some(p) => format!("{}: {}", p, args.message),
+    ///                 None => args.message,
+    ///             };
+    ///             Ok(serde_json::json!({ "message": message }))
+    ///         })
+    ///     })
+    ///     .build();
     /// # Ok::<(), pmcp::Error>(())
+    /// # }
+    /// # }
     /// ```
-    pub fn tool_authorizer(mut self, authorizer: impl auth::ToolAuthorizer + 'static) -> Self {
-        if !self.tool_protections.is_empty() {
-            // Log a warning or panic - for now we'll clear the protections with a warning
-            eprintln!("Warning: Setting a custom tool_authorizer clears any previous protect_tool() configurations");
-            self.tool_protections.clear();
-        }
-        self.tool_authorizer = Some(Arc::new(authorizer));
-        self
-    }
+    #[cfg(feature = "schema-generation")]
+    pub fn tool_typed<T, F, Fut>(mut self, name: impl Into<String>, handler: F) -> Self
+    where
+        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
+        F: Fn(T, crate::RequestHandlerExtra) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = crate::Result<serde_json::Value>> + Send + 'static,
+    {
+        use crate::server::typed_tool::TypedTool;
+        use std::pin::Pin;
 
-    /// Protect a specific tool with required scopes.
-    ///
-    /// This is a convenience method that creates or updates a scope-based authorizer
-    /// to require specific scopes for accessing the named tool.
-    ///
-    /// # Arguments
-    ///
-    /// * `tool_name` - The name of the tool to protect
-    /// * `scopes` - The required scopes for accessing this tool
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use pmcp::Server;
-    ///
-    /// let server = Server::builder()
-    ///     .name("secure-server")
-    ///     .version("1.0.0")
-    ///     .protect_tool("delete_data", vec!["admin".to_string(), "write".to_string()])
-    ///     .protect_tool("read_data", vec!["read".to_string()])
-    ///     .build()?;
-    /// # Ok::<(), pmcp::Error>(())
-    /// ```
-    pub fn protect_tool(mut self, tool_name: impl Into<String>, scopes: Vec<String>) -> Self {
-        // Store the tool protection requirements to be applied at build time
-        self.tool_protections.insert(tool_name.into(), scopes);
+        let name_str = name.into();
+
+        // Wrap the handler to return Pin<Box<dyn Future>>
+        let wrapped_handler = move |args: T,
+                                    extra: crate::RequestHandlerExtra|
+              -> Pin<
+            Box<dyn std::future::Future<Output = crate::Result<serde_json::Value>> + Send>,
+        > { Box::pin(handler(args, extra)) };
+
+        let tool = TypedTool::new(name_str.clone(), wrapped_handler);
+        self.insert_tool(name_str, Arc::new(tool));
         self
     }
 
-    /// Add a description to a tool (Note: Limited support).
-    ///
-    /// **Important**: Due to the immutable design of tool handlers, this method
-    /// cannot retroactively add descriptions to already-registered tools.
-    ///
-    /// **Recommended**: Use the `*_with_description` variants instead:
-    /// - `.tool_typed_with_description()`
-    /// - `.tool_typed_sync_with_description()`
-    /// - `.tool_typed_with_output_and_description()`
-    ///
-    /// This method is provided for API completeness but will log warnings
-    /// when used, encouraging migration to the preferred approaches.
+    /// Add a type-safe tool handler with automatic schema generation and description.
     ///
-    /// # Preferred Examples
+    /// This is a convenience overload that allows setting a description directly
+    /// without needing to chain `.with_description()`.
     ///
-    /// ```rust,no_run
+    /// # Example
+    /// ```no_run
     /// # #[cfg(feature = "schema-generation")]
     /// # {
     /// use pmcp::ServerBuilder;
@@ -2061,466 +3155,2548 @@ impl ServerBuilder {
     /// use serde::{Deserialize, Serialize};
     ///
     /// #[derive(Debug, Deserialize, Serialize, JsonSchema)]
-    /// struct MathArgs { a: f64, b: f64 }
+    /// struct EchoArgs {
+    ///     /// The message to echo
+    ///     message: String,
+    ///     /// Optional prefix
+    ///     prefix: Option<String>,
+    /// }
     ///
-    /// // Preferred: Use the direct description variants
     /// let server = ServerBuilder::new()
     ///     .name("example")
     ///     .tool_typed_with_description(
-    ///         "add",
-    ///         "Adds two numbers together",
-    ///         |args: MathArgs, _| {
+    ///         "echo",
+    ///         "Echoes back a message with an optional prefix",
+    ///         |args: EchoArgs, _| {
     ///             Box::pin(async move {
-    ///                 Ok(serde_json::json!({ "result": args.a + args.b }))
+    ///                 let message = match args.prefix {
+    ///                     Some(p) => format!("{}: {}", p, args.message),
+    ///                     None => args.message,
+    ///                 };
+    ///                 Ok(serde_json::json!({ "message": message }))
     ///             })
     ///         }
-    ///     )
-    ///     .build();
+    ///     );
     /// # }
     /// ```
-    #[deprecated(
-        since = "1.6.0",
-        note = "Use tool_typed_with_description() and similar variants instead"
-    )]
-    pub fn with_tool_description(
-        self,
-        tool_name: impl Into<String>,
+    #[cfg(feature = "schema-generation")]
+    pub fn tool_typed_with_description<T, F, Fut>(
+        mut self,
+        name: impl Into<String>,
         description: impl Into<String>,
-    ) -> Self {
-        let tool_name = tool_name.into();
-        let _description = description.into();
+        handler: F,
+    ) -> Self
+    where
+        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
+        F: Fn(T, crate::RequestHandlerExtra) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = crate::Result<serde_json::Value>> + Send + 'static,
+    {
+        use crate::server::typed_tool::TypedTool;
+        use std::pin::Pin;
 
-        tracing::warn!(
-            "with_tool_description('{}') called but cannot modify immutable tools. \
-            Use tool_typed_with_description() variants instead.",
-            tool_name
-        );
+        let name_str = name.into();
+
+        // Wrap the handler to return Pin<Box<dyn Future>>
+        let wrapped_handler = move |args: T,
+                                    extra: crate::RequestHandlerExtra|
+              -> Pin<
+            Box<dyn std::future::Future<Output = crate::Result<serde_json::Value>> + Send>,
+        > { Box::pin(handler(args, extra)) };
 
+        let tool = TypedTool::new(name_str.clone(), wrapped_handler).with_description(description);
+        self.insert_tool(name_str, Arc::new(tool));
         self
     }
 
-    /// Build the server.
+    /// Add a synchronous type-safe tool handler with automatic schema generation.
     ///
-    /// Constructs the final Server instance from the configured builder.
-    /// This validates that required fields (name and version) are set.
+    /// Similar to `tool_typed` but for synchronous handlers.
     ///
-    /// # Errors
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "schema-generation")]
+    /// # {
+    /// use pmcp::ServerBuilder;
+    /// use schemars::JsonSchema;
+    /// use serde::{Deserialize, Serialize};
     ///
-    /// Returns an error if:
-    /// - The server name is not set
-    /// - The server version is not set
-    pub fn build(self) -> Result<Server> {
-        let name = self
-            .name
-            .ok_or_else(|| crate::Error::validation("Server name is required"))?;
-        let version = self
-            .version
-            .ok_or_else(|| crate::Error::validation("Server version is required"))?;
+    /// #[derive(Debug, Deserialize, Serialize, JsonSchema)]
+    /// struct MathArgs {
+    ///     /// First number
+    ///     a: f64,
+    ///     /// Second number
+    ///     b: f64,
+    ///     /// Operation to perform
+    ///     op: String,
+    /// }
+    ///
+    /// # fn main() -> Result<(), pmcp::Error> {
+    /// let server = ServerBuilder::new()
+    ///     .name("example")
+    ///     .tool_typed_sync("calculator", |args: MathArgs, _| {
+    ///         let result = match args.op.as_str() {
+    ///             "add" => args.a + args.b,
+    ///             "subtract" => args.a - args.b,
+    ///             "multiply" => args.a * args.b,
+    ///             "divide" => args.a / args.b,
+    ///             _ => return Err(pmcp::Error::Validation("Unknown operation".into())),
+    ///         };
+    ///         Ok(serde_json::json!({ "result": result }))
+    ///     })
+    ///     .build();
+    /// # Ok::<(), pmcp::Error>(())
+    /// # }
+    /// # }
+    /// ```
+    #[cfg(feature = "schema-generation")]
+    pub fn tool_typed_sync<T, F>(mut self, name: impl Into<String>, handler: F) -> Self
+    where
+        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
+        F: Fn(T, crate::RequestHandlerExtra) -> crate::Result<serde_json::Value>
+            + Send
+            + Sync
+            + 'static,
+    {
+        use crate::server::typed_tool::TypedSyncTool;
+        let name_str = name.into();
+        let tool = TypedSyncTool::new(name_str.clone(), handler);
+        self.insert_tool(name_str, Arc::new(tool));
+        self
+    }
 
-        // Apply tool protections
-        let tool_authorizer = if !self.tool_protections.is_empty() {
-            if self.tool_authorizer.is_some() {
-                // If there's an existing authorizer and tool protections are specified,
-                // this is a configuration error
-                return Err(crate::Error::validation(
-                    "Cannot use protect_tool() with a custom tool_authorizer. \
-                     Either use protect_tool() to configure scope-based authorization, \
-                     or provide a custom ToolAuthorizer implementation, but not both.",
-                ));
-            }
-            // Create a ScopeBasedAuthorizer with all the tool protections
-            let mut authorizer = auth::ScopeBasedAuthorizer::new();
+    /// Add a synchronous type-safe tool handler with automatic schema generation and description.
+    ///
+    /// This is a convenience overload that allows setting a description directly
+    /// without needing to chain `.with_description()`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "schema-generation")]
+    /// # {
+    /// use pmcp::ServerBuilder;
+    /// use schemars::JsonSchema;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Deserialize, Serialize, JsonSchema)]
+    /// struct MathArgs {
+    ///     /// First number
+    ///     a: f64,
+    ///     /// Second number
+    ///     b: f64,
+    ///     /// Operation to perform
+    ///     op: String,
+    /// }
+    ///
+    /// let server = ServerBuilder::new()
+    ///     .name("example")
+    ///     .tool_typed_sync_with_description(
+    ///         "calculator",
+    ///         "Performs synchronous mathematical operations",
+    ///         |args: MathArgs, _| {
+    ///             let result = match args.op.as_str() {
+    ///                 "add" => args.a + args.b,
+    ///                 "subtract" => args.a - args.b,
+    ///                 "multiply" => args.a * args.b,
+    ///                 "divide" => args.a / args.b,
+    ///                 _ => return Err(pmcp::Error::Validation("Unknown operation".into())),
+    ///             };
+    ///             Ok(serde_json::json!({ "result": result }))
+    ///         }
+    ///     );
+    /// # }
+    /// ```
+    #[cfg(feature = "schema-generation")]
+    pub fn tool_typed_sync_with_description<T, F>(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: F,
+    ) -> Self
+    where
+        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
+        F: Fn(T, crate::RequestHandlerExtra) -> crate::Result<serde_json::Value>
+            + Send
+            + Sync
+            + 'static,
+    {
+        use crate::server::typed_tool::TypedSyncTool;
+        let name_str = name.into();
+        let tool = TypedSyncTool::new(name_str.clone(), handler).with_description(description);
+        self.insert_tool(name_str, Arc::new(tool));
+        self
+    }
+
+    /// Add a type-safe tool handler with both input and output typing.
+    ///
+    /// This method provides full type safety for both input and output types,
+    /// which is useful for testing, documentation, and API contracts.
+    /// Note that output schemas are not part of the MCP protocol but can be
+    /// valuable for development and integration testing.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `TIn` - Input type that implements `JsonSchema`, `Deserialize`, `Send`, `Sync`
+    /// * `TOut` - Output type that implements `JsonSchema`, `Serialize`, `Send`, `Sync`
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "schema-generation")]
+    /// # {
+    /// use pmcp::{ServerBuilder, TypedToolWithOutput};
+    /// use schemars::JsonSchema;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(JsonSchema, Deserialize)]
+    /// struct MathInput { a: f64, b: f64, op: String }
+    ///
+    /// #[derive(JsonSchema, Serialize)]
+    /// struct MathOutput { result: f64, operation: String }
+    ///
+    /// let server = ServerBuilder::new()
+    ///     .name("example")
+    ///     .tool_typed_with_output::<MathInput, MathOutput>("math", |args, _| {
+    ///         Box::pin(async move {
+    ///             let result = match args.op.as_str() {
+    ///                 "add" => args.a + args.b,
+    ///                 "subtract" => args.a - args.b,
+    ///                 _ => return Err(pmcp::Error::Validation("Unknown operation".into())),
+    ///             };
+    ///             Ok(MathOutput {
+    ///                 result,
+    ///                 operation: args.op,
+    ///             })
+    ///         })
+    ///     });
+    /// # }
+    /// ```
+    #[cfg(feature = "schema-generation")]
+    pub fn tool_typed_with_output<TIn, TOut>(
+        mut self,
+        name: impl Into<String>,
+        handler: impl Fn(
+                TIn,
+                crate::RequestHandlerExtra,
+            )
+                -> std::pin::Pin<Box<dyn std::future::Future<Output = crate::Result<TOut>> + Send>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self
+    where
+        TIn: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
+        TOut: serde::Serialize + schemars::JsonSchema + Send + Sync + 'static,
+    {
+        use crate::server::typed_tool::TypedToolWithOutput;
+
+        let name_str = name.into();
+        let tool = TypedToolWithOutput::new(name_str.clone(), handler);
+        self.insert_tool(name_str, Arc::new(tool));
+        self
+    }
+
+    /// Add a type-safe tool handler with both input and output typing and description.
+    ///
+    /// This is a convenience overload that allows setting a description directly
+    /// without needing to chain `.with_description()`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "schema-generation")]
+    /// # {
+    /// use pmcp::ServerBuilder;
+    /// use schemars::JsonSchema;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(JsonSchema, Deserialize)]
+    /// struct MathInput { a: f64, b: f64, op: String }
+    ///
+    /// #[derive(JsonSchema, Serialize)]
+    /// struct MathOutput { result: f64, operation: String }
+    ///
+    /// let server = ServerBuilder::new()
+    ///     .name("example")
+    ///     .tool_typed_with_output_and_description::<MathInput, MathOutput>(
+    ///         "math",
+    ///         "Performs basic mathematical operations on two numbers",
+    ///         |args, _| {
+    ///             Box::pin(async move {
+    ///                 let result = match args.op.as_str() {
+    ///                     "add" => args.a + args.b,
+    ///                     "subtract" => args.a - args.b,
+    ///                     _ => return Err(pmcp::Error::Validation("Unknown operation".into())),
+    ///                 };
+    ///                 Ok(MathOutput { result, operation: args.op })
+    ///             })
+    ///         }
+    ///     );
+    /// # }
+    /// ```
+    #[cfg(feature = "schema-generation")]
+    pub fn tool_typed_with_output_and_description<TIn, TOut>(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: impl Fn(
+                TIn,
+                crate::RequestHandlerExtra,
+            )
+                -> std::pin::Pin<Box<dyn std::future::Future<Output = crate::Result<TOut>> + Send>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self
+    where
+        TIn: serde::de::DeserializeOwned + schemars::JsonSchema + Send + Sync + 'static,
+        TOut: serde::Serialize + schemars::JsonSchema + Send + Sync + 'static,
+    {
+        use crate::server::typed_tool::TypedToolWithOutput;
+
+        let name_str = name.into();
+        let tool =
+            TypedToolWithOutput::new(name_str.clone(), handler).with_description(description);
+        self.insert_tool(name_str, Arc::new(tool));
+        self
+    }
+
+    /// Add a prompt handler.
+    ///
+    /// Registers a prompt that clients can retrieve via the prompts/get method.
+    /// Prompts provide templates that clients can use for various tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the prompt (used by clients to retrieve it)
+    /// * `handler` - The handler implementation for this prompt
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Server, PromptHandler, GetPromptResult, PromptMessage, MessageContent};
+    /// use async_trait::async_trait;
+    /// use std::collections::HashMap;
+    ///
+    /// struct CodeReviewPrompt;
+    ///
+    /// #[async_trait]
+    /// impl PromptHandler for CodeReviewPrompt {
+    ///     async fn handle(&self, args: HashMap<String, String>, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<GetPromptResult> {
+    ///         let language = args.get("language").map(|s| s.as_str()).unwrap_or("unknown");
+    ///         Ok(GetPromptResult {
+    ///             description: Some(format!("Code review prompt for {}", language)),
+    ///             messages: vec![PromptMessage {
+    ///                 role: pmcp::Role::User,
+    ///                 content: pmcp::Content::Text {
+    ///                     text: format!("Please review this {} code:", language),
+    ///                 },
+    ///             }],
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let server = Server::builder()
+    ///     .name("code-server")
+    ///     .version("1.0.0")
+    ///     .prompt("code_review", CodeReviewPrompt{})
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn prompt(
+        mut self,
+        name: impl Into<String>,
+        handler: impl PromptHandler + 'static,
+    ) -> Self {
+        self.prompts.insert(name.into(), Arc::new(handler));
+        self
+    }
+
+    /// Set the resource handler.
+    ///
+    /// Registers a resource handler that provides access to server resources.
+    /// Resources allow clients to read files, configurations, or other data.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The resource handler implementation
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Server, ResourceHandler, ReadResourceResult, ListResourcesResult, ResourceInfo};
+    /// use async_trait::async_trait;
+    ///
+    /// struct FileResourceHandler;
+    ///
+    /// #[async_trait]
+    /// impl ResourceHandler for FileResourceHandler {
+    ///     async fn read(&self, uri: &str, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<ReadResourceResult> {
+    ///         // Read file content...
+    ///         Ok(ReadResourceResult {
+    ///             contents: vec![pmcp::Content::Text {
+    ///                 text: "File content here".to_string(),
+    ///             }],
+    ///             meta: None,
+    ///         })
+    ///     }
+    ///
+    ///     async fn list(&self, _cursor: Option<String>, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<ListResourcesResult> {
+    ///         Ok(ListResourcesResult {
+    ///             resources: vec![pmcp::ResourceInfo {
+    ///                 uri: "file://example.txt".to_string(),
+    ///                 name: "example.txt".to_string(),
+    ///                 description: Some("Example file".to_string()),
+    ///                 mime_type: Some("text/plain".to_string()),
+    ///             }],
+    ///             next_cursor: None,
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let server = Server::builder()
+    ///     .name("file-server")
+    ///     .version("1.0.0")
+    ///     .resources(FileResourceHandler{})
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn resources(mut self, handler: impl ResourceHandler + 'static) -> Self {
+        self.resources = Some(Arc::new(handler));
+        self
+    }
+
+    /// Mount the tools, prompts and resources of `server` under `namespace`.
+    ///
+    /// Tools and prompts are exposed as `namespace.name` and resource URIs as
+    /// `namespace+uri`; requests for them are routed to the mounted handlers
+    /// under their original names. Capabilities follow from the merged
+    /// handlers. Only the handlers are mounted: settings of the mounted
+    /// server, such as authorization, argument rules and resource templates,
+    /// do not carry over. See [`compose`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::server::simple_tool::SyncTool;
+    /// use pmcp::Server;
+    ///
+    /// let fs = Server::builder()
+    ///     .name("fs")
+    ///     .version("1.0.0")
+    ///     .tool("read_file", SyncTool::new("read_file", Ok))
+    ///     .build()?;
+    ///
+    /// let server = Server::builder()
+    ///     .name("workspace")
+    ///     .version("1.0.0")
+    ///     .mount("fs", fs) // serves `fs.read_file`
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn mount(mut self, namespace: impl Into<String>, server: Server) -> Self {
+        let namespace = namespace.into();
+        for (name, handler) in server.tools.entries() {
+            let name = compose::namespaced(&namespace, &name);
+            let mounted = compose::MountedTool::new(name.clone(), handler);
+            self.insert_tool(name, Arc::new(mounted));
+        }
+        for (name, handler) in server.prompts {
+            let name = compose::namespaced(&namespace, &name);
+            let mounted = compose::MountedPrompt::new(name.clone(), handler);
+            self.prompts.insert(name, Arc::new(mounted));
+        }
+        if let Some(resources) = server.resources {
+            self.mounted_resources.push((namespace, resources));
+        }
+        self
+    }
+
+    /// Register a parameterized resource template.
+    ///
+    /// Templates are advertised through `resources/templates/list` so clients
+    /// can discover resources whose URIs follow an RFC 6570 pattern. Reading
+    /// the expanded URIs is still served by the resource handler. Like a
+    /// resource handler, a template enables the `resources` capability, even
+    /// on a server without one. Invalid or duplicate templates are reported by
+    /// `build()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::Server;
+    ///
+    /// let server = Server::builder()
+    ///     .name("log-server")
+    ///     .version("1.0.0")
+    ///     .resource_template(
+    ///         "file:///logs/{date}.log",
+    ///         "Daily log",
+    ///         "Application log for a given date",
+    ///         Some("text/plain".to_string()),
+    ///     )
+    ///     .resource_template_completions(
+    ///         "file:///logs/{date}.log",
+    ///         "date",
+    ///         vec!["2024-01-01".to_string(), "2024-01-02".to_string()],
+    ///     )
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn resource_template(
+        mut self,
+        uri_template: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        mime_type: Option<String>,
+    ) -> Self {
+        self.resource_templates
+            .push(crate::types::ResourceTemplate {
+                uri_template: uri_template.into(),
+                name: name.into(),
+                description: Some(description.into()),
+                mime_type,
+            });
+        self
+    }
+
+    /// Route reads of templated resources to a handler per template.
+    ///
+    /// The router's templates are advertised in `resources/templates/list`.
+    /// Reads of URIs matching no template go to the handler set with
+    /// [`resources`](Self::resources), if any. See
+    /// [`ResourceTemplateRouter`](resource_templates::ResourceTemplateRouter).
+    pub fn resource_template_router(
+        mut self,
+        router: resource_templates::ResourceTemplateRouter,
+    ) -> Self {
+        self.resource_templates.extend(router.templates());
+        self.resource_template_router = Some(router);
+        self
+    }
+
+    /// Set the completion values for a resource template variable.
+    ///
+    /// These values answer `completion/complete` requests that reference the
+    /// template with `ref/resource`, filtered by the prefix typed so far.
+    pub fn resource_template_completions(
+        mut self,
+        uri_template: impl Into<String>,
+        variable: impl Into<String>,
+        values: Vec<String>,
+    ) -> Self {
+        self.resource_template_completions
+            .push((uri_template.into(), variable.into(), values));
+        self
+    }
+
+    /// Set the handler for `completion/complete` requests.
+    ///
+    /// The provider answers all completion requests, replacing the values
+    /// set with [`resource_template_completions`](Self::resource_template_completions),
+    /// and enables the `completions` capability. See
+    /// [`CompletionRouter`](completion::CompletionRouter) for routing by
+    /// prompt argument and template variable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::server::completion::{prefix_matches, CompletionRouter};
+    /// use pmcp::Server;
+    ///
+    /// let server = Server::builder()
+    ///     .name("server")
+    ///     .version("1.0.0")
+    ///     .completion_provider(
+    ///         CompletionRouter::new()
+    ///             .prompt_argument("review", "language", prefix_matches(["rust", "go"])),
+    ///     )
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn completion_provider(
+        mut self,
+        handler: impl completion::CompletionHandler + 'static,
+    ) -> Self {
+        self.completion = Some(Arc::new(handler));
+        self
+    }
+
+    /// Set the sampling handler.
+    ///
+    /// Registers a sampling handler that provides LLM functionality.
+    /// This allows the server to act as a language model provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The sampling handler implementation
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Server, SamplingHandler, CreateMessageParams, CreateMessageResult};
+    /// use async_trait::async_trait;
+    ///
+    /// struct MockLLM;
+    ///
+    /// #[async_trait]
+    /// impl SamplingHandler for MockLLM {
+    ///     async fn create_message(&self, params: CreateMessageParams, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<CreateMessageResult> {
+    ///         // Process the messages and generate a response
+    ///         Ok(CreateMessageResult {
+    ///             content: pmcp::MessageContent::Text {
+    ///                 text: "Generated response".to_string(),
+    ///             },
+    ///             model: "mock-llm-v1".to_string(),
+    ///             usage: Some(pmcp::TokenUsage {
+    ///                 input_tokens: 10,
+    ///                 output_tokens: 5,
+    ///                 total_tokens: 15,
+    ///             }),
+    ///             stop_reason: Some("end_of_text".to_string()),
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let server = Server::builder()
+    ///     .name("llm-server")
+    ///     .version("1.0.0")
+    ///     .sampling(MockLLM{})
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn sampling(mut self, handler: impl SamplingHandler + 'static) -> Self {
+        self.sampling = Some(Arc::new(handler));
+        // Enable sampling capability
+        self.capabilities.sampling = Some(crate::types::SamplingCapabilities::default());
+        self
+    }
+
+    /// Build the server.
+    ///
+    /// Constructs the final Server instance from the configured builder.
+    /// This validates that required fields (name and version) are set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Server, ToolHandler};
+    /// use async_trait::async_trait;
+    /// use serde_json::Value;
+    ///
+    /// struct PingTool;
+    ///
+    /// #[async_trait]
+    /// impl ToolHandler for PingTool {
+    ///     async fn handle(&self, _args: Value, _extra: pmcp::RequestHandlerExtra) -> pmcp::Result<Value> {
+    ///         Ok(serde_json::json!({"response": "pong"}))
+    ///     }
+    /// }
+    ///
+    /// let server = Server::builder()
+    ///     .name("ping-server")
+    ///     .version("1.0.0")
+    ///     .tool("ping", PingTool{})
+    ///     .build()?;
+    ///
+    /// // Server is now ready to run
+    /// // server.run_stdio().await?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    /// Set the authentication provider.
+    ///
+    /// Configures an authentication provider that will validate incoming requests.
+    /// When set, the server will use this provider to authenticate requests before
+    /// processing them.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The authentication provider implementation
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Server, auth::ProxyProvider};
+    ///
+    /// let auth_provider = ProxyProvider::with_upstream("https://oauth.example.com");
+    ///
+    /// let server = Server::builder()
+    ///     .name("secure-server")
+    ///     .version("1.0.0")
+    ///     .auth_provider(auth_provider)
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn auth_provider(mut self, provider: impl auth::AuthProvider + 'static) -> Self {
+        self.auth_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Set the tool authorizer.
+    ///
+    /// Configures a tool authorizer for fine-grained access control.
+    /// The authorizer determines which tools authenticated users can access
+    /// based on their authentication context.
+    ///
+    /// # Arguments
+    ///
+    /// * `authorizer` - The tool authorization implementation
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::{Server, auth::ScopeBasedAuthorizer};
+    ///
+    /// let authorizer = ScopeBasedAuthorizer::new()
+    ///     .require_scopes("sensitive_tool", vec!["admin".to_string()])
+    ///     .default_scopes(vec!["read".to_string()]);
+    ///
+    /// let server = Server::builder()
+    ///     .name("secure-server")
+    ///     .version("1.0.0")
+    ///     .tool_authorizer(authorizer)
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn tool_authorizer(mut self, authorizer: impl auth::ToolAuthorizer + 'static) -> Self {
+        if !self.tool_protections.is_empty() {
+            // Log a warning or panic - for now we'll clear the protections with a warning
+            eprintln!("Warning: Setting a custom tool_authorizer clears any previous protect_tool() configurations");
+            self.tool_protections.clear();
+        }
+        self.tool_authorizer = Some(Arc::new(authorizer));
+        self
+    }
+
+    /// Protect a specific tool with required scopes.
+    ///
+    /// This is a convenience method that creates or updates a scope-based authorizer
+    /// to require specific scopes for accessing the named tool.
+    ///
+    /// # Arguments
+    ///
+    /// * `tool_name` - The name of the tool to protect
+    /// * `scopes` - The required scopes for accessing this tool
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::Server;
+    ///
+    /// let server = Server::builder()
+    ///     .name("secure-server")
+    ///     .version("1.0.0")
+    ///     .protect_tool("delete_data", vec!["admin".to_string(), "write".to_string()])
+    ///     .protect_tool("read_data", vec!["read".to_string()])
+    ///     .build()?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn protect_tool(mut self, tool_name: impl Into<String>, scopes: Vec<String>) -> Self {
+        // Store the tool protection requirements to be applied at build time
+        self.tool_protections.insert(tool_name.into(), scopes);
+        self
+    }
+
+    /// Add a description to a tool (Note: Limited support).
+    ///
+    /// **Important**: Due to the immutable design of tool handlers, this method
+    /// cannot retroactively add descriptions to already-registered tools.
+    ///
+    /// **Recommended**: Use the `*_with_description` variants instead:
+    /// - `.tool_typed_with_description()`
+    /// - `.tool_typed_sync_with_description()`
+    /// - `.tool_typed_with_output_and_description()`
+    ///
+    /// This method is provided for API completeness but will log warnings
+    /// when used, encouraging migration to the preferred approaches.
+    ///
+    /// # Preferred Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "schema-generation")]
+    /// # {
+    /// use pmcp::ServerBuilder;
+    /// use schemars::JsonSchema;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Deserialize, Serialize, JsonSchema)]
+    /// struct MathArgs { a: f64, b: f64 }
+    ///
+    /// // Preferred: Use the direct description variants
+    /// let server = ServerBuilder::new()
+    ///     .name("example")
+    ///     .tool_typed_with_description(
+    ///         "add",
+    ///         "Adds two numbers together",
+    ///         |args: MathArgs, _| {
+    ///             Box::pin(async move {
+    ///                 Ok(serde_json::json!({ "result": args.a + args.b }))
+    ///             })
+    ///         }
+    ///     )
+    ///     .build();
+    /// # }
+    /// ```
+    #[deprecated(
+        since = "1.6.0",
+        note = "Use tool_typed_with_description() and similar variants instead"
+    )]
+    pub fn with_tool_description(
+        self,
+        tool_name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        let tool_name = tool_name.into();
+        let _description = description.into();
+
+        tracing::warn!(
+            "with_tool_description('{}') called but cannot modify immutable tools. \
+            Use tool_typed_with_description() variants instead.",
+            tool_name
+        );
+
+        self
+    }
+
+    /// Build the server.
+    ///
+    /// Constructs the final Server instance from the configured builder.
+    /// This validates that required fields (name and version) are set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The server name is not set
+    /// - The server version is not set
+    /// - A declared capability has no handler to back it
+    /// - Tool registrations have errors (see [`validate_tools`](Self::validate_tools))
+    pub fn build(mut self) -> Result<Server> {
+        let capabilities = self.resolve_capabilities()?;
+
+        let (tool_errors, tool_warnings): (Vec<_>, Vec<_>) = self
+            .validate_tools()
+            .into_iter()
+            .partition(|d| d.severity == tool_validation::DiagnosticSeverity::Error);
+        for warning in &tool_warnings {
+            tracing::warn!("{}", warning);
+        }
+        if !tool_errors.is_empty() {
+            let details: Vec<String> = tool_errors.iter().map(ToString::to_string).collect();
+            return Err(crate::Error::validation(format!(
+                "Invalid tool registrations:\n{}",
+                details.join("\n")
+            )));
+        }
+
+        let name = self
+            .name
+            .ok_or_else(|| crate::Error::validation("Server name is required"))?;
+        let version = self
+            .version
+            .ok_or_else(|| crate::Error::validation("Server version is required"))?;
+
+        for (prompt_name, prompt) in &self.prompts {
+            prompt.validate().map_err(|e| {
+                crate::Error::validation(format!("Invalid prompt '{}': {}", prompt_name, e))
+            })?;
+        }
+
+        let mut resource_templates = resource_templates::ResourceTemplateRegistry::new();
+        for template in self.resource_templates {
+            resource_templates.register(template)?;
+        }
+        for (uri_template, variable, values) in self.resource_template_completions {
+            resource_templates.set_completions(&uri_template, &variable, values)?;
+        }
+        if !self.mounted_resources.is_empty() {
+            let mounts = std::mem::take(&mut self.mounted_resources);
+            self.resources = Some(Arc::new(compose::MountedResources::new(
+                mounts,
+                self.resources.take(),
+            )));
+        }
+        if let Some(mut router) = self.resource_template_router.take() {
+            router.set_fallback(self.resources.take());
+            self.resources = Some(Arc::new(router));
+        }
+
+        // Apply tool protections
+        let tool_authorizer = if !self.tool_protections.is_empty() {
+            if self.tool_authorizer.is_some() {
+                // If there's an existing authorizer and tool protections are specified,
+                // this is a configuration error
+                return Err(crate::Error::validation(
+                    "Cannot use protect_tool() with a custom tool_authorizer. \
+                     Either use protect_tool() to configure scope-based authorization, \
+                     or provide a custom ToolAuthorizer implementation, but not both.",
+                ));
+            }
+            // Create a ScopeBasedAuthorizer with all the tool protections
+            let mut authorizer = auth::ScopeBasedAuthorizer::new();
             for (tool_name, scopes) in self.tool_protections {
                 authorizer = authorizer.require_scopes(tool_name, scopes);
             }
-            Some(Arc::new(authorizer) as Arc<dyn auth::ToolAuthorizer>)
-        } else {
-            self.tool_authorizer
+            Some(Arc::new(authorizer) as Arc<dyn auth::ToolAuthorizer>)
+        } else {
+            self.tool_authorizer
+        };
+
+        #[cfg(feature = "resource-watcher")]
+        let (resource_watcher, resource_events) = match self.resource_watcher {
+            Some(builder) => {
+                let (tx, rx) = mpsc::channel(100);
+                (Some(builder.build(tx)?), Some(rx))
+            },
+            None => (None, None),
+        };
+
+        Ok(Server {
+            info: Implementation { name, version },
+            capabilities,
+            tools: tool_registry::ToolRegistry::from_map(self.tools),
+            prompts: self.prompts,
+            resources: self.resources,
+            resource_templates: Arc::new(resource_templates),
+            completion: self.completion,
+            sampling: self.sampling,
+            client_capabilities: Arc::new(RwLock::new(None)),
+            initialized: Arc::new(RwLock::new(false)),
+            notification_tx: None,
+            cancellation_manager: self.cancellation_manager,
+            roots_manager: Arc::new(RwLock::new(self.roots_manager)),
+            subscription_manager: Arc::new(RwLock::new(subscriptions::SubscriptionManager::new())),
+            elicitation_manager: None,
+            auth_provider: self.auth_provider,
+            tool_authorizer,
+            dispatch_metrics: Arc::new(DispatchMetrics::new()),
+            timing_meta: self.timing_meta,
+            request_guard: self.request_guard,
+            validate_tool_output: self.validate_tool_output,
+            tool_rules: self.tool_rules,
+            argument_coercion: self.argument_coercion,
+            version_negotiator: self.version_negotiator,
+            idempotency: self.idempotency,
+            rate_limiter: self.rate_limiter,
+            audit_logger: self.audit_logger,
+            audit_payload_limit: self.audit_payload_limit,
+            #[cfg(feature = "prometheus")]
+            metrics: self.metrics,
+            logger: logging::ServerLogger::default(),
+            router: self.router,
+            client_requests: client_requests::ClientRequester::new(),
+            #[cfg(feature = "opentelemetry")]
+            transport_type: std::sync::OnceLock::new(),
+            keep_alive: self.keep_alive,
+            on_keep_alive_timeout: self.on_keep_alive_timeout,
+            tool_permits: concurrency::ToolPermits::new(&self.concurrency),
+            concurrency: self.concurrency,
+            #[cfg(feature = "websocket")]
+            websocket_clients: std::sync::OnceLock::new(),
+            #[cfg(feature = "resource-watcher")]
+            resource_watcher,
+            #[cfg(feature = "resource-watcher")]
+            resource_events,
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for ServerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::Transport;
+    use crate::types::{
+        jsonrpc::ResponsePayload, ClientCapabilities, InitializeRequest, ServerCapabilities,
+        ToolCapabilities, TransportMessage,
+    };
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use tokio::time::timeout;
+
+    /// Mock transport for testing
+    #[derive(Debug)]
+    struct MockTransport {
+        messages: Arc<Mutex<Vec<TransportMessage>>>,
+        responses: Arc<Mutex<Vec<TransportMessage>>>,
+    }
+
+    impl MockTransport {
+        #[allow(dead_code)]
+        fn new() -> Self {
+            Self {
+                messages: Arc::new(Mutex::new(Vec::new())),
+                responses: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+
+        fn with_requests(requests: Vec<TransportMessage>) -> Self {
+            Self {
+                messages: Arc::new(Mutex::new(requests)),
+                responses: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+
+        #[allow(dead_code)]
+        fn add_request(&self, request: TransportMessage) {
+            self.messages.lock().unwrap().push(request);
+        }
+
+        #[allow(dead_code)]
+        fn get_sent_responses(&self) -> Vec<TransportMessage> {
+            self.responses.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl Transport for MockTransport {
+        async fn send(&mut self, message: TransportMessage) -> Result<()> {
+            self.responses.lock().unwrap().push(message);
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<TransportMessage> {
+            let mut messages = self.messages.lock().unwrap();
+            messages
+                .pop()
+                .map_or_else(|| Err(Error::protocol_msg("No more messages")), Ok)
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            !self.messages.lock().unwrap().is_empty()
+        }
+
+        fn transport_type(&self) -> &'static str {
+            "mock"
+        }
+    }
+
+    /// Mock tool handler for testing
+    struct MockTool {
+        result: Value,
+    }
+
+    impl MockTool {
+        fn new(result: Value) -> Self {
+            Self { result }
+        }
+    }
+
+    #[async_trait]
+    impl ToolHandler for MockTool {
+        async fn handle(
+            &self,
+            _args: Value,
+            _extra: crate::server::cancellation::RequestHandlerExtra,
+        ) -> Result<Value> {
+            Ok(self.result.clone())
+        }
+    }
+
+    /// Mock prompt handler for testing
+    struct MockPrompt {
+        result: crate::types::GetPromptResult,
+    }
+
+    impl MockPrompt {
+        fn new(result: crate::types::GetPromptResult) -> Self {
+            Self { result }
+        }
+    }
+
+    #[async_trait]
+    impl PromptHandler for MockPrompt {
+        async fn handle(
+            &self,
+            _args: HashMap<String, String>,
+            _extra: crate::server::cancellation::RequestHandlerExtra,
+        ) -> Result<crate::types::GetPromptResult> {
+            Ok(self.result.clone())
+        }
+    }
+
+    /// Mock resource handler for testing
+    struct MockResource {
+        resources: Vec<crate::types::ResourceInfo>,
+        contents: HashMap<String, crate::types::ReadResourceResult>,
+    }
+
+    impl MockResource {
+        fn new() -> Self {
+            Self {
+                resources: Vec::new(),
+                contents: HashMap::new(),
+            }
+        }
+
+        fn with_resource(mut self, uri: String, content: crate::types::ReadResourceResult) -> Self {
+            self.contents.insert(uri, content);
+            self
+        }
+    }
+
+    #[async_trait]
+    impl ResourceHandler for MockResource {
+        async fn read(
+            &self,
+            uri: &str,
+            _extra: crate::server::cancellation::RequestHandlerExtra,
+        ) -> Result<crate::types::ReadResourceResult> {
+            self.contents
+                .get(uri)
+                .cloned()
+                .ok_or_else(|| Error::not_found(format!("Resource '{}' not found", uri)))
+        }
+
+        async fn list(
+            &self,
+            _cursor: Option<String>,
+            _extra: crate::server::cancellation::RequestHandlerExtra,
+        ) -> Result<crate::types::ListResourcesResult> {
+            Ok(crate::types::ListResourcesResult {
+                resources: self.resources.clone(),
+                next_cursor: None,
+            })
+        }
+    }
+
+    #[test]
+    fn test_server_builder() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .capabilities(ServerCapabilities::tools_only())
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .build()
+            .unwrap();
+
+        assert_eq!(server.info.name, "test-server");
+        assert_eq!(server.info.version, "1.0.0");
+        assert!(server.tools.contains("test-tool"));
+    }
+
+    #[test]
+    fn test_server_builder_validation() {
+        // Missing name
+        let result = Server::builder().version("1.0.0").build();
+        assert!(result.is_err());
+
+        // Missing version
+        let result = Server::builder().name("test-server").build();
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_server_initialization() {
+        let init_request = TransportMessage::Request {
+            id: RequestId::from(1i64),
+            request: Request::Client(Box::new(ClientRequest::Initialize(InitializeRequest {
+                protocol_version: "2024-11-05".to_string(),
+                capabilities: ClientCapabilities {
+                    tools: Some(ToolCapabilities::default()),
+                    ..Default::default()
+                },
+                client_info: Implementation {
+                    name: "test-client".to_string(),
+                    version: "1.0.0".to_string(),
+                },
+            }))),
+        };
+
+        let transport = MockTransport::with_requests(vec![init_request]);
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .capabilities(ServerCapabilities::tools_only())
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .build()
+            .unwrap();
+
+        // Test server run for a short time
+        let server_handle = tokio::spawn(async move {
+            let _ = timeout(std::time::Duration::from_millis(100), server.run(transport)).await;
+        });
+
+        // Wait for server to process
+        let _ = timeout(std::time::Duration::from_millis(200), server_handle).await;
+    }
+
+    #[tokio::test]
+    async fn test_initialize_negotiates_protocol_version() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .protocol_versions(crate::shared::VersionNegotiator::new([
+                "2025-03-26",
+                "2024-11-05",
+            ]))
+            .build()
+            .unwrap();
+        let initialize = |version: &str| {
+            Request::Client(Box::new(ClientRequest::Initialize(InitializeRequest {
+                protocol_version: version.to_string(),
+                capabilities: ClientCapabilities::default(),
+                client_info: Implementation {
+                    name: "test-client".to_string(),
+                    version: "1.0.0".to_string(),
+                },
+            })))
+        };
+
+        for (requested, expected) in [
+            ("2024-11-05", "2024-11-05"),
+            ("2025-03-26", "2025-03-26"),
+            ("2025-06-18", "2025-03-26"),
+        ] {
+            let response = server
+                .handle_request(RequestId::from(1i64), initialize(requested))
+                .await;
+            let ResponsePayload::Result(result) = response.payload else {
+                panic!("Expected success response");
+            };
+            assert_eq!(result["protocolVersion"], expected);
+            assert_eq!(server.negotiated_version().unwrap().as_str(), expected);
+        }
+    }
+
+    /// Transport backed by channels, driven by the test as the client.
+    #[derive(Debug)]
+    struct ChannelTransport {
+        incoming: mpsc::UnboundedReceiver<TransportMessage>,
+        outgoing: mpsc::UnboundedSender<TransportMessage>,
+    }
+
+    #[async_trait]
+    impl Transport for ChannelTransport {
+        async fn send(&mut self, message: TransportMessage) -> Result<()> {
+            let _ = self.outgoing.send(message);
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<TransportMessage> {
+            self.incoming
+                .recv()
+                .await
+                .ok_or_else(|| Error::protocol_msg("Client closed"))
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_samples_from_client_mid_call() {
+        struct Ask;
+
+        #[async_trait]
+        impl ToolHandler for Ask {
+            async fn handle(
+                &self,
+                args: Value,
+                extra: cancellation::RequestHandlerExtra,
+            ) -> Result<Value> {
+                let reply = extra
+                    .create_message(crate::types::CreateMessageParams::new(vec![
+                        crate::types::SamplingMessage {
+                            role: crate::types::Role::User,
+                            content: crate::types::Content::Text {
+                                text: args["question"].as_str().unwrap().to_string(),
+                            },
+                        },
+                    ]))
+                    .await?;
+                Ok(json!({"answer": reply.content, "model": reply.model}))
+            }
+        }
+
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("ask", Ask)
+            .build()
+            .unwrap();
+        let (to_server, incoming) = mpsc::unbounded_channel();
+        let (outgoing, mut from_server) = mpsc::unbounded_channel();
+        tokio::spawn(server.run(ChannelTransport { incoming, outgoing }));
+
+        let request = |id: i64, request: ClientRequest| TransportMessage::Request {
+            id: RequestId::from(id),
+            request: Request::Client(Box::new(request)),
+        };
+        async fn next(rx: &mut mpsc::UnboundedReceiver<TransportMessage>) -> TransportMessage {
+            timeout(std::time::Duration::from_secs(5), rx.recv())
+                .await
+                .expect("server did not answer")
+                .unwrap()
+        }
+
+        to_server
+            .send(request(
+                1,
+                ClientRequest::Initialize(InitializeRequest {
+                    protocol_version: "2024-11-05".to_string(),
+                    capabilities: ClientCapabilities {
+                        sampling: Some(crate::types::capabilities::SamplingCapabilities::default()),
+                        ..Default::default()
+                    },
+                    client_info: crate::types::Implementation {
+                        name: "test-client".to_string(),
+                        version: "1.0.0".to_string(),
+                    },
+                }),
+            ))
+            .unwrap();
+        assert!(matches!(
+            next(&mut from_server).await,
+            TransportMessage::Response(_)
+        ));
+
+        to_server
+            .send(request(
+                2,
+                ClientRequest::CallTool(CallToolRequest::new("ask", json!({"question": "2 + 2?"}))),
+            ))
+            .unwrap();
+        let TransportMessage::Request {
+            id,
+            request: Request::Server(sampling),
+        } = next(&mut from_server).await
+        else {
+            panic!("Expected a sampling request");
+        };
+        assert!(matches!(
+            *sampling,
+            crate::types::ServerRequest::CreateMessage(_)
+        ));
+        to_server
+            .send(TransportMessage::Response(JSONRPCResponse::success(
+                id,
+                json!({"content": {"type": "text", "text": "4"}, "model": "test-model"}),
+            )))
+            .unwrap();
+
+        let TransportMessage::Response(response) = next(&mut from_server).await else {
+            panic!("Expected the tool call response");
+        };
+        assert_eq!(response.id, RequestId::from(2i64));
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Tool call failed: {:?}", response.payload);
+        };
+        let result: CallToolResult = serde_json::from_value(result).unwrap();
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["answer"]["text"], "4");
+        assert_eq!(structured["model"], "test-model");
+    }
+
+    #[tokio::test]
+    async fn test_requests_roots_from_outside_handlers() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .build()
+            .unwrap();
+        assert!(server.request_roots().await.is_err());
+        let requester = server.client_requester();
+        let (to_server, incoming) = mpsc::unbounded_channel();
+        let (outgoing, mut from_server) = mpsc::unbounded_channel();
+        tokio::spawn(server.run(ChannelTransport { incoming, outgoing }));
+        async fn next(rx: &mut mpsc::UnboundedReceiver<TransportMessage>) -> TransportMessage {
+            timeout(std::time::Duration::from_secs(5), rx.recv())
+                .await
+                .expect("server did not answer")
+                .unwrap()
+        }
+
+        to_server
+            .send(TransportMessage::Request {
+                id: RequestId::from(1i64),
+                request: Request::Client(Box::new(ClientRequest::Initialize(InitializeRequest {
+                    protocol_version: "2024-11-05".to_string(),
+                    capabilities: ClientCapabilities {
+                        roots: Some(crate::types::RootsCapabilities { list_changed: true }),
+                        ..Default::default()
+                    },
+                    client_info: crate::types::Implementation {
+                        name: "test-client".to_string(),
+                        version: "1.0.0".to_string(),
+                    },
+                }))),
+            })
+            .unwrap();
+        assert!(matches!(
+            next(&mut from_server).await,
+            TransportMessage::Response(_)
+        ));
+
+        let roots = tokio::spawn(async move { requester.list_roots().await });
+        let TransportMessage::Request {
+            id,
+            request: Request::Server(request),
+        } = next(&mut from_server).await
+        else {
+            panic!("Expected a roots request");
+        };
+        assert!(matches!(*request, crate::types::ServerRequest::ListRoots));
+        to_server
+            .send(TransportMessage::Response(JSONRPCResponse::success(
+                id,
+                json!({"roots": [{"uri": "file:///project"}]}),
+            )))
+            .unwrap();
+        let roots = roots.await.unwrap().unwrap().roots;
+        assert_eq!(
+            roots,
+            [roots::Root {
+                uri: "file:///project".to_string(),
+                name: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_respect_tool_limits() {
+        /// Tool that counts its calls and waits for a go-ahead.
+        struct Gate {
+            entered: Arc<std::sync::atomic::AtomicUsize>,
+            release: Arc<tokio::sync::Semaphore>,
+        }
+
+        #[async_trait]
+        impl ToolHandler for Gate {
+            async fn handle(
+                &self,
+                _args: Value,
+                _extra: cancellation::RequestHandlerExtra,
+            ) -> Result<Value> {
+                self.entered
+                    .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                self.release.acquire().await.unwrap().forget();
+                Ok(json!({}))
+            }
+        }
+
+        let entered = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let release = Arc::new(tokio::sync::Semaphore::new(0));
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool(
+                "gate",
+                Gate {
+                    entered: entered.clone(),
+                    release: release.clone(),
+                },
+            )
+            .tool("fast", MockTool::new(json!({})))
+            .concurrency(concurrency::ConcurrencyConfig::new(4).tool_limit("gate", 1))
+            .build()
+            .unwrap();
+        let (to_server, incoming) = mpsc::unbounded_channel();
+        let (outgoing, mut from_server) = mpsc::unbounded_channel();
+        tokio::spawn(server.run(ChannelTransport { incoming, outgoing }));
+
+        let call = |id: i64, name: &str| TransportMessage::Request {
+            id: RequestId::from(id),
+            request: Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                name,
+                json!({}),
+            )))),
         };
+        async fn next_id(rx: &mut mpsc::UnboundedReceiver<TransportMessage>) -> RequestId {
+            match timeout(std::time::Duration::from_secs(5), rx.recv())
+                .await
+                .expect("server did not answer")
+                .unwrap()
+            {
+                TransportMessage::Response(response) => response.id,
+                other => panic!("expected a response, got {:?}", other),
+            }
+        }
 
-        Ok(Server {
-            info: Implementation { name, version },
-            capabilities: self.capabilities,
-            tools: self.tools,
-            prompts: self.prompts,
-            resources: self.resources,
-            sampling: self.sampling,
-            client_capabilities: Arc::new(RwLock::new(None)),
-            initialized: Arc::new(RwLock::new(false)),
-            notification_tx: None,
-            cancellation_manager: self.cancellation_manager,
-            roots_manager: Arc::new(RwLock::new(self.roots_manager)),
-            subscription_manager: Arc::new(RwLock::new(subscriptions::SubscriptionManager::new())),
-            elicitation_manager: None,
-            auth_provider: self.auth_provider,
-            tool_authorizer,
-        })
+        for message in [call(2, "gate"), call(3, "gate"), call(4, "fast")] {
+            to_server.send(message).unwrap();
+        }
+        // The fast call overtakes the gated ones, and the tool limit keeps
+        // the second gated call waiting for the first.
+        assert_eq!(next_id(&mut from_server).await, RequestId::from(4i64));
+        assert_eq!(entered.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        release.add_permits(1);
+        assert_eq!(next_id(&mut from_server).await, RequestId::from(2i64));
+        release.add_permits(1);
+        assert_eq!(next_id(&mut from_server).await, RequestId::from(3i64));
+        assert_eq!(entered.load(std::sync::atomic::Ordering::SeqCst), 2);
     }
-}
 
-#[cfg(not(target_arch = "wasm32"))]
-impl Default for ServerBuilder {
-    fn default() -> Self {
-        Self::new()
+    #[tokio::test]
+    async fn test_keep_alive_drops_silent_client() {
+        let (dead_tx, mut dead_rx) = mpsc::unbounded_channel();
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .keep_alive(
+                crate::shared::KeepAliveConfig::default()
+                    .with_interval(std::time::Duration::from_millis(100))
+                    .with_timeout(std::time::Duration::ZERO)
+                    .with_failure_threshold(2),
+            )
+            .on_keep_alive_timeout(move || {
+                let _ = dead_tx.send(());
+            })
+            .build()
+            .unwrap();
+        let (to_server, incoming) = mpsc::unbounded_channel();
+        let (outgoing, mut from_server) = mpsc::unbounded_channel();
+        tokio::spawn(server.run(ChannelTransport { incoming, outgoing }));
+
+        // Traffic within the silence limit keeps the connection
+        for id in 1..=3i64 {
+            to_server
+                .send(TransportMessage::Request {
+                    id: RequestId::from(id),
+                    request: Request::Client(Box::new(ClientRequest::Ping)),
+                })
+                .unwrap();
+            let answer = timeout(std::time::Duration::from_secs(5), from_server.recv()).await;
+            assert!(matches!(answer, Ok(Some(TransportMessage::Response(_)))));
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        assert!(dead_rx.try_recv().is_err());
+
+        timeout(std::time::Duration::from_secs(5), dead_rx.recv())
+            .await
+            .expect("silent client was not dropped")
+            .unwrap();
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::shared::Transport;
-    use crate::types::{
-        jsonrpc::ResponsePayload, ClientCapabilities, InitializeRequest, ServerCapabilities,
-        ToolCapabilities, TransportMessage,
-    };
-    use async_trait::async_trait;
-    use serde_json::json;
-    use std::sync::{Arc, Mutex};
-    use tokio::time::timeout;
+    #[tokio::test]
+    async fn test_server_capabilities() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .capabilities(ServerCapabilities::tools_only())
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .build()
+            .unwrap();
 
-    /// Mock transport for testing
-    #[derive(Debug)]
-    struct MockTransport {
-        messages: Arc<Mutex<Vec<TransportMessage>>>,
-        responses: Arc<Mutex<Vec<TransportMessage>>>,
+        assert!(!server.is_initialized().await);
+        assert!(server.get_client_capabilities().await.is_none());
     }
 
-    impl MockTransport {
-        #[allow(dead_code)]
-        fn new() -> Self {
-            Self {
-                messages: Arc::new(Mutex::new(Vec::new())),
-                responses: Arc::new(Mutex::new(Vec::new())),
-            }
+    #[test]
+    fn test_capabilities_are_derived_from_handlers() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .build()
+            .unwrap();
+        assert!(server.capabilities.tools.is_some());
+        assert!(server.capabilities.prompts.is_none());
+        assert!(server.capabilities.resources.is_none());
+
+        let Err(err) = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .capabilities(ServerCapabilities::tools_only())
+            .build()
+        else {
+            panic!("declared tools without handlers should not build");
+        };
+        assert!(err.to_string().contains("tools"));
+
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .override_capabilities(ServerCapabilities::tools_only())
+            .build()
+            .unwrap();
+        assert!(server.capabilities.tools.is_some());
+    }
+
+    #[test]
+    fn test_build_rejects_invalid_tool_registrations() {
+        let Err(err) = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .tool("test-tool", MockTool::new(json!({"result": "replaced"})))
+            .build()
+        else {
+            panic!("duplicate tool names should not build");
+        };
+        assert!(err.to_string().contains("registered more than once"));
+    }
+
+    #[tokio::test]
+    async fn test_server_notifications() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .build()
+            .unwrap();
+
+        // Send notification (should not panic even without transport)
+        server
+            .send_notification(ServerNotification::ToolsChanged)
+            .await;
+    }
+
+    #[test]
+    fn test_server_builder_with_all_handlers() {
+        let prompt_result = crate::types::GetPromptResult {
+            description: Some("Test prompt".to_string()),
+            messages: vec![],
+        };
+
+        let resource_content = crate::types::ReadResourceResult {
+            contents: vec![crate::types::Content::Text {
+                text: "Hello, world!".to_string(),
+            }],
+            meta: None,
+        };
+
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .prompt("test-prompt", MockPrompt::new(prompt_result))
+            .resources(
+                MockResource::new().with_resource("test://uri".to_string(), resource_content),
+            )
+            .build()
+            .unwrap();
+
+        assert!(server.tools.contains("test-tool"));
+        assert!(server.prompts.contains_key("test-prompt"));
+        assert!(server.resources.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_handle_request_initialize() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .capabilities(ServerCapabilities::tools_only())
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .build()
+            .unwrap();
+
+        let request = Request::Client(Box::new(ClientRequest::Initialize(InitializeRequest {
+            protocol_version: "2024-11-05".to_string(),
+            capabilities: ClientCapabilities::default(),
+            client_info: Implementation {
+                name: "test-client".to_string(),
+                version: "1.0.0".to_string(),
+            },
+        })));
+
+        let response = server.handle_request(RequestId::from(1i64), request).await;
+
+        assert_eq!(response.id, RequestId::from(1i64));
+        match response.payload {
+            ResponsePayload::Result(_) => {
+                assert!(server.is_initialized().await);
+            },
+            ResponsePayload::Error(_) => panic!("Expected success response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_list_tools() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .build()
+            .unwrap();
+
+        let request = Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
+            cursor: None,
+        })));
+        let response = server.handle_request(RequestId::from(1i64), request).await;
+
+        match response.payload {
+            ResponsePayload::Result(result) => {
+                let tools_result: ListToolsResult = serde_json::from_value(result).unwrap();
+                assert_eq!(tools_result.tools.len(), 1);
+                assert_eq!(tools_result.tools[0].name, "test-tool");
+            },
+            ResponsePayload::Error(_) => panic!("Expected success response"),
         }
+    }
+
+    #[tokio::test]
+    async fn test_resource_subscriptions_are_recorded() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .build()
+            .unwrap();
+        let subscribe = Request::Client(Box::new(ClientRequest::Subscribe(SubscribeRequest {
+            uri: "test://doc".to_string(),
+        })));
+        server
+            .handle_request(RequestId::from(1i64), subscribe)
+            .await;
+
+        let (tx, mut rx) = mpsc::channel(8);
+        server
+            .subscription_manager
+            .write()
+            .await
+            .set_notification_sender(move |n| {
+                let _ = tx.try_send(n);
+            });
+        assert_eq!(
+            server
+                .notify_resource_updated("test://doc".to_string())
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(ServerNotification::ResourceUpdated(p)) if p.uri == "test://doc"
+        ));
+
+        let unsubscribe =
+            Request::Client(Box::new(ClientRequest::Unsubscribe(UnsubscribeRequest {
+                uri: "test://doc".to_string(),
+            })));
+        server
+            .handle_request(RequestId::from(2i64), unsubscribe)
+            .await;
+        assert_eq!(
+            server
+                .notify_resource_updated("test://doc".to_string())
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[cfg(feature = "resource-watcher")]
+    #[tokio::test]
+    async fn test_resource_watcher_notifies_subscribers() {
+        let dir = std::env::temp_dir().join(format!(
+            "pmcp-watch-{}-{}",
+            std::process::id(),
+            crate::shared::runtime::timestamp_millis()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = dir.canonicalize().unwrap();
+        let file = dir.join("notes.txt");
+        std::fs::write(&file, "v1").unwrap();
+        let uri = format!("file://{}", file.display());
+
+        let mut server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .resources(MockResource::new())
+            .watch_resources(
+                resource_watcher::ResourceWatcherBuilder::new()
+                    .base_dir(&dir)
+                    .debounce(std::time::Duration::from_millis(50)),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            server.capabilities.resources.as_ref().unwrap().subscribe,
+            Some(true)
+        );
 
-        fn with_requests(requests: Vec<TransportMessage>) -> Self {
-            Self {
-                messages: Arc::new(Mutex::new(requests)),
-                responses: Arc::new(Mutex::new(Vec::new())),
-            }
-        }
+        let (tx, mut rx) = mpsc::channel(8);
+        server.attach_notifications(tx).await.unwrap();
+        let subscribe = Request::Client(Box::new(ClientRequest::Subscribe(SubscribeRequest {
+            uri: uri.clone(),
+        })));
+        server
+            .handle_request(RequestId::from(1i64), subscribe)
+            .await;
 
-        #[allow(dead_code)]
-        fn add_request(&self, request: TransportMessage) {
-            self.messages.lock().unwrap().push(request);
-        }
+        // Give the watcher time to register before changing the file
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        std::fs::write(&file, "v2").unwrap();
 
-        #[allow(dead_code)]
-        fn get_sent_responses(&self) -> Vec<TransportMessage> {
-            self.responses.lock().unwrap().clone()
-        }
+        let notification = timeout(std::time::Duration::from_secs(5), rx.recv())
+            .await
+            .expect("no update notification")
+            .unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(matches!(
+            notification,
+            Notification::Server(ServerNotification::ResourceUpdated(p)) if p.uri == uri
+        ));
     }
 
-    #[async_trait]
-    impl Transport for MockTransport {
-        async fn send(&mut self, message: TransportMessage) -> Result<()> {
-            self.responses.lock().unwrap().push(message);
-            Ok(())
-        }
-
-        async fn receive(&mut self) -> Result<TransportMessage> {
-            let mut messages = self.messages.lock().unwrap();
-            messages
-                .pop()
-                .map_or_else(|| Err(Error::protocol_msg("No more messages")), Ok)
+    #[tokio::test]
+    async fn test_tool_reports_progress_to_caller_token() {
+        struct Steps;
+
+        #[async_trait]
+        impl ToolHandler for Steps {
+            async fn handle(
+                &self,
+                _args: Value,
+                extra: cancellation::RequestHandlerExtra,
+            ) -> Result<Value> {
+                extra
+                    .report_progress(2.0, Some(2.0), Some("done".to_string()))
+                    .await?;
+                Ok(json!({"steps": 2}))
+            }
         }
 
-        async fn close(&mut self) -> Result<()> {
-            Ok(())
-        }
+        let mut server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("steps", Steps)
+            .build()
+            .unwrap();
+        let (tx, mut rx) = mpsc::channel(8);
+        server.attach_notifications(tx).await.unwrap();
 
-        fn is_connected(&self) -> bool {
-            !self.messages.lock().unwrap().is_empty()
-        }
+        let call = |req: CallToolRequest| Request::Client(Box::new(ClientRequest::CallTool(req)));
+        server
+            .handle_request(
+                RequestId::from(1i64),
+                call(CallToolRequest::new("steps", json!({}))),
+            )
+            .await;
+        assert!(rx.try_recv().is_err());
 
-        fn transport_type(&self) -> &'static str {
-            "mock"
+        let request = CallToolRequest::new("steps", json!({}))
+            .with_progress_token(crate::types::ProgressToken::String("job".to_string()));
+        server
+            .handle_request(RequestId::from(2i64), call(request))
+            .await;
+        match rx.try_recv() {
+            Ok(Notification::Server(ServerNotification::Progress(p))) => {
+                assert_eq!(
+                    p.progress_token,
+                    crate::types::ProgressToken::String("job".to_string())
+                );
+                assert_eq!(p.progress, 100.0);
+                assert_eq!(p.message.as_deref(), Some("done"));
+            },
+            other => panic!("Expected progress notification, got {:?}", other),
         }
     }
 
-    /// Mock tool handler for testing
-    struct MockTool {
-        result: Value,
-    }
+    #[tokio::test]
+    async fn test_runtime_tool_registry() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .dynamic_tools()
+            .build()
+            .unwrap();
+        assert_eq!(
+            server.capabilities.tools.as_ref().unwrap().list_changed,
+            Some(true)
+        );
 
-    impl MockTool {
-        fn new(result: Value) -> Self {
-            Self { result }
-        }
-    }
+        let tools = server.tools().clone();
+        tools
+            .register("late-tool", MockTool::new(json!({"result": "late"})))
+            .await
+            .unwrap();
 
-    #[async_trait]
-    impl ToolHandler for MockTool {
-        async fn handle(
-            &self,
-            _args: Value,
-            _extra: crate::server::cancellation::RequestHandlerExtra,
-        ) -> Result<Value> {
-            Ok(self.result.clone())
-        }
+        let list = || {
+            Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
+                cursor: None,
+            })))
+        };
+        let response = server.handle_request(RequestId::from(1i64), list()).await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        let tools_result: ListToolsResult = serde_json::from_value(result).unwrap();
+        assert_eq!(tools_result.tools[0].name, "late-tool");
+
+        tools.unregister("late-tool").await.unwrap();
+        let call = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+            "late-tool",
+            json!({}),
+        ))));
+        let response = server.handle_request(RequestId::from(2i64), call).await;
+        assert!(matches!(response.payload, ResponsePayload::Error(_)));
     }
 
-    /// Mock prompt handler for testing
-    struct MockPrompt {
-        result: crate::types::GetPromptResult,
-    }
+    #[tokio::test]
+    async fn test_handle_call_tool() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .build()
+            .unwrap();
 
-    impl MockPrompt {
-        fn new(result: crate::types::GetPromptResult) -> Self {
-            Self { result }
-        }
-    }
+        let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest {
+            name: "test-tool".to_string(),
+            arguments: json!({"input": "test"}),
+            meta: None,
+        })));
 
-    #[async_trait]
-    impl PromptHandler for MockPrompt {
-        async fn handle(
-            &self,
-            _args: HashMap<String, String>,
-            _extra: crate::server::cancellation::RequestHandlerExtra,
-        ) -> Result<crate::types::GetPromptResult> {
-            Ok(self.result.clone())
+        let response = server.handle_request(RequestId::from(1i64), request).await;
+
+        match response.payload {
+            ResponsePayload::Result(result) => {
+                let call_result: CallToolResult = serde_json::from_value(result).unwrap();
+                assert!(!call_result.is_error);
+                assert_eq!(call_result.content.len(), 1);
+            },
+            ResponsePayload::Error(_) => panic!("Expected success response"),
         }
     }
 
-    /// Mock resource handler for testing
-    struct MockResource {
-        resources: Vec<crate::types::ResourceInfo>,
-        contents: HashMap<String, crate::types::ReadResourceResult>,
+    #[tokio::test]
+    async fn test_tool_rules_reject_invalid_arguments() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .tool_rules(
+                "test-tool",
+                input_validation::ArgumentRules::new()
+                    .required("input")
+                    .max("count", 10.0),
+            )
+            .build()
+            .unwrap();
+
+        let call = |arguments| {
+            Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "test-tool",
+                arguments,
+            ))))
+        };
+        let response = server
+            .handle_request(RequestId::from(1i64), call(json!({"count": 50})))
+            .await;
+        let ResponsePayload::Error(error) = response.payload else {
+            panic!("Expected error response");
+        };
+        assert_eq!(error.code, crate::error::ErrorCode::INVALID_PARAMS.as_i32());
+        assert_eq!(
+            error.data.unwrap()["violations"].as_array().unwrap().len(),
+            2
+        );
+
+        let response = server
+            .handle_request(RequestId::from(2i64), call(json!({"input": "x"})))
+            .await;
+        assert!(matches!(response.payload, ResponsePayload::Result(_)));
+
+        let response = server
+            .handle_request(
+                RequestId::from(3i64),
+                Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
+                    cursor: None,
+                }))),
+            )
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        assert_eq!(
+            result["tools"][0]["inputSchema"]["required"],
+            json!(["input"])
+        );
     }
 
-    impl MockResource {
-        fn new() -> Self {
-            Self {
-                resources: Vec::new(),
-                contents: HashMap::new(),
+    #[tokio::test]
+    async fn test_coerce_arguments_before_rules_and_handler() {
+        let echo = crate::SyncTool::new("echo", Ok).with_schema(json!({
+            "type": "object",
+            "properties": {
+                "count": {"type": "integer"},
+                "verbose": {"type": "boolean", "default": false}
             }
-        }
+        }));
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("echo", echo)
+            .tool_rules(
+                "echo",
+                input_validation::ArgumentRules::new().max("count", 10.0),
+            )
+            .coerce_arguments(coercion::ArgumentCoercion::new())
+            .build()
+            .unwrap();
 
-        fn with_resource(mut self, uri: String, content: crate::types::ReadResourceResult) -> Self {
-            self.contents.insert(uri, content);
-            self
-        }
+        let call = |arguments| {
+            Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "echo", arguments,
+            ))))
+        };
+        let response = server
+            .handle_request(
+                RequestId::from(1i64),
+                call(json!({"count": "7", "session": "abc"})),
+            )
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        assert_eq!(
+            result["structuredContent"],
+            json!({"count": 7, "verbose": false})
+        );
+
+        // Coerced values are still checked against the rules
+        let response = server
+            .handle_request(RequestId::from(2i64), call(json!({"count": "50"})))
+            .await;
+        assert!(matches!(response.payload, ResponsePayload::Error(_)));
     }
 
-    #[async_trait]
-    impl ResourceHandler for MockResource {
-        async fn read(
-            &self,
-            uri: &str,
-            _extra: crate::server::cancellation::RequestHandlerExtra,
-        ) -> Result<crate::types::ReadResourceResult> {
-            self.contents
-                .get(uri)
-                .cloned()
-                .ok_or_else(|| Error::not_found(format!("Resource '{}' not found", uri)))
-        }
+    #[tokio::test]
+    async fn test_list_tools_includes_annotations() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool(
+                "lookup",
+                simple_tool::SyncTool::new("lookup", |_| Ok(json!({})))
+                    .read_only()
+                    .idempotent(),
+            )
+            .build()
+            .unwrap();
 
-        async fn list(
-            &self,
-            _cursor: Option<String>,
-            _extra: crate::server::cancellation::RequestHandlerExtra,
-        ) -> Result<crate::types::ListResourcesResult> {
-            Ok(crate::types::ListResourcesResult {
-                resources: self.resources.clone(),
-                next_cursor: None,
-            })
-        }
+        let response = server
+            .handle_request(
+                RequestId::from(1i64),
+                Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
+                    cursor: None,
+                }))),
+            )
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        assert_eq!(
+            result["tools"][0]["annotations"],
+            json!({"readOnlyHint": true, "idempotentHint": true})
+        );
+        let tools: ListToolsResult = serde_json::from_value(result).unwrap();
+        assert!(tools.tools[0].is_read_only());
+        assert!(!tools.tools[0].is_destructive());
     }
 
-    #[test]
-    fn test_server_builder() {
+    #[tokio::test]
+    async fn test_timing_breakdown() {
         let server = Server::builder()
             .name("test-server")
             .version("1.0.0")
-            .capabilities(ServerCapabilities::tools_only())
             .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .timing_meta(true)
             .build()
             .unwrap();
 
-        assert_eq!(server.info.name, "test-server");
-        assert_eq!(server.info.version, "1.0.0");
-        assert!(server.tools.contains_key("test-tool"));
-    }
+        let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest {
+            name: "test-tool".to_string(),
+            arguments: json!({}),
+            meta: None,
+        })));
+        let timer = RequestTimer::new();
+        timer.record(Phase::Parse, std::time::Duration::from_millis(2));
+        let response = timer
+            .scope(server.handle_request(RequestId::from(1i64), request))
+            .await;
 
-    #[test]
-    fn test_server_builder_validation() {
-        // Missing name
-        let result = Server::builder().version("1.0.0").build();
-        assert!(result.is_err());
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        let timing = &result["_meta"][timing::TIMING_META_KEY];
+        assert_eq!(timing["parseUs"], 2000);
+        assert!(timing["totalUs"].as_u64().unwrap() >= 2000);
 
-        // Missing version
-        let result = Server::builder().name("test-server").build();
-        assert!(result.is_err());
+        let snapshot = server.timing_snapshot();
+        assert_eq!(snapshot.requests, 1);
+        assert_eq!(snapshot.parse.total, std::time::Duration::from_millis(2));
+
+        // Without the opt-in, timings are aggregated but not attached.
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .build()
+            .unwrap();
+        let response = server
+            .handle_request(
+                RequestId::from(2i64),
+                Request::Client(Box::new(ClientRequest::Ping)),
+            )
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        assert!(result.get("_meta").is_none());
+        assert_eq!(server.timing_snapshot().requests, 1);
     }
 
     #[tokio::test]
-    async fn test_server_initialization() {
-        let init_request = TransportMessage::Request {
-            id: RequestId::from(1i64),
-            request: Request::Client(Box::new(ClientRequest::Initialize(InitializeRequest {
-                protocol_version: "2024-11-05".to_string(),
-                capabilities: ClientCapabilities {
-                    tools: Some(ToolCapabilities::default()),
-                    ..Default::default()
-                },
-                client_info: Implementation {
-                    name: "test-client".to_string(),
-                    version: "1.0.0".to_string(),
-                },
-            }))),
+    async fn test_request_guard_rejects_oversized_params() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .request_guard(crate::utils::validation::RequestGuard::new().with_max_array_length(2))
+            .build()
+            .unwrap();
+
+        let call = |arguments| {
+            Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest {
+                name: "test-tool".to_string(),
+                arguments,
+                meta: None,
+            })))
+        };
+
+        let response = server
+            .handle_request(RequestId::from(1i64), call(json!({"ids": [1, 2]})))
+            .await;
+        assert!(matches!(response.payload, ResponsePayload::Result(_)));
+
+        let response = server
+            .handle_request(RequestId::from(2i64), call(json!({"ids": [1, 2, 3]})))
+            .await;
+        let ResponsePayload::Error(error) = response.payload else {
+            panic!("Expected error response");
         };
+        assert_eq!(error.code, crate::ErrorCode::INVALID_PARAMS.as_i32());
+        assert!(error.message.contains("params/arguments/ids"));
+    }
 
-        let transport = MockTransport::with_requests(vec![init_request]);
-        let server = Server::builder()
+    #[tokio::test]
+    async fn test_set_level_filters_forwarded_log_messages() {
+        let mut server = Server::builder()
             .name("test-server")
             .version("1.0.0")
-            .capabilities(ServerCapabilities::tools_only())
             .build()
             .unwrap();
-
-        // Test server run for a short time
-        let server_handle = tokio::spawn(async move {
-            let _ = timeout(std::time::Duration::from_millis(100), server.run(transport)).await;
-        });
-
-        // Wait for server to process
-        let _ = timeout(std::time::Duration::from_millis(200), server_handle).await;
+        let (tx, mut rx) = mpsc::channel(8);
+        server.attach_notifications(tx).await.unwrap();
+        let logger = server.logger();
+
+        logger.info("before").await.unwrap();
+        let response = server
+            .handle_request(
+                RequestId::from(1i64),
+                Request::Client(Box::new(ClientRequest::SetLoggingLevel {
+                    level: crate::types::LoggingLevel::Error,
+                })),
+            )
+            .await;
+        assert!(matches!(
+            response.payload,
+            crate::types::jsonrpc::ResponsePayload::Result(_)
+        ));
+        logger.warning("filtered").await.unwrap();
+        logger.error("after").await.unwrap();
+
+        let mut messages = Vec::new();
+        while let Ok(Notification::Server(ServerNotification::LogMessage(params))) = rx.try_recv() {
+            messages.push(params.message);
+        }
+        assert_eq!(messages, ["before", "after"]);
     }
 
     #[tokio::test]
-    async fn test_server_capabilities() {
+    async fn test_audit_logger_records_requests() {
+        use crate::server::audit::{AuditEntry, AuditLogger, AuditOutcome};
+
+        #[derive(Default)]
+        struct Recorder(parking_lot::Mutex<Vec<AuditEntry>>);
+
+        #[async_trait]
+        impl AuditLogger for Arc<Recorder> {
+            async fn record(&self, entry: &AuditEntry) -> Result<()> {
+                self.0.lock().push(entry.clone());
+                Ok(())
+            }
+        }
+
+        let recorder = Arc::new(Recorder::default());
         let server = Server::builder()
             .name("test-server")
             .version("1.0.0")
-            .capabilities(ServerCapabilities::tools_only())
+            .tool(
+                "test-tool",
+                MockTool::new(json!({"result": "x".repeat(100)})),
+            )
+            .audit_logger(recorder.clone())
+            .audit_payload_limit(64)
             .build()
             .unwrap();
 
-        assert!(!server.is_initialized().await);
-        assert!(server.get_client_capabilities().await.is_none());
+        for (id, name) in [(1i64, "test-tool"), (2, "missing")] {
+            let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                name,
+                json!({"q": 1}),
+            ))));
+            server.handle_request(RequestId::from(id), request).await;
+        }
+
+        let entries = recorder.0.lock();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].method, "tools/call");
+        assert_eq!(entries[0].principal, CLIENT_SUBSCRIBER);
+        assert_eq!(entries[0].outcome, AuditOutcome::Success);
+        assert_eq!(entries[0].params.as_ref().unwrap()["name"], "test-tool");
+        assert!(entries[0].result.as_ref().unwrap().is_string());
+        assert!(matches!(entries[1].outcome, AuditOutcome::Error { .. }));
+        assert_eq!(entries[1].result, None);
+    }
+
+    /// Authenticates `Bearer <subject>` headers as `<subject>`.
+    struct BearerSubject;
+
+    #[async_trait]
+    impl auth::AuthProvider for BearerSubject {
+        async fn validate_request(
+            &self,
+            authorization_header: Option<&str>,
+        ) -> Result<Option<auth::AuthContext>> {
+            Ok(authorization_header
+                .and_then(|header| header.strip_prefix("Bearer "))
+                .map(|subject| auth::AuthContext {
+                    subject: subject.to_string(),
+                    scopes: Vec::new(),
+                    claims: HashMap::new(),
+                    token: None,
+                    client_id: None,
+                    expires_at: None,
+                }))
+        }
     }
 
     #[tokio::test]
-    async fn test_server_notifications() {
+    async fn test_audit_entry_names_authenticated_subject() {
+        use crate::server::audit::{AuditEntry, AuditLogger};
+
+        struct Principals(Arc<parking_lot::Mutex<Vec<String>>>);
+
+        #[async_trait]
+        impl AuditLogger for Principals {
+            async fn record(&self, entry: &AuditEntry) -> Result<()> {
+                self.0.lock().push(entry.principal.clone());
+                Ok(())
+            }
+        }
+
+        let principals = Arc::new(parking_lot::Mutex::new(Vec::new()));
         let server = Server::builder()
             .name("test-server")
             .version("1.0.0")
+            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .auth_provider(BearerSubject)
+            .audit_logger(Principals(principals.clone()))
             .build()
             .unwrap();
 
-        // Send notification (should not panic even without transport)
-        server
-            .send_notification(ServerNotification::ToolsChanged)
+        let call = || {
+            Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "test-tool",
+                json!({}),
+            ))))
+        };
+        auth::AUTHORIZATION
+            .scope(
+                "Bearer alice".to_string(),
+                server.handle_request(RequestId::from(1i64), call()),
+            )
             .await;
+        server.handle_request(RequestId::from(2i64), call()).await;
+
+        assert_eq!(*principals.lock(), ["alice", CLIENT_SUBSCRIBER]);
     }
 
-    #[test]
-    fn test_server_builder_with_all_handlers() {
-        let prompt_result = crate::types::GetPromptResult {
-            description: Some("Test prompt".to_string()),
-            messages: vec![],
+    #[tokio::test]
+    async fn test_rate_limit_is_per_authenticated_subject() {
+        use crate::server::rate_limit::{PrincipalRateLimiter, TokenBucket, CLIENT_ADDR};
+
+        let limited = |builder: ServerBuilder| {
+            builder
+                .name("test-server")
+                .version("1.0.0")
+                .tool("test-tool", MockTool::new(json!({"result": "success"})))
+                .rate_limit(PrincipalRateLimiter::new().tools(TokenBucket::new(1, 0.0)))
+                .build()
+                .unwrap()
         };
+        async fn call(server: &Server, addr: [u8; 4], authorization: &str, id: i64) -> bool {
+            let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "test-tool",
+                json!({}),
+            ))));
+            let handling = CLIENT_ADDR.scope(
+                addr.into(),
+                server.handle_request(RequestId::from(id), request),
+            );
+            let response = auth::AUTHORIZATION
+                .scope(authorization.to_string(), handling)
+                .await;
+            matches!(response.payload, ResponsePayload::Result(_))
+        }
 
-        let resource_content = crate::types::ReadResourceResult {
-            contents: vec![crate::types::Content::Text {
-                text: "Hello, world!".to_string(),
-            }],
-        };
+        // Subjects are limited apart even from one address
+        let server = limited(Server::builder().auth_provider(BearerSubject));
+        assert!(call(&server, [10, 0, 0, 1], "Bearer alice", 1).await);
+        assert!(call(&server, [10, 0, 0, 1], "Bearer bob", 2).await);
+        assert!(!call(&server, [10, 0, 0, 2], "Bearer alice", 3).await);
+
+        // A placeholder subject doesn't lump clients without credentials
+        let server = limited(Server::builder().auth_provider(auth::NoOpAuthProvider));
+        for (id, addr) in [(1i64, [10, 0, 0, 1]), (2, [10, 0, 0, 2])] {
+            let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "test-tool",
+                json!({}),
+            ))));
+            let response = CLIENT_ADDR
+                .scope(
+                    addr.into(),
+                    server.handle_request(RequestId::from(id), request),
+                )
+                .await;
+            assert!(matches!(response.payload, ResponsePayload::Result(_)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_is_per_client_address() {
+        use crate::server::rate_limit::{PrincipalRateLimiter, TokenBucket, CLIENT_ADDR};
 
         let server = Server::builder()
             .name("test-server")
             .version("1.0.0")
             .tool("test-tool", MockTool::new(json!({"result": "success"})))
-            .prompt("test-prompt", MockPrompt::new(prompt_result))
-            .resources(
-                MockResource::new().with_resource("test://uri".to_string(), resource_content),
-            )
+            .rate_limit(PrincipalRateLimiter::new().tools(TokenBucket::new(1, 0.0)))
             .build()
             .unwrap();
 
-        assert!(server.tools.contains_key("test-tool"));
-        assert!(server.prompts.contains_key("test-prompt"));
-        assert!(server.resources.is_some());
+        let call = |addr: [u8; 4], id: i64| {
+            let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "test-tool",
+                json!({}),
+            ))));
+            CLIENT_ADDR.scope(
+                addr.into(),
+                server.handle_request(RequestId::from(id), request),
+            )
+        };
+
+        let response = call([10, 0, 0, 1], 1).await;
+        assert!(matches!(response.payload, ResponsePayload::Result(_)));
+        let response = call([10, 0, 0, 2], 2).await;
+        assert!(matches!(response.payload, ResponsePayload::Result(_)));
+
+        let response = call([10, 0, 0, 1], 3).await;
+        let ResponsePayload::Error(error) = response.payload else {
+            panic!("Expected error response");
+        };
+        assert_eq!(error.code, crate::ErrorCode::RATE_LIMITED.as_i32());
+        assert!(error.data.unwrap()["retryAfterMs"].is_u64());
+
+        // Pings are never limited
+        let response = CLIENT_ADDR
+            .scope(
+                [10, 0, 0, 1].into(),
+                server.handle_request(
+                    RequestId::from(4i64),
+                    Request::Client(Box::new(ClientRequest::Ping)),
+                ),
+            )
+            .await;
+        assert!(matches!(response.payload, ResponsePayload::Result(_)));
     }
 
     #[tokio::test]
-    async fn test_handle_request_initialize() {
+    async fn test_tool_output_validated_against_output_schema() {
+        use crate::server::typed_tool::TypedToolWithOutput;
+
+        let output_schema = json!({
+            "type": "object",
+            "properties": {"sum": {"type": "integer"}},
+            "required": ["sum"]
+        });
+        let tool = |name: &'static str, key: &'static str| {
+            TypedToolWithOutput::new_with_schemas(
+                name,
+                json!({"type": "object"}),
+                Some(output_schema.clone()),
+                move |_args: Value, _extra| Box::pin(async move { Ok(json!({key: 3})) }),
+            )
+        };
         let server = Server::builder()
             .name("test-server")
             .version("1.0.0")
-            .capabilities(ServerCapabilities::tools_only())
+            .tool("good", tool("good", "sum"))
+            .tool("bad", tool("bad", "total"))
+            .validate_tool_output(true)
             .build()
             .unwrap();
 
-        let request = Request::Client(Box::new(ClientRequest::Initialize(InitializeRequest {
-            protocol_version: "2024-11-05".to_string(),
-            capabilities: ClientCapabilities::default(),
-            client_info: Implementation {
-                name: "test-client".to_string(),
-                version: "1.0.0".to_string(),
-            },
-        })));
+        let listed = server
+            .handle_list_tools(ListToolsRequest { cursor: None })
+            .unwrap();
+        assert_eq!(listed["tools"][0]["outputSchema"], output_schema);
 
-        let response = server.handle_request(RequestId::from(1i64), request).await;
+        let call = |name: &str| {
+            Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                name,
+                json!({}),
+            ))))
+        };
+        let response = server
+            .handle_request(RequestId::from(1i64), call("good"))
+            .await;
+        assert!(matches!(response.payload, ResponsePayload::Result(_)));
 
-        assert_eq!(response.id, RequestId::from(1i64));
-        match response.payload {
-            ResponsePayload::Result(_) => {
-                assert!(server.is_initialized().await);
-            },
-            ResponsePayload::Error(_) => panic!("Expected success response"),
-        }
+        let response = server
+            .handle_request(RequestId::from(2i64), call("bad"))
+            .await;
+        let ResponsePayload::Error(error) = response.payload else {
+            panic!("Expected error response");
+        };
+        assert_eq!(error.code, crate::ErrorCode::INTERNAL_ERROR.as_i32());
+        assert!(error.message.contains("'bad'"), "{}", error.message);
+        assert!(error.message.contains("'sum'"), "{}", error.message);
     }
 
     #[tokio::test]
-    async fn test_handle_list_tools() {
+    async fn test_idempotent_tool_calls_run_once() {
+        struct CountingTool(Arc<std::sync::atomic::AtomicUsize>);
+
+        #[async_trait]
+        impl ToolHandler for CountingTool {
+            async fn handle(
+                &self,
+                _args: Value,
+                _extra: cancellation::RequestHandlerExtra,
+            ) -> Result<Value> {
+                let runs = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                Ok(json!({"runs": runs}))
+            }
+        }
+
+        let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
         let server = Server::builder()
             .name("test-server")
             .version("1.0.0")
-            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .tool("charge", CountingTool(runs.clone()))
+            .idempotency(idempotency::IdempotencyCache::new())
             .build()
             .unwrap();
 
-        let request = Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
-            cursor: None,
-        })));
-        let response = server.handle_request(RequestId::from(1i64), request).await;
+        let call = |key: &str| {
+            Request::Client(Box::new(ClientRequest::CallTool(
+                CallToolRequest::new("charge", json!({"amount": 5})).with_idempotency_key(key),
+            )))
+        };
 
-        match response.payload {
-            ResponsePayload::Result(result) => {
-                let tools_result: ListToolsResult = serde_json::from_value(result).unwrap();
-                assert_eq!(tools_result.tools.len(), 1);
-                assert_eq!(tools_result.tools[0].name, "test-tool");
-            },
-            ResponsePayload::Error(_) => panic!("Expected success response"),
-        }
+        let first = server
+            .handle_request(RequestId::from(1i64), call("k1"))
+            .await;
+        let replay = server
+            .handle_request(RequestId::from(2i64), call("k1"))
+            .await;
+        let (ResponsePayload::Result(first), ResponsePayload::Result(replay)) =
+            (first.payload, replay.payload)
+        else {
+            panic!("Expected success responses");
+        };
+        assert_eq!(first, replay);
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        server
+            .handle_request(RequestId::from(3i64), call("k2"))
+            .await;
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 2);
     }
 
     #[tokio::test]
-    async fn test_handle_call_tool() {
+    async fn test_method_hooks_rewrite_and_reject() {
+        struct EchoTool;
+
+        #[async_trait]
+        impl ToolHandler for EchoTool {
+            async fn handle(
+                &self,
+                args: Value,
+                _extra: cancellation::RequestHandlerExtra,
+            ) -> Result<Value> {
+                Ok(args)
+            }
+        }
+
+        struct ForceUnits;
+
+        #[async_trait]
+        impl router::MethodHook for ForceUnits {
+            async fn before(&self, request: &mut router::HookRequest) -> Result<()> {
+                if request.params["arguments"]["blocked"] == json!(true) {
+                    return Err(Error::validation("blocked by policy"));
+                }
+                request.params["arguments"]["units"] = json!("metric");
+                Ok(())
+            }
+        }
+
         let server = Server::builder()
             .name("test-server")
             .version("1.0.0")
-            .tool("test-tool", MockTool::new(json!({"result": "success"})))
+            .tool("echo", EchoTool)
+            .method_hook("tools/call", ForceUnits)
             .build()
             .unwrap();
 
-        let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest {
-            name: "test-tool".to_string(),
-            arguments: json!({"input": "test"}),
-        })));
+        let call = |args: Value| {
+            Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest::new(
+                "echo", args,
+            ))))
+        };
 
-        let response = server.handle_request(RequestId::from(1i64), request).await;
+        let response = server
+            .handle_request(RequestId::from(1i64), call(json!({"q": 1})))
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        assert_eq!(
+            result["structuredContent"],
+            json!({"q": 1, "units": "metric"})
+        );
 
-        match response.payload {
-            ResponsePayload::Result(result) => {
-                let call_result: CallToolResult = serde_json::from_value(result).unwrap();
-                assert!(!call_result.is_error);
-                assert_eq!(call_result.content.len(), 1);
-            },
-            ResponsePayload::Error(_) => panic!("Expected success response"),
-        }
+        let response = server
+            .handle_request(RequestId::from(2i64), call(json!({"blocked": true})))
+            .await;
+        let ResponsePayload::Error(error) = response.payload else {
+            panic!("Expected error response");
+        };
+        assert!(error.message.contains("blocked by policy"));
+
+        // Other methods are not routed through the hook
+        let response = server
+            .handle_request(
+                RequestId::from(3i64),
+                Request::Client(Box::new(ClientRequest::ListTools(ListToolsRequest {
+                    cursor: None,
+                }))),
+            )
+            .await;
+        assert!(matches!(response.payload, ResponsePayload::Result(_)));
     }
 
     #[tokio::test]
@@ -2534,6 +5710,7 @@ mod tests {
         let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest {
             name: "nonexistent-tool".to_string(),
             arguments: json!({}),
+            meta: None,
         })));
 
         let response = server.handle_request(RequestId::from(1i64), request).await;
@@ -2612,6 +5789,7 @@ mod tests {
             contents: vec![crate::types::Content::Text {
                 text: "Hello, world!".to_string(),
             }],
+            meta: None,
         };
 
         let server = Server::builder()
@@ -2643,6 +5821,7 @@ mod tests {
             contents: vec![crate::types::Content::Text {
                 text: "Hello, world!".to_string(),
             }],
+            meta: None,
         };
 
         let server = Server::builder()
@@ -2657,6 +5836,7 @@ mod tests {
 
         let request = Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceRequest {
             uri: "test://uri".to_string(),
+            meta: None,
         })));
 
         let response = server.handle_request(RequestId::from(1i64), request).await;
@@ -2682,6 +5862,7 @@ mod tests {
 
         let request = Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceRequest {
             uri: "nonexistent://uri".to_string(),
+            meta: None,
         })));
 
         let response = server.handle_request(RequestId::from(1i64), request).await;
@@ -2694,6 +5875,176 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_handle_list_resource_templates_and_complete() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .resource_template(
+                "file:///logs/{date}.log",
+                "Daily log",
+                "Log for a date",
+                Some("text/plain".to_string()),
+            )
+            .resource_template_completions(
+                "file:///logs/{date}.log",
+                "date",
+                vec!["2024-01-01".to_string(), "2023-12-31".to_string()],
+            )
+            .build()
+            .unwrap();
+        assert!(server.capabilities.resources.is_some());
+
+        let request = Request::Client(Box::new(ClientRequest::ListResourceTemplates(
+            ListResourceTemplatesRequest { cursor: None },
+        )));
+        let response = server.handle_request(RequestId::from(1i64), request).await;
+        match response.payload {
+            ResponsePayload::Result(result) => {
+                let templates: ListResourceTemplatesResult =
+                    serde_json::from_value(result).unwrap();
+                assert_eq!(templates.resource_templates.len(), 1);
+                assert_eq!(
+                    templates.resource_templates[0].uri_template,
+                    "file:///logs/{date}.log"
+                );
+            },
+            ResponsePayload::Error(_) => panic!("Expected success response"),
+        }
+
+        let request = Request::Client(Box::new(ClientRequest::Complete(CompleteRequest {
+            r#ref: CompletionReference::Resource {
+                uri: "file:///logs/{date}.log".to_string(),
+            },
+            argument: crate::types::CompletionArgument {
+                name: "date".to_string(),
+                value: "2024".to_string(),
+            },
+        })));
+        let response = server.handle_request(RequestId::from(2i64), request).await;
+        match response.payload {
+            ResponsePayload::Result(result) => {
+                let complete: CompleteResult = serde_json::from_value(result).unwrap();
+                assert_eq!(complete.completion.values, vec!["2024-01-01"]);
+            },
+            ResponsePayload::Error(_) => panic!("Expected success response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resource_template_router_routes_reads() {
+        let router = resource_templates::ResourceTemplateRouter::new().route(
+            "notes://{id}",
+            |uri, params, _extra| {
+                let uri = uri.to_string();
+                Box::pin(async move {
+                    Ok(crate::types::ReadResourceResult {
+                        contents: vec![crate::types::Content::Resource {
+                            uri,
+                            text: Some(format!("note {}", params["id"])),
+                            mime_type: None,
+                        }],
+                        meta: None,
+                    })
+                })
+            },
+        );
+        let fallback = MockResource::new().with_resource(
+            "test://static".to_string(),
+            crate::types::ReadResourceResult {
+                contents: vec![crate::types::Content::Text {
+                    text: "static".to_string(),
+                }],
+                meta: None,
+            },
+        );
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .resource_template_router(router)
+            .resources(fallback)
+            .build()
+            .unwrap();
+
+        let read = |uri: &str| {
+            Request::Client(Box::new(ClientRequest::ReadResource(ReadResourceRequest {
+                uri: uri.to_string(),
+                meta: None,
+            })))
+        };
+        let response = server
+            .handle_request(RequestId::from(1i64), read("notes://42"))
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        assert_eq!(result["contents"][0]["text"], "note 42");
+
+        let response = server
+            .handle_request(RequestId::from(2i64), read("test://static"))
+            .await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        assert_eq!(result["contents"][0]["text"], "static");
+
+        let request = Request::Client(Box::new(ClientRequest::ListResourceTemplates(
+            ListResourceTemplatesRequest { cursor: None },
+        )));
+        let response = server.handle_request(RequestId::from(3i64), request).await;
+        let ResponsePayload::Result(result) = response.payload else {
+            panic!("Expected success response");
+        };
+        assert_eq!(
+            result["resourceTemplates"][0]["uriTemplate"],
+            "notes://{id}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_completion_provider_answers_prompt_completions() {
+        let server = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .completion_provider(completion::CompletionRouter::new().prompt_argument(
+                "review",
+                "language",
+                completion::prefix_matches(["python", "rust", "ruby"]),
+            ))
+            .build()
+            .unwrap();
+        assert!(server.capabilities.completions.is_some());
+
+        let request = Request::Client(Box::new(ClientRequest::Complete(CompleteRequest {
+            r#ref: CompletionReference::Prompt {
+                name: "review".to_string(),
+            },
+            argument: crate::types::CompletionArgument {
+                name: "language".to_string(),
+                value: "ru".to_string(),
+            },
+        })));
+        let response = server.handle_request(RequestId::from(1i64), request).await;
+        match response.payload {
+            ResponsePayload::Result(result) => {
+                let complete: CompleteResult = serde_json::from_value(result).unwrap();
+                assert_eq!(complete.completion.values, vec!["rust", "ruby"]);
+            },
+            ResponsePayload::Error(_) => panic!("Expected success response"),
+        }
+    }
+
+    #[test]
+    fn test_server_builder_rejects_duplicate_resource_template() {
+        let result = Server::builder()
+            .name("test-server")
+            .version("1.0.0")
+            .resource_template("db://{table}", "Table", "A table", None)
+            .resource_template("db://{table}", "Duplicate", "Same pattern again", None)
+            .build();
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_handle_ping() {
         let server = Server::builder()
@@ -2743,4 +6094,58 @@ mod tests {
             ResponsePayload::Result(_) => panic!("Expected error response"),
         }
     }
+
+    #[tokio::test]
+    async fn test_cancelled_notification_cancels_handler_token() {
+        struct WaitForCancel;
+
+        #[async_trait]
+        impl ToolHandler for WaitForCancel {
+            async fn handle(
+                &self,
+                _args: Value,
+                extra: cancellation::RequestHandlerExtra,
+            ) -> Result<Value> {
+                extra.cancelled().await;
+                Err(Error::Cancelled)
+            }
+        }
+
+        let server = Arc::new(
+            Server::builder()
+                .name("test-server")
+                .version("1.0.0")
+                .tool("wait", WaitForCancel)
+                .build()
+                .unwrap(),
+        );
+        let call = tokio::spawn({
+            let server = server.clone();
+            async move {
+                let request = Request::Client(Box::new(ClientRequest::CallTool(
+                    CallToolRequest::new("wait", json!({})),
+                )));
+                server.handle_request(RequestId::from(7i64), request).await
+            }
+        });
+
+        let cancelled = CancelledNotification {
+            request_id: RequestId::from(7i64),
+            reason: Some("No longer needed".to_string()),
+        };
+        timeout(std::time::Duration::from_secs(5), async {
+            while !server.handle_cancelled(&cancelled).await {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("request never became cancellable");
+
+        let response = timeout(std::time::Duration::from_secs(5), call)
+            .await
+            .expect("handler ignored the cancellation")
+            .unwrap();
+        assert!(matches!(response.payload, ResponsePayload::Error(_)));
+        assert!(!server.handle_cancelled(&cancelled).await);
+    }
 }
@@ -0,0 +1,785 @@
+//! OpenAPI-to-tools generator.
+//!
+//! Turns operations from an `OpenAPI` 3 document into [`ToolHandler`]s. Each
+//! generated tool derives its input schema from the operation's parameters and
+//! JSON request body, performs the HTTP call with the configured
+//! authentication, and returns the response as structured JSON.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::openapi::{ApiAuth, OpenApiToolGenerator};
+//! use pmcp::Server;
+//!
+//! # fn example(spec: &str) -> pmcp::Result<()> {
+//! let tools = OpenApiToolGenerator::from_json(spec)?
+//!     .base_url("https://api.example.com/v1")
+//!     .auth(ApiAuth::Bearer("secret-token".to_string()))
+//!     .include_operation("getPet")
+//!     .include_operation("listPets")
+//!     .generate()?;
+//!
+//! let mut builder = Server::builder().name("petstore").version("1.0.0");
+//! for tool in tools {
+//!     builder = builder.tool(tool.name().to_string(), tool);
+//! }
+//! let server = builder.build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::types::ToolInfo;
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::cancellation::RequestHandlerExtra;
+use super::ToolHandler;
+
+pub use super::api_auth::ApiAuth;
+
+/// HTTP methods that may carry operations in an `OpenAPI` path item.
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Maximum depth followed when resolving `$ref` pointers.
+const MAX_REF_DEPTH: usize = 32;
+
+/// Argument name used for the JSON request body in generated schemas.
+///
+/// Operations with both a request body and a parameter of this name can't
+/// be turned into tools.
+pub const BODY_ARGUMENT: &str = "body";
+
+/// Location of an operation parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterLocation {
+    /// Substituted into the path template.
+    Path,
+    /// Appended to the query string.
+    Query,
+    /// Sent as a request header.
+    Header,
+    /// Sent as a cookie.
+    Cookie,
+}
+
+impl ParameterLocation {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "path" => Some(Self::Path),
+            "query" => Some(Self::Query),
+            "header" => Some(Self::Header),
+            "cookie" => Some(Self::Cookie),
+            _ => None,
+        }
+    }
+}
+
+/// A single operation parameter extracted from the document.
+#[derive(Debug, Clone)]
+pub struct OperationParameter {
+    /// Parameter name
+    pub name: String,
+    /// Where the parameter is sent
+    pub location: ParameterLocation,
+    /// Whether the parameter is required
+    pub required: bool,
+}
+
+/// Generator that builds tools from an `OpenAPI` 3 document.
+#[derive(Debug, Clone)]
+pub struct OpenApiToolGenerator {
+    spec: Value,
+    base_url: Option<String>,
+    auth: ApiAuth,
+    operations: Option<HashSet<String>>,
+    default_headers: Vec<(String, String)>,
+    timeout: Duration,
+}
+
+impl OpenApiToolGenerator {
+    /// Create a generator from a parsed `OpenAPI` document.
+    pub fn new(spec: Value) -> Result<Self> {
+        let version = spec
+            .get("openapi")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::validation("Missing 'openapi' version field"))?;
+        if !version.starts_with("3.") {
+            return Err(Error::validation(format!(
+                "Unsupported OpenAPI version: {}",
+                version
+            )));
+        }
+        if !spec.get("paths").is_some_and(Value::is_object) {
+            return Err(Error::validation("OpenAPI document has no 'paths' object"));
+        }
+
+        Ok(Self {
+            spec,
+            base_url: None,
+            auth: ApiAuth::None,
+            operations: None,
+            default_headers: Vec::new(),
+            timeout: Duration::from_secs(30),
+        })
+    }
+
+    /// Create a generator from a JSON-encoded `OpenAPI` document.
+    pub fn from_json(spec: &str) -> Result<Self> {
+        Self::new(serde_json::from_str(spec)?)
+    }
+
+    /// Override the base URL (defaults to the first entry in `servers`).
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    /// Set the authentication used for API calls.
+    pub fn auth(mut self, auth: ApiAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Only generate tools for the given operation (may be called repeatedly).
+    ///
+    /// Operations are matched by `operationId` or by the generated tool name.
+    /// When no operation is selected, every operation in the document is used.
+    pub fn include_operation(mut self, operation: impl Into<String>) -> Self {
+        self.operations
+            .get_or_insert_with(HashSet::new)
+            .insert(operation.into());
+        self
+    }
+
+    /// Add a header sent with every API call.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the timeout for API calls.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Generate tools for the selected operations.
+    pub fn generate(&self) -> Result<Vec<OpenApiTool>> {
+        let base_url = self.resolve_base_url()?;
+        let client = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .map_err(|e| Error::internal(format!("Failed to create HTTP client: {}", e)))?;
+        let shared = Arc::new(SharedConfig {
+            client,
+            base_url,
+            auth: self.auth.clone(),
+            default_headers: self.default_headers.clone(),
+        });
+
+        let mut tools = Vec::new();
+        let mut seen = HashSet::new();
+        let paths = self.spec["paths"].as_object().into_iter().flatten();
+        for (path, item) in paths {
+            let item = self.resolve(item)?;
+            for method in HTTP_METHODS {
+                let Some(operation) = item.get(*method) else {
+                    continue;
+                };
+                let name = operation_name(operation, method, path);
+                if !self.is_selected(operation, &name) {
+                    continue;
+                }
+                if !seen.insert(name.clone()) {
+                    return Err(Error::validation(format!(
+                        "Duplicate tool name generated from OpenAPI document: {}",
+                        name
+                    )));
+                }
+                tools.push(self.build_tool(name, method, path, item, operation, &shared)?);
+            }
+        }
+
+        if let Some(selected) = &self.operations {
+            let missing: Vec<_> = selected
+                .iter()
+                .filter(|op| !tools.iter().any(|t| t.matches(op)))
+                .cloned()
+                .collect();
+            if !missing.is_empty() {
+                return Err(Error::validation(format!(
+                    "Operations not found in OpenAPI document: {}",
+                    missing.join(", ")
+                )));
+            }
+        }
+
+        Ok(tools)
+    }
+
+    fn resolve_base_url(&self) -> Result<String> {
+        let url = match &self.base_url {
+            Some(url) => url.clone(),
+            None => self.spec["servers"][0]["url"]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| {
+                    Error::validation("No base URL configured and no 'servers' entry in document")
+                })?,
+        };
+        Ok(url.trim_end_matches('/').to_string())
+    }
+
+    fn is_selected(&self, operation: &Value, name: &str) -> bool {
+        self.operations.as_ref().is_none_or(|selected| {
+            selected.contains(name)
+                || operation["operationId"]
+                    .as_str()
+                    .is_some_and(|id| selected.contains(id))
+        })
+    }
+
+    fn build_tool(
+        &self,
+        name: String,
+        method: &str,
+        path: &str,
+        item: &Value,
+        operation: &Value,
+        shared: &Arc<SharedConfig>,
+    ) -> Result<OpenApiTool> {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        let mut parameters = Vec::new();
+
+        // Path-level parameters apply unless overridden by the operation.
+        let declared = item["parameters"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .chain(operation["parameters"].as_array().into_iter().flatten());
+        for raw in declared {
+            let param = self.resolve(raw)?;
+            let (Some(param_name), Some(location)) = (
+                param["name"].as_str(),
+                param["in"].as_str().and_then(ParameterLocation::parse),
+            ) else {
+                return Err(Error::validation(format!(
+                    "Invalid parameter in operation '{}'",
+                    name
+                )));
+            };
+            let is_required =
+                location == ParameterLocation::Path || param["required"].as_bool().unwrap_or(false);
+
+            let mut schema = self.inline_refs(&param["schema"], 0)?;
+            if schema.is_null() {
+                schema = json!({"type": "string"});
+            }
+            if let (Some(obj), Some(desc)) = (schema.as_object_mut(), param["description"].as_str())
+            {
+                obj.insert("description".to_string(), json!(desc));
+            }
+            properties.insert(param_name.to_string(), schema);
+
+            parameters
+                .retain(|p: &OperationParameter| !(p.name == param_name && p.location == location));
+            required.retain(|r| r != param_name);
+            if is_required {
+                required.push(param_name.to_string());
+            }
+            parameters.push(OperationParameter {
+                name: param_name.to_string(),
+                location,
+                required: is_required,
+            });
+        }
+
+        let mut has_body = false;
+        if let Some(body) = operation.get("requestBody") {
+            let body = self.resolve(body)?;
+            let schema = &body["content"]["application/json"]["schema"];
+            if !schema.is_null() {
+                if parameters.iter().any(|p| p.name == BODY_ARGUMENT) {
+                    return Err(Error::validation(format!(
+                        "Parameter '{}' of operation '{}' clashes with its request body",
+                        BODY_ARGUMENT, name
+                    )));
+                }
+                has_body = true;
+                let mut schema = self.inline_refs(schema, 0)?;
+                if let (Some(obj), Some(desc)) =
+                    (schema.as_object_mut(), body["description"].as_str())
+                {
+                    obj.entry("description").or_insert_with(|| json!(desc));
+                }
+                properties.insert(BODY_ARGUMENT.to_string(), schema);
+                if body["required"].as_bool().unwrap_or(false) {
+                    required.push(BODY_ARGUMENT.to_string());
+                }
+            }
+        }
+
+        let description = operation["summary"]
+            .as_str()
+            .or_else(|| operation["description"].as_str())
+            .map(str::to_string);
+
+        Ok(OpenApiTool {
+            name,
+            operation_id: operation["operationId"].as_str().map(str::to_string),
+            description,
+            input_schema: json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            }),
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            parameters,
+            has_body,
+            shared: shared.clone(),
+        })
+    }
+
+    /// Follow a local `$ref` pointer if present.
+    fn resolve<'a>(&'a self, value: &'a Value) -> Result<&'a Value> {
+        let mut current = value;
+        for _ in 0..MAX_REF_DEPTH {
+            match current.get("$ref").and_then(Value::as_str) {
+                Some(reference) => current = self.lookup(reference)?,
+                None => return Ok(current),
+            }
+        }
+        Err(Error::validation(
+            "Reference chain too deep in OpenAPI document",
+        ))
+    }
+
+    fn lookup(&self, reference: &str) -> Result<&Value> {
+        reference
+            .strip_prefix('#')
+            .and_then(|pointer| self.spec.pointer(pointer))
+            .ok_or_else(|| Error::validation(format!("Unresolvable reference: {}", reference)))
+    }
+
+    /// Recursively replace `$ref` pointers with the referenced schemas.
+    fn inline_refs(&self, value: &Value, depth: usize) -> Result<Value> {
+        if depth > MAX_REF_DEPTH {
+            return Err(Error::validation(
+                "Schema nesting too deep (recursive $ref?) in OpenAPI document",
+            ));
+        }
+        match value {
+            Value::Object(obj) => {
+                if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+                    return self.inline_refs(self.lookup(reference)?, depth + 1);
+                }
+                obj.iter()
+                    .map(|(k, v)| Ok((k.clone(), self.inline_refs(v, depth + 1)?)))
+                    .collect::<Result<Map<_, _>>>()
+                    .map(Value::Object)
+            },
+            Value::Array(items) => items
+                .iter()
+                .map(|v| self.inline_refs(v, depth + 1))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+            other => Ok(other.clone()),
+        }
+    }
+}
+
+/// Derive a tool name from an operation.
+fn operation_name(operation: &Value, method: &str, path: &str) -> String {
+    if let Some(id) = operation["operationId"].as_str() {
+        return id.to_string();
+    }
+    let mut name = method.to_string();
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        name.push('_');
+        name.extend(
+            segment
+                .chars()
+                .filter(|c| !matches!(c, '{' | '}'))
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }),
+        );
+    }
+    name
+}
+
+/// Configuration shared by all tools generated from one document.
+#[derive(Debug)]
+struct SharedConfig {
+    client: reqwest::Client,
+    base_url: String,
+    auth: ApiAuth,
+    default_headers: Vec<(String, String)>,
+}
+
+/// A tool generated from a single `OpenAPI` operation.
+#[derive(Debug, Clone)]
+pub struct OpenApiTool {
+    name: String,
+    operation_id: Option<String>,
+    description: Option<String>,
+    input_schema: Value,
+    method: String,
+    path: String,
+    parameters: Vec<OperationParameter>,
+    has_body: bool,
+    shared: Arc<SharedConfig>,
+}
+
+impl OpenApiTool {
+    /// Get the tool name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the HTTP method.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Get the path template.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the generated input schema.
+    pub fn input_schema(&self) -> &Value {
+        &self.input_schema
+    }
+
+    /// Get the operation parameters.
+    pub fn parameters(&self) -> &[OperationParameter] {
+        &self.parameters
+    }
+
+    fn matches(&self, selector: &str) -> bool {
+        self.name == selector || self.operation_id.as_deref() == Some(selector)
+    }
+
+    /// Build the request URL by substituting path parameters.
+    fn build_url(&self, args: &Map<String, Value>) -> Result<String> {
+        let mut path = self.path.clone();
+        for param in &self.parameters {
+            if param.location != ParameterLocation::Path {
+                continue;
+            }
+            let value = args.get(&param.name).ok_or_else(|| {
+                Error::invalid_params(format!("Missing path parameter '{}'", param.name))
+            })?;
+            path = path.replace(
+                &format!("{{{}}}", param.name),
+                &urlencoding::encode(&value_to_string(value)),
+            );
+        }
+        Ok(format!("{}{}", self.shared.base_url, path))
+    }
+
+    fn build_request(&self, args: &Map<String, Value>) -> Result<reqwest::RequestBuilder> {
+        let method = reqwest::Method::from_bytes(self.method.as_bytes())
+            .map_err(|e| Error::internal(format!("Invalid HTTP method: {}", e)))?;
+        let mut builder = self.shared.client.request(method, self.build_url(args)?);
+
+        for (name, value) in &self.shared.default_headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        let mut cookies = Vec::new();
+        for param in &self.parameters {
+            let Some(value) = args.get(&param.name) else {
+                if param.required {
+                    return Err(Error::invalid_params(format!(
+                        "Missing required parameter '{}'",
+                        param.name
+                    )));
+                }
+                continue;
+            };
+            match param.location {
+                ParameterLocation::Path => {},
+                ParameterLocation::Query => {
+                    builder = append_query(builder, &param.name, value);
+                },
+                ParameterLocation::Header => {
+                    builder = builder.header(param.name.as_str(), value_to_string(value));
+                },
+                ParameterLocation::Cookie => {
+                    cookies.push(format!("{}={}", param.name, value_to_string(value)));
+                },
+            }
+        }
+        if !cookies.is_empty() {
+            builder = builder.header("cookie", cookies.join("; "));
+        }
+
+        if self.has_body {
+            if let Some(body) = args.get(BODY_ARGUMENT) {
+                builder = builder.json(body);
+            }
+        }
+
+        Ok(self.shared.auth.apply(builder))
+    }
+}
+
+/// Append a query parameter, exploding arrays into repeated keys.
+fn append_query(
+    builder: reqwest::RequestBuilder,
+    name: &str,
+    value: &Value,
+) -> reqwest::RequestBuilder {
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .fold(builder, |b, item| b.query(&[(name, value_to_string(item))])),
+        other => builder.query(&[(name, value_to_string(other))]),
+    }
+}
+
+/// Render a JSON value as a plain string for URLs and headers.
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[async_trait]
+impl ToolHandler for OpenApiTool {
+    async fn handle(&self, args: Value, extra: RequestHandlerExtra) -> Result<Value> {
+        let args = match args {
+            Value::Object(map) => map,
+            Value::Null => Map::new(),
+            _ => return Err(Error::invalid_params("Tool arguments must be an object")),
+        };
+        let request = self.build_request(&args)?;
+
+        let response = tokio::select! {
+            response = request.send() => response
+                .map_err(|e| Error::internal(format!("HTTP request failed: {}", e)))?,
+            () = extra.cancellation_token.cancelled() => return Err(Error::cancelled()),
+        };
+
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| Error::internal(format!("Failed to read response body: {}", e)))?;
+        let body = serde_json::from_str(&text).unwrap_or(Value::String(text));
+
+        if !status.is_success() {
+            return Err(Error::Protocol {
+                code: crate::ErrorCode::INTERNAL_ERROR,
+                message: format!("{} {} returned HTTP {}", self.method, self.path, status),
+                data: Some(json!({ "status": status.as_u16(), "body": body })),
+            });
+        }
+
+        Ok(json!({ "status": status.as_u16(), "body": body }))
+    }
+
+    fn metadata(&self) -> Option<ToolInfo> {
+        Some(ToolInfo {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            input_schema: self.input_schema.clone(),
+            output_schema: None,
+            annotations: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_util::sync::CancellationToken;
+
+    fn petstore() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Petstore", "version": "1.0.0"},
+            "servers": [{"url": "https://petstore.example.com/v1/"}],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "summary": "List all pets",
+                        "parameters": [
+                            {"name": "limit", "in": "query", "schema": {"type": "integer"}}
+                        ]
+                    },
+                    "post": {
+                        "operationId": "createPet",
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": {
+                                    "schema": {"$ref": "#/components/schemas/Pet"}
+                                }
+                            }
+                        }
+                    }
+                },
+                "/pets/{petId}": {
+                    "parameters": [
+                        {"$ref": "#/components/parameters/PetId"}
+                    ],
+                    "delete": {}
+                }
+            },
+            "components": {
+                "parameters": {
+                    "PetId": {
+                        "name": "petId",
+                        "in": "path",
+                        "description": "The pet identifier",
+                        "schema": {"type": "string"}
+                    }
+                },
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {"name": {"type": "string"}}
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_rejects_non_openapi3() {
+        let err = OpenApiToolGenerator::new(json!({"swagger": "2.0", "paths": {}})).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+    }
+
+    #[test]
+    fn test_generates_all_operations() {
+        let tools = OpenApiToolGenerator::new(petstore())
+            .unwrap()
+            .generate()
+            .unwrap();
+        let mut names: Vec<_> = tools.iter().map(OpenApiTool::name).collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["createPet", "delete_pets_petId", "listPets"]);
+    }
+
+    #[test]
+    fn test_schema_from_parameters_and_body() {
+        let tools = OpenApiToolGenerator::new(petstore())
+            .unwrap()
+            .include_operation("createPet")
+            .include_operation("delete_pets_petId")
+            .generate()
+            .unwrap();
+
+        let create = tools.iter().find(|t| t.name() == "createPet").unwrap();
+        assert_eq!(create.input_schema()["required"], json!(["body"]));
+        assert_eq!(
+            create.input_schema()["properties"]["body"]["required"],
+            json!(["name"])
+        );
+
+        let delete = tools
+            .iter()
+            .find(|t| t.name() == "delete_pets_petId")
+            .unwrap();
+        assert_eq!(delete.method(), "DELETE");
+        assert_eq!(delete.input_schema()["required"], json!(["petId"]));
+        assert_eq!(
+            delete.input_schema()["properties"]["petId"]["description"],
+            "The pet identifier"
+        );
+    }
+
+    #[test]
+    fn test_body_parameter_clashing_with_request_body_is_error() {
+        let mut spec = petstore();
+        spec["paths"]["/pets"]["post"]["parameters"] =
+            json!([{"name": "body", "in": "query", "schema": {"type": "string"}}]);
+        let err = OpenApiToolGenerator::new(spec.clone())
+            .unwrap()
+            .generate()
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        assert!(err.to_string().contains("createPet"));
+
+        // Without a request body the name is free
+        spec["paths"]["/pets"]["post"]
+            .as_object_mut()
+            .unwrap()
+            .remove("requestBody");
+        let tools = OpenApiToolGenerator::new(spec)
+            .unwrap()
+            .include_operation("createPet")
+            .generate()
+            .unwrap();
+        assert_eq!(
+            tools[0].input_schema()["properties"]["body"]["type"],
+            "string"
+        );
+    }
+
+    #[test]
+    fn test_unknown_selected_operation_is_error() {
+        let err = OpenApiToolGenerator::new(petstore())
+            .unwrap()
+            .include_operation("missing")
+            .generate()
+            .unwrap_err();
+        assert!(err.to_string().contains("missing"));
+    }
+
+    #[test]
+    fn test_url_building() {
+        let tools = OpenApiToolGenerator::new(petstore())
+            .unwrap()
+            .include_operation("delete_pets_petId")
+            .generate()
+            .unwrap();
+        let mut args = Map::new();
+        args.insert("petId".to_string(), json!("a b"));
+        assert_eq!(
+            tools[0].build_url(&args).unwrap(),
+            "https://petstore.example.com/v1/pets/a%20b"
+        );
+        assert!(tools[0].build_url(&Map::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_performs_http_call() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/pets")
+            .match_query(mockito::Matcher::UrlEncoded("limit".into(), "2".into()))
+            .match_header("authorization", "Bearer token")
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{"name":"rex"}]"#)
+            .create_async()
+            .await;
+
+        let tools = OpenApiToolGenerator::new(petstore())
+            .unwrap()
+            .base_url(server.url())
+            .auth(ApiAuth::Bearer("token".to_string()))
+            .include_operation("listPets")
+            .generate()
+            .unwrap();
+
+        let extra = RequestHandlerExtra::new("1".to_string(), CancellationToken::new());
+        let result = tools[0].handle(json!({"limit": 2}), extra).await.unwrap();
+        assert_eq!(result["status"], 200);
+        assert_eq!(result["body"][0]["name"], "rex");
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,248 @@
+//! DNS rebinding protection for the HTTP servers.
+//!
+//! A web page can point a domain it controls at `127.0.0.1` and have the
+//! visitor's browser send requests to servers on their machine. An
+//! [`OriginPolicy`] makes the streamable HTTP and SSE servers answer such
+//! requests with `403 Forbidden`:
+//!
+//! - a request with an `Origin` header, which browsers send, must come from
+//!   an allowed origin; requests without one, such as those of non-browser
+//!   clients, pass;
+//! - the `Host` header must name an allowed host. A rebound page's requests
+//!   to its own origin carry no `Origin` header, but name the attacker's
+//!   domain as their host.
+//!
+//! The default policy allows pages and hosts on `localhost`, `127.0.0.1` and
+//! `[::1]`, on any port. Servers reached under other names must list them
+//! with [`OriginPolicy::allow_host`]; the servers warn when they listen on
+//! other addresses without such a name.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::server::origin::OriginPolicy;
+//! use pmcp::server::streamable_http_server::StreamableHttpServerConfig;
+//!
+//! let config = StreamableHttpServerConfig {
+//!     origin_policy: OriginPolicy::localhost()
+//!         .allow_origin("https://app.example.com")
+//!         .allow_host("mcp.example.com"),
+//!     ..Default::default()
+//! };
+//! ```
+
+use axum::http::{header, uri::Authority, HeaderMap};
+use std::net::SocketAddr;
+use url::Url;
+
+/// Origins of pages served from this machine, on any port.
+const LOCAL_ORIGINS: &[&str] = &[
+    "http://localhost",
+    "https://localhost",
+    "http://127.0.0.1",
+    "https://127.0.0.1",
+    "http://[::1]",
+    "https://[::1]",
+];
+
+/// Host names of this machine, on any port.
+const LOCAL_HOSTS: &[&str] = &["localhost", "127.0.0.1", "[::1]"];
+
+/// Which origins and hosts an HTTP server accepts requests for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginPolicy {
+    /// Allowed origins; `None` allows any
+    origins: Option<Vec<String>>,
+    /// Allowed hosts; `None` allows any
+    hosts: Option<Vec<String>>,
+}
+
+impl Default for OriginPolicy {
+    fn default() -> Self {
+        Self::localhost()
+    }
+}
+
+impl OriginPolicy {
+    /// Allow pages and hosts on localhost, on any port.
+    pub fn localhost() -> Self {
+        Self {
+            origins: Some(LOCAL_ORIGINS.iter().map(|s| s.to_string()).collect()),
+            hosts: Some(LOCAL_HOSTS.iter().map(|s| s.to_string()).collect()),
+        }
+    }
+
+    /// Allow any origin and host, turning the protection off.
+    pub fn allow_any() -> Self {
+        Self {
+            origins: None,
+            hosts: None,
+        }
+    }
+
+    /// Also allow pages at `origin`, such as `https://app.example.com`.
+    ///
+    /// An origin without a port allows any port. Has no effect if any
+    /// origin is allowed.
+    pub fn allow_origin(mut self, origin: impl Into<String>) -> Self {
+        if let Some(origins) = &mut self.origins {
+            origins.push(origin.into());
+        }
+        self
+    }
+
+    /// Also accept requests for `host`, such as `mcp.example.com` or
+    /// `mcp.example.com:8080`.
+    ///
+    /// A host without a port allows any port. On a policy allowing any
+    /// host, only the listed hosts are accepted from then on.
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.hosts.get_or_insert_with(Vec::new).push(host.into());
+        self
+    }
+
+    /// Whether requests from pages at `origin` are accepted.
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        let Some(origins) = &self.origins else {
+            return true;
+        };
+        // Opaque origins such as `null` never match
+        let Ok(origin) = Url::parse(origin) else {
+            return false;
+        };
+        origins.iter().any(|allowed| {
+            Url::parse(allowed).is_ok_and(|allowed| {
+                allowed.scheme() == origin.scheme()
+                    && allowed.host_str().is_some()
+                    && allowed.host_str() == origin.host_str()
+                    && allowed
+                        .port()
+                        .is_none_or(|port| origin.port() == Some(port))
+            })
+        })
+    }
+
+    /// Whether requests with `Host: host` are accepted.
+    pub fn allows_host(&self, host: &str) -> bool {
+        let Some(hosts) = &self.hosts else {
+            return true;
+        };
+        let Ok(host) = host.parse::<Authority>() else {
+            return false;
+        };
+        hosts.iter().any(|allowed| {
+            allowed.parse::<Authority>().is_ok_and(|allowed| {
+                allowed.host().eq_ignore_ascii_case(host.host())
+                    && allowed
+                        .port_u16()
+                        .is_none_or(|port| host.port_u16() == Some(port))
+            })
+        })
+    }
+
+    /// Check a request's `Origin` and `Host` headers, returning why it is
+    /// rejected.
+    pub(crate) fn check(&self, headers: &HeaderMap) -> Result<(), &'static str> {
+        if let Some(origin) = headers.get(header::ORIGIN) {
+            if !origin
+                .to_str()
+                .is_ok_and(|origin| self.allows_origin(origin))
+            {
+                return Err("Origin not allowed");
+            }
+        }
+        if self.hosts.is_some() {
+            let host = headers
+                .get(header::HOST)
+                .and_then(|host| host.to_str().ok());
+            if !host.is_some_and(|host| self.allows_host(host)) {
+                return Err("Host not allowed");
+            }
+        }
+        Ok(())
+    }
+
+    /// Warn if the server at `addr` can be reached from other machines
+    /// under any host name, or only under local ones.
+    pub(crate) fn warn_if_exposed(&self, addr: SocketAddr) {
+        if addr.ip().is_loopback() {
+            return;
+        }
+        match &self.hosts {
+            None => tracing::warn!(
+                "Listening on {} without a list of allowed hosts; bind to localhost \
+                 or list the server's host names with OriginPolicy::allow_host",
+                addr
+            ),
+            Some(hosts)
+                if hosts
+                    .iter()
+                    .all(|host| LOCAL_HOSTS.contains(&host.as_str())) =>
+            {
+                tracing::warn!(
+                    "Listening on {} but only accepting local host names; list the \
+                     server's host names with OriginPolicy::allow_host",
+                    addr
+                );
+            },
+            Some(_) => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_allows_local_pages() {
+        let policy = OriginPolicy::default();
+        assert!(policy.allows_origin("http://localhost:3000"));
+        assert!(policy.allows_origin("https://127.0.0.1"));
+        assert!(policy.allows_origin("http://[::1]:8080"));
+        assert!(!policy.allows_origin("http://evil.example.com"));
+        assert!(!policy.allows_origin("http://localhost.evil.example.com"));
+        assert!(!policy.allows_origin("null"));
+        assert!(policy.allows_host("localhost:8080"));
+        assert!(policy.allows_host("127.0.0.1"));
+        assert!(policy.allows_host("[::1]:3000"));
+        assert!(!policy.allows_host("evil.example.com:8080"));
+
+        // Requests of a rebound page to its own origin carry no `Origin`
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, "rebound.example.com:8080".parse().unwrap());
+        assert_eq!(policy.check(&headers), Err("Host not allowed"));
+
+        assert!(OriginPolicy::allow_any().allows_origin("null"));
+        assert!(OriginPolicy::allow_any().allows_host("evil.example.com"));
+    }
+
+    #[test]
+    fn test_listed_origins_and_hosts() {
+        let policy = OriginPolicy::localhost()
+            .allow_origin("https://app.example.com:8443")
+            .allow_host("mcp.example.com")
+            .allow_host("api.example.com:8080");
+        assert!(policy.allows_origin("https://app.example.com:8443"));
+        assert!(!policy.allows_origin("https://app.example.com"));
+        assert!(!policy.allows_origin("http://app.example.com:8443"));
+
+        assert!(policy.allows_host("MCP.example.com:443"));
+        assert!(policy.allows_host("api.example.com:8080"));
+        assert!(!policy.allows_host("api.example.com:9090"));
+        assert!(policy.allows_host("localhost:9090"));
+
+        let only_listed = OriginPolicy::allow_any().allow_host("mcp.example.com");
+        assert!(only_listed.allows_host("mcp.example.com"));
+        assert!(!only_listed.allows_host("localhost"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, "mcp.example.com".parse().unwrap());
+        assert_eq!(policy.check(&headers), Ok(()));
+        headers.insert(header::ORIGIN, "http://evil.example.com".parse().unwrap());
+        assert_eq!(policy.check(&headers), Err("Origin not allowed"));
+        headers.remove(header::ORIGIN);
+        headers.insert(header::HOST, "rebound.example.com".parse().unwrap());
+        assert_eq!(policy.check(&headers), Err("Host not allowed"));
+    }
+}
@@ -0,0 +1,177 @@
+//! Progress notifications from long-running handlers.
+//!
+//! A client that wants progress for a `tools/call` sends a `progressToken` in
+//! the request's `_meta`. The server then attaches a [`ProgressReporter`] to
+//! the handler's [`RequestHandlerExtra`], and
+//! [`RequestHandlerExtra::report_progress`] sends `notifications/progress`
+//! tagged with that token. Without a token, reporting is a no-op, so tools can
+//! report unconditionally.
+//!
+//! Updates closer together than the reporter's minimum interval are dropped,
+//! except the final one, so a tight loop cannot flood the transport.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use pmcp::{RequestHandlerExtra, ToolHandler};
+//! use serde_json::{json, Value};
+//!
+//! struct Indexer;
+//!
+//! #[async_trait]
+//! impl ToolHandler for Indexer {
+//!     async fn handle(&self, _args: Value, extra: RequestHandlerExtra) -> pmcp::Result<Value> {
+//!         let files = 250;
+//!         for done in 1..=files {
+//!             // ... index one file ...
+//!             extra
+//!                 .report_progress(f64::from(done), Some(f64::from(files)), None)
+//!                 .await?;
+//!         }
+//!         Ok(json!({"indexed": files}))
+//!     }
+//! }
+//! ```
+//!
+//! [`RequestHandlerExtra`]: crate::server::cancellation::RequestHandlerExtra
+//! [`RequestHandlerExtra::report_progress`]: crate::server::cancellation::RequestHandlerExtra::report_progress
+
+use crate::error::{Error, Result, TransportError};
+use crate::types::{Notification, ProgressNotification, ProgressToken, ServerNotification};
+use parking_lot::Mutex;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Default minimum time between two progress notifications.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Sends progress notifications for a single request.
+///
+/// Clones share the same rate limit.
+#[derive(Clone)]
+pub struct ProgressReporter {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    token: ProgressToken,
+    tx: mpsc::Sender<Notification>,
+    min_interval: Duration,
+    last_sent: Mutex<Option<Instant>>,
+}
+
+impl fmt::Debug for ProgressReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field("token", &self.inner.token)
+            .field("min_interval", &self.inner.min_interval)
+            .finish()
+    }
+}
+
+impl ProgressReporter {
+    /// Create a reporter sending notifications for `token` through `tx`.
+    pub fn new(token: ProgressToken, tx: mpsc::Sender<Notification>) -> Self {
+        Self::with_min_interval(token, tx, DEFAULT_MIN_INTERVAL)
+    }
+
+    /// Create a reporter that sends at most one update per `min_interval`.
+    pub fn with_min_interval(
+        token: ProgressToken,
+        tx: mpsc::Sender<Notification>,
+        min_interval: Duration,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                token,
+                tx,
+                min_interval,
+                last_sent: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// The caller's progress token.
+    pub fn token(&self) -> &ProgressToken {
+        &self.inner.token
+    }
+
+    /// Report `progress` out of `total`.
+    ///
+    /// With a `total`, the notification carries the completed percentage;
+    /// without one, `progress` is sent as is. Returns `false` if the update
+    /// was dropped by the rate limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the server is no longer running.
+    pub async fn report(
+        &self,
+        progress: f64,
+        total: Option<f64>,
+        message: Option<String>,
+    ) -> Result<bool> {
+        let is_final = total.is_some_and(|total| progress >= total);
+        {
+            let mut last_sent = self.inner.last_sent.lock();
+            let now = Instant::now();
+            if !is_final
+                && last_sent.is_some_and(|last| now.duration_since(last) < self.inner.min_interval)
+            {
+                return Ok(false);
+            }
+            *last_sent = Some(now);
+        }
+
+        let progress = match total {
+            Some(total) if total > 0.0 => progress / total * 100.0,
+            _ => progress,
+        };
+        let notification = ServerNotification::Progress(ProgressNotification {
+            progress_token: self.inner.token.clone(),
+            progress,
+            message,
+        });
+        self.inner
+            .tx
+            .send(Notification::Server(notification))
+            .await
+            .map_err(|_| Error::Transport(TransportError::ConnectionClosed))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_report_rate_limits_all_but_final() {
+        let (tx, mut rx) = mpsc::channel(16);
+        let reporter = ProgressReporter::with_min_interval(
+            ProgressToken::Number(7),
+            tx,
+            Duration::from_secs(60),
+        );
+
+        assert!(reporter.report(1.0, Some(4.0), None).await.unwrap());
+        assert!(!reporter.report(2.0, Some(4.0), None).await.unwrap());
+        assert!(reporter
+            .report(4.0, Some(4.0), Some("done".to_string()))
+            .await
+            .unwrap());
+
+        let mut sent = Vec::new();
+        while let Ok(Notification::Server(ServerNotification::Progress(p))) = rx.try_recv() {
+            assert_eq!(p.progress_token, ProgressToken::Number(7));
+            sent.push((p.progress, p.message));
+        }
+        assert_eq!(sent, [(25.0, None), (100.0, Some("done".to_string()))]);
+
+        drop(rx);
+        assert!(reporter.report(4.0, Some(4.0), None).await.is_err());
+    }
+}
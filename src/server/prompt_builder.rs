@@ -0,0 +1,292 @@
+//! Builders for prompt results with embedded content.
+//!
+//! [`PromptResultBuilder`] assembles a [`GetPromptResult`] from text, images
+//! and embedded resources without hand-constructing the nested
+//! [`Content`] variants. Resources can also be fetched from a
+//! [`ResourceHandler`] by URI and embedded directly into a message.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::prompt_builder::PromptResultBuilder;
+//! use pmcp::server::simple_resources::{ResourceCollection, StaticResource};
+//! use pmcp::types::Role;
+//! use pmcp::RequestHandlerExtra;
+//! use tokio_util::sync::CancellationToken;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let resources = ResourceCollection::new()
+//!     .add_resource(StaticResource::new_text("docs://style", "Use four spaces."));
+//! let extra = RequestHandlerExtra::new("req".to_string(), CancellationToken::new());
+//!
+//! let result = PromptResultBuilder::new()
+//!     .with_description("Code review")
+//!     .with_text(Role::User, "Review the following code using our style guide.")
+//!     .with_resource_from(Role::User, &resources, "docs://style", extra)
+//!     .await?
+//!     .with_image_bytes(Role::User, b"\x89PNG...", "image/png")
+//!     .build();
+//! # let _ = result;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types::{Content, GetPromptResult, PromptMessage, ResourceContents, ResourceLink, Role};
+use crate::Result;
+use base64::Engine;
+
+use super::cancellation::RequestHandlerExtra;
+use super::ResourceHandler;
+
+/// Builder for [`GetPromptResult`] values.
+#[derive(Debug, Clone, Default)]
+pub struct PromptResultBuilder {
+    description: Option<String>,
+    messages: Vec<PromptMessage>,
+}
+
+impl PromptResultBuilder {
+    /// Create an empty prompt result builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the description of the prompt result.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a message with arbitrary content.
+    pub fn with_message(mut self, role: Role, content: Content) -> Self {
+        self.messages.push(PromptMessage { role, content });
+        self
+    }
+
+    /// Add a text message.
+    pub fn with_text(self, role: Role, text: impl Into<String>) -> Self {
+        self.with_message(role, Content::Text { text: text.into() })
+    }
+
+    /// Add an image message from base64-encoded data.
+    pub fn with_image(
+        self,
+        role: Role,
+        data: impl Into<String>,
+        mime_type: impl Into<String>,
+    ) -> Self {
+        self.with_message(
+            role,
+            Content::Image {
+                data: data.into(),
+                mime_type: mime_type.into(),
+            },
+        )
+    }
+
+    /// Add an image message from raw bytes, encoding them as base64.
+    pub fn with_image_bytes(
+        self,
+        role: Role,
+        bytes: impl AsRef<[u8]>,
+        mime_type: impl Into<String>,
+    ) -> Self {
+        let data = base64::prelude::BASE64_STANDARD.encode(bytes);
+        self.with_image(role, data, mime_type)
+    }
+
+    /// Add a message embedding a resource with known contents.
+    pub fn with_embedded_resource(
+        self,
+        role: Role,
+        uri: impl Into<String>,
+        text: impl Into<String>,
+        mime_type: Option<String>,
+    ) -> Self {
+        let resource = ResourceContents {
+            mime_type,
+            ..ResourceContents::text(uri, text)
+        };
+        self.with_message(role, Content::embedded_resource(resource))
+    }
+
+    /// Add a message linking to a resource without embedding its contents.
+    pub fn with_resource_link(self, role: Role, link: ResourceLink) -> Self {
+        self.with_message(role, Content::ResourceLink(link))
+    }
+
+    /// Read a resource from `resources` and embed its contents.
+    ///
+    /// Each content item returned by the handler becomes one message,
+    /// embedding its text or binary data as a resource with the handler's
+    /// MIME type. Contents without a URI of their own carry the requested
+    /// one; resource links and embedded resources are kept unchanged.
+    pub async fn with_resource_from<R>(
+        mut self,
+        role: Role,
+        resources: &R,
+        uri: &str,
+        extra: RequestHandlerExtra,
+    ) -> Result<Self>
+    where
+        R: ResourceHandler + ?Sized,
+    {
+        let result = resources.read(uri, extra).await?;
+        if result.contents.is_empty() {
+            return Err(crate::Error::not_found(format!(
+                "Resource '{}' has no contents",
+                uri
+            )));
+        }
+
+        for content in result.contents {
+            let content = match content {
+                Content::Text { text } => ResourceContents::text(uri, text).into(),
+                Content::Resource {
+                    uri,
+                    text,
+                    mime_type,
+                } => ResourceContents {
+                    uri,
+                    mime_type,
+                    text,
+                    blob: None,
+                }
+                .into(),
+                Content::Image { data, mime_type } | Content::Audio { data, mime_type } => {
+                    ResourceContents {
+                        uri: uri.to_string(),
+                        mime_type: Some(mime_type),
+                        text: None,
+                        blob: Some(data),
+                    }
+                    .into()
+                },
+                content @ (Content::ResourceLink(_) | Content::EmbeddedResource { .. }) => content,
+            };
+            self.messages.push(PromptMessage { role, content });
+        }
+        Ok(self)
+    }
+
+    /// Build the prompt result.
+    pub fn build(self) -> GetPromptResult {
+        GetPromptResult {
+            description: self.description,
+            messages: self.messages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::simple_resources::{ResourceCollection, StaticResource};
+    use tokio_util::sync::CancellationToken;
+
+    fn extra() -> RequestHandlerExtra {
+        RequestHandlerExtra::new("test".to_string(), CancellationToken::new())
+    }
+
+    #[test]
+    fn test_builds_mixed_messages() {
+        let result = PromptResultBuilder::new()
+            .with_description("desc")
+            .with_text(Role::User, "hello")
+            .with_image_bytes(Role::User, b"abc", "image/png")
+            .with_embedded_resource(
+                Role::Assistant,
+                "file:///a.txt",
+                "body",
+                Some("text/plain".to_string()),
+            )
+            .with_resource_link(
+                Role::User,
+                ResourceLink::new("file:///b.pdf", "b.pdf").with_mime_type("application/pdf"),
+            )
+            .build();
+
+        assert_eq!(result.description.as_deref(), Some("desc"));
+        assert_eq!(result.messages.len(), 4);
+        match &result.messages[1].content {
+            Content::Image { data, mime_type } => {
+                assert_eq!(data, "YWJj");
+                assert_eq!(mime_type, "image/png");
+            },
+            other => panic!("unexpected content: {:?}", other),
+        }
+        assert_eq!(result.messages[2].role, Role::Assistant);
+
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            value["messages"][2]["content"],
+            serde_json::json!({
+                "type": "resource",
+                "resource": {
+                    "uri": "file:///a.txt",
+                    "mimeType": "text/plain",
+                    "text": "body"
+                }
+            })
+        );
+        assert_eq!(
+            value["messages"][3]["content"],
+            serde_json::json!({
+                "type": "resource_link",
+                "uri": "file:///b.pdf",
+                "name": "b.pdf",
+                "mimeType": "application/pdf"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_embeds_registered_resource() {
+        let resources = ResourceCollection::new()
+            .add_resource(
+                StaticResource::new_text("docs://guide", "Be concise.")
+                    .with_mime_type("text/markdown"),
+            )
+            .add_resource(StaticResource::new_image(
+                "docs://logo",
+                b"\x89PNG",
+                "image/png",
+            ));
+
+        let result = PromptResultBuilder::new()
+            .with_resource_from(Role::User, &resources, "docs://guide", extra())
+            .await
+            .unwrap()
+            .build();
+
+        assert_eq!(result.messages.len(), 1);
+        match &result.messages[0].content {
+            Content::EmbeddedResource { resource } => {
+                assert_eq!(resource.uri, "docs://guide");
+                assert_eq!(resource.text.as_deref(), Some("Be concise."));
+                assert_eq!(resource.mime_type.as_deref(), Some("text/markdown"));
+            },
+            other => panic!("unexpected content: {:?}", other),
+        }
+
+        let result = PromptResultBuilder::new()
+            .with_resource_from(Role::User, &resources, "docs://logo", extra())
+            .await
+            .unwrap()
+            .build();
+        match &result.messages[0].content {
+            Content::EmbeddedResource { resource } => {
+                assert_eq!(resource.uri, "docs://logo");
+                assert_eq!(resource.mime_type.as_deref(), Some("image/png"));
+                assert_eq!(resource.blob.as_deref(), Some("iVBORw=="));
+                assert_eq!(resource.text, None);
+            },
+            other => panic!("unexpected content: {:?}", other),
+        }
+
+        let missing = PromptResultBuilder::new()
+            .with_resource_from(Role::User, &resources, "docs://missing", extra())
+            .await;
+        assert!(missing.is_err());
+    }
+}
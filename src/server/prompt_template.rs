@@ -0,0 +1,326 @@
+//! Prompts rendered from text templates with `{{argument}}` placeholders.
+//!
+//! [`PromptTemplate`] covers the common case of prompts that only substitute
+//! arguments into fixed text, without a template engine. For conditionals and
+//! loops, use [`TemplatePrompt`](super::simple_prompt) (feature `templates`).
+
+use crate::types::{Content, GetPromptResult, PromptArgument, PromptInfo, PromptMessage, Role};
+use crate::{Error, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use super::cancellation::RequestHandlerExtra;
+use super::PromptHandler;
+
+/// Part of a parsed message template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Argument(String),
+}
+
+/// Parse a template into text and `{{name}}` placeholders.
+fn parse(template: &str) -> std::result::Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            segments.push(Segment::Text(rest[..start].to_string()));
+        }
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| {
+            format!(
+                "unclosed placeholder at byte {}",
+                template.len() - rest.len() + start
+            )
+        })?;
+        let name = after[..end].trim();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!("invalid placeholder '{{{{{}}}}}'", &after[..end]));
+        }
+        segments.push(Segment::Argument(name.to_string()));
+        rest = &after[end + 2..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest.to_string()));
+    }
+    Ok(segments)
+}
+
+/// A prompt whose messages are text templates with `{{argument}}`
+/// placeholders.
+///
+/// Placeholders name a declared argument, with optional whitespace inside the
+/// braces (`{{ topic }}`). Missing optional arguments render as empty text;
+/// a missing required argument fails the request. Templates are parsed when
+/// added; parse errors and placeholders naming undeclared arguments are
+/// reported by [`PromptHandler::validate`], so the server fails to build
+/// instead of failing on the first request.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::server::prompt_template::PromptTemplate;
+/// use pmcp::types::Role;
+/// use std::collections::HashMap;
+///
+/// let prompt = PromptTemplate::new("translate")
+///     .with_description("Translate text")
+///     .with_argument("language", "Target language", true)
+///     .with_argument("text", "Text to translate", true)
+///     .with_message(Role::System, "You translate into {{ language }}.")
+///     .with_message(Role::User, "{{text}}");
+///
+/// let args = HashMap::from([
+///     ("language".to_string(), "French".to_string()),
+///     ("text".to_string(), "Good morning".to_string()),
+/// ]);
+/// let result = prompt.render(&args)?;
+/// assert_eq!(result.messages.len(), 2);
+/// assert_eq!(result.messages[1].role, Role::User);
+///
+/// // Required arguments are enforced.
+/// assert!(prompt.render(&HashMap::new()).is_err());
+/// # Ok::<(), pmcp::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    name: String,
+    description: Option<String>,
+    arguments: Vec<PromptArgument>,
+    messages: Vec<(Role, Vec<Segment>)>,
+    errors: Vec<String>,
+}
+
+impl PromptTemplate {
+    /// Create a new prompt template with a name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            arguments: Vec::new(),
+            messages: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Set the description for this prompt.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add an argument to this prompt.
+    pub fn with_argument(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        required: bool,
+    ) -> Self {
+        self.arguments.push(PromptArgument {
+            name: name.into(),
+            description: Some(description.into()),
+            required,
+            completion: None,
+        });
+        self
+    }
+
+    /// Set all arguments at once.
+    pub fn with_arguments(mut self, arguments: Vec<PromptArgument>) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// Add a message rendered from a template.
+    ///
+    /// Messages are emitted in the order they are added.
+    pub fn with_message(mut self, role: Role, template: impl AsRef<str>) -> Self {
+        match parse(template.as_ref()) {
+            Ok(segments) => self.messages.push((role, segments)),
+            Err(e) => {
+                self.errors
+                    .push(format!("message {}: {}", self.messages.len(), e));
+                self.messages.push((role, Vec::new()));
+            },
+        }
+        self
+    }
+
+    /// Render the messages with the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if a required argument is missing or a
+    /// template failed to parse.
+    pub fn render(&self, args: &HashMap<String, String>) -> Result<GetPromptResult> {
+        self.validate_templates()?;
+        for arg in &self.arguments {
+            if arg.required && !args.contains_key(&arg.name) {
+                return Err(Error::validation(format!(
+                    "Required argument '{}' is missing",
+                    arg.name
+                )));
+            }
+        }
+
+        let messages = self
+            .messages
+            .iter()
+            .map(|(role, segments)| {
+                let text = segments
+                    .iter()
+                    .map(|segment| match segment {
+                        Segment::Text(text) => text.as_str(),
+                        Segment::Argument(name) => args.get(name).map_or("", String::as_str),
+                    })
+                    .collect();
+                PromptMessage {
+                    role: *role,
+                    content: Content::Text { text },
+                }
+            })
+            .collect();
+
+        Ok(GetPromptResult {
+            description: self.description.clone(),
+            messages,
+        })
+    }
+
+    fn validate_templates(&self) -> Result<()> {
+        if let Some(error) = self.errors.first() {
+            return Err(Error::validation(format!(
+                "Prompt '{}' has an invalid template: {}",
+                self.name, error
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PromptHandler for PromptTemplate {
+    async fn handle(
+        &self,
+        args: HashMap<String, String>,
+        _extra: RequestHandlerExtra,
+    ) -> Result<GetPromptResult> {
+        self.render(&args)
+    }
+
+    fn metadata(&self) -> Option<PromptInfo> {
+        Some(PromptInfo {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            arguments: if self.arguments.is_empty() {
+                None
+            } else {
+                Some(self.arguments.clone())
+            },
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.validate_templates()?;
+        for (index, (_, segments)) in self.messages.iter().enumerate() {
+            let mut undeclared: Vec<&str> = segments
+                .iter()
+                .filter_map(|segment| match segment {
+                    Segment::Argument(name)
+                        if !self.arguments.iter().any(|arg| &arg.name == name) =>
+                    {
+                        Some(name.as_str())
+                    },
+                    _ => None,
+                })
+                .collect();
+            if !undeclared.is_empty() {
+                undeclared.sort_unstable();
+                undeclared.dedup();
+                return Err(Error::validation(format!(
+                    "Prompt '{}' message {} references undeclared arguments: {}",
+                    self.name,
+                    index,
+                    undeclared.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(result: &GetPromptResult, index: usize) -> &str {
+        match &result.messages[index].content {
+            Content::Text { text } => text,
+            other => panic!("unexpected content: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_placeholders() {
+        assert_eq!(
+            parse("Hi {{ name }}, {{x}}!").unwrap(),
+            vec![
+                Segment::Text("Hi ".to_string()),
+                Segment::Argument("name".to_string()),
+                Segment::Text(", ".to_string()),
+                Segment::Argument("x".to_string()),
+                Segment::Text("!".to_string()),
+            ]
+        );
+        assert_eq!(parse("").unwrap(), vec![]);
+        assert!(parse("{{ open").is_err());
+        assert!(parse("{{}}").is_err());
+        assert!(parse("{{ a b }}").is_err());
+    }
+
+    #[test]
+    fn test_render_substitutes_arguments() {
+        let prompt = PromptTemplate::new("review")
+            .with_argument("language", "Language", true)
+            .with_argument("focus", "Focus", false)
+            .with_message(Role::System, "You review {{language}} code.")
+            .with_message(Role::User, "Focus: {{ focus }}. Language: {{language}}");
+        prompt.validate().unwrap();
+
+        let args = HashMap::from([("language".to_string(), "Rust".to_string())]);
+        let result = prompt.render(&args).unwrap();
+        assert_eq!(result.messages[0].role, Role::System);
+        assert_eq!(text(&result, 0), "You review Rust code.");
+        assert_eq!(text(&result, 1), "Focus: . Language: Rust");
+
+        let err = prompt.render(&HashMap::new()).unwrap_err();
+        assert!(err.to_string().contains("language"));
+    }
+
+    #[test]
+    fn test_validate_reports_template_errors() {
+        let broken = PromptTemplate::new("broken").with_message(Role::User, "{{ unclosed");
+        assert!(broken.validate().is_err());
+        assert!(broken.render(&HashMap::new()).is_err());
+
+        let undeclared = PromptTemplate::new("undeclared")
+            .with_argument("a", "A", true)
+            .with_message(Role::User, "{{ a }} {{ b }} {{ b }}");
+        let err = undeclared.validate().unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with("references undeclared arguments: b"));
+
+        let result = crate::Server::builder()
+            .name("test")
+            .version("1.0.0")
+            .prompt("undeclared", undeclared)
+            .build();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,467 @@
+//! Proxying between an MCP client and an upstream server.
+//!
+//! [`ProxyServer`] forwards every message between a client and an upstream
+//! server, in both directions and over any combination of transports. Hooks
+//! see each message on its way and can log it, rewrite it, hold it back to
+//! add latency, drop it, or answer it in the peer's place to inject errors,
+//! which makes the proxy useful for debugging and chaos testing tool
+//! integrations.
+//!
+//! Serving the client side with an
+//! [`SseServerTransport`](crate::server::transport::SseServerTransport) makes
+//! a server that speaks any other transport reachable from the MCP
+//! Inspector's SSE connection type.
+//!
+//! Hooks run in order on the proxy's single task, so a delay holds back the
+//! traffic behind it and messages never overtake each other. The proxy waits
+//! on both transports at once, so their `receive_batch` must be cancel-safe,
+//! as those of [`StdioTransport`](crate::shared::StdioTransport), `TcpTransport`
+//! and [`InMemoryTransport`](crate::shared::InMemoryTransport) are.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::proxy::{ErrorInjection, Latency, ProxyServer};
+//! use pmcp::ErrorCode;
+//! use std::time::Duration;
+//!
+//! # #[cfg(all(feature = "streamable-http", feature = "tcp"))]
+//! # async fn example() -> pmcp::Result<()> {
+//! use pmcp::server::transport::SseServerTransport;
+//! use pmcp::shared::TcpTransport;
+//!
+//! let upstream = TcpTransport::connect("127.0.0.1:7000").await?;
+//! let mut inspector = SseServerTransport::builder()
+//!     .bind_addr("127.0.0.1:6277".parse().unwrap())
+//!     .build();
+//! inspector.bind().await?;
+//!
+//! ProxyServer::new(upstream)
+//!     .inspect(|direction, message| eprintln!("{:?}: {:?}", direction, message))
+//!     .hook(Latency::new(Duration::from_millis(500)).method("tools/call"))
+//!     .hook(ErrorInjection::new(ErrorCode::INTERNAL_ERROR, "Injected failure").every(3))
+//!     .run(inspector)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, ErrorCode, Result, TransportError};
+use crate::shared::{request_method, Transport, TransportMessage};
+use crate::types::{JSONRPCResponse, RequestId};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Which way a message travels through the proxy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyDirection {
+    /// From the client to the upstream server.
+    ClientToServer,
+    /// From the upstream server to the client.
+    ServerToClient,
+}
+
+/// What a [`ProxyHook`] does with a message.
+#[derive(Debug)]
+pub enum ProxyAction {
+    /// Pass the message on, possibly changed.
+    Forward(TransportMessage),
+    /// Drop the message.
+    Drop,
+    /// Send this message back to the sender instead of forwarding.
+    Reply(TransportMessage),
+}
+
+impl ProxyAction {
+    /// Answer the request `id` with `error` instead of forwarding it.
+    pub fn error(id: RequestId, error: Error) -> Self {
+        Self::Reply(TransportMessage::Response(JSONRPCResponse::error(
+            id,
+            error.into(),
+        )))
+    }
+}
+
+/// Sees each message passing through a [`ProxyServer`].
+///
+/// Hooks run in the order they were added, each on the message the previous
+/// one forwarded; the first to drop or answer a message ends its way
+/// through the proxy.
+#[async_trait]
+pub trait ProxyHook: Send + Sync {
+    /// Decide what happens to `message`.
+    async fn on_message(&self, direction: ProxyDirection, message: TransportMessage)
+        -> ProxyAction;
+}
+
+/// Passes every message to a callback and forwards it unchanged.
+struct Inspect<F>(F);
+
+#[async_trait]
+impl<F> ProxyHook for Inspect<F>
+where
+    F: Fn(ProxyDirection, &TransportMessage) + Send + Sync,
+{
+    async fn on_message(
+        &self,
+        direction: ProxyDirection,
+        message: TransportMessage,
+    ) -> ProxyAction {
+        (self.0)(direction, &message);
+        ProxyAction::Forward(message)
+    }
+}
+
+/// The method of `message` if it is a request.
+fn requested_method(message: &TransportMessage) -> Option<&'static str> {
+    match message {
+        TransportMessage::Request { request, .. } => Some(request_method(request)),
+        _ => None,
+    }
+}
+
+/// Delays messages, to see how a client copes with a slow server.
+#[derive(Debug, Clone)]
+pub struct Latency {
+    delay: Duration,
+    direction: ProxyDirection,
+    method: Option<String>,
+}
+
+impl Latency {
+    /// Delay every message from the client to the server by `delay`.
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            direction: ProxyDirection::ClientToServer,
+            method: None,
+        }
+    }
+
+    /// Delay the messages travelling in `direction` instead.
+    pub fn direction(mut self, direction: ProxyDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Only delay requests for `method`.
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self
+    }
+}
+
+#[async_trait]
+impl ProxyHook for Latency {
+    async fn on_message(
+        &self,
+        direction: ProxyDirection,
+        message: TransportMessage,
+    ) -> ProxyAction {
+        let method_matches = self
+            .method
+            .as_deref()
+            .is_none_or(|method| requested_method(&message) == Some(method));
+        if direction == self.direction && method_matches {
+            tokio::time::sleep(self.delay).await;
+        }
+        ProxyAction::Forward(message)
+    }
+}
+
+/// Answers client requests with an error instead of forwarding them.
+#[derive(Debug)]
+pub struct ErrorInjection {
+    code: ErrorCode,
+    message: String,
+    method: Option<String>,
+    every: u64,
+    seen: AtomicU64,
+}
+
+impl ErrorInjection {
+    /// Fail every request with `code` and `message`.
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            method: None,
+            every: 1,
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    /// Only fail requests for `method`.
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self
+    }
+
+    /// Only fail every `n`th matching request, starting with the `n`th;
+    /// zero is treated as one.
+    pub fn every(mut self, n: u64) -> Self {
+        self.every = n.max(1);
+        self
+    }
+}
+
+#[async_trait]
+impl ProxyHook for ErrorInjection {
+    async fn on_message(
+        &self,
+        direction: ProxyDirection,
+        message: TransportMessage,
+    ) -> ProxyAction {
+        let matches = direction == ProxyDirection::ClientToServer
+            && requested_method(&message).is_some_and(|requested| {
+                self.method
+                    .as_deref()
+                    .is_none_or(|method| method == requested)
+            });
+        if !matches {
+            return ProxyAction::Forward(message);
+        }
+        let seen = self.seen.fetch_add(1, Ordering::Relaxed) + 1;
+        match message {
+            TransportMessage::Request { id, .. } if seen % self.every == 0 => {
+                ProxyAction::error(id, Error::protocol(self.code, self.message.clone()))
+            },
+            message => ProxyAction::Forward(message),
+        }
+    }
+}
+
+/// Forwards traffic between a client and an upstream MCP server.
+///
+/// See the [module documentation](self).
+pub struct ProxyServer<U> {
+    upstream: U,
+    hooks: Vec<Arc<dyn ProxyHook>>,
+}
+
+impl<U: Transport> std::fmt::Debug for ProxyServer<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxyServer")
+            .field("upstream", &self.upstream)
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+impl<U: Transport> ProxyServer<U> {
+    /// Proxy to the server at the other end of `upstream`.
+    pub fn new(upstream: U) -> Self {
+        Self {
+            upstream,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Add a hook, run after those added before it.
+    pub fn hook(mut self, hook: impl ProxyHook + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Pass every message to `inspect`, e.g. to log the traffic.
+    ///
+    /// Messages are seen as the hooks added before leave them.
+    pub fn inspect(
+        self,
+        inspect: impl Fn(ProxyDirection, &TransportMessage) + Send + Sync + 'static,
+    ) -> Self {
+        self.hook(Inspect(inspect))
+    }
+
+    /// Proxy between the client at the other end of `client` and the
+    /// upstream server until either side closes its connection.
+    ///
+    /// Both transports are closed when the proxy stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transport error that stopped the proxy, unless a side
+    /// simply closed its connection.
+    pub async fn run(mut self, mut client: impl Transport) -> Result<()> {
+        let result = self.pump(&mut client).await;
+        // One side is gone; take the other down with it
+        let _ = client.close().await;
+        let _ = self.upstream.close().await;
+        match result {
+            Err(Error::Transport(TransportError::ConnectionClosed)) => Ok(()),
+            result => result,
+        }
+    }
+
+    async fn pump(&mut self, client: &mut impl Transport) -> Result<()> {
+        loop {
+            let (direction, received) = tokio::select! {
+                received = client.receive_batch() => (ProxyDirection::ClientToServer, received),
+                received = self.upstream.receive_batch() => {
+                    (ProxyDirection::ServerToClient, received)
+                },
+            };
+
+            let mut forwarded = Vec::new();
+            let mut replies = Vec::new();
+            for message in received? {
+                match self.apply_hooks(direction, message).await {
+                    ProxyAction::Forward(message) => forwarded.push(message),
+                    ProxyAction::Reply(message) => replies.push(message),
+                    ProxyAction::Drop => {},
+                }
+            }
+
+            match direction {
+                ProxyDirection::ClientToServer => {
+                    deliver(&mut self.upstream, forwarded).await?;
+                    deliver(client, replies).await?;
+                },
+                ProxyDirection::ServerToClient => {
+                    deliver(client, forwarded).await?;
+                    deliver(&mut self.upstream, replies).await?;
+                },
+            }
+        }
+    }
+
+    async fn apply_hooks(
+        &self,
+        direction: ProxyDirection,
+        message: TransportMessage,
+    ) -> ProxyAction {
+        let mut action = ProxyAction::Forward(message);
+        for hook in &self.hooks {
+            let ProxyAction::Forward(message) = action else {
+                break;
+            };
+            action = hook.on_message(direction, message).await;
+        }
+        action
+    }
+}
+
+/// Send the messages of one frame, as a batch if there are several.
+async fn deliver(
+    transport: &mut impl Transport,
+    mut messages: Vec<TransportMessage>,
+) -> Result<()> {
+    match messages.len() {
+        0 => Ok(()),
+        1 => transport.send(messages.remove(0)).await,
+        _ => transport.send_batch(messages).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::simple_tool::SyncTool;
+    use crate::shared::InMemoryTransport;
+    use crate::{Client, ClientCapabilities, Server};
+    use serde_json::json;
+
+    /// A client connected through `proxy` to a server with an `echo` tool.
+    async fn proxied_client(
+        proxy: impl FnOnce(InMemoryTransport) -> ProxyServer<InMemoryTransport>,
+    ) -> Client<InMemoryTransport> {
+        let (upstream, server_transport) = InMemoryTransport::pair();
+        let server = Server::builder()
+            .name("upstream")
+            .version("1.0.0")
+            .tool("echo", SyncTool::new("echo", Ok))
+            .build()
+            .unwrap();
+        tokio::spawn(server.run(server_transport));
+
+        let (client_transport, proxy_transport) = InMemoryTransport::pair();
+        tokio::spawn(proxy(upstream).run(proxy_transport));
+
+        let mut client = Client::new(client_transport);
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+        client
+    }
+
+    #[tokio::test]
+    async fn test_proxy_forwards_and_rewrites_traffic() {
+        struct Rewrite;
+
+        #[async_trait]
+        impl ProxyHook for Rewrite {
+            async fn on_message(
+                &self,
+                _direction: ProxyDirection,
+                mut message: TransportMessage,
+            ) -> ProxyAction {
+                if let TransportMessage::Request { request, .. } = &mut message {
+                    if let crate::types::Request::Client(request) = request {
+                        if let crate::types::ClientRequest::CallTool(call) = &mut **request {
+                            call.arguments = json!({"rewritten": true});
+                        }
+                    }
+                }
+                ProxyAction::Forward(message)
+            }
+        }
+
+        let seen = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let client = proxied_client(|upstream| {
+            ProxyServer::new(upstream)
+                .hook(Rewrite)
+                .inspect(move |direction, message| {
+                    log.lock().push((direction, requested_method(message)));
+                })
+        })
+        .await;
+
+        let result = client
+            .call_tool("echo".to_string(), json!({"text": "hi"}))
+            .await
+            .unwrap();
+        assert!(serde_json::to_string(&result)
+            .unwrap()
+            .contains("rewritten"));
+
+        let seen = seen.lock();
+        assert_eq!(
+            seen.first(),
+            Some(&(ProxyDirection::ClientToServer, Some("initialize")))
+        );
+        assert!(seen.contains(&(ProxyDirection::ClientToServer, Some("tools/call"))));
+        assert!(seen.contains(&(ProxyDirection::ServerToClient, None)));
+    }
+
+    #[tokio::test]
+    async fn test_proxy_injects_errors_and_latency() {
+        let client = proxied_client(|upstream| {
+            ProxyServer::new(upstream)
+                .hook(Latency::new(Duration::from_millis(50)).method("tools/call"))
+                .hook(
+                    ErrorInjection::new(ErrorCode::INTERNAL_ERROR, "Injected failure")
+                        .method("tools/call")
+                        .every(2),
+                )
+        })
+        .await;
+
+        let started = std::time::Instant::now();
+        assert!(client
+            .call_tool("echo".to_string(), json!({}))
+            .await
+            .is_ok());
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        let err = client
+            .call_tool("echo".to_string(), json!({}))
+            .await
+            .unwrap_err();
+        assert!(err.is_error_code(ErrorCode::INTERNAL_ERROR));
+        assert!(err.to_string().contains("Injected failure"));
+        client.ping().await.unwrap();
+    }
+}
@@ -0,0 +1,312 @@
+//! Per-principal rate limiting.
+//!
+//! [`RateLimitMiddleware`](crate::shared::RateLimitMiddleware) limits a
+//! transport as a whole. A [`PrincipalRateLimiter`] installed on the server
+//! instead keeps a token bucket per caller and method group, so one busy
+//! user can't starve the others. Callers are identified by the subject the
+//! server's auth provider authenticates from the request's `Authorization`
+//! header, falling back to the client's IP address for transports that know
+//! it (the streamable HTTP server) and to the connection otherwise.
+//!
+//! Requests over the limit fail with `RATE_LIMITED`; the error data carries
+//! `retryAfterMs`, the time until a token is available again.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::rate_limit::{PrincipalRateLimiter, TokenBucket};
+//! use pmcp::Server;
+//!
+//! let server = Server::builder()
+//!     .name("search")
+//!     .version("1.0.0")
+//!     .rate_limit(
+//!         PrincipalRateLimiter::new()
+//!             // Bursts of 10 tool calls, then one every 2 seconds
+//!             .tools(TokenBucket::new(10, 0.5))
+//!             .resources(TokenBucket::new(100, 20.0)),
+//!     )
+//!     .build()?;
+//! # Ok::<(), pmcp::Error>(())
+//! ```
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::types::ClientRequest;
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// Default number of buckets kept before the least recently used are dropped.
+pub const DEFAULT_MAX_PRINCIPALS: usize = 10_000;
+
+tokio::task_local! {
+    /// Address of the client whose request is being handled, for transports
+    /// that know it
+    pub(crate) static CLIENT_ADDR: IpAddr;
+}
+
+/// Address of the client whose request is being handled, if known.
+pub(crate) fn current_client_addr() -> Option<IpAddr> {
+    CLIENT_ADDR.try_with(|addr| *addr).ok()
+}
+
+/// Families of methods limited together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MethodGroup {
+    /// `tools/list` and `tools/call`.
+    Tools,
+    /// `resources/*`, including subscriptions.
+    Resources,
+    /// `prompts/list` and `prompts/get`.
+    Prompts,
+    /// Everything else except `initialize` and `ping`.
+    Other,
+}
+
+impl MethodGroup {
+    /// The group `request` counts against, or `None` for requests that are
+    /// never limited.
+    pub fn of(request: &ClientRequest) -> Option<Self> {
+        match request {
+            ClientRequest::Initialize(_) | ClientRequest::Ping => None,
+            ClientRequest::ListTools(_) | ClientRequest::CallTool(_) => Some(Self::Tools),
+            ClientRequest::ListResources(_)
+            | ClientRequest::ReadResource(_)
+            | ClientRequest::ListResourceTemplates(_)
+            | ClientRequest::Subscribe(_)
+            | ClientRequest::Unsubscribe(_) => Some(Self::Resources),
+            ClientRequest::ListPrompts(_) | ClientRequest::GetPrompt(_) => Some(Self::Prompts),
+            _ => Some(Self::Other),
+        }
+    }
+}
+
+/// Token bucket settings: up to `capacity` requests in a burst, refilled at
+/// `refill_per_second`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBucket {
+    /// Maximum burst size.
+    pub capacity: u32,
+    /// Tokens added per second.
+    pub refill_per_second: f64,
+}
+
+impl TokenBucket {
+    /// Create bucket settings.
+    pub fn new(capacity: u32, refill_per_second: f64) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            refill_per_second: refill_per_second.max(0.0),
+        }
+    }
+
+    /// Time until a token is available in a bucket holding `tokens`.
+    fn wait_for_token(&self, tokens: f64) -> Duration {
+        if self.refill_per_second == 0.0 {
+            return Duration::MAX;
+        }
+        // A tiny rate puts the wait past what a `Duration` holds
+        Duration::try_from_secs_f64((1.0 - tokens) / self.refill_per_second)
+            .unwrap_or(Duration::MAX)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    /// Position in [`Buckets::recency`]
+    last_used: u64,
+}
+
+type BucketKey = (String, MethodGroup);
+
+/// Buckets with the order they were last used in, to drop the least
+/// recently used first.
+#[derive(Debug, Default)]
+struct Buckets {
+    buckets: HashMap<BucketKey, Bucket>,
+    recency: BTreeMap<u64, BucketKey>,
+    next_use: u64,
+}
+
+/// Rate limiter keeping a token bucket per principal and method group.
+///
+/// Groups without a configured bucket are not limited.
+#[derive(Debug)]
+pub struct PrincipalRateLimiter {
+    limits: HashMap<MethodGroup, TokenBucket>,
+    max_principals: usize,
+    buckets: Mutex<Buckets>,
+}
+
+impl Default for PrincipalRateLimiter {
+    fn default() -> Self {
+        Self {
+            limits: HashMap::new(),
+            max_principals: DEFAULT_MAX_PRINCIPALS,
+            buckets: Mutex::new(Buckets::default()),
+        }
+    }
+}
+
+impl PrincipalRateLimiter {
+    /// Create a limiter with no limits configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit requests in `group`.
+    pub fn limit(mut self, group: MethodGroup, bucket: TokenBucket) -> Self {
+        self.limits.insert(group, bucket);
+        self
+    }
+
+    /// Limit tool listing and calls.
+    pub fn tools(self, bucket: TokenBucket) -> Self {
+        self.limit(MethodGroup::Tools, bucket)
+    }
+
+    /// Limit resource requests.
+    pub fn resources(self, bucket: TokenBucket) -> Self {
+        self.limit(MethodGroup::Resources, bucket)
+    }
+
+    /// Limit prompt requests.
+    pub fn prompts(self, bucket: TokenBucket) -> Self {
+        self.limit(MethodGroup::Prompts, bucket)
+    }
+
+    /// Set how many buckets are kept before the least recently used are
+    /// forgotten.
+    pub fn with_max_principals(mut self, max: usize) -> Self {
+        self.max_principals = max.max(1);
+        self
+    }
+
+    /// Take a token for `principal` from the bucket for `group`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RATE_LIMITED` with `retryAfterMs` in its data if the bucket
+    /// is empty.
+    pub fn check(&self, principal: &str, group: MethodGroup) -> Result<()> {
+        let Some(limit) = self.limits.get(&group) else {
+            return Ok(());
+        };
+        let now = Instant::now();
+        let mut guard = self.buckets.lock();
+        let state = &mut *guard;
+        let key = (principal.to_string(), group);
+        let used = state.next_use;
+        state.next_use += 1;
+
+        match state.buckets.get(&key) {
+            Some(bucket) => {
+                state.recency.remove(&bucket.last_used);
+            },
+            None => {
+                while state.buckets.len() >= self.max_principals {
+                    let Some((_, oldest)) = state.recency.pop_first() else {
+                        break;
+                    };
+                    state.buckets.remove(&oldest);
+                }
+            },
+        }
+        state.recency.insert(used, key.clone());
+        let bucket = state.buckets.entry(key).or_insert(Bucket {
+            tokens: f64::from(limit.capacity),
+            updated: now,
+            last_used: used,
+        });
+        bucket.last_used = used;
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens =
+            (bucket.tokens + elapsed * limit.refill_per_second).min(f64::from(limit.capacity));
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+
+        let retry_after = limit.wait_for_token(bucket.tokens);
+        tracing::warn!("Rate limit exceeded for '{}' ({:?})", principal, group);
+        Err(Error::Protocol {
+            code: ErrorCode::RATE_LIMITED,
+            message: format!("Rate limit exceeded for {:?} requests", group),
+            data: Some(serde_json::json!({
+                "retryAfterMs": u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX),
+            })),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buckets_are_per_principal_and_group() {
+        let limiter = PrincipalRateLimiter::new().tools(TokenBucket::new(2, 0.0));
+
+        assert!(limiter.check("alice", MethodGroup::Tools).is_ok());
+        assert!(limiter.check("alice", MethodGroup::Tools).is_ok());
+        let err = limiter.check("alice", MethodGroup::Tools).unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::RATE_LIMITED));
+
+        assert!(limiter.check("bob", MethodGroup::Tools).is_ok());
+        for _ in 0..10 {
+            assert!(limiter.check("alice", MethodGroup::Resources).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_rejection_reports_retry_after() {
+        let limiter = PrincipalRateLimiter::new().resources(TokenBucket::new(1, 2.0));
+
+        limiter.check("alice", MethodGroup::Resources).unwrap();
+        let Err(Error::Protocol {
+            data: Some(data), ..
+        }) = limiter.check("alice", MethodGroup::Resources)
+        else {
+            panic!("expected a rate limit error with data");
+        };
+        let retry_after = data["retryAfterMs"].as_u64().unwrap();
+        assert!(retry_after > 0 && retry_after <= 500);
+    }
+
+    #[test]
+    fn test_least_recently_used_buckets_are_evicted() {
+        let limiter = PrincipalRateLimiter::new()
+            .tools(TokenBucket::new(1, 0.0))
+            .with_max_principals(2);
+
+        limiter.check("a", MethodGroup::Tools).unwrap();
+        limiter.check("b", MethodGroup::Tools).unwrap();
+        assert!(limiter.check("a", MethodGroup::Tools).is_err());
+        limiter.check("c", MethodGroup::Tools).unwrap();
+        assert_eq!(limiter.buckets.lock().buckets.len(), 2);
+
+        // `b` was forgotten, `a` still waits
+        assert!(limiter.check("a", MethodGroup::Tools).is_err());
+        limiter.check("b", MethodGroup::Tools).unwrap();
+        assert_eq!(limiter.buckets.lock().recency.len(), 2);
+    }
+
+    #[test]
+    fn test_tiny_refill_rate_waits_the_longest() {
+        let limiter = PrincipalRateLimiter::new().tools(TokenBucket::new(1, 1e-300));
+
+        limiter.check("alice", MethodGroup::Tools).unwrap();
+        let Err(Error::Protocol {
+            data: Some(data), ..
+        }) = limiter.check("alice", MethodGroup::Tools)
+        else {
+            panic!("expected a rate limit error with data");
+        };
+        assert_eq!(data["retryAfterMs"], u64::MAX);
+    }
+}
@@ -0,0 +1,349 @@
+//! Server-side caching and conditional reads for resources.
+//!
+//! [`CachedResourceHandler`] wraps any [`ResourceHandler`] (typically a
+//! [`DynamicResourceHandler`](super::simple_resources::DynamicResourceHandler))
+//! and remembers the result of each `resources/read`. Every cached result is
+//! tagged with an entity tag derived from its contents and the time the
+//! contents last changed, exposed to clients through the result's `_meta`.
+//!
+//! Clients that already hold a copy send the entity tag back as
+//! `ifNoneMatch`; the server then answers with an empty, `notModified`
+//! result instead of the full contents (see [`apply_conditional`]).
+//!
+//! Cached entries live until they expire (if a TTL is configured) or are
+//! invalidated through a [`ResourceCache`] handle, for example from a file
+//! watcher or after a write.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::resource_cache::CachedResourceHandler;
+//! use pmcp::{ResourceCollection, StaticResource};
+//! use std::time::Duration;
+//!
+//! let handler = CachedResourceHandler::new(
+//!     ResourceCollection::new().add_resource(StaticResource::new_text("docs://a", "A")),
+//! )
+//! .with_ttl(Duration::from_secs(60));
+//!
+//! // Keep a handle to invalidate entries when the underlying data changes.
+//! let cache = handler.cache();
+//! cache.invalidate("docs://a");
+//! ```
+
+use crate::types::{ListResourcesResult, ReadResourceResult};
+use crate::Result;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::cancellation::RequestHandlerExtra;
+use super::ResourceHandler;
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    result: ReadResourceResult,
+    etag: String,
+    last_modified: String,
+    stored_at: Instant,
+}
+
+/// Shared handle to the entries of a [`CachedResourceHandler`].
+///
+/// Cloning the handle is cheap; all clones refer to the same cache.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceCache {
+    entries: Arc<RwLock<HashMap<String, CacheEntry>>>,
+}
+
+impl ResourceCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop the cached entry for a URI so the next read hits the handler.
+    pub fn invalidate(&self, uri: &str) {
+        self.entries.write().remove(uri);
+    }
+
+    /// Drop all cached entries whose URI starts with `prefix`.
+    pub fn invalidate_prefix(&self, prefix: &str) {
+        self.entries
+            .write()
+            .retain(|uri, _| !uri.starts_with(prefix));
+    }
+
+    /// Drop every cached entry.
+    pub fn invalidate_all(&self) {
+        self.entries.write().clear();
+    }
+
+    /// Get the entity tag currently cached for a URI.
+    pub fn etag(&self, uri: &str) -> Option<String> {
+        self.entries.read().get(uri).map(|entry| entry.etag.clone())
+    }
+
+    /// Get the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    /// Check if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().is_empty()
+    }
+}
+
+/// Resource handler wrapper that caches reads and tags them for conditional requests.
+#[derive(Debug)]
+pub struct CachedResourceHandler<H> {
+    inner: H,
+    cache: ResourceCache,
+    ttl: Option<Duration>,
+}
+
+impl<H: ResourceHandler> CachedResourceHandler<H> {
+    /// Wrap a resource handler with a cache.
+    ///
+    /// Without a TTL, entries are kept until explicitly invalidated.
+    pub fn new(inner: H) -> Self {
+        Self {
+            inner,
+            cache: ResourceCache::new(),
+            ttl: None,
+        }
+    }
+
+    /// Expire cached entries after the given duration.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Use an existing cache handle, e.g. one shared with an invalidation hook.
+    pub fn with_cache(mut self, cache: ResourceCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Get a handle for invalidating cached entries.
+    pub fn cache(&self) -> ResourceCache {
+        self.cache.clone()
+    }
+
+    fn is_fresh(&self, entry: &CacheEntry) -> bool {
+        self.ttl.is_none_or(|ttl| entry.stored_at.elapsed() < ttl)
+    }
+}
+
+#[async_trait]
+impl<H: ResourceHandler> ResourceHandler for CachedResourceHandler<H> {
+    async fn read(&self, uri: &str, extra: RequestHandlerExtra) -> Result<ReadResourceResult> {
+        if let Some(entry) = self.cache.entries.read().get(uri) {
+            if self.is_fresh(entry) {
+                return Ok(with_validators(
+                    entry.result.clone(),
+                    &entry.etag,
+                    &entry.last_modified,
+                ));
+            }
+        }
+
+        let result = self.inner.read(uri, extra).await?;
+        let etag = compute_etag(&result)?;
+
+        let mut entries = self.cache.entries.write();
+        // Keep the original modification time when the contents did not change.
+        let last_modified = match entries.get(uri) {
+            Some(previous) if previous.etag == etag => previous.last_modified.clone(),
+            _ => chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        };
+        entries.insert(
+            uri.to_string(),
+            CacheEntry {
+                result: result.clone(),
+                etag: etag.clone(),
+                last_modified: last_modified.clone(),
+                stored_at: Instant::now(),
+            },
+        );
+
+        Ok(with_validators(result, &etag, &last_modified))
+    }
+
+    async fn open_stream(
+        &self,
+        uri: &str,
+        extra: RequestHandlerExtra,
+    ) -> Result<Option<crate::server::resource_chunks::ResourceStream>> {
+        self.inner.open_stream(uri, extra).await
+    }
+
+    async fn list(
+        &self,
+        cursor: Option<String>,
+        extra: RequestHandlerExtra,
+    ) -> Result<ListResourcesResult> {
+        self.inner.list(cursor, extra).await
+    }
+}
+
+/// Compute a strong entity tag from the contents of a read result.
+pub fn compute_etag(result: &ReadResourceResult) -> Result<String> {
+    let bytes = serde_json::to_vec(&result.contents)?;
+    let digest = Sha256::digest(&bytes);
+    let hex: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!("\"{}\"", hex))
+}
+
+fn with_validators(
+    mut result: ReadResourceResult,
+    etag: &str,
+    last_modified: &str,
+) -> ReadResourceResult {
+    let meta = result
+        .meta
+        .get_or_insert_with(|| Value::Object(Default::default()));
+    if let Value::Object(map) = meta {
+        map.insert("etag".to_string(), Value::String(etag.to_string()));
+        map.insert(
+            "lastModified".to_string(),
+            Value::String(last_modified.to_string()),
+        );
+    }
+    result
+}
+
+/// Answer a conditional read.
+///
+/// If the client's `ifNoneMatch` matches the entity tag of `result`, the
+/// contents are replaced by an empty `notModified` result carrying the same
+/// validators. Otherwise `result` is returned unchanged.
+pub fn apply_conditional(
+    if_none_match: Option<&str>,
+    result: ReadResourceResult,
+) -> ReadResourceResult {
+    match (if_none_match, result.etag()) {
+        (Some(expected), Some(etag)) if expected == etag => {
+            let mut meta = json!({ "etag": etag, "notModified": true });
+            if let Some(last_modified) = result.last_modified() {
+                meta["lastModified"] = Value::String(last_modified.to_string());
+            }
+            ReadResourceResult {
+                contents: vec![],
+                meta: Some(meta),
+            }
+        },
+        _ => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Content;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio_util::sync::CancellationToken;
+
+    struct CountingHandler {
+        reads: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ResourceHandler for CountingHandler {
+        async fn read(&self, uri: &str, _extra: RequestHandlerExtra) -> Result<ReadResourceResult> {
+            let n = self.reads.fetch_add(1, Ordering::SeqCst);
+            Ok(ReadResourceResult {
+                contents: vec![Content::Text {
+                    text: format!("{} v{}", uri, n / 2),
+                }],
+                meta: None,
+            })
+        }
+
+        async fn list(
+            &self,
+            _cursor: Option<String>,
+            _extra: RequestHandlerExtra,
+        ) -> Result<ListResourcesResult> {
+            Ok(ListResourcesResult {
+                resources: vec![],
+                next_cursor: None,
+            })
+        }
+    }
+
+    fn extra() -> RequestHandlerExtra {
+        RequestHandlerExtra::new("test".to_string(), CancellationToken::new())
+    }
+
+    #[tokio::test]
+    async fn test_caches_until_invalidated() {
+        let handler = CachedResourceHandler::new(CountingHandler {
+            reads: AtomicUsize::new(0),
+        });
+        let cache = handler.cache();
+
+        let first = handler.read("r://a", extra()).await.unwrap();
+        let second = handler.read("r://a", extra()).await.unwrap();
+        assert_eq!(handler.inner.reads.load(Ordering::SeqCst), 1);
+        assert!(first.etag().is_some());
+        assert_eq!(first.etag(), second.etag());
+        assert_eq!(cache.etag("r://a").as_deref(), first.etag());
+
+        // Same contents after invalidation keep the same validators.
+        cache.invalidate("r://a");
+        let third = handler.read("r://a", extra()).await.unwrap();
+        assert_eq!(handler.inner.reads.load(Ordering::SeqCst), 2);
+        assert_eq!(third.etag(), first.etag());
+        assert_eq!(third.last_modified(), first.last_modified());
+
+        // Changed contents produce a new entity tag.
+        cache.invalidate_all();
+        let fourth = handler.read("r://a", extra()).await.unwrap();
+        assert_ne!(fourth.etag(), first.etag());
+    }
+
+    #[tokio::test]
+    async fn test_ttl_expiry() {
+        let handler = CachedResourceHandler::new(CountingHandler {
+            reads: AtomicUsize::new(0),
+        })
+        .with_ttl(Duration::from_millis(10));
+
+        handler.read("r://a", extra()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        handler.read("r://a", extra()).await.unwrap();
+        assert_eq!(handler.inner.reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_apply_conditional() {
+        let result = with_validators(
+            ReadResourceResult {
+                contents: vec![Content::Text {
+                    text: "x".to_string(),
+                }],
+                meta: None,
+            },
+            "\"abc\"",
+            "2024-01-01T00:00:00Z",
+        );
+
+        let unchanged = apply_conditional(Some("\"abc\""), result.clone());
+        assert!(unchanged.is_not_modified());
+        assert!(unchanged.contents.is_empty());
+        assert_eq!(unchanged.last_modified(), Some("2024-01-01T00:00:00Z"));
+
+        let changed = apply_conditional(Some("\"old\""), result.clone());
+        assert!(!changed.is_not_modified());
+        assert_eq!(changed.contents.len(), 1);
+
+        assert_eq!(apply_conditional(None, result).contents.len(), 1);
+    }
+}
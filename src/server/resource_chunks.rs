@@ -0,0 +1,584 @@
+//! Chunked resource reads.
+//!
+//! Clients that stream large resources request them piece by piece by
+//! attaching a [`ResourceChunk`] to `resources/read`. The server reads the
+//! resource from its handler as usual and returns only the requested range,
+//! tagging the result with the total length so the client knows when to stop.
+//! Chunks are ordinary requests, so this works over every transport,
+//! including streamable HTTP and WebSocket.
+//!
+//! Text contents are chunked by UTF-8 bytes (never splitting a character);
+//! binary contents are chunked by base64 characters in multiples of four so
+//! every chunk decodes on its own.
+//!
+//! Handlers serving resources too large to hold in memory implement
+//! [`ResourceHandler::open_stream`] and return a [`ResourceStream`]; each
+//! chunk is then read from the stream's byte range alone.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use async_trait::async_trait;
+//! use pmcp::server::cancellation::RequestHandlerExtra;
+//! use pmcp::server::resource_chunks::ResourceStream;
+//! use pmcp::types::{ListResourcesResult, ReadResourceResult};
+//! use pmcp::{Error, ResourceHandler};
+//!
+//! struct Artifacts;
+//!
+//! #[async_trait]
+//! impl ResourceHandler for Artifacts {
+//!     async fn read(&self, uri: &str, _extra: RequestHandlerExtra) -> pmcp::Result<ReadResourceResult> {
+//!         Err(Error::not_found(uri))
+//!     }
+//!
+//!     async fn open_stream(
+//!         &self,
+//!         uri: &str,
+//!         _extra: RequestHandlerExtra,
+//!     ) -> pmcp::Result<Option<ResourceStream>> {
+//!         let Some(name) = uri.strip_prefix("artifact://") else {
+//!             return Ok(None);
+//!         };
+//!         let path = std::path::Path::new("/var/artifacts").join(name);
+//!         Ok(Some(ResourceStream::open_file(path, "application/zip").await?))
+//!     }
+//!
+//!     async fn list(
+//!         &self,
+//!         _cursor: Option<String>,
+//!         _extra: RequestHandlerExtra,
+//!     ) -> pmcp::Result<ListResourcesResult> {
+//!         Ok(ListResourcesResult {
+//!             resources: vec![],
+//!             next_cursor: None,
+//!         })
+//!     }
+//! }
+//! ```
+//!
+//! [`ResourceHandler::open_stream`]: crate::server::ResourceHandler::open_stream
+
+use crate::error::{Error, Result};
+use crate::server::cancellation::RequestHandlerExtra;
+use crate::server::ResourceHandler;
+use crate::types::{Content, ReadResourceRequest, ReadResourceResult, ResourceChunk};
+use base64::Engine;
+use serde_json::Value;
+use std::fmt;
+use std::io::SeekFrom;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+/// MIME type of binary streams that don't name one.
+const DEFAULT_BINARY_MIME_TYPE: &str = "application/octet-stream";
+
+/// Byte source of a [`ResourceStream`].
+trait ReadSeek: AsyncRead + AsyncSeek + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncSeek + Send + Unpin> ReadSeek for T {}
+
+/// Resource contents read on demand from a seekable byte source.
+///
+/// Only the bytes of the requested chunk are read, so resources of any size
+/// can be served; see the [module documentation](self).
+pub struct ResourceStream {
+    reader: Box<dyn ReadSeek>,
+    len: u64,
+    binary: bool,
+    mime_type: Option<String>,
+}
+
+impl fmt::Debug for ResourceStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceStream")
+            .field("len", &self.len)
+            .field("binary", &self.binary)
+            .field("mime_type", &self.mime_type)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ResourceStream {
+    /// Stream `len` bytes of binary contents from `reader`, sent base64
+    /// encoded.
+    pub fn binary(
+        reader: impl AsyncRead + AsyncSeek + Send + Unpin + 'static,
+        len: u64,
+        mime_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            reader: Box::new(reader),
+            len,
+            binary: true,
+            mime_type: Some(mime_type.into()),
+        }
+    }
+
+    /// Stream `len` bytes of UTF-8 text from `reader`.
+    pub fn text(reader: impl AsyncRead + AsyncSeek + Send + Unpin + 'static, len: u64) -> Self {
+        Self {
+            reader: Box::new(reader),
+            len,
+            binary: false,
+            mime_type: None,
+        }
+    }
+
+    /// Stream a file as binary contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    pub async fn open_file(
+        path: impl AsRef<std::path::Path>,
+        mime_type: impl Into<String>,
+    ) -> Result<Self> {
+        let file = tokio::fs::File::open(path).await?;
+        let len = file.metadata().await?.len();
+        Ok(Self::binary(file, len, mime_type))
+    }
+
+    /// Set the MIME type reported with the contents.
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+
+    /// Length of the contents in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the contents are empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Length of the transmitted representation, which chunks count in.
+    fn transmitted_len(&self) -> u64 {
+        if self.binary {
+            self.len.div_ceil(3) * 4
+        } else {
+            self.len
+        }
+    }
+
+    /// Read `len` bytes starting at `start`, stopping early at the end.
+    async fn read_range(&mut self, start: u64, len: u64) -> Result<Vec<u8>> {
+        let start = start.min(self.len);
+        let len = len.min(self.len - start);
+        self.reader.seek(SeekFrom::Start(start)).await?;
+        let mut buf = Vec::with_capacity(usize::try_from(len).unwrap_or(0));
+        (&mut self.reader).take(len).read_to_end(&mut buf).await?;
+        Ok(buf)
+    }
+
+    /// Read the requested chunk of the contents.
+    async fn read_chunk(mut self, uri: &str, chunk: ResourceChunk) -> Result<ReadResourceResult> {
+        let total = self.transmitted_len();
+        let (content, offset) = if self.binary {
+            // Whole base64 groups map to whole 3-byte groups.
+            let offset = chunk.offset / 4 * 4;
+            let length = (chunk.length / 4 * 4).max(4);
+            let bytes = self.read_range(offset / 4 * 3, length / 4 * 3).await?;
+            (self.binary_content(&bytes), offset)
+        } else {
+            // Read one byte past the chunk, and enough for one character, to
+            // find character boundaries.
+            let start = chunk.offset.min(self.len).saturating_sub(3);
+            let window = self
+                .read_range(
+                    start,
+                    (chunk.offset - start).saturating_add(chunk.length.max(4) + 1),
+                )
+                .await?;
+            let at_end = start + window.len() as u64 == self.len;
+            let (from, to) = text_bounds(
+                &window,
+                usize::try_from(chunk.offset - start).unwrap_or(usize::MAX),
+                usize::try_from(chunk.length).unwrap_or(usize::MAX),
+                at_end,
+            );
+            let text = String::from_utf8(window[from..to].to_vec())
+                .map_err(|e| Error::internal(format!("Resource is not valid UTF-8: {}", e)))?;
+            (self.text_content(uri, text), start + from as u64)
+        };
+
+        let length = match &content {
+            Content::Image { data, .. } | Content::Audio { data, .. } => data.len(),
+            Content::Resource {
+                text: Some(text), ..
+            } => text.len(),
+            Content::Text { .. }
+            | Content::Resource { .. }
+            | Content::ResourceLink(_)
+            | Content::EmbeddedResource { .. } => 0,
+        };
+        let returned = ResourceChunk {
+            offset,
+            length: length as u64,
+            total: Some(total),
+        };
+        Ok(ReadResourceResult {
+            contents: vec![content],
+            meta: Some(serde_json::json!({ "chunk": returned })),
+        })
+    }
+
+    /// Read all of the contents.
+    async fn read_all(mut self, uri: &str) -> Result<ReadResourceResult> {
+        let bytes = self.read_range(0, self.len).await?;
+        let content = if self.binary {
+            self.binary_content(&bytes)
+        } else {
+            let text = String::from_utf8(bytes)
+                .map_err(|e| Error::internal(format!("Resource is not valid UTF-8: {}", e)))?;
+            self.text_content(uri, text)
+        };
+        Ok(ReadResourceResult {
+            contents: vec![content],
+            meta: None,
+        })
+    }
+
+    fn binary_content(&self, bytes: &[u8]) -> Content {
+        Content::Image {
+            data: base64::prelude::BASE64_STANDARD.encode(bytes),
+            mime_type: self
+                .mime_type
+                .clone()
+                .unwrap_or_else(|| DEFAULT_BINARY_MIME_TYPE.to_string()),
+        }
+    }
+
+    fn text_content(&self, uri: &str, text: String) -> Content {
+        Content::Resource {
+            uri: uri.to_string(),
+            text: Some(text),
+            mime_type: self.mime_type.clone(),
+        }
+    }
+}
+
+/// Byte range of `window` holding whole characters, starting at or before
+/// `offset` and at most `length` long (but at least one character).
+///
+/// `window` extends one byte past the range unless `at_end`.
+fn text_bounds(window: &[u8], offset: usize, length: usize, at_end: bool) -> (usize, usize) {
+    let is_boundary = |index: usize| {
+        index == 0
+            || (index == window.len() && at_end)
+            || window.get(index).is_some_and(|b| (*b as i8) >= -0x40)
+    };
+    let mut start = offset.min(window.len());
+    while !is_boundary(start) {
+        start -= 1;
+    }
+    let limit = if at_end {
+        window.len()
+    } else {
+        window.len().saturating_sub(1)
+    };
+    let mut end = start.saturating_add(length).min(limit);
+    while end > start && !is_boundary(end) {
+        end -= 1;
+    }
+    if end == start && start < limit {
+        // Always make progress, even if the chunk is smaller than one character.
+        end = start + 1;
+        while end < limit && !is_boundary(end) {
+            end += 1;
+        }
+    }
+    (start, end)
+}
+
+/// Read a resource from `handler`, serving conditional and chunked reads.
+///
+/// Streams opened with [`ResourceHandler::open_stream`] are read directly;
+/// other resources are read whole and then cut to the requested chunk.
+pub(crate) async fn read_resource(
+    handler: &dyn ResourceHandler,
+    req: &ReadResourceRequest,
+    extra: RequestHandlerExtra,
+) -> Result<ReadResourceResult> {
+    if let Some(stream) = handler.open_stream(&req.uri, extra.clone()).await? {
+        return match req.chunk() {
+            Some(chunk) => stream.read_chunk(&req.uri, chunk).await,
+            None => stream.read_all(&req.uri).await,
+        };
+    }
+    let result = handler.read(&req.uri, extra).await?;
+    let result = super::resource_cache::apply_conditional(req.if_none_match(), result);
+    apply_chunk(req.chunk(), result)
+}
+
+/// Restrict a read result to the requested chunk.
+///
+/// Returns `result` unchanged when no chunk was requested. Chunked reads
+/// require the resource to have exactly one content item.
+pub fn apply_chunk(
+    chunk: Option<ResourceChunk>,
+    mut result: ReadResourceResult,
+) -> Result<ReadResourceResult> {
+    let Some(chunk) = chunk else {
+        return Ok(result);
+    };
+    if result.contents.len() != 1 {
+        return Err(Error::invalid_params(format!(
+            "Chunked reads require a single content item, resource has {}",
+            result.contents.len()
+        )));
+    }
+
+    let (content, total) = match result.contents.remove(0) {
+        Content::Text { text } => {
+            let total = text.len();
+            let text = slice_text(&text, chunk.offset, chunk.length);
+            (Content::Text { text }, total)
+        },
+        Content::Resource {
+            uri,
+            text: Some(text),
+            mime_type,
+        } => {
+            let total = text.len();
+            let text = slice_text(&text, chunk.offset, chunk.length);
+            (
+                Content::Resource {
+                    uri,
+                    text: Some(text),
+                    mime_type,
+                },
+                total,
+            )
+        },
+        Content::Image { data, mime_type } => {
+            let total = data.len();
+            let data = slice_base64(&data, chunk.offset, chunk.length);
+            (Content::Image { data, mime_type }, total)
+        },
+        Content::Audio { data, mime_type } => {
+            let total = data.len();
+            let data = slice_base64(&data, chunk.offset, chunk.length);
+            (Content::Audio { data, mime_type }, total)
+        },
+        other => (other, 0),
+    };
+
+    let length = match &content {
+        Content::Text { text }
+        | Content::Resource {
+            text: Some(text), ..
+        } => text.len(),
+        Content::Image { data, .. } | Content::Audio { data, .. } => data.len(),
+        Content::Resource { .. } | Content::ResourceLink(_) | Content::EmbeddedResource { .. } => 0,
+    };
+    let returned = ResourceChunk {
+        offset: chunk.offset,
+        length: length as u64,
+        total: Some(total as u64),
+    };
+
+    let meta = result
+        .meta
+        .get_or_insert_with(|| Value::Object(Default::default()));
+    if let Value::Object(map) = meta {
+        map.insert("chunk".to_string(), serde_json::to_value(returned)?);
+    }
+    result.contents.push(content);
+    Ok(result)
+}
+
+/// Slice text by bytes, shrinking the range to character boundaries.
+fn slice_text(text: &str, offset: u64, length: u64) -> String {
+    let start = floor_char_boundary(text, usize::try_from(offset).unwrap_or(usize::MAX));
+    let end = start.saturating_add(usize::try_from(length).unwrap_or(usize::MAX));
+    let mut end = floor_char_boundary(text, end);
+    if end == start && start < text.len() {
+        // Always make progress, even if the chunk is smaller than one character.
+        end = text[start..]
+            .chars()
+            .next()
+            .map_or(text.len(), |c| start + c.len_utf8());
+    }
+    text[start..end].to_string()
+}
+
+/// Slice base64 data, aligning the range to whole 4-character groups.
+fn slice_base64(data: &str, offset: u64, length: u64) -> String {
+    let start = usize::try_from(offset).unwrap_or(usize::MAX) / 4 * 4;
+    let length = (usize::try_from(length).unwrap_or(usize::MAX) / 4 * 4).max(4);
+    let start = start.min(data.len());
+    let end = start.saturating_add(length).min(data.len());
+    data[start..end].to_string()
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    if index >= text.len() {
+        return text.len();
+    }
+    let mut index = index;
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_result(text: &str) -> ReadResourceResult {
+        ReadResourceResult {
+            contents: vec![Content::Text {
+                text: text.to_string(),
+            }],
+            meta: None,
+        }
+    }
+
+    #[test]
+    fn test_text_chunks_respect_char_boundaries() {
+        let chunk = ResourceChunk {
+            offset: 0,
+            length: 2,
+            total: None,
+        };
+        let result = apply_chunk(Some(chunk), text_result("aé😀")).unwrap();
+        let returned = result.chunk().unwrap();
+        assert_eq!(returned.total, Some(7));
+        match &result.contents[0] {
+            Content::Text { text } => assert_eq!(text, "a"),
+            other => panic!("unexpected content: {:?}", other),
+        }
+
+        let chunk = ResourceChunk {
+            offset: 3,
+            length: 1,
+            total: None,
+        };
+        let result = apply_chunk(Some(chunk), text_result("aé😀")).unwrap();
+        match &result.contents[0] {
+            Content::Text { text } => assert_eq!(text, "😀"),
+            other => panic!("unexpected content: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_binary_chunks_align_to_base64_groups() {
+        let result = ReadResourceResult {
+            contents: vec![Content::Image {
+                data: "AAAABBBBCC==".to_string(),
+                mime_type: "image/png".to_string(),
+            }],
+            meta: None,
+        };
+        let chunk = ResourceChunk {
+            offset: 4,
+            length: 6,
+            total: None,
+        };
+        let result = apply_chunk(Some(chunk), result).unwrap();
+        match &result.contents[0] {
+            Content::Image { data, .. } => assert_eq!(data, "BBBB"),
+            other => panic!("unexpected content: {:?}", other),
+        }
+        assert_eq!(result.chunk().unwrap().length, 4);
+    }
+
+    #[test]
+    fn test_no_chunk_passthrough_and_multi_content_error() {
+        let result = apply_chunk(None, text_result("hello")).unwrap();
+        assert!(result.meta.is_none());
+
+        let multi = ReadResourceResult {
+            contents: vec![
+                Content::Text {
+                    text: "a".to_string(),
+                },
+                Content::Text {
+                    text: "b".to_string(),
+                },
+            ],
+            meta: None,
+        };
+        let chunk = ResourceChunk {
+            offset: 0,
+            length: 1,
+            total: None,
+        };
+        assert!(apply_chunk(Some(chunk), multi).is_err());
+    }
+
+    fn chunk(offset: u64, length: u64) -> ResourceChunk {
+        ResourceChunk {
+            offset,
+            length,
+            total: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_text_stream_chunks_respect_char_boundaries() {
+        let text = "aé😀b";
+        let stream = || ResourceStream::text(std::io::Cursor::new(text.as_bytes().to_vec()), 8);
+
+        let mut collected = String::new();
+        let mut offset = 0;
+        while offset < 8 {
+            let result = stream()
+                .read_chunk("mem://t", chunk(offset, 2))
+                .await
+                .unwrap();
+            let returned = result.chunk().unwrap();
+            assert_eq!(returned.total, Some(8));
+            match &result.contents[0] {
+                Content::Resource {
+                    text: Some(text), ..
+                } => collected.push_str(text),
+                other => panic!("unexpected content: {:?}", other),
+            }
+            offset = returned.offset + returned.length;
+        }
+        assert_eq!(collected, text);
+
+        // A chunk starting inside a character starts at the character.
+        let result = stream().read_chunk("mem://t", chunk(5, 1)).await.unwrap();
+        assert_eq!(result.chunk().unwrap().offset, 3);
+    }
+
+    #[tokio::test]
+    async fn test_binary_stream_chunks_match_whole_encoding() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let encoded = base64::prelude::BASE64_STANDARD.encode(&bytes);
+        let stream = || ResourceStream::binary(std::io::Cursor::new(bytes.clone()), 256, "x/y");
+
+        let mut collected = String::new();
+        let mut offset = 0;
+        loop {
+            let result = stream()
+                .read_chunk("mem://b", chunk(offset, 30))
+                .await
+                .unwrap();
+            let returned = result.chunk().unwrap();
+            assert_eq!(returned.total, Some(encoded.len() as u64));
+            match &result.contents[0] {
+                Content::Image { data, mime_type } => {
+                    assert_eq!(mime_type, "x/y");
+                    collected.push_str(data);
+                },
+                other => panic!("unexpected content: {:?}", other),
+            }
+            offset = returned.offset + returned.length;
+            if returned.length == 0 || offset >= encoded.len() as u64 {
+                break;
+            }
+        }
+        assert_eq!(collected, encoded);
+
+        let whole = stream().read_all("mem://b").await.unwrap();
+        assert!(whole.chunk().is_none());
+        assert!(matches!(&whole.contents[0], Content::Image { data, .. } if *data == encoded));
+    }
+}
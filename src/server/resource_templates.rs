@@ -0,0 +1,512 @@
+//! Registry of parameterized resource templates.
+//!
+//! Resource templates advertise families of resources whose URIs follow an
+//! RFC 6570 pattern such as `file:///logs/{date}.log`. The registry backs the
+//! `resources/templates/list` request, matches concrete URIs back to the
+//! template that produced them, and answers `completion/complete` requests
+//! that reference a template (`ref/resource`).
+//!
+//! [`ResourceTemplateRouter`] additionally routes `resources/read` requests to
+//! a handler per template, passing the variables extracted from the URI.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::server::resource_templates::ResourceTemplateRegistry;
+//! use pmcp::types::ResourceTemplate;
+//!
+//! let mut registry = ResourceTemplateRegistry::new();
+//! registry
+//!     .register(ResourceTemplate {
+//!         uri_template: "db://{table}/{id}".to_string(),
+//!         name: "Database row".to_string(),
+//!         description: None,
+//!         mime_type: Some("application/json".to_string()),
+//!     })
+//!     .unwrap();
+//! registry
+//!     .set_completions("db://{table}/{id}", "table", vec!["users".into(), "orders".into()])
+//!     .unwrap();
+//!
+//! let (template, vars) = registry.match_uri("db://users/42").unwrap();
+//! assert_eq!(template.name, "Database row");
+//! assert_eq!(vars["table"], "users");
+//! ```
+
+use crate::error::{Error, Result};
+use crate::shared::uri_template::UriTemplate;
+use crate::types::{
+    CompletionArgument, CompletionResult, ListResourcesResult, ReadResourceResult, ResourceTemplate,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use super::cancellation::RequestHandlerExtra;
+use super::ResourceHandler;
+
+/// Maximum number of completion values returned in a single response.
+const MAX_COMPLETION_VALUES: usize = 100;
+
+#[derive(Debug, Clone)]
+struct TemplateEntry {
+    template: ResourceTemplate,
+    matcher: UriTemplate,
+    completions: HashMap<String, Vec<String>>,
+}
+
+/// Registry of resource templates exposed by a server.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceTemplateRegistry {
+    entries: Vec<TemplateEntry>,
+}
+
+impl ResourceTemplateRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a resource template.
+    ///
+    /// Returns an error if the URI template cannot be parsed or a template
+    /// with the same pattern is already registered.
+    pub fn register(&mut self, template: ResourceTemplate) -> Result<()> {
+        if self.find(&template.uri_template).is_some() {
+            return Err(Error::validation(format!(
+                "Resource template '{}' is already registered",
+                template.uri_template
+            )));
+        }
+
+        let matcher = UriTemplate::new(template.uri_template.clone())?;
+        self.entries.push(TemplateEntry {
+            template,
+            matcher,
+            completions: HashMap::new(),
+        });
+        Ok(())
+    }
+
+    /// Set the completion values offered for a template variable.
+    pub fn set_completions(
+        &mut self,
+        uri_template: &str,
+        variable: &str,
+        values: Vec<String>,
+    ) -> Result<()> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.template.uri_template == uri_template)
+            .ok_or_else(|| {
+                Error::not_found(format!("Unknown resource template '{}'", uri_template))
+            })?;
+
+        if !entry.matcher.variables().iter().any(|v| v == variable) {
+            return Err(Error::validation(format!(
+                "Resource template '{}' has no variable '{}'",
+                uri_template, variable
+            )));
+        }
+
+        entry.completions.insert(variable.to_string(), values);
+        Ok(())
+    }
+
+    /// Get all registered templates in registration order.
+    pub fn templates(&self) -> Vec<ResourceTemplate> {
+        self.entries
+            .iter()
+            .map(|entry| entry.template.clone())
+            .collect()
+    }
+
+    /// Get the number of registered templates.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Find the template matching a concrete resource URI.
+    ///
+    /// Returns the template together with the extracted variable values.
+    /// Templates are tried in registration order.
+    pub fn match_uri(&self, uri: &str) -> Option<(&ResourceTemplate, HashMap<String, String>)> {
+        self.entries.iter().find_map(|entry| {
+            entry
+                .matcher
+                .match_uri(uri)
+                .map(|vars| (&entry.template, vars))
+        })
+    }
+
+    /// Complete a template variable for a `ref/resource` completion request.
+    ///
+    /// Values registered with [`set_completions`](Self::set_completions) are
+    /// filtered by the prefix the client has typed so far.
+    pub fn complete(
+        &self,
+        uri_template: &str,
+        argument: &CompletionArgument,
+    ) -> Result<CompletionResult> {
+        let entry = self.find(uri_template).ok_or_else(|| {
+            Error::invalid_params(format!("Unknown resource template '{}'", uri_template))
+        })?;
+
+        let matches: Vec<String> = entry
+            .completions
+            .get(&argument.name)
+            .map(|values| {
+                values
+                    .iter()
+                    .filter(|value| value.starts_with(&argument.value))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let total = matches.len();
+        Ok(CompletionResult {
+            values: matches.into_iter().take(MAX_COMPLETION_VALUES).collect(),
+            total: Some(total),
+            has_more: total > MAX_COMPLETION_VALUES,
+        })
+    }
+
+    fn find(&self, uri_template: &str) -> Option<&TemplateEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.template.uri_template == uri_template)
+    }
+}
+
+/// Handler reading resources that match a URI template.
+///
+/// Implemented for closures taking the URI, the extracted template variables
+/// and the request context.
+#[async_trait]
+pub trait ResourceTemplateHandler: Send + Sync {
+    /// Read the resource at `uri`, whose template variables are `params`.
+    async fn read(
+        &self,
+        uri: &str,
+        params: HashMap<String, String>,
+        extra: RequestHandlerExtra,
+    ) -> Result<ReadResourceResult>;
+}
+
+#[async_trait]
+impl<F> ResourceTemplateHandler for F
+where
+    F: Fn(
+            &str,
+            HashMap<String, String>,
+            RequestHandlerExtra,
+        ) -> Pin<Box<dyn Future<Output = Result<ReadResourceResult>> + Send>>
+        + Send
+        + Sync,
+{
+    async fn read(
+        &self,
+        uri: &str,
+        params: HashMap<String, String>,
+        extra: RequestHandlerExtra,
+    ) -> Result<ReadResourceResult> {
+        self(uri, params, extra).await
+    }
+}
+
+struct Route {
+    template: ResourceTemplate,
+    matcher: Option<UriTemplate>,
+    handler: Arc<dyn ResourceTemplateHandler>,
+}
+
+/// Resource handler routing reads to a handler per URI template.
+///
+/// Templates are tried in registration order. URIs matching no template go
+/// to the fallback handler, if any. Register the router with
+/// [`ServerBuilder::resource_template_router`](crate::ServerBuilder::resource_template_router)
+/// to also advertise its templates in `resources/templates/list`; a handler
+/// set with [`ServerBuilder::resources`](crate::ServerBuilder::resources)
+/// becomes the fallback.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::server::resource_templates::ResourceTemplateRouter;
+/// use pmcp::types::{Content, ReadResourceResult};
+/// use pmcp::Server;
+///
+/// let router = ResourceTemplateRouter::new().route("notes://{id}", |uri, params, _extra| {
+///     let uri = uri.to_string();
+///     Box::pin(async move {
+///         Ok(ReadResourceResult {
+///             contents: vec![Content::Resource {
+///                 uri,
+///                 text: Some(format!("Note {}", params["id"])),
+///                 mime_type: Some("text/plain".to_string()),
+///             }],
+///             meta: None,
+///         })
+///     })
+/// });
+///
+/// let server = Server::builder()
+///     .name("notes")
+///     .version("1.0.0")
+///     .resource_template_router(router)
+///     .build()?;
+/// # Ok::<(), pmcp::Error>(())
+/// ```
+#[derive(Default)]
+pub struct ResourceTemplateRouter {
+    routes: Vec<Route>,
+    fallback: Option<Arc<dyn ResourceHandler>>,
+}
+
+impl fmt::Debug for ResourceTemplateRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceTemplateRouter")
+            .field("templates", &self.templates())
+            .field("fallback", &self.fallback.is_some())
+            .finish()
+    }
+}
+
+impl ResourceTemplateRouter {
+    /// Create a router with no routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route URIs matching `uri_template` to `handler`.
+    ///
+    /// The template is advertised with the pattern as its name.
+    pub fn route<F>(self, uri_template: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(
+                &str,
+                HashMap<String, String>,
+                RequestHandlerExtra,
+            ) -> Pin<Box<dyn Future<Output = Result<ReadResourceResult>> + Send>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let uri_template = uri_template.into();
+        self.route_template(
+            ResourceTemplate {
+                name: uri_template.clone(),
+                uri_template,
+                description: None,
+                mime_type: None,
+            },
+            handler,
+        )
+    }
+
+    /// Route URIs matching `template` to `handler`, advertising the
+    /// template's name, description and MIME type.
+    ///
+    /// An invalid pattern never matches; registering the router with a
+    /// server builder reports it when the server is built.
+    pub fn route_template(
+        mut self,
+        template: ResourceTemplate,
+        handler: impl ResourceTemplateHandler + 'static,
+    ) -> Self {
+        let matcher = UriTemplate::new(template.uri_template.clone()).ok();
+        self.routes.push(Route {
+            template,
+            matcher,
+            handler: Arc::new(handler),
+        });
+        self
+    }
+
+    /// Handle reads and listings for URIs that match no template.
+    pub fn fallback(mut self, handler: impl ResourceHandler + 'static) -> Self {
+        self.fallback = Some(Arc::new(handler));
+        self
+    }
+
+    pub(crate) fn set_fallback(&mut self, handler: Option<Arc<dyn ResourceHandler>>) {
+        if handler.is_some() {
+            self.fallback = handler;
+        }
+    }
+
+    /// Templates in registration order.
+    pub fn templates(&self) -> Vec<ResourceTemplate> {
+        self.routes
+            .iter()
+            .map(|route| route.template.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ResourceHandler for ResourceTemplateRouter {
+    async fn read(&self, uri: &str, extra: RequestHandlerExtra) -> Result<ReadResourceResult> {
+        let matched = self.routes.iter().find_map(|route| {
+            route
+                .matcher
+                .as_ref()
+                .and_then(|matcher| matcher.match_uri(uri))
+                .map(|params| (route, params))
+        });
+        match (matched, &self.fallback) {
+            (Some((route, params)), _) => route.handler.read(uri, params, extra).await,
+            (None, Some(fallback)) => fallback.read(uri, extra).await,
+            (None, None) => Err(Error::not_found(format!("Resource '{}' not found", uri))),
+        }
+    }
+
+    async fn list(
+        &self,
+        cursor: Option<String>,
+        extra: RequestHandlerExtra,
+    ) -> Result<ListResourcesResult> {
+        match &self.fallback {
+            Some(fallback) => fallback.list(cursor, extra).await,
+            None => Ok(ListResourcesResult {
+                resources: Vec::new(),
+                next_cursor: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(uri: &str, name: &str) -> ResourceTemplate {
+        ResourceTemplate {
+            uri_template: uri.to_string(),
+            name: name.to_string(),
+            description: None,
+            mime_type: None,
+        }
+    }
+
+    #[test]
+    fn test_register_and_match() {
+        let mut registry = ResourceTemplateRegistry::new();
+        registry
+            .register(template("file:///logs/{date}.log", "Log"))
+            .unwrap();
+        registry
+            .register(template("users://{id}/profile", "Profile"))
+            .unwrap();
+
+        assert_eq!(registry.len(), 2);
+        let (matched, vars) = registry.match_uri("users://7/profile").unwrap();
+        assert_eq!(matched.name, "Profile");
+        assert_eq!(vars["id"], "7");
+        assert!(registry.match_uri("other://x").is_none());
+
+        assert!(registry
+            .register(template("users://{id}/profile", "Again"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_completion_filters_by_prefix() {
+        let mut registry = ResourceTemplateRegistry::new();
+        registry
+            .register(template("db://{table}", "Table"))
+            .unwrap();
+        registry
+            .set_completions(
+                "db://{table}",
+                "table",
+                vec!["users".into(), "usage".into(), "orders".into()],
+            )
+            .unwrap();
+
+        let result = registry
+            .complete(
+                "db://{table}",
+                &CompletionArgument {
+                    name: "table".to_string(),
+                    value: "us".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(result.values, vec!["users", "usage"]);
+        assert_eq!(result.total, Some(2));
+        assert!(!result.has_more);
+
+        assert!(registry
+            .set_completions("db://{table}", "missing", vec![])
+            .is_err());
+        assert!(registry
+            .complete(
+                "db://{other}",
+                &CompletionArgument {
+                    name: "other".to_string(),
+                    value: String::new(),
+                },
+            )
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_router_extracts_params_and_reports_unmatched_uris() {
+        let router = ResourceTemplateRouter::new()
+            .route("users://{id}/profile", |uri, params, _extra| {
+                let uri = uri.to_string();
+                Box::pin(async move {
+                    Ok(ReadResourceResult {
+                        contents: vec![crate::types::Content::Resource {
+                            uri,
+                            text: Some(params["id"].clone()),
+                            mime_type: None,
+                        }],
+                        meta: None,
+                    })
+                })
+            })
+            .route("bad://{a b}", |_, _, _| {
+                Box::pin(async { Err(Error::internal("unreachable")) })
+            });
+        let extra = || {
+            RequestHandlerExtra::new(
+                "test".to_string(),
+                tokio_util::sync::CancellationToken::new(),
+            )
+        };
+
+        let result = router.read("users://7/profile", extra()).await.unwrap();
+        assert!(matches!(
+            &result.contents[0],
+            crate::types::Content::Resource { text: Some(id), .. } if id == "7"
+        ));
+        let err = router.read("other://x", extra()).await.unwrap_err();
+        assert!(err.to_string().contains("not found"));
+        assert!(router
+            .list(None, extra())
+            .await
+            .unwrap()
+            .resources
+            .is_empty());
+
+        assert_eq!(router.templates().len(), 2);
+        let result = crate::Server::builder()
+            .name("test")
+            .version("1.0.0")
+            .resource_template_router(router)
+            .build();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,289 @@
+//! Per-method request hooks.
+//!
+//! A [`MethodRouter`] runs [`MethodHook`]s around the server's handling of
+//! individual MCP methods such as `tools/call` or `resources/read`. Hooks
+//! see the decoded method and params rather than raw transport messages, so
+//! they can implement auditing, quota enforcement, or request rewriting
+//! without touching the handlers:
+//!
+//! - [`MethodHook::before`] may modify the params or reject the request by
+//!   returning an error, which is sent to the client instead of calling the
+//!   handler.
+//! - [`MethodHook::after`] sees the outcome and may replace it.
+//!
+//! `before` hooks run in registration order and `after` hooks in reverse, so
+//! the first hook registered wraps all others. Hooks apply to every client
+//! request except `initialize`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use pmcp::server::router::{HookRequest, MethodHook, MethodRouter};
+//! use pmcp::Server;
+//! use std::sync::atomic::{AtomicU32, Ordering};
+//!
+//! /// Reject tool calls once a fixed budget is spent.
+//! struct Quota(AtomicU32);
+//!
+//! #[async_trait]
+//! impl MethodHook for Quota {
+//!     async fn before(&self, _request: &mut HookRequest) -> pmcp::Result<()> {
+//!         self.0
+//!             .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| left.checked_sub(1))
+//!             .map(|_| ())
+//!             .map_err(|_| pmcp::Error::validation("Tool call quota exhausted"))
+//!     }
+//! }
+//!
+//! let server = Server::builder()
+//!     .name("metered")
+//!     .version("1.0.0")
+//!     .router(MethodRouter::new().on("tools/call", Quota(AtomicU32::new(100))))
+//!     .build()?;
+//! # Ok::<(), pmcp::Error>(())
+//! ```
+
+use crate::error::Result;
+use crate::types::RequestId;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+
+/// A request as seen by [`MethodHook`]s.
+#[derive(Debug, Clone)]
+pub struct HookRequest {
+    /// MCP method, e.g. `tools/call`
+    pub method: String,
+    /// JSON-RPC request ID
+    pub request_id: RequestId,
+    /// Request params; `before` hooks may modify them
+    pub params: Value,
+}
+
+/// Hook run around requests for the methods it is registered for.
+#[async_trait]
+pub trait MethodHook: Send + Sync {
+    /// Called before the request is handled.
+    ///
+    /// Changes to `request.params` are passed on to the handler. Returning an
+    /// error skips the handler and the remaining `before` hooks.
+    async fn before(&self, _request: &mut HookRequest) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called after the request is handled, or rejected by a `before` hook.
+    ///
+    /// `outcome` is the result that will be sent to the client and may be
+    /// replaced.
+    async fn after(&self, _request: &HookRequest, _outcome: &mut Result<Value>) {}
+}
+
+/// Which methods a hook applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MethodPattern {
+    Any,
+    Prefix(String),
+    Exact(String),
+}
+
+impl MethodPattern {
+    fn parse(pattern: &str) -> Self {
+        if pattern == "*" {
+            Self::Any
+        } else if let Some(prefix) = pattern.strip_suffix('*') {
+            Self::Prefix(prefix.to_string())
+        } else {
+            Self::Exact(pattern.to_string())
+        }
+    }
+
+    fn matches(&self, method: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Prefix(prefix) => method.starts_with(prefix.as_str()),
+            Self::Exact(exact) => method == exact,
+        }
+    }
+}
+
+/// Ordered set of method hooks.
+#[derive(Clone, Default)]
+pub struct MethodRouter {
+    hooks: Vec<(MethodPattern, Arc<dyn MethodHook>)>,
+}
+
+impl fmt::Debug for MethodRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MethodRouter")
+            .field(
+                "patterns",
+                &self.hooks.iter().map(|(p, _)| p).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl MethodRouter {
+    /// Create a router with no hooks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a hook for a method.
+    ///
+    /// `method` is an exact method name, a prefix ending in `*` such as
+    /// `resources/*`, or `*` for every method.
+    pub fn on(mut self, method: &str, hook: impl MethodHook + 'static) -> Self {
+        self.hooks
+            .push((MethodPattern::parse(method), Arc::new(hook)));
+        self
+    }
+
+    /// Register a hook for every method.
+    pub fn on_any(self, hook: impl MethodHook + 'static) -> Self {
+        self.on("*", hook)
+    }
+
+    /// Whether no hooks are registered.
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Whether any hook applies to `method`.
+    pub fn routes(&self, method: &str) -> bool {
+        self.hooks
+            .iter()
+            .any(|(pattern, _)| pattern.matches(method))
+    }
+
+    /// Run `handle` wrapped in the hooks registered for the request's method.
+    ///
+    /// `handle` receives the params as modified by the `before` hooks.
+    pub async fn run<F, Fut>(&self, mut request: HookRequest, handle: F) -> Result<Value>
+    where
+        F: FnOnce(HookRequest) -> Fut,
+        Fut: std::future::Future<Output = Result<Value>>,
+    {
+        let hooks: Vec<&Arc<dyn MethodHook>> = self
+            .hooks
+            .iter()
+            .filter(|(pattern, _)| pattern.matches(&request.method))
+            .map(|(_, hook)| hook)
+            .collect();
+
+        let mut entered = 0;
+        let mut outcome = None;
+        for hook in &hooks {
+            entered += 1;
+            if let Err(e) = hook.before(&mut request).await {
+                outcome = Some(Err(e));
+                break;
+            }
+        }
+
+        let mut outcome = match outcome {
+            Some(rejected) => rejected,
+            None => handle(request.clone()).await,
+        };
+        for hook in hooks[..entered].iter().rev() {
+            hook.after(&request, &mut outcome).await;
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        reject: bool,
+    }
+
+    #[async_trait]
+    impl MethodHook for Recorder {
+        async fn before(&self, request: &mut HookRequest) -> Result<()> {
+            self.log.lock().push(format!("before {}", self.name));
+            request.params["seen"] = json!(self.name);
+            if self.reject {
+                return Err(Error::validation("rejected"));
+            }
+            Ok(())
+        }
+
+        async fn after(&self, _request: &HookRequest, outcome: &mut Result<Value>) {
+            self.log.lock().push(format!("after {}", self.name));
+            if let Ok(value) = outcome {
+                value["wrapped_by"] = json!(self.name);
+            }
+        }
+    }
+
+    fn request(method: &str) -> HookRequest {
+        HookRequest {
+            method: method.to_string(),
+            request_id: RequestId::from(1i64),
+            params: json!({}),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hooks_wrap_handler_in_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let hook = |name, reject| Recorder {
+            name,
+            log: log.clone(),
+            reject,
+        };
+        let router = MethodRouter::new()
+            .on_any(hook("outer", false))
+            .on("tools/*", hook("inner", false))
+            .on("resources/read", hook("other", false));
+        assert!(router.routes("tools/call"));
+        assert!(!MethodRouter::new()
+            .on("tools/list", hook("x", false))
+            .routes("tools/call"));
+
+        let result = router
+            .run(request("tools/call"), |request| async move {
+                Ok(json!({"params": request.params}))
+            })
+            .await
+            .unwrap();
+        assert_eq!(result["params"]["seen"], "inner");
+        assert_eq!(result["wrapped_by"], "outer");
+        assert_eq!(
+            *log.lock(),
+            ["before outer", "before inner", "after inner", "after outer"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejection_skips_handler_and_later_hooks() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let router = MethodRouter::new()
+            .on_any(Recorder {
+                name: "quota",
+                log: log.clone(),
+                reject: true,
+            })
+            .on_any(Recorder {
+                name: "audit",
+                log: log.clone(),
+                reject: false,
+            });
+
+        let result = router
+            .run(request("tools/call"), |_| async { panic!("handler ran") })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(*log.lock(), ["before quota", "after quota"]);
+    }
+}
@@ -6,6 +6,42 @@
 #[cfg(feature = "schema-generation")]
 use serde_json::{json, Value};
 
+/// Generate a JSON schema for a type using schemars.
+///
+/// Nested types and enums are inlined; recursive types keep the
+/// definitions they refer to.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::server::schema_utils::generate_schema;
+/// use schemars::JsonSchema;
+///
+/// #[derive(JsonSchema)]
+/// struct Args {
+///     city: String,
+/// }
+///
+/// let schema = generate_schema::<Args>();
+/// assert_eq!(schema["properties"]["city"]["type"], "string");
+/// ```
+#[cfg(feature = "schema-generation")]
+pub fn generate_schema<T: schemars::JsonSchema>() -> Value {
+    let schema = schemars::schema_for!(T);
+
+    // Convert the schema to JSON value
+    let json_schema = serde_json::to_value(&schema).unwrap_or_else(|_| {
+        json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": true
+        })
+    });
+
+    // Normalize the schema by inlining $ref references
+    normalize_schema(json_schema)
+}
+
 /// Configuration for schema normalization
 #[cfg(feature = "schema-generation")]
 #[derive(Debug, Clone)]
@@ -57,6 +93,12 @@ pub fn normalize_schema(schema: Value) -> Value {
 }
 
 /// Normalize a JSON schema with custom configuration
+///
+/// References are inlined unless that would recurse into a definition
+/// already being expanded (recursive types), nest deeper than
+/// `max_inline_depth`, or grow the schema beyond `max_inline_size`. If any
+/// reference is left in place, the definitions are kept so that it still
+/// resolves.
 #[cfg(feature = "schema-generation")]
 pub fn normalize_schema_with_config(mut schema: Value, config: &NormalizerConfig) -> Value {
     // Check size limit early
@@ -72,39 +114,28 @@ pub fn normalize_schema_with_config(mut schema: Value, config: &NormalizerConfig
         return schema;
     }
 
-    // Extract definitions if they exist
-    let definitions = if config.keep_definitions {
-        // Keep definitions but still process refs
-        schema
-            .as_object()
-            .and_then(|obj| obj.get("definitions").or_else(|| obj.get("$defs")))
-            .cloned()
-    } else {
-        // Remove definitions from schema
-        schema
-            .as_object_mut()
-            .and_then(|obj| obj.remove("definitions"))
-            .or_else(|| schema.as_object_mut().and_then(|obj| obj.remove("$defs")))
-    };
-
-    // Clone definitions for later reference if needed
-    let defs_clone = definitions.clone();
-
-    if let Some(defs) = definitions {
-        // Inline references with depth tracking
+    // Take the definitions out while inlining, remembering where they were
+    let definitions = schema.as_object_mut().and_then(|obj| {
+        ["$defs", "definitions"]
+            .into_iter()
+            .find_map(|key| obj.remove(key).map(|defs| (key, defs)))
+    });
+
+    if let Some((key, defs)) = definitions {
         let mut context = InlineContext {
             definitions: &defs,
-            current_depth: 0,
+            expanding: Vec::new(),
             max_depth: config.max_inline_depth,
             current_size: schema_size,
             max_size: config.max_inline_size,
+            kept_refs: false,
         };
         inline_refs_with_context(&mut schema, &mut context);
 
-        // If keeping definitions, put them back
-        if config.keep_definitions {
+        // Put the definitions back if asked to, or if references remain
+        if config.keep_definitions || context.kept_refs {
             if let Some(obj) = schema.as_object_mut() {
-                obj.insert("definitions".to_string(), defs);
+                obj.insert(key.to_string(), defs);
             }
         }
     }
@@ -114,83 +145,81 @@ pub fn normalize_schema_with_config(mut schema: Value, config: &NormalizerConfig
         if let Some(obj) = schema.as_object_mut() {
             obj.remove("$schema");
             obj.remove("$id");
-
-            // If the schema has a single root definition reference, inline it
-            if !config.keep_definitions {
-                if let Some(Value::String(ref_str)) = obj.get("$ref") {
-                    if ref_str.starts_with("#/definitions/") || ref_str.starts_with("#/$defs/") {
-                        let def_name = ref_str.rsplit('/').next().unwrap_or("");
-                        if let Some(defs) = defs_clone.as_ref() {
-                            if let Some(def_value) = defs.get(def_name) {
-                                return def_value.clone();
-                            }
-                        }
-                    }
-                }
-            }
         }
     }
 
     schema
 }
 
+/// Name of the local definition a `$ref` points to, if any
+#[cfg(feature = "schema-generation")]
+fn local_definition_name(reference: &str) -> Option<&str> {
+    reference
+        .strip_prefix("#/$defs/")
+        .or_else(|| reference.strip_prefix("#/definitions/"))
+}
+
 /// Context for tracking inlining depth and size
 #[cfg(feature = "schema-generation")]
 struct InlineContext<'a> {
     definitions: &'a Value,
-    current_depth: usize,
+    /// Definitions being expanded on the current path
+    expanding: Vec<String>,
     max_depth: usize,
     current_size: usize,
     max_size: usize,
+    /// Whether any reference was left in place
+    kept_refs: bool,
 }
 
 /// Recursively inline references with depth and size tracking
 #[cfg(feature = "schema-generation")]
 fn inline_refs_with_context(value: &mut Value, context: &mut InlineContext<'_>) {
-    // Check depth limit
-    if context.current_depth >= context.max_depth {
-        return;
-    }
-
-    // Check size limit
-    let value_size = serde_json::to_string(&value).unwrap_or_default().len();
-    if context.current_size + value_size > context.max_size {
-        return;
-    }
-
-    context.current_depth += 1;
-    context.current_size += value_size;
-
     match value {
         Value::Object(map) => {
-            // Check if this object has a $ref
-            if let Some(Value::String(ref_str)) = map.get("$ref") {
-                if ref_str.starts_with("#/definitions/") || ref_str.starts_with("#/$defs/") {
-                    let def_name = ref_str.rsplit('/').next().unwrap_or("");
-
-                    // Find the definition and clone it
-                    if let Some(def_value) = context.definitions.get(def_name) {
-                        // Check if inlining this would exceed size limit
-                        let def_size = serde_json::to_string(&def_value).unwrap_or_default().len();
-                        if context.current_size + def_size <= context.max_size {
-                            // Replace the entire object with the definition
-                            if let Some(def_obj) = def_value.as_object() {
-                                map.remove("$ref");
-                                for (key, val) in def_obj {
-                                    if !map.contains_key(key) {
-                                        map.insert(key.clone(), val.clone());
-                                    }
-                                }
+            let mut expanded = false;
+            let reference = map
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(local_definition_name)
+                .map(str::to_string);
+            if let Some(def_name) = reference {
+                let definition = context
+                    .definitions
+                    .get(&def_name)
+                    .and_then(Value::as_object);
+                let def_size = definition
+                    .map(|def| serde_json::to_string(def).unwrap_or_default().len())
+                    .unwrap_or_default();
+                match definition {
+                    Some(def_obj)
+                        if !context.expanding.contains(&def_name)
+                            && context.expanding.len() < context.max_depth
+                            && context.current_size + def_size <= context.max_size =>
+                    {
+                        // Replace the reference with the definition
+                        map.remove("$ref");
+                        for (key, val) in def_obj {
+                            if !map.contains_key(key) {
+                                map.insert(key.clone(), val.clone());
                             }
                         }
-                    }
+                        context.current_size += def_size;
+                        context.expanding.push(def_name);
+                        expanded = true;
+                    },
+                    _ => context.kept_refs = true,
                 }
             }
 
             // Process all values in the object
-            for (_key, val) in map.iter_mut() {
+            for val in map.values_mut() {
                 inline_refs_with_context(val, context);
             }
+
+            if expanded {
+                context.expanding.pop();
+            }
         },
         Value::Array(arr) => {
             // Process all items in the array
@@ -200,8 +229,6 @@ fn inline_refs_with_context(value: &mut Value, context: &mut InlineContext<'_>)
         },
         _ => {},
     }
-
-    context.current_depth -= 1;
 }
 
 /// Recursively inline all $ref references in a JSON value (legacy, unlimited)
@@ -395,6 +422,107 @@ mod tests {
         assert!(address_props.contains_key("city"));
     }
 
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Level3 {
+        value: u32,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Level2 {
+        inner: Option<Level3>,
+        many: Vec<Level3>,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    #[serde(tag = "kind")]
+    enum Shape {
+        Circle { radius: f64 },
+        Nested { level: Level2 },
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct TreeNode {
+        name: String,
+        children: Vec<TreeNode>,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Deep {
+        shape: Shape,
+        color: Color,
+        level: Level2,
+    }
+
+    /// Collect every `$ref` in a schema
+    fn refs(value: &Value, found: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(r)) = map.get("$ref") {
+                    found.push(r.clone());
+                }
+                map.values().for_each(|v| refs(v, found));
+            },
+            Value::Array(items) => items.iter().for_each(|v| refs(v, found)),
+            _ => {},
+        }
+    }
+
+    #[test]
+    fn test_nested_types_and_enums_are_fully_inlined() {
+        let schema = serde_json::to_value(schemars::schema_for!(Deep)).unwrap();
+        let normalized = normalize_schema(schema);
+
+        let mut found = Vec::new();
+        refs(&normalized, &mut found);
+        assert!(found.is_empty(), "dangling refs: {:?}", found);
+        assert!(normalized.get("$defs").is_none());
+
+        let props = &normalized["properties"];
+        assert_eq!(props["color"]["enum"], json!(["Red", "Green"]));
+        let variants = props["shape"]["oneOf"].as_array().unwrap();
+        let nested = &variants[1]["properties"]["level"]["properties"];
+        assert_eq!(
+            nested["many"]["items"]["properties"]["value"]["type"],
+            "integer"
+        );
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Forest {
+        trees: Vec<TreeNode>,
+    }
+
+    #[test]
+    fn test_recursive_types_keep_resolvable_refs() {
+        let schema = serde_json::to_value(schemars::schema_for!(Forest)).unwrap();
+        let normalized = normalize_schema(schema);
+
+        let mut found = Vec::new();
+        refs(&normalized, &mut found);
+        assert!(!found.is_empty());
+        for reference in found {
+            let name = local_definition_name(&reference).unwrap();
+            assert!(
+                normalized["$defs"].get(name).is_some(),
+                "unresolvable ref {}",
+                reference
+            );
+        }
+    }
+
     #[test]
     fn test_simple_schema() {
         let string_schema = simple_schema("string", Some("A test string"));
@@ -0,0 +1,480 @@
+//! Session storage for the streamable HTTP server.
+//!
+//! [`StreamableHttpServer`] looks sessions up through a [`SessionBackend`].
+//! The default [`InMemorySessionBackend`] keeps them in the process, which is
+//! enough for a single instance. Behind a load balancer, use a shared backend
+//! such as `RedisSessionBackend` (feature `redis`) so a session created on one
+//! replica is accepted by the others.
+//!
+//! The Redis backend also implements [`EventStore`], so clients can resume an
+//! SSE stream with `Last-Event-ID` on any replica. Open SSE streams themselves
+//! stay on the replica that serves them.
+//!
+//! Sessions live until the client deletes them unless a [`SessionPolicy`]
+//! gives them a lifetime. To keep sessions across restarts, implement
+//! [`SessionBackend`] over the application's own storage, or save an
+//! [`InMemorySessionBackend::snapshot`] on shutdown and
+//! [`restore`](InMemorySessionBackend::restore) it on start.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "redis")]
+//! # async fn example() -> pmcp::Result<()> {
+//! use pmcp::server::session_backend::RedisSessionBackend;
+//! use pmcp::server::streamable_http_server::StreamableHttpServerConfig;
+//! use std::sync::Arc;
+//!
+//! let backend = Arc::new(RedisSessionBackend::connect("redis://127.0.0.1/").await?);
+//! let config = StreamableHttpServerConfig {
+//!     session_backend: backend.clone(),
+//!     event_store: Some(backend),
+//!     ..Default::default()
+//! };
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`StreamableHttpServer`]: crate::server::streamable_http_server::StreamableHttpServer
+//! [`EventStore`]: crate::server::streamable_http_server::EventStore
+
+use crate::error::Result;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "redis")]
+pub use self::redis_backend::RedisSessionBackend;
+
+/// State the server keeps for a session.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionInfo {
+    /// Whether the session has completed initialization
+    pub initialized: bool,
+    /// Protocol version negotiated for the session
+    pub protocol_version: Option<String>,
+    /// When the session was created, in milliseconds since the Unix epoch
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    /// When the session was last used, in milliseconds since the Unix epoch
+    #[serde(default)]
+    pub last_used_at: Option<u64>,
+    /// Digest of the `Authorization` header the session was last used with
+    #[serde(default)]
+    pub auth_digest: Option<String>,
+}
+
+impl SessionInfo {
+    /// A session created at `now`, in milliseconds since the Unix epoch.
+    pub fn created(now: u64) -> Self {
+        Self {
+            created_at: Some(now),
+            last_used_at: Some(now),
+            ..Self::default()
+        }
+    }
+
+    /// Whether the session has expired at `now` under `policy`.
+    ///
+    /// Sessions without timestamps, such as ones stored by older versions,
+    /// never expire.
+    pub fn is_expired(&self, policy: &SessionPolicy, now: u64) -> bool {
+        let exceeds = |since: Option<u64>, limit: Option<Duration>| match (since, limit) {
+            (Some(since), Some(limit)) => {
+                u128::from(now.saturating_sub(since)) >= limit.as_millis()
+            },
+            _ => false,
+        };
+        exceeds(self.created_at, policy.max_lifetime)
+            || exceeds(self.last_used_at.or(self.created_at), policy.idle_timeout)
+    }
+}
+
+/// When sessions end on their own, and when they get a new ID.
+///
+/// The default keeps sessions until the client deletes them. Requests for
+/// an expired session are answered with `404 Not Found`, telling the client
+/// to initialize a new one.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::server::session_backend::SessionPolicy;
+/// use pmcp::server::streamable_http_server::StreamableHttpServerConfig;
+/// use std::time::Duration;
+///
+/// let config = StreamableHttpServerConfig {
+///     session_policy: SessionPolicy {
+///         idle_timeout: Some(Duration::from_secs(30 * 60)),
+///         max_lifetime: Some(Duration::from_secs(24 * 60 * 60)),
+///         rotate_on_auth_change: true,
+///     },
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionPolicy {
+    /// End sessions unused for this long
+    pub idle_timeout: Option<Duration>,
+    /// End sessions this long after they were created, however busy
+    pub max_lifetime: Option<Duration>,
+    /// Give a session a new ID when a request's `Authorization` header
+    /// differs from the one the session was last used with
+    pub rotate_on_auth_change: bool,
+}
+
+/// The current time in milliseconds since the Unix epoch.
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+        })
+}
+
+/// Storage for streamable HTTP sessions.
+#[async_trait]
+pub trait SessionBackend: Send + Sync {
+    /// Create or replace a session.
+    async fn put_session(&self, session_id: &str, info: &SessionInfo) -> Result<()>;
+
+    /// Look up a session, returning `None` if it is unknown or expired.
+    async fn get_session(&self, session_id: &str) -> Result<Option<SessionInfo>>;
+
+    /// Remove a session, returning whether it existed.
+    async fn remove_session(&self, session_id: &str) -> Result<bool>;
+
+    /// Remove the sessions expired at `now` under `policy`, returning their
+    /// IDs.
+    ///
+    /// The server calls this when it creates a session, so abandoned
+    /// sessions do not pile up. The default does nothing, for backends that
+    /// expire sessions on their own.
+    async fn remove_expired(&self, policy: &SessionPolicy, now: u64) -> Result<Vec<String>> {
+        let _ = (policy, now);
+        Ok(Vec::new())
+    }
+}
+
+/// Session backend keeping sessions in process memory.
+#[derive(Debug, Default)]
+pub struct InMemorySessionBackend {
+    sessions: RwLock<HashMap<String, SessionInfo>>,
+}
+
+impl InMemorySessionBackend {
+    /// Create an empty backend.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a backend holding `sessions`, such as a
+    /// [`snapshot`](Self::snapshot) saved before a restart.
+    pub fn restore(sessions: HashMap<String, SessionInfo>) -> Self {
+        Self {
+            sessions: RwLock::new(sessions),
+        }
+    }
+
+    /// A copy of every session, to save and [`restore`](Self::restore) later.
+    pub fn snapshot(&self) -> HashMap<String, SessionInfo> {
+        self.sessions.read().clone()
+    }
+}
+
+#[async_trait]
+impl SessionBackend for InMemorySessionBackend {
+    async fn put_session(&self, session_id: &str, info: &SessionInfo) -> Result<()> {
+        self.sessions
+            .write()
+            .insert(session_id.to_string(), info.clone());
+        Ok(())
+    }
+
+    async fn get_session(&self, session_id: &str) -> Result<Option<SessionInfo>> {
+        Ok(self.sessions.read().get(session_id).cloned())
+    }
+
+    async fn remove_session(&self, session_id: &str) -> Result<bool> {
+        Ok(self.sessions.write().remove(session_id).is_some())
+    }
+
+    async fn remove_expired(&self, policy: &SessionPolicy, now: u64) -> Result<Vec<String>> {
+        let mut expired = Vec::new();
+        self.sessions.write().retain(|id, info| {
+            let keep = !info.is_expired(policy, now);
+            if !keep {
+                expired.push(id.clone());
+            }
+            keep
+        });
+        Ok(expired)
+    }
+}
+
+#[cfg(feature = "redis")]
+mod redis_backend {
+    use super::{SessionBackend, SessionInfo};
+    use crate::error::{Error, Result};
+    use crate::server::streamable_http_server::EventStore;
+    use crate::shared::TransportMessage;
+    use async_trait::async_trait;
+    use redis::aio::ConnectionManager;
+    use redis::AsyncCommands;
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+
+    /// Default time a session or event stream lives without activity.
+    const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+    /// Default number of events kept per stream for resumption.
+    const DEFAULT_MAX_EVENTS_PER_STREAM: usize = 1000;
+
+    /// An event as stored in a stream's list.
+    #[derive(Serialize, Deserialize)]
+    struct StoredEvent {
+        id: String,
+        message: TransportMessage,
+    }
+
+    fn redis_error(e: redis::RedisError) -> Error {
+        Error::internal(format!("Redis error: {}", e))
+    }
+
+    /// Session backend and event store shared through Redis.
+    ///
+    /// Sessions expire after the TTL unless used; each request refreshes it.
+    /// Events are kept per stream, up to a maximum count, with the same TTL.
+    #[derive(Clone)]
+    pub struct RedisSessionBackend {
+        connection: ConnectionManager,
+        key_prefix: String,
+        ttl: Duration,
+        max_events_per_stream: usize,
+    }
+
+    impl std::fmt::Debug for RedisSessionBackend {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("RedisSessionBackend")
+                .field("key_prefix", &self.key_prefix)
+                .field("ttl", &self.ttl)
+                .field("max_events_per_stream", &self.max_events_per_stream)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl RedisSessionBackend {
+        /// Connect to the Redis server at `url`, e.g. `redis://127.0.0.1/`.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the URL is invalid or the server is unreachable.
+        pub async fn connect(url: &str) -> Result<Self> {
+            let client = redis::Client::open(url).map_err(redis_error)?;
+            let connection = client.get_connection_manager().await.map_err(redis_error)?;
+            Ok(Self::from_connection(connection))
+        }
+
+        /// Use an existing connection.
+        pub fn from_connection(connection: ConnectionManager) -> Self {
+            Self {
+                connection,
+                key_prefix: "pmcp:".to_string(),
+                ttl: DEFAULT_TTL,
+                max_events_per_stream: DEFAULT_MAX_EVENTS_PER_STREAM,
+            }
+        }
+
+        /// Prefix for all keys, so several servers can share a database.
+        pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+            self.key_prefix = prefix.into();
+            self
+        }
+
+        /// Time sessions and event streams live without activity.
+        pub fn with_ttl(mut self, ttl: Duration) -> Self {
+            self.ttl = ttl;
+            self
+        }
+
+        /// Number of events kept per stream for resumption.
+        pub fn with_max_events_per_stream(mut self, max: usize) -> Self {
+            self.max_events_per_stream = max.max(1);
+            self
+        }
+
+        fn session_key(&self, session_id: &str) -> String {
+            format!("{}session:{}", self.key_prefix, session_id)
+        }
+
+        fn stream_key(&self, stream_id: &str) -> String {
+            format!("{}stream:{}", self.key_prefix, stream_id)
+        }
+
+        fn event_key(&self, event_id: &str) -> String {
+            format!("{}event:{}", self.key_prefix, event_id)
+        }
+
+        fn ttl_secs(&self) -> u64 {
+            self.ttl.as_secs().max(1)
+        }
+    }
+
+    #[async_trait]
+    impl SessionBackend for RedisSessionBackend {
+        async fn put_session(&self, session_id: &str, info: &SessionInfo) -> Result<()> {
+            let value = serde_json::to_string(info)?;
+            let mut connection = self.connection.clone();
+            connection
+                .set_ex::<_, _, ()>(self.session_key(session_id), value, self.ttl_secs())
+                .await
+                .map_err(redis_error)
+        }
+
+        async fn get_session(&self, session_id: &str) -> Result<Option<SessionInfo>> {
+            let key = self.session_key(session_id);
+            let mut connection = self.connection.clone();
+            let value: Option<String> = redis::pipe()
+                .get(&key)
+                .expire(&key, i64::try_from(self.ttl_secs()).unwrap_or(i64::MAX))
+                .ignore()
+                .query_async::<(Option<String>,)>(&mut connection)
+                .await
+                .map_err(redis_error)?
+                .0;
+            value
+                .map(|value| serde_json::from_str(&value).map_err(Error::from))
+                .transpose()
+        }
+
+        async fn remove_session(&self, session_id: &str) -> Result<bool> {
+            let mut connection = self.connection.clone();
+            let removed: usize = connection
+                .del(self.session_key(session_id))
+                .await
+                .map_err(redis_error)?;
+            Ok(removed > 0)
+        }
+    }
+
+    #[async_trait]
+    impl EventStore for RedisSessionBackend {
+        async fn store_event(
+            &self,
+            stream_id: &str,
+            event_id: &str,
+            message: &TransportMessage,
+        ) -> Result<()> {
+            let event = serde_json::to_string(&StoredEvent {
+                id: event_id.to_string(),
+                message: message.clone(),
+            })?;
+            let stream_key = self.stream_key(stream_id);
+            let max = isize::try_from(self.max_events_per_stream).unwrap_or(isize::MAX);
+            let ttl = i64::try_from(self.ttl_secs()).unwrap_or(i64::MAX);
+            let mut connection = self.connection.clone();
+            redis::pipe()
+                .atomic()
+                .rpush(&stream_key, event)
+                .ignore()
+                .ltrim(&stream_key, -max, -1)
+                .ignore()
+                .expire(&stream_key, ttl)
+                .ignore()
+                .set_ex(self.event_key(event_id), stream_id, self.ttl_secs())
+                .ignore()
+                .query_async::<()>(&mut connection)
+                .await
+                .map_err(redis_error)
+        }
+
+        async fn replay_events_after(
+            &self,
+            last_event_id: &str,
+        ) -> Result<Vec<(String, TransportMessage)>> {
+            let Some(stream_id) = self.get_stream_for_event(last_event_id).await? else {
+                return Ok(Vec::new());
+            };
+            let mut connection = self.connection.clone();
+            let events: Vec<String> = connection
+                .lrange(self.stream_key(&stream_id), 0, -1)
+                .await
+                .map_err(redis_error)?;
+
+            let mut replay = Vec::new();
+            let mut found = false;
+            for event in events {
+                let event: StoredEvent = serde_json::from_str(&event)?;
+                if found {
+                    replay.push((event.id, event.message));
+                } else {
+                    found = event.id == last_event_id;
+                }
+            }
+            Ok(replay)
+        }
+
+        async fn get_stream_for_event(&self, event_id: &str) -> Result<Option<String>> {
+            let mut connection = self.connection.clone();
+            connection
+                .get(self.event_key(event_id))
+                .await
+                .map_err(redis_error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_backend_round_trip() {
+        let backend = InMemorySessionBackend::new();
+        assert_eq!(backend.get_session("s1").await.unwrap(), None);
+
+        let info = SessionInfo {
+            initialized: true,
+            protocol_version: Some("2025-06-18".to_string()),
+            ..SessionInfo::default()
+        };
+        backend.put_session("s1", &info).await.unwrap();
+        assert_eq!(backend.get_session("s1").await.unwrap(), Some(info));
+
+        assert!(backend.remove_session("s1").await.unwrap());
+        assert!(!backend.remove_session("s1").await.unwrap());
+        assert_eq!(backend.get_session("s1").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_expire_and_survive_restore() {
+        let policy = SessionPolicy {
+            idle_timeout: Some(Duration::from_secs(60)),
+            max_lifetime: Some(Duration::from_secs(3600)),
+            rotate_on_auth_change: false,
+        };
+        let mut busy = SessionInfo::created(0);
+        busy.last_used_at = Some(3_000_000);
+        assert!(!busy.is_expired(&policy, 3_030_000));
+        assert!(busy.is_expired(&policy, 3_600_000));
+        assert!(SessionInfo::created(0).is_expired(&policy, 60_000));
+        assert!(!SessionInfo::default().is_expired(&policy, u64::MAX));
+        assert!(!busy.is_expired(&SessionPolicy::default(), u64::MAX));
+
+        let backend = InMemorySessionBackend::new();
+        backend.put_session("busy", &busy).await.unwrap();
+        backend
+            .put_session("idle", &SessionInfo::created(0))
+            .await
+            .unwrap();
+        let expired = backend.remove_expired(&policy, 3_030_000).await.unwrap();
+        assert_eq!(expired, vec!["idle".to_string()]);
+
+        let restored = InMemorySessionBackend::restore(backend.snapshot());
+        assert_eq!(restored.get_session("busy").await.unwrap(), Some(busy));
+        assert_eq!(restored.get_session("idle").await.unwrap(), None);
+    }
+}
@@ -236,3 +236,299 @@ where
         })
     }
 }
+
+/// A prompt whose messages are authored as templates.
+///
+/// Message bodies use [minijinja](https://docs.rs/minijinja) syntax, so they
+/// support argument interpolation (`{{ topic }}`), conditionals
+/// (`{% if detail %}...{% endif %}`) and loops (`{% for item in items %}`).
+/// Arguments whose value is a JSON array or object are exposed to the
+/// template as structured values so they can be iterated over.
+///
+/// Templates are compiled when they are added. Compilation errors and
+/// references to undeclared arguments are reported by
+/// [`PromptHandler::validate`], which the server builders call, so a broken
+/// template fails at startup instead of on the first request.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::server::simple_prompt::TemplatePrompt;
+/// use pmcp::types::Role;
+///
+/// let prompt = TemplatePrompt::new("review")
+///     .with_description("Review code in a given language")
+///     .with_argument("language", "Programming language", true)
+///     .with_argument("focus", "Optional review focus", false)
+///     .with_message(
+///         Role::User,
+///         "Review this {{ language }} code.{% if focus %} Focus on {{ focus }}.{% endif %}",
+///     );
+/// ```
+#[cfg(feature = "templates")]
+pub struct TemplatePrompt {
+    name: String,
+    description: Option<String>,
+    arguments: Vec<PromptArgument>,
+    messages: Vec<(crate::types::Role, String)>,
+    env: minijinja::Environment<'static>,
+    errors: Vec<String>,
+}
+
+#[cfg(feature = "templates")]
+impl fmt::Debug for TemplatePrompt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemplatePrompt")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("arguments", &self.arguments)
+            .field("messages", &self.messages.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "templates")]
+impl TemplatePrompt {
+    /// Create a new template prompt with a name.
+    pub fn new(name: impl Into<String>) -> Self {
+        let mut env = minijinja::Environment::new();
+        env.set_undefined_behavior(minijinja::UndefinedBehavior::Lenient);
+        Self {
+            name: name.into(),
+            description: None,
+            arguments: Vec::new(),
+            messages: Vec::new(),
+            env,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Set the description for this prompt.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add an argument to this prompt.
+    pub fn with_argument(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        required: bool,
+    ) -> Self {
+        self.arguments.push(PromptArgument {
+            name: name.into(),
+            description: Some(description.into()),
+            required,
+            completion: None,
+        });
+        self
+    }
+
+    /// Set all arguments at once.
+    pub fn with_arguments(mut self, arguments: Vec<PromptArgument>) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// Add a message rendered from a template.
+    ///
+    /// Messages are emitted in the order they are added.
+    pub fn with_message(mut self, role: crate::types::Role, template: impl Into<String>) -> Self {
+        let key = format!("message_{}", self.messages.len());
+        let source = template.into();
+        if let Err(e) = self.env.add_template_owned(key, source.clone()) {
+            self.errors
+                .push(format!("message {}: {}", self.messages.len(), e));
+        }
+        self.messages.push((role, source));
+        self
+    }
+
+    fn context(&self, args: &HashMap<String, String>) -> minijinja::Value {
+        let values: HashMap<&str, serde_json::Value> = args
+            .iter()
+            .map(|(name, value)| {
+                let value = match serde_json::from_str::<serde_json::Value>(value) {
+                    Ok(parsed @ (serde_json::Value::Array(_) | serde_json::Value::Object(_))) => {
+                        parsed
+                    },
+                    _ => serde_json::Value::String(value.clone()),
+                };
+                (name.as_str(), value)
+            })
+            .collect();
+        minijinja::Value::from_serialize(&values)
+    }
+}
+
+#[cfg(feature = "templates")]
+#[async_trait]
+impl PromptHandler for TemplatePrompt {
+    async fn handle(
+        &self,
+        args: HashMap<String, String>,
+        _extra: RequestHandlerExtra,
+    ) -> Result<GetPromptResult> {
+        // Validate required arguments
+        for arg in &self.arguments {
+            if arg.required && !args.contains_key(&arg.name) {
+                return Err(crate::Error::validation(format!(
+                    "Required argument '{}' is missing",
+                    arg.name
+                )));
+            }
+        }
+
+        let ctx = self.context(&args);
+        let mut messages = Vec::with_capacity(self.messages.len());
+        for (index, (role, _)) in self.messages.iter().enumerate() {
+            let text = self
+                .env
+                .get_template(&format!("message_{}", index))
+                .and_then(|template| template.render(&ctx))
+                .map_err(|e| {
+                    crate::Error::internal(format!(
+                        "Failed to render prompt '{}' message {}: {}",
+                        self.name, index, e
+                    ))
+                })?;
+            messages.push(crate::types::PromptMessage {
+                role: *role,
+                content: crate::types::Content::Text { text },
+            });
+        }
+
+        Ok(GetPromptResult {
+            description: self.description.clone(),
+            messages,
+        })
+    }
+
+    fn metadata(&self) -> Option<PromptInfo> {
+        Some(PromptInfo {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            arguments: if self.arguments.is_empty() {
+                None
+            } else {
+                Some(self.arguments.clone())
+            },
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(error) = self.errors.first() {
+            return Err(crate::Error::validation(format!(
+                "Template compilation failed: {}",
+                error
+            )));
+        }
+
+        for index in 0..self.messages.len() {
+            let template = self
+                .env
+                .get_template(&format!("message_{}", index))
+                .map_err(|e| crate::Error::validation(e.to_string()))?;
+            let mut undeclared: Vec<String> = template
+                .undeclared_variables(false)
+                .into_iter()
+                .filter(|var| !self.arguments.iter().any(|arg| &arg.name == var))
+                .collect();
+            if !undeclared.is_empty() {
+                undeclared.sort();
+                return Err(crate::Error::validation(format!(
+                    "message {} references undeclared arguments: {}",
+                    index,
+                    undeclared.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "templates"))]
+mod tests {
+    use super::*;
+    use crate::types::{Content, Role};
+    use tokio_util::sync::CancellationToken;
+
+    fn extra() -> RequestHandlerExtra {
+        RequestHandlerExtra::new("test".to_string(), CancellationToken::new())
+    }
+
+    fn text(result: &GetPromptResult, index: usize) -> &str {
+        match &result.messages[index].content {
+            Content::Text { text } => text,
+            other => panic!("unexpected content: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_template_prompt_renders_conditionals_and_loops() {
+        let prompt = TemplatePrompt::new("plan")
+            .with_argument("goal", "Goal", true)
+            .with_argument("steps", "JSON array of steps", false)
+            .with_message(Role::System, "You are a planner.")
+            .with_message(
+                Role::User,
+                "Goal: {{ goal }}{% if steps %}\n{% for s in steps %}- {{ s }}\n{% endfor %}{% endif %}",
+            );
+        prompt.validate().unwrap();
+
+        let mut args = HashMap::new();
+        args.insert("goal".to_string(), "ship".to_string());
+        args.insert("steps".to_string(), r#"["build","test"]"#.to_string());
+        let result = prompt.handle(args, extra()).await.unwrap();
+
+        assert_eq!(result.messages.len(), 2);
+        assert_eq!(result.messages[0].role, Role::System);
+        assert_eq!(text(&result, 1), "Goal: ship\n- build\n- test\n");
+
+        let mut args = HashMap::new();
+        args.insert("goal".to_string(), "ship".to_string());
+        let result = prompt.handle(args, extra()).await.unwrap();
+        assert_eq!(text(&result, 1), "Goal: ship");
+    }
+
+    #[tokio::test]
+    async fn test_template_prompt_requires_arguments() {
+        let prompt = TemplatePrompt::new("greet")
+            .with_argument("name", "Name", true)
+            .with_message(Role::User, "Hello {{ name }}");
+        let err = prompt.handle(HashMap::new(), extra()).await.unwrap_err();
+        assert!(err.to_string().contains("name"));
+    }
+
+    #[test]
+    fn test_template_prompt_validation() {
+        let broken = TemplatePrompt::new("broken").with_message(Role::User, "{% if x %}open");
+        assert!(broken.validate().is_err());
+
+        let undeclared = TemplatePrompt::new("undeclared")
+            .with_argument("a", "A", true)
+            .with_message(Role::User, "{{ a }} {{ b }}");
+        let err = undeclared.validate().unwrap_err();
+        assert!(err.to_string().contains("b"));
+
+        let loop_var = TemplatePrompt::new("loop")
+            .with_argument("items", "Items", true)
+            .with_message(Role::User, "{% for i in items %}{{ i }}{% endfor %}");
+        assert!(loop_var.validate().is_ok());
+    }
+
+    #[test]
+    fn test_server_build_rejects_invalid_template() {
+        let result = crate::Server::builder()
+            .name("test")
+            .version("1.0.0")
+            .prompt(
+                "broken",
+                TemplatePrompt::new("broken").with_message(Role::User, "{{ unclosed"),
+            )
+            .build();
+        assert!(result.is_err());
+    }
+}
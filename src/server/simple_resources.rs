@@ -4,8 +4,10 @@ use crate::types::{Content, ListResourcesResult, ReadResourceResult, ResourceInf
 use crate::Result;
 use async_trait::async_trait;
 use base64::Engine;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fmt;
+use std::ops::Bound;
+use std::path::Path;
 use std::sync::Arc;
 
 use super::cancellation::RequestHandlerExtra;
@@ -88,17 +90,36 @@ impl StaticResource {
     pub fn uri(&self) -> &str {
         &self.uri
     }
+
+    /// Contents returned by `resources/read`, with text carrying the URI and
+    /// MIME type.
+    fn read_content(&self) -> Content {
+        match &self.content {
+            Content::Text { text } => Content::Resource {
+                uri: self.uri.clone(),
+                text: Some(text.clone()),
+                mime_type: self.mime_type.clone(),
+            },
+            other => other.clone(),
+        }
+    }
 }
 
 /// A collection of resources that can be managed together.
+///
+/// Resources are listed in URI order. With a page size set, `resources/list`
+/// returns one page at a time; the cursor names the last URI of the previous
+/// page, so paging stays consistent when resources are added in between.
 pub struct ResourceCollection {
-    resources: HashMap<String, Arc<StaticResource>>,
+    resources: BTreeMap<String, Arc<StaticResource>>,
+    page_size: Option<usize>,
 }
 
 impl fmt::Debug for ResourceCollection {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ResourceCollection")
             .field("resources", &self.resources.keys().collect::<Vec<_>>())
+            .field("page_size", &self.page_size)
             .finish()
     }
 }
@@ -113,10 +134,19 @@ impl ResourceCollection {
     /// Create a new empty resource collection.
     pub fn new() -> Self {
         Self {
-            resources: HashMap::new(),
+            resources: BTreeMap::new(),
+            page_size: None,
         }
     }
 
+    /// List at most `page_size` resources per `resources/list` response.
+    ///
+    /// A page size of zero is treated as one.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size.max(1));
+        self
+    }
+
     /// Add a resource to the collection.
     pub fn add_resource(mut self, resource: StaticResource) -> Self {
         self.resources
@@ -133,26 +163,253 @@ impl ResourceCollection {
         self
     }
 
+    /// Add every file under `base_dir` whose relative path matches `pattern`.
+    ///
+    /// Patterns use glob syntax (`**/*.md`, `config/*.{toml,json}`) and are
+    /// matched against paths relative to `base_dir` using `/` separators.
+    /// Each file becomes a resource with the URI `uri_prefix` followed by
+    /// its relative path. UTF-8 files are served as text, images as image
+    /// content; other binary files are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::ResourceCollection;
+    ///
+    /// let docs = ResourceCollection::new().add_glob("./docs", "**/*.md", "docs://")?;
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn add_glob(
+        mut self,
+        base_dir: impl AsRef<Path>,
+        pattern: &str,
+        uri_prefix: &str,
+    ) -> Result<Self> {
+        let base_dir = base_dir.as_ref();
+        let mut files = Vec::new();
+        collect_files(base_dir, base_dir, &mut files)?;
+
+        for (relative, path) in files {
+            if !glob_match::glob_match(pattern, &relative) {
+                continue;
+            }
+            let bytes = std::fs::read(&path).map_err(|e| {
+                crate::Error::internal(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            let uri = format!("{}{}", uri_prefix, relative);
+            if let Some(resource) = resource_from_bytes(uri, &relative, bytes) {
+                self.resources
+                    .insert(resource.uri.clone(), Arc::new(resource));
+            } else {
+                tracing::debug!("Skipping binary file {}", path.display());
+            }
+        }
+        Ok(self)
+    }
+
+    /// Add every file of a directory embedded with `include_dir!`.
+    ///
+    /// Usually invoked through the [`embed_dir!`](crate::embed_dir) macro,
+    /// which embeds the directory at compile time. Files are converted the
+    /// same way as in [`add_glob`](Self::add_glob).
+    #[cfg(feature = "embed")]
+    pub fn add_embedded_dir(mut self, dir: &include_dir::Dir<'_>, uri_prefix: &str) -> Self {
+        let mut stack = vec![dir];
+        while let Some(dir) = stack.pop() {
+            for entry in dir.entries() {
+                match entry {
+                    include_dir::DirEntry::Dir(child) => stack.push(child),
+                    include_dir::DirEntry::File(file) => {
+                        let relative = file.path().to_string_lossy().replace('\\', "/");
+                        let uri = format!("{}{}", uri_prefix, relative);
+                        if let Some(resource) =
+                            resource_from_bytes(uri, &relative, file.contents().to_vec())
+                        {
+                            self.resources
+                                .insert(resource.uri.clone(), Arc::new(resource));
+                        }
+                    },
+                }
+            }
+        }
+        self
+    }
+
     /// Get a resource by URI.
     pub fn get(&self, uri: &str) -> Option<&Arc<StaticResource>> {
         self.resources.get(uri)
     }
 
-    /// List all resources.
+    /// List all resources, ordered by URI.
     pub fn list(&self) -> Vec<ResourceInfo> {
         self.resources
             .values()
             .map(|resource| resource.info())
             .collect()
     }
+
+    /// List the page of resources following `cursor`.
+    ///
+    /// Without a page size, the first page contains every resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid params error if the cursor was not produced by
+    /// this collection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::{ResourceCollection, StaticResource};
+    ///
+    /// let collection = ResourceCollection::new()
+    ///     .add_resources((1..=5).map(|i| StaticResource::new_text(format!("file://{}", i), "")).collect())
+    ///     .with_page_size(2);
+    ///
+    /// let first = collection.list_page(None)?;
+    /// assert_eq!(first.resources.len(), 2);
+    /// let second = collection.list_page(first.next_cursor)?;
+    /// assert_eq!(second.resources[0].uri, "file://3");
+    /// # Ok::<(), pmcp::Error>(())
+    /// ```
+    pub fn list_page(&self, cursor: Option<String>) -> Result<ListResourcesResult> {
+        let after = cursor.map(|cursor| decode_cursor(&cursor)).transpose()?;
+        let mut remaining = match &after {
+            Some(after) => self
+                .resources
+                .range::<String, _>((Bound::Excluded(after), Bound::Unbounded)),
+            None => self.resources.range::<String, _>(..),
+        };
+        let page_size = self.page_size.unwrap_or(usize::MAX);
+
+        let resources: Vec<ResourceInfo> = remaining
+            .clone()
+            .take(page_size)
+            .map(|(_, resource)| resource.info())
+            .collect();
+        let next_cursor = if remaining.nth(page_size).is_some() {
+            resources.last().map(|last| encode_cursor(&last.uri))
+        } else {
+            None
+        };
+
+        Ok(ListResourcesResult {
+            resources,
+            next_cursor,
+        })
+    }
+}
+
+/// Encode the last URI of a page as an opaque cursor.
+fn encode_cursor(uri: &str) -> String {
+    base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(uri)
+}
+
+/// Decode a cursor produced by [`encode_cursor`].
+fn decode_cursor(cursor: &str) -> Result<String> {
+    base64::prelude::BASE64_URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| {
+            crate::Error::protocol(
+                crate::ErrorCode::INVALID_PARAMS,
+                format!("Invalid resource cursor: {}", cursor),
+            )
+        })
+}
+
+/// Recursively collect files below `dir` as `(relative_path, path)` pairs.
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(String, std::path::PathBuf)>,
+) -> Result<()> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        crate::Error::internal(format!("Failed to read directory {}: {}", dir.display(), e))
+    })?;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| crate::Error::internal(format!("Failed to read entry: {}", e)))?
+            .path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let relative = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((relative, path));
+        }
+    }
+    Ok(())
 }
 
+/// Build a static resource from file contents, guessing the MIME type from the extension.
+fn resource_from_bytes(uri: String, relative: &str, bytes: Vec<u8>) -> Option<StaticResource> {
+    let extension = relative
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    let mime_type = match extension.as_str() {
+        "md" | "markdown" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "csv" => "text/csv",
+        "js" => "text/javascript",
+        "json" => "application/json",
+        "toml" => "application/toml",
+        "yaml" | "yml" => "application/yaml",
+        "xml" => "application/xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        _ => "text/plain",
+    };
+
+    if mime_type.starts_with("image/") && mime_type != "image/svg+xml" {
+        return Some(StaticResource::new_image(uri, &bytes, mime_type));
+    }
+    String::from_utf8(bytes)
+        .ok()
+        .map(|text| StaticResource::new_text(uri, text).with_mime_type(mime_type))
+}
+
+/// Build a [`ResourceCollection`] from a directory embedded at compile time.
+///
+/// The path is resolved relative to the crate's `Cargo.toml`, as with
+/// `include_dir!`. Requires the `embed` feature.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let docs = pmcp::embed_dir!("$CARGO_MANIFEST_DIR/docs", "docs://");
+/// ```
+#[cfg(feature = "embed")]
+#[macro_export]
+macro_rules! embed_dir {
+    ($path:tt, $uri_prefix:expr) => {{
+        static DIR: $crate::server::simple_resources::include_dir::Dir<'static> =
+            $crate::server::simple_resources::include_dir::include_dir!($path);
+        $crate::server::simple_resources::ResourceCollection::new()
+            .add_embedded_dir(&DIR, $uri_prefix)
+    }};
+}
+
+#[cfg(feature = "embed")]
+#[doc(hidden)]
+pub use include_dir;
+
 #[async_trait]
 impl ResourceHandler for ResourceCollection {
     async fn read(&self, uri: &str, _extra: RequestHandlerExtra) -> Result<ReadResourceResult> {
         match self.resources.get(uri) {
             Some(resource) => Ok(ReadResourceResult {
-                contents: vec![resource.content.clone()],
+                contents: vec![resource.read_content()],
+                meta: None,
             }),
             None => Err(crate::Error::protocol(
                 crate::ErrorCode::INVALID_PARAMS,
@@ -163,13 +420,10 @@ impl ResourceHandler for ResourceCollection {
 
     async fn list(
         &self,
-        _cursor: Option<String>,
+        cursor: Option<String>,
         _extra: RequestHandlerExtra,
     ) -> Result<ListResourcesResult> {
-        Ok(ListResourcesResult {
-            resources: self.list(),
-            next_cursor: None,
-        })
+        self.list_page(cursor)
     }
 }
 
@@ -244,6 +498,14 @@ where
             list_handler,
         }
     }
+
+    /// Cache reads and tag them with entity tags for conditional requests.
+    ///
+    /// See [`CachedResourceHandler`](super::resource_cache::CachedResourceHandler)
+    /// for TTL and invalidation options.
+    pub fn cached(self) -> super::resource_cache::CachedResourceHandler<Self> {
+        super::resource_cache::CachedResourceHandler::new(self)
+    }
 }
 
 #[async_trait]
@@ -276,3 +538,106 @@ where
         (self.list_handler)(cursor, extra).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_util::sync::CancellationToken;
+
+    fn extra() -> RequestHandlerExtra {
+        RequestHandlerExtra::new("test".to_string(), CancellationToken::new())
+    }
+
+    #[tokio::test]
+    async fn test_add_glob() {
+        let dir = std::env::temp_dir().join(format!("pmcp-glob-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("guides")).unwrap();
+        std::fs::write(dir.join("README.md"), "# Readme").unwrap();
+        std::fs::write(dir.join("guides/intro.md"), "Intro").unwrap();
+        std::fs::write(dir.join("guides/data.json"), "{}").unwrap();
+        std::fs::write(dir.join("blob.md"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let collection = ResourceCollection::new()
+            .add_glob(&dir, "**/*.md", "docs://")
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let mut uris: Vec<_> = collection.list().into_iter().map(|r| r.uri).collect();
+        uris.sort();
+        assert_eq!(uris, vec!["docs://README.md", "docs://guides/intro.md"]);
+        assert_eq!(
+            collection
+                .get("docs://guides/intro.md")
+                .unwrap()
+                .info()
+                .mime_type,
+            Some("text/markdown".to_string())
+        );
+
+        let result = collection.read("docs://README.md", extra()).await.unwrap();
+        match &result.contents[0] {
+            Content::Resource {
+                uri,
+                text,
+                mime_type,
+            } => {
+                assert_eq!(uri, "docs://README.md");
+                assert_eq!(text.as_deref(), Some("# Readme"));
+                assert_eq!(mime_type.as_deref(), Some("text/markdown"));
+            },
+            other => panic!("unexpected content: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_paginates_in_uri_order() {
+        let collection = ResourceCollection::new()
+            .add_resources(
+                ["c", "a", "e", "b", "d"]
+                    .iter()
+                    .map(|name| StaticResource::new_text(format!("mem://{}", name), *name))
+                    .collect(),
+            )
+            .with_page_size(2);
+
+        let mut uris = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = ResourceHandler::list(&collection, cursor, extra())
+                .await
+                .unwrap();
+            assert!(page.resources.len() <= 2);
+            uris.extend(page.resources.into_iter().map(|r| r.uri));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(
+            uris,
+            vec!["mem://a", "mem://b", "mem://c", "mem://d", "mem://e"]
+        );
+
+        let unpaged = ResourceCollection::new()
+            .add_resource(StaticResource::new_text("mem://a", "a"))
+            .list_page(None)
+            .unwrap();
+        assert_eq!(unpaged.resources.len(), 1);
+        assert!(unpaged.next_cursor.is_none());
+
+        assert!(collection.list_page(Some("%%%".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_add_glob_missing_directory() {
+        let result = ResourceCollection::new().add_glob("/nonexistent/dir", "*", "x://");
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "embed")]
+    #[test]
+    fn test_embed_dir() {
+        let collection = crate::embed_dir!("$CARGO_MANIFEST_DIR/src/types", "src://");
+        assert!(collection.get("src://protocol.rs").is_some());
+    }
+}
@@ -1,6 +1,9 @@
 //! Simple tool implementations with schema support.
 
-use crate::{types::ToolInfo, Result};
+use crate::{
+    types::{ToolAnnotations, ToolInfo},
+    Result,
+};
 use async_trait::async_trait;
 use serde_json::Value;
 use std::fmt;
@@ -20,6 +23,7 @@ where
     name: String,
     description: Option<String>,
     input_schema: Value,
+    annotations: ToolAnnotations,
     handler: F,
 }
 
@@ -34,6 +38,7 @@ where
             .field("name", &self.name)
             .field("description", &self.description)
             .field("input_schema", &self.input_schema)
+            .field("annotations", &self.annotations)
             .finish()
     }
 }
@@ -54,6 +59,7 @@ where
                 "properties": {},
                 "additionalProperties": true
             }),
+            annotations: ToolAnnotations::default(),
             handler,
         }
     }
@@ -64,6 +70,37 @@ where
         self
     }
 
+    /// Set the behavior hints advertised for this tool.
+    pub fn with_annotations(mut self, annotations: ToolAnnotations) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Advertise that this tool only reads its environment.
+    pub fn read_only(mut self) -> Self {
+        self.annotations.read_only_hint = Some(true);
+        self
+    }
+
+    /// Advertise that this tool may make destructive changes.
+    pub fn destructive(mut self) -> Self {
+        self.annotations.destructive_hint = Some(true);
+        self
+    }
+
+    /// Advertise that repeating a call with the same arguments has no
+    /// further effect.
+    pub fn idempotent(mut self) -> Self {
+        self.annotations.idempotent_hint = Some(true);
+        self
+    }
+
+    /// Advertise that this tool interacts with external entities.
+    pub fn open_world(mut self) -> Self {
+        self.annotations.open_world_hint = Some(true);
+        self
+    }
+
     /// Set the input schema for this tool.
     pub fn with_schema(mut self, schema: Value) -> Self {
         self.input_schema = schema;
@@ -87,6 +124,8 @@ where
             name: self.name.clone(),
             description: self.description.clone(),
             input_schema: self.input_schema.clone(),
+            output_schema: None,
+            annotations: (!self.annotations.is_empty()).then(|| self.annotations.clone()),
         })
     }
 }
@@ -99,6 +138,7 @@ where
     name: String,
     description: Option<String>,
     input_schema: Value,
+    annotations: ToolAnnotations,
     handler: F,
 }
 
@@ -111,6 +151,7 @@ where
             .field("name", &self.name)
             .field("description", &self.description)
             .field("input_schema", &self.input_schema)
+            .field("annotations", &self.annotations)
             .finish()
     }
 }
@@ -129,6 +170,7 @@ where
                 "properties": {},
                 "additionalProperties": true
             }),
+            annotations: ToolAnnotations::default(),
             handler,
         }
     }
@@ -139,6 +181,37 @@ where
         self
     }
 
+    /// Set the behavior hints advertised for this tool.
+    pub fn with_annotations(mut self, annotations: ToolAnnotations) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Advertise that this tool only reads its environment.
+    pub fn read_only(mut self) -> Self {
+        self.annotations.read_only_hint = Some(true);
+        self
+    }
+
+    /// Advertise that this tool may make destructive changes.
+    pub fn destructive(mut self) -> Self {
+        self.annotations.destructive_hint = Some(true);
+        self
+    }
+
+    /// Advertise that repeating a call with the same arguments has no
+    /// further effect.
+    pub fn idempotent(mut self) -> Self {
+        self.annotations.idempotent_hint = Some(true);
+        self
+    }
+
+    /// Advertise that this tool interacts with external entities.
+    pub fn open_world(mut self) -> Self {
+        self.annotations.open_world_hint = Some(true);
+        self
+    }
+
     /// Set the input schema for this tool.
     pub fn with_schema(mut self, schema: Value) -> Self {
         self.input_schema = schema;
@@ -160,6 +233,8 @@ where
             name: self.name.clone(),
             description: self.description.clone(),
             input_schema: self.input_schema.clone(),
+            output_schema: None,
+            annotations: (!self.annotations.is_empty()).then(|| self.annotations.clone()),
         })
     }
 }
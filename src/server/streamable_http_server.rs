@@ -1,15 +1,30 @@
 //! Streamable HTTP server implementation for MCP.
 use crate::error::Result;
+use crate::server::auth::oauth2::{
+    client_registration_routes, protected_resource_routes, OAuthProvider, ProtectedResourceMetadata,
+};
+use crate::server::cancellation::{request_key, SESSION_ID};
+use crate::server::client_requests::{ClientRequester, SESSION_REQUESTER};
+#[cfg(feature = "prometheus")]
+use crate::server::metrics::{metrics_routes, ServerMetrics};
+use crate::server::origin::OriginPolicy;
+use crate::server::session_backend::{
+    now_millis, InMemorySessionBackend, SessionBackend, SessionInfo, SessionPolicy,
+};
 use crate::server::Server;
+use crate::shared::cors::CorsConfig;
 use crate::shared::http_constants::{
     APPLICATION_JSON, LAST_EVENT_ID, MCP_PROTOCOL_VERSION, MCP_SESSION_ID, TEXT_EVENT_STREAM,
 };
-use crate::shared::TransportMessage;
-use crate::types::{ClientRequest, Request};
+use crate::shared::timing::{Phase, RequestTimer};
+use crate::shared::{ProtocolOptions, TransportMessage};
+use crate::types::{ClientNotification, ClientRequest, Notification, Request};
+use crate::utils::batching::{WriteBatcher, WriteBatchingConfig};
 use async_trait::async_trait;
 use axum::{
-    extract::State,
-    http::{header, HeaderMap, HeaderValue, StatusCode},
+    extract::{ConnectInfo, DefaultBodyLimit, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
     response::{sse::Event, IntoResponse, Response, Sse},
     routing::{delete, get, post},
     Json, Router,
@@ -17,6 +32,7 @@ use axum::{
 use futures_util::StreamExt;
 use parking_lot::RwLock;
 use serde_json::json;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::convert::Infallible;
 use std::net::SocketAddr;
@@ -45,6 +61,11 @@ pub trait EventStore: Send + Sync {
 
     /// Get stream ID for an event ID
     async fn get_stream_for_event(&self, event_id: &str) -> Result<Option<String>>;
+
+    /// Number of stored events, or `None` if the store can't tell cheaply
+    async fn event_count(&self) -> Result<Option<usize>> {
+        Ok(None)
+    }
 }
 
 /// Type alias for event list
@@ -121,11 +142,18 @@ impl EventStore for InMemoryEventStore {
     async fn get_stream_for_event(&self, event_id: &str) -> Result<Option<String>> {
         Ok(self.event_to_stream.read().get(event_id).cloned())
     }
+
+    async fn event_count(&self) -> Result<Option<usize>> {
+        Ok(Some(self.event_order.read().len()))
+    }
 }
 
 /// Type alias for session callback
 type SessionCallback = Box<dyn Fn(&str) + Send + Sync>;
 
+/// Type alias for session rotation callback, given the old and new IDs
+type SessionRotatedCallback = Box<dyn Fn(&str, &str) + Send + Sync>;
+
 /// Configuration for the streamable HTTP server.
 ///
 /// # Examples
@@ -141,6 +169,7 @@ type SessionCallback = Box<dyn Fn(&str) + Send + Sync>;
 ///     event_store: None,
 ///     on_session_initialized: None,
 ///     on_session_closed: None,
+///     ..Default::default()
 /// };
 ///
 /// // Stateful configuration with custom session IDs
@@ -156,6 +185,7 @@ type SessionCallback = Box<dyn Fn(&str) + Send + Sync>;
 ///     on_session_closed: Some(Box::new(|session_id| {
 ///         println!("Session ended: {}", session_id);
 ///     })),
+///     ..Default::default()
 /// };
 /// ```
 pub struct StreamableHttpServerConfig {
@@ -163,26 +193,68 @@ pub struct StreamableHttpServerConfig {
     pub session_id_generator: Option<Box<dyn Fn() -> String + Send + Sync>>,
     /// Enable JSON responses instead of SSE
     pub enable_json_response: bool,
-    /// Event store for resumability (using concrete type for object safety)
-    pub event_store: Option<Arc<InMemoryEventStore>>,
+    /// Event store for resumability
+    pub event_store: Option<Arc<dyn EventStore>>,
+    /// Where sessions are kept; share one backend between replicas to scale out
+    pub session_backend: Arc<dyn SessionBackend>,
+    /// When sessions expire and get new IDs
+    pub session_policy: SessionPolicy,
     /// Callback when session is initialized
     pub on_session_initialized: Option<SessionCallback>,
-    /// Callback when session is closed
+    /// Callback when session is closed or expires
     pub on_session_closed: Option<SessionCallback>,
+    /// Callback when a session gets a new ID, with the old and new IDs
+    pub on_session_rotated: Option<SessionRotatedCallback>,
+    /// Protocol options applied to incoming messages (e.g. strict validation)
+    pub protocol_options: ProtocolOptions,
+    /// Whether requests after initialization must carry the
+    /// `MCP-Protocol-Version` header
+    pub protocol_version_header: ProtocolVersionHeader,
+    /// Origins and hosts requests are accepted for, against DNS rebinding
+    pub origin_policy: OriginPolicy,
+    /// CORS headers sent to browser-based clients
+    pub cors: CorsConfig,
+    /// Protected resource metadata (RFC 9728) to serve at
+    /// `/.well-known/oauth-protected-resource`, so clients can discover the
+    /// authorization server
+    pub protected_resource: Option<ProtectedResourceMetadata>,
+    /// OAuth provider to serve a dynamic client registration (RFC 7591)
+    /// endpoint for at `/oauth2/register`
+    pub client_registration: Option<Arc<dyn OAuthProvider>>,
+    /// Batching of messages written to SSE streams; `None` writes each
+    /// message as soon as it is queued
+    pub write_batching: Option<WriteBatchingConfig>,
+    /// Metrics to serve at `/metrics`; sessions opened and closed here are
+    /// counted in them
+    #[cfg(feature = "prometheus")]
+    pub metrics: Option<Arc<ServerMetrics>>,
 }
 
 impl std::fmt::Debug for StreamableHttpServerConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("StreamableHttpServerConfig")
+        let mut debug = f.debug_struct("StreamableHttpServerConfig");
+        debug
             .field("session_id_generator", &self.session_id_generator.is_some())
             .field("enable_json_response", &self.enable_json_response)
             .field("event_store", &self.event_store.is_some())
+            .field("session_backend", &"SessionBackend { ... }")
+            .field("session_policy", &self.session_policy)
             .field(
                 "on_session_initialized",
                 &self.on_session_initialized.is_some(),
             )
             .field("on_session_closed", &self.on_session_closed.is_some())
-            .finish()
+            .field("on_session_rotated", &self.on_session_rotated.is_some())
+            .field("protocol_options", &self.protocol_options)
+            .field("protocol_version_header", &self.protocol_version_header)
+            .field("origin_policy", &self.origin_policy)
+            .field("cors", &self.cors)
+            .field("protected_resource", &self.protected_resource)
+            .field("client_registration", &self.client_registration.is_some())
+            .field("write_batching", &self.write_batching);
+        #[cfg(feature = "prometheus")]
+        debug.field("metrics", &self.metrics.is_some());
+        debug.finish()
     }
 }
 
@@ -192,28 +264,49 @@ impl Default for StreamableHttpServerConfig {
             session_id_generator: Some(Box::new(|| Uuid::new_v4().to_string())),
             enable_json_response: false,
             event_store: Some(Arc::new(InMemoryEventStore::default())),
+            session_backend: Arc::new(InMemorySessionBackend::new()),
+            session_policy: SessionPolicy::default(),
             on_session_initialized: None,
             on_session_closed: None,
+            on_session_rotated: None,
+            protocol_options: ProtocolOptions::default(),
+            protocol_version_header: ProtocolVersionHeader::default(),
+            origin_policy: OriginPolicy::default(),
+            cors: CorsConfig::default(),
+            protected_resource: None,
+            client_registration: None,
+            write_batching: None,
+            #[cfg(feature = "prometheus")]
+            metrics: None,
         }
     }
 }
 
-/// Session information
-#[derive(Debug, Clone)]
-struct SessionInfo {
-    initialized: bool,
-    protocol_version: Option<String>,
+/// How the `MCP-Protocol-Version` header is checked on requests after
+/// initialization.
+///
+/// A header naming a version the server does not speak, or one other than
+/// the version negotiated for the session, is always answered with
+/// `400 Bad Request`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProtocolVersionHeader {
+    /// Accept requests without the header, assuming the negotiated version,
+    /// for clients predating the header.
+    #[default]
+    Optional,
+    /// Answer requests without the header with `400 Bad Request`.
+    Required,
 }
 
 /// Server state shared across routes
 #[derive(Clone)]
 struct ServerState {
-    server: Arc<tokio::sync::Mutex<Server>>,
+    server: Arc<Server>,
     config: Arc<StreamableHttpServerConfig>,
     /// Active SSE streams by session ID
     sse_streams: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<TransportMessage>>>>,
-    /// Session tracking (session ID -> session info)
-    sessions: Arc<RwLock<HashMap<String, SessionInfo>>>,
+    /// Requests to the client of each session
+    requesters: Arc<RwLock<HashMap<String, ClientRequester>>>,
 }
 
 /// A streamable HTTP server for MCP.
@@ -231,6 +324,61 @@ impl std::fmt::Debug for StreamableHttpServer {
     }
 }
 
+/// A streamable HTTP server as a [`tower::Service`](tower_service::Service).
+///
+/// Created by [`StreamableHttpServer::into_service`].
+#[derive(Clone)]
+pub struct McpService {
+    router: Router,
+}
+
+impl std::fmt::Debug for McpService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpService")
+            .field("router", &self.router)
+            .finish()
+    }
+}
+
+impl<B> tower_service::Service<axum::http::Request<B>> for McpService
+where
+    Router: tower_service::Service<axum::http::Request<B>>,
+{
+    type Response = <Router as tower_service::Service<axum::http::Request<B>>>::Response;
+    type Error = <Router as tower_service::Service<axum::http::Request<B>>>::Error;
+    type Future = <Router as tower_service::Service<axum::http::Request<B>>>::Future;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::result::Result<(), Self::Error>> {
+        tower_service::Service::<axum::http::Request<B>>::poll_ready(&mut self.router, cx)
+    }
+
+    fn call(&mut self, request: axum::http::Request<B>) -> Self::Future {
+        self.router.call(request)
+    }
+}
+
+/// The client's address, if the app is served with connect info.
+struct PeerAddr(Option<SocketAddr>);
+
+impl<S: Send + Sync> axum::extract::FromRequestParts<S> for PeerAddr {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        _state: &S,
+    ) -> std::result::Result<Self, Self::Rejection> {
+        Ok(Self(
+            parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| *addr),
+        ))
+    }
+}
+
 /// Helper function to create JSON-RPC error response
 fn create_error_response(status: StatusCode, code: i32, message: &str) -> Response {
     let error_body = json!({
@@ -242,46 +390,314 @@ fn create_error_response(status: StatusCode, code: i32, message: &str) -> Respon
         "id": null
     });
 
-    let mut resp = (status, Json(error_body)).into_response();
-    add_cors_headers(resp.headers_mut());
-    resp
+    (status, Json(error_body)).into_response()
+}
+
+/// Reject requests from origins or for hosts the policy does not allow
+fn check_origin(state: &ServerState, headers: &HeaderMap) -> std::result::Result<(), Response> {
+    state.config.origin_policy.check(headers).map_err(|reason| {
+        tracing::debug!("Rejecting request: {}", reason);
+        create_error_response(StatusCode::FORBIDDEN, -32600, reason)
+    })
+}
+
+/// Helper function to report a session backend failure
+fn session_backend_error(error: &crate::Error) -> Response {
+    tracing::error!("Session backend error: {}", error);
+    create_error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        -32603,
+        "Session storage unavailable",
+    )
+}
+
+/// Look up a session, mapping backend failures to an error response
+async fn lookup_session(
+    state: &ServerState,
+    session_id: &str,
+) -> std::result::Result<Option<SessionInfo>, Response> {
+    state
+        .config
+        .session_backend
+        .get_session(session_id)
+        .await
+        .map_err(|e| session_backend_error(&e))
+}
+
+/// Digest of the request's `Authorization` header, to notice when the
+/// credentials behind a session change without keeping them
+fn auth_digest(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(header::AUTHORIZATION)?;
+    let digest = Sha256::digest(value.as_bytes());
+    Some(digest[..16].iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Attach `requester` to the SSE stream of `session_id`
+fn attach_requester(state: &ServerState, session_id: &str, requester: &ClientRequester) {
+    let streams = state.sse_streams.clone();
+    let session_id = session_id.to_string();
+    requester.attach_sink(move |message| {
+        let streams = streams.read();
+        let stream = streams.get(&session_id).ok_or_else(|| {
+            crate::Error::invalid_state("Client has no SSE stream open for server requests")
+        })?;
+        stream
+            .send(message)
+            .map_err(|_| crate::Error::Transport(crate::error::TransportError::ConnectionClosed))
+    });
+}
+
+/// The requester of a session, created on first use
+fn session_requester(state: &ServerState, session_id: &str) -> ClientRequester {
+    if let Some(requester) = state.requesters.read().get(session_id) {
+        return requester.clone();
+    }
+    state
+        .requesters
+        .write()
+        .entry(session_id.to_string())
+        .or_insert_with(|| {
+            let requester = ClientRequester::new();
+            attach_requester(state, session_id, &requester);
+            requester
+        })
+        .clone()
+}
+
+/// Drop the SSE stream and requester of a session that has ended and report it
+fn session_ended(state: &ServerState, session_id: &str) {
+    state.sse_streams.write().remove(session_id);
+    state.requesters.write().remove(session_id);
+    if let Some(callback) = &state.config.on_session_closed {
+        callback(session_id);
+    }
+    #[cfg(feature = "prometheus")]
+    if let Some(metrics) = &state.config.metrics {
+        metrics.session_closed();
+    }
+}
+
+/// Look up the session a request names under the session policy.
+///
+/// Expired sessions are removed, and a session used with new credentials
+/// is moved to a new ID if the policy asks for it. Returns the session's
+/// current ID and info, or `None` if it is unknown or expired.
+async fn resume_session(
+    state: &ServerState,
+    session_id: String,
+    headers: &HeaderMap,
+) -> std::result::Result<Option<(String, SessionInfo)>, Response> {
+    let Some(mut info) = lookup_session(state, &session_id).await? else {
+        return Ok(None);
+    };
+    let policy = &state.config.session_policy;
+    let backend = &state.config.session_backend;
+    let now = now_millis();
+
+    if info.is_expired(policy, now) {
+        tracing::debug!("Session {} expired", session_id);
+        backend
+            .remove_session(&session_id)
+            .await
+            .map_err(|e| session_backend_error(&e))?;
+        session_ended(state, &session_id);
+        return Ok(None);
+    }
+
+    let digest = auth_digest(headers);
+    let rotate = policy.rotate_on_auth_change && info.initialized && info.auth_digest != digest;
+    let generator = state.config.session_id_generator.as_ref();
+    if let (true, Some(generator)) = (rotate, generator) {
+        let new_id = generator();
+        info.auth_digest = digest;
+        info.last_used_at = Some(now);
+        backend
+            .put_session(&new_id, &info)
+            .await
+            .map_err(|e| session_backend_error(&e))?;
+        backend
+            .remove_session(&session_id)
+            .await
+            .map_err(|e| session_backend_error(&e))?;
+        {
+            let mut streams = state.sse_streams.write();
+            if let Some(stream) = streams.remove(&session_id) {
+                streams.insert(new_id.clone(), stream);
+            }
+        }
+        let requester = state.requesters.write().remove(&session_id);
+        if let Some(requester) = requester {
+            attach_requester(state, &new_id, &requester);
+            state.requesters.write().insert(new_id.clone(), requester);
+        }
+        tracing::debug!("Session {} rotated after credentials changed", session_id);
+        if let Some(callback) = &state.config.on_session_rotated {
+            callback(&session_id, &new_id);
+        }
+        return Ok(Some((new_id, info)));
+    }
+
+    if policy.idle_timeout.is_some() {
+        info.last_used_at = Some(now);
+        backend
+            .put_session(&session_id, &info)
+            .await
+            .map_err(|e| session_backend_error(&e))?;
+    }
+    Ok(Some((session_id, info)))
+}
+
+/// Remove the sessions that have expired under the session policy
+async fn remove_expired_sessions(state: &ServerState) {
+    let policy = &state.config.session_policy;
+    if policy.idle_timeout.is_none() && policy.max_lifetime.is_none() {
+        return;
+    }
+    match state
+        .config
+        .session_backend
+        .remove_expired(policy, now_millis())
+        .await
+    {
+        Ok(expired) => {
+            for session_id in expired {
+                session_ended(state, &session_id);
+            }
+        },
+        Err(e) => tracing::warn!("Failed to remove expired sessions: {}", e),
+    }
 }
 
 impl StreamableHttpServer {
     /// Creates a new `StreamableHttpServer` with default config
-    pub fn new(addr: SocketAddr, server: Arc<tokio::sync::Mutex<Server>>) -> Self {
+    ///
+    /// Requests of all sessions are handled concurrently by the shared
+    /// `server`.
+    pub fn new(addr: SocketAddr, server: Arc<Server>) -> Self {
         Self::with_config(addr, server, StreamableHttpServerConfig::default())
     }
 
     /// Creates a new `StreamableHttpServer` with custom config
     pub fn with_config(
         addr: SocketAddr,
-        server: Arc<tokio::sync::Mutex<Server>>,
+        server: Arc<Server>,
         config: StreamableHttpServerConfig,
     ) -> Self {
         let state = ServerState {
             server,
             config: Arc::new(config),
             sse_streams: Arc::new(RwLock::new(HashMap::new())),
-            sessions: Arc::new(RwLock::new(HashMap::new())),
+            requesters: Arc::new(RwLock::new(HashMap::new())),
         };
 
         Self { addr, state }
     }
 
-    /// Starts the server and returns the bound address and a task handle.
-    pub async fn start(self) -> Result<(SocketAddr, tokio::task::JoinHandle<()>)> {
-        let app = Router::new()
+    /// Build the server's routes, to mount into an existing axum app.
+    ///
+    /// The MCP endpoint is served at `/` of the returned router, so nest it
+    /// where it should live; the router fits apps of any state type, and
+    /// layers of the app (authentication, tracing, CORS and so on) apply to
+    /// it like to any other route. The address given at construction is
+    /// not used. Apps served without
+    /// [`into_make_service_with_connect_info`](Router::into_make_service_with_connect_info)
+    /// work too, but per-principal rate limits then cannot fall back to the
+    /// client's IP address.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use axum::{routing::get, Router};
+    /// use pmcp::server::streamable_http_server::StreamableHttpServer;
+    /// use pmcp::Server;
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let server = Server::builder().name("embedded").version("1.0.0").build()?;
+    /// let mcp = StreamableHttpServer::new(([0, 0, 0, 0], 0).into(), Arc::new(server));
+    ///
+    /// let app = Router::new()
+    ///     .route("/health", get(|| async { "ok" }))
+    ///     .nest("/mcp", mcp.into_router());
+    ///
+    /// let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+    /// axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_router<S>(self) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        // Refuse to buffer bodies far beyond the inbound limit; smaller
+        // oversized bodies are answered with a JSON-RPC error instead.
+        let body_limit = self
+            .state
+            .config
+            .protocol_options
+            .message_size_limits
+            .inbound_close_threshold();
+        let protected_resource = self.state.config.protected_resource.clone();
+        let client_registration = self.state.config.client_registration.clone();
+        let cors = Arc::new(self.state.config.cors.clone());
+        #[cfg(feature = "prometheus")]
+        let metrics = self
+            .state
+            .config
+            .metrics
+            .clone()
+            .map(|metrics| metrics_routes(metrics, self.state.config.event_store.clone()));
+
+        let mut app: Router = Router::new()
             .route("/", post(handle_post_request))
             .route("/", get(handle_get_sse))
             .route("/", delete(handle_delete_session))
             .route("/", axum::routing::options(handle_options))
+            .route_layer(middleware::from_fn_with_state(cors, apply_cors))
             .with_state(self.state);
+        if let Some(metadata) = &protected_resource {
+            app = app.merge(protected_resource_routes(metadata));
+        }
+        if let Some(provider) = client_registration {
+            app = app.merge(client_registration_routes(provider));
+        }
+        #[cfg(feature = "prometheus")]
+        if let Some(routes) = metrics {
+            app = app.merge(routes);
+        }
+        if let Some(limit) = body_limit {
+            app = app.layer(DefaultBodyLimit::max(limit));
+        }
+        app.with_state(())
+    }
 
-        let listener = tokio::net::TcpListener::bind(self.addr).await?;
+    /// Build the server as a [`tower::Service`](tower_service::Service), to
+    /// serve it with hyper or wrap it in tower middleware.
+    ///
+    /// See [`into_router`](Self::into_router).
+    pub fn into_service(self) -> McpService {
+        McpService {
+            router: self.into_router(),
+        }
+    }
+
+    /// Starts the server and returns the bound address and a task handle.
+    pub async fn start(self) -> Result<(SocketAddr, tokio::task::JoinHandle<()>)> {
+        let addr = self.addr;
+        let origin_policy = self.state.config.origin_policy.clone();
+        let app: Router = self.into_router();
+
+        let listener = tokio::net::TcpListener::bind(addr).await?;
         let local_addr = listener.local_addr()?;
+        origin_policy.warn_if_exposed(local_addr);
         let server_task = tokio::spawn(async move {
-            axum::serve(listener, app).await.unwrap();
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap();
         });
 
         Ok((local_addr, server_task))
@@ -355,8 +771,9 @@ fn validate_headers(headers: &HeaderMap, method: &str) -> std::result::Result<()
 }
 
 /// Process session for initialization request
-fn process_init_session(
+async fn process_init_session(
     state: &ServerState,
+    headers: &HeaderMap,
     session_id: Option<String>,
     protocol_version: Option<String>,
 ) -> std::result::Result<(Option<String>, bool), Response> {
@@ -364,7 +781,7 @@ fn process_init_session(
         // Stateful mode
         if let Some(sid) = session_id {
             // Check if session already exists and is initialized
-            if let Some(session_info) = state.sessions.read().get(&sid) {
+            if let Some(session_info) = lookup_session(state, &sid).await? {
                 if session_info.initialized {
                     // Session already initialized - reject re-initialization
                     return Err(create_error_response(
@@ -377,19 +794,31 @@ fn process_init_session(
             // Use existing session ID
             Ok((Some(sid), false))
         } else {
+            remove_expired_sessions(state).await;
             // Generate new session ID
             let new_id = generator();
             // Create new session entry
-            state.sessions.write().insert(
-                new_id.clone(),
-                SessionInfo {
-                    initialized: false,
-                    protocol_version,
-                },
-            );
+            let info = SessionInfo {
+                initialized: false,
+                protocol_version,
+                auth_digest: auth_digest(headers),
+                ..SessionInfo::created(now_millis())
+            };
+            if let Err(e) = state
+                .config
+                .session_backend
+                .put_session(&new_id, &info)
+                .await
+            {
+                return Err(session_backend_error(&e));
+            }
             if let Some(callback) = &state.config.on_session_initialized {
                 callback(&new_id);
             }
+            #[cfg(feature = "prometheus")]
+            if let Some(metrics) = &state.config.metrics {
+                metrics.session_opened();
+            }
             Ok((Some(new_id), true))
         }
     } else {
@@ -398,11 +827,12 @@ fn process_init_session(
     }
 }
 
-/// Validate session for non-initialization request
-fn validate_non_init_session(
+/// Validate session for non-initialization request, returning its ID and info
+async fn validate_non_init_session(
     state: &ServerState,
+    headers: &HeaderMap,
     session_id: Option<String>,
-) -> std::result::Result<Option<String>, Response> {
+) -> std::result::Result<Option<(String, SessionInfo)>, Response> {
     if state.config.session_id_generator.is_some() {
         // Stateful mode - require and validate session ID
         match session_id {
@@ -416,15 +846,14 @@ fn validate_non_init_session(
             },
             Some(sid) => {
                 // Validate session exists
-                if !state.sessions.read().contains_key(&sid) {
-                    // Unknown session ID
-                    Err(create_error_response(
+                match resume_session(state, sid, headers).await? {
+                    Some(session) => Ok(Some(session)),
+                    // Unknown or expired session ID
+                    None => Err(create_error_response(
                         StatusCode::NOT_FOUND,
                         -32600,
                         "Unknown session ID",
-                    ))
-                } else {
-                    Ok(Some(sid))
+                    )),
                 }
             },
         }
@@ -449,18 +878,66 @@ fn extract_negotiated_version(response: &TransportMessage) -> Option<String> {
 }
 
 /// Update session info after initialization
-fn update_session_after_init(
+async fn update_session_after_init(
     state: &ServerState,
     session_id: Option<&String>,
     negotiated_version: Option<String>,
-) {
+) -> Result<()> {
     if let Some(sid) = session_id {
-        if let Some(session_info) = state.sessions.write().get_mut(sid) {
+        let backend = &state.config.session_backend;
+        if let Some(mut session_info) = backend.get_session(sid).await? {
             session_info.initialized = true;
             session_info.protocol_version =
                 negotiated_version.or_else(|| Some(crate::DEFAULT_PROTOCOL_VERSION.to_string()));
+            backend.put_session(sid, &session_info).await?;
         }
     }
+    Ok(())
+}
+
+/// Replace a response that exceeds the outbound size limit with an error
+fn limit_response_size(state: &ServerState, message: TransportMessage) -> TransportMessage {
+    let limits = state.config.protocol_options.message_size_limits;
+    if limits.outbound.is_none() {
+        return message;
+    }
+    let Ok(bytes) = crate::shared::StdioTransport::serialize_message(&message) else {
+        return message;
+    };
+    match (limits.check_outbound(bytes.len()), message) {
+        (Err(e), TransportMessage::Response(response)) => {
+            TransportMessage::Response(crate::types::JSONRPCResponse::error(
+                response.id,
+                crate::types::jsonrpc::JSONRPCError::from(e),
+            ))
+        },
+        (_, message) => message,
+    }
+}
+
+/// Messages to write to an SSE stream, gathered into batches if
+/// `write_batching` is set.
+///
+/// A batch is yielded message by message without pausing in between, so
+/// the connection is flushed once per batch.
+fn sse_messages(
+    rx: mpsc::UnboundedReceiver<TransportMessage>,
+    write_batching: Option<WriteBatchingConfig>,
+) -> futures_util::stream::BoxStream<'static, TransportMessage> {
+    let Some(config) = write_batching else {
+        return UnboundedReceiverStream::new(rx).boxed();
+    };
+    let batcher = WriteBatcher::new(config);
+    futures_util::stream::unfold(rx, move |mut rx| {
+        let batcher = batcher.clone();
+        async move {
+            let first = rx.recv().await?;
+            let batch = batcher.gather(first, &mut rx).await;
+            Some((futures_util::stream::iter(batch), rx))
+        }
+    })
+    .flatten()
+    .boxed()
 }
 
 /// Build response with appropriate format (JSON or SSE)
@@ -469,6 +946,7 @@ fn build_response(
     response: TransportMessage,
     session_id: Option<&String>,
 ) -> Response {
+    let response = limit_response_size(state, response);
     if state.config.enable_json_response {
         // JSON response mode - use JSON-RPC compatibility layer
         let json_bytes = match crate::shared::StdioTransport::serialize_message(&response) {
@@ -494,9 +972,7 @@ fn build_response(
             },
         };
 
-        let mut resp = (StatusCode::OK, Json(json_value)).into_response();
-        add_cors_headers(resp.headers_mut());
-        resp
+        (StatusCode::OK, Json(json_value)).into_response()
     } else {
         // SSE streaming mode
         if let Some(sid) = session_id {
@@ -509,7 +985,7 @@ fn build_response(
                 let (tx, rx) = mpsc::unbounded_channel();
                 tx.send(response).unwrap();
 
-                let stream = UnboundedReceiverStream::new(rx);
+                let stream = sse_messages(rx, state.config.write_batching.clone());
                 let sse = Sse::new(stream.map(|msg| {
                     let event_id = Uuid::new_v4().to_string();
                     // Use JSON-RPC compatibility layer for SSE messages
@@ -561,13 +1037,22 @@ fn build_response(
 
 /// Validate protocol version for non-init requests
 fn validate_protocol_version(
-    state: &ServerState,
-    session_id: Option<&String>,
+    config: &StreamableHttpServerConfig,
+    session_info: Option<&SessionInfo>,
     protocol_version: Option<&String>,
 ) -> std::result::Result<(), Response> {
+    if protocol_version.is_none()
+        && config.protocol_version_header == ProtocolVersionHeader::Required
+    {
+        return Err(create_error_response(
+            StatusCode::BAD_REQUEST,
+            -32600,
+            "Missing MCP-Protocol-Version header",
+        ));
+    }
     if let Some(version) = protocol_version {
         // Check if the provided version is supported
-        if !crate::SUPPORTED_PROTOCOL_VERSIONS.contains(&version.as_str()) {
+        if !config.protocol_options.versions.supports(version) {
             return Err(create_error_response(
                 StatusCode::BAD_REQUEST,
                 -32600,
@@ -577,24 +1062,18 @@ fn validate_protocol_version(
     }
 
     // For stateful mode, also validate against session's negotiated version if exists
-    if state.config.session_id_generator.is_some() {
-        if let Some(sid) = session_id {
-            if let Some(session_info) = state.sessions.read().get(sid.as_str()) {
-                if let Some(ref negotiated_version) = session_info.protocol_version {
-                    // If header provided, it should match the negotiated version
-                    if let Some(provided_version) = protocol_version {
-                        if provided_version != negotiated_version {
-                            return Err(create_error_response(
-                                StatusCode::BAD_REQUEST,
-                                -32600,
-                                &format!(
-                                    "Protocol version mismatch: expected {}, got {}",
-                                    negotiated_version, provided_version
-                                ),
-                            ));
-                        }
-                    }
-                }
+    if let Some(negotiated_version) = session_info.and_then(|info| info.protocol_version.as_ref()) {
+        // If header provided, it should match the negotiated version
+        if let Some(provided_version) = protocol_version {
+            if provided_version != negotiated_version {
+                return Err(create_error_response(
+                    StatusCode::BAD_REQUEST,
+                    -32600,
+                    &format!(
+                        "Protocol version mismatch: expected {}, got {}",
+                        negotiated_version, provided_version
+                    ),
+                ));
             }
         }
     }
@@ -604,26 +1083,60 @@ fn validate_protocol_version(
 /// Handle POST requests
 async fn handle_post_request(
     State(state): State<ServerState>,
+    PeerAddr(peer): PeerAddr,
     headers: HeaderMap,
     body: String,
 ) -> impl IntoResponse {
+    if let Err(error_response) = check_origin(&state, &headers) {
+        return error_response;
+    }
+
     // Validate headers
     if let Err(error_response) = validate_headers(&headers, "POST") {
         return error_response;
     }
 
+    // Reject oversized bodies before parsing
+    let limits = state.config.protocol_options.message_size_limits;
+    if let Err(e) = limits.check_inbound(body.len()) {
+        let error = crate::types::jsonrpc::JSONRPCError::from(e);
+        return create_error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            crate::ErrorCode::INVALID_REQUEST.as_i32(),
+            &error.message,
+        );
+    }
+    if let Err(e) = limits.check_inbound_structure(body.as_bytes()) {
+        let error = crate::types::jsonrpc::JSONRPCError::from(e);
+        return create_error_response(StatusCode::BAD_REQUEST, error.code, &error.message);
+    }
+
+    // Reject malformed envelopes before parsing in strict mode
+    if state.config.protocol_options.strict {
+        let validation = serde_json::from_str::<serde_json::Value>(&body)
+            .map_err(|e| crate::Error::parse(format!("Invalid JSON: {}", e)))
+            .and_then(|value| crate::shared::strict::validate_message(&value));
+        if let Err(e) = validation {
+            let error = crate::types::jsonrpc::JSONRPCError::from(e);
+            return create_error_response(StatusCode::BAD_REQUEST, error.code, &error.message);
+        }
+    }
+
     // Parse the JSON body using JSON-RPC compatibility layer
-    let message: TransportMessage =
-        match crate::shared::StdioTransport::parse_message(body.as_bytes()) {
-            Ok(msg) => msg,
-            Err(e) => {
-                return create_error_response(
-                    StatusCode::BAD_REQUEST,
-                    -32700,
-                    &format!("Invalid JSON: {}", e),
-                );
-            },
-        };
+    let timer = RequestTimer::new();
+    let parse_started = std::time::Instant::now();
+    let parsed = crate::shared::StdioTransport::parse_message(body.as_bytes());
+    timer.record(Phase::Parse, parse_started.elapsed());
+    let message: TransportMessage = match parsed {
+        Ok(msg) => msg,
+        Err(e) => {
+            return create_error_response(
+                StatusCode::BAD_REQUEST,
+                -32700,
+                &format!("Invalid JSON: {}", e),
+            );
+        },
+    };
 
     // Extract session ID from headers
     let session_id = headers
@@ -645,23 +1158,33 @@ async fn handle_post_request(
     );
 
     // Handle session ID logic based on request type
-    let (response_session_id, _is_new_session) = if is_init_request {
-        match process_init_session(&state, session_id.clone(), protocol_version.clone()) {
-            Ok(result) => result,
+    let (response_session_id, session_info) = if is_init_request {
+        match process_init_session(
+            &state,
+            &headers,
+            session_id.clone(),
+            protocol_version.clone(),
+        )
+        .await
+        {
+            Ok((sid, _is_new_session)) => (sid, None),
             Err(error_response) => return error_response,
         }
     } else {
-        match validate_non_init_session(&state, session_id.clone()) {
-            Ok(sid) => (sid, false),
+        match validate_non_init_session(&state, &headers, session_id.clone()).await {
+            Ok(Some((sid, info))) => (Some(sid), Some(info)),
+            Ok(None) => (None, None),
             Err(error_response) => return error_response,
         }
     };
 
     // Validate protocol version for non-init requests
     if !is_init_request {
-        if let Err(error_response) =
-            validate_protocol_version(&state, session_id.as_ref(), protocol_version.as_ref())
-        {
+        if let Err(error_response) = validate_protocol_version(
+            &state.config,
+            session_info.as_ref(),
+            protocol_version.as_ref(),
+        ) {
             return error_response;
         }
     }
@@ -669,14 +1192,57 @@ async fn handle_post_request(
     // Process the message
     match message {
         TransportMessage::Request { id, request } => {
-            let server = state.server.lock().await;
-            let json_response = server.handle_request(id, request).await;
+            let server = &state.server;
+            #[cfg(feature = "opentelemetry")]
+            let _ = server.transport_type.set("streamable-http");
+            // Boxed, as the request's future is too large to nest on the stack
+            let dispatch = Box::pin(timer.scope(server.handle_request(id, request)));
+            let session = response_session_id
+                .clone()
+                .map(|sid| (session_requester(&state, &sid), sid));
+            let dispatch = async move {
+                match session {
+                    Some((requester, sid)) => {
+                        SESSION_ID
+                            .scope(sid, SESSION_REQUESTER.scope(requester, dispatch))
+                            .await
+                    },
+                    None => dispatch.await,
+                }
+            };
+            let authorization = headers
+                .get(header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            let dispatch = async move {
+                match authorization {
+                    Some(authorization) => {
+                        crate::server::auth::AUTHORIZATION
+                            .scope(authorization, dispatch)
+                            .await
+                    },
+                    None => dispatch.await,
+                }
+            };
+            let json_response = match peer {
+                Some(peer) => {
+                    crate::server::rate_limit::CLIENT_ADDR
+                        .scope(peer.ip(), dispatch)
+                        .await
+                },
+                None => dispatch.await,
+            };
             let response = TransportMessage::Response(json_response.clone());
 
             // Handle initialization response
             let negotiated_version = if is_init_request {
                 let version = extract_negotiated_version(&response);
-                update_session_after_init(&state, response_session_id.as_ref(), version.clone());
+                if let Err(e) =
+                    update_session_after_init(&state, response_session_id.as_ref(), version.clone())
+                        .await
+                {
+                    return session_backend_error(&e);
+                }
                 version
             } else {
                 None
@@ -705,20 +1271,11 @@ async fn handle_post_request(
                 // For init responses, use the negotiated version
                 negotiated_version.unwrap_or_else(|| crate::DEFAULT_PROTOCOL_VERSION.to_string())
             } else {
-                // For subsequent responses, echo the session's negotiated version
-                if let Some(ref sid) = response_session_id {
-                    if let Some(session_info) = state.sessions.read().get(sid) {
-                        session_info
-                            .protocol_version
-                            .clone()
-                            .unwrap_or_else(|| crate::DEFAULT_PROTOCOL_VERSION.to_string())
-                    } else {
-                        crate::DEFAULT_PROTOCOL_VERSION.to_string()
-                    }
-                } else {
-                    // Stateless mode or no session - use default
-                    crate::DEFAULT_PROTOCOL_VERSION.to_string()
-                }
+                // For subsequent responses, echo the session's negotiated version;
+                // stateless mode or no session uses the default
+                session_info
+                    .and_then(|info| info.protocol_version)
+                    .unwrap_or_else(|| crate::DEFAULT_PROTOCOL_VERSION.to_string())
             };
 
             response
@@ -727,22 +1284,41 @@ async fn handle_post_request(
 
             response
         },
-        TransportMessage::Notification { .. } => {
+        TransportMessage::Notification(notification) => {
+            if let Notification::Cancelled(cancelled)
+            | Notification::Client(ClientNotification::Cancelled(cancelled)) = notification
+            {
+                state
+                    .server
+                    .cancellation_manager
+                    .cancel_from_peer(&request_key(
+                        response_session_id.as_deref(),
+                        &cancelled.request_id,
+                    ))
+                    .await;
+            }
             // Notifications get 202 Accepted
-            let mut resp = StatusCode::ACCEPTED.into_response();
-            add_cors_headers(resp.headers_mut());
-            resp
+            StatusCode::ACCEPTED.into_response()
         },
-        TransportMessage::Response(_) => {
-            let mut resp = StatusCode::ACCEPTED.into_response();
-            add_cors_headers(resp.headers_mut());
-            resp
+        TransportMessage::Response(response) => {
+            // Answers to requests the server made to the client
+            let requester = response_session_id
+                .as_deref()
+                .and_then(|sid| state.requesters.read().get(sid).cloned());
+            if !requester.is_some_and(|requester| requester.resolve(response)) {
+                tracing::debug!("Ignoring response that answers no pending request");
+            }
+            StatusCode::ACCEPTED.into_response()
         },
     }
 }
 
 /// Handle GET requests for SSE streams
 async fn handle_get_sse(State(state): State<ServerState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Err(error_response) = check_origin(&state, &headers) {
+        return error_response;
+    }
+
     // Validate headers
     if let Err(error_response) = validate_headers(&headers, "GET") {
         return error_response;
@@ -754,24 +1330,55 @@ async fn handle_get_sse(State(state): State<ServerState>, headers: HeaderMap) ->
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string());
 
+    let protocol_version = headers
+        .get(MCP_PROTOCOL_VERSION)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
     // Validate or generate session ID
     let session_id = if let Some(sid) = session_id {
         // Validate session exists
-        if state.config.session_id_generator.is_some() && !state.sessions.read().contains_key(&sid)
-        {
-            return create_error_response(StatusCode::NOT_FOUND, -32600, "Unknown session ID");
+        if state.config.session_id_generator.is_some() {
+            match resume_session(&state, sid, &headers).await {
+                Ok(Some((sid, info))) => {
+                    if let Err(error_response) = validate_protocol_version(
+                        &state.config,
+                        Some(&info),
+                        protocol_version.as_ref(),
+                    ) {
+                        return error_response;
+                    }
+                    sid
+                },
+                Ok(None) => {
+                    return create_error_response(
+                        StatusCode::NOT_FOUND,
+                        -32600,
+                        "Unknown session ID",
+                    );
+                },
+                Err(error_response) => return error_response,
+            }
+        } else {
+            sid
         }
-        sid
     } else if let Some(generator) = &state.config.session_id_generator {
+        remove_expired_sessions(&state).await;
         // Generate new session for GET SSE
         let new_id = generator();
-        state.sessions.write().insert(
-            new_id.clone(),
-            SessionInfo {
-                initialized: true, // GET SSE implicitly initializes
-                protocol_version: None,
-            },
-        );
+        let info = SessionInfo {
+            initialized: true, // GET SSE implicitly initializes
+            auth_digest: auth_digest(&headers),
+            ..SessionInfo::created(now_millis())
+        };
+        if let Err(e) = state
+            .config
+            .session_backend
+            .put_session(&new_id, &info)
+            .await
+        {
+            return session_backend_error(&e);
+        }
         if let Some(callback) = &state.config.on_session_initialized {
             callback(&new_id);
         }
@@ -815,7 +1422,7 @@ async fn handle_get_sse(State(state): State<ServerState>, headers: HeaderMap) ->
         }
     }
 
-    let stream = UnboundedReceiverStream::new(rx);
+    let stream = sse_messages(rx, state.config.write_batching.clone());
     let session_id_header = session_id.clone();
 
     let sse = Sse::new(stream.map(move |msg| {
@@ -832,19 +1439,23 @@ async fn handle_get_sse(State(state): State<ServerState>, headers: HeaderMap) ->
             });
         }
 
+        // Use JSON-RPC compatibility layer for SSE messages
+        let json_bytes =
+            crate::shared::StdioTransport::serialize_message(&msg).unwrap_or_else(|e| {
+                eprintln!("Failed to serialize SSE message: {}", e);
+                Vec::new()
+            });
+        let json_str = String::from_utf8(json_bytes).unwrap_or_else(|_| "{}".to_string());
         Ok::<_, Infallible>(
             Event::default()
                 .id(event_id)
                 .event("message")
-                .data(serde_json::to_string(&msg).unwrap()),
+                .data(json_str),
         )
     }));
 
     let mut response = sse.into_response();
 
-    // Add CORS headers
-    add_cors_headers(response.headers_mut());
-
     // Add session ID header
     response
         .headers_mut()
@@ -868,6 +1479,10 @@ async fn handle_delete_session(
     State(state): State<ServerState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
+    if let Err(error_response) = check_origin(&state, &headers) {
+        return error_response;
+    }
+
     // Extract session ID
     let session_id = headers
         .get(MCP_SESSION_ID)
@@ -875,58 +1490,65 @@ async fn handle_delete_session(
         .map(|s| s.to_string());
 
     if let Some(sid) = session_id {
-        // Check if session exists
-        let session_exists = state.sessions.read().contains_key(&sid);
+        // Remove session from tracking, checking that it existed
+        let session_existed = match state.config.session_backend.remove_session(&sid).await {
+            Ok(existed) => existed,
+            Err(e) => return session_backend_error(&e),
+        };
 
-        if !session_exists && state.config.session_id_generator.is_some() {
+        if !session_existed && state.config.session_id_generator.is_some() {
             // Unknown session in stateful mode
             return create_error_response(StatusCode::NOT_FOUND, -32600, "Unknown session ID");
         }
 
-        // Remove SSE stream if exists
+        // Remove SSE stream and requester if they exist
         state.sse_streams.write().remove(&sid);
-
-        // Remove session from tracking
-        state.sessions.write().remove(&sid);
+        state.requesters.write().remove(&sid);
 
         // Notify callback
         if let Some(callback) = &state.config.on_session_closed {
             callback(&sid);
         }
+        #[cfg(feature = "prometheus")]
+        if let (Some(metrics), true) = (&state.config.metrics, session_existed) {
+            metrics.session_closed();
+        }
 
-        let mut resp = (StatusCode::OK, Json(json!({"status": "ok"}))).into_response();
-        add_cors_headers(resp.headers_mut());
-        resp
+        (StatusCode::OK, Json(json!({"status": "ok"}))).into_response()
     } else {
         // No session to delete
         create_error_response(StatusCode::NOT_FOUND, -32600, "No session ID provided")
     }
 }
 
-/// Add CORS headers to a `HeaderMap`
-fn add_cors_headers(headers: &mut HeaderMap) {
-    headers.insert("Access-Control-Allow-Origin", HeaderValue::from_static("*"));
-    headers.insert(
-        "Access-Control-Allow-Methods",
-        HeaderValue::from_static("GET, POST, DELETE, OPTIONS"),
-    );
-    headers.insert(
-        "Access-Control-Allow-Headers",
-        HeaderValue::from_static(
-            "Content-Type, Accept, mcp-session-id, mcp-protocol-version, last-event-id",
-        ),
-    );
-    headers.insert(
-        "Access-Control-Expose-Headers",
-        HeaderValue::from_static("mcp-session-id, mcp-protocol-version"),
-    );
+/// Add the CORS headers for the request's origin to the response
+async fn apply_cors(
+    State(cors): State<Arc<CorsConfig>>,
+    request: axum::extract::Request,
+    next: Next,
+) -> Response {
+    let origin = request
+        .headers()
+        .get(header::ORIGIN)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+    let preflight = request.method() == Method::OPTIONS;
+
+    let mut response = next.run(request).await;
+    let cors_headers = if preflight {
+        cors.preflight_headers(origin.as_deref())
+    } else {
+        cors.headers(origin.as_deref())
+    };
+    for (name, value) in cors_headers {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+    response
 }
 
 /// Handle OPTIONS request for CORS preflight
 async fn handle_options() -> impl IntoResponse {
-    let mut headers = HeaderMap::new();
-    add_cors_headers(&mut headers);
-    headers.insert("Access-Control-Max-Age", HeaderValue::from_static("86400"));
-
-    (StatusCode::OK, headers, "")
+    StatusCode::OK
 }
@@ -0,0 +1,265 @@
+//! Runtime tool registry.
+//!
+//! Tools registered with [`ServerBuilder::tool`](crate::server::ServerBuilder::tool)
+//! seed a [`ToolRegistry`] that stays mutable after the server is built.
+//! [`Server::tools`](crate::Server::tools) returns a cloneable handle, so
+//! plugins can add, remove, or swap tools while clients are connected. Every
+//! change sends `notifications/tools/list_changed` to the client once the
+//! server is running.
+//!
+//! Servers that start without tools and register them later should call
+//! [`ServerBuilder::dynamic_tools`](crate::server::ServerBuilder::dynamic_tools)
+//! so the `tools` capability is advertised with `listChanged`.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use async_trait::async_trait;
+//! use pmcp::{RequestHandlerExtra, Server, ToolHandler};
+//! use serde_json::{json, Value};
+//!
+//! struct Echo;
+//!
+//! #[async_trait]
+//! impl ToolHandler for Echo {
+//!     async fn handle(&self, args: Value, _extra: RequestHandlerExtra) -> pmcp::Result<Value> {
+//!         Ok(json!({"echo": args}))
+//!     }
+//! }
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let server = Server::builder()
+//!     .name("pluggable")
+//!     .version("1.0.0")
+//!     .dynamic_tools()
+//!     .build()?;
+//!
+//! let tools = server.tools().clone();
+//! tokio::spawn(server.run_stdio());
+//!
+//! // Later, e.g. when a plugin loads:
+//! tools.register("echo", Echo).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::server::ToolHandler;
+use crate::types::{Notification, ServerNotification, ToolInfo};
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Shared, mutable set of tool handlers.
+///
+/// Cloning the registry yields another handle to the same tools.
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: Arc<RwLock<HashMap<String, Arc<dyn ToolHandler>>>>,
+    /// Sender for change notifications, set once the server runs
+    notifier: Arc<Mutex<Option<mpsc::Sender<Notification>>>>,
+}
+
+impl fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.names())
+            .finish()
+    }
+}
+
+impl ToolRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn from_map(tools: HashMap<String, Arc<dyn ToolHandler>>) -> Self {
+        Self {
+            tools: Arc::new(RwLock::new(tools)),
+            notifier: Arc::default(),
+        }
+    }
+
+    /// Send change notifications through `tx` from now on.
+    pub(crate) fn attach(&self, tx: mpsc::Sender<Notification>) {
+        *self.notifier.lock() = Some(tx);
+    }
+
+    /// Add a tool.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if a tool with this name is already
+    /// registered; use [`replace`](Self::replace) to swap it.
+    pub async fn register(
+        &self,
+        name: impl Into<String>,
+        handler: impl ToolHandler + 'static,
+    ) -> Result<()> {
+        self.register_arc(name, Arc::new(handler)).await
+    }
+
+    /// Add a shared tool handler. See [`register`](Self::register).
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if a tool with this name is already
+    /// registered.
+    pub async fn register_arc(
+        &self,
+        name: impl Into<String>,
+        handler: Arc<dyn ToolHandler>,
+    ) -> Result<()> {
+        let name = name.into();
+        {
+            let mut tools = self.tools.write();
+            if tools.contains_key(&name) {
+                return Err(Error::validation(format!(
+                    "Tool '{}' is already registered",
+                    name
+                )));
+            }
+            tools.insert(name, handler);
+        }
+        self.notify_changed().await;
+        Ok(())
+    }
+
+    /// Remove a tool, returning its handler if it was registered.
+    ///
+    /// Calls already in progress finish with the removed handler.
+    pub async fn unregister(&self, name: &str) -> Option<Arc<dyn ToolHandler>> {
+        let removed = self.tools.write().remove(name);
+        if removed.is_some() {
+            self.notify_changed().await;
+        }
+        removed
+    }
+
+    /// Add a tool or swap the handler of an existing one, returning the
+    /// previous handler.
+    pub async fn replace(
+        &self,
+        name: impl Into<String>,
+        handler: impl ToolHandler + 'static,
+    ) -> Option<Arc<dyn ToolHandler>> {
+        let previous = self
+            .tools
+            .write()
+            .insert(name.into(), Arc::new(handler) as Arc<dyn ToolHandler>);
+        self.notify_changed().await;
+        previous
+    }
+
+    /// Get the handler for a tool.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn ToolHandler>> {
+        self.tools.read().get(name).cloned()
+    }
+
+    /// Check if a tool is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.read().contains_key(name)
+    }
+
+    /// Names of the registered tools, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.tools.read().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Number of registered tools.
+    pub fn len(&self) -> usize {
+        self.tools.read().len()
+    }
+
+    /// Whether no tools are registered.
+    pub fn is_empty(&self) -> bool {
+        self.tools.read().is_empty()
+    }
+
+    /// The registered tools and their handlers.
+    pub(crate) fn entries(&self) -> Vec<(String, Arc<dyn ToolHandler>)> {
+        self.tools
+            .read()
+            .iter()
+            .map(|(name, handler)| (name.clone(), handler.clone()))
+            .collect()
+    }
+
+    /// Metadata of every tool, as returned by `tools/list`.
+    pub(crate) fn list(&self) -> Vec<ToolInfo> {
+        self.tools
+            .read()
+            .iter()
+            .map(|(name, handler)| {
+                // Try to get metadata from the handler, otherwise use defaults
+                handler.metadata().unwrap_or_else(|| ToolInfo {
+                    name: name.clone(),
+                    description: None,
+                    input_schema: serde_json::json!({
+                        "type": "object",
+                        "properties": {}
+                    }),
+                    output_schema: None,
+                    annotations: None,
+                })
+            })
+            .collect()
+    }
+
+    async fn notify_changed(&self) {
+        let tx = self.notifier.lock().clone();
+        if let Some(tx) = tx {
+            let _ = tx
+                .send(Notification::Server(ServerNotification::ToolsChanged))
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::cancellation::RequestHandlerExtra;
+    use async_trait::async_trait;
+    use serde_json::Value;
+
+    struct Fixed(&'static str);
+
+    #[async_trait]
+    impl ToolHandler for Fixed {
+        async fn handle(&self, _args: Value, _extra: RequestHandlerExtra) -> Result<Value> {
+            Ok(Value::from(self.0))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_changes_notify_once_attached() {
+        let registry = ToolRegistry::new();
+        registry.register("a", Fixed("a")).await.unwrap();
+
+        let (tx, mut rx) = mpsc::channel(8);
+        registry.clone().attach(tx);
+
+        assert!(registry.register("a", Fixed("again")).await.is_err());
+        registry.register("b", Fixed("b")).await.unwrap();
+        assert!(registry.replace("a", Fixed("a2")).await.is_some());
+        assert!(registry.unregister("b").await.is_some());
+        assert!(registry.unregister("missing").await.is_none());
+
+        assert_eq!(registry.names(), ["a"]);
+        let mut sent = 0;
+        while let Ok(notification) = rx.try_recv() {
+            assert!(matches!(
+                notification,
+                Notification::Server(ServerNotification::ToolsChanged)
+            ));
+            sent += 1;
+        }
+        assert_eq!(sent, 3);
+    }
+}
@@ -0,0 +1,474 @@
+//! Build-time validation of tool registrations.
+//!
+//! [`ServerBuilder::build`](super::ServerBuilder::build) runs these checks so
+//! that broken tool metadata is reported when the server is assembled rather
+//! than discovered later by a client or conformance tester:
+//!
+//! - tool names must be unique, both as registered and as advertised
+//! - tools should have a non-empty description (warning)
+//! - input and output schemas must be syntactically valid JSON Schema
+//!
+//! With the `validation` feature enabled, schemas are additionally checked
+//! against the JSON Schema meta-schema.
+//!
+//! [`check_tool_output`] applies at call time instead: it checks a tool's
+//! result against the output schema the tool advertises.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::server::tool_validation::{validate_tool_info, DiagnosticSeverity};
+//! use pmcp::types::ToolInfo;
+//! use serde_json::json;
+//!
+//! let info = ToolInfo {
+//!     name: "search".to_string(),
+//!     description: None,
+//!     input_schema: json!({"type": "object", "required": "query"}),
+//!     output_schema: None,
+//!     annotations: None,
+//! };
+//!
+//! let diagnostics = validate_tool_info("search", &info);
+//! assert!(diagnostics
+//!     .iter()
+//!     .any(|d| d.severity == DiagnosticSeverity::Warning));
+//! assert!(diagnostics
+//!     .iter()
+//!     .any(|d| d.severity == DiagnosticSeverity::Error));
+//! ```
+
+use crate::error::{Error, Result};
+use crate::types::ToolInfo;
+use crate::utils::validation::schema_violations;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// JSON Schema primitive type names.
+const SCHEMA_TYPES: &[&str] = &[
+    "null", "boolean", "object", "array", "number", "string", "integer",
+];
+
+/// Keywords whose value is a single subschema.
+const SUBSCHEMA_KEYWORDS: &[&str] = &[
+    "additionalProperties",
+    "additionalItems",
+    "contains",
+    "propertyNames",
+    "not",
+    "if",
+    "then",
+    "else",
+];
+
+/// Keywords whose value maps names to subschemas.
+const SCHEMA_MAP_KEYWORDS: &[&str] = &["properties", "patternProperties", "$defs", "definitions"];
+
+/// Keywords whose value is a non-empty array of subschemas.
+const SCHEMA_ARRAY_KEYWORDS: &[&str] = &["allOf", "anyOf", "oneOf"];
+
+/// Keywords whose value must be a number.
+const NUMBER_KEYWORDS: &[&str] = &[
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+];
+
+/// Keywords whose value must be a non-negative integer.
+const COUNT_KEYWORDS: &[&str] = &[
+    "minLength",
+    "maxLength",
+    "minItems",
+    "maxItems",
+    "minProperties",
+    "maxProperties",
+];
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagnosticSeverity {
+    /// Metadata works but is likely to confuse clients.
+    Warning,
+    /// Metadata is broken and the server should not be built.
+    Error,
+}
+
+/// A problem found in a tool registration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolDiagnostic {
+    /// Name the tool was registered under
+    pub tool: String,
+    /// How serious the problem is
+    pub severity: DiagnosticSeverity,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl ToolDiagnostic {
+    fn error(tool: &str, message: impl Into<String>) -> Self {
+        Self {
+            tool: tool.to_string(),
+            severity: DiagnosticSeverity::Error,
+            message: message.into(),
+        }
+    }
+
+    fn warning(tool: &str, message: impl Into<String>) -> Self {
+        Self {
+            tool: tool.to_string(),
+            severity: DiagnosticSeverity::Warning,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ToolDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            DiagnosticSeverity::Warning => "warning",
+            DiagnosticSeverity::Error => "error",
+        };
+        write!(f, "{} in tool '{}': {}", severity, self.tool, self.message)
+    }
+}
+
+/// Validate a set of registered tools.
+///
+/// `tools` maps registration names to the metadata the tool advertises (or
+/// `None` if it advertises none), and `duplicates` lists names that were
+/// registered more than once.
+pub fn validate_tools<'a>(
+    tools: impl IntoIterator<Item = (&'a str, Option<ToolInfo>)>,
+    duplicates: &[String],
+) -> Vec<ToolDiagnostic> {
+    let mut diagnostics: Vec<ToolDiagnostic> = duplicates
+        .iter()
+        .map(|name| {
+            ToolDiagnostic::error(
+                name,
+                "registered more than once; later registrations replace earlier ones",
+            )
+        })
+        .collect();
+
+    let mut advertised: HashMap<String, Vec<&str>> = HashMap::new();
+    for (name, info) in tools {
+        match info {
+            Some(info) => {
+                diagnostics.extend(validate_tool_info(name, &info));
+                advertised.entry(info.name).or_default().push(name);
+            },
+            None => advertised.entry(name.to_string()).or_default().push(name),
+        }
+    }
+
+    let mut clashes: Vec<_> = advertised
+        .into_iter()
+        .filter(|(_, registered)| registered.len() > 1)
+        .collect();
+    clashes.sort();
+    for (advertised_name, mut registered) in clashes {
+        registered.sort_unstable();
+        diagnostics.push(ToolDiagnostic::error(
+            registered[0],
+            format!(
+                "advertised name '{}' is shared by tools {}",
+                advertised_name,
+                registered.join(", ")
+            ),
+        ));
+    }
+
+    diagnostics
+}
+
+/// Validate the metadata a single tool advertises.
+pub fn validate_tool_info(tool: &str, info: &ToolInfo) -> Vec<ToolDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if info.name.trim().is_empty() {
+        diagnostics.push(ToolDiagnostic::error(tool, "advertised name is empty"));
+    }
+    if info
+        .description
+        .as_deref()
+        .is_none_or(|d| d.trim().is_empty())
+    {
+        diagnostics.push(ToolDiagnostic::warning(tool, "description is empty"));
+    }
+
+    if !info.input_schema.is_object() {
+        diagnostics.push(ToolDiagnostic::error(
+            tool,
+            "input schema must be a JSON object",
+        ));
+        return diagnostics;
+    }
+
+    let schemas = std::iter::once(("input", &info.input_schema))
+        .chain(info.output_schema.as_ref().map(|schema| ("output", schema)));
+    for (kind, schema) in schemas {
+        let mut problems = Vec::new();
+        check_schema(schema, "#", &mut problems);
+        #[cfg(feature = "validation")]
+        if problems.is_empty() {
+            if let Err(e) = jsonschema::meta::validate(schema) {
+                problems.push(format!("#: {}", e));
+            }
+        }
+        diagnostics.extend(
+            problems
+                .into_iter()
+                .map(|p| ToolDiagnostic::error(tool, format!("invalid {} schema at {}", kind, p))),
+        );
+    }
+
+    diagnostics
+}
+
+/// Check a tool's result against the output schema it advertises.
+///
+/// Tools without an output schema always pass. Mismatches are reported as
+/// an internal error naming each offending location.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::server::tool_validation::check_tool_output;
+/// use pmcp::types::ToolInfo;
+/// use serde_json::json;
+///
+/// let info = ToolInfo {
+///     name: "add".to_string(),
+///     description: None,
+///     input_schema: json!({"type": "object"}),
+///     output_schema: Some(json!({"type": "object", "required": ["sum"]})),
+///     annotations: None,
+/// };
+///
+/// assert!(check_tool_output("add", &info, &json!({"sum": 3})).is_ok());
+/// let err = check_tool_output("add", &info, &json!({"total": 3})).unwrap_err();
+/// assert!(err.to_string().contains("'sum'"));
+/// ```
+pub fn check_tool_output(tool: &str, info: &ToolInfo, output: &Value) -> Result<()> {
+    let Some(schema) = &info.output_schema else {
+        return Ok(());
+    };
+    let problems = schema_violations(schema, output);
+    if problems.is_empty() {
+        return Ok(());
+    }
+    tracing::warn!("Tool '{}' returned output not matching its schema", tool);
+    Err(Error::internal(format!(
+        "Output of tool '{}' does not match its output schema: {}",
+        tool,
+        problems.join("; ")
+    )))
+}
+
+/// Collect structural problems in a schema and its subschemas.
+fn check_schema(schema: &Value, path: &str, problems: &mut Vec<String>) {
+    let map = match schema {
+        Value::Bool(_) => return,
+        Value::Object(map) => map,
+        other => {
+            problems.push(format!(
+                "{}: schema must be an object or boolean, got {}",
+                path, other
+            ));
+            return;
+        },
+    };
+
+    if let Some(ty) = map.get("type") {
+        let valid = match ty {
+            Value::String(t) => SCHEMA_TYPES.contains(&t.as_str()),
+            Value::Array(types) => {
+                !types.is_empty()
+                    && types
+                        .iter()
+                        .all(|t| t.as_str().is_some_and(|t| SCHEMA_TYPES.contains(&t)))
+            },
+            _ => false,
+        };
+        if !valid {
+            problems.push(format!("{}/type: unknown type {}", path, ty));
+        }
+    }
+
+    if let Some(required) = map.get("required") {
+        let names: Option<Vec<&str>> = required
+            .as_array()
+            .and_then(|items| items.iter().map(Value::as_str).collect());
+        match names {
+            Some(names)
+                if names
+                    .iter()
+                    .enumerate()
+                    .all(|(i, n)| !names[..i].contains(n)) => {},
+            _ => problems.push(format!(
+                "{}/required: must be an array of unique strings",
+                path
+            )),
+        }
+    }
+
+    if map.get("enum").is_some_and(|e| !e.is_array()) {
+        problems.push(format!("{}/enum: must be an array", path));
+    }
+
+    if let Some(pattern) = map.get("pattern") {
+        match pattern.as_str() {
+            Some(p) => {
+                if let Err(e) = regex::Regex::new(p) {
+                    problems.push(format!("{}/pattern: invalid regex: {}", path, e));
+                }
+            },
+            None => problems.push(format!("{}/pattern: must be a string", path)),
+        }
+    }
+
+    for keyword in NUMBER_KEYWORDS {
+        if map.get(*keyword).is_some_and(|v| !v.is_number()) {
+            problems.push(format!("{}/{}: must be a number", path, keyword));
+        }
+    }
+    for keyword in COUNT_KEYWORDS {
+        if map.get(*keyword).is_some_and(|v| !v.is_u64()) {
+            problems.push(format!(
+                "{}/{}: must be a non-negative integer",
+                path, keyword
+            ));
+        }
+    }
+
+    for keyword in SUBSCHEMA_KEYWORDS {
+        if let Some(sub) = map.get(*keyword) {
+            check_schema(sub, &format!("{}/{}", path, keyword), problems);
+        }
+    }
+    for keyword in SCHEMA_MAP_KEYWORDS {
+        match map.get(*keyword) {
+            None => {},
+            Some(Value::Object(subs)) => {
+                for (name, sub) in subs {
+                    check_schema(sub, &format!("{}/{}/{}", path, keyword, name), problems);
+                }
+            },
+            Some(_) => problems.push(format!("{}/{}: must be an object", path, keyword)),
+        }
+    }
+    for keyword in SCHEMA_ARRAY_KEYWORDS {
+        match map.get(*keyword) {
+            None => {},
+            Some(Value::Array(subs)) if !subs.is_empty() => {
+                for (i, sub) in subs.iter().enumerate() {
+                    check_schema(sub, &format!("{}/{}/{}", path, keyword, i), problems);
+                }
+            },
+            Some(_) => problems.push(format!("{}/{}: must be a non-empty array", path, keyword)),
+        }
+    }
+    match map.get("items") {
+        None => {},
+        Some(Value::Array(subs)) => {
+            for (i, sub) in subs.iter().enumerate() {
+                check_schema(sub, &format!("{}/items/{}", path, i), problems);
+            }
+        },
+        Some(sub) => check_schema(sub, &format!("{}/items", path), problems),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn info(name: &str, description: Option<&str>, input_schema: Value) -> ToolInfo {
+        ToolInfo {
+            name: name.to_string(),
+            description: description.map(str::to_string),
+            input_schema,
+            output_schema: None,
+            annotations: None,
+        }
+    }
+
+    fn errors(diagnostics: &[ToolDiagnostic]) -> Vec<&str> {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == DiagnosticSeverity::Error)
+            .map(|d| d.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_tool_has_no_diagnostics() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "query": {"type": "string", "minLength": 1, "pattern": "^[a-z]+$"},
+                "limit": {"type": ["integer", "null"], "minimum": 0},
+                "tags": {"type": "array", "items": {"type": "string"}}
+            },
+            "required": ["query"]
+        });
+        assert!(validate_tool_info("search", &info("search", Some("Search"), schema)).is_empty());
+    }
+
+    #[test]
+    fn test_missing_description_is_a_warning() {
+        let diagnostics = validate_tool_info(
+            "search",
+            &info("search", Some("  "), json!({"type": "object"})),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+    }
+
+    #[test]
+    fn test_invalid_schemas_are_errors() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "a": {"type": "text"},
+                "b": {"type": "string", "pattern": "("},
+                "c": {"anyOf": []}
+            },
+            "required": ["a", "a"]
+        });
+        let diagnostics = validate_tool_info("t", &info("t", Some("T"), schema));
+        let found = errors(&diagnostics);
+        assert!(found.iter().any(|e| e.contains("#/properties/a/type")));
+        assert!(found.iter().any(|e| e.contains("#/properties/b/pattern")));
+        assert!(found.iter().any(|e| e.contains("#/properties/c/anyOf")));
+        assert!(found.iter().any(|e| e.contains("#/required")));
+
+        let diagnostics = validate_tool_info("t", &info("t", Some("T"), json!("object")));
+        assert_eq!(errors(&diagnostics).len(), 1);
+    }
+
+    #[test]
+    fn test_duplicate_and_clashing_names_are_errors() {
+        let tools = vec![
+            (
+                "a",
+                Some(info("shared", Some("A"), json!({"type": "object"}))),
+            ),
+            (
+                "b",
+                Some(info("shared", Some("B"), json!({"type": "object"}))),
+            ),
+            ("c", None),
+        ];
+        let diagnostics = validate_tools(tools, &["c".to_string()]);
+        let found = errors(&diagnostics);
+        assert_eq!(found.len(), 2);
+        assert!(found[0].contains("registered more than once"));
+        assert!(found[1].contains("'shared' is shared by tools a, b"));
+    }
+}
@@ -1,13 +1,27 @@
 //! Server-specific transport implementations.
 
+#[cfg(feature = "streamable-http")]
+pub mod sse;
+
+#[cfg(all(feature = "tcp", not(target_arch = "wasm32")))]
+pub mod tcp;
+
 #[cfg(feature = "websocket")]
 pub mod websocket;
 
 #[cfg(feature = "websocket")]
 pub mod websocket_enhanced;
 
+#[cfg(feature = "streamable-http")]
+pub use sse::{SseServerBuilder, SseServerConfig, SseServerTransport};
+
+#[cfg(all(feature = "tcp", not(target_arch = "wasm32")))]
+pub use tcp::TcpServerListener;
+
 #[cfg(feature = "websocket")]
 pub use websocket::{WebSocketServerBuilder, WebSocketServerConfig, WebSocketServerTransport};
 
 #[cfg(feature = "websocket")]
-pub use websocket_enhanced::{ClientId, EnhancedWebSocketConfig, EnhancedWebSocketServer};
+pub use websocket_enhanced::{
+    ClientId, ConnectedClients, EnhancedWebSocketConfig, EnhancedWebSocketServer,
+};
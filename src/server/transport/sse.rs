@@ -0,0 +1,603 @@
+//! HTTP with Server-Sent Events server transport.
+//!
+//! Implements the classic MCP SSE transport used by clients that predate
+//! streamable HTTP: the client opens a `GET` event stream, receives an
+//! `endpoint` event naming the URL to `POST` its messages to, and reads
+//! server messages as `message` events on the stream.
+//!
+//! Like [`WebSocketServerTransport`](super::WebSocketServerTransport), the
+//! transport serves one client at a time. A new event stream replaces the
+//! previous one, and messages posted for a replaced session are rejected.
+//! Requests from origins the [`OriginPolicy`] does not allow are rejected
+//! with `403 Forbidden`.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::transport::SseServerBuilder;
+//! use pmcp::Server;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let server = Server::builder().name("legacy").version("1.0.0").build()?;
+//!
+//! let transport = SseServerBuilder::new()
+//!     .bind_addr("0.0.0.0:8080".parse().unwrap())
+//!     .sse_path("/events")
+//!     .message_path("/messages")
+//!     .keep_alive(Duration::from_secs(30))
+//!     .build();
+//!
+//! server.run_sse(transport).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result, TransportError};
+use crate::server::auth::oauth2::{protected_resource_routes, ProtectedResourceMetadata};
+#[cfg(feature = "prometheus")]
+use crate::server::metrics::{metrics_routes, ServerMetrics};
+use crate::server::origin::OriginPolicy;
+use crate::shared::{MessageSizeLimits, Transport, TransportMessage};
+use async_trait::async_trait;
+use axum::{
+    extract::{DefaultBodyLimit, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse, Response, Sse,
+    },
+    routing::{get, post},
+    Router,
+};
+use parking_lot::RwLock;
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::StreamExt;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Configuration for the SSE server transport.
+#[derive(Debug, Clone)]
+pub struct SseServerConfig {
+    /// Address to bind to
+    pub bind_addr: SocketAddr,
+    /// Path clients open the event stream on
+    pub sse_path: String,
+    /// Path clients post messages to
+    pub message_path: String,
+    /// Interval between keep-alive comments on an idle stream, if any
+    pub keep_alive: Option<Duration>,
+    /// Protected resource metadata (RFC 9728) to serve at
+    /// `/.well-known/oauth-protected-resource`, if any
+    pub protected_resource: Option<ProtectedResourceMetadata>,
+    /// Metrics to serve at `/metrics`, if any
+    #[cfg(feature = "prometheus")]
+    pub metrics: Option<Arc<ServerMetrics>>,
+    /// Limits on posted messages
+    pub message_size_limits: MessageSizeLimits,
+    /// Origins and hosts requests are accepted for, against DNS rebinding
+    pub origin_policy: OriginPolicy,
+}
+
+impl Default for SseServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_addr: "127.0.0.1:8080".parse().expect("Valid default address"),
+            sse_path: "/sse".to_string(),
+            message_path: "/message".to_string(),
+            keep_alive: Some(Duration::from_secs(15)),
+            protected_resource: None,
+            #[cfg(feature = "prometheus")]
+            metrics: None,
+            message_size_limits: MessageSizeLimits::default(),
+            origin_policy: OriginPolicy::default(),
+        }
+    }
+}
+
+/// The connected client's event stream.
+#[derive(Debug)]
+struct Session {
+    id: String,
+    outgoing: mpsc::UnboundedSender<TransportMessage>,
+}
+
+/// State shared with the HTTP handlers.
+#[derive(Debug)]
+struct Shared {
+    config: SseServerConfig,
+    incoming: mpsc::Sender<TransportMessage>,
+    session: RwLock<Option<Session>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionQuery {
+    #[serde(rename = "sessionId")]
+    session_id: String,
+}
+
+/// SSE server transport that accepts a client over HTTP.
+pub struct SseServerTransport {
+    shared: Arc<Shared>,
+    incoming: Mutex<mpsc::Receiver<TransportMessage>>,
+    local_addr: Option<SocketAddr>,
+    server_task: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl SseServerTransport {
+    /// Create a new SSE server transport with the given configuration.
+    pub fn new(config: SseServerConfig) -> Self {
+        let (incoming_tx, incoming_rx) = mpsc::channel(100);
+        Self {
+            shared: Arc::new(Shared {
+                config,
+                incoming: incoming_tx,
+                session: RwLock::new(None),
+            }),
+            incoming: Mutex::new(incoming_rx),
+            local_addr: None,
+            server_task: None,
+        }
+    }
+
+    /// Create a builder for the transport.
+    pub fn builder() -> SseServerBuilder {
+        SseServerBuilder::new()
+    }
+
+    /// Bind and start serving the SSE and message endpoints.
+    ///
+    /// Returns the bound address, which differs from the configured one when
+    /// binding to port 0.
+    pub async fn bind(&mut self) -> Result<SocketAddr> {
+        let config = &self.shared.config;
+        let listener = tokio::net::TcpListener::bind(config.bind_addr)
+            .await
+            .map_err(|e| {
+                Error::internal(format!("Failed to bind to {}: {}", config.bind_addr, e))
+            })?;
+        let local_addr = listener.local_addr()?;
+
+        let mut app = Router::new()
+            .route(&config.sse_path, get(handle_sse))
+            .route(&config.message_path, post(handle_message))
+            .with_state(Arc::clone(&self.shared));
+        if let Some(metadata) = &config.protected_resource {
+            app = app.merge(protected_resource_routes(metadata));
+        }
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = &config.metrics {
+            app = app.merge(metrics_routes(Arc::clone(metrics), None));
+        }
+        // Refuse to buffer bodies far beyond the inbound limit
+        if let Some(limit) = config.message_size_limits.inbound_close_threshold() {
+            app = app.layer(DefaultBodyLimit::max(limit));
+        }
+
+        info!("SSE server listening on {}", local_addr);
+        config.origin_policy.warn_if_exposed(local_addr);
+        self.server_task = Some(tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                warn!("SSE server stopped: {}", e);
+            }
+        }));
+        self.local_addr = Some(local_addr);
+        Ok(local_addr)
+    }
+
+    /// Address the transport is serving on, once bound.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Whether [`bind`](Self::bind) has been called.
+    pub fn is_bound(&self) -> bool {
+        self.server_task.is_some()
+    }
+
+    /// Session ID of the connected client, if any.
+    pub fn session_id(&self) -> Option<String> {
+        self.shared
+            .session
+            .read()
+            .as_ref()
+            .map(|session| session.id.clone())
+    }
+}
+
+/// Reject requests from origins or for hosts the policy does not allow.
+fn check_origin(shared: &Shared, headers: &HeaderMap) -> std::result::Result<(), Response> {
+    shared
+        .config
+        .origin_policy
+        .check(headers)
+        .map_err(|reason| {
+            debug!("Rejecting SSE request: {}", reason);
+            (StatusCode::FORBIDDEN, reason).into_response()
+        })
+}
+
+/// Open an event stream and announce the message endpoint.
+async fn handle_sse(State(shared): State<Arc<Shared>>, headers: HeaderMap) -> Response {
+    if let Err(response) = check_origin(&shared, &headers) {
+        return response;
+    }
+    let session_id = Uuid::new_v4().to_string();
+    let (outgoing_tx, outgoing_rx) = mpsc::unbounded_channel();
+    // Replacing the session drops the previous sender, ending its stream
+    let _replaced = shared.session.write().replace(Session {
+        id: session_id.clone(),
+        outgoing: outgoing_tx,
+    });
+    #[cfg(feature = "prometheus")]
+    if let (Some(metrics), None) = (&shared.config.metrics, _replaced) {
+        metrics.session_opened();
+    }
+    info!("SSE client connected with session {}", session_id);
+
+    let endpoint = format!("{}?sessionId={}", shared.config.message_path, session_id);
+    let messages = UnboundedReceiverStream::new(outgoing_rx).filter_map(|message| {
+        match crate::shared::StdioTransport::serialize_message(&message)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| String::from_utf8(bytes).map_err(|e| e.to_string()))
+        {
+            Ok(json) => Some(Ok::<_, Infallible>(
+                Event::default().event("message").data(json),
+            )),
+            Err(e) => {
+                warn!("Failed to serialize SSE message: {}", e);
+                None
+            },
+        }
+    });
+    let stream =
+        tokio_stream::once(Ok(Event::default().event("endpoint").data(endpoint))).chain(messages);
+
+    match shared.config.keep_alive {
+        Some(interval) => Sse::new(stream)
+            .keep_alive(KeepAlive::new().interval(interval))
+            .into_response(),
+        None => Sse::new(stream).into_response(),
+    }
+}
+
+/// Accept a message posted by the connected client.
+async fn handle_message(
+    State(shared): State<Arc<Shared>>,
+    Query(query): Query<SessionQuery>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    if let Err(response) = check_origin(&shared, &headers) {
+        return response;
+    }
+    let current = shared
+        .session
+        .read()
+        .as_ref()
+        .is_some_and(|session| session.id == query.session_id);
+    if !current {
+        return (StatusCode::NOT_FOUND, "Session not found").into_response();
+    }
+
+    let limits = shared.config.message_size_limits;
+    if let Err(e) = limits.check_inbound(body.len()) {
+        return (StatusCode::PAYLOAD_TOO_LARGE, e.to_string()).into_response();
+    }
+    if let Err(e) = limits.check_inbound_structure(body.as_bytes()) {
+        return (StatusCode::BAD_REQUEST, e.to_string()).into_response();
+    }
+
+    let message = match crate::shared::StdioTransport::parse_message(body.as_bytes()) {
+        Ok(message) => message,
+        Err(e) => {
+            debug!("Rejecting malformed SSE message: {}", e);
+            return (StatusCode::BAD_REQUEST, e.to_string()).into_response();
+        },
+    };
+
+    if shared.incoming.send(message).await.is_err() {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Server is shutting down").into_response();
+    }
+    StatusCode::ACCEPTED.into_response()
+}
+
+#[async_trait]
+impl Transport for SseServerTransport {
+    async fn send(&mut self, message: TransportMessage) -> Result<()> {
+        let session = self.shared.session.read();
+        let session = session
+            .as_ref()
+            .ok_or(Error::Transport(TransportError::ConnectionClosed))?;
+        session
+            .outgoing
+            .send(message)
+            .map_err(|e| Error::Transport(TransportError::Send(e.to_string())))
+    }
+
+    async fn receive(&mut self) -> Result<TransportMessage> {
+        self.incoming
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or(Error::Transport(TransportError::ConnectionClosed))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if let Some(task) = self.server_task.take() {
+            task.abort();
+        }
+        let _closed = self.shared.session.write().take();
+        #[cfg(feature = "prometheus")]
+        if let (Some(metrics), Some(_)) = (&self.shared.config.metrics, _closed) {
+            metrics.session_closed();
+        }
+
+        info!("SSE server transport closed");
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.shared
+            .session
+            .read()
+            .as_ref()
+            .is_some_and(|session| !session.outgoing.is_closed())
+    }
+
+    fn transport_type(&self) -> &'static str {
+        "sse-server"
+    }
+}
+
+impl std::fmt::Debug for SseServerTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SseServerTransport")
+            .field("config", &self.shared.config)
+            .field("local_addr", &self.local_addr)
+            .field("session_id", &self.session_id())
+            .finish()
+    }
+}
+
+/// Builder for the SSE server transport.
+#[derive(Debug, Default)]
+pub struct SseServerBuilder {
+    config: SseServerConfig,
+}
+
+impl SseServerBuilder {
+    /// Create a new builder with default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bind address.
+    pub fn bind_addr(mut self, addr: SocketAddr) -> Self {
+        self.config.bind_addr = addr;
+        self
+    }
+
+    /// Set the path of the event stream endpoint.
+    pub fn sse_path(mut self, path: impl Into<String>) -> Self {
+        self.config.sse_path = path.into();
+        self
+    }
+
+    /// Set the path of the message endpoint.
+    pub fn message_path(mut self, path: impl Into<String>) -> Self {
+        self.config.message_path = path.into();
+        self
+    }
+
+    /// Set the keep-alive interval for idle streams.
+    pub fn keep_alive(mut self, interval: Duration) -> Self {
+        self.config.keep_alive = Some(interval);
+        self
+    }
+
+    /// Disable keep-alive comments.
+    pub fn no_keep_alive(mut self) -> Self {
+        self.config.keep_alive = None;
+        self
+    }
+
+    /// Serve protected resource metadata so clients can discover the
+    /// authorization server.
+    pub fn protected_resource(mut self, metadata: ProtectedResourceMetadata) -> Self {
+        self.config.protected_resource = Some(metadata);
+        self
+    }
+
+    /// Set the limits on posted messages.
+    pub fn message_size_limits(mut self, limits: MessageSizeLimits) -> Self {
+        self.config.message_size_limits = limits;
+        self
+    }
+
+    /// Set the origins and hosts requests are accepted for.
+    pub fn origin_policy(mut self, policy: OriginPolicy) -> Self {
+        self.config.origin_policy = policy;
+        self
+    }
+
+    /// Serve `metrics` at `/metrics` and count the client's session in
+    /// them.
+    #[cfg(feature = "prometheus")]
+    pub fn metrics(mut self, metrics: Arc<ServerMetrics>) -> Self {
+        self.config.metrics = Some(metrics);
+        self
+    }
+
+    /// Build the transport.
+    pub fn build(self) -> SseServerTransport {
+        SseServerTransport::new(self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ClientRequest, JSONRPCResponse, Request, RequestId};
+    use serde_json::json;
+
+    /// Read from an event stream until `needle` has been received.
+    async fn read_until(response: &mut reqwest::Response, buffer: &mut String, needle: &str) {
+        while !buffer.contains(needle) {
+            let chunk = response.chunk().await.unwrap().expect("stream ended");
+            buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_builder() {
+        let transport = SseServerBuilder::new()
+            .bind_addr("127.0.0.1:9003".parse().unwrap())
+            .sse_path("/events")
+            .message_path("/rpc")
+            .no_keep_alive()
+            .build();
+
+        let config = &transport.shared.config;
+        assert_eq!(config.bind_addr.to_string(), "127.0.0.1:9003");
+        assert_eq!(config.sse_path, "/events");
+        assert_eq!(config.message_path, "/rpc");
+        assert!(config.keep_alive.is_none());
+        assert!(!transport.is_bound());
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let mut transport = SseServerBuilder::new()
+            .bind_addr("127.0.0.1:0".parse().unwrap())
+            .build();
+        let addr = transport.bind().await.unwrap();
+        let client = reqwest::Client::new();
+
+        let mut stream = client
+            .get(format!("http://{}/sse", addr))
+            .send()
+            .await
+            .unwrap();
+        let mut events = String::new();
+        read_until(&mut stream, &mut events, "sessionId=").await;
+        read_until(&mut stream, &mut events, "\n\n").await;
+        let endpoint = events
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .unwrap()
+            .to_string();
+        assert!(transport.is_connected());
+
+        let stale = client
+            .post(format!("http://{}/message?sessionId=stale", addr))
+            .body("{}")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(stale.status(), reqwest::StatusCode::NOT_FOUND);
+
+        let accepted = client
+            .post(format!("http://{}{}", addr, endpoint))
+            .body(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), reqwest::StatusCode::ACCEPTED);
+        let received = transport.receive().await.unwrap();
+        assert!(matches!(
+            received,
+            TransportMessage::Request { request: Request::Client(ref r), .. }
+                if matches!(**r, ClientRequest::Ping)
+        ));
+
+        transport
+            .send(TransportMessage::Response(JSONRPCResponse::success(
+                RequestId::from(1i64),
+                json!({}),
+            )))
+            .await
+            .unwrap();
+        read_until(&mut stream, &mut events, "event: message").await;
+        read_until(&mut stream, &mut events, "\"result\"").await;
+
+        transport.close().await.unwrap();
+        assert!(!transport.is_connected());
+    }
+
+    #[tokio::test]
+    async fn test_serves_protected_resource_metadata() {
+        let metadata = ProtectedResourceMetadata::new("http://127.0.0.1/mcp")
+            .authorization_server("https://auth.example.com");
+        let mut transport = SseServerBuilder::new()
+            .bind_addr("127.0.0.1:0".parse().unwrap())
+            .protected_resource(metadata.clone())
+            .build();
+        let addr = transport.bind().await.unwrap();
+
+        for path in metadata.well_known_paths() {
+            let served: ProtectedResourceMetadata =
+                reqwest::get(format!("http://{}{}", addr, path))
+                    .await
+                    .unwrap()
+                    .json()
+                    .await
+                    .unwrap();
+            assert_eq!(served, metadata);
+        }
+        transport.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_foreign_origins() {
+        let mut transport = SseServerBuilder::new()
+            .bind_addr("127.0.0.1:0".parse().unwrap())
+            .origin_policy(OriginPolicy::localhost())
+            .build();
+        let addr = transport.bind().await.unwrap();
+        let client = reqwest::Client::new();
+        let url = format!("http://localhost:{}/sse", addr.port());
+
+        let rebound = client
+            .get(&url)
+            .header("origin", "http://evil.example.com")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(rebound.status(), reqwest::StatusCode::FORBIDDEN);
+        let wrong_host = client
+            .get(&url)
+            .header("host", "rebound.example.com")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(wrong_host.status(), reqwest::StatusCode::FORBIDDEN);
+        let message = client
+            .post(format!(
+                "http://localhost:{}/message?sessionId=x",
+                addr.port()
+            ))
+            .header("origin", "http://evil.example.com")
+            .body("{}")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(message.status(), reqwest::StatusCode::FORBIDDEN);
+
+        let local = client
+            .get(&url)
+            .header("origin", "http://localhost:3000")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(local.status(), reqwest::StatusCode::OK);
+        transport.close().await.unwrap();
+    }
+}
@@ -0,0 +1,158 @@
+//! TCP listener accepting MCP connections, optionally over TLS.
+//!
+//! Each accepted connection is a [`TcpTransport`] for one client. Servers
+//! keep per-client state, so build one [`Server`](crate::Server) per
+//! connection.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::server::transport::TcpServerListener;
+//! use pmcp::Server;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let listener = TcpServerListener::bind("0.0.0.0:7000").await?;
+//! loop {
+//!     let transport = match listener.accept().await {
+//!         Ok(transport) => transport,
+//!         Err(e) => {
+//!             tracing::warn!("Failed to accept connection: {}", e);
+//!             continue;
+//!         },
+//!     };
+//!     tokio::spawn(async move {
+//!         let server = Server::builder()
+//!             .name("tcp-server")
+//!             .version("1.0.0")
+//!             .build()?;
+//!         server.run(transport).await
+//!     });
+//! }
+//! # }
+//! ```
+
+use crate::error::{Result, TransportError};
+use crate::shared::protocol::MessageSizeLimits;
+use crate::shared::tcp::TcpTransport;
+use std::fmt;
+use std::net::SocketAddr;
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+#[cfg(feature = "tls")]
+use crate::shared::tcp::rustls;
+#[cfg(feature = "tls")]
+use std::sync::Arc;
+#[cfg(feature = "tls")]
+use std::time::Duration;
+
+/// Time a client gets to complete the TLS handshake.
+#[cfg(feature = "tls")]
+pub const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Listens for MCP clients on a TCP socket.
+pub struct TcpServerListener {
+    listener: TcpListener,
+    #[cfg(feature = "tls")]
+    tls: Option<tokio_rustls::TlsAcceptor>,
+    limits: MessageSizeLimits,
+}
+
+impl fmt::Debug for TcpServerListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("TcpServerListener");
+        debug.field("local_addr", &self.listener.local_addr().ok());
+        #[cfg(feature = "tls")]
+        debug.field("tls", &self.tls.is_some());
+        debug.finish_non_exhaustive()
+    }
+}
+
+impl TcpServerListener {
+    /// Listen on `addr`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the address cannot be bound.
+    pub async fn bind(addr: impl ToSocketAddrs) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(TransportError::from)?;
+        Ok(Self {
+            listener,
+            #[cfg(feature = "tls")]
+            tls: None,
+            limits: MessageSizeLimits::default(),
+        })
+    }
+
+    /// Require clients to connect over TLS.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmcp::server::transport::TcpServerListener;
+    /// use pmcp::shared::tcp::tls_server_config;
+    ///
+    /// # async fn example() -> pmcp::Result<()> {
+    /// let config = tls_server_config(
+    ///     &std::fs::read("server.pem")?,
+    ///     &std::fs::read("server.key")?,
+    /// )?;
+    /// let listener = TcpServerListener::bind("0.0.0.0:7443")
+    ///     .await?
+    ///     .with_tls(config);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tls")]
+    pub fn with_tls(mut self, config: Arc<rustls::ServerConfig>) -> Self {
+        self.tls = Some(tokio_rustls::TlsAcceptor::from(config));
+        self
+    }
+
+    /// Enforce limits on the size of messages on accepted connections.
+    pub fn with_message_size_limits(mut self, limits: MessageSizeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Address the listener is bound to.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the socket address cannot be read.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr().map_err(TransportError::from)?)
+    }
+
+    /// Wait for the next client connection.
+    ///
+    /// With TLS, the handshake completes before this returns and fails after
+    /// [`TLS_HANDSHAKE_TIMEOUT`]. Errors concern a single connection, so an
+    /// accept loop should log them and keep accepting.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if accepting or the TLS handshake fails.
+    pub async fn accept(&self) -> Result<TcpTransport> {
+        let (stream, peer_addr) = self.listener.accept().await.map_err(TransportError::from)?;
+
+        #[cfg(feature = "tls")]
+        if let Some(acceptor) = &self.tls {
+            stream.set_nodelay(true).map_err(TransportError::from)?;
+            let stream = tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream))
+                .await
+                .map_err(|_| {
+                    crate::Error::timeout(
+                        u64::try_from(TLS_HANDSHAKE_TIMEOUT.as_millis()).unwrap_or(u64::MAX),
+                    )
+                })?
+                .map_err(TransportError::from)?;
+            return Ok(TcpTransport::from_tls_server_stream(stream, peer_addr)
+                .with_message_size_limits(self.limits));
+        }
+
+        tracing::debug!("Accepted TCP connection from {}", peer_addr);
+        Ok(TcpTransport::from_stream(stream)?.with_message_size_limits(self.limits))
+    }
+}
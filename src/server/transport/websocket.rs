@@ -1,7 +1,8 @@
 //! WebSocket server transport implementation.
 
 use crate::error::{Error, Result};
-use crate::shared::{Transport, TransportMessage};
+use crate::shared::{MessageSizeLimits, StdioTransport, Transport, TransportMessage};
+use crate::utils::batching::{WriteBatcher, WriteBatchingConfig};
 use async_trait::async_trait;
 use futures::{SinkExt, StreamExt};
 use std::net::SocketAddr;
@@ -10,7 +11,8 @@ use std::sync::Arc;
 use tokio::net::TcpListener;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::sync::{mpsc, Mutex};
-use tokio_tungstenite::{accept_async, tungstenite::protocol::Message};
+use tokio_tungstenite::accept_async_with_config;
+use tokio_tungstenite::tungstenite::protocol::{Message, WebSocketConfig};
 use tracing::{error, info, warn};
 
 /// Configuration for WebSocket server transport.
@@ -24,6 +26,12 @@ pub struct WebSocketServerConfig {
     pub max_message_size: Option<usize>,
     /// Whether to accept unmasked frames from clients
     pub accept_unmasked_frames: bool,
+    /// Batching of outgoing messages; `None` writes and flushes each
+    /// message on its own
+    pub write_batching: Option<WriteBatchingConfig>,
+    /// Limits on received messages, answered with an error instead of
+    /// closing the connection like `max_message_size`
+    pub message_size_limits: MessageSizeLimits,
 }
 
 impl Default for WebSocketServerConfig {
@@ -33,6 +41,8 @@ impl Default for WebSocketServerConfig {
             max_frame_size: Some(64 * 1024 * 1024),   // 64MB
             max_message_size: Some(64 * 1024 * 1024), // 64MB
             accept_unmasked_frames: false,
+            write_batching: None,
+            message_size_limits: MessageSizeLimits::default(),
         }
     }
 }
@@ -94,7 +104,11 @@ impl WebSocketServerTransport {
         info!("Accepting WebSocket connection from {}", peer_addr);
 
         // Accept the WebSocket handshake
-        let ws_stream = accept_async(tcp_stream)
+        let ws_config = WebSocketConfig::default()
+            .max_frame_size(self.config.max_frame_size)
+            .max_message_size(self.config.max_message_size)
+            .accept_unmasked_frames(self.config.accept_unmasked_frames);
+        let ws_stream = accept_async_with_config(tcp_stream, Some(ws_config))
             .await
             .map_err(|e| Error::internal(format!("WebSocket handshake failed: {}", e)))?;
 
@@ -105,6 +119,8 @@ impl WebSocketServerTransport {
         let (incoming_tx, incoming_rx) = mpsc::channel::<TransportMessage>(100);
 
         // Store the channels
+        let reject_tx = outgoing_tx.clone();
+        let limits = self.config.message_size_limits;
         *self.incoming_rx.lock().await = Some(incoming_rx);
         *self.outgoing_tx.lock().await = Some(outgoing_tx);
 
@@ -114,28 +130,16 @@ impl WebSocketServerTransport {
         let (mut ws_sink, mut ws_stream) = ws_stream.split();
 
         // Spawn task to handle outgoing messages and pongs
+        let batcher = self.config.write_batching.clone().map(WriteBatcher::new);
         tokio::spawn(async move {
             loop {
                 tokio::select! {
                     Some(msg) = outgoing_rx.recv() => {
-                        let json_bytes = match crate::shared::stdio::StdioTransport::serialize_message(&msg)
-                        {
-                            Ok(bytes) => bytes,
-                            Err(e) => {
-                                error!("Failed to serialize message: {}", e);
-                                continue;
-                            },
-                        };
-
-                        let json = match String::from_utf8(json_bytes) {
-                            Ok(json) => json,
-                            Err(e) => {
-                                error!("Failed to convert to UTF-8: {}", e);
-                                continue;
-                            },
+                        let batch = match &batcher {
+                            Some(batcher) => batcher.gather(msg, &mut outgoing_rx).await,
+                            None => vec![msg],
                         };
-
-                        if let Err(e) = ws_sink.send(Message::Text(json.into())).await {
+                        if let Err(e) = write_batch(&mut ws_sink, batch).await {
                             error!("Failed to send WebSocket message: {}", e);
                             break;
                         }
@@ -156,7 +160,26 @@ impl WebSocketServerTransport {
             while let Some(result) = ws_stream.next().await {
                 match result {
                     Ok(Message::Text(text)) => {
-                        match crate::shared::stdio::StdioTransport::parse_message(text.as_bytes()) {
+                        let checked = limits
+                            .check_inbound(text.len())
+                            .and_then(|()| limits.check_inbound_structure(text.as_bytes()));
+                        if let Err(e) = checked {
+                            if matches!(e, Error::Transport(_)) {
+                                error!("Closing WebSocket connection: {}", e);
+                                break;
+                            }
+                            for response in StdioTransport::rejections(text.as_bytes(), e) {
+                                if reject_tx
+                                    .send(TransportMessage::Response(response))
+                                    .await
+                                    .is_err()
+                                {
+                                    break;
+                                }
+                            }
+                            continue;
+                        }
+                        match StdioTransport::parse_message(text.as_bytes()) {
                             Ok(msg) => {
                                 if let Err(e) = incoming_tx.send(msg).await {
                                     error!("Failed to queue incoming message: {}", e);
@@ -200,6 +223,36 @@ impl WebSocketServerTransport {
     }
 }
 
+/// Write `batch` as text frames and flush once.
+async fn write_batch<S>(
+    sink: &mut S,
+    batch: Vec<TransportMessage>,
+) -> std::result::Result<(), S::Error>
+where
+    S: futures::Sink<Message> + Unpin,
+{
+    for msg in batch {
+        let json_bytes = match crate::shared::stdio::StdioTransport::serialize_message(&msg) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                error!("Failed to serialize message: {}", e);
+                continue;
+            },
+        };
+
+        let json = match String::from_utf8(json_bytes) {
+            Ok(json) => json,
+            Err(e) => {
+                error!("Failed to convert to UTF-8: {}", e);
+                continue;
+            },
+        };
+
+        sink.feed(Message::Text(json.into())).await?;
+    }
+    sink.flush().await
+}
+
 #[async_trait]
 impl Transport for WebSocketServerTransport {
     async fn send(&mut self, message: TransportMessage) -> Result<()> {
@@ -299,6 +352,18 @@ impl WebSocketServerBuilder {
         self
     }
 
+    /// Batch outgoing messages, flushing once per batch.
+    pub fn write_batching(mut self, config: WriteBatchingConfig) -> Self {
+        self.config.write_batching = Some(config);
+        self
+    }
+
+    /// Set the limits on received messages.
+    pub fn message_size_limits(mut self, limits: MessageSizeLimits) -> Self {
+        self.config.message_size_limits = limits;
+        self
+    }
+
     /// Build the transport.
     pub fn build(self) -> WebSocketServerTransport {
         WebSocketServerTransport::new(self.config)
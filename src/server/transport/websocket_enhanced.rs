@@ -8,7 +8,7 @@
 //! - Advanced error recovery
 
 use crate::error::{Error, Result};
-use crate::shared::TransportMessage;
+use crate::shared::{MessageSizeLimits, StdioTransport, TransportMessage};
 use futures::{SinkExt, StreamExt};
 use std::collections::HashMap;
 use std::net::SocketAddr;
@@ -17,7 +17,8 @@ use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::sync::{mpsc, RwLock};
 use tokio::time::{interval, timeout};
-use tokio_tungstenite::{accept_async, tungstenite::protocol::Message};
+use tokio_tungstenite::accept_async_with_config;
+use tokio_tungstenite::tungstenite::protocol::{Message, WebSocketConfig};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -43,6 +44,9 @@ pub struct EnhancedWebSocketConfig {
     pub enable_pooling: bool,
     /// Enable broadcast mode
     pub enable_broadcast: bool,
+    /// Limits on received messages, answered with an error instead of
+    /// closing the connection like `max_message_size`
+    pub message_size_limits: MessageSizeLimits,
 }
 
 impl Default for EnhancedWebSocketConfig {
@@ -56,6 +60,7 @@ impl Default for EnhancedWebSocketConfig {
             max_message_size: Some(64 * 1024 * 1024),
             enable_pooling: true,
             enable_broadcast: false,
+            message_size_limits: MessageSizeLimits::default(),
         }
     }
 }
@@ -71,6 +76,84 @@ struct ClientConnection {
     last_seen: std::time::Instant,
 }
 
+/// Handle for sending to the clients connected to an
+/// [`EnhancedWebSocketServer`].
+///
+/// Clones share the same set of connections, so a handle taken before the
+/// server starts sees every client that connects later.
+#[derive(Clone)]
+pub struct ConnectedClients {
+    clients: Arc<RwLock<HashMap<ClientId, ClientConnection>>>,
+}
+
+impl std::fmt::Debug for ConnectedClients {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectedClients")
+            .field(
+                "clients",
+                &self.clients.try_read().map(|c| c.len()).unwrap_or(0),
+            )
+            .finish()
+    }
+}
+
+impl ConnectedClients {
+    /// IDs of the connected clients.
+    pub async fn ids(&self) -> Vec<ClientId> {
+        self.clients.read().await.keys().copied().collect()
+    }
+
+    /// Whether a client is connected.
+    pub async fn contains(&self, client_id: ClientId) -> bool {
+        self.clients.read().await.contains_key(&client_id)
+    }
+
+    /// Send a message to one client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is not connected.
+    pub async fn send_to(&self, client_id: ClientId, message: TransportMessage) -> Result<()> {
+        // Clone the sender so a slow client does not hold the lock
+        let tx = self
+            .clients
+            .read()
+            .await
+            .get(&client_id)
+            .map(|client| client.tx.clone())
+            .ok_or_else(|| Error::internal(format!("Client {} not found", client_id)))?;
+
+        tx.send(message)
+            .await
+            .map_err(|_| Error::internal("Failed to send to client"))
+    }
+
+    /// Send a message to every connected client.
+    ///
+    /// Returns the number of clients the message was queued for.
+    pub async fn broadcast(&self, message: TransportMessage) -> usize {
+        let senders: Vec<_> = self
+            .clients
+            .read()
+            .await
+            .iter()
+            .map(|(id, client)| (*id, client.tx.clone()))
+            .collect();
+
+        let mut send_count = 0;
+        for (client_id, tx) in senders {
+            if let Err(e) = tx.send(message.clone()).await {
+                warn!("Failed to send to client {}: {}", client_id, e);
+            } else {
+                send_count += 1;
+            }
+        }
+
+        debug!("Broadcast sent to {} clients", send_count);
+        send_count
+    }
+}
+
 /// Enhanced WebSocket server with multi-client support
 pub struct EnhancedWebSocketServer {
     config: EnhancedWebSocketConfig,
@@ -153,6 +236,10 @@ impl EnhancedWebSocketServer {
         let incoming_tx = self.incoming_tx.clone();
         let max_connections = self.config.max_connections;
         let connection_timeout = self.config.connection_timeout;
+        let ws_config = WebSocketConfig::default()
+            .max_frame_size(self.config.max_frame_size)
+            .max_message_size(self.config.max_message_size);
+        let limits = self.config.message_size_limits;
 
         tokio::spawn(async move {
             loop {
@@ -174,7 +261,14 @@ impl EnhancedWebSocketServer {
                         tokio::spawn(async move {
                             match timeout(
                                 connection_timeout,
-                                Self::handle_connection(stream, addr, clients, incoming_tx),
+                                Self::handle_connection(
+                                    stream,
+                                    addr,
+                                    clients,
+                                    incoming_tx,
+                                    ws_config,
+                                    limits,
+                                ),
                             )
                             .await
                             {
@@ -274,9 +368,11 @@ impl EnhancedWebSocketServer {
         addr: SocketAddr,
         clients: Arc<RwLock<HashMap<ClientId, ClientConnection>>>,
         incoming_tx: mpsc::Sender<(ClientId, TransportMessage)>,
+        ws_config: WebSocketConfig,
+        limits: MessageSizeLimits,
     ) -> Result<()> {
         // Perform WebSocket handshake
-        let ws_stream = accept_async(stream)
+        let ws_stream = accept_async_with_config(stream, Some(ws_config))
             .await
             .map_err(|e| Error::internal(format!("WebSocket handshake failed: {}", e)))?;
 
@@ -284,8 +380,7 @@ impl EnhancedWebSocketServer {
         info!("Client {} connected from {}", client_id, addr);
 
         // Setup client connection
-        let (_client_tx, client_rx) = Self::setup_client(client_id, addr, &clients).await;
-        // client_tx is stored in clients map for send_to_client operations
+        let (client_tx, client_rx) = Self::setup_client(client_id, addr, &clients).await;
 
         // Split WebSocket stream
         let (ws_sink, mut ws_stream) = ws_stream.split();
@@ -311,7 +406,27 @@ impl EnhancedWebSocketServer {
                         }
                     }
 
-                    match crate::shared::stdio::StdioTransport::parse_message(text.as_bytes()) {
+                    let checked = limits
+                        .check_inbound(text.len())
+                        .and_then(|()| limits.check_inbound_structure(text.as_bytes()));
+                    if let Err(e) = checked {
+                        if matches!(e, Error::Transport(_)) {
+                            error!("Closing connection from {}: {}", client_id, e);
+                            break;
+                        }
+                        for response in StdioTransport::rejections(text.as_bytes(), e) {
+                            if client_tx
+                                .send(TransportMessage::Response(response))
+                                .await
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                        continue;
+                    }
+
+                    match StdioTransport::parse_message(text.as_bytes()) {
                         Ok(msg) => {
                             if let Err(e) = incoming_tx.send((client_id, msg)).await {
                                 error!("Failed to queue message from {}: {}", client_id, e);
@@ -378,18 +493,7 @@ impl EnhancedWebSocketServer {
             return Err(Error::internal("Broadcast mode not enabled"));
         }
 
-        let clients = self.clients.read().await;
-        let mut send_count = 0;
-
-        for (client_id, client) in clients.iter() {
-            if let Err(e) = client.tx.send(message.clone()).await {
-                warn!("Failed to send to client {}: {}", client_id, e);
-            } else {
-                send_count += 1;
-            }
-        }
-
-        debug!("Broadcast sent to {} clients", send_count);
+        self.connected_clients().broadcast(message).await;
         Ok(())
     }
 
@@ -399,19 +503,19 @@ impl EnhancedWebSocketServer {
         client_id: ClientId,
         message: TransportMessage,
     ) -> Result<()> {
-        let clients = self.clients.read().await;
-
-        let client = clients
-            .get(&client_id)
-            .ok_or_else(|| Error::internal(format!("Client {} not found", client_id)))?;
+        self.connected_clients().send_to(client_id, message).await
+    }
 
-        client
-            .tx
-            .send(message)
-            .await
-            .map_err(|_| Error::internal("Failed to send to client"))?;
+    /// Handle for sending to connected clients from other tasks.
+    pub fn connected_clients(&self) -> ConnectedClients {
+        ConnectedClients {
+            clients: self.clients.clone(),
+        }
+    }
 
-        Ok(())
+    /// Whether [`start`](Self::start) has bound the listener.
+    pub fn is_listening(&self) -> bool {
+        self.listener.is_some()
     }
 
     /// Get list of connected client IDs
@@ -4,7 +4,10 @@
 //! input and output typing support. For WASM environments, see `wasm_typed_tool.rs`
 //! which provides input typing only due to async constraints.
 
-use crate::{types::ToolInfo, Error, Result};
+use crate::{
+    types::{ToolAnnotations, ToolInfo},
+    Error, Result,
+};
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -17,6 +20,8 @@ use std::pin::Pin;
 use super::cancellation::RequestHandlerExtra;
 use super::ToolHandler;
 
+#[cfg(feature = "schema-generation")]
+use super::schema_utils::generate_schema;
 #[cfg(feature = "schema-generation")]
 use schemars::JsonSchema;
 
@@ -31,6 +36,7 @@ where
     name: String,
     description: Option<String>,
     input_schema: Value,
+    annotations: ToolAnnotations,
     handler: F,
     _phantom: PhantomData<T>,
 }
@@ -47,6 +53,7 @@ where
             .field("name", &self.name)
             .field("description", &self.description)
             .field("input_schema", &self.input_schema)
+            .field("annotations", &self.annotations)
             .finish()
     }
 }
@@ -69,6 +76,7 @@ where
             name: name.into(),
             description: None,
             input_schema: schema,
+            annotations: ToolAnnotations::default(),
             handler,
             _phantom: PhantomData,
         }
@@ -80,6 +88,7 @@ where
             name: name.into(),
             description: None,
             input_schema: schema,
+            annotations: ToolAnnotations::default(),
             handler,
             _phantom: PhantomData,
         }
@@ -90,6 +99,37 @@ where
         self.description = Some(description.into());
         self
     }
+
+    /// Set the behavior hints advertised for this tool.
+    pub fn with_annotations(mut self, annotations: ToolAnnotations) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Advertise that this tool only reads its environment.
+    pub fn read_only(mut self) -> Self {
+        self.annotations.read_only_hint = Some(true);
+        self
+    }
+
+    /// Advertise that this tool may make destructive changes.
+    pub fn destructive(mut self) -> Self {
+        self.annotations.destructive_hint = Some(true);
+        self
+    }
+
+    /// Advertise that repeating a call with the same arguments has no
+    /// further effect.
+    pub fn idempotent(mut self) -> Self {
+        self.annotations.idempotent_hint = Some(true);
+        self
+    }
+
+    /// Advertise that this tool interacts with external entities.
+    pub fn open_world(mut self) -> Self {
+        self.annotations.open_world_hint = Some(true);
+        self
+    }
 }
 
 #[async_trait]
@@ -115,6 +155,8 @@ where
             name: self.name.clone(),
             description: self.description.clone(),
             input_schema: self.input_schema.clone(),
+            output_schema: None,
+            annotations: (!self.annotations.is_empty()).then(|| self.annotations.clone()),
         })
     }
 }
@@ -128,6 +170,7 @@ where
     name: String,
     description: Option<String>,
     input_schema: Value,
+    annotations: ToolAnnotations,
     handler: F,
     _phantom: PhantomData<T>,
 }
@@ -142,6 +185,7 @@ where
             .field("name", &self.name)
             .field("description", &self.description)
             .field("input_schema", &self.input_schema)
+            .field("annotations", &self.annotations)
             .finish()
     }
 }
@@ -162,6 +206,7 @@ where
             name: name.into(),
             description: None,
             input_schema: schema,
+            annotations: ToolAnnotations::default(),
             handler,
             _phantom: PhantomData,
         }
@@ -173,6 +218,7 @@ where
             name: name.into(),
             description: None,
             input_schema: schema,
+            annotations: ToolAnnotations::default(),
             handler,
             _phantom: PhantomData,
         }
@@ -183,6 +229,37 @@ where
         self.description = Some(description.into());
         self
     }
+
+    /// Set the behavior hints advertised for this tool.
+    pub fn with_annotations(mut self, annotations: ToolAnnotations) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Advertise that this tool only reads its environment.
+    pub fn read_only(mut self) -> Self {
+        self.annotations.read_only_hint = Some(true);
+        self
+    }
+
+    /// Advertise that this tool may make destructive changes.
+    pub fn destructive(mut self) -> Self {
+        self.annotations.destructive_hint = Some(true);
+        self
+    }
+
+    /// Advertise that repeating a call with the same arguments has no
+    /// further effect.
+    pub fn idempotent(mut self) -> Self {
+        self.annotations.idempotent_hint = Some(true);
+        self
+    }
+
+    /// Advertise that this tool interacts with external entities.
+    pub fn open_world(mut self) -> Self {
+        self.annotations.open_world_hint = Some(true);
+        self
+    }
 }
 
 #[async_trait]
@@ -206,28 +283,12 @@ where
             name: self.name.clone(),
             description: self.description.clone(),
             input_schema: self.input_schema.clone(),
+            output_schema: None,
+            annotations: (!self.annotations.is_empty()).then(|| self.annotations.clone()),
         })
     }
 }
 
-/// Generate a JSON schema for a type using schemars.
-#[cfg(feature = "schema-generation")]
-fn generate_schema<T: JsonSchema>() -> Value {
-    let schema = schemars::schema_for!(T);
-
-    // Convert the schema to JSON value
-    let json_schema = serde_json::to_value(&schema).unwrap_or_else(|_| {
-        serde_json::json!({
-            "type": "object",
-            "properties": {},
-            "additionalProperties": true
-        })
-    });
-
-    // Normalize the schema by inlining $ref references
-    crate::server::schema_utils::normalize_schema(json_schema)
-}
-
 /// Extension trait to add type-safe schema generation to `SimpleTool`.
 pub trait SimpleToolExt {
     /// Create a `SimpleTool` with schema generated from a type.
@@ -273,8 +334,9 @@ where
 /// A typed tool with both input and output type safety
 ///
 /// This variant provides type safety for both input arguments and return values.
-/// While output schemas are not part of the MCP protocol, they're useful for
-/// testing, documentation, and API contracts.
+/// The output schema is advertised as the tool's `outputSchema`; enable
+/// [`ServerBuilder::validate_tool_output`](crate::ServerBuilder::validate_tool_output)
+/// to check results against it at runtime.
 pub struct TypedToolWithOutput<TIn, TOut, F>
 where
     TIn: DeserializeOwned + Send + Sync + 'static,
@@ -287,6 +349,7 @@ where
     description: Option<String>,
     input_schema: Value,
     output_schema: Option<Value>,
+    annotations: ToolAnnotations,
     handler: F,
     _phantom: PhantomData<(TIn, TOut)>,
 }
@@ -304,6 +367,7 @@ where
             .field("name", &self.name)
             .field("description", &self.description)
             .field("input_schema", &self.input_schema)
+            .field("annotations", &self.annotations)
             .field("output_schema", &self.output_schema)
             .finish()
     }
@@ -332,6 +396,7 @@ where
             description: None,
             input_schema,
             output_schema,
+            annotations: ToolAnnotations::default(),
             handler,
             _phantom: PhantomData,
         }
@@ -350,6 +415,7 @@ where
             description: None,
             input_schema,
             output_schema: None,
+            annotations: ToolAnnotations::default(),
             handler,
             _phantom: PhantomData,
         }
@@ -367,6 +433,7 @@ where
             description: None,
             input_schema,
             output_schema,
+            annotations: ToolAnnotations::default(),
             handler,
             _phantom: PhantomData,
         }
@@ -378,6 +445,37 @@ where
         self
     }
 
+    /// Set the behavior hints advertised for this tool.
+    pub fn with_annotations(mut self, annotations: ToolAnnotations) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Advertise that this tool only reads its environment.
+    pub fn read_only(mut self) -> Self {
+        self.annotations.read_only_hint = Some(true);
+        self
+    }
+
+    /// Advertise that this tool may make destructive changes.
+    pub fn destructive(mut self) -> Self {
+        self.annotations.destructive_hint = Some(true);
+        self
+    }
+
+    /// Advertise that repeating a call with the same arguments has no
+    /// further effect.
+    pub fn idempotent(mut self) -> Self {
+        self.annotations.idempotent_hint = Some(true);
+        self
+    }
+
+    /// Advertise that this tool interacts with external entities.
+    pub fn open_world(mut self) -> Self {
+        self.annotations.open_world_hint = Some(true);
+        self
+    }
+
     /// Get the output schema (if any) for testing/documentation purposes
     pub fn output_schema(&self) -> Option<&Value> {
         self.output_schema.as_ref()
@@ -411,6 +509,8 @@ where
             name: self.name.clone(),
             description: self.description.clone(),
             input_schema: self.input_schema.clone(),
+            output_schema: self.output_schema.clone(),
+            annotations: (!self.annotations.is_empty()).then(|| self.annotations.clone()),
         })
     }
 }
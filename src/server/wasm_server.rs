@@ -2,19 +2,89 @@
 //!
 //! This module provides a properly typed MCP server that maintains type safety
 //! while being deployable to any WASI environment.
+//!
+//! # Sessions
+//!
+//! Serverless platforms may route every request of a client to a fresh
+//! instance, so nothing learned at `initialize` survives in memory.
+//! [`WasmMcpServer::handle_http_session`] keeps that state in a
+//! [`SessionStore`] instead: initialization issues an `mcp-session-id`, and
+//! later requests must present a live session and, if they send one, the
+//! negotiated `mcp-protocol-version`.
+//!
+//! Responses of [`handle_http_session`](WasmMcpServer::handle_http_session)
+//! carry the CORS headers of the server's
+//! [`CorsConfig`](crate::shared::cors::CorsConfig) for the wrapper to send;
+//! [`preflight`](WasmMcpServer::preflight) answers `OPTIONS` requests.
+//!
+//! Stores wrap the platform's key-value storage. On Fermyon Spin, see
+//! `examples/wasm-mcp-server/deployments/fermyon-spin`; on Cloudflare
+//! Workers, a KV namespace (or a Durable Object's storage, for strongly
+//! consistent sessions) can back the store:
+//!
+//! ```rust,ignore
+//! use async_trait::async_trait;
+//! use pmcp::server::wasm_server::SessionStore;
+//! use std::time::Duration;
+//!
+//! struct KvSessionStore(worker::kv::KvStore);
+//!
+//! fn kv_error(e: worker::kv::KvError) -> pmcp::Error {
+//!     pmcp::Error::internal(format!("KV error: {:?}", e))
+//! }
+//!
+//! #[async_trait(?Send)]
+//! impl SessionStore for KvSessionStore {
+//!     async fn get(&self, key: &str) -> pmcp::Result<Option<String>> {
+//!         self.0.get(key).text().await.map_err(kv_error)
+//!     }
+//!
+//!     async fn put(&self, key: &str, value: String, ttl: Duration) -> pmcp::Result<()> {
+//!         self.0
+//!             .put(key, value)
+//!             .map_err(kv_error)?
+//!             .expiration_ttl(ttl.as_secs().max(60))
+//!             .execute()
+//!             .await
+//!             .map_err(kv_error)
+//!     }
+//!
+//!     async fn delete(&self, key: &str) -> pmcp::Result<()> {
+//!         self.0.delete(key).await.map_err(kv_error)
+//!     }
+//! }
+//!
+//! // In the fetch handler:
+//! // let store = KvSessionStore(env.kv("MCP_SESSIONS")?);
+//! // let response = server.handle_http_session(&store, headers, &body).await;
+//! ```
 
 use crate::error::{Error, Result};
+use crate::shared::cors::CorsConfig;
+use crate::shared::http_constants::{MCP_PROTOCOL_VERSION, MCP_SESSION_ID};
+use crate::shared::protocol_helpers::parse_request;
 use crate::types::{
     CallToolParams, CallToolResult, ClientRequest, Content, GetPromptParams, GetPromptResult,
-    Implementation, InitializeParams, InitializeResult, JSONRPCError, JSONRPCResponse,
-    ListPromptsParams, ListPromptsResult, ListResourcesParams, ListResourcesResult,
-    ListToolsParams, ListToolsResult, PromptInfo, ReadResourceParams, ReadResourceResult, Request,
-    RequestId, ResourceInfo, ServerCapabilities, ToolInfo,
+    Implementation, InitializeParams, InitializeResult, JSONRPCError, JSONRPCRequest,
+    JSONRPCResponse, ListPromptsParams, ListPromptsResult, ListResourceTemplatesRequest,
+    ListResourceTemplatesResult, ListResourcesParams, ListResourcesResult, ListToolsParams,
+    ListToolsResult, PromptArgument, PromptInfo, ReadResourceParams, ReadResourceResult, Request,
+    RequestId, ResourceInfo, ResourceTemplate, ServerCapabilities, ToolInfo,
 };
 use crate::{ErrorCode, SUPPORTED_PROTOCOL_VERSIONS};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// Default time a session stays alive without requests.
+pub const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Prefix of the keys sessions are stored under.
+const SESSION_KEY_PREFIX: &str = "mcp-session:";
 
 /// A tool that can be executed in WASM environments.
 pub trait WasmTool: Send + Sync {
@@ -34,6 +104,9 @@ pub trait WasmResource: Send + Sync {
     fn list(&self, cursor: Option<String>) -> Result<ListResourcesResult>;
 
     /// Get resource templates if any.
+    ///
+    /// Each entry's `uri` is a URI template, listed by
+    /// `resources/templates/list`.
     fn templates(&self) -> Vec<ResourceInfo> {
         Vec::new()
     }
@@ -52,12 +125,51 @@ pub trait WasmPrompt: Send + Sync {
 ///
 /// This server maintains full type safety while being deployable to any
 /// WASI environment (Cloudflare Workers, Fermyon Spin, Wasmtime, etc).
+///
+/// Tools, prompts, and resource providers are listed in name order.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use pmcp::server::wasm_server::{SimplePrompt, SimpleTool, StaticResources, WasmMcpServer};
+/// use pmcp::types::{GetPromptResult, MessageContent, PromptMessage, Role};
+/// use serde_json::json;
+///
+/// let server = WasmMcpServer::builder()
+///     .name("edge-server")
+///     .tool(
+///         "echo",
+///         SimpleTool::new("echo", "Echo the input", |args| Ok(json!({"echo": args}))),
+///     )
+///     .prompt(
+///         "greet",
+///         SimplePrompt::new("greet", |args| {
+///             Ok(GetPromptResult {
+///                 description: None,
+///                 messages: vec![PromptMessage {
+///                     role: Role::User,
+///                     content: MessageContent::Text {
+///                         text: format!("Say hello to {}", args["name"]),
+///                     },
+///                 }],
+///             })
+///         })
+///         .with_argument("name", "Who to greet", true),
+///     )
+///     .resource(
+///         "docs",
+///         StaticResources::new().add_text("docs://readme", "# Edge server"),
+///     )
+///     .build();
+/// ```
 pub struct WasmMcpServer {
     info: Implementation,
     capabilities: ServerCapabilities,
-    tools: HashMap<String, Box<dyn WasmTool>>,
-    resources: HashMap<String, Box<dyn WasmResource>>,
-    prompts: HashMap<String, Box<dyn WasmPrompt>>,
+    tools: BTreeMap<String, Box<dyn WasmTool>>,
+    resources: BTreeMap<String, Box<dyn WasmResource>>,
+    prompts: BTreeMap<String, Box<dyn WasmPrompt>>,
+    session_ttl: Duration,
+    cors: CorsConfig,
 }
 
 impl WasmMcpServer {
@@ -103,12 +215,288 @@ impl WasmMcpServer {
         }
     }
 
+    /// Handle the body of an HTTP POST carrying JSON-RPC.
+    ///
+    /// Runs the full cycle for a single message or a batch: parsing, dispatch,
+    /// and serialization, answering malformed input with JSON-RPC errors.
+    /// Notifications and client responses produce no output, so an empty
+    /// string means the HTTP wrapper should reply `202 Accepted` without a
+    /// body.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let body = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#;
+    /// let response = server.handle_http(body).await;
+    /// if response.is_empty() {
+    ///     // 202 Accepted
+    /// } else {
+    ///     // 200 OK with `Content-Type: application/json`
+    /// }
+    /// ```
+    pub async fn handle_http(&self, body: &str) -> String {
+        let value = match serde_json::from_str::<Value>(body) {
+            Ok(value) => value,
+            Err(e) => {
+                return Self::error_without_id(
+                    ErrorCode::PARSE_ERROR,
+                    &format!("Parse error: {}", e),
+                )
+                .to_string();
+            },
+        };
+
+        let reply = match value {
+            Value::Array(messages) if messages.is_empty() => Some(Self::error_without_id(
+                ErrorCode::INVALID_REQUEST,
+                "Empty batch",
+            )),
+            Value::Array(messages) => {
+                let mut responses = Vec::new();
+                for message in messages {
+                    responses.extend(self.handle_json_message(message).await);
+                }
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            },
+            message => self.handle_json_message(message).await,
+        };
+        reply.map(|reply| reply.to_string()).unwrap_or_default()
+    }
+
+    /// Handle a raw HTTP body. See [`handle_http`](Self::handle_http).
+    pub async fn handle_http_bytes(&self, body: &[u8]) -> Vec<u8> {
+        match std::str::from_utf8(body) {
+            Ok(body) => self.handle_http(body).await.into_bytes(),
+            Err(_) => {
+                Self::error_without_id(ErrorCode::PARSE_ERROR, "Parse error: body is not UTF-8")
+                    .to_string()
+                    .into_bytes()
+            },
+        }
+    }
+
+    /// Handle an HTTP POST within a session kept in `store`.
+    ///
+    /// An `initialize` request starts a session and the response carries its
+    /// id, to be returned to the client in the `mcp-session-id` header. Other
+    /// requests must carry a live session id, or are rejected with status 400
+    /// (missing) or 404 (unknown or expired, so the client re-initializes).
+    /// A protocol version header that differs from the negotiated version is
+    /// rejected with status 400. Every response carries the CORS headers for
+    /// the request's origin.
+    pub async fn handle_http_session(
+        &self,
+        store: &dyn SessionStore,
+        headers: SessionHeaders<'_>,
+        body: &str,
+    ) -> WasmHttpResponse {
+        let mut response = self.session_response(store, headers, body).await;
+        response.headers = self.cors.headers(headers.origin);
+        response
+    }
+
+    /// Answer a CORS preflight (`OPTIONS`) request from `origin`.
+    pub fn preflight(&self, origin: Option<&str>) -> WasmHttpResponse {
+        WasmHttpResponse {
+            status: 204,
+            body: String::new(),
+            session_id: None,
+            headers: self.cors.preflight_headers(origin),
+        }
+    }
+
+    /// CORS headers for other responses to requests from `origin`, such as
+    /// to a DELETE ending a session.
+    pub fn cors_headers(&self, origin: Option<&str>) -> Vec<(&'static str, String)> {
+        self.cors.headers(origin)
+    }
+
+    async fn session_response(
+        &self,
+        store: &dyn SessionStore,
+        headers: SessionHeaders<'_>,
+        body: &str,
+    ) -> WasmHttpResponse {
+        let is_initialize = serde_json::from_str::<Value>(body)
+            .is_ok_and(|value| value.get("method").and_then(Value::as_str) == Some("initialize"));
+        if is_initialize {
+            return self.initialize_session(store, body).await;
+        }
+
+        let Some(session_id) = headers.session_id else {
+            return WasmHttpResponse::rejected(
+                400,
+                ErrorCode::INVALID_REQUEST,
+                &format!("Missing {} header", MCP_SESSION_ID),
+            );
+        };
+        let key = format!("{}{}", SESSION_KEY_PREFIX, session_id);
+        let record = match store.get(&key).await {
+            Ok(Some(record)) => record,
+            Ok(None) => {
+                return WasmHttpResponse::rejected(
+                    404,
+                    ErrorCode::INVALID_REQUEST,
+                    "Session not found",
+                );
+            },
+            Err(e) => {
+                return WasmHttpResponse::rejected(500, ErrorCode::INTERNAL_ERROR, &e.to_string());
+            },
+        };
+        let Ok(session) = serde_json::from_str::<SessionRecord>(&record) else {
+            return WasmHttpResponse::rejected(
+                404,
+                ErrorCode::INVALID_REQUEST,
+                "Session not found",
+            );
+        };
+        if let Some(version) = headers.protocol_version {
+            if version != session.protocol_version {
+                return WasmHttpResponse::rejected(
+                    400,
+                    ErrorCode::INVALID_REQUEST,
+                    &format!(
+                        "{} '{}' does not match the negotiated version '{}'",
+                        MCP_PROTOCOL_VERSION, version, session.protocol_version
+                    ),
+                );
+            }
+        }
+        if let Err(e) = store.put(&key, record, self.session_ttl).await {
+            return WasmHttpResponse::rejected(500, ErrorCode::INTERNAL_ERROR, &e.to_string());
+        }
+
+        let body = self.handle_http(body).await;
+        WasmHttpResponse {
+            status: if body.is_empty() { 202 } else { 200 },
+            body,
+            session_id: Some(session_id.to_string()),
+            headers: Vec::new(),
+        }
+    }
+
+    /// End a session, e.g. on an HTTP DELETE from the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns the store's error if the session cannot be deleted.
+    pub async fn end_session(&self, store: &dyn SessionStore, session_id: &str) -> Result<()> {
+        store
+            .delete(&format!("{}{}", SESSION_KEY_PREFIX, session_id))
+            .await
+    }
+
+    async fn initialize_session(&self, store: &dyn SessionStore, body: &str) -> WasmHttpResponse {
+        let body = self.handle_http(body).await;
+        let protocol_version = serde_json::from_str::<Value>(&body).ok().and_then(|reply| {
+            reply["result"]["protocolVersion"]
+                .as_str()
+                .map(str::to_string)
+        });
+        let Some(protocol_version) = protocol_version else {
+            // Failed initialization starts no session.
+            return WasmHttpResponse {
+                status: 200,
+                body,
+                session_id: None,
+                headers: Vec::new(),
+            };
+        };
+
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let record = match serde_json::to_string(&SessionRecord { protocol_version }) {
+            Ok(record) => record,
+            Err(e) => {
+                return WasmHttpResponse::rejected(500, ErrorCode::INTERNAL_ERROR, &e.to_string());
+            },
+        };
+        if let Err(e) = store
+            .put(
+                &format!("{}{}", SESSION_KEY_PREFIX, session_id),
+                record,
+                self.session_ttl,
+            )
+            .await
+        {
+            return WasmHttpResponse::rejected(500, ErrorCode::INTERNAL_ERROR, &e.to_string());
+        }
+        WasmHttpResponse {
+            status: 200,
+            body,
+            session_id: Some(session_id),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Dispatch one decoded JSON-RPC message, returning the response to send.
+    async fn handle_json_message(&self, message: Value) -> Option<Value> {
+        let Some(object) = message.as_object() else {
+            return Some(Self::error_without_id(
+                ErrorCode::INVALID_REQUEST,
+                "Invalid request: expected an object",
+            ));
+        };
+        if !object.contains_key("method") {
+            if object.contains_key("result") || object.contains_key("error") {
+                // Responses from the client have nothing to answer.
+                return None;
+            }
+            return Some(Self::error_without_id(
+                ErrorCode::INVALID_REQUEST,
+                "Invalid request: missing method",
+            ));
+        }
+        if !object.contains_key("id") {
+            // Notifications need no response and change no server state.
+            return None;
+        }
+
+        let request = match serde_json::from_value::<JSONRPCRequest<Value>>(message) {
+            Ok(request) => request,
+            Err(e) => {
+                return Some(Self::error_without_id(
+                    ErrorCode::INVALID_REQUEST,
+                    &format!("Invalid request: {}", e),
+                ));
+            },
+        };
+        let id = request.id.clone();
+        let response = match parse_request(request) {
+            Ok((id, request)) => self.handle_request(id, request).await,
+            Err(error) => JSONRPCResponse::error(
+                id,
+                JSONRPCError {
+                    code: Self::map_error_code(&error).0,
+                    message: error.to_string(),
+                    data: None,
+                },
+            ),
+        };
+        serde_json::to_value(response).ok()
+    }
+
+    /// Error response for a message whose id could not be determined.
+    fn error_without_id(code: ErrorCode, message: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": {
+                "code": code.0,
+                "message": message,
+            },
+        })
+    }
+
     async fn handle_client_request(&self, request: ClientRequest) -> Result<Value> {
         match request {
             ClientRequest::Initialize(params) => self.handle_initialize(params),
             ClientRequest::ListTools(params) => self.handle_list_tools(params),
             ClientRequest::CallTool(params) => self.handle_call_tool(params),
             ClientRequest::ListResources(params) => self.handle_list_resources(params),
+            ClientRequest::ListResourceTemplates(params) => {
+                self.handle_list_resource_templates(params)
+            },
             ClientRequest::ReadResource(params) => self.handle_read_resource(params),
             ClientRequest::ListPrompts(params) => self.handle_list_prompts(params),
             ClientRequest::GetPrompt(params) => self.handle_get_prompt(params),
@@ -179,6 +567,7 @@ impl WasmMcpServer {
                 let result = CallToolResult {
                     content,
                     is_error: false,
+                    structured_content: None,
                 };
                 serde_json::to_value(result).map_err(|e| Error::internal(&e.to_string()))
             },
@@ -188,63 +577,82 @@ impl WasmMcpServer {
                         text: format!("Error: {}", e),
                     }],
                     is_error: true,
+                    structured_content: None,
                 };
                 serde_json::to_value(result).map_err(|e| Error::internal(&e.to_string()))
             },
         }
     }
 
+    /// List resources one provider page at a time.
+    ///
+    /// Cursors have the form `provider:cursor`; an empty provider cursor
+    /// starts that provider's listing. A cursor without a provider is passed
+    /// to the first provider.
     fn handle_list_resources(&self, params: ListResourcesParams) -> Result<Value> {
-        // Aggregate resources from all providers with cursor support
-        let mut all_resources = Vec::new();
-        let mut next_cursor = None;
-
-        // Parse cursor to determine which provider to query
-        let (provider_name, provider_cursor) = if let Some(cursor) = params.cursor {
-            // Format: "provider:cursor" or just "cursor" for first provider
-            if let Some((name, cur)) = cursor.split_once(':') {
-                (Some(name.to_string()), Some(cur.to_string()))
-            } else {
-                (None, Some(cursor))
-            }
-        } else {
-            (None, None)
+        let (start, provider_cursor) = match params.cursor {
+            Some(cursor) => match cursor.split_once(':') {
+                Some((name, cur)) => {
+                    if !self.resources.contains_key(name) {
+                        return Err(Error::protocol(
+                            ErrorCode::INVALID_PARAMS,
+                            format!("Invalid resource cursor: {}", cursor),
+                        ));
+                    }
+                    (Some(name.to_string()), Some(cur.to_string()))
+                },
+                None => (None, Some(cursor)),
+            },
+            None => (None, None),
         };
+        let provider_cursor = provider_cursor.filter(|cursor| !cursor.is_empty());
 
-        // Query the appropriate provider(s)
-        let mut found_provider = provider_name.is_none();
-        for (name, resource) in &self.resources {
-            if let Some(ref pname) = provider_name {
-                if name != pname {
-                    continue;
-                }
-            }
-
-            if found_provider {
-                match resource.list(provider_cursor.clone()) {
-                    Ok(result) => {
-                        all_resources.extend(result.resources);
-                        if let Some(cursor) = result.next_cursor {
-                            next_cursor = Some(format!("{}:{}", name, cursor));
-                        }
-                        break; // Only query one provider at a time for pagination
-                    },
-                    Err(_) => continue,
-                }
-            }
-
-            if provider_name.is_none() {
-                found_provider = true;
-            }
-        }
+        let mut providers = self
+            .resources
+            .iter()
+            .skip_while(|(name, _)| start.as_ref().is_some_and(|start| *name != start));
+        let Some((name, resource)) = providers.next() else {
+            return serde_json::to_value(ListResourcesResult {
+                resources: Vec::new(),
+                next_cursor: None,
+            })
+            .map_err(|e| Error::internal(&e.to_string()));
+        };
 
+        let page = resource.list(provider_cursor)?;
+        let next_cursor = match page.next_cursor {
+            Some(cursor) => Some(format!("{}:{}", name, cursor)),
+            None => providers.next().map(|(next, _)| format!("{}:", next)),
+        };
         let result = ListResourcesResult {
-            resources: all_resources,
+            resources: page.resources,
             next_cursor,
         };
         serde_json::to_value(result).map_err(|e| Error::internal(&e.to_string()))
     }
 
+    fn handle_list_resource_templates(
+        &self,
+        _params: ListResourceTemplatesRequest,
+    ) -> Result<Value> {
+        let resource_templates = self
+            .resources
+            .values()
+            .flat_map(|resource| resource.templates())
+            .map(|info| ResourceTemplate {
+                uri_template: info.uri,
+                name: info.name,
+                description: info.description,
+                mime_type: info.mime_type,
+            })
+            .collect();
+        let result = ListResourceTemplatesResult {
+            resource_templates,
+            next_cursor: None,
+        };
+        serde_json::to_value(result).map_err(|e| Error::internal(&e.to_string()))
+    }
+
     fn handle_read_resource(&self, params: ReadResourceParams) -> Result<Value> {
         // Find the first resource that can handle this URI
         for resource in self.resources.values() {
@@ -281,14 +689,107 @@ impl WasmMcpServer {
     }
 }
 
+/// Key-value storage that keeps session state between requests.
+///
+/// Values are opaque strings. Implementations should let entries expire
+/// after their `ttl`; expired entries must read as missing.
+#[async_trait(?Send)]
+pub trait SessionStore {
+    /// Read the value stored under `key`.
+    async fn get(&self, key: &str) -> Result<Option<String>>;
+
+    /// Store `value` under `key` for `ttl`, replacing any previous value.
+    async fn put(&self, key: &str, value: String, ttl: Duration) -> Result<()>;
+
+    /// Remove the value stored under `key`, if any.
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Session store kept in memory.
+///
+/// Entries never expire, and instances do not share them, so this is meant
+/// for tests and single-instance deployments.
+#[derive(Debug, Default)]
+pub struct MemorySessionStore {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+impl MemorySessionStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait(?Send)]
+impl SessionStore for MemorySessionStore {
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.entries.lock().get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, value: String, _ttl: Duration) -> Result<()> {
+        self.entries.lock().insert(key.to_string(), value);
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.entries.lock().remove(key);
+        Ok(())
+    }
+}
+
+/// Session headers of an incoming HTTP request.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionHeaders<'a> {
+    /// Value of the `mcp-session-id` header
+    pub session_id: Option<&'a str>,
+    /// Value of the `mcp-protocol-version` header
+    pub protocol_version: Option<&'a str>,
+    /// Value of the `origin` header
+    pub origin: Option<&'a str>,
+}
+
+/// Reply to an HTTP request handled within a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmHttpResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// JSON body, empty for `202 Accepted`
+    pub body: String,
+    /// Session id to send in the `mcp-session-id` header
+    pub session_id: Option<String>,
+    /// CORS headers to send
+    pub headers: Vec<(&'static str, String)>,
+}
+
+impl WasmHttpResponse {
+    fn rejected(status: u16, code: ErrorCode, message: &str) -> Self {
+        Self {
+            status,
+            body: WasmMcpServer::error_without_id(code, message).to_string(),
+            session_id: None,
+            headers: Vec::new(),
+        }
+    }
+}
+
+/// Session state stored between requests.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionRecord {
+    protocol_version: String,
+}
+
 /// Builder for WasmMcpServer.
 pub struct WasmMcpServerBuilder {
     name: String,
     version: String,
     capabilities: ServerCapabilities,
-    tools: HashMap<String, Box<dyn WasmTool>>,
-    resources: HashMap<String, Box<dyn WasmResource>>,
-    prompts: HashMap<String, Box<dyn WasmPrompt>>,
+    tools: BTreeMap<String, Box<dyn WasmTool>>,
+    resources: BTreeMap<String, Box<dyn WasmResource>>,
+    prompts: BTreeMap<String, Box<dyn WasmPrompt>>,
+    session_ttl: Duration,
+    cors: CorsConfig,
 }
 
 impl WasmMcpServerBuilder {
@@ -298,9 +799,11 @@ impl WasmMcpServerBuilder {
             name: "wasm-mcp-server".to_string(),
             version: "1.0.0".to_string(),
             capabilities: ServerCapabilities::default(),
-            tools: HashMap::new(),
-            resources: HashMap::new(),
-            prompts: HashMap::new(),
+            tools: BTreeMap::new(),
+            resources: BTreeMap::new(),
+            prompts: BTreeMap::new(),
+            session_ttl: DEFAULT_SESSION_TTL,
+            cors: CorsConfig::default(),
         }
     }
 
@@ -347,6 +850,20 @@ impl WasmMcpServerBuilder {
         self
     }
 
+    /// Set how long sessions stay alive without requests.
+    ///
+    /// Every request within a session restarts the timer.
+    pub fn session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = ttl;
+        self
+    }
+
+    /// Set the CORS headers sent to browser-based clients.
+    pub fn cors(mut self, cors: CorsConfig) -> Self {
+        self.cors = cors;
+        self
+    }
+
     /// Build the server.
     pub fn build(self) -> WasmMcpServer {
         WasmMcpServer {
@@ -358,6 +875,8 @@ impl WasmMcpServerBuilder {
             tools: self.tools,
             resources: self.resources,
             prompts: self.prompts,
+            session_ttl: self.session_ttl,
+            cors: self.cors,
         }
     }
 }
@@ -408,6 +927,153 @@ where
             name: self.name.clone(),
             description: Some(self.description.clone()),
             input_schema: self.input_schema.clone(),
+            output_schema: None,
+            annotations: None,
         }
     }
 }
+
+/// Function-based prompt implementation.
+pub struct SimplePrompt<F> {
+    name: String,
+    description: Option<String>,
+    arguments: Vec<PromptArgument>,
+    handler: F,
+}
+
+impl<F> std::fmt::Debug for SimplePrompt<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimplePrompt")
+            .field("name", &self.name)
+            .field("arguments", &self.arguments)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> SimplePrompt<F>
+where
+    F: Fn(HashMap<String, String>) -> Result<GetPromptResult> + Send + Sync,
+{
+    /// Create a new prompt with a name and handler.
+    pub fn new(name: impl Into<String>, handler: F) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            arguments: Vec::new(),
+            handler,
+        }
+    }
+
+    /// Set the description for this prompt.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add an argument to this prompt.
+    pub fn with_argument(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        required: bool,
+    ) -> Self {
+        self.arguments.push(PromptArgument {
+            name: name.into(),
+            description: Some(description.into()),
+            required,
+            completion: None,
+        });
+        self
+    }
+}
+
+impl<F> WasmPrompt for SimplePrompt<F>
+where
+    F: Fn(HashMap<String, String>) -> Result<GetPromptResult> + Send + Sync,
+{
+    fn generate(&self, args: HashMap<String, String>) -> Result<GetPromptResult> {
+        if let Some(missing) = self
+            .arguments
+            .iter()
+            .find(|arg| arg.required && !args.contains_key(&arg.name))
+        {
+            return Err(Error::protocol(
+                ErrorCode::INVALID_PARAMS,
+                format!("Required argument '{}' is missing", missing.name),
+            ));
+        }
+        (self.handler)(args)
+    }
+
+    fn info(&self) -> PromptInfo {
+        PromptInfo {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            arguments: (!self.arguments.is_empty()).then(|| self.arguments.clone()),
+        }
+    }
+}
+
+/// Fixed set of text resources.
+#[derive(Debug, Clone, Default)]
+pub struct StaticResources {
+    resources: Vec<(ResourceInfo, String)>,
+}
+
+impl StaticResources {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a plain-text resource, named after the last segment of its URI.
+    pub fn add_text(self, uri: impl Into<String>, content: impl Into<String>) -> Self {
+        let uri = uri.into();
+        let name = uri.rsplit('/').next().unwrap_or(&uri).to_string();
+        self.add(
+            ResourceInfo {
+                uri,
+                name,
+                description: None,
+                mime_type: Some("text/plain".to_string()),
+            },
+            content,
+        )
+    }
+
+    /// Add a text resource with full metadata.
+    pub fn add(mut self, info: ResourceInfo, content: impl Into<String>) -> Self {
+        self.resources.push((info, content.into()));
+        self
+    }
+}
+
+impl WasmResource for StaticResources {
+    fn read(&self, uri: &str) -> Result<ReadResourceResult> {
+        let (_, text) = self
+            .resources
+            .iter()
+            .find(|(info, _)| info.uri == uri)
+            .ok_or_else(|| {
+                Error::protocol(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("Resource not found: {}", uri),
+                )
+            })?;
+        Ok(ReadResourceResult {
+            contents: vec![Content::Text { text: text.clone() }],
+            meta: None,
+        })
+    }
+
+    fn list(&self, _cursor: Option<String>) -> Result<ListResourcesResult> {
+        Ok(ListResourcesResult {
+            resources: self
+                .resources
+                .iter()
+                .map(|(info, _)| info.clone())
+                .collect(),
+            next_cursor: None,
+        })
+    }
+}
@@ -120,6 +120,7 @@ mod tests {
         let params = CallToolParams {
             name: "echo".to_string(),
             arguments: json!({ "message": "Hello, WASM!" }),
+            meta: None,
         };
 
         let request = Request::Client(Box::new(ClientRequest::CallTool(params)));
@@ -148,6 +149,7 @@ mod tests {
         let params = CallToolParams {
             name: "nonexistent".to_string(),
             arguments: json!({}),
+            meta: None,
         };
 
         let request = Request::Client(Box::new(ClientRequest::CallTool(params)));
@@ -170,6 +172,7 @@ mod tests {
         let params = CallToolParams {
             name: "error_tool".to_string(),
             arguments: json!({}),
+            meta: None,
         };
 
         let request = Request::Client(Box::new(ClientRequest::CallTool(params)));
@@ -221,6 +224,7 @@ mod tests {
                     contents: vec![Content::Text {
                         text: "test".to_string(),
                     }],
+                    meta: None,
                 })
             }
 
@@ -311,6 +315,7 @@ mod tests {
         let params = CallToolParams {
             name: "text_tool".to_string(),
             arguments: json!({}),
+            meta: None,
         };
         let request = Request::Client(Box::new(ClientRequest::CallTool(params)));
         let response = server
@@ -328,6 +333,7 @@ mod tests {
         let params = CallToolParams {
             name: "object_tool".to_string(),
             arguments: json!({}),
+            meta: None,
         };
         let request = Request::Client(Box::new(ClientRequest::CallTool(params)));
         let response = server
@@ -344,4 +350,276 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_prompts_and_resource_providers() {
+        use crate::server::wasm_server::{SimplePrompt, StaticResources};
+        use crate::types::{GetPromptResult, MessageContent, PromptMessage, Role};
+
+        let server = WasmMcpServer::builder()
+            .prompt(
+                "greet",
+                SimplePrompt::new("greet", |args| {
+                    Ok(GetPromptResult {
+                        description: None,
+                        messages: vec![PromptMessage {
+                            role: Role::User,
+                            content: MessageContent::Text {
+                                text: format!("Hello, {}", args["name"]),
+                            },
+                        }],
+                    })
+                })
+                .with_argument("name", "Who to greet", true),
+            )
+            .resource("b", StaticResources::new().add_text("b://two", "two"))
+            .resource("a", StaticResources::new().add_text("a://one", "one"))
+            .build();
+
+        async fn call(server: &WasmMcpServer, request: ClientRequest) -> Result<Value> {
+            let response = server
+                .handle_request(RequestId::from(1i64), Request::Client(Box::new(request)))
+                .await;
+            match response.payload {
+                crate::types::jsonrpc::ResponsePayload::Result(value) => Ok(value),
+                crate::types::jsonrpc::ResponsePayload::Error(e) => {
+                    Err(Error::protocol(ErrorCode(e.code), e.message))
+                },
+            }
+        }
+
+        let prompts: ListPromptsResult = serde_json::from_value(
+            call(
+                &server,
+                ClientRequest::ListPrompts(ListPromptsParams { cursor: None }),
+            )
+            .await
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(prompts.prompts[0].name, "greet");
+        assert!(prompts.prompts[0].arguments.as_ref().unwrap()[0].required);
+
+        let mut params = GetPromptParams {
+            name: "greet".to_string(),
+            arguments: Default::default(),
+        };
+        let err = call(&server, ClientRequest::GetPrompt(params.clone()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::INVALID_PARAMS));
+        params
+            .arguments
+            .insert("name".to_string(), "Ada".to_string());
+        let prompt = call(&server, ClientRequest::GetPrompt(params))
+            .await
+            .unwrap();
+        assert_eq!(prompt["messages"][0]["content"]["text"], "Hello, Ada");
+
+        // Providers are paged through in name order.
+        let mut uris = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page: ListResourcesResult = serde_json::from_value(
+                call(
+                    &server,
+                    ClientRequest::ListResources(ListResourcesParams { cursor }),
+                )
+                .await
+                .unwrap(),
+            )
+            .unwrap();
+            uris.extend(page.resources.into_iter().map(|r| r.uri));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(uris, ["a://one", "b://two"]);
+
+        let read: ReadResourceResult = serde_json::from_value(
+            call(
+                &server,
+                ClientRequest::ReadResource(crate::types::ReadResourceParams::new("b://two")),
+            )
+            .await
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(&read.contents[0], Content::Text { text } if text == "two"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_http_dispatch() {
+        let server = create_test_server();
+
+        let reply: Value = serde_json::from_str(
+            &server
+                .handle_http(r#"{"jsonrpc":"2.0","id":7,"method":"tools/list"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(reply["id"], 7);
+        assert_eq!(reply["result"]["tools"].as_array().unwrap().len(), 2);
+
+        // Notifications and client responses produce no output.
+        assert!(server
+            .handle_http(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+            .await
+            .is_empty());
+        assert!(server
+            .handle_http(r#"{"jsonrpc":"2.0","id":"s1","result":{}}"#)
+            .await
+            .is_empty());
+
+        let reply: Value = serde_json::from_str(&server.handle_http("{not json").await).unwrap();
+        assert_eq!(reply["id"], Value::Null);
+        assert_eq!(reply["error"]["code"], ErrorCode::PARSE_ERROR.0);
+
+        let reply: Value = serde_json::from_str(
+            &server
+                .handle_http(r#"{"jsonrpc":"2.0","id":2,"method":"no/such"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(reply["id"], 2);
+        assert_eq!(reply["error"]["code"], ErrorCode::METHOD_NOT_FOUND.0);
+
+        // Batches answer every request, skipping notifications.
+        let reply = server
+            .handle_http_bytes(
+                br#"[
+                    {"jsonrpc":"2.0","id":1,"method":"ping"},
+                    {"jsonrpc":"2.0","method":"notifications/initialized"},
+                    {"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"echo","arguments":{"message":"hi"}}}
+                ]"#,
+            )
+            .await;
+        let reply: Vec<Value> = serde_json::from_slice(&reply).unwrap();
+        assert_eq!(reply.len(), 2);
+        assert_eq!(reply[0]["id"], 1);
+        assert_eq!(reply[1]["id"], 2);
+        assert!(reply[1]["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("hi"));
+
+        let reply: Value = serde_json::from_str(&server.handle_http("[]").await).unwrap();
+        assert_eq!(reply["error"]["code"], ErrorCode::INVALID_REQUEST.0);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_survive_across_requests() {
+        use crate::server::wasm_server::{MemorySessionStore, SessionHeaders};
+
+        let server = create_test_server();
+        let store = MemorySessionStore::new();
+        let list = r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#;
+
+        let response = server
+            .handle_http_session(&store, SessionHeaders::default(), list)
+            .await;
+        assert_eq!(response.status, 400);
+
+        let initialize = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": {"name": "test-client", "version": "1.0.0"}
+            }
+        })
+        .to_string();
+        let response = server
+            .handle_http_session(&store, SessionHeaders::default(), &initialize)
+            .await;
+        assert_eq!(response.status, 200);
+        let session_id = response.session_id.unwrap();
+
+        // A different server instance sharing the store accepts the session.
+        let other = create_test_server();
+        let headers = SessionHeaders {
+            session_id: Some(&session_id),
+            protocol_version: Some("2025-06-18"),
+            ..SessionHeaders::default()
+        };
+        let response = other.handle_http_session(&store, headers, list).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.session_id.as_deref(), Some(session_id.as_str()));
+        let reply: Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(reply["result"]["tools"].as_array().unwrap().len(), 2);
+
+        let response = other
+            .handle_http_session(
+                &store,
+                headers,
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            )
+            .await;
+        assert_eq!(response.status, 202);
+        assert!(response.body.is_empty());
+
+        let mismatched = SessionHeaders {
+            protocol_version: Some("2024-11-05"),
+            ..headers
+        };
+        assert_eq!(
+            other
+                .handle_http_session(&store, mismatched, list)
+                .await
+                .status,
+            400
+        );
+
+        other.end_session(&store, &session_id).await.unwrap();
+        assert_eq!(
+            other
+                .handle_http_session(&store, headers, list)
+                .await
+                .status,
+            404
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_responses_carry_cors_headers() {
+        use crate::server::wasm_server::{MemorySessionStore, SessionHeaders};
+        use crate::shared::cors::CorsConfig;
+
+        let server = WasmMcpServer::builder()
+            .name("cors-server")
+            .cors(CorsConfig::new().allow_origin("https://app.example.com"))
+            .build();
+        let store = MemorySessionStore::new();
+        let list = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#;
+
+        let preflight = server.preflight(Some("https://app.example.com"));
+        assert_eq!(preflight.status, 204);
+        assert!(preflight.headers.contains(&(
+            "Access-Control-Allow-Origin",
+            "https://app.example.com".to_string()
+        )));
+        assert!(preflight
+            .headers
+            .iter()
+            .any(|(name, _)| *name == "Access-Control-Allow-Methods"));
+
+        let headers = SessionHeaders {
+            origin: Some("https://app.example.com"),
+            ..SessionHeaders::default()
+        };
+        let response = server.handle_http_session(&store, headers, list).await;
+        assert_eq!(response.status, 400);
+        assert_eq!(response.headers, server.cors_headers(headers.origin));
+        assert!(!response.headers.is_empty());
+
+        let foreign = SessionHeaders {
+            origin: Some("https://evil.example.com"),
+            ..SessionHeaders::default()
+        };
+        let response = server.handle_http_session(&store, foreign, list).await;
+        assert!(response.headers.is_empty());
+    }
 }
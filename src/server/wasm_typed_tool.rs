@@ -97,6 +97,8 @@ where
             name: self.name.clone(),
             description: self.description.clone(),
             input_schema: self.input_schema.clone(),
+            output_schema: None,
+            annotations: None,
         }
     }
 }
@@ -189,6 +191,8 @@ where
             name: self.name.clone(),
             description: self.description.clone(),
             input_schema: self.input_schema.clone(),
+            output_schema: None,
+            annotations: None,
         }
     }
 }
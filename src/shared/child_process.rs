@@ -0,0 +1,526 @@
+//! stdio transport to a server running as a child process.
+//!
+//! [`StdioTransport`] speaks over the current process's own stdin and
+//! stdout, which is the server side of a stdio connection. A client
+//! launching the server itself uses [`ChildProcessTransport`] instead: it
+//! spawns the server command described by a [`ChildProcessCommand`] and
+//! exchanges messages over the child's stdin and stdout, with the same
+//! Content-Length framing.
+//!
+//! The child's stderr is read line by line and logged through `tracing`
+//! under the `pmcp::child_process` target. A child that crashes can be
+//! restarted according to a [`RestartPolicy`], and the child is killed when
+//! the transport is dropped.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::shared::{ChildProcessCommand, ChildProcessTransport, RestartPolicy};
+//! use pmcp::{Client, ClientCapabilities};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let command = ChildProcessCommand::new("my-mcp-server")
+//!     .args(["--root", "/srv/data"])
+//!     .env("RUST_LOG", "info")
+//!     .current_dir("/srv")
+//!     .restart_policy(RestartPolicy::OnCrash {
+//!         max_restarts: 3,
+//!         delay: Duration::from_millis(500),
+//!     });
+//!
+//! let transport = ChildProcessTransport::spawn(command)?;
+//! let mut client = Client::new(transport);
+//! client.initialize(ClientCapabilities::default()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result, TransportError};
+use crate::shared::protocol::MessageSizeLimits;
+use crate::shared::stdio::{write_frame, FrameProgress, StdioTransport};
+use crate::shared::timing::{self, Phase};
+use crate::shared::transport::{Transport, TransportMessage};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+/// How long [`ChildProcessTransport::close`] waits for the child to exit
+/// after closing its stdin, before killing it.
+const CLOSE_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// When a crashed child process is restarted.
+///
+/// A child crashes when it exits unsuccessfully or closes its stdout or
+/// stdin while the transport is open. A child exiting successfully closes
+/// the transport under every policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RestartPolicy {
+    /// Close the transport when the child crashes.
+    #[default]
+    Never,
+
+    /// Restart the child after it crashes, waiting `delay` first, up to
+    /// `max_restarts` times over the life of the transport.
+    OnCrash {
+        /// Maximum number of restarts
+        max_restarts: u32,
+        /// Delay before each restart
+        delay: Duration,
+    },
+}
+
+/// The server command a [`ChildProcessTransport`] runs.
+///
+/// The child inherits the environment of the current process, with the
+/// variables set by [`env`](Self::env) added.
+#[derive(Debug, Clone)]
+pub struct ChildProcessCommand {
+    program: OsString,
+    args: Vec<OsString>,
+    env: Vec<(OsString, OsString)>,
+    current_dir: Option<PathBuf>,
+    restart_policy: RestartPolicy,
+}
+
+impl ChildProcessCommand {
+    /// Run `program`, looked up in `PATH` unless it is a path.
+    pub fn new(program: impl Into<OsString>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            env: Vec::new(),
+            current_dir: None,
+            restart_policy: RestartPolicy::default(),
+        }
+    }
+
+    /// Add an argument.
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Add several arguments.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set an environment variable for the child.
+    pub fn env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Run the child in `dir` instead of the current directory.
+    pub fn current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+
+    /// Restart the child when it crashes, as `policy` allows.
+    pub fn restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart_policy = policy;
+        self
+    }
+
+    /// The program being run, for log messages.
+    fn display_program(&self) -> String {
+        self.program.to_string_lossy().into_owned()
+    }
+}
+
+/// A running child and the ends of its stdio pipes.
+#[derive(Debug)]
+struct Process {
+    child: Child,
+    /// Taken when the transport closes, so the child sees EOF
+    stdin: Option<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Process {
+    fn spawn(command: &ChildProcessCommand) -> Result<Self> {
+        let mut cmd = Command::new(&command.program);
+        cmd.args(&command.args)
+            .envs(command.env.iter().map(|(key, value)| (key, value)))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(dir) = &command.current_dir {
+            cmd.current_dir(dir);
+        }
+
+        let mut child = cmd.spawn().map_err(|e| {
+            Error::from(TransportError::Io(std::io::Error::new(
+                e.kind(),
+                format!("Failed to spawn '{}': {}", command.display_program(), e),
+            )))
+        })?;
+        let stdin = child.stdin.take();
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| Error::internal("Child process has no stdout"))?;
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(log_stderr(
+                command.display_program(),
+                child.id(),
+                BufReader::new(stderr),
+            ));
+        }
+        tracing::debug!(
+            "Spawned '{}' with pid {:?}",
+            command.display_program(),
+            child.id()
+        );
+
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        })
+    }
+}
+
+/// Log each line the child writes to stderr until it closes it.
+async fn log_stderr(
+    program: String,
+    pid: Option<u32>,
+    stderr: BufReader<tokio::process::ChildStderr>,
+) {
+    let mut lines = stderr.lines();
+    loop {
+        match lines.next_line().await {
+            Ok(Some(line)) => {
+                tracing::info!(target: "pmcp::child_process", program = %program, pid, "{}", line);
+            },
+            Ok(None) => break,
+            Err(e) => {
+                tracing::warn!(target: "pmcp::child_process", program = %program, pid, "Failed to read stderr: {}", e);
+                break;
+            },
+        }
+    }
+}
+
+/// stdio transport to a server spawned as a child process.
+///
+/// See the [module documentation](self). Receiving is cancel-safe, as for
+/// [`StdioTransport`].
+///
+/// A restarted child is a new server process: requests it had not answered
+/// are lost, and the client is responsible for initializing it again if the
+/// server requires it.
+#[derive(Debug)]
+pub struct ChildProcessTransport {
+    command: ChildProcessCommand,
+    process: Process,
+    restarts: u32,
+    /// Messages from a received batch that have not been returned yet
+    pending: VecDeque<TransportMessage>,
+    /// Limits on the size of framed messages
+    limits: MessageSizeLimits,
+    /// Progress through the frame being read
+    frame: FrameProgress,
+    closed: bool,
+}
+
+impl ChildProcessTransport {
+    /// Spawn the child described by `command`.
+    ///
+    /// Must be called within a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the child cannot be spawned.
+    pub fn spawn(command: ChildProcessCommand) -> Result<Self> {
+        let process = Process::spawn(&command)?;
+        Ok(Self {
+            command,
+            process,
+            restarts: 0,
+            pending: VecDeque::new(),
+            limits: MessageSizeLimits::default(),
+            frame: FrameProgress::default(),
+            closed: false,
+        })
+    }
+
+    /// Enforce limits on the size of sent and received messages.
+    ///
+    /// Limits behave as for [`StdioTransport::with_message_size_limits`].
+    pub fn with_message_size_limits(mut self, limits: MessageSizeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Process ID of the running child, if it has not been reaped.
+    pub fn pid(&self) -> Option<u32> {
+        self.process.child.id()
+    }
+
+    /// Number of times the child has been restarted.
+    pub fn restart_count(&self) -> u32 {
+        self.restarts
+    }
+
+    fn ensure_open(&self) -> Result<()> {
+        if self.closed {
+            return Err(TransportError::ConnectionClosed.into());
+        }
+        Ok(())
+    }
+
+    /// Handle the child's end of a pipe closing.
+    ///
+    /// Waits for the child to exit, then restarts it if it crashed and the
+    /// policy allows; otherwise closes the transport and returns
+    /// `ConnectionClosed`.
+    async fn child_gone(&mut self) -> Result<()> {
+        let status = self.reap().await;
+        let crashed = status.is_none_or(|status| !status.success());
+        tracing::warn!(
+            "Child process '{}' exited ({})",
+            self.command.display_program(),
+            status.map_or_else(|| "unknown status".to_string(), |status| status.to_string())
+        );
+
+        let RestartPolicy::OnCrash {
+            max_restarts,
+            delay,
+        } = self.command.restart_policy
+        else {
+            return self.give_up();
+        };
+        if !crashed || self.restarts >= max_restarts {
+            return self.give_up();
+        }
+
+        tokio::time::sleep(delay).await;
+        self.process = match Process::spawn(&self.command) {
+            Ok(process) => process,
+            Err(e) => {
+                self.closed = true;
+                return Err(e);
+            },
+        };
+        self.restarts += 1;
+        self.frame = FrameProgress::default();
+        tracing::info!(
+            "Restarted child process '{}' ({} of {})",
+            self.command.display_program(),
+            self.restarts,
+            max_restarts
+        );
+        Ok(())
+    }
+
+    fn give_up(&mut self) -> Result<()> {
+        self.closed = true;
+        Err(TransportError::ConnectionClosed.into())
+    }
+
+    /// Wait for the child to exit, killing it if it closed a pipe but kept
+    /// running.
+    async fn reap(&mut self) -> Option<ExitStatus> {
+        let child = &mut self.process.child;
+        if let Ok(status) = tokio::time::timeout(CLOSE_GRACE_PERIOD, child.wait()).await {
+            return status.ok();
+        }
+        let _ = child.start_kill();
+        child.wait().await.ok()
+    }
+
+    /// Write a frame, restarting the child and retrying if it crashed.
+    async fn write_message(&mut self, json_bytes: &[u8]) -> Result<()> {
+        loop {
+            let written = match self.process.stdin.as_mut() {
+                Some(stdin) => write_frame(stdin, json_bytes).await,
+                None => return Err(TransportError::ConnectionClosed.into()),
+            };
+            match written {
+                Ok(()) => return Ok(()),
+                Err(e) if !matches!(e, Error::Transport(_)) => return Err(e),
+                Err(_) => self.child_gone().await?,
+            }
+        }
+    }
+
+    /// Answer every request in a rejected frame with `error`.
+    async fn reject_frame(&mut self, buffer: &[u8], error: Error) -> Result<()> {
+        for response in StdioTransport::rejections(buffer, error) {
+            let json_bytes =
+                StdioTransport::serialize_message(&TransportMessage::Response(response))?;
+            self.write_message(&json_bytes).await?;
+        }
+        Ok(())
+    }
+
+    /// Read the body of the current frame, or `None` if the child went
+    /// away and was restarted.
+    async fn read_body(&mut self, content_length: usize) -> Result<Option<Vec<u8>>> {
+        let body = self
+            .frame
+            .read_body(&mut self.process.stdout, content_length)
+            .await?;
+        if body.is_none() {
+            self.child_gone().await?;
+        }
+        Ok(body)
+    }
+}
+
+#[async_trait]
+impl Transport for ChildProcessTransport {
+    async fn send(&mut self, message: TransportMessage) -> Result<()> {
+        self.ensure_open()?;
+        let json_bytes = StdioTransport::serialize_within_limits(message, &self.limits)?;
+        self.write_message(&json_bytes).await
+    }
+
+    async fn send_batch(&mut self, messages: Vec<TransportMessage>) -> Result<()> {
+        self.ensure_open()?;
+        let json_bytes = StdioTransport::serialize_batch(&messages)?;
+        self.limits.check_outbound(json_bytes.len())?;
+        self.write_message(&json_bytes).await
+    }
+
+    async fn receive(&mut self) -> Result<TransportMessage> {
+        self.ensure_open()?;
+
+        loop {
+            if let Some(message) = self.pending.pop_front() {
+                return Ok(message);
+            }
+
+            let Some(content_length) = self.frame.read_headers(&mut self.process.stdout).await?
+            else {
+                self.child_gone().await?;
+                continue;
+            };
+            if let Err(e) = self.limits.check_inbound(content_length) {
+                if matches!(e, Error::Transport(_)) {
+                    self.closed = true;
+                    return Err(e);
+                }
+                if let Some(buffer) = self.read_body(content_length).await? {
+                    self.reject_frame(&buffer, e).await?;
+                }
+                continue;
+            }
+            let Some(buffer) = self.read_body(content_length).await? else {
+                continue;
+            };
+            if let Err(e) = self.limits.check_inbound_structure(&buffer) {
+                self.reject_frame(&buffer, e).await?;
+                continue;
+            }
+            self.pending.extend(timing::time_sync(Phase::Parse, || {
+                StdioTransport::parse_batch(&buffer)
+            })?);
+        }
+    }
+
+    async fn receive_batch(&mut self) -> Result<Vec<TransportMessage>> {
+        // Receiving parses a whole frame and leaves the rest of a batch
+        // pending.
+        if self.pending.is_empty() {
+            let first = self.receive().await?;
+            self.pending.push_front(first);
+        }
+        Ok(self.pending.drain(..).collect())
+    }
+
+    /// Close the child's stdin, and kill the child if it has not exited
+    /// shortly after.
+    async fn close(&mut self) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+        self.process.stdin = None;
+        self.reap().await;
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        !self.closed
+    }
+
+    fn transport_type(&self) -> &'static str {
+        "child-process"
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::types::{ClientRequest, Request, RequestId};
+
+    fn ping(id: i64) -> TransportMessage {
+        TransportMessage::Request {
+            id: RequestId::from(id),
+            request: Request::Client(Box::new(ClientRequest::Ping)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exchanges_frames_with_child() {
+        // `cat` echoes every frame back
+        let mut transport = ChildProcessTransport::spawn(ChildProcessCommand::new("cat")).unwrap();
+        assert!(transport.pid().is_some());
+
+        transport.send(ping(1)).await.unwrap();
+        transport.send_batch(vec![ping(2), ping(3)]).await.unwrap();
+        let TransportMessage::Request { id, .. } = transport.receive().await.unwrap() else {
+            panic!("expected a request");
+        };
+        assert_eq!(id, RequestId::from(1i64));
+        assert_eq!(transport.receive_batch().await.unwrap().len(), 2);
+
+        transport.close().await.unwrap();
+        assert!(!transport.is_connected());
+        assert!(transport.receive().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_restarts_crashed_child_per_policy() {
+        let command = ChildProcessCommand::new("sh")
+            .args(["-c", "echo \"failing in $PWD\" >&2; exit 3"])
+            .current_dir("/")
+            .restart_policy(RestartPolicy::OnCrash {
+                max_restarts: 2,
+                delay: Duration::from_millis(10),
+            });
+        let mut transport = ChildProcessTransport::spawn(command).unwrap();
+
+        assert!(transport.receive().await.is_err());
+        assert_eq!(transport.restart_count(), 2);
+        assert!(!transport.is_connected());
+
+        // A clean exit is not a crash
+        let command = ChildProcessCommand::new("sh")
+            .args(["-c", "exit 0"])
+            .restart_policy(RestartPolicy::OnCrash {
+                max_restarts: 2,
+                delay: Duration::from_millis(10),
+            });
+        let mut transport = ChildProcessTransport::spawn(command).unwrap();
+        assert!(transport.receive().await.is_err());
+        assert_eq!(transport.restart_count(), 0);
+
+        assert!(ChildProcessTransport::spawn(ChildProcessCommand::new("/nonexistent")).is_err());
+    }
+}
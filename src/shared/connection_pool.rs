@@ -11,10 +11,13 @@
 //! - Health checking and automatic failover
 //! - Connection lifecycle management
 
-use crate::error::{Error, Result};
+use crate::error::{Error, ErrorCategory, Result, TransportError};
+use crate::shared::reconnect::{JitterStrategy, ReconnectConfig, ReconnectManager};
 use crate::shared::{Transport, TransportMessage};
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::{ClientNotification, ClientRequest, Notification, Request, RequestId};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, RwLock};
@@ -24,6 +27,12 @@ use uuid::Uuid;
 /// Unique identifier for a connection in the pool
 pub type ConnectionId = Uuid;
 
+/// Weight of the newest sample in rolling latency and error rate averages
+const ROLLING_WEIGHT: f64 = 0.1;
+
+/// Factory creating new transports for the pool
+pub type ConnectionFactory<T> = Arc<dyn Fn() -> Result<T> + Send + Sync>;
+
 /// Load balancing strategies
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LoadBalanceStrategy {
@@ -35,6 +44,8 @@ pub enum LoadBalanceStrategy {
     WeightedRoundRobin,
     /// Random selection
     Random,
+    /// Prefer connections with low rolling latency and error rate
+    AdaptiveLatency,
 }
 
 /// Connection health status
@@ -71,6 +82,12 @@ pub struct ConnectionPoolConfig {
     pub max_retries: usize,
     /// Retry delay
     pub retry_delay: Duration,
+    /// Minimum number of idle connections kept ready, replenished in the
+    /// background on every health check
+    pub min_idle: usize,
+    /// Backoff for retrying connections that fail during warm-up and
+    /// replenishment
+    pub warmup_reconnect: ReconnectConfig,
 }
 
 impl Default for ConnectionPoolConfig {
@@ -85,6 +102,15 @@ impl Default for ConnectionPoolConfig {
             auto_scaling: true,
             max_retries: 3,
             retry_delay: Duration::from_secs(1),
+            min_idle: 0,
+            warmup_reconnect: ReconnectConfig {
+                initial_delay: Duration::from_millis(100),
+                max_delay: Duration::from_secs(5),
+                max_retries: Some(3),
+                jitter: JitterStrategy::Full,
+                circuit_breaker_threshold: None,
+                ..Default::default()
+            },
         }
     }
 }
@@ -110,6 +136,31 @@ pub struct ConnectionInfo {
     pub avg_latency: Duration,
     /// Error count in recent window
     pub recent_errors: usize,
+    /// Rolling error rate (0.0 to 1.0)
+    pub error_rate: f64,
+}
+
+impl ConnectionInfo {
+    /// Fold the outcome of one request into the rolling averages
+    fn record_outcome(&mut self, latency: Option<Duration>, success: bool) {
+        if let Some(latency) = latency {
+            self.avg_latency = Duration::from_secs_f64(
+                self.avg_latency
+                    .as_secs_f64()
+                    .mul_add(1.0 - ROLLING_WEIGHT, latency.as_secs_f64() * ROLLING_WEIGHT),
+            );
+        }
+        let sample = if success { 0.0 } else { 1.0 };
+        self.error_rate = self
+            .error_rate
+            .mul_add(1.0 - ROLLING_WEIGHT, sample * ROLLING_WEIGHT);
+    }
+
+    /// Load balancing cost, lower is better
+    fn adaptive_score(&self) -> f64 {
+        let load = 1.0 + self.active_requests as f64;
+        self.avg_latency.as_secs_f64() * load / (1.0 - self.error_rate).max(0.01)
+    }
 }
 
 /// Pooled connection wrapper
@@ -134,6 +185,9 @@ pub struct ConnectionPool<T: Transport> {
     connections: Arc<RwLock<HashMap<ConnectionId, PooledConnection<T>>>>,
     round_robin_index: Arc<RwLock<usize>>,
     health_checker: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+    factory: Option<ConnectionFactory<T>>,
+    /// Set when a failure that new connections would repeat stops replenishing
+    replenish_halted: Arc<parking_lot::Mutex<Option<ErrorCategory>>>,
 }
 
 impl<T: Transport + Clone + Send + Sync + 'static> std::fmt::Debug for ConnectionPool<T> {
@@ -152,23 +206,50 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
             connections: Arc::new(RwLock::new(HashMap::new())),
             round_robin_index: Arc::new(RwLock::new(0)),
             health_checker: Arc::new(RwLock::new(None)),
+            factory: None,
+            replenish_halted: Arc::new(parking_lot::Mutex::new(None)),
         }
     }
 
-    /// Start the connection pool and initialize minimum connections
-    #[allow(clippy::cognitive_complexity)]
+    /// The pool's configuration
+    pub fn config(&self) -> &ConnectionPoolConfig {
+        &self.config
+    }
+
+    /// Another handle to the same pool, for background tasks
+    fn handle(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            connections: self.connections.clone(),
+            round_robin_index: self.round_robin_index.clone(),
+            health_checker: self.health_checker.clone(),
+            factory: self.factory.clone(),
+            replenish_halted: self.replenish_halted.clone(),
+        }
+    }
+
+    /// Start the connection pool and warm it up
+    ///
+    /// Opens `max(min_connections, min_idle)` connections before returning,
+    /// retrying failed attempts with the `warmup_reconnect` backoff, so the
+    /// first requests do not pay connection-establishment latency.
     pub async fn start<F>(&mut self, connection_factory: F) -> Result<()>
     where
         F: Fn() -> Result<T> + Send + Sync + 'static,
     {
-        info!(
-            "Starting connection pool with {} min connections",
-            self.config.min_connections
-        );
+        let target = self
+            .config
+            .min_connections
+            .max(self.config.min_idle)
+            .min(self.config.max_connections);
+        info!("Starting connection pool with {} warm connections", target);
+
+        self.factory = Some(Arc::new(connection_factory));
 
         // Create initial connections
-        for _ in 0..self.config.min_connections {
-            match connection_factory() {
+        let attempts = (0..target).map(|_| self.connect_with_retry());
+        for result in futures::future::join_all(attempts).await {
+            match result {
                 Ok(transport) => {
                     if let Err(e) = self.add_connection(transport).await {
                         warn!("Failed to add initial connection: {}", e);
@@ -176,6 +257,7 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
                 },
                 Err(e) => {
                     warn!("Failed to create initial connection: {}", e);
+                    self.halt_replenishing_if_unrecoverable(&e);
                 },
             }
         }
@@ -186,6 +268,128 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
         Ok(())
     }
 
+    /// Create a transport, retrying failures with jittered backoff
+    async fn connect_with_retry(&self) -> Result<T> {
+        let factory = self
+            .factory
+            .clone()
+            .ok_or_else(|| Error::internal("Connection pool has not been started"))?;
+        let manager = ReconnectManager::new(self.config.warmup_reconnect.clone());
+        let connected = parking_lot::Mutex::new(None);
+
+        manager
+            .reconnect_with(|| {
+                let result = factory();
+                let connected = &connected;
+                async move {
+                    *connected.lock() = Some(result?);
+                    Ok(())
+                }
+            })
+            .await?;
+
+        connected
+            .into_inner()
+            .ok_or_else(|| Error::internal("Connection factory returned no transport"))
+    }
+
+    /// Number of healthy connections without active requests
+    pub async fn idle_connections(&self) -> usize {
+        self.connections
+            .read()
+            .await
+            .values()
+            .filter(|conn| {
+                conn.info.health == HealthStatus::Healthy && conn.info.active_requests == 0
+            })
+            .count()
+    }
+
+    /// Open connections until `min_idle` are idle or the pool is full
+    ///
+    /// Called after every health check once the pool is started. Returns the
+    /// number of connections added.
+    pub async fn replenish(&self) -> usize {
+        let idle = self.idle_connections().await;
+        let total = self.connections.read().await.len();
+        let missing = self
+            .config
+            .min_idle
+            .saturating_sub(idle)
+            .min(self.config.max_connections.saturating_sub(total));
+        if missing == 0 || self.factory.is_none() {
+            return 0;
+        }
+        if let Some(category) = self.replenish_halted() {
+            debug!("Not replenishing after {:?} failure", category);
+            return 0;
+        }
+
+        debug!("Replenishing {} idle connections", missing);
+        let attempts = (0..missing).map(|_| self.connect_with_retry());
+        let mut added = 0;
+        for result in futures::future::join_all(attempts).await {
+            match result {
+                Ok(transport) => match self.add_connection(transport).await {
+                    Ok(_) => added += 1,
+                    Err(e) => warn!("Failed to add replenished connection: {}", e),
+                },
+                Err(e) => {
+                    warn!("Failed to replenish connection: {}", e);
+                    self.halt_replenishing_if_unrecoverable(&e);
+                },
+            }
+        }
+        added
+    }
+
+    /// Category of the failure that stopped replenishing, if any
+    ///
+    /// Connection failures that a new connection would repeat, such as
+    /// rejected credentials, stop [`replenish`](Self::replenish) from opening
+    /// connections until [`resume_replenishing`](Self::resume_replenishing)
+    /// is called.
+    pub fn replenish_halted(&self) -> Option<ErrorCategory> {
+        *self.replenish_halted.lock()
+    }
+
+    /// Allow replenishing again, e.g. after credentials were refreshed
+    pub fn resume_replenishing(&self) {
+        *self.replenish_halted.lock() = None;
+    }
+
+    fn halt_replenishing_if_unrecoverable(&self, error: &Error) {
+        let category = error.category();
+        if !category.is_recoverable() {
+            error!(
+                "Stopping connection replenishment after {:?} failure: {}",
+                category, error
+            );
+            *self.replenish_halted.lock() = Some(category);
+        }
+    }
+
+    /// React to a transport failure on a pooled connection
+    ///
+    /// Connections that failed recoverably are dropped so replenishment can
+    /// replace them. Unrecoverable failures mark the connection unhealthy and
+    /// halt replenishment, since a replacement would fail the same way.
+    async fn on_transport_error(&self, id: ConnectionId, error: &Error) {
+        let category = error.category();
+        if category.is_recoverable() {
+            let removed = self.connections.write().await.remove(&id);
+            if let Some(mut conn) = removed {
+                warn!("Dropping connection {} after {:?} failure", id, category);
+                let _ = conn.transport.close().await;
+            }
+        } else {
+            if let Some(conn) = self.connections.write().await.get_mut(&id) {
+                conn.info.health = HealthStatus::Unhealthy;
+            }
+            self.halt_replenishing_if_unrecoverable(error);
+        }
+    }
+
     /// Add a new connection to the pool
     pub async fn add_connection(&self, transport: T) -> Result<ConnectionId> {
         let mut connections = self.connections.write().await;
@@ -202,16 +406,19 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
 
         // Start message forwarding tasks
         let mut transport_send = transport.clone();
+        let pool = self.handle();
         tokio::spawn(async move {
             while let Some(msg) = send_rx.recv().await {
                 if let Err(e) = transport_send.send(msg).await {
                     error!("Failed to send message through transport: {}", e);
+                    pool.on_transport_error(id, &e).await;
                     break;
                 }
             }
         });
 
         let mut transport_recv = transport.clone();
+        let pool = self.handle();
         tokio::spawn(async move {
             loop {
                 match transport_recv.receive().await {
@@ -223,6 +430,7 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
                     },
                     Err(e) => {
                         error!("Transport receive error: {}", e);
+                        pool.on_transport_error(id, &e).await;
                         break;
                     },
                 }
@@ -239,6 +447,7 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
             created_at: now,
             avg_latency: Duration::from_millis(10),
             recent_errors: 0,
+            error_rate: 0.0,
         };
 
         let pooled = PooledConnection {
@@ -304,6 +513,9 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
                 Self::select_weighted_round_robin(self, &healthy_connections)
             },
             LoadBalanceStrategy::Random => Self::select_random(self, &healthy_connections),
+            LoadBalanceStrategy::AdaptiveLatency => {
+                Self::select_adaptive_latency(&healthy_connections)
+            },
         };
 
         Ok(selected_id)
@@ -338,6 +550,7 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
         conn.info.total_requests += 1;
 
         if result.is_err() {
+            conn.info.record_outcome(None, false);
             conn.info.recent_errors += 1;
             conn.info.health = if conn.info.recent_errors > 5 {
                 HealthStatus::Degraded
@@ -347,19 +560,51 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
             return Err(Error::internal("Failed to send message"));
         }
 
-        // Update latency (simplified moving average)
-        let latency = start_time.elapsed();
-        #[allow(clippy::cast_sign_loss)]
-        {
-            conn.info.avg_latency = Duration::from_nanos(
-                (conn.info.avg_latency.as_nanos() as f64)
-                    .mul_add(0.9, latency.as_nanos() as f64 * 0.1) as u64,
-            );
-        }
+        conn.info.record_outcome(Some(start_time.elapsed()), true);
 
         Ok(())
     }
 
+    /// Record the outcome of a request handled by a connection
+    ///
+    /// Callers that measure full round trips should report them here so
+    /// [`LoadBalanceStrategy::AdaptiveLatency`] sees end-to-end latency
+    /// rather than just the time to queue a message.
+    pub async fn record_outcome(
+        &self,
+        connection_id: ConnectionId,
+        latency: Duration,
+        success: bool,
+    ) -> Result<()> {
+        let mut connections = self.connections.write().await;
+        let conn = connections
+            .get_mut(&connection_id)
+            .ok_or_else(|| Error::internal("Connection not found"))?;
+        conn.info
+            .record_outcome(success.then_some(latency), success);
+        Ok(())
+    }
+
+    /// Receive the next message from a specific connection
+    ///
+    /// Fails with [`TransportError::ConnectionClosed`] once the connection
+    /// has closed or been removed from the pool.
+    pub async fn receive_from_connection(
+        &self,
+        connection_id: ConnectionId,
+    ) -> Result<TransportMessage> {
+        // Release the pool lock before waiting so other connections keep working
+        let receiver = self
+            .connections
+            .read()
+            .await
+            .get(&connection_id)
+            .map(|conn| conn.recv_rx.clone())
+            .ok_or(Error::Transport(TransportError::ConnectionClosed))?;
+        let message = receiver.write().await.recv().await;
+        message.ok_or(Error::Transport(TransportError::ConnectionClosed))
+    }
+
     /// Receive message from any connection in the pool
     pub async fn receive_message(&self) -> Result<(ConnectionId, TransportMessage)> {
         let connections = self.connections.read().await;
@@ -417,6 +662,18 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
 
         let active_requests: usize = connections.values().map(|c| c.info.active_requests).sum();
 
+        let (avg_latency, error_rate) = if connections.is_empty() {
+            (Duration::ZERO, 0.0)
+        } else {
+            let count = connections.len() as f64;
+            let latency: f64 = connections
+                .values()
+                .map(|c| c.info.avg_latency.as_secs_f64())
+                .sum();
+            let errors: f64 = connections.values().map(|c| c.info.error_rate).sum();
+            (Duration::from_secs_f64(latency / count), errors / count)
+        };
+
         PoolStats {
             total_connections,
             healthy_connections: healthy_count,
@@ -425,6 +682,9 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
             total_requests,
             active_requests,
             strategy: self.config.strategy,
+            avg_latency,
+            error_rate,
+            connections: connections.values().map(|c| c.info.clone()).collect(),
         }
     }
 
@@ -433,7 +693,7 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
         let (tx, mut rx) = mpsc::channel(1);
         *self.health_checker.write().await = Some(tx);
 
-        let connections = self.connections.clone();
+        let pool = self.handle();
         let interval = self.config.health_check_interval;
 
         tokio::spawn(async move {
@@ -442,7 +702,8 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
             loop {
                 tokio::select! {
                     _ = ticker.tick() => {
-                        Self::perform_health_check(&connections).await;
+                        Self::perform_health_check(&pool.connections).await;
+                        pool.replenish().await;
                     }
                     _ = rx.recv() => {
                         info!("Health checker shutting down");
@@ -541,6 +802,16 @@ impl<T: Transport + Clone + Send + Sync + 'static> ConnectionPool<T> {
         best.info.id
     }
 
+    /// Lowest latency, adjusted for load and error rate
+    fn select_adaptive_latency(connections: &[&PooledConnection<T>]) -> ConnectionId {
+        connections
+            .iter()
+            .min_by(|a, b| a.info.adaptive_score().total_cmp(&b.info.adaptive_score()))
+            .unwrap()
+            .info
+            .id
+    }
+
     /// Random selection
     fn select_random(_self: &Self, connections: &[&PooledConnection<T>]) -> ConnectionId {
         #![allow(clippy::unused_self)]
@@ -592,11 +863,32 @@ pub struct PoolStats {
     pub active_requests: usize,
     /// Current load balancing strategy
     pub strategy: LoadBalanceStrategy,
+    /// Mean rolling latency across connections
+    pub avg_latency: Duration,
+    /// Mean rolling error rate across connections
+    pub error_rate: f64,
+    /// Per-connection statistics
+    pub connections: Vec<ConnectionInfo>,
 }
 
 /// Pooled transport that implements the Transport trait
+///
+/// Each request goes to the connection the pool's strategy selects, and its
+/// response is read from that same connection. Every pooled connection is a
+/// separate MCP session, so the client's `initialize` handshake is replayed
+/// on each connection before its first request. When a connection fails
+/// while a request is outstanding, the request is resent on another healthy
+/// connection, up to the pool's `max_retries`.
 pub struct PooledTransport<T: Transport> {
     pool: Arc<ConnectionPool<T>>,
+    /// Connection the last request went out on
+    current: Option<ConnectionId>,
+    /// Request awaiting its response, and when it was sent
+    pending: Option<(TransportMessage, Instant)>,
+    /// The client's `initialize` request and `initialized` notification
+    handshake: Vec<TransportMessage>,
+    /// Connections that have completed the handshake
+    initialized: HashSet<ConnectionId>,
 }
 
 impl<T: Transport + Clone + Send + Sync + 'static> PooledTransport<T> {
@@ -604,6 +896,10 @@ impl<T: Transport + Clone + Send + Sync + 'static> PooledTransport<T> {
     pub fn new(pool: ConnectionPool<T>) -> Self {
         Self {
             pool: Arc::new(pool),
+            current: None,
+            pending: None,
+            handshake: Vec::new(),
+            initialized: HashSet::new(),
         }
     }
 
@@ -611,23 +907,174 @@ impl<T: Transport + Clone + Send + Sync + 'static> PooledTransport<T> {
     pub fn pool(&self) -> &ConnectionPool<T> {
         &self.pool
     }
+
+    /// Send a request on a connection chosen by the pool, failing over to
+    /// other connections if sending fails
+    async fn send_request(&mut self, message: TransportMessage) -> Result<()> {
+        let mut attempts = 0;
+        loop {
+            match self.send_on_selected(message.clone()).await {
+                Ok(()) => {
+                    self.pending = Some((message, Instant::now()));
+                    return Ok(());
+                },
+                Err(e) if attempts < self.pool.config.max_retries => {
+                    attempts += 1;
+                    warn!("Pooled send failed, trying another connection: {}", e);
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn send_on_selected(&mut self, message: TransportMessage) -> Result<()> {
+        let id = self.pool.get_connection().await?;
+        self.current = Some(id);
+        if !self.initialized.contains(&id) && !self.handshake.is_empty() {
+            if let Err(e) = self.replay_handshake(id).await {
+                // A connection that cannot join the session is of no use
+                let _ = self.pool.remove_connection(id).await;
+                return Err(e);
+            }
+        }
+        // Failed connections are reported to the pool by their own tasks
+        self.pool.send_to_connection(id, message).await
+    }
+
+    /// Initialize a connection the way the client initialized the first one
+    async fn replay_handshake(&mut self, id: ConnectionId) -> Result<()> {
+        debug!("Initializing pooled connection {}", id);
+        for message in self.handshake.clone() {
+            let request_id = match &message {
+                TransportMessage::Request { id, .. } => Some(id.clone()),
+                _ => None,
+            };
+            self.pool.send_to_connection(id, message).await?;
+            if let Some(request_id) = request_id {
+                tokio::time::timeout(
+                    self.pool.config.operation_timeout,
+                    self.await_response(id, &request_id),
+                )
+                .await
+                .map_err(|_| {
+                    let timeout = self.pool.config.operation_timeout.as_millis();
+                    Error::timeout(u64::try_from(timeout).unwrap_or(u64::MAX))
+                })??;
+            }
+        }
+        self.initialized.insert(id);
+        Ok(())
+    }
+
+    async fn await_response(&self, id: ConnectionId, request_id: &RequestId) -> Result<()> {
+        loop {
+            match self.pool.receive_from_connection(id).await? {
+                TransportMessage::Response(response) if response.id == *request_id => {
+                    return match response.payload {
+                        ResponsePayload::Result(_) => Ok(()),
+                        ResponsePayload::Error(error) => Err(Error::from_jsonrpc_error(error)),
+                    };
+                },
+                other => debug!("Dropping message received during handshake: {:?}", other),
+            }
+        }
+    }
+
+    /// Send a message on the connection of the current session
+    async fn send_to_current(&mut self, message: TransportMessage) -> Result<()> {
+        match self.current {
+            Some(id) => self.pool.send_to_connection(id, message).await,
+            None => self.pool.send_message(message).await,
+        }
+    }
+
+    /// Resend the outstanding request after its connection failed
+    async fn fail_over(&mut self, error: Error) -> Result<()> {
+        let Some((message, _)) = self.pending.take() else {
+            return Err(error);
+        };
+        if let Some(id) = self.current.take() {
+            self.initialized.remove(&id);
+        }
+        warn!("Pooled connection failed, resending request: {}", error);
+        self.send_request(message).await
+    }
 }
 
 impl<T: Transport + Clone + Send + Sync + 'static> std::fmt::Debug for PooledTransport<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("PooledTransport").finish()
+        f.debug_struct("PooledTransport")
+            .field("current", &self.current)
+            .field("initialized", &self.initialized.len())
+            .finish()
     }
 }
 
 #[async_trait]
 impl<T: Transport + Clone + Send + Sync + 'static> Transport for PooledTransport<T> {
     async fn send(&mut self, message: TransportMessage) -> Result<()> {
-        self.pool.send_message(message).await
+        let initializing = matches!(
+            &message,
+            TransportMessage::Request { request: Request::Client(request), .. }
+                if matches!(**request, ClientRequest::Initialize(_))
+        );
+        match &message {
+            TransportMessage::Request { .. } if initializing => {
+                // A new session: connections initialized before must redo it
+                self.initialized.clear();
+                self.handshake.clear();
+                self.send_request(message.clone()).await?;
+                self.handshake.push(message);
+                if let Some(id) = self.current {
+                    self.initialized.insert(id);
+                }
+                Ok(())
+            },
+            TransportMessage::Request { .. } => self.send_request(message).await,
+            TransportMessage::Notification(Notification::Client(
+                ClientNotification::Initialized,
+            )) => {
+                self.handshake.push(message.clone());
+                self.send_to_current(message).await
+            },
+            // Answers and notifications belong to the session they concern
+            _ => self.send_to_current(message).await,
+        }
     }
 
     async fn receive(&mut self) -> Result<TransportMessage> {
-        let (_connection_id, message) = self.pool.receive_message().await?;
-        Ok(message)
+        loop {
+            let Some(id) = self.current else {
+                let (_connection_id, message) = self.pool.receive_message().await?;
+                return Ok(message);
+            };
+
+            let message = match self.pool.receive_from_connection(id).await {
+                Ok(message) => message,
+                Err(e) if self.pending.is_some() => {
+                    self.fail_over(e).await?;
+                    continue;
+                },
+                Err(e) => return Err(e),
+            };
+
+            if let TransportMessage::Response(response) = &message {
+                let answered = matches!(
+                    &self.pending,
+                    Some((TransportMessage::Request { id: request_id, .. }, _)) if *request_id == response.id
+                );
+                if answered {
+                    if let Some((_, sent_at)) = self.pending.take() {
+                        let success = matches!(response.payload, ResponsePayload::Result(_));
+                        let _ = self
+                            .pool
+                            .record_outcome(id, sent_at.elapsed(), success)
+                            .await;
+                    }
+                }
+            }
+            return Ok(message);
+        }
     }
 
     async fn close(&mut self) -> Result<()> {
@@ -680,4 +1127,248 @@ mod tests {
         assert_eq!(HealthStatus::Healthy, HealthStatus::Healthy);
         assert_ne!(HealthStatus::Healthy, HealthStatus::Degraded);
     }
+
+    #[derive(Debug, Clone)]
+    struct IdleTransport;
+
+    #[async_trait]
+    impl Transport for IdleTransport {
+        async fn send(&mut self, _message: TransportMessage) -> Result<()> {
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<TransportMessage> {
+            std::future::pending().await
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_retries_and_replenishes_idle_connections() {
+        let config = ConnectionPoolConfig {
+            min_connections: 1,
+            min_idle: 3,
+            max_connections: 5,
+            health_check_interval: Duration::from_secs(3600),
+            warmup_reconnect: ReconnectConfig {
+                initial_delay: Duration::from_millis(1),
+                ..ConnectionPoolConfig::default().warmup_reconnect
+            },
+            ..Default::default()
+        };
+        let mut pool = ConnectionPool::new(config);
+
+        // The first two attempts fail and are retried
+        let calls = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let factory_calls = calls.clone();
+        pool.start(move || {
+            if factory_calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) < 2 {
+                Err(Error::internal("refused"))
+            } else {
+                Ok(IdleTransport)
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(pool.get_stats().await.total_connections, 3);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 5);
+
+        let id = pool.get_connection().await.unwrap();
+        pool.remove_connection(id).await.unwrap();
+        assert_eq!(pool.idle_connections().await, 2);
+        assert_eq!(pool.replenish().await, 1);
+        assert_eq!(pool.idle_connections().await, 3);
+        assert_eq!(pool.replenish().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_auth_failures_halt_replenishing() {
+        let mut pool = ConnectionPool::new(ConnectionPoolConfig {
+            min_idle: 2,
+            health_check_interval: Duration::from_secs(3600),
+            warmup_reconnect: ReconnectConfig {
+                initial_delay: Duration::from_millis(1),
+                ..ConnectionPoolConfig::default().warmup_reconnect
+            },
+            ..Default::default()
+        });
+
+        let calls = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let factory_calls = calls.clone();
+        pool.start(move || {
+            factory_calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err::<IdleTransport, _>(Error::authentication("invalid token"))
+        })
+        .await
+        .unwrap();
+
+        // Each warm-up attempt gives up immediately instead of retrying
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(pool.replenish_halted(), Some(ErrorCategory::AuthFailure));
+        assert_eq!(pool.replenish().await, 0);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        pool.resume_replenishing();
+        assert_eq!(pool.replenish_halted(), None);
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_latency_prefers_fast_healthy_connections() {
+        let pool = ConnectionPool::new(ConnectionPoolConfig {
+            strategy: LoadBalanceStrategy::AdaptiveLatency,
+            ..Default::default()
+        });
+        let fast = pool.add_connection(IdleTransport).await.unwrap();
+        let slow = pool.add_connection(IdleTransport).await.unwrap();
+
+        for _ in 0..20 {
+            pool.record_outcome(fast, Duration::from_millis(5), true)
+                .await
+                .unwrap();
+            pool.record_outcome(slow, Duration::from_millis(200), true)
+                .await
+                .unwrap();
+        }
+        assert_eq!(pool.get_connection().await.unwrap(), fast);
+
+        // A fast connection that mostly fails loses to a slow reliable one
+        for _ in 0..40 {
+            pool.record_outcome(fast, Duration::from_millis(5), false)
+                .await
+                .unwrap();
+        }
+        assert_eq!(pool.get_connection().await.unwrap(), slow);
+
+        let stats = pool.get_stats().await;
+        assert_eq!(stats.connections.len(), 2);
+        assert!(stats.error_rate > 0.4);
+        assert!(stats.avg_latency > Duration::from_millis(5));
+    }
+
+    /// Answers requests like a server; clones share one connection
+    #[derive(Debug, Clone)]
+    struct ServerStub {
+        methods: Arc<parking_lot::Mutex<Vec<String>>>,
+        replies: mpsc::UnboundedSender<Result<TransportMessage>>,
+        inbox: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<Result<TransportMessage>>>>,
+        /// Set until a `tools/list` request has dropped a connection
+        drop_on_list: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl ServerStub {
+        fn new(drop_on_list: Arc<std::sync::atomic::AtomicBool>) -> Self {
+            let (replies, inbox) = mpsc::unbounded_channel();
+            Self {
+                methods: Arc::default(),
+                replies,
+                inbox: Arc::new(tokio::sync::Mutex::new(inbox)),
+                drop_on_list,
+            }
+        }
+
+        fn methods(&self) -> Vec<String> {
+            self.methods.lock().clone()
+        }
+    }
+
+    #[async_trait]
+    impl Transport for ServerStub {
+        async fn send(&mut self, message: TransportMessage) -> Result<()> {
+            match message {
+                TransportMessage::Request { id, request } => {
+                    let method = crate::shared::request_method(&request).to_string();
+                    self.methods.lock().push(method.clone());
+                    let result = match method.as_str() {
+                        "initialize" => serde_json::json!({
+                            "protocolVersion": crate::LATEST_PROTOCOL_VERSION,
+                            "capabilities": {"tools": {}},
+                            "serverInfo": {"name": "stub", "version": "1.0.0"}
+                        }),
+                        "tools/list" => {
+                            if self
+                                .drop_on_list
+                                .swap(false, std::sync::atomic::Ordering::SeqCst)
+                            {
+                                let _ = self
+                                    .replies
+                                    .send(Err(TransportError::ConnectionClosed.into()));
+                                return Ok(());
+                            }
+                            serde_json::json!({"tools": []})
+                        },
+                        _ => serde_json::json!({}),
+                    };
+                    let _ = self.replies.send(Ok(TransportMessage::Response(
+                        crate::types::JSONRPCResponse::success(id, result),
+                    )));
+                },
+                TransportMessage::Notification(Notification::Client(
+                    ClientNotification::Initialized,
+                )) => {
+                    self.methods
+                        .lock()
+                        .push("notifications/initialized".to_string());
+                },
+                _ => {},
+            }
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<TransportMessage> {
+            self.inbox
+                .lock()
+                .await
+                .recv()
+                .await
+                .unwrap_or_else(|| Err(TransportError::ConnectionClosed.into()))
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_over_pool_initializes_each_connection_and_fails_over() {
+        let drop_on_list = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let stubs = [
+            ServerStub::new(drop_on_list.clone()),
+            ServerStub::new(drop_on_list.clone()),
+        ];
+        let pool = ConnectionPool::new(ConnectionPoolConfig::default());
+        for stub in &stubs {
+            pool.add_connection(stub.clone()).await.unwrap();
+        }
+
+        let stats = pool.handle();
+        let mut client = crate::Client::with_pool(pool);
+        client
+            .initialize(crate::ClientCapabilities::default())
+            .await
+            .unwrap();
+        // Round-robin sends this to the connection not yet initialized
+        client.ping().await.unwrap();
+
+        let mut methods: Vec<_> = stubs.iter().map(ServerStub::methods).collect();
+        methods.sort_by_key(Vec::len);
+        assert_eq!(methods[0], ["initialize", "notifications/initialized"]);
+        assert_eq!(
+            methods[1],
+            ["initialize", "notifications/initialized", "ping"]
+        );
+
+        // The connection that drops the listing is replaced by the other one
+        let tools = client.list_tools(None).await.unwrap();
+        assert!(tools.tools.is_empty());
+        let lists: usize = stubs
+            .iter()
+            .map(|stub| stub.methods().iter().filter(|m| *m == "tools/list").count())
+            .sum();
+        assert_eq!(lists, 2);
+        assert_eq!(stats.get_stats().await.total_connections, 1);
+    }
 }
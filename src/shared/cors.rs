@@ -0,0 +1,264 @@
+//! Cross-origin resource sharing (CORS) for the HTTP servers.
+//!
+//! Browser-based clients can only talk to a server on another origin if its
+//! responses carry `Access-Control-*` headers. A [`CorsConfig`] describes
+//! which origins may call the server, and computes those headers for a
+//! request's `Origin`: [`StreamableHttpServer`] adds them to every response
+//! and answers preflight requests with them, and `WasmMcpServer` returns
+//! them for the platform wrapper to send.
+//!
+//! The default allows any origin without credentials, with the headers MCP
+//! clients send. Browser requests to the streamable HTTP server must also
+//! pass its [`OriginPolicy`], which by default only admits pages on
+//! localhost.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::shared::cors::CorsConfig;
+//! use std::time::Duration;
+//!
+//! let cors = CorsConfig::new()
+//!     .allow_origin("https://app.example.com")
+//!     .allow_header("Authorization")
+//!     .allow_credentials(true)
+//!     .max_age(Duration::from_secs(600));
+//!
+//! let headers = cors.headers(Some("https://app.example.com"));
+//! assert!(headers.contains(&(
+//!     "Access-Control-Allow-Origin",
+//!     "https://app.example.com".to_string()
+//! )));
+//! assert!(cors.headers(Some("https://evil.example.com")).is_empty());
+//! ```
+//!
+//! [`StreamableHttpServer`]: crate::server::streamable_http_server::StreamableHttpServer
+//! [`OriginPolicy`]: crate::server::origin::OriginPolicy
+
+use crate::shared::http_constants::{
+    ACCEPT, CONTENT_TYPE, LAST_EVENT_ID, MCP_PROTOCOL_VERSION, MCP_SESSION_ID,
+};
+use std::time::Duration;
+
+/// Which cross-origin requests browsers may make to a server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// Allowed origins; `None` allows any
+    origins: Option<Vec<String>>,
+    methods: Vec<String>,
+    headers: Vec<String>,
+    exposed_headers: Vec<String>,
+    max_age: Option<Duration>,
+    credentials: bool,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CorsConfig {
+    /// Allow any origin to use the MCP endpoint, without credentials.
+    pub fn new() -> Self {
+        Self {
+            origins: None,
+            methods: ["GET", "POST", "DELETE", "OPTIONS"]
+                .iter()
+                .map(|method| method.to_string())
+                .collect(),
+            headers: [
+                CONTENT_TYPE,
+                ACCEPT,
+                MCP_SESSION_ID,
+                MCP_PROTOCOL_VERSION,
+                LAST_EVENT_ID,
+            ]
+            .iter()
+            .map(|header| header.to_string())
+            .collect(),
+            exposed_headers: vec![MCP_SESSION_ID.to_string(), MCP_PROTOCOL_VERSION.to_string()],
+            max_age: Some(Duration::from_secs(24 * 60 * 60)),
+            credentials: false,
+        }
+    }
+
+    /// Only allow `origin`, such as `https://app.example.com`, and the
+    /// other listed origins.
+    pub fn allow_origin(mut self, origin: impl Into<String>) -> Self {
+        self.origins
+            .get_or_insert_with(Vec::new)
+            .push(origin.into());
+        self
+    }
+
+    /// Set the methods allowed in cross-origin requests.
+    pub fn allow_methods<I, S>(mut self, methods: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.methods = methods.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Also allow requests to send `header`, such as `Authorization`.
+    pub fn allow_header(mut self, header: impl Into<String>) -> Self {
+        self.headers.push(header.into());
+        self
+    }
+
+    /// Also let pages read response header `header`.
+    pub fn expose_header(mut self, header: impl Into<String>) -> Self {
+        self.exposed_headers.push(header.into());
+        self
+    }
+
+    /// Set how long browsers may cache a preflight response.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Whether requests may carry cookies and HTTP authentication.
+    ///
+    /// Credentialed responses name the request's origin instead of `*`.
+    /// Credentials need origins listed with [`allow_origin`](Self::allow_origin):
+    /// without a list, no origin is allowed, since any website could
+    /// otherwise make calls with the user's credentials.
+    pub fn allow_credentials(mut self, allow: bool) -> Self {
+        self.credentials = allow;
+        self
+    }
+
+    /// Whether pages at `origin` may call the server.
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        match &self.origins {
+            Some(origins) => origins
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(origin)),
+            None => !self.credentials,
+        }
+    }
+
+    /// Headers for the response to a request from `origin`.
+    ///
+    /// Empty for a request from an origin that is not allowed, which the
+    /// browser then does not let the page read.
+    pub fn headers(&self, origin: Option<&str>) -> Vec<(&'static str, String)> {
+        let allow_origin = match origin {
+            Some(origin) if self.allows_origin(origin) => {
+                if self.origins.is_none() {
+                    "*".to_string()
+                } else {
+                    origin.to_string()
+                }
+            },
+            Some(_) => return Vec::new(),
+            None if self.origins.is_none() && !self.credentials => "*".to_string(),
+            None => return Vec::new(),
+        };
+
+        let mut headers = Vec::new();
+        if allow_origin != "*" {
+            headers.push(("Vary", "Origin".to_string()));
+        }
+        headers.push(("Access-Control-Allow-Origin", allow_origin));
+        if self.credentials {
+            headers.push(("Access-Control-Allow-Credentials", "true".to_string()));
+        }
+        if !self.exposed_headers.is_empty() {
+            headers.push((
+                "Access-Control-Expose-Headers",
+                self.exposed_headers.join(", "),
+            ));
+        }
+        headers
+    }
+
+    /// Headers for the response to a preflight (`OPTIONS`) request from
+    /// `origin`.
+    pub fn preflight_headers(&self, origin: Option<&str>) -> Vec<(&'static str, String)> {
+        let mut headers = self.headers(origin);
+        if headers.is_empty() {
+            return headers;
+        }
+        headers.push(("Access-Control-Allow-Methods", self.methods.join(", ")));
+        headers.push(("Access-Control-Allow-Headers", self.headers.join(", ")));
+        if let Some(max_age) = self.max_age {
+            headers.push(("Access-Control-Max-Age", max_age.as_secs().to_string()));
+        }
+        headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header<'a>(headers: &'a [(&'static str, String)], name: &str) -> Option<&'a str> {
+        headers
+            .iter()
+            .find(|(header, _)| *header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn test_default_allows_any_origin() {
+        let cors = CorsConfig::default();
+        let headers = cors.preflight_headers(Some("https://app.example.com"));
+        assert_eq!(header(&headers, "Access-Control-Allow-Origin"), Some("*"));
+        assert_eq!(
+            header(&headers, "Access-Control-Allow-Methods"),
+            Some("GET, POST, DELETE, OPTIONS")
+        );
+        assert!(header(&headers, "Access-Control-Allow-Headers")
+            .unwrap()
+            .contains(MCP_SESSION_ID));
+        assert_eq!(header(&headers, "Access-Control-Max-Age"), Some("86400"));
+        assert_eq!(header(&headers, "Vary"), None);
+
+        let headers = cors.headers(None);
+        assert_eq!(
+            header(&headers, "Access-Control-Expose-Headers"),
+            Some("mcp-session-id, mcp-protocol-version")
+        );
+        assert_eq!(header(&headers, "Access-Control-Allow-Methods"), None);
+    }
+
+    #[test]
+    fn test_listed_origins_with_credentials() {
+        let cors = CorsConfig::new()
+            .allow_origin("https://app.example.com")
+            .allow_methods(["POST"])
+            .allow_credentials(true);
+
+        let headers = cors.preflight_headers(Some("https://app.example.com"));
+        assert_eq!(
+            header(&headers, "Access-Control-Allow-Origin"),
+            Some("https://app.example.com")
+        );
+        assert_eq!(header(&headers, "Vary"), Some("Origin"));
+        assert_eq!(
+            header(&headers, "Access-Control-Allow-Credentials"),
+            Some("true")
+        );
+        assert_eq!(
+            header(&headers, "Access-Control-Allow-Methods"),
+            Some("POST")
+        );
+
+        assert!(cors
+            .preflight_headers(Some("https://evil.example.com"))
+            .is_empty());
+        assert!(cors.headers(None).is_empty());
+
+        // Credentials are only allowed for listed origins
+        let cors = CorsConfig::new().allow_credentials(true);
+        assert!(!cors.allows_origin("https://other.example.com"));
+        assert!(cors.headers(Some("https://other.example.com")).is_empty());
+        assert!(cors
+            .preflight_headers(Some("https://other.example.com"))
+            .is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+//! Zero-copy parsing of JSON-RPC frames.
+//!
+//! [`parse_frame`] decodes a message straight from the bytes of its frame.
+//! Only the envelope is read up front; strings without escapes are borrowed,
+//! and `params`, `result` and `error` stay as raw slices of the frame until
+//! they are deserialized directly into their final types. No intermediate
+//! [`serde_json::Value`] tree is built for the message as a whole, which
+//! saves most of the per-message allocations when large tool arguments or
+//! results are piped through a server.
+//!
+//! UTF-8 is validated once up front, with the `simd` feature's vectorized
+//! validator when enabled, so the JSON parser can skip re-validating every
+//! string.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::shared::frame_parser::{parse_frame, parse_frame_batch};
+//! use pmcp::shared::TransportMessage;
+//!
+//! let frame = br#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"echo","arguments":{"text":"hi"}}}"#;
+//! assert!(matches!(parse_frame(frame)?, TransportMessage::Request { .. }));
+//!
+//! let batch = br#"[{"jsonrpc":"2.0","method":"notifications/initialized"},{"jsonrpc":"2.0","id":2,"result":{}}]"#;
+//! assert_eq!(parse_frame_batch(batch)?.len(), 2);
+//! # Ok::<(), pmcp::Error>(())
+//! ```
+
+use crate::error::{Result, TransportError};
+use crate::shared::protocol_helpers::{parse_notification_raw, parse_request_raw};
+use crate::shared::transport::TransportMessage;
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::{JSONRPCError, JSONRPCResponse, RequestId};
+use serde::{Deserialize, Deserializer};
+use serde_json::error::Category;
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+
+#[cfg(feature = "simd")]
+use crate::simd::framing::validate_utf8;
+
+/// Scalar counterpart of [`crate::simd::framing::validate_utf8`].
+#[cfg(not(feature = "simd"))]
+fn validate_utf8(input: &[u8]) -> Option<&str> {
+    std::str::from_utf8(input).ok()
+}
+
+/// The top-level members of a JSON-RPC message, borrowed from its frame.
+#[derive(Deserialize)]
+struct Envelope<'a> {
+    #[serde(default, borrow)]
+    jsonrpc: Option<Cow<'a, str>>,
+    #[serde(default, borrow)]
+    method: Option<Cow<'a, str>>,
+    #[serde(default, borrow, deserialize_with = "present")]
+    id: Option<&'a RawValue>,
+    #[serde(default, borrow)]
+    params: Option<&'a RawValue>,
+    #[serde(default, borrow, deserialize_with = "present")]
+    result: Option<&'a RawValue>,
+    #[serde(default, borrow, deserialize_with = "present")]
+    error: Option<&'a RawValue>,
+}
+
+/// Keep a member that is present but `null`, unlike a plain `Option`.
+fn present<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<&'de RawValue>, D::Error> {
+    <&RawValue>::deserialize(deserializer).map(Some)
+}
+
+/// Parse a single JSON-RPC message from a frame.
+pub fn parse_frame(bytes: &[u8]) -> Result<TransportMessage> {
+    parse_text(frame_text(bytes)?)
+}
+
+/// Parse a frame that may hold a JSON-RPC batch array.
+///
+/// A single message is returned as a one-element vector.
+pub fn parse_frame_batch(bytes: &[u8]) -> Result<Vec<TransportMessage>> {
+    let text = frame_text(bytes)?;
+    if !text.trim_start().starts_with('[') {
+        return Ok(vec![parse_text(text)?]);
+    }
+
+    let items: Vec<&RawValue> = serde_json::from_str(text).map_err(invalid_json)?;
+    if items.is_empty() {
+        return Err(TransportError::InvalidMessage("Empty batch".to_string()).into());
+    }
+    items
+        .into_iter()
+        .map(|item| parse_text(item.get()))
+        .collect()
+}
+
+/// Ids of the requests in a frame, read without decoding anything else.
+///
+/// Lets a transport answer every request in a frame it rejects before
+/// parsing; a frame that is not valid JSON yields none.
+pub(crate) fn request_ids(bytes: &[u8]) -> Vec<RequestId> {
+    /// Just enough of a message to tell a request and find its id.
+    #[derive(Deserialize)]
+    struct RequestEnvelope<'a> {
+        #[serde(default, borrow, deserialize_with = "present")]
+        method: Option<&'a RawValue>,
+        #[serde(default, borrow, deserialize_with = "present")]
+        id: Option<&'a RawValue>,
+    }
+
+    let Some(text) = validate_utf8(bytes) else {
+        return Vec::new();
+    };
+    let items: Vec<&RawValue> = if text.trim_start().starts_with('[') {
+        serde_json::from_str(text).unwrap_or_default()
+    } else {
+        serde_json::from_str(text)
+            .map(|item| vec![item])
+            .unwrap_or_default()
+    };
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let envelope: RequestEnvelope<'_> = serde_json::from_str(item.get()).ok()?;
+            envelope.method?;
+            serde_json::from_str(envelope.id?.get()).ok()
+        })
+        .collect()
+}
+
+fn frame_text(bytes: &[u8]) -> Result<&str> {
+    validate_utf8(bytes).ok_or_else(|| {
+        TransportError::InvalidMessage("Invalid JSON: message is not valid UTF-8".to_string())
+            .into()
+    })
+}
+
+fn invalid_json(error: serde_json::Error) -> crate::Error {
+    TransportError::InvalidMessage(format!("Invalid JSON: {}", error)).into()
+}
+
+fn invalid(kind: &str, error: impl std::fmt::Display) -> crate::Error {
+    TransportError::InvalidMessage(format!("Invalid {}: {}", kind, error)).into()
+}
+
+fn parse_text(text: &str) -> Result<TransportMessage> {
+    let envelope: Envelope<'_> = serde_json::from_str(text).map_err(|e| match e.classify() {
+        // Well-formed JSON that is not shaped like a JSON-RPC message
+        Category::Data => TransportError::InvalidMessage("Unknown message type".to_string()).into(),
+        _ => invalid_json(e),
+    })?;
+
+    match (&envelope.method, envelope.id) {
+        (Some(method), Some(id)) => {
+            let kind = "request";
+            if envelope.jsonrpc.is_none() {
+                return Err(invalid(kind, "missing field `jsonrpc`"));
+            }
+            let id: RequestId = serde_json::from_str(id.get()).map_err(|e| invalid(kind, e))?;
+            let (id, request) =
+                parse_request_raw(id, method, envelope.params).map_err(|e| invalid(kind, e))?;
+            Ok(TransportMessage::Request { id, request })
+        },
+        (Some(method), None) => {
+            let kind = "notification";
+            if envelope.jsonrpc.is_none() {
+                return Err(invalid(kind, "missing field `jsonrpc`"));
+            }
+            let notification =
+                parse_notification_raw(method, envelope.params).map_err(|e| invalid(kind, e))?;
+            Ok(TransportMessage::Notification(notification))
+        },
+        (None, id) if envelope.result.is_some() || envelope.error.is_some() => {
+            let kind = "response";
+            let jsonrpc = envelope
+                .jsonrpc
+                .ok_or_else(|| invalid(kind, "missing field `jsonrpc`"))?;
+            let id = id.ok_or_else(|| invalid(kind, "missing field `id`"))?;
+            let id: RequestId = serde_json::from_str(id.get()).map_err(|e| invalid(kind, e))?;
+            let payload = match (envelope.result, envelope.error) {
+                (Some(result), None) => ResponsePayload::Result(
+                    serde_json::from_str(result.get()).map_err(|e| invalid(kind, e))?,
+                ),
+                (None, Some(error)) => ResponsePayload::Error(
+                    serde_json::from_str::<JSONRPCError>(error.get())
+                        .map_err(|e| invalid(kind, e))?,
+                ),
+                _ => return Err(invalid(kind, "both `result` and `error` are present")),
+            };
+            Ok(TransportMessage::Response(JSONRPCResponse {
+                jsonrpc: jsonrpc.into_owned(),
+                id,
+                payload,
+            }))
+        },
+        _ => Err(TransportError::InvalidMessage("Unknown message type".to_string()).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ClientNotification, ClientRequest, Notification, Request};
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_frame_matches_value_parsing() {
+        let frames = [
+            json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}),
+            json!({"jsonrpc": "2.0", "id": "a", "method": "tools/list", "params": null}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call",
+                   "params": {"name": "echo", "arguments": {"text": "line\nbreak"}}}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "roots/list"}),
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+            json!({"jsonrpc": "2.0", "method": "notifications/progress",
+                   "params": {"progressToken": "t", "progress": 1.0}}),
+            json!({"jsonrpc": "2.0", "method": "notifications/tools/list_changed"}),
+            json!({"jsonrpc": "2.0", "id": 4, "result": null}),
+            json!({"jsonrpc": "2.0", "id": 5, "error": {"code": -32601, "message": "nope"}}),
+        ];
+
+        for frame in frames {
+            let text = frame.to_string();
+            let borrowed = serde_json::to_value(parse_frame(text.as_bytes()).unwrap()).unwrap();
+            let owned = serde_json::to_value(
+                crate::shared::StdioTransport::parse_message(text.as_bytes()).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(borrowed, owned, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_parse_frame_message_kinds() {
+        let request = parse_frame(br#"{"jsonrpc":"2.0","id":7,"method":"ping"}"#).unwrap();
+        assert!(matches!(
+            request,
+            TransportMessage::Request { request: Request::Client(ref r), .. }
+                if matches!(**r, ClientRequest::Ping)
+        ));
+
+        let notification =
+            parse_frame(br#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#).unwrap();
+        assert!(matches!(
+            notification,
+            TransportMessage::Notification(Notification::Client(ClientNotification::Initialized))
+        ));
+
+        let response = parse_frame(br#"{"jsonrpc":"2.0","id":1,"result":{"ok":true}}"#).unwrap();
+        let TransportMessage::Response(response) = response else {
+            panic!("expected a response");
+        };
+        assert!(matches!(response.payload, ResponsePayload::Result(ref v) if v["ok"] == true));
+    }
+
+    #[test]
+    fn test_parse_frame_errors() {
+        let error = |frame: &[u8]| parse_frame(frame).unwrap_err().to_string();
+
+        assert!(error(b"{").contains("Invalid JSON"));
+        assert!(error(b"42").contains("Unknown message type"));
+        assert!(error(br#"{"jsonrpc":"2.0","id":1}"#).contains("Unknown message type"));
+        assert!(
+            error(br#"{"jsonrpc":"2.0","id":1,"method":"no/such"}"#).contains("Invalid request")
+        );
+        assert!(error(br#"{"id":1,"method":"ping"}"#).contains("jsonrpc"));
+        assert!(error(br#"{"jsonrpc":"2.0","id":1,"result":{},"error":{}}"#)
+            .contains("Invalid response"));
+        assert!(parse_frame_batch(b" [ ] ").is_err());
+    }
+}
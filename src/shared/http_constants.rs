@@ -16,6 +16,9 @@ pub const ACCEPT: &str = "Accept";
 /// HTTP Content-Type header name
 pub const CONTENT_TYPE: &str = "Content-Type";
 
+/// HTTP WWW-Authenticate header name
+pub const WWW_AUTHENTICATE: &str = "WWW-Authenticate";
+
 // Content Types
 /// JSON content type value
 pub const APPLICATION_JSON: &str = "application/json";
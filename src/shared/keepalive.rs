@@ -0,0 +1,269 @@
+//! Keep-alive pings for idle connections.
+//!
+//! MCP defines `ping` but nothing sends it on its own, so a connection whose
+//! peer went away without closing it (a half-open connection) looks healthy
+//! until the next request hangs. A keep-alive pings the peer every interval
+//! and, after a number of consecutive pings fail or go unanswered, declares
+//! the connection dead so it can be closed or reconnected.
+//!
+//! [`Client::start_keep_alive`] pings the server while the client is idle.
+//! Servers cannot ping over a transport they read one message at a time, so
+//! [`ServerBuilder::keep_alive`] watches for the client's traffic instead.
+//! [`spawn_keep_alive`] runs the same schedule with any ping function.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::shared::keepalive::KeepAliveConfig;
+//! use pmcp::{Client, ClientCapabilities, StdioTransport};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let mut client = Client::new(StdioTransport::new());
+//! client.initialize(ClientCapabilities::default()).await?;
+//!
+//! let keep_alive = client.start_keep_alive(
+//!     KeepAliveConfig::default()
+//!         .with_interval(Duration::from_secs(15))
+//!         .with_failure_threshold(2),
+//!     || eprintln!("server stopped answering pings"),
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Client::start_keep_alive`]: crate::Client::start_keep_alive
+//! [`ServerBuilder::keep_alive`]: crate::ServerBuilder::keep_alive
+
+use crate::error::Result;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Default time between pings.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default time to wait for a ping to be answered.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default number of consecutive failed pings before the connection is
+/// considered dead.
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Schedule for keep-alive pings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepAliveConfig {
+    interval: Duration,
+    timeout: Duration,
+    failure_threshold: u32,
+}
+
+impl Default for KeepAliveConfig {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_INTERVAL,
+            timeout: DEFAULT_TIMEOUT,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+        }
+    }
+}
+
+impl KeepAliveConfig {
+    /// Set the time between pings.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set how long to wait for a ping to be answered.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set how many consecutive pings may fail before the connection is
+    /// considered dead. A threshold of 0 is treated as 1.
+    pub fn with_failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Time between pings.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Time to wait for a ping to be answered.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Consecutive failed pings before the connection is considered dead.
+    pub fn failure_threshold(&self) -> u32 {
+        self.failure_threshold
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    failures: AtomicU32,
+    timed_out: AtomicBool,
+}
+
+/// A running keep-alive. Dropping the handle stops it.
+#[derive(Debug)]
+pub struct KeepAliveHandle {
+    task: JoinHandle<()>,
+    state: Arc<State>,
+}
+
+impl KeepAliveHandle {
+    /// Consecutive pings that have failed so far.
+    pub fn failures(&self) -> u32 {
+        self.state.failures.load(Ordering::Acquire)
+    }
+
+    /// Whether the connection was declared dead.
+    pub fn timed_out(&self) -> bool {
+        self.state.timed_out.load(Ordering::Acquire)
+    }
+
+    /// Whether the keep-alive is still pinging.
+    pub fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Stop pinging.
+    pub fn stop(self) {
+        // Dropping aborts the task
+    }
+}
+
+impl Drop for KeepAliveHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Ping with `ping` on `config`'s schedule until the connection is dead.
+///
+/// A ping fails if it returns an error or does not complete within the
+/// configured timeout; a successful ping resets the failure count. Once
+/// `failure_threshold` pings in a row have failed, `on_timeout` runs and the
+/// keep-alive stops.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::shared::keepalive::{spawn_keep_alive, KeepAliveConfig};
+/// use std::time::Duration;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let (dead_tx, dead_rx) = tokio::sync::oneshot::channel();
+/// let keep_alive = spawn_keep_alive(
+///     KeepAliveConfig::default()
+///         .with_interval(Duration::from_millis(10))
+///         .with_failure_threshold(2),
+///     || async { Err(pmcp::Error::internal("peer is gone")) },
+///     move || async move {
+///         let _ = dead_tx.send(());
+///     },
+/// );
+///
+/// dead_rx.await.unwrap();
+/// assert!(keep_alive.timed_out());
+/// # }
+/// ```
+pub fn spawn_keep_alive<P, PF, C, CF>(
+    config: KeepAliveConfig,
+    mut ping: P,
+    on_timeout: C,
+) -> KeepAliveHandle
+where
+    P: FnMut() -> PF + Send + 'static,
+    PF: Future<Output = Result<()>> + Send,
+    C: FnOnce() -> CF + Send + 'static,
+    CF: Future<Output = ()> + Send,
+{
+    let state = Arc::new(State::default());
+    let task = tokio::spawn({
+        let state = state.clone();
+        async move {
+            loop {
+                tokio::time::sleep(config.interval).await;
+                let answered = matches!(
+                    tokio::time::timeout(config.timeout, ping()).await,
+                    Ok(Ok(()))
+                );
+                if answered {
+                    state.failures.store(0, Ordering::Release);
+                    continue;
+                }
+
+                let failures = state.failures.fetch_add(1, Ordering::AcqRel) + 1;
+                tracing::debug!(
+                    "Keep-alive ping failed ({}/{})",
+                    failures,
+                    config.failure_threshold
+                );
+                if failures >= config.failure_threshold {
+                    tracing::warn!("Peer did not answer {} keep-alive pings", failures);
+                    state.timed_out.store(true, Ordering::Release);
+                    on_timeout().await;
+                    break;
+                }
+            }
+        }
+    });
+    KeepAliveHandle { task, state }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[tokio::test]
+    async fn test_success_resets_failures() {
+        // Fails, fails, succeeds, then never answers
+        let calls = Arc::new(AtomicU32::new(0));
+        let (dead_tx, dead_rx) = tokio::sync::oneshot::channel();
+        let handle = spawn_keep_alive(
+            KeepAliveConfig::default()
+                .with_interval(Duration::from_millis(5))
+                .with_timeout(Duration::from_millis(20))
+                .with_failure_threshold(3),
+            {
+                let calls = calls.clone();
+                move || {
+                    let call = calls.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        match call {
+                            0 | 1 => Err(Error::internal("no answer")),
+                            2 => Ok(()),
+                            _ => std::future::pending().await,
+                        }
+                    }
+                }
+            },
+            move || async move {
+                let _ = dead_tx.send(());
+            },
+        );
+
+        dead_rx.await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 6);
+        assert_eq!(handle.failures(), 3);
+        assert!(handle.timed_out());
+    }
+
+    #[test]
+    fn test_zero_failure_threshold_is_one() {
+        let config = KeepAliveConfig::default().with_failure_threshold(0);
+        assert_eq!(config.failure_threshold(), 1);
+        assert_eq!(config.interval(), DEFAULT_INTERVAL);
+    }
+}
@@ -0,0 +1,156 @@
+//! In-process transport connecting a client and a server directly.
+//!
+//! [`InMemoryTransport::pair`] returns two connected ends: whatever one end
+//! sends, the other receives, in order and without loss. Messages are passed
+//! as values, without serialization, so tests can run a [`Server`] and a
+//! [`Client`] in one process without stdio or sockets.
+//!
+//! Closing or dropping one end closes the connection: the other end still
+//! receives the messages already sent, then gets
+//! [`TransportError::ConnectionClosed`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::shared::InMemoryTransport;
+//! use pmcp::{Client, ClientCapabilities, Server};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> pmcp::Result<()> {
+//! let (client_transport, server_transport) = InMemoryTransport::pair();
+//!
+//! let server = Server::builder()
+//!     .name("in-process")
+//!     .version("1.0.0")
+//!     .build()?;
+//! tokio::spawn(server.run(server_transport));
+//!
+//! let mut client = Client::new(client_transport);
+//! let result = client.initialize(ClientCapabilities::default()).await?;
+//! assert_eq!(result.server_info.name, "in-process");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Server`]: crate::Server
+//! [`Client`]: crate::Client
+
+use crate::error::{Result, TransportError};
+use crate::shared::transport::{Transport, TransportMessage};
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+
+/// One end of an in-process connection.
+#[derive(Debug)]
+pub struct InMemoryTransport {
+    /// Sender to the peer, `None` once this end is closed
+    tx: Option<mpsc::UnboundedSender<TransportMessage>>,
+    rx: mpsc::UnboundedReceiver<TransportMessage>,
+}
+
+impl InMemoryTransport {
+    /// Create two connected ends, e.g. one for a client and one for a server.
+    pub fn pair() -> (Self, Self) {
+        let (a_tx, b_rx) = mpsc::unbounded_channel();
+        let (b_tx, a_rx) = mpsc::unbounded_channel();
+        (
+            Self {
+                tx: Some(a_tx),
+                rx: a_rx,
+            },
+            Self {
+                tx: Some(b_tx),
+                rx: b_rx,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Transport for InMemoryTransport {
+    async fn send(&mut self, message: TransportMessage) -> Result<()> {
+        let tx = self.tx.as_ref().ok_or(TransportError::ConnectionClosed)?;
+        tx.send(message)
+            .map_err(|_| TransportError::ConnectionClosed)?;
+        Ok(())
+    }
+
+    async fn receive(&mut self) -> Result<TransportMessage> {
+        if self.tx.is_none() {
+            return Err(TransportError::ConnectionClosed.into());
+        }
+        match self.rx.recv().await {
+            Some(message) => Ok(message),
+            None => {
+                self.tx = None;
+                Err(TransportError::ConnectionClosed.into())
+            },
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.tx = None;
+        self.rx.close();
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.tx.as_ref().is_some_and(|tx| !tx.is_closed())
+    }
+
+    fn transport_type(&self) -> &'static str {
+        "memory"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ClientNotification, Notification, ProgressNotification, ProgressToken};
+
+    fn progress(value: f64) -> TransportMessage {
+        TransportMessage::Notification(Notification::Client(ClientNotification::Progress(
+            ProgressNotification {
+                progress_token: ProgressToken::Number(1),
+                progress: value,
+                message: None,
+            },
+        )))
+    }
+
+    #[tokio::test]
+    async fn test_messages_arrive_in_order_after_close() {
+        let (mut a, mut b) = InMemoryTransport::pair();
+        for i in 0..100 {
+            a.send(progress(f64::from(i))).await.unwrap();
+        }
+        a.close().await.unwrap();
+        assert!(!a.is_connected());
+        assert!(a.send(progress(0.0)).await.is_err());
+        assert!(!b.is_connected());
+
+        for i in 0..100 {
+            let TransportMessage::Notification(Notification::Client(ClientNotification::Progress(
+                p,
+            ))) = b.receive().await.unwrap()
+            else {
+                panic!("Expected progress notification");
+            };
+            assert_eq!(p.progress, f64::from(i));
+        }
+        assert!(matches!(
+            b.receive().await,
+            Err(crate::Error::Transport(TransportError::ConnectionClosed))
+        ));
+        assert!(b.send(progress(0.0)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dropping_one_end_closes_the_other() {
+        let (a, mut b) = InMemoryTransport::pair();
+        assert!(b.is_connected());
+        drop(a);
+        assert!(!b.is_connected());
+        assert!(b.receive().await.is_err());
+    }
+}
@@ -105,6 +105,22 @@ impl PerformanceMetrics {
         self.metrics.get(name).map(|v| *v)
     }
 
+    /// Add `delta` to a custom metric, starting from zero
+    pub fn add(&self, name: &str, delta: f64) {
+        *self.metrics.entry(name.to_string()).or_insert(0.0) += delta;
+    }
+
+    /// Get all custom metrics, sorted by name
+    pub fn custom_metrics(&self) -> Vec<(String, f64)> {
+        let mut metrics: Vec<_> = self
+            .metrics
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect();
+        metrics.sort_by(|a, b| a.0.cmp(&b.0));
+        metrics
+    }
+
     /// Increment request count
     pub fn inc_requests(&self) {
         self.request_count.fetch_add(1, Ordering::Relaxed);
@@ -131,6 +147,11 @@ impl PerformanceMetrics {
         self.error_count.load(Ordering::Relaxed)
     }
 
+    /// Get total processing time
+    pub fn total_time(&self) -> Duration {
+        Duration::from_micros(self.total_time_us.load(Ordering::Relaxed))
+    }
+
     /// Get average processing time
     pub fn average_time(&self) -> Duration {
         let total_time = self.total_time_us.load(Ordering::Relaxed);
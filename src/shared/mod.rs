@@ -1,17 +1,34 @@
 //! Shared components used by both client and server.
 
 pub mod batch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod child_process;
 pub mod context;
+pub mod cors;
 pub mod event_store;
+pub mod frame_parser;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod keepalive;
 pub mod logging;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod memory;
 pub mod middleware;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod outgoing;
 pub mod protocol;
 pub mod protocol_helpers;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod reconnect;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod recording;
 pub mod session;
 pub mod simd_parsing;
 pub mod sse_parser;
+pub mod strict;
+#[cfg(feature = "opentelemetry")]
+pub mod telemetry;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod timing;
 
 #[cfg(feature = "sse")]
 pub mod sse_optimized;
@@ -39,6 +56,8 @@ pub mod wasm_http;
 #[cfg(all(feature = "http", not(target_arch = "wasm32")))]
 pub mod http;
 pub mod http_constants;
+#[cfg(all(feature = "tcp", not(target_arch = "wasm32")))]
+pub mod tcp;
 
 #[cfg(all(feature = "streamable-http", not(target_arch = "wasm32")))]
 /// Streamable HTTP transport implementation for MCP.
@@ -46,29 +65,47 @@ pub mod streamable_http;
 
 // Re-export commonly used types
 pub use batch::{BatchRequest, BatchResponse};
+#[cfg(not(target_arch = "wasm32"))]
+pub use child_process::{ChildProcessCommand, ChildProcessTransport, RestartPolicy};
 pub use context::{ClientInfo, ContextPropagator, RequestContext};
 pub use event_store::{
     EventStore, EventStoreConfig, InMemoryEventStore, MessageDirection, ResumptionManager,
     ResumptionState, ResumptionToken, StoredEvent,
 };
 #[cfg(not(target_arch = "wasm32"))]
+pub use keepalive::{KeepAliveConfig, KeepAliveHandle};
+#[cfg(not(target_arch = "wasm32"))]
 pub use logging::init_logging;
 pub use logging::{CorrelatedLogger, LogConfig, LogEntry, LogFormat, LogLevel};
+#[cfg(not(target_arch = "wasm32"))]
+pub use memory::InMemoryTransport;
 pub use middleware::{
     AdvancedMiddleware, AuthMiddleware, CircuitBreakerMiddleware, CompressionMiddleware,
     CompressionType, EnhancedMiddlewareChain, LoggingMiddleware, MetricsMiddleware, Middleware,
     MiddlewareChain, MiddlewareContext, MiddlewarePriority, PerformanceMetrics,
     RateLimitMiddleware, RetryMiddleware,
 };
-pub use protocol::{ProgressCallback, Protocol, ProtocolOptions, RequestOptions};
+#[cfg(not(target_arch = "wasm32"))]
+pub use outgoing::{MessagePriority, OutgoingQueue};
+pub use protocol::{
+    MessageSizeLimits, MonotonicIdGenerator, NegotiatedVersion, PrefixedIdGenerator,
+    ProgressCallback, Protocol, ProtocolFeature, ProtocolOptions, RequestIdGenerator,
+    RequestOptions, UuidIdGenerator, VersionNegotiator, DEFAULT_TOOL_CALL_TIMEOUT,
+};
 pub use protocol_helpers::{
-    create_notification, create_request, parse_notification, parse_request,
+    create_notification, create_request, parse_notification, parse_request, request_method,
 };
 #[cfg(not(target_arch = "wasm32"))]
-pub use reconnect::{ReconnectConfig, ReconnectGuard, ReconnectManager};
+pub use reconnect::{
+    GiveUpReason, JitterStrategy, ReconnectConfig, ReconnectEvent, ReconnectGuard, ReconnectManager,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use recording::{RecordedMessage, Recording, RecordingTransport, ReplayTransport};
 pub use session::{Session, SessionConfig, SessionManager};
 #[cfg(not(target_arch = "wasm32"))]
 pub use stdio::StdioTransport;
+#[cfg(not(target_arch = "wasm32"))]
+pub use timing::{DispatchMetrics, RequestTimings, TimingSnapshot};
 pub use transport::{Transport, TransportMessage};
 pub use uri_template::UriTemplate;
 
@@ -84,6 +121,9 @@ pub use wasm_http::{WasmHttpClient, WasmHttpConfig, WasmHttpTransport};
 #[cfg(all(feature = "http", not(target_arch = "wasm32")))]
 pub use http::{HttpConfig, HttpTransport};
 
+#[cfg(all(feature = "tcp", not(target_arch = "wasm32")))]
+pub use tcp::TcpTransport;
+
 #[cfg(all(feature = "streamable-http", not(target_arch = "wasm32")))]
 pub use streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
 
@@ -92,10 +132,11 @@ pub use sse_optimized::{OptimizedSseConfig, OptimizedSseTransport};
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use connection_pool::{
-    ConnectionId, ConnectionPool, ConnectionPoolConfig, HealthStatus, LoadBalanceStrategy,
-    PoolStats, PooledTransport,
+    ConnectionFactory, ConnectionId, ConnectionPool, ConnectionPoolConfig, HealthStatus,
+    LoadBalanceStrategy, PoolStats, PooledTransport,
 };
 
 pub use simd_parsing::{
-    CpuFeatures, ParsingMetrics, SimdBase64, SimdHttpHeaderParser, SimdJsonParser, SimdSseParser,
+    CpuFeatures, ParsingMetrics, SimdBackend, SimdBase64, SimdHttpHeaderParser, SimdJsonParser,
+    SimdSseParser,
 };
@@ -0,0 +1,266 @@
+//! Prioritized queue for outgoing messages.
+//!
+//! On a slow transport, messages pile up faster than they can be written. A
+//! plain FIFO lets a burst of notifications delay the responses a peer is
+//! waiting on. [`OutgoingQueue`] instead releases messages by
+//! [`MessagePriority`], and within a priority round-robins across sessions so
+//! one busy session cannot starve the others.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::shared::outgoing::OutgoingQueue;
+//! use pmcp::shared::TransportMessage;
+//! use pmcp::types::{JSONRPCResponse, Notification, RequestId, ServerNotification};
+//!
+//! let queue = OutgoingQueue::new();
+//! queue.push(
+//!     None,
+//!     TransportMessage::Notification(Notification::Server(ServerNotification::ToolsChanged)),
+//! );
+//! queue.push(
+//!     None,
+//!     TransportMessage::Response(JSONRPCResponse::success(
+//!         RequestId::from(1i64),
+//!         serde_json::json!({}),
+//!     )),
+//! );
+//!
+//! // The response jumps ahead of the notification queued before it.
+//! let (_, first) = queue.pop().unwrap();
+//! assert!(matches!(first, TransportMessage::Response(_)));
+//! ```
+
+use crate::shared::TransportMessage;
+use crate::types::{ClientNotification, Notification};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use tokio::sync::Notify;
+
+/// Priority of an outgoing message, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MessagePriority {
+    /// Responses to peer requests.
+    Response,
+    /// Cancellation notifications.
+    Cancellation,
+    /// All other notifications.
+    Notification,
+    /// New requests to the peer.
+    Request,
+}
+
+impl MessagePriority {
+    /// All priorities, highest first.
+    pub const ALL: [Self; 4] = [
+        Self::Response,
+        Self::Cancellation,
+        Self::Notification,
+        Self::Request,
+    ];
+
+    /// Classify a message.
+    pub fn of(message: &TransportMessage) -> Self {
+        match message {
+            TransportMessage::Response(_) => Self::Response,
+            TransportMessage::Notification(
+                Notification::Cancelled(_) | Notification::Client(ClientNotification::Cancelled(_)),
+            ) => Self::Cancellation,
+            TransportMessage::Notification(_) => Self::Notification,
+            TransportMessage::Request { .. } => Self::Request,
+        }
+    }
+}
+
+/// Messages of one priority, kept per session.
+#[derive(Debug, Default)]
+struct Lanes {
+    /// Sessions with queued messages, in round-robin order
+    order: VecDeque<Option<String>>,
+    queues: HashMap<Option<String>, VecDeque<TransportMessage>>,
+}
+
+impl Lanes {
+    fn push(&mut self, session: Option<String>, message: TransportMessage) {
+        let queue = self.queues.entry(session.clone()).or_default();
+        if queue.is_empty() {
+            self.order.push_back(session);
+        }
+        queue.push_back(message);
+    }
+
+    fn pop(&mut self) -> Option<(Option<String>, TransportMessage)> {
+        let session = self.order.pop_front()?;
+        let queue = self.queues.get_mut(&session)?;
+        let message = queue.pop_front()?;
+        if queue.is_empty() {
+            self.queues.remove(&session);
+        } else {
+            self.order.push_back(session.clone());
+        }
+        Some((session, message))
+    }
+}
+
+/// Thread-safe prioritized queue of outgoing messages.
+///
+/// Messages are tagged with an optional session id. [`pop`](Self::pop)
+/// returns the oldest message of the highest non-empty priority, taking
+/// turns between sessions that have messages at that priority.
+#[derive(Debug, Default)]
+pub struct OutgoingQueue {
+    lanes: Mutex<[Lanes; 4]>,
+    notify: Notify,
+}
+
+impl OutgoingQueue {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a message for a session.
+    pub fn push(&self, session: Option<&str>, message: TransportMessage) {
+        let priority = MessagePriority::of(&message) as usize;
+        self.lanes.lock()[priority].push(session.map(str::to_string), message);
+        self.notify.notify_one();
+    }
+
+    /// Take the next message to send, if any.
+    pub fn pop(&self) -> Option<(Option<String>, TransportMessage)> {
+        self.lanes.lock().iter_mut().find_map(Lanes::pop)
+    }
+
+    /// Wait for the next message to send.
+    pub async fn next(&self) -> (Option<String>, TransportMessage) {
+        loop {
+            let notified = self.notify.notified();
+            if let Some(entry) = self.pop() {
+                return entry;
+            }
+            notified.await;
+        }
+    }
+
+    /// Wait until a message is queued, leaving it in the queue.
+    pub async fn ready(&self) {
+        loop {
+            let notified = self.notify.notified();
+            if !self.is_empty() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Number of queued messages at a priority.
+    pub fn len_at(&self, priority: MessagePriority) -> usize {
+        self.lanes.lock()[priority as usize]
+            .queues
+            .values()
+            .map(VecDeque::len)
+            .sum()
+    }
+
+    /// Total number of queued messages.
+    pub fn len(&self) -> usize {
+        MessagePriority::ALL.iter().map(|p| self.len_at(*p)).sum()
+    }
+
+    /// Whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        CancelledNotification, ClientRequest, JSONRPCResponse, Request, RequestId,
+        ServerNotification,
+    };
+
+    fn notification() -> TransportMessage {
+        TransportMessage::Notification(Notification::Server(ServerNotification::ToolsChanged))
+    }
+
+    fn response(id: i64) -> TransportMessage {
+        TransportMessage::Response(JSONRPCResponse::success(
+            RequestId::from(id),
+            serde_json::json!({}),
+        ))
+    }
+
+    fn response_id(message: &TransportMessage) -> RequestId {
+        match message {
+            TransportMessage::Response(response) => response.id.clone(),
+            other => panic!("expected response, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_messages_leave_in_priority_order() {
+        let queue = OutgoingQueue::new();
+        queue.push(
+            None,
+            TransportMessage::Request {
+                id: RequestId::from(9i64),
+                request: Request::Client(Box::new(ClientRequest::Ping)),
+            },
+        );
+        queue.push(None, notification());
+        queue.push(
+            None,
+            TransportMessage::Notification(Notification::Cancelled(CancelledNotification {
+                request_id: RequestId::from(3i64),
+                reason: None,
+            })),
+        );
+        queue.push(None, response(1));
+        assert_eq!(queue.len(), 4);
+
+        let priorities: Vec<_> = std::iter::from_fn(|| queue.pop())
+            .map(|(_, message)| MessagePriority::of(&message))
+            .collect();
+        assert_eq!(priorities, MessagePriority::ALL);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_sessions_take_turns_within_a_priority() {
+        let queue = OutgoingQueue::new();
+        for id in 0..3 {
+            queue.push(Some("busy"), response(id));
+        }
+        queue.push(Some("quiet"), response(100));
+
+        let order: Vec<_> = std::iter::from_fn(|| queue.pop())
+            .map(|(session, message)| (session.unwrap(), response_id(&message)))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("busy".to_string(), RequestId::from(0i64)),
+                ("quiet".to_string(), RequestId::from(100i64)),
+                ("busy".to_string(), RequestId::from(1i64)),
+                ("busy".to_string(), RequestId::from(2i64)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_next_waits_for_messages() {
+        let queue = std::sync::Arc::new(OutgoingQueue::new());
+        let waiter = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.next().await }
+        });
+        tokio::task::yield_now().await;
+        queue.push(None, response(7));
+
+        let (session, message) = waiter.await.unwrap();
+        assert_eq!(session, None);
+        assert_eq!(response_id(&message), RequestId::from(7i64));
+    }
+}
@@ -2,33 +2,616 @@
 //!
 //! This module provides the core protocol state machine and request handling.
 
-use crate::error::Result;
+use crate::error::{Error, ErrorCode, Result, TransportError};
 use crate::runtime::oneshot;
 use crate::runtime::{self, Mutex};
 use crate::types::{JSONRPCResponse, RequestId};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+/// Number of recent peer request ids remembered for duplicate detection.
+const PEER_REQUEST_ID_HISTORY: usize = 4096;
+
+/// How many times larger than the configured limit a message may be before
+/// the connection is closed instead of the message being answered with an
+/// error.
+pub const OVERSIZED_MESSAGE_CLOSE_FACTOR: usize = 4;
+
+/// Default timeout for `tools/call` requests, which may run far longer than
+/// the [`DEFAULT_REQUEST_TIMEOUT_MS`](crate::DEFAULT_REQUEST_TIMEOUT_MS) other
+/// requests get.
+pub const DEFAULT_TOOL_CALL_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Nesting depth past which `serde_json` refuses to parse a message anyway.
+const PARSER_DEPTH_LIMIT: usize = 128;
+
 /// Progress callback type.
 pub type ProgressCallback = Box<dyn Fn(u64, Option<u64>) + Send + Sync>;
 
 /// Protocol options for configuring behavior.
-#[derive(Debug, Clone, Default)]
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::shared::protocol::ProtocolOptions;
+/// use std::time::Duration;
+///
+/// let options = ProtocolOptions::default()
+///     .with_method_timeout("ping", Duration::from_secs(5))
+///     .with_method_timeout("tools/call", Duration::from_secs(600));
+///
+/// assert_eq!(options.timeout_for("ping"), Duration::from_secs(5));
+/// assert_eq!(options.timeout_for("tools/call"), Duration::from_secs(600));
+/// assert_eq!(options.timeout_for("tools/list"), Duration::from_secs(60));
+/// ```
+#[derive(Debug, Clone)]
 pub struct ProtocolOptions {
     /// Whether to enforce strict capability checking.
     pub enforce_strict_capabilities: bool,
     /// Methods that should be debounced.
     pub debounced_notification_methods: Vec<String>,
+    /// Timeout for requests whose method has no entry in `method_timeouts`.
+    pub default_timeout: Duration,
+    /// Timeouts for specific methods, keyed by method name (e.g. `tools/call`).
+    ///
+    /// Holds [`DEFAULT_TOOL_CALL_TIMEOUT`] for `tools/call` by default.
+    pub method_timeouts: HashMap<String, Duration>,
+    /// Reject malformed envelopes and unknown methods from peers.
+    ///
+    /// See [`strict`](super::strict) for the rules enforced.
+    pub strict: bool,
+    /// Limits on the size of serialized messages.
+    pub message_size_limits: MessageSizeLimits,
+    /// Protocol versions this side speaks.
+    pub versions: VersionNegotiator,
+}
+
+impl Default for ProtocolOptions {
+    fn default() -> Self {
+        Self {
+            enforce_strict_capabilities: false,
+            debounced_notification_methods: Vec::new(),
+            default_timeout: Duration::from_millis(crate::DEFAULT_REQUEST_TIMEOUT_MS),
+            method_timeouts: HashMap::from([("tools/call".to_string(), DEFAULT_TOOL_CALL_TIMEOUT)]),
+            strict: false,
+            message_size_limits: MessageSizeLimits::default(),
+            versions: VersionNegotiator::default(),
+        }
+    }
+}
+
+impl ProtocolOptions {
+    /// Set the timeout for requests without a method-specific timeout.
+    pub fn with_default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = timeout;
+        self
+    }
+
+    /// Set the timeout for a specific method.
+    pub fn with_method_timeout(mut self, method: impl Into<String>, timeout: Duration) -> Self {
+        self.method_timeouts.insert(method.into(), timeout);
+        self
+    }
+
+    /// Enable or disable strict protocol validation.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Set the maximum size in bytes of messages accepted from the peer.
+    pub fn with_max_inbound_message_size(mut self, bytes: usize) -> Self {
+        self.message_size_limits.inbound = Some(bytes);
+        self
+    }
+
+    /// Set the maximum size in bytes of messages sent to the peer.
+    pub fn with_max_outbound_message_size(mut self, bytes: usize) -> Self {
+        self.message_size_limits.outbound = Some(bytes);
+        self
+    }
+
+    /// Set how deeply arrays and objects may nest in messages from the peer.
+    pub fn with_max_nesting_depth(mut self, depth: usize) -> Self {
+        self.message_size_limits.max_depth = Some(depth);
+        self
+    }
+
+    /// Set how many elements an array in a message from the peer may hold.
+    pub fn with_max_array_length(mut self, length: usize) -> Self {
+        self.message_size_limits.max_array_length = Some(length);
+        self
+    }
+
+    /// Set the protocol versions this side speaks.
+    pub fn with_versions(mut self, versions: VersionNegotiator) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    /// Get the default timeout for a method.
+    pub fn timeout_for(&self, method: &str) -> Duration {
+        self.method_timeouts
+            .get(method)
+            .copied()
+            .unwrap_or(self.default_timeout)
+    }
+}
+
+/// Limits on the size and shape of serialized JSON-RPC messages.
+///
+/// A message over the size limit is answered with an `INVALID_REQUEST`
+/// error. A message more than [`OVERSIZED_MESSAGE_CLOSE_FACTOR`] times the
+/// limit is treated as abuse: transports refuse to buffer it and close the
+/// connection.
+///
+/// The nesting depth and array length of inbound messages can be capped as
+/// well, so that a small message cannot expand into a huge tree when
+/// decoded. Those are checked by a scan of the raw bytes before decoding,
+/// and a message breaking them is answered with an `INVALID_REQUEST` error.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::shared::protocol::MessageSizeLimits;
+/// use pmcp::{Error, ErrorCode};
+///
+/// let limits = MessageSizeLimits::default()
+///     .with_inbound(1024)
+///     .with_max_depth(8)
+///     .with_max_array_length(100);
+///
+/// assert!(limits.check_inbound(1024).is_ok());
+/// assert_eq!(
+///     limits.check_inbound(2048).unwrap_err().error_code(),
+///     Some(ErrorCode::INVALID_REQUEST)
+/// );
+/// assert!(matches!(limits.check_inbound(1024 * 1024), Err(Error::Transport(_))));
+///
+/// assert!(limits.check_inbound_structure(br#"{"params":{"items":[1,2,3]}}"#).is_ok());
+/// assert_eq!(
+///     limits
+///         .check_inbound_structure(br#"{"params":[[[[[[[[[]]]]]]]]]}"#)
+///         .unwrap_err()
+///         .error_code(),
+///     Some(ErrorCode::INVALID_REQUEST)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MessageSizeLimits {
+    /// Maximum size in bytes of messages received from the peer.
+    pub inbound: Option<usize>,
+    /// Maximum size in bytes of messages sent to the peer.
+    pub outbound: Option<usize>,
+    /// Maximum nesting depth of arrays and objects in messages received
+    /// from the peer; the message object itself is at depth one.
+    pub max_depth: Option<usize>,
+    /// Maximum number of elements of any array in messages received from
+    /// the peer, including the top-level array of a batch.
+    pub max_array_length: Option<usize>,
+}
+
+impl MessageSizeLimits {
+    /// Set the inbound limit.
+    pub fn with_inbound(mut self, bytes: usize) -> Self {
+        self.inbound = Some(bytes);
+        self
+    }
+
+    /// Set the outbound limit.
+    pub fn with_outbound(mut self, bytes: usize) -> Self {
+        self.outbound = Some(bytes);
+        self
+    }
+
+    /// Set the inbound nesting depth limit.
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Set the inbound array length limit.
+    pub fn with_max_array_length(mut self, length: usize) -> Self {
+        self.max_array_length = Some(length);
+        self
+    }
+
+    /// Largest inbound message a transport should buffer at all.
+    ///
+    /// Messages between the inbound limit and this size are read so they can
+    /// be answered with an error; anything larger closes the connection.
+    pub fn inbound_close_threshold(&self) -> Option<usize> {
+        self.inbound
+            .map(|limit| limit.saturating_mul(OVERSIZED_MESSAGE_CLOSE_FACTOR))
+    }
+
+    /// Check the size of a message received from the peer.
+    ///
+    /// Returns a protocol error to send back to the peer for oversized
+    /// messages, and a transport error when the connection should be closed.
+    pub fn check_inbound(&self, len: usize) -> Result<()> {
+        let Some(limit) = self.inbound else {
+            return Ok(());
+        };
+        if len <= limit {
+            return Ok(());
+        }
+        if self.inbound_close_threshold().is_some_and(|max| len > max) {
+            return Err(TransportError::InvalidMessage(format!(
+                "Message of {} bytes exceeds the {}-byte limit, closing connection",
+                len, limit
+            ))
+            .into());
+        }
+        Err(Error::protocol(
+            ErrorCode::INVALID_REQUEST,
+            format!("Message of {} bytes exceeds the {}-byte limit", len, limit),
+        ))
+    }
+
+    /// Check the nesting depth and array lengths of a message received from
+    /// the peer, without decoding it.
+    ///
+    /// Returns a protocol error to send back to the peer when a limit is
+    /// broken. Malformed JSON is left for the parser to report.
+    pub fn check_inbound_structure(&self, bytes: &[u8]) -> Result<()> {
+        if self.max_depth.is_none() && self.max_array_length.is_none() {
+            return Ok(());
+        }
+        // Deeper messages are rejected by the parser, so there is no need to
+        // track them without a depth limit.
+        let depth_limit = self.max_depth.unwrap_or(PARSER_DEPTH_LIMIT);
+
+        // Element counts of the open containers; `None` for objects
+        let mut open: Vec<Option<usize>> = Vec::new();
+        // Whether the current array element has already been counted
+        let mut counted = false;
+        let mut in_string = false;
+        let mut escaped = false;
+
+        for &byte in bytes {
+            if in_string {
+                match byte {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {},
+                }
+                continue;
+            }
+            match byte {
+                b' ' | b'\t' | b'\n' | b'\r' => continue,
+                b']' | b'}' => {
+                    open.pop();
+                    // The closed container was an element of its parent
+                    counted = true;
+                    continue;
+                },
+                b',' => {
+                    counted = false;
+                    continue;
+                },
+                b':' => continue,
+                _ => {},
+            }
+
+            // Any other byte starts a value or object key
+            if let Some(Some(count)) = open.last_mut() {
+                if !counted {
+                    *count += 1;
+                    counted = true;
+                    if let Some(max) = self.max_array_length.filter(|max| *count > *max) {
+                        return Err(Error::protocol(
+                            ErrorCode::INVALID_REQUEST,
+                            format!("Message has an array of more than {} elements", max),
+                        ));
+                    }
+                }
+            }
+            match byte {
+                b'"' => in_string = true,
+                b'[' | b'{' => {
+                    if open.len() == depth_limit {
+                        return match self.max_depth {
+                            Some(max) => Err(Error::protocol(
+                                ErrorCode::INVALID_REQUEST,
+                                format!("Message nests more than {} levels deep", max),
+                            )),
+                            None => Ok(()),
+                        };
+                    }
+                    open.push((byte == b'[').then_some(0));
+                    counted = false;
+                },
+                _ => {},
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the size of a message about to be sent to the peer.
+    pub fn check_outbound(&self, len: usize) -> Result<()> {
+        match self.outbound {
+            Some(limit) if len > limit => Err(Error::protocol(
+                ErrorCode::INTERNAL_ERROR,
+                format!(
+                    "Outgoing message of {} bytes exceeds the {}-byte limit",
+                    len, limit
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A protocol feature added after the first MCP revision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ProtocolFeature {
+    /// Audio content blocks.
+    AudioContent,
+    /// Tool annotations in `tools/list`.
+    ToolAnnotations,
+    /// Servers asking the user for input (`elicitation/create`).
+    Elicitation,
+    /// Structured content in tool results.
+    StructuredToolOutput,
+    /// Resource link content blocks.
+    ResourceLinks,
+}
+
+impl ProtocolFeature {
+    /// The first protocol version with this feature.
+    pub fn since(self) -> &'static str {
+        match self {
+            Self::AudioContent | Self::ToolAnnotations => "2025-03-26",
+            Self::Elicitation | Self::StructuredToolOutput | Self::ResourceLinks => "2025-06-18",
+        }
+    }
+}
+
+impl std::fmt::Display for ProtocolFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::AudioContent => "audio content",
+            Self::ToolAnnotations => "tool annotations",
+            Self::Elicitation => "elicitation",
+            Self::StructuredToolOutput => "structured tool output",
+            Self::ResourceLinks => "resource links",
+        })
+    }
+}
+
+/// The protocol version agreed on for a connection.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::shared::protocol::{NegotiatedVersion, ProtocolFeature};
+///
+/// let version = NegotiatedVersion::new("2025-03-26");
+/// assert!(version.supports(ProtocolFeature::AudioContent));
+/// assert!(version.require(ProtocolFeature::Elicitation).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NegotiatedVersion(String);
+
+impl NegotiatedVersion {
+    /// Wrap an agreed version.
+    pub fn new(version: impl Into<String>) -> Self {
+        Self(version.into())
+    }
+
+    /// The version string, such as `2025-06-18`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether `feature` is available at this version.
+    pub fn supports(&self, feature: ProtocolFeature) -> bool {
+        // Versions are dates, so they order as strings
+        self.0.as_str() >= feature.since()
+    }
+
+    /// Fail unless `feature` is available at this version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedProtocolFeature`] if the version predates
+    /// the feature.
+    pub fn require(&self, feature: ProtocolFeature) -> Result<()> {
+        if self.supports(feature) {
+            Ok(())
+        } else {
+            Err(Error::UnsupportedProtocolFeature {
+                feature,
+                negotiated: self.0.clone(),
+            })
+        }
+    }
+}
+
+impl std::fmt::Display for NegotiatedVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Picks the protocol version for a connection.
+///
+/// The client proposes the newest version it speaks in `initialize`. The
+/// server answers with the same version if it speaks it, and otherwise with
+/// its own newest; the client then accepts the answer if it speaks that
+/// version too, or gives up. Defaults to
+/// [`SUPPORTED_PROTOCOL_VERSIONS`](crate::SUPPORTED_PROTOCOL_VERSIONS).
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::shared::protocol::VersionNegotiator;
+///
+/// let server = VersionNegotiator::new(["2025-03-26", "2024-11-05"]);
+///
+/// // A newer client is answered with the server's newest version
+/// let version = server.respond_to("2025-06-18");
+/// assert_eq!(version.as_str(), "2025-03-26");
+///
+/// // which the client accepts, downgrading
+/// let client = VersionNegotiator::default();
+/// assert_eq!(client.accept(version.as_str()).unwrap(), version);
+///
+/// let common = client.highest_common(server.supported());
+/// assert_eq!(common, Some(version));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionNegotiator {
+    /// Newest first
+    supported: Vec<String>,
+}
+
+impl Default for VersionNegotiator {
+    fn default() -> Self {
+        Self::new(crate::SUPPORTED_PROTOCOL_VERSIONS.iter().copied())
+    }
+}
+
+impl VersionNegotiator {
+    /// Speak `versions`, in any order.
+    ///
+    /// An empty list speaks only
+    /// [`LATEST_PROTOCOL_VERSION`](crate::LATEST_PROTOCOL_VERSION).
+    pub fn new<I, S>(versions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut supported: Vec<String> = versions.into_iter().map(Into::into).collect();
+        if supported.is_empty() {
+            supported.push(crate::LATEST_PROTOCOL_VERSION.to_string());
+        }
+        supported.sort_unstable_by(|a, b| b.cmp(a));
+        supported.dedup();
+        Self { supported }
+    }
+
+    /// The versions spoken, newest first.
+    pub fn supported(&self) -> &[String] {
+        &self.supported
+    }
+
+    /// The newest version spoken.
+    pub fn latest(&self) -> &str {
+        &self.supported[0]
+    }
+
+    /// Whether `version` is spoken.
+    pub fn supports(&self, version: &str) -> bool {
+        self.supported.iter().any(|v| v == version)
+    }
+
+    /// The newest version spoken by both sides, if any.
+    pub fn highest_common<S: AsRef<str>>(&self, peer: &[S]) -> Option<NegotiatedVersion> {
+        self.supported
+            .iter()
+            .find(|version| peer.iter().any(|p| p.as_ref() == version.as_str()))
+            .map(|version| NegotiatedVersion::new(version.as_str()))
+    }
+
+    /// The server's answer to a client proposing `requested`.
+    pub fn respond_to(&self, requested: &str) -> NegotiatedVersion {
+        if self.supports(requested) {
+            NegotiatedVersion::new(requested)
+        } else {
+            NegotiatedVersion::new(self.latest())
+        }
+    }
+
+    /// The client's check of the version the server answered with.
+    ///
+    /// # Errors
+    ///
+    /// Returns a protocol error if `offered` is not spoken.
+    pub fn accept(&self, offered: &str) -> Result<NegotiatedVersion> {
+        if !self.supports(offered) {
+            return Err(Error::protocol_msg(format!(
+                "Server protocol version {} not supported",
+                offered
+            )));
+        }
+        if offered != self.latest() {
+            tracing::debug!(
+                "Downgrading protocol version from {} to {}",
+                self.latest(),
+                offered
+            );
+        }
+        Ok(NegotiatedVersion::new(offered))
+    }
 }
 
 /// Request options for individual requests.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::shared::RequestOptions;
+/// use std::time::Duration;
+///
+/// // Fail after 30s without progress, and after 10 minutes regardless.
+/// let options = RequestOptions::default()
+///     .with_timeout(Duration::from_secs(30))
+///     .with_reset_timeout_on_progress(true)
+///     .with_max_total_timeout(Duration::from_secs(600))
+///     .with_progress(|progress, _total| println!("{}%", progress));
+/// ```
 #[derive(Default)]
 pub struct RequestOptions {
-    /// Timeout for the request.
+    /// Timeout for the request, overriding the method's default timeout.
     pub timeout: Option<Duration>,
     /// Progress callback.
     pub on_progress: Option<ProgressCallback>,
+    /// Restart the timeout whenever the server reports progress.
+    pub reset_timeout_on_progress: bool,
+    /// Upper bound on the request's total duration, even while progress
+    /// keeps resetting the timeout.
+    pub max_total_timeout: Option<Duration>,
+}
+
+impl RequestOptions {
+    /// Set the timeout, overriding the method's default timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Call `callback` with each progress notification for the request.
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(u64, Option<u64>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Restart the timeout whenever the server reports progress.
+    pub fn with_reset_timeout_on_progress(mut self, reset: bool) -> Self {
+        self.reset_timeout_on_progress = reset;
+        self
+    }
+
+    /// Fail the request once it has run for `timeout` in total.
+    pub fn with_max_total_timeout(mut self, timeout: Duration) -> Self {
+        self.max_total_timeout = Some(timeout);
+        self
+    }
+
+    /// Whether the request needs progress notifications from the server.
+    pub(crate) fn wants_progress(&self) -> bool {
+        self.on_progress.is_some() || self.reset_timeout_on_progress
+    }
 }
 
 impl std::fmt::Debug for RequestOptions {
@@ -39,10 +622,96 @@ impl std::fmt::Debug for RequestOptions {
                 "on_progress",
                 &self.on_progress.as_ref().map(|_| "<callback>"),
             )
+            .field("reset_timeout_on_progress", &self.reset_timeout_on_progress)
+            .field("max_total_timeout", &self.max_total_timeout)
             .finish()
     }
 }
 
+/// Source of ids for outgoing requests.
+///
+/// Ids must be unique within a session. The default generator produces
+/// random UUIDs; [`MonotonicIdGenerator`] and [`PrefixedIdGenerator`] produce
+/// shorter, ordered ids that are easier to follow in logs.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::shared::protocol::{
+///     MonotonicIdGenerator, Protocol, ProtocolOptions, RequestIdGenerator,
+/// };
+/// use pmcp::types::RequestId;
+///
+/// let protocol = Protocol::new(ProtocolOptions::default())
+///     .with_id_generator(MonotonicIdGenerator::new());
+/// assert_eq!(protocol.next_request_id(), RequestId::Number(1));
+/// assert_eq!(protocol.next_request_id(), RequestId::Number(2));
+/// ```
+pub trait RequestIdGenerator: Send + Sync + Debug {
+    /// Produce the id for the next outgoing request.
+    fn next_id(&self) -> RequestId;
+}
+
+/// Generates random UUID string ids.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidIdGenerator;
+
+impl RequestIdGenerator for UuidIdGenerator {
+    fn next_id(&self) -> RequestId {
+        RequestId::String(uuid::Uuid::new_v4().to_string())
+    }
+}
+
+/// Generates increasing numeric ids starting at 1.
+#[derive(Debug, Default)]
+pub struct MonotonicIdGenerator {
+    next: AtomicI64,
+}
+
+impl MonotonicIdGenerator {
+    /// Create a generator starting at 1.
+    pub fn new() -> Self {
+        Self::starting_at(1)
+    }
+
+    /// Create a generator starting at the given id.
+    pub fn starting_at(first: i64) -> Self {
+        Self {
+            next: AtomicI64::new(first),
+        }
+    }
+}
+
+impl RequestIdGenerator for MonotonicIdGenerator {
+    fn next_id(&self) -> RequestId {
+        RequestId::Number(self.next.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Generates increasing string ids with a fixed prefix, e.g. `client-1`.
+#[derive(Debug)]
+pub struct PrefixedIdGenerator {
+    prefix: String,
+    counter: MonotonicIdGenerator,
+}
+
+impl PrefixedIdGenerator {
+    /// Create a generator producing `{prefix}1`, `{prefix}2`, ...
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            counter: MonotonicIdGenerator::new(),
+        }
+    }
+}
+
+impl RequestIdGenerator for PrefixedIdGenerator {
+    fn next_id(&self) -> RequestId {
+        let n = self.counter.next.fetch_add(1, Ordering::Relaxed);
+        RequestId::String(format!("{}{}", self.prefix, n))
+    }
+}
+
 /// Unique identifier for a transport instance.
 ///
 /// # Examples
@@ -107,6 +776,14 @@ pub struct Protocol {
     pending_requests: HashMap<RequestId, RequestContext>,
     /// Current transport ID for this protocol instance.
     transport_id: TransportId,
+    /// Generator for outgoing request ids.
+    id_generator: Arc<dyn RequestIdGenerator>,
+    /// Recently seen request ids from the peer, for duplicate detection.
+    peer_request_ids: HashSet<RequestId>,
+    /// Order in which peer request ids were seen, oldest first.
+    peer_request_order: VecDeque<RequestId>,
+    /// Version agreed on at initialization.
+    negotiated_version: Option<NegotiatedVersion>,
 }
 
 impl Protocol {
@@ -124,15 +801,12 @@ impl Protocol {
     /// let options = ProtocolOptions {
     ///     enforce_strict_capabilities: true,
     ///     debounced_notification_methods: vec!["progress".to_string()],
+    ///     ..Default::default()
     /// };
     /// let protocol = Protocol::new(options);
     /// ```
     pub fn new(options: ProtocolOptions) -> Self {
-        Self {
-            options,
-            pending_requests: HashMap::new(),
-            transport_id: TransportId::new(),
-        }
+        Self::with_transport_id(options, TransportId::new())
     }
 
     /// Create a new protocol instance with a specific transport ID.
@@ -157,7 +831,60 @@ impl Protocol {
             options,
             pending_requests: HashMap::new(),
             transport_id,
+            id_generator: Arc::new(UuidIdGenerator),
+            peer_request_ids: HashSet::new(),
+            peer_request_order: VecDeque::new(),
+            negotiated_version: None,
+        }
+    }
+
+    /// Use a custom generator for outgoing request ids.
+    pub fn with_id_generator(mut self, generator: impl RequestIdGenerator + 'static) -> Self {
+        self.id_generator = Arc::new(generator);
+        self
+    }
+
+    /// Replace the generator for outgoing request ids.
+    pub fn set_id_generator(&mut self, generator: Arc<dyn RequestIdGenerator>) {
+        self.id_generator = generator;
+    }
+
+    /// Generate the id for the next outgoing request.
+    pub fn next_request_id(&self) -> RequestId {
+        self.id_generator.next_id()
+    }
+
+    /// Record a request id received from the peer.
+    ///
+    /// Request ids must not be reused within a session. Returns an
+    /// `INVALID_REQUEST` protocol error if `id` was already used by one of the
+    /// peer's recent requests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::shared::protocol::{Protocol, ProtocolOptions};
+    /// use pmcp::types::RequestId;
+    ///
+    /// let mut protocol = Protocol::new(ProtocolOptions::default());
+    /// assert!(protocol.observe_peer_request(&RequestId::Number(2)).is_ok());
+    /// assert!(protocol.observe_peer_request(&RequestId::Number(2)).is_err());
+    /// ```
+    pub fn observe_peer_request(&mut self, id: &RequestId) -> Result<()> {
+        if !self.peer_request_ids.insert(id.clone()) {
+            return Err(Error::protocol(
+                ErrorCode::INVALID_REQUEST,
+                format!("Duplicate request id {} within session", id),
+            ));
+        }
+
+        self.peer_request_order.push_back(id.clone());
+        if self.peer_request_order.len() > PEER_REQUEST_ID_HISTORY {
+            if let Some(oldest) = self.peer_request_order.pop_front() {
+                self.peer_request_ids.remove(&oldest);
+            }
         }
+        Ok(())
     }
 
     /// Get protocol options.
@@ -165,11 +892,55 @@ impl Protocol {
         &self.options
     }
 
+    /// Record the version agreed on at initialization.
+    pub fn set_negotiated_version(&mut self, version: NegotiatedVersion) {
+        self.negotiated_version = Some(version);
+    }
+
+    /// The version agreed on at initialization, once initialized.
+    pub fn negotiated_version(&self) -> Option<&NegotiatedVersion> {
+        self.negotiated_version.as_ref()
+    }
+
+    /// Validate a raw incoming message when strict mode is enabled.
+    ///
+    /// Does nothing unless [`ProtocolOptions::strict`] is set.
+    pub fn validate_incoming(&self, message: &serde_json::Value) -> Result<()> {
+        if self.options.strict {
+            super::strict::validate_message(message)?;
+        }
+        Ok(())
+    }
+
+    /// Get the timeout for a request.
+    ///
+    /// A timeout set in `request` takes precedence over the method's timeout
+    /// from [`ProtocolOptions`].
+    pub fn request_timeout(&self, method: &str, request: &RequestOptions) -> Duration {
+        request
+            .timeout
+            .unwrap_or_else(|| self.options.timeout_for(method))
+    }
+
     /// Get the transport ID for this protocol instance.
     pub fn transport_id(&self) -> &TransportId {
         &self.transport_id
     }
 
+    /// Register a pending request, rejecting ids that are already pending.
+    pub fn try_register_request(
+        &mut self,
+        id: RequestId,
+    ) -> Result<oneshot::Receiver<JSONRPCResponse>> {
+        if self.pending_requests.contains_key(&id) {
+            return Err(Error::protocol(
+                ErrorCode::INVALID_REQUEST,
+                format!("Request id {} is already in use", id),
+            ));
+        }
+        Ok(self.register_request(id))
+    }
+
     /// Register a pending request.
     pub fn register_request(&mut self, id: RequestId) -> oneshot::Receiver<JSONRPCResponse> {
         let (tx, rx) = oneshot::channel();
@@ -279,11 +1050,43 @@ impl Protocol {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_version_negotiation() {
+        let server = VersionNegotiator::new(["2024-11-05", "2025-03-26", "2024-11-05"]);
+        assert_eq!(server.supported(), ["2025-03-26", "2024-11-05"]);
+        assert_eq!(server.respond_to("2024-11-05").as_str(), "2024-11-05");
+        assert_eq!(server.respond_to("2030-01-01").as_str(), "2025-03-26");
+
+        let client = VersionNegotiator::default();
+        assert_eq!(
+            client.accept("2025-03-26").unwrap(),
+            NegotiatedVersion::new("2025-03-26")
+        );
+        assert!(client.accept("2030-01-01").is_err());
+        assert_eq!(
+            client.highest_common(&["2024-11-05", "2025-06-18"]),
+            Some(NegotiatedVersion::new("2025-06-18"))
+        );
+        assert_eq!(client.highest_common(&["1999-01-01"]), None);
+
+        let old = NegotiatedVersion::new("2024-11-05");
+        assert!(!old.supports(ProtocolFeature::AudioContent));
+        let err = old
+            .require(ProtocolFeature::StructuredToolOutput)
+            .unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::UNSUPPORTED_CAPABILITY));
+        assert_eq!(
+            err.to_string(),
+            "structured tool output needs protocol version 2025-06-18 or later, but 2024-11-05 was negotiated"
+        );
+    }
+
     #[test]
     fn test_protocol_options() {
         let options = ProtocolOptions {
             enforce_strict_capabilities: true,
             debounced_notification_methods: vec!["test".to_string()],
+            ..Default::default()
         };
         assert!(options.enforce_strict_capabilities);
         assert_eq!(options.debounced_notification_methods, vec!["test"]);
@@ -291,6 +1094,43 @@ mod tests {
         let default_options = ProtocolOptions::default();
         assert!(!default_options.enforce_strict_capabilities);
         assert!(default_options.debounced_notification_methods.is_empty());
+        assert_eq!(
+            default_options.default_timeout,
+            Duration::from_millis(crate::DEFAULT_REQUEST_TIMEOUT_MS)
+        );
+        assert_eq!(
+            default_options.timeout_for("tools/call"),
+            DEFAULT_TOOL_CALL_TIMEOUT
+        );
+    }
+
+    #[test]
+    fn test_request_timeouts() {
+        let protocol = Protocol::new(
+            ProtocolOptions::default()
+                .with_default_timeout(Duration::from_secs(30))
+                .with_method_timeout("tools/call", Duration::from_secs(300)),
+        );
+
+        let defaults = RequestOptions::default();
+        assert_eq!(
+            protocol.request_timeout("tools/call", &defaults),
+            Duration::from_secs(300)
+        );
+        assert_eq!(
+            protocol.request_timeout("ping", &defaults),
+            Duration::from_secs(30)
+        );
+
+        let overridden = RequestOptions {
+            timeout: Some(Duration::from_secs(1)),
+            on_progress: None,
+            ..Default::default()
+        };
+        assert_eq!(
+            protocol.request_timeout("tools/call", &overridden),
+            Duration::from_secs(1)
+        );
     }
 
     #[test]
@@ -298,6 +1138,7 @@ mod tests {
         let options = RequestOptions {
             timeout: Some(Duration::from_secs(30)),
             on_progress: None,
+            ..Default::default()
         };
         assert_eq!(options.timeout, Some(Duration::from_secs(30)));
         assert!(options.on_progress.is_none());
@@ -404,6 +1245,7 @@ mod tests {
                 assert_eq!(current, 50);
                 assert_eq!(total, Some(100));
             })),
+            ..Default::default()
         };
 
         // Call the progress callback
@@ -414,6 +1256,119 @@ mod tests {
         assert!(called.load(std::sync::atomic::Ordering::SeqCst));
     }
 
+    #[test]
+    fn test_message_size_limits() {
+        let options = ProtocolOptions::default()
+            .with_max_inbound_message_size(100)
+            .with_max_outbound_message_size(50);
+        let limits = options.message_size_limits;
+
+        assert!(limits.check_inbound(100).is_ok());
+        assert_eq!(
+            limits.check_inbound(101).unwrap_err().error_code(),
+            Some(ErrorCode::INVALID_REQUEST)
+        );
+        assert!(limits
+            .check_inbound(400)
+            .is_err_and(|e| !matches!(e, Error::Transport(_))));
+        assert!(matches!(
+            limits.check_inbound(401),
+            Err(Error::Transport(TransportError::InvalidMessage(_)))
+        ));
+
+        assert!(limits.check_outbound(50).is_ok());
+        assert_eq!(
+            limits.check_outbound(51).unwrap_err().error_code(),
+            Some(ErrorCode::INTERNAL_ERROR)
+        );
+
+        let unlimited = MessageSizeLimits::default();
+        assert!(unlimited.check_inbound(usize::MAX).is_ok());
+        assert!(unlimited.check_outbound(usize::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_message_structure_limits() {
+        let limits = ProtocolOptions::default()
+            .with_max_nesting_depth(3)
+            .with_max_array_length(3)
+            .message_size_limits;
+        let check = |json: &str| {
+            limits
+                .check_inbound_structure(json.as_bytes())
+                .map_err(|e| e.error_code())
+        };
+
+        assert!(check(r#"{"a":{"b":[1,2,3]}}"#).is_ok());
+        assert!(check(r#"[{"a":[]},{"b":{}},{"c":"[[[[,,,,"}]"#).is_ok());
+        assert!(check(r#"{"a":["x\"]]]]","y",{"z":1}]}"#).is_ok());
+        assert_eq!(
+            check(r#"{"a":{"b":{"c":[]}}}"#),
+            Err(Some(ErrorCode::INVALID_REQUEST))
+        );
+        assert_eq!(
+            check(r#"{"a":[1, 2, 3, 4]}"#),
+            Err(Some(ErrorCode::INVALID_REQUEST))
+        );
+        assert_eq!(
+            check(r#"[{}, [], "x", 1]"#),
+            Err(Some(ErrorCode::INVALID_REQUEST))
+        );
+
+        // Without limits nothing is scanned, and without a depth limit
+        // messages too deep for the parser are left to it
+        let unlimited = MessageSizeLimits::default();
+        assert!(unlimited.check_inbound_structure(b"[[[[[[[[[[").is_ok());
+        let arrays_only = MessageSizeLimits::default().with_max_array_length(1);
+        assert!(arrays_only
+            .check_inbound_structure("[".repeat(10_000).as_bytes())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_request_id_generators() {
+        let protocol = Protocol::new(ProtocolOptions::default());
+        assert_ne!(protocol.next_request_id(), protocol.next_request_id());
+
+        let protocol = protocol.with_id_generator(MonotonicIdGenerator::starting_at(10));
+        assert_eq!(protocol.next_request_id(), RequestId::Number(10));
+        assert_eq!(protocol.next_request_id(), RequestId::Number(11));
+
+        let prefixed = PrefixedIdGenerator::new("client-");
+        assert_eq!(
+            prefixed.next_id(),
+            RequestId::String("client-1".to_string())
+        );
+        assert_eq!(
+            prefixed.next_id(),
+            RequestId::String("client-2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_duplicate_request_ids_are_rejected() {
+        let mut protocol = Protocol::new(ProtocolOptions::default());
+
+        protocol
+            .observe_peer_request(&RequestId::Number(2))
+            .unwrap();
+        let err = protocol
+            .observe_peer_request(&RequestId::Number(2))
+            .unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::INVALID_REQUEST));
+
+        // Old ids are forgotten once the history is full.
+        for n in 0..PEER_REQUEST_ID_HISTORY as i64 {
+            protocol
+                .observe_peer_request(&RequestId::Number(1000 + n))
+                .unwrap();
+        }
+        assert!(protocol.observe_peer_request(&RequestId::Number(2)).is_ok());
+
+        let _rx = protocol.try_register_request(RequestId::Number(7)).unwrap();
+        assert!(protocol.try_register_request(RequestId::Number(7)).is_err());
+    }
+
     #[test]
     fn test_protocol_with_enforced_capabilities() {
         let options = ProtocolOptions {
@@ -422,6 +1377,7 @@ mod tests {
                 "notifications/progress".to_string(),
                 "notifications/cancelled".to_string(),
             ],
+            ..Default::default()
         };
 
         let protocol = Protocol::new(options);
@@ -6,6 +6,10 @@ use crate::types::{
     ClientNotification, ClientRequest, JSONRPCNotification, JSONRPCRequest, Notification, Request,
     RequestId, ServerNotification, ServerRequest,
 };
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{self, DeserializeSeed, MapAccess, Visitor};
+use serde::Deserialize;
+use serde_json::value::RawValue;
 use serde_json::Value;
 use std::sync::LazyLock;
 
@@ -111,7 +115,7 @@ pub fn create_notification(notification: Notification) -> JSONRPCNotification<Va
 fn parse_client_request(method: &str, params: &Value) -> Result<ClientRequest> {
     // For methods that don't accept params at all (like "ping"), we should not include
     // the params field. For methods that accept optional params, we convert null to empty object.
-    let request_json = if method == "ping" {
+    let request_json = if client_request_without_params(method) {
         // Ping doesn't accept params at all
         serde_json::json!({
             "method": method,
@@ -137,7 +141,7 @@ fn parse_client_request(method: &str, params: &Value) -> Result<ClientRequest> {
 fn parse_server_request(method: &str, params: &Value) -> Result<ServerRequest> {
     // For methods that don't accept params at all (like "roots/list"), we should not include
     // the params field. For methods that accept optional params, we convert null to empty object.
-    let request_json = if method == "roots/list" {
+    let request_json = if server_request_without_params(method) {
         // roots/list doesn't accept params at all
         serde_json::json!({
             "method": method,
@@ -163,10 +167,7 @@ fn parse_server_request(method: &str, params: &Value) -> Result<ServerRequest> {
 fn parse_client_notification(method: &str, params: &Value) -> Result<ClientNotification> {
     // For notifications that don't accept params, we should not include
     // the params field at all in the JSON object we construct for deserialization
-    let notif_json = if matches!(
-        method,
-        "notifications/initialized" | "notifications/roots/list_changed"
-    ) {
+    let notif_json = if client_notification_without_params(method) {
         // Don't include params field for parameterless notifications
         serde_json::json!({
             "method": method,
@@ -192,13 +193,7 @@ fn parse_client_notification(method: &str, params: &Value) -> Result<ClientNotif
 fn parse_server_notification(method: &str, params: &Value) -> Result<ServerNotification> {
     // For notifications that don't accept params, we should not include
     // the params field at all in the JSON object we construct for deserialization
-    let notif_json = if matches!(
-        method,
-        "notifications/tools/list_changed"
-            | "notifications/prompts/list_changed"
-            | "notifications/resources/list_changed"
-            | "notifications/roots/list_changed"
-    ) {
+    let notif_json = if server_notification_without_params(method) {
         // Don't include params field for parameterless notifications
         serde_json::json!({
             "method": method,
@@ -221,6 +216,186 @@ fn parse_server_notification(method: &str, params: &Value) -> Result<ServerNotif
         .map_err(|e| Error::parse(format!("Invalid server notification: {}", e)))
 }
 
+fn client_request_without_params(method: &str) -> bool {
+    method == "ping"
+}
+
+fn server_request_without_params(method: &str) -> bool {
+    method == "roots/list"
+}
+
+fn client_notification_without_params(method: &str) -> bool {
+    matches!(
+        method,
+        "notifications/initialized" | "notifications/roots/list_changed"
+    )
+}
+
+fn server_notification_without_params(method: &str) -> bool {
+    matches!(
+        method,
+        "notifications/tools/list_changed"
+            | "notifications/prompts/list_changed"
+            | "notifications/resources/list_changed"
+            | "notifications/roots/list_changed"
+    )
+}
+
+/// Parse a request from its method and raw, still-serialized params.
+///
+/// Unlike [`parse_request`], the params are deserialized straight from the
+/// source text into the typed request, without first being built up as a
+/// [`Value`] tree.
+pub fn parse_request_raw(
+    id: RequestId,
+    method: &str,
+    params: Option<&RawValue>,
+) -> Result<(RequestId, Request)> {
+    if let Ok(client_req) = typed_from_raw(method, params, !client_request_without_params(method)) {
+        return Ok((id, Request::Client(Box::new(client_req))));
+    }
+
+    if let Ok(server_req) = typed_from_raw(method, params, !server_request_without_params(method)) {
+        return Ok((id, Request::Server(Box::new(server_req))));
+    }
+
+    Err(Error::method_not_found(method))
+}
+
+/// Parse a notification from its method and raw, still-serialized params.
+///
+/// The borrowed counterpart of [`parse_notification`].
+pub fn parse_notification_raw(method: &str, params: Option<&RawValue>) -> Result<Notification> {
+    let params_json = params.map_or("null", RawValue::get);
+
+    if method == "notifications/progress" {
+        let progress = serde_json::from_str(params_json)
+            .map_err(|e| Error::parse(format!("Invalid progress notification: {}", e)))?;
+        return Ok(Notification::Progress(progress));
+    }
+
+    if method == "notifications/cancelled" {
+        let cancelled = serde_json::from_str(params_json)
+            .map_err(|e| Error::parse(format!("Invalid cancelled notification: {}", e)))?;
+        return Ok(Notification::Cancelled(cancelled));
+    }
+
+    if let Ok(client_notif) =
+        typed_from_raw(method, params, !client_notification_without_params(method))
+    {
+        return Ok(Notification::Client(client_notif));
+    }
+
+    if let Ok(server_notif) =
+        typed_from_raw(method, params, !server_notification_without_params(method))
+    {
+        return Ok(Notification::Server(server_notif));
+    }
+
+    Err(Error::method_not_found(method))
+}
+
+/// Deserialize a `method`/`params` tagged message enum from raw params,
+/// following the same rules as the [`Value`] based helpers: params are left
+/// out for methods that take none, and missing or null params become `{}`.
+fn typed_from_raw<'a, T: Deserialize<'a>>(
+    method: &'a str,
+    params: Option<&'a RawValue>,
+    takes_params: bool,
+) -> serde_json::Result<T> {
+    let params = match params {
+        _ if !takes_params => None,
+        Some(raw) if raw.get() != "null" => Some(raw),
+        _ => Some(serde_json::from_str::<&'static RawValue>("{}")?),
+    };
+    T::deserialize(MethodParams {
+        method: Some(method),
+        params,
+    })
+}
+
+/// Presents a method and its raw params as the `{"method": .., "params": ..}`
+/// map that the typed message enums deserialize from.
+struct MethodParams<'a> {
+    method: Option<&'a str>,
+    params: Option<&'a RawValue>,
+}
+
+impl<'de> de::Deserializer<'de> for MethodParams<'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        visitor.visit_map(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for MethodParams<'de> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> serde_json::Result<Option<K::Value>> {
+        let key = if self.method.is_some() {
+            "method"
+        } else if self.params.is_some() {
+            "params"
+        } else {
+            return Ok(None);
+        };
+        seed.deserialize(BorrowedStrDeserializer::new(key))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> serde_json::Result<V::Value> {
+        if let Some(method) = self.method.take() {
+            return seed.deserialize(BorrowedStrDeserializer::new(method));
+        }
+        match self.params.take() {
+            Some(params) => seed.deserialize(params),
+            None => Err(de::Error::custom("value requested before key")),
+        }
+    }
+}
+
+/// Get the JSON-RPC method name of a typed request.
+pub fn request_method(request: &Request) -> &'static str {
+    match request {
+        Request::Client(req) => match **req {
+            ClientRequest::Initialize(_) => "initialize",
+            ClientRequest::Ping => "ping",
+            ClientRequest::SetLoggingLevel { .. } => "logging/setLevel",
+            ClientRequest::ListTools(_) => "tools/list",
+            ClientRequest::CallTool(_) => "tools/call",
+            ClientRequest::ListPrompts(_) => "prompts/list",
+            ClientRequest::GetPrompt(_) => "prompts/get",
+            ClientRequest::ListResources(_) => "resources/list",
+            ClientRequest::ListResourceTemplates(_) => "resources/templates/list",
+            ClientRequest::ReadResource(_) => "resources/read",
+            ClientRequest::Subscribe(_) => "resources/subscribe",
+            ClientRequest::Unsubscribe(_) => "resources/unsubscribe",
+            ClientRequest::Complete(_) => "completion/complete",
+            ClientRequest::CreateMessage(_) => "sampling/createMessage",
+            ClientRequest::ElicitInputResponse(_) => "elicitation/response",
+        },
+        Request::Server(req) => match **req {
+            ServerRequest::CreateMessage(_) => "sampling/createMessage",
+            ServerRequest::ListRoots => "roots/list",
+            ServerRequest::ElicitInput(_) => "elicitation/input",
+            ServerRequest::Elicit(_) => "elicitation/create",
+        },
+    }
+}
+
 fn client_request_to_jsonrpc(req: ClientRequest) -> (String, Option<Value>) {
     match req {
         // Core protocol requests
@@ -276,6 +451,10 @@ fn server_request_to_jsonrpc(req: ServerRequest) -> (String, Option<Value>) {
             "elicitation/input".to_string(),
             Some(serde_json::to_value(params).unwrap()),
         ),
+        ServerRequest::Elicit(params) => (
+            "elicitation/create".to_string(),
+            Some(serde_json::to_value(params).unwrap()),
+        ),
     }
 }
 
@@ -628,6 +807,7 @@ mod tests {
         let request = Request::Client(Box::new(ClientRequest::CallTool(CallToolRequest {
             name: "test-tool".to_string(),
             arguments: json!({"input": "test"}),
+            meta: None,
         })));
 
         let jsonrpc_request = create_request(id.clone(), request);
@@ -773,6 +953,7 @@ mod tests {
             (
                 ClientRequest::ReadResource(ReadResourceRequest {
                     uri: "test://uri".to_string(),
+                    meta: None,
                 }),
                 "resources/read",
             ),
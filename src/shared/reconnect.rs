@@ -3,12 +3,13 @@
 //! This module provides sophisticated reconnection strategies for network transports,
 //! including exponential backoff, jitter, and circuit breaker patterns.
 
-use crate::error::{Error, ErrorCode, Result};
+use crate::error::{Error, ErrorCategory, ErrorCode, Result};
 #[cfg(target_arch = "wasm32")]
 use futures::lock::{Mutex, RwLock};
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::sync::{Mutex, RwLock};
 #[cfg(not(target_arch = "wasm32"))]
@@ -38,8 +39,17 @@ pub struct ReconnectConfig {
     pub max_retries: Option<u32>,
 
     /// Jitter factor (0.0 to 1.0) to randomize delays.
+    ///
+    /// Only used by [`JitterStrategy::Proportional`].
     pub jitter_factor: f64,
 
+    /// How delays are randomized.
+    pub jitter: JitterStrategy,
+
+    /// Stop retrying once this much time has passed since the first attempt
+    /// (None for unlimited).
+    pub max_elapsed_time: Option<Duration>,
+
     /// Whether to reset delay after successful connection.
     pub reset_on_success: bool,
 
@@ -61,6 +71,8 @@ impl Default for ReconnectConfig {
             growth_factor: 2.0,
             max_retries: None,
             jitter_factor: 0.1,
+            jitter: JitterStrategy::Proportional,
+            max_elapsed_time: None,
             reset_on_success: true,
             success_threshold: Duration::from_secs(60),
             circuit_breaker_threshold: Some(5),
@@ -69,6 +81,100 @@ impl Default for ReconnectConfig {
     }
 }
 
+/// Strategy for randomizing retry delays.
+///
+/// Randomizing delays keeps many clients that lost their connection at the
+/// same time from retrying in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JitterStrategy {
+    /// Use the exponential delay as is.
+    None,
+
+    /// Add up to `jitter_factor` of the delay in either direction.
+    #[default]
+    Proportional,
+
+    /// Pick a delay between zero and the exponential delay.
+    Full,
+
+    /// Keep half of the exponential delay and randomize the other half.
+    Equal,
+
+    /// Pick a delay between the initial delay and three times the previous
+    /// delay, capped at the maximum delay.
+    Decorrelated,
+}
+
+/// Why the reconnection manager stopped retrying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GiveUpReason {
+    /// Reconnection was disabled.
+    Disabled,
+
+    /// `max_retries` attempts failed.
+    MaxRetries,
+
+    /// `max_elapsed_time` passed since the first attempt.
+    MaxElapsedTime,
+
+    /// The circuit breaker is open.
+    CircuitOpen,
+
+    /// The last attempt failed in a way reconnecting cannot fix, such as
+    /// rejected credentials.
+    Unrecoverable(ErrorCategory),
+}
+
+/// Lifecycle event published by a [`ReconnectManager`].
+///
+/// Subscribe with [`ReconnectManager::subscribe`] to reflect connection state
+/// in a user interface.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReconnectEvent {
+    /// A connection attempt is starting (attempts are numbered from 1).
+    Attempting {
+        /// Attempt number.
+        attempt: u32,
+    },
+
+    /// A connection was established.
+    Connected,
+
+    /// A connection attempt failed.
+    Failed {
+        /// Attempt number.
+        attempt: u32,
+        /// Error message.
+        error: String,
+    },
+
+    /// Waiting before the next attempt.
+    Retrying {
+        /// Delay before the next attempt.
+        delay: Duration,
+    },
+
+    /// An established connection was lost.
+    Disconnected,
+
+    /// The circuit breaker opened.
+    CircuitOpened,
+
+    /// The circuit breaker closed again.
+    CircuitClosed,
+
+    /// The manager stopped retrying.
+    GaveUp {
+        /// Number of attempts made.
+        attempts: u32,
+        /// Why retrying stopped.
+        reason: GiveUpReason,
+    },
+}
+
+/// Number of events buffered for slow subscribers.
+const EVENT_CAPACITY: usize = 64;
+
 /// Connection state for reconnection logic.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConnectionState {
@@ -117,9 +223,21 @@ pub struct ReconnectManager {
     /// Circuit breaker opened time.
     circuit_opened_at: Arc<Mutex<Option<Instant>>>,
 
+    /// Start of the current run of attempts.
+    attempts_started_at: Arc<Mutex<Option<Instant>>>,
+
+    /// Previous delay in nanoseconds, for decorrelated jitter.
+    last_delay_nanos: AtomicU64,
+
+    /// Lifecycle event publisher.
+    events: broadcast::Sender<ReconnectEvent>,
+
     /// Whether reconnection is enabled.
     enabled: AtomicBool,
 
+    /// Category of the last failure, if reconnecting cannot recover from it.
+    unrecoverable: parking_lot::Mutex<Option<ErrorCategory>>,
+
     /// Callbacks.
     callbacks: Arc<ReconnectCallbacks>,
 }
@@ -196,7 +314,11 @@ impl ReconnectManager {
             last_attempt: Arc::new(Mutex::new(None)),
             last_success: Arc::new(Mutex::new(None)),
             circuit_opened_at: Arc::new(Mutex::new(None)),
+            attempts_started_at: Arc::new(Mutex::new(None)),
+            last_delay_nanos: AtomicU64::new(0),
+            events: broadcast::channel(EVENT_CAPACITY).0,
             enabled: AtomicBool::new(true),
+            unrecoverable: parking_lot::Mutex::new(None),
             callbacks: Arc::new(ReconnectCallbacks::default()),
         }
     }
@@ -206,6 +328,18 @@ impl ReconnectManager {
         self.callbacks = Arc::new(callbacks);
     }
 
+    /// Subscribe to lifecycle events.
+    ///
+    /// Only events published after subscribing are received.
+    pub fn subscribe(&self) -> broadcast::Receiver<ReconnectEvent> {
+        self.events.subscribe()
+    }
+
+    fn emit(&self, event: ReconnectEvent) {
+        // Having no subscribers is not an error
+        let _ = self.events.send(event);
+    }
+
     /// Enable or disable reconnection.
     pub fn set_enabled(&self, enabled: bool) {
         self.enabled.store(enabled, Ordering::Relaxed);
@@ -218,43 +352,60 @@ impl ReconnectManager {
 
     /// Check if reconnection should be attempted.
     pub async fn should_reconnect(&self) -> bool {
-        if !self.enabled.load(Ordering::Relaxed) {
-            return false;
-        }
-
         let state = *self.state.read().await;
         match state {
             ConnectionState::Connected | ConnectionState::Connecting => false,
-            ConnectionState::CircuitOpen => {
-                // Check if circuit should be closed
-                let opened_at_opt = *self.circuit_opened_at.lock().await;
-                if let Some(opened_at) = opened_at_opt {
-                    if opened_at.elapsed() >= self.config.circuit_breaker_timeout {
-                        info!("Circuit breaker timeout reached, closing circuit");
-                        *self.circuit_opened_at.lock().await = None;
-                        *self.state.write().await = ConnectionState::Disconnected;
-
-                        if let Some(callback) = &self.callbacks.on_circuit_close {
-                            callback();
-                        }
-
-                        true
-                    } else {
-                        false
+            _ => self.give_up_reason().await.is_none(),
+        }
+    }
+
+    /// Reason to stop retrying, if any.
+    ///
+    /// Closes the circuit breaker when its timeout has passed.
+    async fn give_up_reason(&self) -> Option<GiveUpReason> {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return Some(GiveUpReason::Disabled);
+        }
+
+        if let Some(category) = *self.unrecoverable.lock() {
+            return Some(GiveUpReason::Unrecoverable(category));
+        }
+
+        if *self.state.read().await == ConnectionState::CircuitOpen {
+            // Check if circuit should be closed
+            let opened_at_opt = *self.circuit_opened_at.lock().await;
+            match opened_at_opt {
+                Some(opened_at) if opened_at.elapsed() >= self.config.circuit_breaker_timeout => {
+                    info!("Circuit breaker timeout reached, closing circuit");
+                    *self.circuit_opened_at.lock().await = None;
+                    *self.state.write().await = ConnectionState::Disconnected;
+
+                    if let Some(callback) = &self.callbacks.on_circuit_close {
+                        callback();
                     }
-                } else {
-                    false
-                }
-            },
-            _ => {
-                // Check retry limit
-                if let Some(max_retries) = self.config.max_retries {
-                    self.retry_count.load(Ordering::Relaxed) < max_retries
-                } else {
-                    true
-                }
-            },
+                    self.emit(ReconnectEvent::CircuitClosed);
+                    return None;
+                },
+                _ => return Some(GiveUpReason::CircuitOpen),
+            }
         }
+
+        // Check retry limit
+        if let Some(max_retries) = self.config.max_retries {
+            if self.retry_count.load(Ordering::Relaxed) >= max_retries {
+                return Some(GiveUpReason::MaxRetries);
+            }
+        }
+
+        // Check elapsed time limit
+        if let Some(max_elapsed) = self.config.max_elapsed_time {
+            let started_at = *self.attempts_started_at.lock().await;
+            if started_at.is_some_and(|started| started.elapsed() >= max_elapsed) {
+                return Some(GiveUpReason::MaxElapsedTime);
+            }
+        }
+
+        None
     }
 
     /// Calculate next retry delay with exponential backoff and jitter.
@@ -269,29 +420,46 @@ impl ReconnectManager {
                 .powi(i32::try_from(retry_count).unwrap_or(i32::MAX));
 
         // Cap at maximum delay
-        let capped_delay = base_delay.min(self.config.max_delay.as_secs_f64());
-
-        // Add jitter
-        let jitter_range = capped_delay * self.config.jitter_factor;
-        // Simple jitter calculation without external dependency
-        let jitter = {
-            use std::time::{SystemTime, UNIX_EPOCH};
-            let nanos = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .subsec_nanos();
-            let random_factor = nanos as f64 / 1_000_000_000.0; // 0.0 to 1.0
-            (random_factor * jitter_range).mul_add(2.0, -jitter_range)
+        let max_delay = self.config.max_delay.as_secs_f64();
+        let capped_delay = base_delay.min(max_delay);
+
+        let final_delay = match self.config.jitter {
+            JitterStrategy::None => capped_delay,
+            JitterStrategy::Proportional => {
+                let jitter_range = capped_delay * self.config.jitter_factor;
+                let jitter = (random_fraction() * jitter_range).mul_add(2.0, -jitter_range);
+                (capped_delay + jitter).max(0.0)
+            },
+            JitterStrategy::Full => capped_delay * random_fraction(),
+            JitterStrategy::Equal => {
+                (capped_delay / 2.0).mul_add(random_fraction(), capped_delay / 2.0)
+            },
+            JitterStrategy::Decorrelated => {
+                let initial = self.config.initial_delay.as_secs_f64();
+                let previous = Duration::from_nanos(self.last_delay_nanos.load(Ordering::Relaxed))
+                    .as_secs_f64()
+                    .max(initial);
+                let upper = previous * 3.0;
+                (upper - initial)
+                    .mul_add(random_fraction(), initial)
+                    .min(max_delay)
+            },
         };
-        let final_delay = (capped_delay + jitter).max(0.0);
 
-        Duration::from_secs_f64(final_delay)
+        let delay = Duration::from_secs_f64(final_delay);
+        self.last_delay_nanos.store(
+            u64::try_from(delay.as_nanos()).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        delay
     }
 
     /// Notify that a connection attempt is starting.
     pub async fn on_connecting(&self) {
         *self.state.write().await = ConnectionState::Connecting;
-        *self.last_attempt.lock().await = Some(Instant::now());
+        let now = Instant::now();
+        *self.last_attempt.lock().await = Some(now);
+        self.attempts_started_at.lock().await.get_or_insert(now);
         self.total_attempts.fetch_add(1, Ordering::Relaxed);
 
         let retry_count = self.retry_count.load(Ordering::Relaxed);
@@ -300,12 +468,18 @@ impl ReconnectManager {
         if let Some(callback) = &self.callbacks.on_connecting {
             callback(retry_count);
         }
+        self.emit(ReconnectEvent::Attempting {
+            attempt: retry_count + 1,
+        });
     }
 
     /// Notify that a connection was successful.
     pub async fn on_connected(&self) {
         *self.state.write().await = ConnectionState::Connected;
         *self.last_success.lock().await = Some(Instant::now());
+        *self.attempts_started_at.lock().await = None;
+        self.last_delay_nanos.store(0, Ordering::Relaxed);
+        *self.unrecoverable.lock() = None;
 
         self.total_successes.fetch_add(1, Ordering::Relaxed);
         self.failure_count.store(0, Ordering::Relaxed);
@@ -319,16 +493,30 @@ impl ReconnectManager {
         if let Some(callback) = &self.callbacks.on_connected {
             callback();
         }
+        self.emit(ReconnectEvent::Connected);
     }
 
     /// Notify that a connection attempt failed.
+    ///
+    /// Failures whose [`ErrorCategory`] is not recoverable, such as
+    /// authentication failures, stop further attempts until the manager is
+    /// [`reset`](Self::reset) or a connection succeeds.
     pub async fn on_connection_failed(&self, error: &Error) {
         *self.state.write().await = ConnectionState::WaitingRetry;
 
-        self.retry_count.fetch_add(1, Ordering::Relaxed);
+        let category = error.category();
+        if !category.is_recoverable() {
+            *self.unrecoverable.lock() = Some(category);
+        }
+
+        let attempt = self.retry_count.fetch_add(1, Ordering::Relaxed) + 1;
         let failure_count = self.failure_count.fetch_add(1, Ordering::Relaxed) + 1;
 
         warn!("Connection attempt failed: {}", error);
+        self.emit(ReconnectEvent::Failed {
+            attempt,
+            error: error.to_string(),
+        });
 
         // Check circuit breaker
         if let Some(threshold) = self.config.circuit_breaker_threshold {
@@ -341,6 +529,7 @@ impl ReconnectManager {
                 if let Some(callback) = &self.callbacks.on_circuit_open {
                     callback();
                 }
+                self.emit(ReconnectEvent::CircuitOpened);
             }
         }
 
@@ -366,6 +555,7 @@ impl ReconnectManager {
 
         *self.state.write().await = ConnectionState::Disconnected;
         info!("Connection lost");
+        self.emit(ReconnectEvent::Disconnected);
     }
 
     /// Execute reconnection with the provided connect function.
@@ -375,7 +565,8 @@ impl ReconnectManager {
         Fut: std::future::Future<Output = Result<()>>,
     {
         loop {
-            if !self.should_reconnect().await {
+            if let Some(reason) = self.give_up_reason().await {
+                self.give_up(reason);
                 return Err(Error::protocol(
                     ErrorCode::INTERNAL_ERROR,
                     "Reconnection disabled or limit reached",
@@ -392,18 +583,29 @@ impl ReconnectManager {
                 Err(e) => {
                     self.on_connection_failed(&e).await;
 
-                    if !self.should_reconnect().await {
+                    if let Some(reason) = self.give_up_reason().await {
+                        self.give_up(reason);
                         return Err(e);
                     }
 
                     let delay = self.calculate_delay();
                     info!("Retrying connection in {:?}", delay);
+                    self.emit(ReconnectEvent::Retrying { delay });
                     sleep(delay).await;
                 },
             }
         }
     }
 
+    fn give_up(&self, reason: GiveUpReason) {
+        let attempts = self.retry_count.load(Ordering::Relaxed);
+        warn!(
+            "Giving up reconnection after {} attempts: {:?}",
+            attempts, reason
+        );
+        self.emit(ReconnectEvent::GaveUp { attempts, reason });
+    }
+
     /// Get reconnection statistics.
     pub fn stats(&self) -> ReconnectStats {
         ReconnectStats {
@@ -418,9 +620,20 @@ impl ReconnectManager {
     pub fn reset(&self) {
         self.retry_count.store(0, Ordering::Relaxed);
         self.failure_count.store(0, Ordering::Relaxed);
+        *self.unrecoverable.lock() = None;
     }
 }
 
+/// Pseudo-random number in `[0, 1)` without an external dependency.
+fn random_fraction() -> f64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .subsec_nanos();
+    f64::from(nanos) / 1_000_000_000.0
+}
+
 /// Reconnection statistics.
 #[derive(Debug, Clone)]
 pub struct ReconnectStats {
@@ -592,4 +805,103 @@ mod tests {
         assert_eq!(attempt_count.load(Ordering::Relaxed), 3);
         assert_eq!(manager.state().await, ConnectionState::Connected);
     }
+
+    #[tokio::test]
+    async fn test_unrecoverable_failures_stop_retrying() {
+        let manager = ReconnectManager::new(ReconnectConfig {
+            initial_delay: Duration::from_millis(1),
+            max_retries: Some(5),
+            ..Default::default()
+        });
+        let attempt_count = AtomicU32::new(0);
+
+        let result = manager
+            .reconnect_with(|| {
+                attempt_count.fetch_add(1, Ordering::Relaxed);
+                async { Err(Error::authentication("invalid token")) }
+            })
+            .await;
+
+        assert!(matches!(result, Err(Error::Authentication(_))));
+        assert_eq!(attempt_count.load(Ordering::Relaxed), 1);
+        assert!(!manager.should_reconnect().await);
+
+        manager.reset();
+        assert!(manager.should_reconnect().await);
+
+        // Transient failures keep retrying until the limit.
+        let result = manager
+            .reconnect_with(|| {
+                attempt_count.fetch_add(1, Ordering::Relaxed);
+                async {
+                    Err(Error::from(std::io::Error::new(
+                        std::io::ErrorKind::ConnectionReset,
+                        "reset",
+                    )))
+                }
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempt_count.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    fn test_jitter_strategies_stay_in_bounds() {
+        let delay_with = |jitter| {
+            let manager = ReconnectManager::new(ReconnectConfig {
+                initial_delay: Duration::from_millis(100),
+                max_delay: Duration::from_secs(1),
+                jitter,
+                ..Default::default()
+            });
+            manager.retry_count.store(2, Ordering::Relaxed);
+            manager.calculate_delay()
+        };
+
+        assert_eq!(delay_with(JitterStrategy::None), Duration::from_millis(400));
+        assert!(delay_with(JitterStrategy::Full) <= Duration::from_millis(400));
+        let equal = delay_with(JitterStrategy::Equal);
+        assert!(equal >= Duration::from_millis(200) && equal <= Duration::from_millis(400));
+        let decorrelated = delay_with(JitterStrategy::Decorrelated);
+        assert!(
+            decorrelated >= Duration::from_millis(100)
+                && decorrelated <= Duration::from_millis(300)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_events_and_max_elapsed_time() {
+        let manager = ReconnectManager::new(ReconnectConfig {
+            initial_delay: Duration::from_millis(20),
+            jitter: JitterStrategy::None,
+            max_elapsed_time: Some(Duration::from_millis(30)),
+            circuit_breaker_threshold: None,
+            ..Default::default()
+        });
+        let mut events = manager.subscribe();
+
+        let result = manager
+            .reconnect_with(|| async { Err(Error::internal("refused")) })
+            .await;
+        assert!(result.is_err());
+
+        let mut received = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            received.push(event);
+        }
+        assert_eq!(
+            received.first(),
+            Some(&ReconnectEvent::Attempting { attempt: 1 })
+        );
+        assert!(received.contains(&ReconnectEvent::Retrying {
+            delay: Duration::from_millis(40)
+        }));
+        assert!(matches!(
+            received.last(),
+            Some(ReconnectEvent::GaveUp {
+                reason: GiveUpReason::MaxElapsedTime,
+                ..
+            })
+        ));
+    }
 }
@@ -0,0 +1,516 @@
+//! Recording and replaying transport sessions.
+//!
+//! [`RecordingTransport`] wraps another transport and appends every message
+//! sent or received through it to a file, one JSON object per line, with the
+//! time since recording started and the message as it appears on the wire:
+//!
+//! ```text
+//! {"elapsedMs":0,"direction":"Outbound","message":{"jsonrpc":"2.0","id":1,"method":"initialize",...}}
+//! {"elapsedMs":3,"direction":"Inbound","message":{"jsonrpc":"2.0","id":1,"result":{...}}}
+//! ```
+//!
+//! [`ReplayTransport`] plays such a [`Recording`] back as a fake server: each
+//! message the client sends must match the next recorded outbound message,
+//! and the recorded inbound messages that follow are returned from
+//! `receive`. Together they make golden-file tests for clients possible
+//! without running a real server: record a session once against the real
+//! thing, commit the file, and replay it in CI.
+//!
+//! Messages are compared without their request ids, and ids in the replayed
+//! responses are rewritten to those the client actually used, so clients
+//! with random ids replay too. Ids that appear elsewhere, such as in
+//! progress tokens derived from them, are not rewritten; record and replay
+//! with a deterministic [`RequestIdGenerator`] such as
+//! [`MonotonicIdGenerator`] when a session depends on them.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::shared::recording::{RecordingTransport, ReplayTransport};
+//! use pmcp::{Client, ClientCapabilities, StdioTransport};
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! // Record a session against a real server
+//! let transport = RecordingTransport::create(StdioTransport::new(), "tests/golden/tools.jsonl")?;
+//! let mut client = Client::new(transport);
+//! client.initialize(ClientCapabilities::default()).await?;
+//! let recorded = client.list_tools(None).await?;
+//!
+//! // Replay it later, without the server
+//! let mut client = Client::new(ReplayTransport::open("tests/golden/tools.jsonl")?);
+//! client.initialize(ClientCapabilities::default()).await?;
+//! let replayed = client.list_tools(None).await?;
+//! assert_eq!(replayed.tools.len(), recorded.tools.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`RequestIdGenerator`]: crate::shared::RequestIdGenerator
+//! [`MonotonicIdGenerator`]: crate::shared::MonotonicIdGenerator
+
+use crate::error::{Result, TransportError};
+use crate::shared::event_store::MessageDirection;
+use crate::shared::frame_parser::parse_frame;
+use crate::shared::stdio::StdioTransport;
+use crate::shared::transport::{Transport, TransportMessage};
+use crate::types::RequestId;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// One line of a recording file.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Line<'a> {
+    elapsed_ms: u64,
+    direction: MessageDirection,
+    #[serde(borrow)]
+    message: &'a RawValue,
+}
+
+/// A message captured by a [`RecordingTransport`].
+#[derive(Debug, Clone)]
+pub struct RecordedMessage {
+    /// Time since the recording started.
+    pub elapsed: Duration,
+    /// Whether the recording side sent or received the message.
+    pub direction: MessageDirection,
+    /// The message.
+    pub message: TransportMessage,
+}
+
+/// A recorded session, as written by a [`RecordingTransport`].
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    messages: Vec<RecordedMessage>,
+}
+
+impl Recording {
+    /// Read a recording file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse the contents of a recording file; blank lines are skipped.
+    pub fn parse(text: &str) -> Result<Self> {
+        let messages = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                let invalid = |e: &dyn std::fmt::Display| {
+                    TransportError::InvalidMessage(format!(
+                        "Invalid recording line {}: {}",
+                        index + 1,
+                        e
+                    ))
+                };
+                let line: Line<'_> = serde_json::from_str(line).map_err(|e| invalid(&e))?;
+                Ok(RecordedMessage {
+                    elapsed: Duration::from_millis(line.elapsed_ms),
+                    direction: line.direction,
+                    message: parse_frame(line.message.get().as_bytes()).map_err(|e| invalid(&e))?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { messages })
+    }
+
+    /// The recorded messages, in order.
+    pub fn messages(&self) -> &[RecordedMessage] {
+        &self.messages
+    }
+}
+
+/// The wire form of a message, as a JSON value.
+fn wire_value(message: &TransportMessage) -> Result<Value> {
+    serde_json::from_slice(&StdioTransport::serialize_message(message)?)
+        .map_err(|e| TransportError::Serialization(e.to_string()).into())
+}
+
+/// A transport that writes every message through it to a recording file.
+///
+/// See the [module documentation](self).
+#[derive(Debug)]
+pub struct RecordingTransport<T> {
+    inner: T,
+    file: BufWriter<File>,
+    started: Instant,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    /// Record the messages of `inner` to the file at `path`, replacing it if
+    /// it exists.
+    pub fn create(inner: T, path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            inner,
+            file: BufWriter::new(File::create(path)?),
+            started: Instant::now(),
+        })
+    }
+
+    /// Stop recording and return the wrapped transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    fn record(&mut self, direction: MessageDirection, message: &TransportMessage) -> Result<()> {
+        let bytes = StdioTransport::serialize_message(message)?;
+        let message = String::from_utf8(bytes)
+            .map_err(|e| TransportError::Serialization(e.to_string()))
+            .and_then(|text| {
+                RawValue::from_string(text)
+                    .map_err(|e| TransportError::Serialization(e.to_string()))
+            })?;
+        let line = Line {
+            elapsed_ms: u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            direction,
+            message: &message,
+        };
+        serde_json::to_writer(&mut self.file, &line)
+            .map_err(|e| TransportError::Serialization(e.to_string()))?;
+        // Flush every line so a crashed session still leaves its recording
+        self.file.write_all(b"\n")?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: Transport> Transport for RecordingTransport<T> {
+    async fn send(&mut self, message: TransportMessage) -> Result<()> {
+        self.record(MessageDirection::Outbound, &message)?;
+        self.inner.send(message).await
+    }
+
+    async fn receive(&mut self) -> Result<TransportMessage> {
+        let message = self.inner.receive().await?;
+        self.record(MessageDirection::Inbound, &message)?;
+        Ok(message)
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner.close().await
+    }
+
+    async fn send_batch(&mut self, messages: Vec<TransportMessage>) -> Result<()> {
+        for message in &messages {
+            self.record(MessageDirection::Outbound, message)?;
+        }
+        self.inner.send_batch(messages).await
+    }
+
+    async fn receive_batch(&mut self) -> Result<Vec<TransportMessage>> {
+        let messages = self.inner.receive_batch().await?;
+        for message in &messages {
+            self.record(MessageDirection::Inbound, message)?;
+        }
+        Ok(messages)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    fn transport_type(&self) -> &'static str {
+        self.inner.transport_type()
+    }
+}
+
+/// A fake server that plays back a [`Recording`].
+///
+/// The recording must have been made on the client's side. A message sent
+/// out of turn, or unlike the recorded one, is rejected with
+/// [`TransportError::InvalidMessage`] describing both. Once the recording is
+/// exhausted, `receive` fails with [`TransportError::ConnectionClosed`].
+#[derive(Debug)]
+pub struct ReplayTransport {
+    messages: VecDeque<RecordedMessage>,
+    /// Request ids of the recording mapped to those the client used
+    ids: HashMap<RequestId, RequestId>,
+    check_params: bool,
+    paced: bool,
+    /// Recorded time of the last message played
+    elapsed: Duration,
+    closed: bool,
+}
+
+impl ReplayTransport {
+    /// Play back `recording`.
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            messages: recording.messages.into(),
+            ids: HashMap::new(),
+            check_params: true,
+            paced: false,
+            elapsed: Duration::ZERO,
+            closed: false,
+        }
+    }
+
+    /// Play back the recording file at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Recording::load(path).map(Self::new)
+    }
+
+    /// Set whether sent messages must match the recorded ones in full, or
+    /// only in kind and method.
+    ///
+    /// Defaults to `true`.
+    pub fn check_params(mut self, check: bool) -> Self {
+        self.check_params = check;
+        self
+    }
+
+    /// Set whether inbound messages are delayed by the gaps recorded before
+    /// them.
+    ///
+    /// Defaults to `false`, returning them immediately.
+    pub fn paced(mut self, paced: bool) -> Self {
+        self.paced = paced;
+        self
+    }
+
+    /// Number of recorded messages not played yet.
+    pub fn remaining(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether every recorded message has been played.
+    pub fn is_finished(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Check a sent message against the recorded one.
+    fn matches(&self, expected: &TransportMessage, actual: &TransportMessage) -> Result<bool> {
+        let mut expected = wire_value(expected)?;
+        let mut actual = wire_value(actual)?;
+        for value in [&mut expected, &mut actual] {
+            if let Some(object) = value.as_object_mut() {
+                object.remove("id");
+                if !self.check_params {
+                    object.remove("params");
+                    object.remove("result");
+                    object.remove("error");
+                }
+            }
+        }
+        Ok(expected == actual)
+    }
+}
+
+/// A short description of a message for mismatch errors.
+fn describe(message: &TransportMessage) -> String {
+    match wire_value(message) {
+        Ok(value) => value.to_string(),
+        Err(e) => format!("<{}>", e),
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn send(&mut self, message: TransportMessage) -> Result<()> {
+        if self.closed {
+            return Err(TransportError::ConnectionClosed.into());
+        }
+        let expected = match self.messages.front() {
+            Some(recorded) if recorded.direction == MessageDirection::Outbound => recorded,
+            Some(recorded) => {
+                return Err(TransportError::InvalidMessage(format!(
+                    "Replay expected {} from the server, but the client sent {}",
+                    describe(&recorded.message),
+                    describe(&message)
+                ))
+                .into())
+            },
+            None => {
+                return Err(TransportError::InvalidMessage(format!(
+                    "Replay has ended, but the client sent {}",
+                    describe(&message)
+                ))
+                .into())
+            },
+        };
+        if !self.matches(&expected.message, &message)? {
+            return Err(TransportError::InvalidMessage(format!(
+                "Replay expected the client to send {}, but it sent {}",
+                describe(&expected.message),
+                describe(&message)
+            ))
+            .into());
+        }
+
+        let recorded_id = match &expected.message {
+            TransportMessage::Request { id, .. } => Some(id.clone()),
+            _ => None,
+        };
+        self.elapsed = expected.elapsed;
+        if let (Some(recorded), TransportMessage::Request { id, .. }) = (recorded_id, message) {
+            self.ids.insert(recorded, id);
+        }
+        self.messages.pop_front();
+        Ok(())
+    }
+
+    async fn receive(&mut self) -> Result<TransportMessage> {
+        if self.closed {
+            return Err(TransportError::ConnectionClosed.into());
+        }
+        let recorded = match self.messages.front() {
+            Some(recorded) if recorded.direction == MessageDirection::Inbound => recorded,
+            Some(recorded) => {
+                return Err(TransportError::InvalidMessage(format!(
+                    "Replay expected the client to send {} next",
+                    describe(&recorded.message)
+                ))
+                .into())
+            },
+            None => {
+                self.closed = true;
+                return Err(TransportError::ConnectionClosed.into());
+            },
+        };
+
+        if self.paced {
+            tokio::time::sleep(recorded.elapsed.saturating_sub(self.elapsed)).await;
+        }
+        let Some(recorded) = self.messages.pop_front() else {
+            return Err(TransportError::ConnectionClosed.into());
+        };
+        self.elapsed = recorded.elapsed;
+
+        let mut message = recorded.message;
+        if let TransportMessage::Response(response) = &mut message {
+            if let Some(actual) = self.ids.remove(&response.id) {
+                response.id = actual;
+            }
+        }
+        Ok(message)
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.closed = true;
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        !self.closed
+    }
+
+    fn transport_type(&self) -> &'static str {
+        "replay"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::simple_tool::SyncTool;
+    use crate::shared::{InMemoryTransport, MonotonicIdGenerator};
+    use crate::types::{ClientRequest, Request};
+    use crate::{Client, ClientBuilder, ClientCapabilities, Server};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_recorded_session_replays_without_server() {
+        let path =
+            std::env::temp_dir().join(format!("pmcp-recording-{}.jsonl", uuid::Uuid::new_v4()));
+        let (client_transport, server_transport) = InMemoryTransport::pair();
+        let server = Server::builder()
+            .name("recorded")
+            .version("1.0.0")
+            .tool("echo", SyncTool::new("echo", Ok))
+            .build()
+            .unwrap();
+        tokio::spawn(server.run(server_transport));
+
+        let mut client =
+            ClientBuilder::new(RecordingTransport::create(client_transport, &path).unwrap())
+                .request_id_generator(MonotonicIdGenerator::new())
+                .build();
+        client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+        let recorded = client
+            .call_tool("echo".to_string(), json!({"text": "hi"}))
+            .await
+            .unwrap();
+        drop(client);
+
+        let recording = Recording::load(&path).unwrap();
+        let directions: Vec<_> = recording.messages().iter().map(|m| m.direction).collect();
+        assert_eq!(
+            directions,
+            [
+                MessageDirection::Outbound,
+                MessageDirection::Inbound,
+                MessageDirection::Outbound,
+                MessageDirection::Outbound,
+                MessageDirection::Inbound,
+            ]
+        );
+
+        // Replay with random ids, which are mapped onto the recorded ones
+        let mut client = Client::new(ReplayTransport::new(recording));
+        let result = client
+            .initialize(ClientCapabilities::default())
+            .await
+            .unwrap();
+        assert_eq!(result.server_info.name, "recorded");
+        let replayed = client
+            .call_tool("echo".to_string(), json!({"text": "hi"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(replayed).unwrap(),
+            serde_json::to_value(recorded).unwrap()
+        );
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_replay_rejects_unexpected_messages() {
+        let recording = Recording::parse(concat!(
+            r#"{"elapsedMs":0,"direction":"Outbound","message":{"jsonrpc":"2.0","id":1,"method":"ping"}}"#,
+            "\n\n",
+            r#"{"elapsedMs":5,"direction":"Inbound","message":{"jsonrpc":"2.0","id":1,"result":{}}}"#,
+        ))
+        .unwrap();
+        let request = |request: ClientRequest| TransportMessage::Request {
+            id: RequestId::from("a"),
+            request: Request::Client(Box::new(request)),
+        };
+
+        let mut replay = ReplayTransport::new(recording);
+        assert!(replay.receive().await.is_err());
+        let err = replay
+            .send(request(ClientRequest::ListTools(Default::default())))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("tools/list"));
+
+        replay.send(request(ClientRequest::Ping)).await.unwrap();
+        let TransportMessage::Response(response) = replay.receive().await.unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(response.id, RequestId::from("a"));
+        assert!(replay.is_finished());
+        assert!(matches!(
+            replay.receive().await,
+            Err(crate::Error::Transport(TransportError::ConnectionClosed))
+        ));
+
+        assert!(Recording::parse("{}")
+            .unwrap_err()
+            .to_string()
+            .contains("line 1"));
+    }
+}
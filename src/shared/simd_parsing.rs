@@ -8,6 +8,15 @@
 //! - Parallel HTTP header parsing
 //! - Vectorized base64 encoding/decoding
 //! - SIMD-accelerated UTF-8 validation
+//!
+//! CPU features are detected once per process. Each parser then dispatches to
+//! the best available [`SimdBackend`] (AVX2, SSE4.2, NEON, WebAssembly SIMD,
+//! or scalar), and [`ParsingMetrics`] reports which one ran.
+//!
+//! WebAssembly has no runtime feature detection: the SIMD128 backend is used
+//! when the module is compiled with `-C target-feature=+simd128`, which every
+//! current browser, Cloudflare Workers and Spin support, and the scalar
+//! backend otherwise.
 
 use crate::error::{Error, Result};
 use crate::shared::sse_parser::SseEvent;
@@ -16,8 +25,10 @@ use base64::{engine::general_purpose, Engine as _};
 use serde_json;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 
 /// CPU feature detection results.
 #[derive(Debug, Clone, Copy)]
@@ -28,15 +39,50 @@ pub struct CpuFeatures {
     pub sse42: bool,
     /// SSSE3 support available
     pub ssse3: bool,
+    /// NEON support available
+    pub neon: bool,
+    /// WebAssembly SIMD128 support compiled in
+    pub simd128: bool,
 }
 
 impl CpuFeatures {
     /// Detect CPU features at runtime.
+    ///
+    /// Detection runs once; later calls return the cached result.
     pub fn detect() -> Self {
-        Self {
+        static DETECTED: OnceLock<CpuFeatures> = OnceLock::new();
+        *DETECTED.get_or_init(|| Self {
             avx2: Self::has_avx2(),
             sse42: Self::has_sse42(),
             ssse3: Self::has_ssse3(),
+            neon: Self::has_neon(),
+            simd128: Self::has_simd128(),
+        })
+    }
+
+    /// The fastest backend these features support.
+    pub fn best_backend(&self) -> SimdBackend {
+        if self.avx2 {
+            SimdBackend::Avx2
+        } else if self.sse42 {
+            SimdBackend::Sse42
+        } else if self.neon {
+            SimdBackend::Neon
+        } else if self.simd128 {
+            SimdBackend::Simd128
+        } else {
+            SimdBackend::Scalar
+        }
+    }
+
+    /// Whether `backend` can run with these features.
+    pub fn supports(&self, backend: SimdBackend) -> bool {
+        match backend {
+            SimdBackend::Avx2 => self.avx2,
+            SimdBackend::Sse42 => self.sse42,
+            SimdBackend::Neon => self.neon,
+            SimdBackend::Simd128 => self.simd128,
+            SimdBackend::Scalar => true,
         }
     }
 
@@ -69,6 +115,204 @@ impl CpuFeatures {
     fn has_ssse3() -> bool {
         false
     }
+
+    #[cfg(target_arch = "aarch64")]
+    fn has_neon() -> bool {
+        std::arch::is_aarch64_feature_detected!("neon")
+    }
+
+    #[cfg(not(target_arch = "aarch64"))]
+    fn has_neon() -> bool {
+        false
+    }
+
+    fn has_simd128() -> bool {
+        cfg!(all(target_arch = "wasm32", target_feature = "simd128"))
+    }
+}
+
+/// Implementation used for byte scanning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SimdBackend {
+    /// 32 bytes at a time with AVX2
+    Avx2,
+    /// 16 bytes at a time with SSE4.2
+    Sse42,
+    /// 16 bytes at a time with NEON
+    Neon,
+    /// 16 bytes at a time with WebAssembly SIMD128
+    Simd128,
+    /// One byte at a time
+    #[default]
+    Scalar,
+}
+
+impl SimdBackend {
+    /// Whether this backend uses vector instructions.
+    pub fn is_vectorized(self) -> bool {
+        self != Self::Scalar
+    }
+
+    /// Position of the first byte in `data` equal to any of `needles`.
+    ///
+    /// A backend the CPU does not support degrades to the scalar scan.
+    pub fn find_any(self, data: &[u8], needles: &[u8]) -> Option<usize> {
+        if !CpuFeatures::detect().supports(self) {
+            return kernels::find_any_scalar(data, needles);
+        }
+        kernels::find_any(self, data, needles)
+    }
+}
+
+/// Vectorized byte search kernels.
+///
+/// Callers must only select a backend the CPU supports.
+#[allow(unsafe_code)]
+mod kernels {
+    use super::SimdBackend;
+
+    pub(super) fn find_any(backend: SimdBackend, data: &[u8], needles: &[u8]) -> Option<usize> {
+        match backend {
+            // SAFETY: the caller checked that the CPU supports the backend.
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Avx2 => unsafe { find_any_avx2(data, needles) },
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Sse42 => unsafe { find_any_sse42(data, needles) },
+            #[cfg(target_arch = "aarch64")]
+            SimdBackend::Neon => unsafe { find_any_neon(data, needles) },
+            #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+            SimdBackend::Simd128 => find_any_simd128(data, needles),
+            _ => find_any_scalar(data, needles),
+        }
+    }
+
+    pub(super) fn find_any_scalar(data: &[u8], needles: &[u8]) -> Option<usize> {
+        data.iter().position(|b| needles.contains(b))
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx2")]
+    unsafe fn find_any_avx2(data: &[u8], needles: &[u8]) -> Option<usize> {
+        use std::arch::x86_64::*;
+
+        let mut offset = 0;
+        while offset + 32 <= data.len() {
+            let chunk = _mm256_loadu_si256(data.as_ptr().add(offset).cast());
+            let mut hits = _mm256_setzero_si256();
+            for &needle in needles {
+                let matches = _mm256_cmpeq_epi8(chunk, _mm256_set1_epi8(needle as i8));
+                hits = _mm256_or_si256(hits, matches);
+            }
+            let mask = _mm256_movemask_epi8(hits);
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += 32;
+        }
+        find_any_scalar(&data[offset..], needles).map(|i| offset + i)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "sse4.2")]
+    unsafe fn find_any_sse42(data: &[u8], needles: &[u8]) -> Option<usize> {
+        use std::arch::x86_64::*;
+
+        let mut offset = 0;
+        while offset + 16 <= data.len() {
+            let chunk = _mm_loadu_si128(data.as_ptr().add(offset).cast());
+            let mut hits = _mm_setzero_si128();
+            for &needle in needles {
+                hits = _mm_or_si128(hits, _mm_cmpeq_epi8(chunk, _mm_set1_epi8(needle as i8)));
+            }
+            let mask = _mm_movemask_epi8(hits);
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += 16;
+        }
+        find_any_scalar(&data[offset..], needles).map(|i| offset + i)
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[target_feature(enable = "neon")]
+    unsafe fn find_any_neon(data: &[u8], needles: &[u8]) -> Option<usize> {
+        use std::arch::aarch64::*;
+
+        let mut offset = 0;
+        while offset + 16 <= data.len() {
+            let chunk = vld1q_u8(data.as_ptr().add(offset));
+            let mut hits = vdupq_n_u8(0);
+            for &needle in needles {
+                hits = vorrq_u8(hits, vceqq_u8(chunk, vdupq_n_u8(needle)));
+            }
+            // NEON has no movemask; locate the hit within the chunk instead
+            if vmaxvq_u8(hits) != 0 {
+                return find_any_scalar(&data[offset..offset + 16], needles).map(|i| offset + i);
+            }
+            offset += 16;
+        }
+        find_any_scalar(&data[offset..], needles).map(|i| offset + i)
+    }
+
+    /// Needs no CPU check: the instructions are only compiled in when the
+    /// module targets SIMD128.
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    fn find_any_simd128(data: &[u8], needles: &[u8]) -> Option<usize> {
+        use std::arch::wasm32::*;
+
+        let mut offset = 0;
+        while offset + 16 <= data.len() {
+            // SAFETY: the 16 bytes at `offset` are in bounds, and `v128_load`
+            // accepts unaligned addresses.
+            let chunk = unsafe { v128_load(data.as_ptr().add(offset).cast()) };
+            let mut hits = u8x16_splat(0);
+            for &needle in needles {
+                hits = v128_or(hits, u8x16_eq(chunk, u8x16_splat(needle)));
+            }
+            let mask = u8x16_bitmask(hits);
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += 16;
+        }
+        find_any_scalar(&data[offset..], needles).map(|i| offset + i)
+    }
+}
+
+/// Clock for parse timings.
+///
+/// `Instant` panics on `wasm32-unknown-unknown`, so WASM builds read the
+/// JavaScript clock at millisecond resolution instead.
+#[derive(Debug, Clone, Copy)]
+struct ParseTimer {
+    #[cfg(not(target_arch = "wasm32"))]
+    start: Instant,
+    #[cfg(target_arch = "wasm32")]
+    start_ms: u64,
+}
+
+impl ParseTimer {
+    fn start() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            start: Instant::now(),
+            #[cfg(target_arch = "wasm32")]
+            start_ms: crate::shared::runtime::timestamp_millis(),
+        }
+    }
+
+    fn elapsed(self) -> Duration {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.start.elapsed()
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Duration::from_millis(
+                crate::shared::runtime::timestamp_millis().saturating_sub(self.start_ms),
+            )
+        }
+    }
 }
 
 /// SIMD parsing performance metrics.
@@ -86,6 +330,8 @@ pub struct ParsingMetrics {
     pub simd_operations_used: u64,
     /// Number of fallback operations to scalar code
     pub fallback_operations: u64,
+    /// Backend the parser dispatches to
+    pub backend: SimdBackend,
 }
 
 impl ParsingMetrics {
@@ -109,6 +355,7 @@ impl ParsingMetrics {
 #[derive(Debug)]
 pub struct SimdJsonParser {
     features: CpuFeatures,
+    backend: SimdBackend,
     metrics: Arc<AtomicMetrics>,
 }
 
@@ -125,107 +372,139 @@ struct AtomicMetrics {
 impl SimdJsonParser {
     /// Create a new SIMD JSON parser with automatic feature detection.
     pub fn new() -> Self {
+        let features = CpuFeatures::detect();
         Self {
-            features: CpuFeatures::detect(),
+            features,
+            backend: features.best_backend(),
             metrics: Arc::new(AtomicMetrics::default()),
         }
     }
 
+    /// Use a specific backend instead of the fastest available one.
+    ///
+    /// A backend the CPU does not support degrades to [`SimdBackend::Scalar`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::shared::simd_parsing::{SimdBackend, SimdJsonParser};
+    ///
+    /// let parser = SimdJsonParser::new().with_backend(SimdBackend::Scalar);
+    /// parser
+    ///     .parse_request(br#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#)
+    ///     .unwrap();
+    ///
+    /// let metrics = parser.get_metrics();
+    /// assert_eq!(metrics.backend, SimdBackend::Scalar);
+    /// assert_eq!(metrics.fallback_operations, 1);
+    /// ```
+    pub fn with_backend(mut self, backend: SimdBackend) -> Self {
+        self.backend = if self.features.supports(backend) {
+            backend
+        } else {
+            SimdBackend::Scalar
+        };
+        self
+    }
+
+    /// Backend this parser dispatches to.
+    pub fn backend(&self) -> SimdBackend {
+        self.backend
+    }
+
     /// Parse a JSON-RPC request from bytes.
     pub fn parse_request(&self, input: &[u8]) -> Result<JSONRPCRequest> {
-        let start = Instant::now();
-
-        // Quick validation using SIMD if available
-        if self.features.avx2 || self.features.sse42 {
-            if !self.validate_json_structure(input) {
-                self.metrics.fallback_ops.fetch_add(1, Ordering::Relaxed);
-                return Err(Error::parse(
-                    "Invalid JSON structure detected by SIMD validation",
-                ));
-            }
-            self.metrics.simd_ops.fetch_add(1, Ordering::Relaxed);
+        let timer = ParseTimer::start();
+
+        if !self.validate_json_structure(input) {
+            return Err(Error::parse(
+                "Invalid JSON structure detected by SIMD validation",
+            ));
         }
 
         // Parse with serde_json (still fastest for complete JSON parsing)
         let result: JSONRPCRequest = serde_json::from_slice(input)
             .map_err(|e| Error::parse(format!("JSON parsing failed: {}", e)))?;
 
-        self.update_metrics(input.len(), start.elapsed());
+        self.update_metrics(input.len(), timer.elapsed());
         Ok(result)
     }
 
     /// Parse a JSON-RPC response from bytes.
     pub fn parse_response(&self, input: &[u8]) -> Result<JSONRPCResponse> {
-        let start = Instant::now();
-
-        if self.features.avx2 || self.features.sse42 {
-            if !self.validate_json_structure(input) {
-                self.metrics.fallback_ops.fetch_add(1, Ordering::Relaxed);
-                return Err(Error::parse(
-                    "Invalid JSON structure detected by SIMD validation",
-                ));
-            }
-            self.metrics.simd_ops.fetch_add(1, Ordering::Relaxed);
+        let timer = ParseTimer::start();
+
+        if !self.validate_json_structure(input) {
+            return Err(Error::parse(
+                "Invalid JSON structure detected by SIMD validation",
+            ));
         }
 
         let result: JSONRPCResponse = serde_json::from_slice(input)
             .map_err(|e| Error::parse(format!("JSON response parsing failed: {}", e)))?;
 
-        self.update_metrics(input.len(), start.elapsed());
+        self.update_metrics(input.len(), timer.elapsed());
         Ok(result)
     }
 
     /// Parse multiple JSON-RPC requests in parallel.
     pub fn parse_batch_requests(&self, input: &[u8]) -> Result<Vec<JSONRPCRequest>> {
-        let start = Instant::now();
+        let timer = ParseTimer::start();
 
         let results: Vec<JSONRPCRequest> = serde_json::from_slice(input)
             .map_err(|e| Error::parse(format!("Batch JSON parsing failed: {}", e)))?;
 
-        self.update_metrics(input.len(), start.elapsed());
+        self.update_metrics(input.len(), timer.elapsed());
         Ok(results)
     }
 
     /// Parse multiple JSON-RPC responses in parallel.
     pub fn parse_batch_responses(&self, input: &[u8]) -> Result<Vec<JSONRPCResponse>> {
-        let start = Instant::now();
+        let timer = ParseTimer::start();
 
         let results: Vec<JSONRPCResponse> = serde_json::from_slice(input)
             .map_err(|e| Error::parse(format!("Batch response parsing failed: {}", e)))?;
 
-        self.update_metrics(input.len(), start.elapsed());
+        self.update_metrics(input.len(), timer.elapsed());
         Ok(results)
     }
 
     /// Validate JSON structure using SIMD operations.
-    #[allow(clippy::unused_self)]
     fn validate_json_structure(&self, input: &[u8]) -> bool {
+        let counter = if self.backend.is_vectorized() {
+            &self.metrics.simd_ops
+        } else {
+            &self.metrics.fallback_ops
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
         if input.is_empty() {
             return false;
         }
 
-        // Fast validation: check for balanced braces and basic JSON patterns
+        // Fast validation: check for balanced braces and basic JSON patterns,
+        // jumping straight to the next byte that can change the state
         let mut brace_count = 0i32;
         let mut in_string = false;
-        let mut escaped = false;
-
-        for &byte in input {
-            if escaped {
-                escaped = false;
-                continue;
-            }
+        let mut pos = 0;
 
-            match byte {
+        while let Some(found) = input.get(pos..).and_then(|rest| {
+            let needles: &[u8] = if in_string { b"\"\\" } else { b"\"{}" };
+            self.backend.find_any(rest, needles)
+        }) {
+            let index = pos + found;
+            pos = index + 1;
+            match input[index] {
                 b'"' => in_string = !in_string,
-                b'\\' if in_string => escaped = true,
-                b'{' if !in_string => brace_count += 1,
-                b'}' if !in_string => {
+                // Skip the escaped byte
+                b'\\' => pos += 1,
+                b'{' => brace_count += 1,
+                _ => {
                     brace_count -= 1;
                     if brace_count < 0 {
                         return false;
                     }
                 },
-                _ => {},
             }
         }
 
@@ -269,6 +548,7 @@ impl SimdJsonParser {
             documents_per_second,
             simd_operations_used: simd_ops,
             fallback_operations: fallback_ops,
+            backend: self.backend,
         }
     }
 
@@ -288,18 +568,26 @@ impl Default for SimdJsonParser {
 #[derive(Debug)]
 pub struct SimdSseParser {
     features: CpuFeatures,
+    backend: SimdBackend,
     buffer: Vec<u8>,
 }
 
 impl SimdSseParser {
     /// Create a new SIMD SSE parser.
     pub fn new() -> Self {
+        let features = CpuFeatures::detect();
         Self {
-            features: CpuFeatures::detect(),
+            features,
+            backend: features.best_backend(),
             buffer: Vec::with_capacity(4096),
         }
     }
 
+    /// Backend this parser dispatches to.
+    pub fn backend(&self) -> SimdBackend {
+        self.backend
+    }
+
     /// Parse SSE events from a chunk of data.
     pub fn parse_chunk(&mut self, data: &[u8]) -> Result<Vec<SseEvent>> {
         self.buffer.extend_from_slice(data);
@@ -323,43 +611,19 @@ impl SimdSseParser {
         Ok(events)
     }
 
-    /// Find the boundary of the next SSE event (double newline).
+    /// Find the boundary of the next SSE event (`\n\n` or `\r\n\r\n`).
     fn find_event_boundary(&self, data: &[u8]) -> Option<usize> {
-        // Use SIMD features for optimized boundary detection when available
-        if self.features.sse42 {
-            // SSE4.2 optimized search for "\n\n" pattern
-            self.simd_find_double_newline(data)
-        } else {
-            self.scalar_find_double_newline(data)
-        }
-    }
-
-    #[allow(clippy::unused_self)]
-    fn simd_find_double_newline(&self, data: &[u8]) -> Option<usize> {
-        // Look for \n\n or \r\n\r\n
-        for i in 0..data.len().saturating_sub(1) {
-            if data[i] == b'\n' && data[i + 1] == b'\n' {
-                return Some(i + 2);
+        let mut pos = 0;
+        while let Some(found) = self.backend.find_any(&data[pos..], b"\n") {
+            let newline = pos + found;
+            let rest = &data[newline + 1..];
+            if rest.starts_with(b"\n") {
+                return Some(newline + 2);
             }
-            if i + 3 < data.len()
-                && data[i] == b'\r'
-                && data[i + 1] == b'\n'
-                && data[i + 2] == b'\r'
-                && data[i + 3] == b'\n'
-            {
-                return Some(i + 4);
-            }
-        }
-        None
-    }
-
-    #[allow(clippy::unused_self)]
-    fn scalar_find_double_newline(&self, data: &[u8]) -> Option<usize> {
-        // Standard scalar implementation as fallback
-        for i in 0..data.len().saturating_sub(1) {
-            if data[i] == b'\n' && data[i + 1] == b'\n' {
-                return Some(i + 2);
+            if rest.starts_with(b"\r\n") {
+                return Some(newline + 3);
             }
+            pos = newline + 1;
         }
         None
     }
@@ -519,6 +783,29 @@ mod tests {
         println!("Detected features: {:?}", features);
     }
 
+    #[test]
+    fn test_simd128_backend_selection() {
+        let wasm = CpuFeatures {
+            avx2: false,
+            sse42: false,
+            ssse3: false,
+            neon: false,
+            simd128: true,
+        };
+        assert_eq!(wasm.best_backend(), SimdBackend::Simd128);
+        assert_eq!(
+            CpuFeatures::detect().simd128,
+            cfg!(all(target_arch = "wasm32", target_feature = "simd128"))
+        );
+
+        // Without SIMD128 compiled in, the backend degrades to the scalar scan
+        let data = br#"{"text":"a long enough string to span a vector"}"#;
+        assert_eq!(
+            SimdBackend::Simd128.find_any(data, b"}"),
+            Some(data.len() - 1)
+        );
+    }
+
     #[test]
     fn test_simd_json_parser_basic() {
         let parser = SimdJsonParser::new();
@@ -585,6 +872,43 @@ mod tests {
         assert_eq!(parsed.get("content-length"), Some(&"123".to_string()));
     }
 
+    #[test]
+    fn test_backends_agree_with_scalar() {
+        let features = CpuFeatures::detect();
+        assert!(features.supports(features.best_backend()));
+
+        let mut input = br#"{"a":"x\"}{","b":[{"c":"\\"}]}"#.repeat(5);
+        input.extend_from_slice(b"tail\n");
+        for backend in [
+            SimdBackend::Avx2,
+            SimdBackend::Sse42,
+            SimdBackend::Neon,
+            SimdBackend::Scalar,
+        ] {
+            for start in 0..input.len() {
+                let data = &input[start..];
+                assert_eq!(
+                    backend.find_any(data, b"\"{}"),
+                    kernels::find_any_scalar(data, b"\"{}"),
+                    "{backend:?} at {start}"
+                );
+            }
+
+            let parser = SimdJsonParser::new().with_backend(backend);
+            assert!(features.supports(parser.backend()));
+            assert!(parser.validate_json_structure(&input[..input.len() - 5]));
+            assert!(!parser.validate_json_structure(br#"{"a":"}"#));
+        }
+    }
+
+    #[test]
+    fn test_sse_event_boundaries() {
+        let parser = SimdSseParser::new();
+        assert_eq!(parser.find_event_boundary(b"data: a\n\nrest"), Some(9));
+        assert_eq!(parser.find_event_boundary(b"data: a\r\n\r\nrest"), Some(11));
+        assert_eq!(parser.find_event_boundary(b"data: a\ndata: b\n"), None);
+    }
+
     #[test]
     fn test_json_structure_validation() {
         let parser = SimdJsonParser::new();
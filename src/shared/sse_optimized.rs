@@ -250,7 +250,7 @@ impl OptimizedSseTransport {
         )
         .await
         .map_err(|_| Error::internal("SSE connection timeout"))?
-        .map_err(|e| Error::internal(format!("SSE connection failed: {}", e)))?;
+        .map_err(Error::from)?;
 
         if !response.status().is_success() {
             return Err(Error::internal(format!(
@@ -386,7 +386,7 @@ impl OptimizedSseTransport {
             .body(json)
             .send()
             .await
-            .map_err(|e| Error::internal(format!("Failed to send event: {}", e)))?;
+            .map_err(Error::from)?;
 
         if !response.status().is_success() {
             return Err(Error::internal(format!(
@@ -404,7 +404,7 @@ impl OptimizedSseTransport {
             .get(format!("{}/ping", config.url))
             .send()
             .await
-            .map_err(|e| Error::internal(format!("Keepalive failed: {}", e)))?;
+            .map_err(Error::from)?;
 
         if !response.status().is_success() {
             return Err(Error::internal("Keepalive ping failed"));
@@ -3,20 +3,36 @@
 //! This transport uses stdin/stdout for communication, with length-prefixed
 //! framing to ensure message boundaries are preserved.
 
-use crate::error::{Result, TransportError};
+use crate::error::{Error, Result, TransportError};
+use crate::shared::frame_parser;
+use crate::shared::protocol::MessageSizeLimits;
+use crate::shared::timing::{self, Phase};
 use crate::shared::transport::{Transport, TransportMessage};
+use crate::types::{JSONRPCError, JSONRPCResponse};
 use async_trait::async_trait;
+use std::collections::VecDeque;
 #[cfg(not(target_arch = "wasm32"))]
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::sync::Mutex;
 
+#[cfg(feature = "simd")]
+use crate::simd::framing::find_newline;
+
 /// Line-delimited JSON framing header.
 const CONTENT_LENGTH_HEADER: &str = "Content-Length: ";
 
+/// Scalar counterpart of [`crate::simd::framing::find_newline`].
+#[cfg(not(feature = "simd"))]
+fn find_newline(input: &[u8]) -> Option<usize> {
+    input.iter().position(|&b| b == b'\n')
+}
+
 /// stdio transport for MCP communication.
 ///
 /// Uses length-prefixed framing compatible with the TypeScript SDK.
+/// Receiving is cancel-safe: a partially read frame is kept in the transport
+/// and completed by the next call.
 ///
 /// # Examples
 ///
@@ -34,6 +50,26 @@ pub struct StdioTransport {
     stdin: Mutex<BufReader<tokio::io::Stdin>>,
     stdout: Mutex<tokio::io::Stdout>,
     closed: std::sync::atomic::AtomicBool,
+    /// Messages from a received batch that have not been returned yet
+    pending: VecDeque<TransportMessage>,
+    /// Limits on the size of framed messages
+    limits: MessageSizeLimits,
+    /// Progress through the frame being read
+    frame: FrameProgress,
+}
+
+/// Progress through a frame, kept across receives so that cancelling one
+/// loses no input.
+#[derive(Debug, Default)]
+pub(crate) struct FrameProgress {
+    /// Header line read so far
+    line: Vec<u8>,
+    /// Length announced by the headers read so far
+    content_length: Option<usize>,
+    /// Length of the body once all headers are read
+    body_length: Option<usize>,
+    /// Body read so far
+    body: Vec<u8>,
 }
 
 impl StdioTransport {
@@ -52,9 +88,35 @@ impl StdioTransport {
             stdin: Mutex::new(BufReader::new(tokio::io::stdin())),
             stdout: Mutex::new(tokio::io::stdout()),
             closed: std::sync::atomic::AtomicBool::new(false),
+            pending: VecDeque::new(),
+            limits: MessageSizeLimits::default(),
+            frame: FrameProgress::default(),
         }
     }
 
+    /// Enforce limits on the size of sent and received messages.
+    ///
+    /// Oversized requests from the peer are answered with an
+    /// `INVALID_REQUEST` error, and the transport closes when a frame is too
+    /// large to be worth reading at all. Oversized outgoing responses are
+    /// replaced by an `INTERNAL_ERROR` response for the same request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::shared::{MessageSizeLimits, StdioTransport};
+    ///
+    /// let transport = StdioTransport::new().with_message_size_limits(
+    ///     MessageSizeLimits::default()
+    ///         .with_inbound(4 * 1024 * 1024)
+    ///         .with_outbound(16 * 1024 * 1024),
+    /// );
+    /// ```
+    pub fn with_message_size_limits(mut self, limits: MessageSizeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Parse a content-length header.
     ///
     /// Parses lines like "Content-Length: 42" to extract the length.
@@ -64,6 +126,98 @@ impl StdioTransport {
     }
 }
 
+impl FrameProgress {
+    /// Read headers and extract content length.
+    ///
+    /// Returns `None` at EOF.
+    pub(crate) async fn read_headers<R: AsyncBufRead + Unpin>(
+        &mut self,
+        reader: &mut R,
+    ) -> Result<Option<usize>> {
+        if let Some(length) = self.body_length {
+            return Ok(Some(length));
+        }
+
+        // Read headers until the blank line ending them
+        loop {
+            let bytes_read = StdioTransport::read_header_line(reader, &mut self.line)
+                .await
+                .map_err(TransportError::from)?;
+
+            if bytes_read == 0 {
+                return Ok(None);
+            }
+
+            let line = std::mem::take(&mut self.line);
+            let line = std::str::from_utf8(&line)
+                .map_err(|_| TransportError::InvalidMessage("Invalid header encoding".to_string()))?
+                .trim();
+
+            if line.is_empty() {
+                // End of headers
+                break;
+            }
+
+            if let Some(length) = StdioTransport::parse_content_length(line) {
+                self.content_length = Some(length);
+            }
+        }
+
+        let length = self.content_length.take().ok_or_else(|| {
+            Error::from(TransportError::InvalidMessage(
+                "Missing Content-Length header".to_string(),
+            ))
+        })?;
+        self.body_length = Some(length);
+        Ok(Some(length))
+    }
+
+    /// Read message body with specified content length.
+    ///
+    /// Returns `None` at EOF.
+    pub(crate) async fn read_body<R: AsyncBufRead + Unpin>(
+        &mut self,
+        reader: &mut R,
+        content_length: usize,
+    ) -> Result<Option<Vec<u8>>> {
+        self.body.reserve(content_length - self.body.len());
+        while self.body.len() < content_length {
+            let available = reader.fill_buf().await.map_err(TransportError::from)?;
+            if available.is_empty() {
+                return Ok(None);
+            }
+            let used = available.len().min(content_length - self.body.len());
+            self.body.extend_from_slice(&available[..used]);
+            reader.consume(used);
+        }
+        self.body_length = None;
+        Ok(Some(std::mem::take(&mut self.body)))
+    }
+}
+
+/// Write one framed message to `writer` and flush it.
+pub(crate) async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    json_bytes: &[u8],
+) -> Result<()> {
+    // Write content-length header
+    let header = format!("{}{}\r\n\r\n", CONTENT_LENGTH_HEADER, json_bytes.len());
+    writer
+        .write_all(header.as_bytes())
+        .await
+        .map_err(TransportError::from)?;
+
+    // Write message payload
+    writer
+        .write_all(json_bytes)
+        .await
+        .map_err(TransportError::from)?;
+
+    // Always flush stdio
+    writer.flush().await.map_err(TransportError::from)?;
+    Ok(())
+}
+
 impl Default for StdioTransport {
     fn default() -> Self {
         Self::new()
@@ -77,7 +231,17 @@ impl Transport for StdioTransport {
             return Err(TransportError::ConnectionClosed.into());
         }
 
-        let json_bytes = Self::serialize_message(&message)?;
+        let json_bytes = Self::serialize_within_limits(message, &self.limits)?;
+        self.write_message(&json_bytes).await
+    }
+
+    async fn send_batch(&mut self, messages: Vec<TransportMessage>) -> Result<()> {
+        if self.closed.load(std::sync::atomic::Ordering::Acquire) {
+            return Err(TransportError::ConnectionClosed.into());
+        }
+
+        let json_bytes = Self::serialize_batch(&messages)?;
+        self.limits.check_outbound(json_bytes.len())?;
         self.write_message(&json_bytes).await
     }
 
@@ -86,9 +250,41 @@ impl Transport for StdioTransport {
             return Err(TransportError::ConnectionClosed.into());
         }
 
-        let content_length = self.read_headers().await?;
-        let buffer = self.read_message_body(content_length).await?;
-        Self::parse_message(&buffer)
+        loop {
+            if let Some(message) = self.pending.pop_front() {
+                return Ok(message);
+            }
+
+            let content_length = self.read_headers().await?;
+            if let Err(e) = self.limits.check_inbound(content_length) {
+                if matches!(e, Error::Transport(_)) {
+                    self.closed
+                        .store(true, std::sync::atomic::Ordering::Release);
+                    return Err(e);
+                }
+                let buffer = self.read_message_body(content_length).await?;
+                self.reject_frame(&buffer, e).await?;
+                continue;
+            }
+            let buffer = self.read_message_body(content_length).await?;
+            if let Err(e) = self.limits.check_inbound_structure(&buffer) {
+                self.reject_frame(&buffer, e).await?;
+                continue;
+            }
+            self.pending.extend(timing::time_sync(Phase::Parse, || {
+                Self::parse_batch(&buffer)
+            })?);
+        }
+    }
+
+    async fn receive_batch(&mut self) -> Result<Vec<TransportMessage>> {
+        // Receiving parses a whole frame and leaves the rest of a batch
+        // pending.
+        if self.pending.is_empty() {
+            let first = self.receive().await?;
+            self.pending.push_front(first);
+        }
+        Ok(self.pending.drain(..).collect())
     }
 
     async fn close(&mut self) -> Result<()> {
@@ -140,129 +336,133 @@ impl StdioTransport {
         }
     }
 
-    /// Write framed message to stdout.
-    async fn write_message(&self, json_bytes: &[u8]) -> Result<()> {
-        let mut stdout = self.stdout.lock().await;
-
-        // Write content-length header
-        let header = format!("{}{}\r\n\r\n", CONTENT_LENGTH_HEADER, json_bytes.len());
-        stdout
-            .write_all(header.as_bytes())
-            .await
-            .map_err(TransportError::from)?;
+    /// Serialize several transport messages as a JSON-RPC batch array.
+    pub fn serialize_batch(messages: &[TransportMessage]) -> Result<Vec<u8>> {
+        let mut batch = Vec::with_capacity(messages.len());
+        for message in messages {
+            let bytes = Self::serialize_message(message)?;
+            let value: serde_json::Value = serde_json::from_slice(&bytes).map_err(|e| {
+                TransportError::InvalidMessage(format!("Failed to serialize batch: {}", e))
+            })?;
+            batch.push(value);
+        }
+        serde_json::to_vec(&batch).map_err(|e| {
+            TransportError::InvalidMessage(format!("Failed to serialize batch: {}", e)).into()
+        })
+    }
 
-        // Write message payload
-        stdout
-            .write_all(json_bytes)
-            .await
-            .map_err(TransportError::from)?;
+    /// Serialize a message, checking it against the outbound limit.
+    ///
+    /// An oversized response is replaced by an `INTERNAL_ERROR` response for
+    /// the same request; other oversized messages are an error.
+    pub(crate) fn serialize_within_limits(
+        message: TransportMessage,
+        limits: &MessageSizeLimits,
+    ) -> Result<Vec<u8>> {
+        let json_bytes = Self::serialize_message(&message)?;
+        let Err(e) = limits.check_outbound(json_bytes.len()) else {
+            return Ok(json_bytes);
+        };
+        let TransportMessage::Response(response) = message else {
+            return Err(e);
+        };
+        tracing::warn!("Replacing oversized response to {}: {}", response.id, e);
+        let error = JSONRPCResponse::error(response.id, JSONRPCError::from(e));
+        Self::serialize_message(&TransportMessage::Response(error))
+    }
 
-        // Always flush stdio
-        stdout.flush().await.map_err(TransportError::from)?;
-        drop(stdout);
+    /// Error responses answering every request in a rejected frame.
+    ///
+    /// Notifications and responses in the frame are dropped.
+    pub(crate) fn rejections(buffer: &[u8], error: Error) -> Vec<JSONRPCResponse> {
+        let error = JSONRPCError::from(error);
+        tracing::warn!("Rejecting message: {}", error.message);
+
+        frame_parser::request_ids(buffer)
+            .into_iter()
+            .map(|id| JSONRPCResponse::error(id, error.clone()))
+            .collect()
+    }
 
+    /// Answer every request in a rejected frame with `error`.
+    async fn reject_frame(&self, buffer: &[u8], error: Error) -> Result<()> {
+        for response in Self::rejections(buffer, error) {
+            let json_bytes = Self::serialize_message(&TransportMessage::Response(response))?;
+            self.write_message(&json_bytes).await?;
+        }
         Ok(())
     }
 
+    /// Write framed message to stdout.
+    async fn write_message(&self, json_bytes: &[u8]) -> Result<()> {
+        let mut stdout = self.stdout.lock().await;
+        write_frame(&mut *stdout, json_bytes).await
+    }
+
     /// Read headers and extract content length.
-    async fn read_headers(&self) -> Result<usize> {
+    async fn read_headers(&mut self) -> Result<usize> {
         let mut stdin = self.stdin.lock().await;
-        let mut line = String::new();
-        let mut content_length = None;
-
-        // Read headers until we find content-length
-        loop {
-            line.clear();
-            let bytes_read = stdin
-                .read_line(&mut line)
-                .await
-                .map_err(TransportError::from)?;
-
-            if bytes_read == 0 {
-                // EOF reached
-                drop(stdin);
-                self.closed
-                    .store(true, std::sync::atomic::Ordering::Release);
-                return Err(TransportError::ConnectionClosed.into());
-            }
+        let length = self.frame.read_headers(&mut *stdin).await?;
+        drop(stdin);
 
-            let line = line.trim();
+        length.ok_or_else(|| {
+            // EOF reached
+            self.closed
+                .store(true, std::sync::atomic::Ordering::Release);
+            TransportError::ConnectionClosed.into()
+        })
+    }
 
-            if line.is_empty() {
-                // End of headers
-                break;
+    /// Read one header line, including its trailing `\n`, into `line`.
+    ///
+    /// Scans the reader's buffer for the delimiter directly instead of
+    /// decoding into a `String`. Returns the number of bytes read, which is
+    /// zero at EOF.
+    async fn read_header_line<R: AsyncBufRead + Unpin>(
+        reader: &mut R,
+        line: &mut Vec<u8>,
+    ) -> std::io::Result<usize> {
+        let mut bytes_read = 0;
+        loop {
+            let available = reader.fill_buf().await?;
+            if available.is_empty() {
+                return Ok(bytes_read);
             }
-
-            if let Some(length) = Self::parse_content_length(line) {
-                content_length = Some(length);
+            let (used, done) = match find_newline(available) {
+                Some(newline) => (newline + 1, true),
+                None => (available.len(), false),
+            };
+            line.extend_from_slice(&available[..used]);
+            reader.consume(used);
+            bytes_read += used;
+            if done {
+                return Ok(bytes_read);
             }
         }
-        drop(stdin);
-
-        content_length.ok_or_else(|| {
-            TransportError::InvalidMessage("Missing Content-Length header".to_string()).into()
-        })
     }
 
     /// Read message body with specified content length.
-    async fn read_message_body(&self, content_length: usize) -> Result<Vec<u8>> {
+    async fn read_message_body(&mut self, content_length: usize) -> Result<Vec<u8>> {
         let mut stdin = self.stdin.lock().await;
-        let mut buffer = vec![0u8; content_length];
-        stdin
-            .read_exact(&mut buffer)
-            .await
-            .map_err(TransportError::from)?;
+        let body = self.frame.read_body(&mut *stdin, content_length).await?;
         drop(stdin);
-        Ok(buffer)
+        body.ok_or_else(|| TransportError::ConnectionClosed.into())
     }
 
     /// Parse JSON message and determine its type.
+    ///
+    /// The message is decoded straight from the buffer by
+    /// [`parse_frame`](crate::shared::frame_parser::parse_frame), without
+    /// building an intermediate `serde_json::Value`.
     pub fn parse_message(buffer: &[u8]) -> Result<TransportMessage> {
-        let json_value: serde_json::Value = serde_json::from_slice(buffer)
-            .map_err(|e| TransportError::InvalidMessage(format!("Invalid JSON: {}", e)))?;
-
-        if json_value.get("method").is_some() {
-            Self::parse_method_message(json_value)
-        } else if json_value.get("result").is_some() || json_value.get("error").is_some() {
-            Self::parse_response_message(json_value)
-        } else {
-            Err(TransportError::InvalidMessage("Unknown message type".to_string()).into())
-        }
-    }
-
-    /// Parse message with method field (request or notification).
-    fn parse_method_message(json_value: serde_json::Value) -> Result<TransportMessage> {
-        if json_value.get("id").is_some() {
-            // It's a request
-            let request: crate::types::JSONRPCRequest<serde_json::Value> =
-                serde_json::from_value(json_value).map_err(|e| {
-                    TransportError::InvalidMessage(format!("Invalid request: {}", e))
-                })?;
-
-            let parsed_request = crate::shared::parse_request(request)
-                .map_err(|e| TransportError::InvalidMessage(format!("Invalid request: {}", e)))?;
-
-            Ok(TransportMessage::Request {
-                id: parsed_request.0,
-                request: parsed_request.1,
-            })
-        } else {
-            // It's a notification
-            let parsed_notification =
-                crate::shared::parse_notification(json_value).map_err(|e| {
-                    TransportError::InvalidMessage(format!("Invalid notification: {}", e))
-                })?;
-
-            Ok(TransportMessage::Notification(parsed_notification))
-        }
+        frame_parser::parse_frame(buffer)
     }
 
-    /// Parse response message.
-    fn parse_response_message(json_value: serde_json::Value) -> Result<TransportMessage> {
-        let response: crate::types::JSONRPCResponse = serde_json::from_value(json_value)
-            .map_err(|e| TransportError::InvalidMessage(format!("Invalid response: {}", e)))?;
-
-        Ok(TransportMessage::Response(response))
+    /// Parse a JSON message that may be a JSON-RPC batch array.
+    ///
+    /// A single message is returned as a one-element vector.
+    pub fn parse_batch(buffer: &[u8]) -> Result<Vec<TransportMessage>> {
+        frame_parser::parse_frame_batch(buffer)
     }
 }
 
@@ -309,6 +509,73 @@ mod tests {
         assert_eq!(StdioTransport::parse_content_length("Content-Length"), None);
     }
 
+    #[test]
+    fn batch_round_trip() {
+        let messages = vec![
+            TransportMessage::Request {
+                id: crate::types::RequestId::from(1i64),
+                request: crate::types::Request::Client(Box::new(crate::types::ClientRequest::Ping)),
+            },
+            TransportMessage::Notification(crate::types::Notification::Client(
+                crate::types::ClientNotification::Initialized,
+            )),
+        ];
+        let bytes = StdioTransport::serialize_batch(&messages).unwrap();
+        assert!(bytes.starts_with(b"["));
+
+        let parsed = StdioTransport::parse_batch(&bytes).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert!(matches!(parsed[0], TransportMessage::Request { .. }));
+        assert!(matches!(parsed[1], TransportMessage::Notification(_)));
+
+        let single =
+            StdioTransport::parse_batch(br#"{"jsonrpc":"2.0","id":1,"result":{}}"#).unwrap();
+        assert_eq!(single.len(), 1);
+        assert!(StdioTransport::parse_batch(b"[]").is_err());
+    }
+
+    #[tokio::test]
+    async fn read_header_lines() {
+        let input = "Content-Length: 17\r\n\r\n{\"partial\"";
+        let mut reader = tokio::io::BufReader::with_capacity(4, input.as_bytes());
+        let mut line = Vec::new();
+
+        let read = StdioTransport::read_header_line(&mut reader, &mut line)
+            .await
+            .unwrap();
+        assert_eq!(read, 20);
+        assert_eq!(line, b"Content-Length: 17\r\n");
+
+        line.clear();
+        StdioTransport::read_header_line(&mut reader, &mut line)
+            .await
+            .unwrap();
+        assert_eq!(line, b"\r\n");
+
+        // A line cut off by EOF is returned as is, then EOF reads nothing
+        line.clear();
+        StdioTransport::read_header_line(&mut reader, &mut line)
+            .await
+            .unwrap();
+        assert_eq!(line, b"{\"partial\"");
+        line.clear();
+        let read = StdioTransport::read_header_line(&mut reader, &mut line)
+            .await
+            .unwrap();
+        assert_eq!(read, 0);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_utf8() {
+        let mut body = br#"{"jsonrpc":"2.0","method":"ping","id":1,"x":""#.to_vec();
+        body.extend_from_slice(&[0xFF, b'"', b'}']);
+        let err = StdioTransport::parse_message(&body).unwrap_err();
+        assert!(err.to_string().contains("not valid UTF-8"));
+
+        let text = r#"{"jsonrpc":"2.0","method":"ping","id":1,"_meta":{"note":"héllo"}}"#;
+        assert!(StdioTransport::parse_message(text.as_bytes()).is_ok());
+    }
+
     #[tokio::test]
     async fn transport_properties() {
         let transport = StdioTransport::new();
@@ -1,10 +1,13 @@
 use crate::error::{Error, Result, TransportError};
 use crate::shared::http_constants::{
     ACCEPT, ACCEPT_STREAMABLE, APPLICATION_JSON, CONTENT_TYPE, LAST_EVENT_ID, MCP_PROTOCOL_VERSION,
-    MCP_SESSION_ID, TEXT_EVENT_STREAM,
+    MCP_SESSION_ID, TEXT_EVENT_STREAM, WWW_AUTHENTICATE,
 };
+use crate::shared::reconnect::{ReconnectConfig, ReconnectManager};
 use crate::shared::sse_parser::SseParser;
 use crate::shared::{Transport, TransportMessage};
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::{ClientRequest, Request, RequestId};
 use async_trait::async_trait;
 use parking_lot::RwLock;
 use reqwest::{Client, RequestBuilder, Response};
@@ -130,12 +133,16 @@ pub struct StreamableHttpTransport {
     receiver: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<TransportMessage>>>,
     /// Sender for messages
     sender: mpsc::UnboundedSender<TransportMessage>,
-    /// Protocol version negotiated with server
+    /// Protocol version negotiated with server, sent as `MCP-Protocol-Version`
     protocol_version: Arc<RwLock<Option<String>>>,
+    /// ID of the `initialize` request awaiting its response
+    init_request_id: Arc<RwLock<Option<RequestId>>>,
     /// Abort controller for SSE streams
     abort_handle: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
     /// Last event ID for resumability
     last_event_id: Arc<RwLock<Option<String>>>,
+    /// Reconnects interrupted SSE streams when auto-resume is enabled
+    resume: Option<Arc<ReconnectManager>>,
 }
 
 impl Debug for StreamableHttpTransport {
@@ -158,11 +165,47 @@ impl StreamableHttpTransport {
             receiver: Arc::new(tokio::sync::Mutex::new(receiver)),
             sender,
             protocol_version: Arc::new(RwLock::new(None)),
+            init_request_id: Arc::new(RwLock::new(None)),
             abort_handle: Arc::new(RwLock::new(None)),
             last_event_id: Arc::new(RwLock::new(None)),
+            resume: None,
         }
     }
 
+    /// Automatically re-establish interrupted SSE streams.
+    ///
+    /// When the GET stream ends, or a POST response stream fails before
+    /// completing, the transport reopens the GET stream with the last
+    /// received event ID as `Last-Event-ID` so the server can replay missed
+    /// events. Attempts are spaced and limited by `config`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::shared::reconnect::ReconnectConfig;
+    /// use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
+    /// use std::time::Duration;
+    /// use url::Url;
+    ///
+    /// let transport = StreamableHttpTransport::new(StreamableHttpTransportConfig {
+    ///     url: Url::parse("http://localhost:8080").unwrap(),
+    ///     extra_headers: vec![],
+    ///     auth_provider: None,
+    ///     session_id: None,
+    ///     enable_json_response: false,
+    ///     on_resumption_token: None,
+    /// })
+    /// .with_auto_resume(ReconnectConfig {
+    ///     initial_delay: Duration::from_millis(500),
+    ///     max_retries: Some(10),
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn with_auto_resume(mut self, config: ReconnectConfig) -> Self {
+        self.resume = Some(Arc::new(ReconnectManager::new(config)));
+        self
+    }
+
     /// Get the current session ID
     pub fn session_id(&self) -> Option<String> {
         self.config.read().session_id.clone()
@@ -178,11 +221,46 @@ impl StreamableHttpTransport {
         self.protocol_version.read().clone()
     }
 
-    /// Set the protocol version (called after initialization)
+    /// Set the protocol version sent with every request.
+    ///
+    /// The transport picks up the version from the server's `initialize`
+    /// response on its own; set it to resume a session initialized
+    /// elsewhere.
     pub fn set_protocol_version(&self, version: Option<String>) {
         *self.protocol_version.write() = version;
     }
 
+    /// Note an outgoing `initialize` request, to learn the negotiated
+    /// version from its response.
+    fn observe_outgoing(&self, message: &TransportMessage) {
+        if let TransportMessage::Request {
+            id,
+            request: Request::Client(request),
+        } = message
+        {
+            if matches!(**request, ClientRequest::Initialize(_)) {
+                *self.init_request_id.write() = Some(id.clone());
+            }
+        }
+    }
+
+    /// Record the version from the response to the `initialize` request.
+    fn observe_incoming(&self, message: &TransportMessage) {
+        let TransportMessage::Response(response) = message else {
+            return;
+        };
+        let mut init_request_id = self.init_request_id.write();
+        if init_request_id.as_ref() != Some(&response.id) {
+            return;
+        }
+        *init_request_id = None;
+        if let ResponsePayload::Result(result) = &response.payload {
+            if let Some(version) = result.get("protocolVersion").and_then(|v| v.as_str()) {
+                *self.protocol_version.write() = Some(version.to_string());
+            }
+        }
+    }
+
     /// Get the last event ID (for resumability)
     pub fn last_event_id(&self) -> Option<String> {
         self.last_event_id.read().clone()
@@ -196,53 +274,116 @@ impl StreamableHttpTransport {
             handle.abort();
         }
 
-        let url = self.config.read().url.clone();
-        let mut builder = self.build_request(reqwest::Method::GET, url).await?;
+        let Some(response) = self.open_sse(resumption_token).await? else {
+            // Server doesn't support GET SSE, which is OK
+            return Ok(());
+        };
 
-        builder = builder.header(ACCEPT, TEXT_EVENT_STREAM);
+        let transport = self.clone();
+        let handle = tokio::spawn(async move {
+            transport.run_sse(response, true).await;
+        });
 
-        // Add Last-Event-ID for resumability
-        if let Some(token) = resumption_token {
-            builder = builder.header(LAST_EVENT_ID, token);
-        }
+        *self.abort_handle.write() = Some(handle);
+        Ok(())
+    }
 
-        let response = builder.send().await;
+    /// Open the GET SSE stream, returning `None` if the server answers 405.
+    async fn open_sse(&self, resumption_token: Option<String>) -> Result<Option<Response>> {
+        let response = self
+            .execute(reqwest::Method::GET, |builder| {
+                let builder = builder.header(ACCEPT, TEXT_EVENT_STREAM);
+                // Add Last-Event-ID for resumability
+                match &resumption_token {
+                    Some(token) => builder.header(LAST_EVENT_ID, token),
+                    None => builder,
+                }
+            })
+            .await?;
 
         // Handle 405 (SSE not supported) gracefully
-        if let Ok(resp) = &response {
-            if resp.status().as_u16() == 405 {
-                // Server doesn't support GET SSE, which is OK
-                return Ok(());
-            }
+        if response.status().as_u16() == 405 {
+            return Ok(None);
+        }
 
-            if !resp.status().is_success() {
-                return Err(Error::Transport(TransportError::Request(format!(
-                    "SSE request failed with status: {}",
-                    resp.status()
-                ))));
-            }
-        } else if let Err(e) = response {
-            return Err(Error::Transport(TransportError::Request(e.to_string())));
+        if !response.status().is_success() {
+            return Err(Error::Transport(TransportError::Request(format!(
+                "SSE request failed with status: {}",
+                response.status()
+            ))));
         }
 
-        let response = response.unwrap();
         self.process_response_headers(&response);
+        Ok(Some(response))
+    }
 
-        // Start streaming task
-        let sender = self.sender.clone();
-        let on_resumption = self.config.read().on_resumption_token.clone();
-        let last_event_id = self.last_event_id.clone();
+    /// Forward the events of an SSE response, resuming the stream if it is
+    /// interrupted and auto-resume is enabled.
+    ///
+    /// Streams that end cleanly are only resumed when `resume_on_end` is set,
+    /// i.e. for the long-lived GET stream.
+    async fn run_sse(&self, response: Response, resume_on_end: bool) {
+        let mut response = response;
+        loop {
+            let outcome = self.pump_sse(response).await;
+            let Some(manager) = &self.resume else {
+                return;
+            };
+            match outcome {
+                Ok(()) if !resume_on_end => return,
+                Ok(()) => tracing::debug!("SSE stream ended, resuming"),
+                Err(e) => tracing::debug!("SSE stream interrupted, resuming: {}", e),
+            }
 
-        let handle = tokio::spawn(async move {
-            let mut sse_parser = SseParser::new();
-            let body = response.text().await.unwrap_or_default();
+            manager.on_disconnected().await;
+            let reopened = parking_lot::Mutex::new(None);
+            let result = manager
+                .reconnect_with(|| {
+                    let reopened = &reopened;
+                    async move {
+                        let token = self.last_event_id();
+                        *reopened.lock() = self.open_sse(token).await?;
+                        Ok(())
+                    }
+                })
+                .await;
+
+            match (result, reopened.into_inner()) {
+                (Ok(()), Some(next)) => response = next,
+                (Ok(()), None) => return,
+                (Err(e), _) => {
+                    tracing::warn!("Giving up resuming SSE stream: {}", e);
+                    return;
+                },
+            }
+        }
+    }
 
-            // For now, parse the whole body - later we can stream with hyper Body
-            let events = sse_parser.feed(&body);
-            for event in events {
+    /// Read an SSE response body as it arrives and forward its messages.
+    async fn pump_sse(&self, mut response: Response) -> Result<()> {
+        let on_resumption = self.config.read().on_resumption_token.clone();
+        let mut sse_parser = SseParser::new();
+        // Bytes of a UTF-8 sequence split across chunks
+        let mut pending = Vec::new();
+
+        while let Some(chunk) = response.chunk().await.map_err(Error::from)? {
+            pending.extend_from_slice(&chunk);
+            let valid = match std::str::from_utf8(&pending) {
+                Ok(text) => text.len(),
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                Err(e) => {
+                    return Err(Error::Transport(TransportError::Deserialization(
+                        e.to_string(),
+                    )))
+                },
+            };
+            let text = String::from_utf8_lossy(&pending[..valid]).into_owned();
+            pending.drain(..valid);
+
+            for event in sse_parser.feed(&text) {
                 // Update last event ID and notify callback
                 if let Some(id) = &event.id {
-                    *last_event_id.write() = Some(id.clone());
+                    *self.last_event_id.write() = Some(id.clone());
                     if let Some(callback) = &on_resumption {
                         callback(id.clone());
                     }
@@ -254,13 +395,11 @@ impl StreamableHttpTransport {
                     if let Ok(msg) =
                         crate::shared::StdioTransport::parse_message(event.data.as_bytes())
                     {
-                        let _ = sender.send(msg);
+                        let _ = self.sender.send(msg);
                     }
                 }
             }
-        });
-
-        *self.abort_handle.write() = Some(handle);
+        }
         Ok(())
     }
 
@@ -301,6 +440,60 @@ impl StreamableHttpTransport {
         Ok(builder)
     }
 
+    /// Send a request to the endpoint, as [`execute_authorized`](Self::execute_authorized).
+    ///
+    /// A `404 Not Found` for a request in a session means the server ended
+    /// the session: the session is forgotten, so the next `initialize`
+    /// starts a new one, and [`TransportError::SessionExpired`] is returned.
+    async fn execute<F>(&self, method: reqwest::Method, prepare: F) -> Result<Response>
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let in_session = self.session_id().is_some();
+        let response = self.execute_authorized(method, prepare).await?;
+        if in_session && response.status() == reqwest::StatusCode::NOT_FOUND {
+            tracing::debug!("Server ended the session");
+            self.config.write().session_id = None;
+            *self.protocol_version.write() = None;
+            return Err(Error::Transport(TransportError::SessionExpired));
+        }
+        Ok(response)
+    }
+
+    /// Send a request to the endpoint, retrying it once with fresh
+    /// credentials if the server answers `401 Unauthorized` and the auth
+    /// provider was able to obtain new ones.
+    async fn execute_authorized<F>(&self, method: reqwest::Method, prepare: F) -> Result<Response>
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let url = self.config.read().url.clone();
+        let builder = self.build_request(method.clone(), url.clone()).await?;
+        let response = prepare(builder).send().await.map_err(Error::from)?;
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let Some(auth_provider) = self.config.read().auth_provider.clone() else {
+            return Ok(response);
+        };
+        let challenge = response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        if !auth_provider
+            .handle_unauthorized(challenge.as_deref())
+            .await?
+        {
+            return Ok(response);
+        }
+
+        tracing::debug!("Retrying request with refreshed credentials");
+        let builder = self.build_request(method, url).await?;
+        prepare(builder).send().await.map_err(Error::from)
+    }
+
     /// Process response headers and extract session/protocol information
     fn process_response_headers(&self, response: &Response) {
         // Update session ID from response header
@@ -335,16 +528,14 @@ impl StreamableHttpTransport {
         let body = String::from_utf8(body_bytes)
             .map_err(|e| Error::Transport(TransportError::Serialization(e.to_string())))?;
 
-        let url = self.config.read().url.clone();
-        let builder = self.build_request(reqwest::Method::POST, url).await?;
-
-        let response = builder
-            .header(CONTENT_TYPE, APPLICATION_JSON)
-            .header(ACCEPT, ACCEPT_STREAMABLE)
-            .body(body)
-            .send()
-            .await
-            .map_err(|e| Error::Transport(TransportError::Request(e.to_string())))?;
+        let response = self
+            .execute(reqwest::Method::POST, |builder| {
+                builder
+                    .header(CONTENT_TYPE, APPLICATION_JSON)
+                    .header(ACCEPT, ACCEPT_STREAMABLE)
+                    .body(body.clone())
+            })
+            .await?;
 
         // Process headers for session and protocol info
         self.process_response_headers(&response);
@@ -384,10 +575,7 @@ impl StreamableHttpTransport {
         // (often happens with notifications), check if it's actually empty
         if status_code == 200 && (content_length == Some(0) || content_type.is_empty()) {
             // Check if there's actually no body by consuming it
-            let body = response
-                .bytes()
-                .await
-                .map_err(|e| Error::Transport(TransportError::Request(e.to_string())))?;
+            let body = response.bytes().await.map_err(Error::from)?;
 
             if body.is_empty() {
                 // Empty 200 response (e.g., for notifications) - just return Ok
@@ -426,10 +614,7 @@ impl StreamableHttpTransport {
 
         if content_type.contains(APPLICATION_JSON) {
             // JSON response (single or batch)
-            let response_bytes = response
-                .bytes()
-                .await
-                .map_err(|e| Error::Transport(TransportError::Request(e.to_string())))?;
+            let response_bytes = response.bytes().await.map_err(Error::from)?;
 
             // Try to parse as array first (batch response - JSON-RPC 2.0)
             if let Ok(batch) = serde_json::from_slice::<Vec<serde_json::Value>>(&response_bytes) {
@@ -452,35 +637,9 @@ impl StreamableHttpTransport {
             }
         } else if content_type.contains(TEXT_EVENT_STREAM) {
             // SSE stream response - handle streaming
-            let sender = self.sender.clone();
-            let on_resumption = self.config.read().on_resumption_token.clone();
-            let last_event_id = self.last_event_id.clone();
-
+            let transport = self.clone();
             tokio::spawn(async move {
-                let mut sse_parser = SseParser::new();
-                let body = response.text().await.unwrap_or_default();
-
-                // Parse the SSE body
-                let events = sse_parser.feed(&body);
-                for event in events {
-                    // Update last event ID and notify callback
-                    if let Some(id) = &event.id {
-                        *last_event_id.write() = Some(id.clone());
-                        if let Some(callback) = &on_resumption {
-                            callback(id.clone());
-                        }
-                    }
-
-                    // Only process "message" events
-                    if event.event.as_deref() == Some("message") || event.event.is_none() {
-                        // Use JSON-RPC compatibility layer
-                        if let Ok(msg) =
-                            crate::shared::StdioTransport::parse_message(event.data.as_bytes())
-                        {
-                            let _ = sender.send(msg);
-                        }
-                    }
-                }
+                transport.run_sse(response, false).await;
             });
         } else if status_code.as_u16() == 202 {
             // 202 Accepted with no body is valid
@@ -499,6 +658,7 @@ impl StreamableHttpTransport {
 #[async_trait]
 impl Transport for StreamableHttpTransport {
     async fn send(&mut self, message: TransportMessage) -> Result<()> {
+        self.observe_outgoing(&message);
         self.send_with_options(message, SendOptions::default())
             .await
     }
@@ -506,10 +666,12 @@ impl Transport for StreamableHttpTransport {
     async fn receive(&mut self) -> Result<TransportMessage> {
         // Receive from channel - this will block until a message is available
         let mut receiver = self.receiver.lock().await;
-        receiver
+        let message = receiver
             .recv()
             .await
-            .ok_or_else(|| Error::Transport(TransportError::ConnectionClosed))
+            .ok_or_else(|| Error::Transport(TransportError::ConnectionClosed))?;
+        self.observe_incoming(&message);
+        Ok(message)
     }
 
     async fn close(&mut self) -> Result<()> {
@@ -552,4 +714,14 @@ impl Transport for StreamableHttpTransport {
 pub trait AuthProvider: Send + Sync + Debug {
     /// Returns an access token.
     async fn get_access_token(&self) -> Result<String>;
+
+    /// Called when the server rejects a request with `401 Unauthorized`.
+    ///
+    /// `www_authenticate` is the response's `WWW-Authenticate` header, if
+    /// any. Return `true` once new credentials are available to have the
+    /// transport retry the request a single time; the default gives up.
+    async fn handle_unauthorized(&self, www_authenticate: Option<&str>) -> Result<bool> {
+        let _ = www_authenticate;
+        Ok(false)
+    }
 }
@@ -0,0 +1,224 @@
+//! Strict validation of JSON-RPC envelopes.
+//!
+//! By default the SDK is lenient about what it accepts from peers: unknown
+//! fields are ignored and the `jsonrpc` version is not checked. Strict mode
+//! (see [`ProtocolOptions::strict`](super::protocol::ProtocolOptions::strict))
+//! rejects anything that is not a well-formed MCP message, which is useful
+//! for conformance testing and for hardening internet-facing servers.
+//!
+//! Violations are reported as protocol errors carrying the JSON-RPC error
+//! code a peer should receive:
+//!
+//! - `INVALID_REQUEST` for malformed envelopes (wrong version, unknown
+//!   top-level fields, invalid ids, responses with both `result` and `error`)
+//! - `METHOD_NOT_FOUND` for requests and notifications with unknown methods
+//! - `INVALID_PARAMS` for `params` that are neither an object nor an array
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::shared::strict::validate_message;
+//! use pmcp::ErrorCode;
+//! use serde_json::json;
+//!
+//! assert!(validate_message(&json!({"jsonrpc": "2.0", "id": 1, "method": "ping"})).is_ok());
+//!
+//! let err = validate_message(&json!({"jsonrpc": "2.0", "id": 1, "method": "nope"})).unwrap_err();
+//! assert_eq!(err.error_code(), Some(ErrorCode::METHOD_NOT_FOUND));
+//! ```
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::types::jsonrpc::JSONRPC_VERSION;
+use serde_json::Value;
+
+/// Top-level fields allowed in a JSON-RPC message.
+const ENVELOPE_FIELDS: &[&str] = &["jsonrpc", "id", "method", "params", "result", "error"];
+
+/// Request methods defined by MCP.
+const KNOWN_REQUEST_METHODS: &[&str] = &[
+    "initialize",
+    "ping",
+    "logging/setLevel",
+    "tools/list",
+    "tools/call",
+    "prompts/list",
+    "prompts/get",
+    "resources/list",
+    "resources/templates/list",
+    "resources/read",
+    "resources/subscribe",
+    "resources/unsubscribe",
+    "completion/complete",
+    "sampling/createMessage",
+    "roots/list",
+    "elicitation/input",
+    "elicitation/response",
+];
+
+/// Notification methods defined by MCP.
+const KNOWN_NOTIFICATION_METHODS: &[&str] = &[
+    "notifications/initialized",
+    "notifications/cancelled",
+    "notifications/progress",
+    "notifications/message",
+    "notifications/roots/list_changed",
+    "notifications/tools/list_changed",
+    "notifications/prompts/list_changed",
+    "notifications/resources/list_changed",
+    "notifications/resources/updated",
+];
+
+/// Validate a single JSON-RPC message or a batch of messages.
+pub fn validate_message(value: &Value) -> Result<()> {
+    match value {
+        Value::Array(items) if items.is_empty() => Err(invalid_request("Empty batch")),
+        Value::Array(items) => items.iter().try_for_each(validate_single),
+        _ => validate_single(value),
+    }
+}
+
+fn validate_single(value: &Value) -> Result<()> {
+    let Value::Object(map) = value else {
+        return Err(invalid_request("Message must be a JSON object"));
+    };
+
+    match map.get("jsonrpc") {
+        Some(Value::String(version)) if version == JSONRPC_VERSION => {},
+        Some(other) => {
+            return Err(invalid_request(format!(
+                "Unsupported jsonrpc version {}, expected \"{}\"",
+                other, JSONRPC_VERSION
+            )))
+        },
+        None => return Err(invalid_request("Missing jsonrpc field")),
+    }
+
+    if let Some(field) = map
+        .keys()
+        .find(|key| !ENVELOPE_FIELDS.contains(&key.as_str()))
+    {
+        return Err(invalid_request(format!("Unknown field '{}'", field)));
+    }
+
+    if let Some(id) = map.get("id") {
+        if !(id.is_string() || id.is_i64() || id.is_u64()) {
+            return Err(invalid_request(format!(
+                "Request id must be a string or integer, got {}",
+                id
+            )));
+        }
+    }
+
+    match map.get("method") {
+        Some(Value::String(method)) => {
+            if map.contains_key("result") || map.contains_key("error") {
+                return Err(invalid_request(
+                    "Requests must not contain 'result' or 'error'",
+                ));
+            }
+            let known = if map.contains_key("id") {
+                KNOWN_REQUEST_METHODS
+            } else {
+                KNOWN_NOTIFICATION_METHODS
+            };
+            if !known.contains(&method.as_str()) {
+                return Err(Error::protocol(
+                    ErrorCode::METHOD_NOT_FOUND,
+                    format!("Unknown method '{}'", method),
+                ));
+            }
+            match map.get("params") {
+                None | Some(Value::Object(_) | Value::Array(_)) => Ok(()),
+                Some(_) => Err(Error::protocol(
+                    ErrorCode::INVALID_PARAMS,
+                    "params must be an object or an array",
+                )),
+            }
+        },
+        Some(_) => Err(invalid_request("method must be a string")),
+        None => {
+            if map.contains_key("params") {
+                return Err(invalid_request("Responses must not contain 'params'"));
+            }
+            match (map.contains_key("result"), map.contains_key("error")) {
+                (true, true) => Err(invalid_request(
+                    "Responses must not contain both 'result' and 'error'",
+                )),
+                (false, false) => Err(invalid_request(
+                    "Message has neither 'method' nor 'result' or 'error'",
+                )),
+                _ if !map.contains_key("id") => Err(invalid_request("Responses require an id")),
+                _ => Ok(()),
+            }
+        },
+    }
+}
+
+fn invalid_request(message: impl Into<String>) -> Error {
+    Error::protocol(ErrorCode::INVALID_REQUEST, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn code(value: Value) -> Option<ErrorCode> {
+        validate_message(&value).unwrap_err().error_code()
+    }
+
+    #[test]
+    fn test_accepts_well_formed_messages() {
+        let messages = [
+            json!({"jsonrpc": "2.0", "id": "a", "method": "tools/call", "params": {"name": "x"}}),
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+            json!({"jsonrpc": "2.0", "id": 1, "result": {}}),
+            json!({"jsonrpc": "2.0", "id": 1, "error": {"code": -1, "message": "x"}}),
+            json!([{"jsonrpc": "2.0", "id": 1, "method": "ping"}]),
+        ];
+        for message in messages {
+            assert!(validate_message(&message).is_ok(), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed_envelopes() {
+        let invalid = Some(ErrorCode::INVALID_REQUEST);
+        assert_eq!(
+            code(json!({"jsonrpc": "1.0", "id": 1, "method": "ping"})),
+            invalid
+        );
+        assert_eq!(code(json!({"id": 1, "method": "ping"})), invalid);
+        assert_eq!(
+            code(json!({"jsonrpc": "2.0", "id": 1, "method": "ping", "extra": true})),
+            invalid
+        );
+        assert_eq!(
+            code(json!({"jsonrpc": "2.0", "id": 1, "result": {}, "error": {}})),
+            invalid
+        );
+        assert_eq!(
+            code(json!({"jsonrpc": "2.0", "id": 1.5, "method": "ping"})),
+            invalid
+        );
+        assert_eq!(code(json!({"jsonrpc": "2.0", "id": 1})), invalid);
+        assert_eq!(code(json!([])), invalid);
+    }
+
+    #[test]
+    fn test_rejects_unknown_methods_and_bad_params() {
+        assert_eq!(
+            code(json!({"jsonrpc": "2.0", "id": 1, "method": "tools/explode"})),
+            Some(ErrorCode::METHOD_NOT_FOUND)
+        );
+        // Request methods are not valid notifications.
+        assert_eq!(
+            code(json!({"jsonrpc": "2.0", "method": "tools/list"})),
+            Some(ErrorCode::METHOD_NOT_FOUND)
+        );
+        assert_eq!(
+            code(json!({"jsonrpc": "2.0", "id": 1, "method": "ping", "params": 3})),
+            Some(ErrorCode::INVALID_PARAMS)
+        );
+    }
+}
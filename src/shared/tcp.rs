@@ -0,0 +1,387 @@
+//! Raw TCP transport with optional TLS.
+//!
+//! Messages are newline-delimited JSON-RPC: each message (or batch array) is
+//! serialized on a single line. This needs no HTTP stack, so servers can sit
+//! behind plain socket load balancers.
+//!
+//! With the `tls` feature, [`TcpTransport::connect_tls`] and
+//! [`TcpServerListener::with_tls`](crate::server::transport::TcpServerListener::with_tls)
+//! wrap the socket in rustls. [`tls_client_config`] and [`tls_server_config`]
+//! build configurations from PEM data; anything more specific can be built
+//! with the re-exported [`rustls`] crate.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use pmcp::shared::TcpTransport;
+//! use pmcp::{Client, ClientCapabilities};
+//!
+//! # async fn example() -> pmcp::Result<()> {
+//! let transport = TcpTransport::connect("127.0.0.1:7000").await?;
+//! let mut client = Client::new(transport);
+//! client.initialize(ClientCapabilities::default()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Result, TransportError};
+use crate::shared::protocol::MessageSizeLimits;
+use crate::shared::stdio::StdioTransport;
+use crate::shared::transport::{Transport, TransportMessage};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::fmt;
+use std::net::SocketAddr;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpStream, ToSocketAddrs};
+
+#[cfg(feature = "tls")]
+use std::sync::Arc;
+#[cfg(feature = "tls")]
+pub use tokio_rustls::rustls;
+
+/// Size of each read from the socket.
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+
+/// A byte stream a [`TcpTransport`] can run over.
+trait Connection: AsyncRead + AsyncWrite + Unpin + Send + Sync {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send + Sync> Connection for T {}
+
+/// Newline-delimited JSON-RPC over a TCP connection, optionally TLS.
+///
+/// Receiving is cancel-safe: partially read lines are kept in the transport
+/// and completed by the next call.
+pub struct TcpTransport {
+    stream: Box<dyn Connection>,
+    peer_addr: SocketAddr,
+    /// Bytes read from the socket that do not form a complete line yet
+    buffer: Vec<u8>,
+    /// Length of the prefix of `buffer` known to contain no newline
+    scanned: usize,
+    /// Messages from a received batch that have not been returned yet
+    pending: VecDeque<TransportMessage>,
+    /// Limits on the size of framed messages
+    limits: MessageSizeLimits,
+    tls: bool,
+    closed: bool,
+}
+
+impl fmt::Debug for TcpTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TcpTransport")
+            .field("peer_addr", &self.peer_addr)
+            .field("tls", &self.tls)
+            .field("closed", &self.closed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TcpTransport {
+    /// Connect to a server over plain TCP.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the connection cannot be established.
+    pub async fn connect(addr: impl ToSocketAddrs) -> Result<Self> {
+        let stream = TcpStream::connect(addr)
+            .await
+            .map_err(TransportError::from)?;
+        Self::from_stream(stream)
+    }
+
+    /// Connect to a server over TLS.
+    ///
+    /// `server_name` is the name the server's certificate is verified
+    /// against.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `server_name` is invalid, or a transport error if
+    /// the connection or the TLS handshake fails.
+    #[cfg(feature = "tls")]
+    pub async fn connect_tls(
+        addr: impl ToSocketAddrs,
+        server_name: &str,
+        config: Arc<rustls::ClientConfig>,
+    ) -> Result<Self> {
+        let server_name = rustls_pki_types::ServerName::try_from(server_name.to_string())
+            .map_err(|e| crate::Error::validation(format!("Invalid TLS server name: {}", e)))?;
+        let stream = TcpStream::connect(addr)
+            .await
+            .map_err(TransportError::from)?;
+        stream.set_nodelay(true).map_err(TransportError::from)?;
+        let peer_addr = stream.peer_addr().map_err(TransportError::from)?;
+        let stream = tokio_rustls::TlsConnector::from(config)
+            .connect(server_name, stream)
+            .await
+            .map_err(TransportError::from)?;
+        Ok(Self::new(Box::new(stream), peer_addr, true))
+    }
+
+    /// Wrap an established plain TCP connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns a transport error if the socket is no longer connected.
+    pub fn from_stream(stream: TcpStream) -> Result<Self> {
+        stream.set_nodelay(true).map_err(TransportError::from)?;
+        let peer_addr = stream.peer_addr().map_err(TransportError::from)?;
+        Ok(Self::new(Box::new(stream), peer_addr, false))
+    }
+
+    /// Wrap a TLS connection accepted by a server.
+    #[cfg(feature = "tls")]
+    pub(crate) fn from_tls_server_stream(
+        stream: tokio_rustls::server::TlsStream<TcpStream>,
+        peer_addr: SocketAddr,
+    ) -> Self {
+        Self::new(Box::new(stream), peer_addr, true)
+    }
+
+    fn new(stream: Box<dyn Connection>, peer_addr: SocketAddr, tls: bool) -> Self {
+        Self {
+            stream,
+            peer_addr,
+            buffer: Vec::new(),
+            scanned: 0,
+            pending: VecDeque::new(),
+            limits: MessageSizeLimits::default(),
+            tls,
+            closed: false,
+        }
+    }
+
+    /// Enforce limits on the size of sent and received messages.
+    ///
+    /// Limits apply per line and behave as for
+    /// [`StdioTransport::with_message_size_limits`].
+    pub fn with_message_size_limits(mut self, limits: MessageSizeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Address of the remote end of the connection.
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.peer_addr
+    }
+
+    /// Whether the connection is encrypted with TLS.
+    pub fn is_tls(&self) -> bool {
+        self.tls
+    }
+
+    /// Take the next complete line out of the read buffer, without its
+    /// line terminator.
+    fn take_line(&mut self) -> Option<Vec<u8>> {
+        let newline = self.buffer[self.scanned..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map(|i| self.scanned + i);
+        let Some(newline) = newline else {
+            self.scanned = self.buffer.len();
+            return None;
+        };
+        let mut line: Vec<u8> = self.buffer.drain(..=newline).collect();
+        self.scanned = 0;
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Some(line)
+    }
+
+    async fn write_line(&mut self, json_bytes: &[u8]) -> Result<()> {
+        let mut line = Vec::with_capacity(json_bytes.len() + 1);
+        line.extend_from_slice(json_bytes);
+        line.push(b'\n');
+        self.stream
+            .write_all(&line)
+            .await
+            .map_err(TransportError::from)?;
+        self.stream.flush().await.map_err(TransportError::from)?;
+        Ok(())
+    }
+
+    fn ensure_open(&self) -> Result<()> {
+        if self.closed {
+            return Err(TransportError::ConnectionClosed.into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Transport for TcpTransport {
+    async fn send(&mut self, message: TransportMessage) -> Result<()> {
+        self.ensure_open()?;
+        let json_bytes = StdioTransport::serialize_within_limits(message, &self.limits)?;
+        self.write_line(&json_bytes).await
+    }
+
+    async fn send_batch(&mut self, messages: Vec<TransportMessage>) -> Result<()> {
+        self.ensure_open()?;
+        let json_bytes = StdioTransport::serialize_batch(&messages)?;
+        self.limits.check_outbound(json_bytes.len())?;
+        self.write_line(&json_bytes).await
+    }
+
+    async fn receive(&mut self) -> Result<TransportMessage> {
+        self.ensure_open()?;
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+
+        loop {
+            if let Some(message) = self.pending.pop_front() {
+                return Ok(message);
+            }
+
+            if let Some(line) = self.take_line() {
+                if line.iter().all(u8::is_ascii_whitespace) {
+                    continue;
+                }
+                let checked = self
+                    .limits
+                    .check_inbound(line.len())
+                    .and_then(|()| self.limits.check_inbound_structure(&line));
+                if let Err(e) = checked {
+                    if matches!(e, crate::Error::Transport(_)) {
+                        self.closed = true;
+                        return Err(e);
+                    }
+                    for response in StdioTransport::rejections(&line, e) {
+                        let json_bytes = StdioTransport::serialize_message(
+                            &TransportMessage::Response(response),
+                        )?;
+                        self.write_line(&json_bytes).await?;
+                    }
+                    continue;
+                }
+                self.pending.extend(StdioTransport::parse_batch(&line)?);
+                continue;
+            }
+
+            if let Some(max) = self.limits.inbound_close_threshold() {
+                if self.buffer.len() > max {
+                    self.closed = true;
+                    return Err(TransportError::InvalidMessage(format!(
+                        "Line of more than {} bytes without a terminator, closing connection",
+                        max
+                    ))
+                    .into());
+                }
+            }
+
+            let read = self
+                .stream
+                .read(&mut chunk)
+                .await
+                .map_err(TransportError::from)?;
+            if read == 0 {
+                self.closed = true;
+                return Err(TransportError::ConnectionClosed.into());
+            }
+            self.buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+
+    async fn receive_batch(&mut self) -> Result<Vec<TransportMessage>> {
+        // Receiving parses a whole frame and leaves the rest of a batch
+        // pending.
+        if self.pending.is_empty() {
+            let first = self.receive().await?;
+            self.pending.push_front(first);
+        }
+        Ok(self.pending.drain(..).collect())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+        self.stream.shutdown().await.map_err(TransportError::from)?;
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        !self.closed
+    }
+
+    fn transport_type(&self) -> &'static str {
+        if self.tls {
+            "tls"
+        } else {
+            "tcp"
+        }
+    }
+}
+
+/// Build a TLS client configuration trusting the CA certificates in `ca_pem`.
+///
+/// # Errors
+///
+/// Returns a validation error if `ca_pem` contains no valid certificate.
+#[cfg(feature = "tls")]
+pub fn tls_client_config(ca_pem: &[u8]) -> Result<Arc<rustls::ClientConfig>> {
+    use rustls_pki_types::pem::PemObject;
+    use rustls_pki_types::CertificateDer;
+
+    let mut roots = rustls::RootCertStore::empty();
+    for cert in CertificateDer::pem_slice_iter(ca_pem) {
+        let cert = cert.map_err(|e| tls_config_error("Invalid CA certificate", e))?;
+        roots
+            .add(cert)
+            .map_err(|e| tls_config_error("Invalid CA certificate", e))?;
+    }
+    if roots.is_empty() {
+        return Err(crate::Error::validation(
+            "No CA certificate found in PEM data",
+        ));
+    }
+
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| tls_config_error("Unsupported TLS configuration", e))?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+/// Build a TLS server configuration from a PEM certificate chain and private
+/// key.
+///
+/// # Errors
+///
+/// Returns a validation error if the certificate chain or key is invalid or
+/// they do not match.
+#[cfg(feature = "tls")]
+pub fn tls_server_config(cert_pem: &[u8], key_pem: &[u8]) -> Result<Arc<rustls::ServerConfig>> {
+    use rustls_pki_types::pem::PemObject;
+    use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+
+    let certs = CertificateDer::pem_slice_iter(cert_pem)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| tls_config_error("Invalid certificate", e))?;
+    if certs.is_empty() {
+        return Err(crate::Error::validation("No certificate found in PEM data"));
+    }
+    let key = PrivateKeyDer::from_pem_slice(key_pem)
+        .map_err(|e| tls_config_error("Invalid private key", e))?;
+
+    let config = rustls::ServerConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| tls_config_error("Unsupported TLS configuration", e))?
+    .with_no_client_auth()
+    .with_single_cert(certs, key)
+    .map_err(|e| tls_config_error("Invalid certificate or key", e))?;
+    Ok(Arc::new(config))
+}
+
+#[cfg(feature = "tls")]
+fn tls_config_error(context: &str, error: impl fmt::Display) -> crate::Error {
+    crate::Error::validation(format!("{}: {}", context, error))
+}
@@ -0,0 +1,306 @@
+//! OpenTelemetry instrumentation of request handling.
+//!
+//! With the `opentelemetry` feature, clients and servers wrap each request
+//! in a span (kind `CLIENT` or `SERVER`) named after the method, with the
+//! method, request ID, and transport as attributes. Clients inject the
+//! current trace context into the request's `_meta` (`traceparent` and
+//! `tracestate`, following W3C Trace Context), and servers continue that
+//! trace, so one trace spans both sides.
+//!
+//! Request counts, errors, and latencies are recorded as metrics under the
+//! `pmcp` meter:
+//!
+//! - `mcp.{client,server}.requests`: counter of handled requests
+//! - `mcp.{client,server}.errors`: counter of failed requests, by error code
+//! - `mcp.{client,server}.operation.duration`: histogram of latencies, in seconds
+//!
+//! Spans and metrics go to the globally installed providers, and context is
+//! propagated with the global propagator, so install them (e.g. an OTLP
+//! exporter and `TraceContextPropagator`) before creating clients or
+//! servers. Without them, instrumentation costs next to nothing.
+//!
+//! Trace context travels in `_meta`, which only tool calls and resource
+//! reads carry; other requests start a new trace on the server.
+
+use crate::types::jsonrpc::ResponsePayload;
+use crate::types::{ClientRequest, JSONRPCResponse, Request, RequestId};
+use opentelemetry::metrics::{Counter, Histogram};
+use opentelemetry::propagation::{Extractor, Injector};
+use opentelemetry::trace::{SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::{global, Context, KeyValue};
+use serde_json::{Map, Value};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+/// Name of the tracer and meter used by the SDK.
+pub const INSTRUMENTATION_SCOPE: &str = "pmcp";
+
+/// Which end of the connection a request is observed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Side {
+    Client,
+    Server,
+}
+
+struct Instruments {
+    requests: Counter<u64>,
+    errors: Counter<u64>,
+    duration: Histogram<f64>,
+}
+
+impl Instruments {
+    fn new(side: &str) -> Self {
+        let meter = global::meter(INSTRUMENTATION_SCOPE);
+        Self {
+            requests: meter
+                .u64_counter(format!("mcp.{}.requests", side))
+                .with_description("Number of MCP requests")
+                .build(),
+            errors: meter
+                .u64_counter(format!("mcp.{}.errors", side))
+                .with_description("Number of failed MCP requests")
+                .build(),
+            duration: meter
+                .f64_histogram(format!("mcp.{}.operation.duration", side))
+                .with_description("Duration of MCP requests")
+                .with_unit("s")
+                .build(),
+        }
+    }
+
+    fn get(side: Side) -> &'static Self {
+        static CLIENT: OnceLock<Instruments> = OnceLock::new();
+        static SERVER: OnceLock<Instruments> = OnceLock::new();
+        match side {
+            Side::Client => CLIENT.get_or_init(|| Self::new("client")),
+            Side::Server => SERVER.get_or_init(|| Self::new("server")),
+        }
+    }
+}
+
+/// `_meta` object read as a propagation carrier.
+struct MetaExtractor<'a>(&'a Map<String, Value>);
+
+impl Extractor for MetaExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(Value::as_str)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(String::as_str).collect()
+    }
+}
+
+/// `_meta` object written as a propagation carrier.
+struct MetaInjector<'a>(&'a mut Map<String, Value>);
+
+impl Injector for MetaInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_string(), Value::String(value));
+    }
+}
+
+/// Write the trace context of `cx` into a `_meta` value.
+pub fn inject_context(meta: &mut Option<Value>, cx: &Context) {
+    let meta = meta.get_or_insert_with(|| Value::Object(Map::new()));
+    if let Value::Object(map) = meta {
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(cx, &mut MetaInjector(map));
+        });
+    }
+}
+
+/// Read the trace context carried in a `_meta` value, if any.
+pub fn extract_context(meta: Option<&Value>) -> Context {
+    match meta {
+        Some(Value::Object(map)) => {
+            global::get_text_map_propagator(|propagator| propagator.extract(&MetaExtractor(map)))
+        },
+        _ => Context::new(),
+    }
+}
+
+/// The `_meta` of requests that can carry one.
+fn request_meta(request: &mut Request) -> Option<&mut Option<Value>> {
+    let Request::Client(request) = request else {
+        return None;
+    };
+    match request.as_mut() {
+        ClientRequest::CallTool(call) => Some(&mut call.meta),
+        ClientRequest::ReadResource(read) => Some(&mut read.meta),
+        _ => None,
+    }
+}
+
+/// Trace context sent along with `request`.
+pub(crate) fn request_context(request: &Request) -> Context {
+    let Request::Client(request) = request else {
+        return Context::new();
+    };
+    match request.as_ref() {
+        ClientRequest::CallTool(call) => extract_context(call.meta.as_ref()),
+        ClientRequest::ReadResource(read) => extract_context(read.meta.as_ref()),
+        _ => Context::new(),
+    }
+}
+
+/// A request being traced and measured.
+pub(crate) struct RequestSpan {
+    cx: Context,
+    side: Side,
+    method: &'static str,
+    started: Instant,
+}
+
+impl RequestSpan {
+    /// Start a span for `method` as a child of `parent`.
+    pub(crate) fn start(
+        side: Side,
+        method: &'static str,
+        request_id: &RequestId,
+        transport: &'static str,
+        parent: &Context,
+    ) -> Self {
+        let tracer = global::tracer(INSTRUMENTATION_SCOPE);
+        let span = tracer
+            .span_builder(method)
+            .with_kind(match side {
+                Side::Client => SpanKind::Client,
+                Side::Server => SpanKind::Server,
+            })
+            .with_attributes([
+                KeyValue::new("mcp.method.name", method),
+                KeyValue::new("jsonrpc.request.id", request_id.to_string()),
+                KeyValue::new("mcp.transport", transport),
+            ])
+            .start_with_context(&tracer, parent);
+        Self {
+            cx: parent.with_span(span),
+            side,
+            method,
+            started: Instant::now(),
+        }
+    }
+
+    /// Context to run the request in, so nested spans become children.
+    pub(crate) fn context(&self) -> &Context {
+        &self.cx
+    }
+
+    /// Carry this span's context in `request`'s `_meta`, if it has one.
+    pub(crate) fn inject_into(&self, request: &mut Request) {
+        if let Some(meta) = request_meta(request) {
+            inject_context(meta, &self.cx);
+        }
+    }
+
+    /// End the span and record metrics for a request that produced
+    /// `response`, or failed before one arrived.
+    pub(crate) fn finish(self, response: std::result::Result<&JSONRPCResponse, &crate::Error>) {
+        let error = match response {
+            Ok(JSONRPCResponse {
+                payload: ResponsePayload::Error(error),
+                ..
+            }) => Some((error.code, error.message.clone())),
+            Ok(_) => None,
+            Err(e) => Some((
+                e.error_code()
+                    .map_or(crate::ErrorCode::INTERNAL_ERROR.as_i32(), |c| c.as_i32()),
+                e.to_string(),
+            )),
+        };
+
+        let instruments = Instruments::get(self.side);
+        let method = KeyValue::new("mcp.method.name", self.method);
+        let span = self.cx.span();
+        match error {
+            Some((code, message)) => {
+                span.set_attribute(KeyValue::new("rpc.jsonrpc.error_code", i64::from(code)));
+                span.set_status(Status::error(message));
+                instruments.errors.add(
+                    1,
+                    &[
+                        method.clone(),
+                        KeyValue::new("rpc.jsonrpc.error_code", i64::from(code)),
+                    ],
+                );
+            },
+            None => span.set_status(Status::Ok),
+        }
+        span.end();
+        instruments.requests.add(1, std::slice::from_ref(&method));
+        instruments
+            .duration
+            .record(self.started.elapsed().as_secs_f64(), &[method]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{SpanContext, SpanId, TraceFlags, TraceId, TraceState};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+
+    #[test]
+    fn test_trace_context_round_trips_through_meta() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let span_context = SpanContext::new(
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+            SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+        let cx = Context::new().with_remote_span_context(span_context.clone());
+
+        let mut meta = Some(serde_json::json!({"progressToken": 1}));
+        inject_context(&mut meta, &cx);
+        let meta = meta.unwrap();
+        assert_eq!(
+            meta["traceparent"],
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+        assert_eq!(meta["progressToken"], 1);
+
+        let extracted = extract_context(Some(&meta));
+        assert_eq!(
+            extracted.span().span_context().trace_id(),
+            span_context.trace_id()
+        );
+        assert!(!extract_context(None).span().span_context().is_valid());
+    }
+
+    #[test]
+    fn test_request_span_records_error_status() {
+        let exporter = InMemorySpanExporter::default();
+        global::set_tracer_provider(
+            SdkTracerProvider::builder()
+                .with_simple_exporter(exporter.clone())
+                .build(),
+        );
+
+        let request_id = RequestId::String("telemetry-test".to_string());
+        let span = RequestSpan::start(
+            Side::Server,
+            "tools/call",
+            &request_id,
+            "memory",
+            &Context::new(),
+        );
+        let error = crate::Error::not_found("no such tool");
+        span.finish(Err(&error));
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let span = spans
+            .iter()
+            .find(|span| {
+                span.attributes
+                    .contains(&KeyValue::new("jsonrpc.request.id", "telemetry-test"))
+            })
+            .unwrap();
+        assert_eq!(span.name, "tools/call");
+        assert_eq!(span.span_kind, SpanKind::Server);
+        assert!(matches!(span.status, Status::Error { .. }));
+    }
+}
@@ -0,0 +1,359 @@
+//! Per-request timing breakdown.
+//!
+//! Request dispatch records how long each phase took so users can tell
+//! whether latency comes from their handler or from the SDK:
+//!
+//! - **parse**: decoding the JSON-RPC message
+//! - **middleware**: routing, authentication, and other SDK work around the
+//!   handler
+//! - **handler**: the registered tool, prompt, resource, or sampling handler
+//! - **serialize**: encoding the handler result as JSON
+//!
+//! Code on the dispatch path reports phases with [`record`], [`time`], or
+//! [`time_sync`]. These are no-ops unless a [`RequestTimer`] is in scope, so
+//! transports shared with the client pay nothing. Finished timings are
+//! aggregated by [`DispatchMetrics`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::shared::timing::{self, DispatchMetrics, Phase, RequestTimer};
+//! use std::time::Duration;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let timer = RequestTimer::new();
+//! timer
+//!     .scope(async {
+//!         timing::record(Phase::Parse, Duration::from_micros(40));
+//!         timing::time(Phase::Handler, async { /* call the handler */ }).await;
+//!     })
+//!     .await;
+//!
+//! let timings = timer.finish(Duration::from_millis(1));
+//! assert_eq!(timings.parse, Duration::from_micros(40));
+//!
+//! let metrics = DispatchMetrics::new();
+//! metrics.record(&timings);
+//! assert_eq!(metrics.snapshot().requests, 1);
+//! # }
+//! ```
+
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Key of the timing block in a result's `_meta`.
+pub const TIMING_META_KEY: &str = "pmcp/timing";
+
+tokio::task_local! {
+    static CURRENT: RequestTimer;
+}
+
+/// A phase of request processing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Decoding the JSON-RPC message.
+    Parse,
+    /// SDK work around the handler.
+    Middleware,
+    /// The registered handler.
+    Handler,
+    /// Encoding the handler result.
+    Serialize,
+}
+
+/// Time spent in each phase of one request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestTimings {
+    /// Decoding the JSON-RPC message
+    pub parse: Duration,
+    /// Routing, authentication, and other SDK work around the handler
+    pub middleware: Duration,
+    /// The registered handler
+    pub handler: Duration,
+    /// Encoding the handler result
+    pub serialize: Duration,
+}
+
+impl RequestTimings {
+    /// Total time across all phases.
+    pub fn total(&self) -> Duration {
+        self.parse + self.middleware + self.handler + self.serialize
+    }
+
+    /// Time recorded for a phase.
+    pub fn get(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Parse => self.parse,
+            Phase::Middleware => self.middleware,
+            Phase::Handler => self.handler,
+            Phase::Serialize => self.serialize,
+        }
+    }
+
+    fn get_mut(&mut self, phase: Phase) -> &mut Duration {
+        match phase {
+            Phase::Parse => &mut self.parse,
+            Phase::Middleware => &mut self.middleware,
+            Phase::Handler => &mut self.handler,
+            Phase::Serialize => &mut self.serialize,
+        }
+    }
+
+    /// Timings as a JSON object of microsecond values.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "parseUs": self.parse.as_micros() as u64,
+            "middlewareUs": self.middleware.as_micros() as u64,
+            "handlerUs": self.handler.as_micros() as u64,
+            "serializeUs": self.serialize.as_micros() as u64,
+            "totalUs": self.total().as_micros() as u64,
+        })
+    }
+
+    /// Add these timings to the `_meta` of a result object.
+    ///
+    /// Results that are not JSON objects are left unchanged.
+    pub fn attach_to(&self, result: &mut Value) {
+        let Some(object) = result.as_object_mut() else {
+            return;
+        };
+        let meta = object.entry("_meta").or_insert_with(|| json!({}));
+        if let Some(meta) = meta.as_object_mut() {
+            meta.insert(TIMING_META_KEY.to_string(), self.to_json());
+        }
+    }
+}
+
+/// Collects phase timings for the request being processed.
+///
+/// Cloning a timer yields a handle to the same timings.
+#[derive(Debug, Clone, Default)]
+pub struct RequestTimer {
+    timings: Arc<Mutex<RequestTimings>>,
+}
+
+impl RequestTimer {
+    /// Create a timer with no recorded time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The timer in scope for the current task, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(Clone::clone).ok()
+    }
+
+    /// Run a future with this timer in scope.
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        CURRENT.scope(self.clone(), future).await
+    }
+
+    /// Add time to a phase.
+    pub fn record(&self, phase: Phase, elapsed: Duration) {
+        *self.timings.lock().get_mut(phase) += elapsed;
+    }
+
+    /// Remove and return the time recorded for a phase.
+    pub fn take(&self, phase: Phase) -> Duration {
+        std::mem::take(self.timings.lock().get_mut(phase))
+    }
+
+    /// Timings recorded so far.
+    pub fn timings(&self) -> RequestTimings {
+        *self.timings.lock()
+    }
+
+    /// Complete the timings for a dispatch that took `dispatch` in total.
+    ///
+    /// Dispatch time not spent in the handler or serializing is attributed to
+    /// middleware.
+    pub fn finish(&self, dispatch: Duration) -> RequestTimings {
+        let mut timings = self.timings.lock();
+        let remainder = dispatch
+            .saturating_sub(timings.handler)
+            .saturating_sub(timings.serialize);
+        timings.middleware += remainder;
+        *timings
+    }
+}
+
+/// Add time to a phase of the current request, if one is being timed.
+pub fn record(phase: Phase, elapsed: Duration) {
+    let _ = CURRENT.try_with(|timer| timer.record(phase, elapsed));
+}
+
+/// Await a future, recording its duration under `phase`.
+pub async fn time<F: Future>(phase: Phase, future: F) -> F::Output {
+    let started = Instant::now();
+    let output = future.await;
+    record(phase, started.elapsed());
+    output
+}
+
+/// Run a closure, recording its duration under `phase`.
+pub fn time_sync<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let output = f();
+    record(phase, started.elapsed());
+    output
+}
+
+/// Serialize a value, recording the time under [`Phase::Serialize`].
+pub fn to_value<T: serde::Serialize>(value: T) -> serde_json::Result<Value> {
+    time_sync(Phase::Serialize, || serde_json::to_value(value))
+}
+
+/// Aggregate statistics for one phase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseStats {
+    /// Total time across all requests
+    pub total: Duration,
+    /// Longest time for a single request
+    pub max: Duration,
+}
+
+impl PhaseStats {
+    fn add(&mut self, elapsed: Duration) {
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+
+    /// Mean time per request.
+    pub fn mean(&self, requests: u64) -> Duration {
+        if requests == 0 {
+            Duration::ZERO
+        } else {
+            self.total / requests as u32
+        }
+    }
+}
+
+/// Point-in-time copy of [`DispatchMetrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimingSnapshot {
+    /// Number of requests recorded
+    pub requests: u64,
+    /// Parse phase statistics
+    pub parse: PhaseStats,
+    /// Middleware phase statistics
+    pub middleware: PhaseStats,
+    /// Handler phase statistics
+    pub handler: PhaseStats,
+    /// Serialize phase statistics
+    pub serialize: PhaseStats,
+}
+
+impl TimingSnapshot {
+    /// Statistics for a phase.
+    pub fn phase(&self, phase: Phase) -> PhaseStats {
+        match phase {
+            Phase::Parse => self.parse,
+            Phase::Middleware => self.middleware,
+            Phase::Handler => self.handler,
+            Phase::Serialize => self.serialize,
+        }
+    }
+}
+
+/// Thread-safe aggregate of request timings.
+#[derive(Debug, Default)]
+pub struct DispatchMetrics {
+    snapshot: Mutex<TimingSnapshot>,
+}
+
+impl DispatchMetrics {
+    /// Create empty metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold one request's timings into the aggregate.
+    pub fn record(&self, timings: &RequestTimings) {
+        let mut snapshot = self.snapshot.lock();
+        snapshot.requests += 1;
+        snapshot.parse.add(timings.parse);
+        snapshot.middleware.add(timings.middleware);
+        snapshot.handler.add(timings.handler);
+        snapshot.serialize.add(timings.serialize);
+    }
+
+    /// Copy of the current statistics.
+    pub fn snapshot(&self) -> TimingSnapshot {
+        *self.snapshot.lock()
+    }
+
+    /// Clear all statistics.
+    pub fn reset(&self) {
+        *self.snapshot.lock() = TimingSnapshot::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_phases_are_recorded_only_in_scope() {
+        record(Phase::Handler, Duration::from_secs(1));
+        assert!(RequestTimer::current().is_none());
+
+        let timer = RequestTimer::new();
+        timer
+            .scope(async {
+                record(Phase::Parse, Duration::from_millis(1));
+                record(Phase::Handler, Duration::from_millis(5));
+                record(Phase::Handler, Duration::from_millis(5));
+                let value = to_value(vec![1, 2, 3]).unwrap();
+                assert_eq!(value, json!([1, 2, 3]));
+                assert!(RequestTimer::current().is_some());
+            })
+            .await;
+
+        let timings = timer.timings();
+        assert_eq!(timings.parse, Duration::from_millis(1));
+        assert_eq!(timings.handler, Duration::from_millis(10));
+        assert_eq!(timings.middleware, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_finish_attributes_remainder_to_middleware() {
+        let timer = RequestTimer::new();
+        timer.record(Phase::Handler, Duration::from_millis(6));
+        timer.record(Phase::Serialize, Duration::from_millis(1));
+        let timings = timer.finish(Duration::from_millis(10));
+        assert_eq!(timings.middleware, Duration::from_millis(3));
+        assert_eq!(timings.total(), Duration::from_millis(10));
+
+        let mut result = json!({"content": []});
+        timings.attach_to(&mut result);
+        assert_eq!(result["_meta"][TIMING_META_KEY]["handlerUs"], 6000);
+        assert_eq!(result["_meta"][TIMING_META_KEY]["totalUs"], 10000);
+    }
+
+    #[test]
+    fn test_metrics_aggregate_requests() {
+        let metrics = DispatchMetrics::new();
+        for handler_ms in [2, 8] {
+            metrics.record(&RequestTimings {
+                handler: Duration::from_millis(handler_ms),
+                ..Default::default()
+            });
+        }
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.requests, 2);
+        assert_eq!(snapshot.handler.total, Duration::from_millis(10));
+        assert_eq!(snapshot.handler.max, Duration::from_millis(8));
+        assert_eq!(
+            snapshot.phase(Phase::Handler).mean(snapshot.requests),
+            Duration::from_millis(5)
+        );
+
+        metrics.reset();
+        assert_eq!(metrics.snapshot().requests, 0);
+    }
+}
@@ -218,6 +218,27 @@ pub trait Transport: Send + Sync + Debug {
     /// more messages for sending or receiving.
     async fn close(&mut self) -> Result<()>;
 
+    /// Send several messages as a single JSON-RPC batch.
+    ///
+    /// Transports that can frame a batch as one JSON array should override
+    /// this. The default implementation sends the messages one at a time.
+    async fn send_batch(&mut self, messages: Vec<TransportMessage>) -> Result<()> {
+        for message in messages {
+            self.send(message).await?;
+        }
+        Ok(())
+    }
+
+    /// Receive every message of the next frame.
+    ///
+    /// A JSON-RPC batch arrives as one frame holding several messages, which
+    /// a peer expects answered together. Transports that can frame a batch
+    /// as one JSON array should override this. The default implementation
+    /// returns the next message on its own.
+    async fn receive_batch(&mut self) -> Result<Vec<TransportMessage>> {
+        Ok(vec![self.receive().await?])
+    }
+
     /// Check if the transport is still connected.
     ///
     /// Default implementation always returns true.
@@ -243,6 +264,19 @@ pub trait Transport: Debug {
     /// Close the transport.
     async fn close(&mut self) -> Result<()>;
 
+    /// Send several messages as a single JSON-RPC batch.
+    async fn send_batch(&mut self, messages: Vec<TransportMessage>) -> Result<()> {
+        for message in messages {
+            self.send(message).await?;
+        }
+        Ok(())
+    }
+
+    /// Receive every message of the next frame.
+    async fn receive_batch(&mut self) -> Result<Vec<TransportMessage>> {
+        Ok(vec![self.receive().await?])
+    }
+
     /// Check if the transport is still connected.
     fn is_connected(&self) -> bool {
         true
@@ -547,6 +547,30 @@ impl UriTemplate {
 
         vars
     }
+
+    /// Get the variables of simple (`{var}`, `{+var}`) and path segment
+    /// (`{/var}`) expansions, which the expanded URI needs.
+    ///
+    /// Query, fragment and other expansions are left out, as they can be
+    /// omitted.
+    pub fn path_variables(&self) -> Vec<String> {
+        let mut vars = Vec::new();
+
+        for expr in &self.expressions {
+            match expr {
+                Expression::Simple(name)
+                | Expression::Reserved(name)
+                | Expression::PathSegment(name) => vars.push(name.clone()),
+                Expression::Multiple(
+                    Operator::Simple | Operator::Reserved | Operator::PathSegment,
+                    specs,
+                ) => vars.extend(specs.iter().map(|spec| spec.name.clone())),
+                _ => {},
+            }
+        }
+
+        vars
+    }
 }
 
 impl fmt::Display for UriTemplate {
@@ -616,4 +640,10 @@ mod tests {
         let vars = template.variables();
         assert_eq!(vars, vec!["id", "post_id"]);
     }
+
+    #[test]
+    fn test_path_variables() {
+        let template = UriTemplate::new("/users/{id}{/section}{?verbose,page}{#frag}").unwrap();
+        assert_eq!(template.path_variables(), vec!["id", "section"]);
+    }
 }
@@ -152,10 +152,10 @@ impl Transport for WasmWebSocketTransport {
 
     async fn close(&mut self) -> Result<()> {
         self.ws.close().map_err(|e| {
-            Error::Transport(TransportError::Io(format!(
+            Error::Transport(TransportError::Io(std::io::Error::other(format!(
                 "Failed to close WebSocket: {:?}",
                 e
-            )))
+            ))))
         })?;
         Ok(())
     }
@@ -571,6 +571,156 @@ pub mod batch {
     }
 }
 
+/// SIMD-accelerated message framing helpers
+///
+/// Safe entry points that pick the AVX2 path at runtime when the CPU
+/// supports it and fall back to scalar code otherwise. WebAssembly builds
+/// compiled with `-C target-feature=+simd128` use SIMD128 instead, as wasm
+/// has no runtime feature detection.
+pub mod framing {
+    use super::*;
+
+    /// Find the first `\n` in `input`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::simd::framing::find_newline;
+    ///
+    /// assert_eq!(find_newline(b"Content-Length: 2\r\n"), Some(18));
+    /// assert_eq!(find_newline(b"no newline"), None);
+    /// ```
+    pub fn find_newline(input: &[u8]) -> Option<usize> {
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 support was checked above.
+            return unsafe { find_byte_avx2(input, b'\n') };
+        }
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        {
+            find_byte_simd128(input, b'\n')
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+        {
+            input.iter().position(|&b| b == b'\n')
+        }
+    }
+
+    /// Validate that `input` is UTF-8 and view it as a `str`.
+    ///
+    /// All-ASCII input, the common case for JSON-RPC traffic, is accepted
+    /// after a vectorized scan; anything else goes through full validation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmcp::simd::framing::validate_utf8;
+    ///
+    /// assert_eq!(validate_utf8(b"{\"id\":1}"), Some("{\"id\":1}"));
+    /// assert_eq!(validate_utf8("héllo".as_bytes()), Some("héllo"));
+    /// assert_eq!(validate_utf8(&[0xC0, 0x80]), None);
+    /// ```
+    pub fn validate_utf8(input: &[u8]) -> Option<&str> {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: AVX2 support is checked before the call, and all-ASCII
+        // bytes are always valid UTF-8.
+        if is_x86_feature_detected!("avx2") && unsafe { is_ascii_avx2(input) } {
+            return Some(unsafe { std::str::from_utf8_unchecked(input) });
+        }
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        if is_ascii_simd128(input) {
+            // SAFETY: all-ASCII bytes are always valid UTF-8.
+            return Some(unsafe { std::str::from_utf8_unchecked(input) });
+        }
+        std::str::from_utf8(input).ok()
+    }
+
+    /// Position of the first `needle` byte, 32 bytes at a time.
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx2")]
+    unsafe fn find_byte_avx2(input: &[u8], needle: u8) -> Option<usize> {
+        let len = input.len();
+        let target = _mm256_set1_epi8(needle as i8);
+
+        let mut offset = 0;
+        while offset + 32 <= len {
+            let data = _mm256_loadu_si256(input.as_ptr().add(offset) as *const __m256i);
+            let mask = _mm256_movemask_epi8(_mm256_cmpeq_epi8(data, target));
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += 32;
+        }
+
+        input[offset..]
+            .iter()
+            .position(|&b| b == needle)
+            .map(|i| offset + i)
+    }
+
+    /// Whether every byte is below 0x80, 32 bytes at a time.
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx2")]
+    unsafe fn is_ascii_avx2(input: &[u8]) -> bool {
+        let len = input.len();
+
+        let mut offset = 0;
+        while offset + 32 <= len {
+            let data = _mm256_loadu_si256(input.as_ptr().add(offset) as *const __m256i);
+            // The sign bit of each byte is set exactly for non-ASCII bytes
+            if _mm256_movemask_epi8(data) != 0 {
+                return false;
+            }
+            offset += 32;
+        }
+
+        input[offset..].is_ascii()
+    }
+
+    /// Position of the first `needle` byte, 16 bytes at a time.
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    fn find_byte_simd128(input: &[u8], needle: u8) -> Option<usize> {
+        use std::arch::wasm32::*;
+
+        let target = u8x16_splat(needle);
+        let mut offset = 0;
+        while offset + 16 <= input.len() {
+            // SAFETY: the 16 bytes at `offset` are in bounds, and `v128_load`
+            // accepts unaligned addresses.
+            let data = unsafe { v128_load(input.as_ptr().add(offset).cast()) };
+            let mask = u8x16_bitmask(u8x16_eq(data, target));
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += 16;
+        }
+
+        input[offset..]
+            .iter()
+            .position(|&b| b == needle)
+            .map(|i| offset + i)
+    }
+
+    /// Whether every byte is below 0x80, 16 bytes at a time.
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    fn is_ascii_simd128(input: &[u8]) -> bool {
+        use std::arch::wasm32::*;
+
+        let mut offset = 0;
+        while offset + 16 <= input.len() {
+            // SAFETY: as in `find_byte_simd128`.
+            let data = unsafe { v128_load(input.as_ptr().add(offset).cast()) };
+            // The sign bit of each byte is set exactly for non-ASCII bytes
+            if u8x16_bitmask(data) != 0 {
+                return false;
+            }
+            offset += 16;
+        }
+
+        input[offset..].is_ascii()
+    }
+}
+
 // Fallback implementations for non-x86_64 or when SIMD is not available
 #[cfg(not(all(target_arch = "x86_64", target_feature = "avx2")))]
 /// Fallback implementations for SIMD operations when hardware SIMD is not available.
@@ -627,6 +777,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_framing_scans() {
+        let mut frame = vec![b'a'; 70];
+        frame.extend_from_slice(b"\r\n{}");
+        assert_eq!(framing::find_newline(&frame), Some(71));
+        assert_eq!(framing::find_newline(&frame[..71]), None);
+
+        let mut text = "x".repeat(40);
+        text.push('é');
+        assert_eq!(framing::validate_utf8(text.as_bytes()), Some(text.as_str()));
+        let mut invalid = vec![b'x'; 40];
+        invalid.push(0xFF);
+        assert_eq!(framing::validate_utf8(&invalid), None);
+    }
+
     #[test]
     #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
     fn test_xor_mask() {
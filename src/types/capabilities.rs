@@ -47,6 +47,10 @@ pub struct ClientCapabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub roots: Option<RootsCapabilities>,
 
+    /// Elicitation capabilities (asking the user for input)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elicitation: Option<ElicitationCapabilities>,
+
     /// Experimental capabilities
     #[serde(skip_serializing_if = "Option::is_none")]
     pub experimental: Option<HashMap<String, serde_json::Value>>,
@@ -134,6 +138,11 @@ pub struct SamplingCapabilities {
     pub models: Option<Vec<String>>,
 }
 
+/// Elicitation capabilities.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitationCapabilities {}
+
 /// Roots capabilities.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -229,6 +238,7 @@ impl ClientCapabilities {
             }),
             sampling: Some(SamplingCapabilities::default()),
             roots: Some(RootsCapabilities { list_changed: true }),
+            elicitation: Some(ElicitationCapabilities::default()),
             experimental: None,
         }
     }
@@ -369,6 +379,12 @@ impl ClientCapabilities {
     pub fn supports_sampling(&self) -> bool {
         self.sampling.is_some()
     }
+
+    /// Check if the client can ask its user for input on the server's
+    /// behalf.
+    pub fn supports_elicitation(&self) -> bool {
+        self.elicitation.is_some()
+    }
 }
 
 impl ServerCapabilities {
@@ -519,6 +535,7 @@ impl ServerCapabilities {
     /// #     async fn read(&self, uri: &str, _extra: pmcp::RequestHandlerExtra) -> Result<ReadResourceResult, pmcp::Error> {
     /// #         Ok(ReadResourceResult {
     /// #             contents: vec![Content::Text { text: "File contents".to_string() }],
+    /// #             meta: None,
     /// #         })
     /// #     }
     /// #     async fn list(&self, _path: Option<String>, _extra: pmcp::RequestHandlerExtra) -> Result<ListResourcesResult, pmcp::Error> {
@@ -0,0 +1,365 @@
+//! Helpers for building [`Content`] items.
+//!
+//! Binary content travels base64 encoded with a MIME type. The constructors
+//! here encode the bytes, check that the MIME type matches the kind of
+//! content, and refuse payloads larger than [`MAX_INLINE_CONTENT_SIZE`],
+//! which would bloat every message they appear in; serve such data as a
+//! resource instead and return a [`Content::resource_link`].
+//!
+//! A tool can also return a resource's contents along with its result, as
+//! an embedded resource built from [`ResourceContents`].
+//!
+//! File constructors sniff the MIME type from the file's leading bytes,
+//! falling back to its extension.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::types::{Content, ResourceContents, ResourceLink};
+//!
+//! let png = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+//! let image = Content::image_from_bytes(&png, "image/png").unwrap();
+//! assert!(matches!(image, Content::Image { ref data, .. } if data == "iVBORw0KGgo="));
+//!
+//! // Audio bytes with an image MIME type are rejected.
+//! assert!(Content::audio_from_bytes(&png, "image/png").is_err());
+//!
+//! let link = ResourceLink::new("file:///reports/q3.pdf", "q3.pdf")
+//!     .with_description("Third quarter report")
+//!     .with_mime_type("application/pdf");
+//! let summary = ResourceContents::text("file:///reports/q3.md", "Revenue grew 4%.")
+//!     .with_mime_type("text/markdown");
+//! let result = vec![Content::from(link), Content::from(summary)];
+//! ```
+
+use super::protocol::{Content, ResourceContents, ResourceLink};
+use crate::error::{Error, Result};
+use base64::Engine;
+
+/// Largest payload, in bytes before encoding, the constructors accept.
+pub const MAX_INLINE_CONTENT_SIZE: usize = 10 * 1024 * 1024;
+
+/// Leading bytes identifying common image and audio formats.
+const SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"BM", "image/bmp"),
+    (b"fLaC", "audio/flac"),
+    (b"OggS", "audio/ogg"),
+    (b"ID3", "audio/mpeg"),
+    (b"\xff\xfb", "audio/mpeg"),
+    (b"\xff\xf3", "audio/mpeg"),
+    (b"\xff\xf2", "audio/mpeg"),
+];
+
+/// File extensions of common image and audio formats.
+#[cfg(not(target_arch = "wasm32"))]
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("bmp", "image/bmp"),
+    ("svg", "image/svg+xml"),
+    ("wav", "audio/wav"),
+    ("mp3", "audio/mpeg"),
+    ("ogg", "audio/ogg"),
+    ("flac", "audio/flac"),
+    ("m4a", "audio/mp4"),
+    ("webm", "audio/webm"),
+];
+
+/// Guess the MIME type of image or audio data from its leading bytes.
+pub fn sniff_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    // RIFF containers name their format at offset 8.
+    if bytes.len() >= 12 && &bytes[..4] == b"RIFF" {
+        return match &bytes[8..12] {
+            b"WEBP" => Some("image/webp"),
+            b"WAVE" => Some("audio/wav"),
+            _ => None,
+        };
+    }
+    SIGNATURES
+        .iter()
+        .find(|(signature, _)| bytes.starts_with(signature))
+        .map(|(_, mime_type)| *mime_type)
+}
+
+/// Guess the MIME type of a file from its extension.
+#[cfg(not(target_arch = "wasm32"))]
+fn mime_type_from_extension(path: &std::path::Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    EXTENSIONS
+        .iter()
+        .find(|(known, _)| *known == extension)
+        .map(|(_, mime_type)| *mime_type)
+}
+
+/// Check `bytes` against the size limit and `mime_type` against the
+/// expected kind (`"image"` or `"audio"`), then encode them.
+fn encode(bytes: &[u8], mime_type: &str, kind: &str) -> Result<String> {
+    if mime_type.split('/').next() != Some(kind) {
+        return Err(Error::validation(format!(
+            "MIME type '{}' is not an {} type",
+            mime_type, kind
+        )));
+    }
+    encode_limited(bytes, kind)
+}
+
+/// Check `bytes` against the size limit, then encode them.
+fn encode_limited(bytes: &[u8], kind: &str) -> Result<String> {
+    if bytes.len() > MAX_INLINE_CONTENT_SIZE {
+        return Err(Error::validation(format!(
+            "{} content is {} bytes, more than the {} byte limit",
+            kind,
+            bytes.len(),
+            MAX_INLINE_CONTENT_SIZE
+        )));
+    }
+    Ok(base64::prelude::BASE64_STANDARD.encode(bytes))
+}
+
+/// Read a file no larger than the size limit and work out its MIME type.
+#[cfg(not(target_arch = "wasm32"))]
+async fn read_media_file(path: &std::path::Path) -> Result<(Vec<u8>, String)> {
+    let size = tokio::fs::metadata(path).await?.len();
+    if size > MAX_INLINE_CONTENT_SIZE as u64 {
+        return Err(Error::validation(format!(
+            "'{}' is {} bytes, more than the {} byte limit",
+            path.display(),
+            size,
+            MAX_INLINE_CONTENT_SIZE
+        )));
+    }
+    let bytes = tokio::fs::read(path).await?;
+    let mime_type = sniff_mime_type(&bytes)
+        .or_else(|| mime_type_from_extension(path))
+        .ok_or_else(|| Error::validation(format!("Unknown media type of '{}'", path.display())))?;
+    Ok((bytes, mime_type.to_string()))
+}
+
+impl Content {
+    /// Text content.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text { text: text.into() }
+    }
+
+    /// Image content from raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `mime_type` is not an `image/` type or
+    /// the image exceeds [`MAX_INLINE_CONTENT_SIZE`].
+    pub fn image_from_bytes(bytes: &[u8], mime_type: impl Into<String>) -> Result<Self> {
+        let mime_type = mime_type.into();
+        Ok(Self::Image {
+            data: encode(bytes, &mime_type, "image")?,
+            mime_type,
+        })
+    }
+
+    /// Audio content from raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `mime_type` is not an `audio/` type or
+    /// the audio exceeds [`MAX_INLINE_CONTENT_SIZE`].
+    pub fn audio_from_bytes(bytes: &[u8], mime_type: impl Into<String>) -> Result<Self> {
+        let mime_type = mime_type.into();
+        Ok(Self::Audio {
+            data: encode(bytes, &mime_type, "audio")?,
+            mime_type,
+        })
+    }
+
+    /// Image content read from a file, with its MIME type sniffed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not a recognized
+    /// image, or exceeds [`MAX_INLINE_CONTENT_SIZE`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn image_from_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let (bytes, mime_type) = read_media_file(path.as_ref()).await?;
+        Self::image_from_bytes(&bytes, mime_type)
+    }
+
+    /// Audio content read from a file, with its MIME type sniffed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not recognized
+    /// audio, or exceeds [`MAX_INLINE_CONTENT_SIZE`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn audio_from_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let (bytes, mime_type) = read_media_file(path.as_ref()).await?;
+        Self::audio_from_bytes(&bytes, mime_type)
+    }
+
+    /// A link to the resource at `uri` named `name`, without its contents.
+    ///
+    /// Use [`ResourceLink`] to also give a description or MIME type.
+    pub fn resource_link(uri: impl Into<String>, name: impl Into<String>) -> Self {
+        ResourceLink::new(uri, name).into()
+    }
+
+    /// The contents of a resource, embedded in the message.
+    pub fn embedded_resource(resource: ResourceContents) -> Self {
+        Self::EmbeddedResource { resource }
+    }
+}
+
+impl From<ResourceLink> for Content {
+    fn from(link: ResourceLink) -> Self {
+        Self::ResourceLink(link)
+    }
+}
+
+impl From<ResourceContents> for Content {
+    fn from(resource: ResourceContents) -> Self {
+        Self::embedded_resource(resource)
+    }
+}
+
+impl ResourceLink {
+    /// A link to the resource at `uri` named `name`.
+    pub fn new(uri: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            name: name.into(),
+            description: None,
+            mime_type: None,
+        }
+    }
+
+    /// Describe the linked resource.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the MIME type of the linked resource.
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+}
+
+impl ResourceContents {
+    /// Contents of the text resource at `uri`.
+    pub fn text(uri: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            mime_type: None,
+            text: Some(text.into()),
+            blob: None,
+        }
+    }
+
+    /// Contents of the binary resource at `uri`, from raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the data exceeds
+    /// [`MAX_INLINE_CONTENT_SIZE`].
+    pub fn blob_from_bytes(uri: impl Into<String>, bytes: &[u8]) -> Result<Self> {
+        Ok(Self {
+            uri: uri.into(),
+            mime_type: None,
+            text: None,
+            blob: Some(encode_limited(bytes, "resource")?),
+        })
+    }
+
+    /// Set the MIME type of the contents.
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_mime_type() {
+        assert_eq!(sniff_mime_type(b"\xff\xd8\xff\xe0rest"), Some("image/jpeg"));
+        assert_eq!(sniff_mime_type(b"RIFF\0\0\0\0WAVEfmt "), Some("audio/wav"));
+        assert_eq!(sniff_mime_type(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff_mime_type(b"plain text"), None);
+    }
+
+    #[test]
+    fn test_size_and_kind_guards() {
+        let oversized = vec![0u8; MAX_INLINE_CONTENT_SIZE + 1];
+        let err = Content::image_from_bytes(&oversized, "image/png").unwrap_err();
+        assert!(err.to_string().contains("limit"), "{}", err);
+        assert!(Content::image_from_bytes(b"abc", "audio/wav").is_err());
+
+        let audio = Content::audio_from_bytes(b"abc", "audio/wav").unwrap();
+        assert!(matches!(audio, Content::Audio { ref data, .. } if data == "YWJj"));
+        assert_eq!(
+            serde_json::to_value(&audio).unwrap(),
+            serde_json::json!({"type": "audio", "data": "YWJj", "mimeType": "audio/wav"})
+        );
+    }
+
+    #[test]
+    fn test_resource_builders() {
+        let link = Content::from(
+            ResourceLink::new("file:///q3.pdf", "q3.pdf").with_mime_type("application/pdf"),
+        );
+        assert_eq!(
+            serde_json::to_value(&link).unwrap(),
+            serde_json::json!({
+                "type": "resource_link",
+                "uri": "file:///q3.pdf",
+                "name": "q3.pdf",
+                "mimeType": "application/pdf"
+            })
+        );
+
+        let blob = ResourceContents::blob_from_bytes("file:///a.bin", b"abc")
+            .unwrap()
+            .with_mime_type("application/octet-stream");
+        assert_eq!(
+            serde_json::to_value(Content::from(blob)).unwrap(),
+            serde_json::json!({
+                "type": "resource",
+                "resource": {
+                    "uri": "file:///a.bin",
+                    "mimeType": "application/octet-stream",
+                    "blob": "YWJj"
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_from_file_sniffs_then_falls_back_to_extension() {
+        let dir = std::env::temp_dir().join(format!("pmcp-content-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let sniffed = dir.join("clip.bin");
+        std::fs::write(&sniffed, b"fLaC\0\0\0\x22").unwrap();
+        let content = Content::audio_from_file(&sniffed).await.unwrap();
+        assert!(
+            matches!(content, Content::Audio { ref mime_type, .. } if mime_type == "audio/flac")
+        );
+
+        let by_extension = dir.join("icon.svg");
+        std::fs::write(&by_extension, b"<svg/>").unwrap();
+        let content = Content::image_from_file(&by_extension).await.unwrap();
+        assert!(
+            matches!(content, Content::Image { ref mime_type, .. } if mime_type == "image/svg+xml")
+        );
+
+        // A recognized type of the wrong kind is rejected.
+        assert!(Content::image_from_file(&sniffed).await.is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
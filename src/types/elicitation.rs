@@ -372,6 +372,109 @@ impl ElicitInputBuilder {
     }
 }
 
+/// Parameters of an `elicitation/create` request.
+///
+/// The server asks the user, through the client, for input matching
+/// `requested_schema`: a flat JSON Schema object whose properties are
+/// strings, numbers, booleans or enums.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::types::elicitation::ElicitRequestParams;
+/// use serde_json::json;
+///
+/// let params = ElicitRequestParams::new(
+///     "Which branch should be deployed?",
+///     json!({
+///         "type": "object",
+///         "properties": {"branch": {"type": "string"}},
+///         "required": ["branch"]
+///     }),
+/// );
+/// assert_eq!(
+///     serde_json::to_value(&params).unwrap()["requestedSchema"]["required"],
+///     json!(["branch"])
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitRequestParams {
+    /// Message shown to the user.
+    pub message: String,
+
+    /// Schema the user's response must match.
+    pub requested_schema: Value,
+}
+
+impl ElicitRequestParams {
+    /// Create parameters asking for input matching `requested_schema`.
+    pub fn new(message: impl Into<String>, requested_schema: Value) -> Self {
+        Self {
+            message: message.into(),
+            requested_schema,
+        }
+    }
+}
+
+/// How the user answered an elicitation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ElicitAction {
+    /// The user submitted the requested data.
+    Accept,
+    /// The user explicitly declined to provide it.
+    Decline,
+    /// The user dismissed the request without choosing.
+    Cancel,
+}
+
+/// Result of an `elicitation/create` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitResult {
+    /// How the user answered.
+    pub action: ElicitAction,
+
+    /// Submitted data, present when the user accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<serde_json::Map<String, Value>>,
+}
+
+impl ElicitResult {
+    /// The user accepted and submitted `content`.
+    pub fn accept(content: serde_json::Map<String, Value>) -> Self {
+        Self {
+            action: ElicitAction::Accept,
+            content: Some(content),
+        }
+    }
+
+    /// The user declined.
+    pub fn decline() -> Self {
+        Self {
+            action: ElicitAction::Decline,
+            content: None,
+        }
+    }
+
+    /// The user cancelled.
+    pub fn cancel() -> Self {
+        Self {
+            action: ElicitAction::Cancel,
+            content: None,
+        }
+    }
+
+    /// The submitted data, if the user accepted.
+    pub fn accepted(&self) -> Option<&serde_json::Map<String, Value>> {
+        match self.action {
+            ElicitAction::Accept => self.content.as_ref(),
+            ElicitAction::Decline | ElicitAction::Cancel => None,
+        }
+    }
+}
+
 /// Helper function to create a text input elicitation.
 pub fn elicit_text(prompt: impl Into<String>) -> ElicitInputBuilder {
     ElicitInputBuilder::new(InputType::Text, prompt)
@@ -485,4 +588,16 @@ mod tests {
         let deserialized: ElicitInputRequest = serde_json::from_value(json).unwrap();
         assert_eq!(deserialized.prompt, request.prompt);
     }
+
+    #[test]
+    fn test_elicit_result_serialization() {
+        let result: ElicitResult =
+            serde_json::from_value(json!({"action": "accept", "content": {"branch": "main"}}))
+                .unwrap();
+        assert_eq!(result.accepted().unwrap()["branch"], "main");
+
+        let declined = serde_json::to_value(ElicitResult::decline()).unwrap();
+        assert_eq!(declined, json!({"action": "decline"}));
+        assert!(ElicitResult::cancel().accepted().is_none());
+    }
 }
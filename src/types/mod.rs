@@ -6,6 +6,7 @@
 pub mod auth;
 pub mod capabilities;
 pub mod completable;
+pub mod content;
 pub mod elicitation;
 pub mod jsonrpc;
 pub mod protocol;
@@ -19,9 +20,9 @@ pub use crate::{DEFAULT_PROTOCOL_VERSION, LATEST_PROTOCOL_VERSION, SUPPORTED_PRO
 // Re-export commonly used types
 pub use auth::{AuthInfo, AuthScheme};
 pub use capabilities::{
-    ClientCapabilities, CompletionCapabilities, LoggingCapabilities, PromptCapabilities,
-    ResourceCapabilities, RootsCapabilities, SamplingCapabilities, ServerCapabilities,
-    ToolCapabilities,
+    ClientCapabilities, CompletionCapabilities, ElicitationCapabilities, LoggingCapabilities,
+    PromptCapabilities, ResourceCapabilities, RootsCapabilities, SamplingCapabilities,
+    ServerCapabilities, ToolCapabilities,
 };
 pub use jsonrpc::{JSONRPCError, JSONRPCNotification, JSONRPCRequest, JSONRPCResponse, RequestId};
 pub use protocol::{
@@ -35,6 +36,7 @@ pub use protocol::{
     ListToolsParams, ListToolsRequest, ListToolsResult, LoggingLevel, MessageContent, ModelHint,
     ModelPreferences, Notification, Progress, ProgressNotification, ProgressToken, PromptArgument,
     PromptInfo, PromptMessage, ProtocolVersion, ReadResourceParams, ReadResourceRequest,
-    ReadResourceResult, Request, ResourceInfo, ResourceTemplate, Role, SamplingMessage,
-    ServerNotification, ServerRequest, SubscribeRequest, TokenUsage, ToolInfo, UnsubscribeRequest,
+    ReadResourceResult, Request, ResourceChunk, ResourceContents, ResourceInfo, ResourceLink,
+    ResourceTemplate, Role, SamplingMessage, ServerNotification, ServerRequest, SubscribeRequest,
+    TokenUsage, ToolAnnotations, ToolInfo, UnsubscribeRequest,
 };
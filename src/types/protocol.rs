@@ -98,6 +98,151 @@ pub struct ToolInfo {
     pub description: Option<String>,
     /// JSON Schema for tool parameters
     pub input_schema: Value,
+    /// JSON Schema the tool's `structuredContent` conforms to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<Value>,
+    /// Hints about the tool's behavior
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
+}
+
+impl ToolInfo {
+    /// Whether the tool only reads its environment.
+    pub fn is_read_only(&self) -> bool {
+        self.annotations
+            .as_ref()
+            .is_some_and(ToolAnnotations::is_read_only)
+    }
+
+    /// Whether the tool may destroy data or make irreversible changes.
+    pub fn is_destructive(&self) -> bool {
+        self.annotations
+            .as_ref()
+            .is_none_or(ToolAnnotations::is_destructive)
+    }
+
+    /// Whether repeating a call with the same arguments has no further
+    /// effect.
+    pub fn is_idempotent(&self) -> bool {
+        self.annotations
+            .as_ref()
+            .is_some_and(ToolAnnotations::is_idempotent)
+    }
+
+    /// Whether the tool reaches beyond a closed set of entities, e.g. the
+    /// web.
+    pub fn is_open_world(&self) -> bool {
+        self.annotations
+            .as_ref()
+            .is_none_or(ToolAnnotations::is_open_world)
+    }
+}
+
+/// Hints describing how a tool behaves, for clients to show or gate calls
+/// by.
+///
+/// Hints are advisory: clients must not rely on them for tools from
+/// untrusted servers. Unset hints take the defaults of the specification,
+/// which assume the worst: a tool is destructive and open-world unless it
+/// says otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::types::ToolAnnotations;
+///
+/// let annotations = ToolAnnotations::new().with_read_only(true);
+/// assert!(annotations.is_read_only());
+/// assert!(!annotations.is_destructive());
+///
+/// let json = serde_json::to_value(&annotations).unwrap();
+/// assert_eq!(json, serde_json::json!({"readOnlyHint": true}));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    /// Human-readable title of the tool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The tool does not modify its environment (default `false`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only_hint: Option<bool>,
+    /// The tool may perform destructive updates (default `true`; meaningful
+    /// only when not read-only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destructive_hint: Option<bool>,
+    /// Repeated calls with the same arguments have no additional effect
+    /// (default `false`; meaningful only when not read-only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idempotent_hint: Option<bool>,
+    /// The tool interacts with an open world of external entities (default
+    /// `true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_world_hint: Option<bool>,
+}
+
+impl ToolAnnotations {
+    /// Create annotations with every hint unset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the human-readable title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set whether the tool only reads its environment.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only_hint = Some(read_only);
+        self
+    }
+
+    /// Set whether the tool may perform destructive updates.
+    pub fn with_destructive(mut self, destructive: bool) -> Self {
+        self.destructive_hint = Some(destructive);
+        self
+    }
+
+    /// Set whether repeated calls have no additional effect.
+    pub fn with_idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent_hint = Some(idempotent);
+        self
+    }
+
+    /// Set whether the tool interacts with external entities.
+    pub fn with_open_world(mut self, open_world: bool) -> Self {
+        self.open_world_hint = Some(open_world);
+        self
+    }
+
+    /// Whether no hint and no title is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the tool only reads its environment.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only_hint.unwrap_or(false)
+    }
+
+    /// Whether the tool may perform destructive updates; read-only tools
+    /// never do.
+    pub fn is_destructive(&self) -> bool {
+        !self.is_read_only() && self.destructive_hint.unwrap_or(true)
+    }
+
+    /// Whether repeated calls have no additional effect; read-only tools
+    /// always qualify.
+    pub fn is_idempotent(&self) -> bool {
+        self.is_read_only() || self.idempotent_hint.unwrap_or(false)
+    }
+
+    /// Whether the tool interacts with external entities.
+    pub fn is_open_world(&self) -> bool {
+        self.open_world_hint.unwrap_or(true)
+    }
 }
 
 /// List tools response.
@@ -120,6 +265,59 @@ pub struct CallToolRequest {
     /// Tool arguments (must match input schema)
     #[serde(default)]
     pub arguments: Value,
+    /// Request metadata (e.g. idempotency key)
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+}
+
+impl CallToolRequest {
+    /// Create a call to the named tool.
+    pub fn new(name: impl Into<String>, arguments: Value) -> Self {
+        Self {
+            name: name.into(),
+            arguments,
+            meta: None,
+        }
+    }
+
+    /// Tag the call with an idempotency key.
+    ///
+    /// Servers with idempotency enabled run a tool at most once per key and
+    /// answer redelivered calls with the original result.
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        let meta = self
+            .meta
+            .get_or_insert_with(|| Value::Object(Default::default()));
+        if let Value::Object(map) = meta {
+            map.insert("idempotencyKey".to_string(), Value::String(key.into()));
+        }
+        self
+    }
+
+    /// Get the idempotency key, if any.
+    pub fn idempotency_key(&self) -> Option<&str> {
+        self.meta.as_ref()?.get("idempotencyKey")?.as_str()
+    }
+
+    /// Ask the server to report progress under `token`.
+    pub fn with_progress_token(mut self, token: ProgressToken) -> Self {
+        let meta = self
+            .meta
+            .get_or_insert_with(|| Value::Object(Default::default()));
+        if let Value::Object(map) = meta {
+            map.insert(
+                "progressToken".to_string(),
+                serde_json::to_value(token).unwrap_or(Value::Null),
+            );
+        }
+        self
+    }
+
+    /// Get the progress token, if the caller asked for progress.
+    pub fn progress_token(&self) -> Option<ProgressToken> {
+        let token = self.meta.as_ref()?.get("progressToken")?;
+        serde_json::from_value(token.clone()).ok()
+    }
 }
 
 /// Tool call parameters (legacy name).
@@ -135,6 +333,10 @@ pub struct CallToolResult {
     /// Whether the tool call represents an error
     #[serde(default)]
     pub is_error: bool,
+    /// Machine-readable result object, alongside its text rendering in
+    /// `content`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured_content: Option<Value>,
 }
 
 /// Message content type alias.
@@ -142,7 +344,7 @@ pub type MessageContent = Content;
 
 /// Content item in responses.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "camelCase")]
+#[serde(tag = "type", rename_all = "camelCase", from = "ContentRepr")]
 pub enum Content {
     /// Text content
     #[serde(rename_all = "camelCase")]
@@ -158,6 +360,14 @@ pub enum Content {
         /// MIME type (e.g., "image/png")
         mime_type: String,
     },
+    /// Audio content
+    #[serde(rename_all = "camelCase")]
+    Audio {
+        /// Base64-encoded audio data
+        data: String,
+        /// MIME type (e.g., "audio/wav")
+        mime_type: String,
+    },
     /// Resource reference
     #[serde(rename_all = "camelCase")]
     Resource {
@@ -170,6 +380,114 @@ pub enum Content {
         #[serde(skip_serializing_if = "Option::is_none")]
         mime_type: Option<String>,
     },
+    /// Link to a resource the client can read itself
+    #[serde(rename = "resource_link")]
+    ResourceLink(ResourceLink),
+    /// Resource contents embedded in the message
+    #[serde(rename = "resource")]
+    EmbeddedResource {
+        /// The embedded contents
+        resource: ResourceContents,
+    },
+}
+
+/// Link to a resource, as returned in tool results.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLink {
+    /// Resource URI
+    pub uri: String,
+    /// Resource name
+    pub name: String,
+    /// Resource description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// MIME type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Contents of a resource embedded in a message.
+///
+/// Text resources carry `text`, binary ones base64-encoded `blob`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceContents {
+    /// Resource URI
+    pub uri: String,
+    /// MIME type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Text of a text resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Base64-encoded data of a binary resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+/// Wire form of [`Content`].
+///
+/// Embedded resources and resource references share the `resource` type and
+/// are told apart by whether they carry a `resource` field.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum ContentRepr {
+    Text {
+        text: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Image {
+        data: String,
+        mime_type: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Audio {
+        data: String,
+        mime_type: String,
+    },
+    Resource(ResourceRepr),
+    #[serde(rename = "resource_link")]
+    ResourceLink(ResourceLink),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ResourceRepr {
+    Embedded {
+        resource: ResourceContents,
+    },
+    #[serde(rename_all = "camelCase")]
+    Reference {
+        uri: String,
+        #[serde(default)]
+        text: Option<String>,
+        #[serde(default)]
+        mime_type: Option<String>,
+    },
+}
+
+impl From<ContentRepr> for Content {
+    fn from(repr: ContentRepr) -> Self {
+        match repr {
+            ContentRepr::Text { text } => Self::Text { text },
+            ContentRepr::Image { data, mime_type } => Self::Image { data, mime_type },
+            ContentRepr::Audio { data, mime_type } => Self::Audio { data, mime_type },
+            ContentRepr::Resource(ResourceRepr::Embedded { resource }) => {
+                Self::EmbeddedResource { resource }
+            },
+            ContentRepr::Resource(ResourceRepr::Reference {
+                uri,
+                text,
+                mime_type,
+            }) => Self::Resource {
+                uri,
+                text,
+                mime_type,
+            },
+            ContentRepr::ResourceLink(link) => Self::ResourceLink(link),
+        }
+    }
 }
 
 /// List prompts request.
@@ -328,6 +646,75 @@ pub struct ListResourcesResult {
 pub struct ReadResourceRequest {
     /// Resource URI
     pub uri: String,
+    /// Request metadata (e.g. conditional read headers)
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+}
+
+impl ReadResourceRequest {
+    /// Create a read request for a URI.
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            meta: None,
+        }
+    }
+
+    /// Make the read conditional on the resource having changed from `etag`.
+    pub fn with_if_none_match(mut self, etag: impl Into<String>) -> Self {
+        let meta = self
+            .meta
+            .get_or_insert_with(|| Value::Object(Default::default()));
+        if let Value::Object(map) = meta {
+            map.insert("ifNoneMatch".to_string(), Value::String(etag.into()));
+        }
+        self
+    }
+
+    /// Get the entity tag the client already holds, if any.
+    pub fn if_none_match(&self) -> Option<&str> {
+        self.meta.as_ref()?.get("ifNoneMatch")?.as_str()
+    }
+
+    /// Request only a chunk of the resource contents.
+    pub fn with_chunk(mut self, offset: u64, length: u64) -> Self {
+        let meta = self
+            .meta
+            .get_or_insert_with(|| Value::Object(Default::default()));
+        if let Value::Object(map) = meta {
+            let chunk = ResourceChunk {
+                offset,
+                length,
+                total: None,
+            };
+            map.insert(
+                "chunk".to_string(),
+                serde_json::to_value(chunk).unwrap_or(Value::Null),
+            );
+        }
+        self
+    }
+
+    /// Get the requested chunk, if this is a chunked read.
+    pub fn chunk(&self) -> Option<ResourceChunk> {
+        serde_json::from_value(self.meta.as_ref()?.get("chunk")?.clone()).ok()
+    }
+}
+
+/// A byte range of resource contents used for chunked reads.
+///
+/// Offsets and lengths count bytes of the transmitted representation: UTF-8
+/// bytes for text contents and base64 characters for binary contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceChunk {
+    /// Start of the chunk
+    pub offset: u64,
+    /// Length of the chunk
+    pub length: u64,
+    /// Total length of the contents (set on results)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
 }
 
 /// Read resource params (legacy name).
@@ -467,6 +854,38 @@ pub enum LoggingLevel {
 pub struct ReadResourceResult {
     /// Resource contents
     pub contents: Vec<Content>,
+    /// Result metadata (e.g. cache validators)
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+}
+
+impl ReadResourceResult {
+    /// Get the entity tag identifying this version of the resource.
+    pub fn etag(&self) -> Option<&str> {
+        self.meta.as_ref()?.get("etag")?.as_str()
+    }
+
+    /// Get the RFC 3339 timestamp of the last change to the resource.
+    pub fn last_modified(&self) -> Option<&str> {
+        self.meta.as_ref()?.get("lastModified")?.as_str()
+    }
+
+    /// Get the chunk returned by a chunked read.
+    pub fn chunk(&self) -> Option<ResourceChunk> {
+        serde_json::from_value(self.meta.as_ref()?.get("chunk")?.clone()).ok()
+    }
+
+    /// Check whether the server reported the resource as unchanged.
+    ///
+    /// Not-modified results carry no contents; the client should reuse the
+    /// copy it already holds for the same entity tag.
+    pub fn is_not_modified(&self) -> bool {
+        self.meta
+            .as_ref()
+            .and_then(|meta| meta.get("notModified"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
 }
 
 /// Model preferences for sampling.
@@ -589,6 +1008,9 @@ pub enum ServerRequest {
     /// Elicit input from user
     #[serde(rename = "elicitation/elicitInput")]
     ElicitInput(Box<crate::types::elicitation::ElicitInputRequest>),
+    /// Ask the user for structured input
+    #[serde(rename = "elicitation/create")]
+    Elicit(Box<crate::types::elicitation::ElicitRequestParams>),
 }
 
 /// Create message parameters (for server requests).
@@ -620,6 +1042,22 @@ pub struct CreateMessageParams {
     pub metadata: Option<Value>,
 }
 
+impl CreateMessageParams {
+    /// Create parameters sampling from `messages` with default options.
+    pub fn new(messages: Vec<SamplingMessage>) -> Self {
+        Self {
+            messages,
+            model_preferences: None,
+            system_prompt: None,
+            include_context: IncludeContext::default(),
+            temperature: None,
+            max_tokens: None,
+            stop_sequences: None,
+            metadata: None,
+        }
+    }
+}
+
 /// Create message request (for client requests).
 pub type CreateMessageRequest = CreateMessageParams;
 
@@ -826,6 +1264,30 @@ mod tests {
         assert_eq!(json["text"], "Hello");
     }
 
+    #[test]
+    fn tool_annotations_apply_spec_defaults() {
+        let tool: ToolInfo = serde_json::from_value(json!({
+            "name": "rm",
+            "inputSchema": {"type": "object"}
+        }))
+        .unwrap();
+        assert!(!tool.is_read_only());
+        assert!(tool.is_destructive());
+        assert!(!tool.is_idempotent());
+        assert!(tool.is_open_world());
+
+        let tool: ToolInfo = serde_json::from_value(json!({
+            "name": "put",
+            "inputSchema": {"type": "object"},
+            "annotations": {"title": "Put", "destructiveHint": false, "idempotentHint": true, "openWorldHint": false}
+        }))
+        .unwrap();
+        assert!(!tool.is_destructive());
+        assert!(tool.is_idempotent());
+        assert!(!tool.is_open_world());
+        assert_eq!(tool.annotations.unwrap().title.as_deref(), Some("Put"));
+    }
+
     #[test]
     fn tool_info_serialization() {
         let tool = ToolInfo {
@@ -837,6 +1299,8 @@ mod tests {
                     "param": {"type": "string"}
                 }
             }),
+            output_schema: None,
+            annotations: None,
         };
 
         let json = serde_json::to_value(&tool).unwrap();
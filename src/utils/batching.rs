@@ -1,9 +1,11 @@
 //! Message batching and debouncing utilities.
 
 use crate::error::Result;
+use crate::shared::{create_notification, TransportMessage};
 use crate::types::Notification;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::Duration;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::sync::{mpsc, Mutex};
@@ -464,6 +466,184 @@ impl MessageDebouncer {
     }
 }
 
+/// Method of the progress notifications debounced by default.
+#[cfg_attr(
+    not(any(feature = "websocket", feature = "streamable-http")),
+    allow(dead_code)
+)]
+const PROGRESS_METHOD: &str = "notifications/progress";
+
+/// Write-side batching of a transport's outgoing messages.
+///
+/// Messages queued while the transport is busy writing go out together with
+/// a single flush, so nothing is held back while the connection keeps up.
+/// Beyond that:
+///
+/// - `batching.max_batch_size` caps the messages written per flush, and a
+///   batch made only of notifications among `batching.batched_methods` (all
+///   notifications if empty) waits up to `batching.max_wait_time` for more.
+/// - Notifications with a method in `debouncing.debounced_methods` (progress
+///   notifications, waiting `debouncing.wait_time`, if empty) wait up to
+///   their method's time for newer ones, and only the latest per progress
+///   token or resource URI is written.
+///
+/// Responses and requests are never held back.
+#[derive(Debug, Clone, Default)]
+pub struct WriteBatchingConfig {
+    /// Grouping of queued messages
+    pub batching: BatchingConfig,
+    /// Coalescing of superseded notifications
+    pub debouncing: DebouncingConfig,
+}
+
+/// Source of queued outgoing messages.
+#[cfg_attr(
+    not(any(feature = "websocket", feature = "streamable-http")),
+    allow(dead_code)
+)]
+pub(crate) trait MessageSource {
+    /// Take a message that is already queued.
+    fn try_take(&mut self) -> Option<TransportMessage>;
+
+    /// Poll for the next message; `None` once the queue is closed.
+    fn poll_take(&mut self, cx: &mut Context<'_>) -> Poll<Option<TransportMessage>>;
+}
+
+impl MessageSource for mpsc::Receiver<TransportMessage> {
+    fn try_take(&mut self) -> Option<TransportMessage> {
+        self.try_recv().ok()
+    }
+
+    fn poll_take(&mut self, cx: &mut Context<'_>) -> Poll<Option<TransportMessage>> {
+        self.poll_recv(cx)
+    }
+}
+
+impl MessageSource for mpsc::UnboundedReceiver<TransportMessage> {
+    fn try_take(&mut self) -> Option<TransportMessage> {
+        self.try_recv().ok()
+    }
+
+    fn poll_take(&mut self, cx: &mut Context<'_>) -> Poll<Option<TransportMessage>> {
+        self.poll_recv(cx)
+    }
+}
+
+/// Groups a transport's outgoing messages as set out in
+/// [`WriteBatchingConfig`].
+#[cfg_attr(
+    not(any(feature = "websocket", feature = "streamable-http")),
+    allow(dead_code)
+)]
+#[derive(Debug, Clone)]
+pub(crate) struct WriteBatcher {
+    config: WriteBatchingConfig,
+}
+
+#[cfg_attr(
+    not(any(feature = "websocket", feature = "streamable-http")),
+    allow(dead_code)
+)]
+impl WriteBatcher {
+    pub(crate) fn new(config: WriteBatchingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Collect the batch starting with `first` from `source`, coalesced.
+    pub(crate) async fn gather<S: MessageSource>(
+        &self,
+        first: TransportMessage,
+        source: &mut S,
+    ) -> Vec<TransportMessage> {
+        let started = tokio::time::Instant::now();
+        let max = self.config.batching.max_batch_size.max(1);
+        let mut batch = vec![first];
+        loop {
+            while batch.len() < max {
+                match source.try_take() {
+                    Some(message) => batch.push(message),
+                    None => break,
+                }
+            }
+            if batch.len() >= max {
+                break;
+            }
+            let Some(linger) = self.linger(&batch) else {
+                break;
+            };
+            let next = std::future::poll_fn(|cx| source.poll_take(cx));
+            match tokio::time::timeout_at(started + linger, next).await {
+                Ok(Some(message)) => batch.push(message),
+                Ok(None) | Err(_) => break,
+            }
+        }
+        self.coalesce(batch)
+    }
+
+    /// How long `batch` may wait for more messages, or `None` if it holds
+    /// a message that must be written at once.
+    fn linger(&self, batch: &[TransportMessage]) -> Option<Duration> {
+        let mut linger = Duration::ZERO;
+        for message in batch {
+            let TransportMessage::Notification(notification) = message else {
+                return None;
+            };
+            let method = create_notification(notification.clone()).method;
+            let wait = match self.debounce_wait(&method) {
+                Some(wait) => wait,
+                None if self.is_batched(&method) => self.config.batching.max_wait_time,
+                None => return None,
+            };
+            linger = linger.max(wait);
+        }
+        (!linger.is_zero()).then_some(linger)
+    }
+
+    /// Whether notifications of `method` wait for others.
+    fn is_batched(&self, method: &str) -> bool {
+        let methods = &self.config.batching.batched_methods;
+        methods.is_empty() || methods.iter().any(|m| m == method)
+    }
+
+    /// How long notifications of `method` wait for newer ones, if they are
+    /// debounced.
+    fn debounce_wait(&self, method: &str) -> Option<Duration> {
+        let debouncing = &self.config.debouncing;
+        if debouncing.debounced_methods.is_empty() {
+            (method == PROGRESS_METHOD).then_some(debouncing.wait_time)
+        } else {
+            debouncing.debounced_methods.get(method).copied()
+        }
+    }
+
+    /// Drop debounced notifications superseded by a later one in `batch`.
+    fn coalesce(&self, batch: Vec<TransportMessage>) -> Vec<TransportMessage> {
+        let mut seen = std::collections::HashSet::new();
+        let mut kept: Vec<_> = batch
+            .into_iter()
+            .rev()
+            .filter(|message| {
+                let TransportMessage::Notification(notification) = message else {
+                    return true;
+                };
+                let notification = create_notification(notification.clone());
+                if self.debounce_wait(&notification.method).is_none() {
+                    return true;
+                }
+                let subject = notification.params.as_ref().and_then(|params| {
+                    params
+                        .get("progressToken")
+                        .or_else(|| params.get("uri"))
+                        .map(ToString::to_string)
+                });
+                seen.insert((notification.method, subject))
+            })
+            .collect();
+        kept.reverse();
+        kept
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -520,4 +700,107 @@ mod tests {
             _ => panic!("Wrong notification received"),
         }
     }
+
+    fn progress(token: &str, progress: f64) -> TransportMessage {
+        TransportMessage::Notification(Notification::Server(
+            crate::types::ServerNotification::Progress(crate::types::ProgressNotification {
+                progress_token: crate::types::ProgressToken::String(token.to_string()),
+                progress,
+                message: None,
+            }),
+        ))
+    }
+
+    fn progress_of(message: &TransportMessage) -> (String, f64) {
+        match message {
+            TransportMessage::Notification(Notification::Server(
+                crate::types::ServerNotification::Progress(params),
+            )) => match &params.progress_token {
+                crate::types::ProgressToken::String(token) => (token.clone(), params.progress),
+                crate::types::ProgressToken::Number(token) => (token.to_string(), params.progress),
+            },
+            other => panic!("expected progress, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_batcher_coalesces_queued_progress() {
+        let batcher = WriteBatcher::new(WriteBatchingConfig {
+            debouncing: DebouncingConfig {
+                wait_time: Duration::ZERO,
+                debounced_methods: HashMap::new(),
+            },
+            batching: BatchingConfig {
+                max_wait_time: Duration::ZERO,
+                ..Default::default()
+            },
+        });
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        for message in [
+            progress("a", 10.0),
+            progress("b", 10.0),
+            progress("a", 20.0),
+        ] {
+            tx.send(message).unwrap();
+        }
+        tx.send(TransportMessage::Response(
+            crate::types::JSONRPCResponse::success(
+                crate::types::RequestId::from(1i64),
+                serde_json::json!({}),
+            ),
+        ))
+        .unwrap();
+
+        let first = rx.recv().await.unwrap();
+        let batch = batcher.gather(first, &mut rx).await;
+        assert_eq!(batch.len(), 3);
+        // The earlier update for "a" is superseded; "b" keeps its place.
+        assert_eq!(progress_of(&batch[0]), ("b".to_string(), 10.0));
+        assert_eq!(progress_of(&batch[1]), ("a".to_string(), 20.0));
+        assert!(matches!(batch[2], TransportMessage::Response(_)));
+    }
+
+    #[tokio::test]
+    async fn test_write_batcher_lingers_only_for_notifications() {
+        let batcher = WriteBatcher::new(WriteBatchingConfig {
+            batching: BatchingConfig {
+                max_batch_size: 2,
+                max_wait_time: Duration::from_secs(5),
+                batched_methods: vec![],
+            },
+            ..Default::default()
+        });
+        let (tx, mut rx) = mpsc::channel(8);
+
+        // A notification waits for company, up to the batch size.
+        let sender = tx.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(20)).await;
+            let _ = sender.send(progress("a", 1.0)).await;
+        });
+        let batch = batcher
+            .gather(
+                TransportMessage::Notification(Notification::Client(
+                    ClientNotification::RootsListChanged,
+                )),
+                &mut rx,
+            )
+            .await;
+        assert_eq!(batch.len(), 2);
+
+        // A response is written at once.
+        let started = std::time::Instant::now();
+        let batch = batcher
+            .gather(
+                TransportMessage::Response(crate::types::JSONRPCResponse::success(
+                    crate::types::RequestId::from(1i64),
+                    serde_json::json!({}),
+                )),
+                &mut rx,
+            )
+            .await;
+        assert_eq!(batch.len(), 1);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        drop(tx);
+    }
 }
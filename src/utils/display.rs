@@ -0,0 +1,476 @@
+//! Human-readable rendering of protocol types.
+//!
+//! CLI clients and test tools often need to show tools, tool results, and
+//! capabilities to a person. The wrappers in this module implement
+//! [`fmt::Display`] for those types; obtain them through the `display()`
+//! methods, in the spirit of [`std::path::Path::display`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::types::ToolInfo;
+//! use serde_json::json;
+//!
+//! let tool = ToolInfo {
+//!     name: "search".to_string(),
+//!     description: Some("Search the index".to_string()),
+//!     input_schema: json!({
+//!         "type": "object",
+//!         "properties": {
+//!             "query": { "type": "string", "description": "Search terms" },
+//!             "limit": { "type": "integer", "default": 10 }
+//!         },
+//!         "required": ["query"]
+//!     }),
+//!     output_schema: None,
+//!     annotations: None,
+//! };
+//!
+//! let rendered = tool.display().to_string();
+//! assert!(rendered.starts_with("search - Search the index"));
+//! assert!(rendered.contains("query"));
+//! ```
+
+use crate::types::{CallToolResult, ClientCapabilities, Content, ServerCapabilities, ToolInfo};
+use serde_json::Value;
+use std::fmt;
+
+/// Maximum number of characters of inline JSON shown for defaults and enums.
+const MAX_INLINE_JSON: usize = 40;
+
+/// Displays a [`ToolInfo`] with a table of its parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct ToolDisplay<'a>(&'a ToolInfo);
+
+/// Displays the contents of a [`CallToolResult`].
+#[derive(Debug, Clone, Copy)]
+pub struct CallToolResultDisplay<'a>(&'a CallToolResult);
+
+/// Displays the features advertised in [`ServerCapabilities`].
+#[derive(Debug, Clone, Copy)]
+pub struct ServerCapabilitiesDisplay<'a>(&'a ServerCapabilities);
+
+/// Displays the features advertised in [`ClientCapabilities`].
+#[derive(Debug, Clone, Copy)]
+pub struct ClientCapabilitiesDisplay<'a>(&'a ClientCapabilities);
+
+impl ToolInfo {
+    /// Get a human-readable rendering of the tool and its parameters.
+    pub fn display(&self) -> ToolDisplay<'_> {
+        ToolDisplay(self)
+    }
+}
+
+impl CallToolResult {
+    /// Get a human-readable rendering of the result contents.
+    pub fn display(&self) -> CallToolResultDisplay<'_> {
+        CallToolResultDisplay(self)
+    }
+}
+
+impl ServerCapabilities {
+    /// Get a human-readable list of the advertised capabilities.
+    pub fn display(&self) -> ServerCapabilitiesDisplay<'_> {
+        ServerCapabilitiesDisplay(self)
+    }
+}
+
+impl ClientCapabilities {
+    /// Get a human-readable list of the advertised capabilities.
+    pub fn display(&self) -> ClientCapabilitiesDisplay<'_> {
+        ClientCapabilitiesDisplay(self)
+    }
+}
+
+impl fmt::Display for ToolDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tool = self.0;
+        match &tool.description {
+            Some(description) => writeln!(f, "{} - {}", tool.name, description)?,
+            None => writeln!(f, "{}", tool.name)?,
+        }
+
+        let properties = match tool.input_schema.get("properties") {
+            Some(Value::Object(properties)) if !properties.is_empty() => properties,
+            _ => return write!(f, "  (no parameters)"),
+        };
+        let required: Vec<&str> = tool
+            .input_schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let rows: Vec<[String; 4]> = properties
+            .iter()
+            .map(|(name, schema)| {
+                [
+                    name.clone(),
+                    schema_type(schema),
+                    if required.contains(&name.as_str()) {
+                        "yes".to_string()
+                    } else {
+                        "no".to_string()
+                    },
+                    schema_notes(schema),
+                ]
+            })
+            .collect();
+        write_table(f, ["NAME", "TYPE", "REQUIRED", "DESCRIPTION"], &rows)
+    }
+}
+
+impl fmt::Display for CallToolResultDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = self.0;
+        if result.is_error {
+            writeln!(f, "Error:")?;
+        }
+        if result.content.is_empty() {
+            return write!(f, "(no content)");
+        }
+
+        for (i, content) in result.content.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            match content {
+                Content::Text { text } => write!(f, "{}", text)?,
+                Content::Image { data, mime_type } => write!(
+                    f,
+                    "[image: {}, {} bytes]",
+                    mime_type,
+                    base64_decoded_len(data)
+                )?,
+                Content::Audio { data, mime_type } => write!(
+                    f,
+                    "[audio: {}, {} bytes]",
+                    mime_type,
+                    base64_decoded_len(data)
+                )?,
+                Content::Resource {
+                    uri,
+                    text,
+                    mime_type,
+                } => {
+                    match mime_type {
+                        Some(mime_type) => write!(f, "[resource: {} ({})]", uri, mime_type)?,
+                        None => write!(f, "[resource: {}]", uri)?,
+                    }
+                    if let Some(text) = text {
+                        for line in text.lines() {
+                            write!(f, "\n  {}", line)?;
+                        }
+                    }
+                },
+                Content::ResourceLink(link) => match &link.mime_type {
+                    Some(mime_type) => {
+                        write!(f, "[link: {} {} ({})]", link.name, link.uri, mime_type)?
+                    },
+                    None => write!(f, "[link: {} {}]", link.name, link.uri)?,
+                },
+                Content::EmbeddedResource { resource } => {
+                    match &resource.mime_type {
+                        Some(mime_type) => {
+                            write!(f, "[resource: {} ({})]", resource.uri, mime_type)?
+                        },
+                        None => write!(f, "[resource: {}]", resource.uri)?,
+                    }
+                    if let Some(text) = &resource.text {
+                        for line in text.lines() {
+                            write!(f, "\n  {}", line)?;
+                        }
+                    } else if let Some(blob) = &resource.blob {
+                        write!(f, "\n  {} bytes", base64_decoded_len(blob))?;
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ServerCapabilitiesDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let caps = self.0;
+        let mut lines = Vec::new();
+        if let Some(tools) = &caps.tools {
+            lines.push(capability("tools", &[("listChanged", tools.list_changed)]));
+        }
+        if let Some(prompts) = &caps.prompts {
+            lines.push(capability(
+                "prompts",
+                &[("listChanged", prompts.list_changed)],
+            ));
+        }
+        if let Some(resources) = &caps.resources {
+            lines.push(capability(
+                "resources",
+                &[
+                    ("subscribe", resources.subscribe),
+                    ("listChanged", resources.list_changed),
+                ],
+            ));
+        }
+        if let Some(logging) = &caps.logging {
+            lines.push(list_capability("logging", logging.levels.as_deref()));
+        }
+        if caps.completions.is_some() {
+            lines.push("completions".to_string());
+        }
+        if let Some(sampling) = &caps.sampling {
+            lines.push(list_capability("sampling", sampling.models.as_deref()));
+        }
+        lines.extend(experimental(caps.experimental.as_ref()));
+        write_lines(f, &lines)
+    }
+}
+
+impl fmt::Display for ClientCapabilitiesDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let caps = self.0;
+        let mut lines = Vec::new();
+        if let Some(tools) = &caps.tools {
+            lines.push(capability("tools", &[("listChanged", tools.list_changed)]));
+        }
+        if let Some(prompts) = &caps.prompts {
+            lines.push(capability(
+                "prompts",
+                &[("listChanged", prompts.list_changed)],
+            ));
+        }
+        if let Some(resources) = &caps.resources {
+            lines.push(capability(
+                "resources",
+                &[
+                    ("subscribe", resources.subscribe),
+                    ("listChanged", resources.list_changed),
+                ],
+            ));
+        }
+        if let Some(logging) = &caps.logging {
+            lines.push(list_capability("logging", logging.levels.as_deref()));
+        }
+        if let Some(sampling) = &caps.sampling {
+            lines.push(list_capability("sampling", sampling.models.as_deref()));
+        }
+        if let Some(roots) = &caps.roots {
+            lines.push(capability(
+                "roots",
+                &[("listChanged", Some(roots.list_changed))],
+            ));
+        }
+        lines.extend(experimental(caps.experimental.as_ref()));
+        write_lines(f, &lines)
+    }
+}
+
+/// Render a schema's type, e.g. `string`, `array<integer>`, or `string|null`.
+fn schema_type(schema: &Value) -> String {
+    let base = match schema.get("type") {
+        Some(Value::String(ty)) => ty.clone(),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join("|"),
+        _ if schema.get("enum").is_some() => "enum".to_string(),
+        _ if schema.get("anyOf").is_some() || schema.get("oneOf").is_some() => "union".to_string(),
+        _ => "any".to_string(),
+    };
+
+    match schema.get("items") {
+        Some(items) if base == "array" => format!("array<{}>", schema_type(items)),
+        _ => base,
+    }
+}
+
+/// Render the description of a schema along with its default and allowed values.
+fn schema_notes(schema: &Value) -> String {
+    let mut notes = schema
+        .get("description")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let mut append = |label: &str, value: &Value| {
+        if !notes.is_empty() {
+            notes.push(' ');
+        }
+        notes.push_str(&format!("({}: {})", label, inline_json(value)));
+    };
+    if let Some(values) = schema.get("enum") {
+        append("one of", values);
+    }
+    if let Some(default) = schema.get("default") {
+        append("default", default);
+    }
+    notes
+}
+
+fn inline_json(value: &Value) -> String {
+    let json = value.to_string();
+    if json.chars().count() <= MAX_INLINE_JSON {
+        return json;
+    }
+    let truncated: String = json.chars().take(MAX_INLINE_JSON - 3).collect();
+    format!("{}...", truncated)
+}
+
+fn base64_decoded_len(data: &str) -> usize {
+    let padding = data.bytes().rev().take_while(|&b| b == b'=').count();
+    (data.len() / 4 * 3).saturating_sub(padding)
+}
+
+fn capability(name: &str, flags: &[(&str, Option<bool>)]) -> String {
+    let enabled: Vec<&str> = flags
+        .iter()
+        .filter(|(_, value)| value.unwrap_or(false))
+        .map(|(flag, _)| *flag)
+        .collect();
+    if enabled.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} ({})", name, enabled.join(", "))
+    }
+}
+
+fn list_capability(name: &str, values: Option<&[String]>) -> String {
+    match values {
+        Some(values) if !values.is_empty() => format!("{} ({})", name, values.join(", ")),
+        _ => name.to_string(),
+    }
+}
+
+fn experimental(experimental: Option<&std::collections::HashMap<String, Value>>) -> Option<String> {
+    let experimental = experimental.filter(|map| !map.is_empty())?;
+    let mut names: Vec<&str> = experimental.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    Some(format!("experimental ({})", names.join(", ")))
+}
+
+fn write_lines(f: &mut fmt::Formatter<'_>, lines: &[String]) -> fmt::Result {
+    if lines.is_empty() {
+        return write!(f, "(none)");
+    }
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+        write!(f, "- {}", line)?;
+    }
+    Ok(())
+}
+
+fn write_table(f: &mut fmt::Formatter<'_>, header: [&str; 4], rows: &[[String; 4]]) -> fmt::Result {
+    let mut widths = header.map(|cell| cell.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let header = header.map(str::to_string);
+    for (i, row) in std::iter::once(&header).chain(rows).enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        write!(f, "  {}", line.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::capabilities::{LoggingCapabilities, ResourceCapabilities};
+    use serde_json::json;
+
+    #[test]
+    fn test_tool_display_renders_parameter_table() {
+        let tool = ToolInfo {
+            name: "search".to_string(),
+            description: Some("Search the index".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Search terms" },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                    "sort": { "enum": ["asc", "desc"], "default": "asc" }
+                },
+                "required": ["query"]
+            }),
+            output_schema: None,
+            annotations: None,
+        };
+
+        let expected = "\
+search - Search the index
+  NAME   TYPE           REQUIRED  DESCRIPTION
+  query  string         yes       Search terms
+  tags   array<string>  no
+  sort   enum           no        (one of: [\"asc\",\"desc\"]) (default: \"asc\")";
+        assert_eq!(tool.display().to_string(), expected);
+
+        let bare = ToolInfo {
+            name: "ping".to_string(),
+            description: None,
+            input_schema: json!({ "type": "object" }),
+            output_schema: None,
+            annotations: None,
+        };
+        assert_eq!(bare.display().to_string(), "ping\n  (no parameters)");
+    }
+
+    #[test]
+    fn test_call_tool_result_display() {
+        let result = CallToolResult {
+            content: vec![
+                Content::Text {
+                    text: "done".to_string(),
+                },
+                Content::Image {
+                    data: "aGVsbG8=".to_string(),
+                    mime_type: "image/png".to_string(),
+                },
+                Content::Resource {
+                    uri: "file:///a.txt".to_string(),
+                    text: Some("line 1\nline 2".to_string()),
+                    mime_type: Some("text/plain".to_string()),
+                },
+            ],
+            is_error: true,
+            structured_content: None,
+        };
+        assert_eq!(
+            result.display().to_string(),
+            "Error:\ndone\n[image: image/png, 5 bytes]\n[resource: file:///a.txt (text/plain)]\n  line 1\n  line 2"
+        );
+    }
+
+    #[test]
+    fn test_capabilities_display() {
+        let server = ServerCapabilities {
+            resources: Some(ResourceCapabilities {
+                subscribe: Some(true),
+                list_changed: Some(false),
+            }),
+            logging: Some(LoggingCapabilities {
+                levels: Some(vec!["info".to_string(), "error".to_string()]),
+            }),
+            ..ServerCapabilities::tools_only()
+        };
+        assert_eq!(
+            server.display().to_string(),
+            "- tools (listChanged)\n- resources (subscribe)\n- logging (info, error)"
+        );
+        assert_eq!(
+            ClientCapabilities::default().display().to_string(),
+            "(none)"
+        );
+    }
+}
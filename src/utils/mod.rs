@@ -2,6 +2,7 @@
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod batching;
+pub mod display;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod parallel_batch;
 pub mod validation;
@@ -11,7 +12,9 @@ pub mod validation;
 pub mod json_simd;
 
 #[cfg(not(target_arch = "wasm32"))]
-pub use batching::{BatchingConfig, DebouncingConfig, MessageBatcher, MessageDebouncer};
+pub use batching::{
+    BatchingConfig, DebouncingConfig, MessageBatcher, MessageDebouncer, WriteBatchingConfig,
+};
 #[cfg(not(target_arch = "wasm32"))]
 pub use parallel_batch::{
     process_batch_parallel, process_batch_parallel_stateful, BatchProcessor, ParallelBatchConfig,
@@ -1,3 +1,250 @@
 //! Validation utilities for MCP protocol.
 //!
-//! This module is reserved for future validation functionality.
+//! [`RequestGuard`] bounds the shape of incoming request params (nesting
+//! depth, string lengths, and array sizes) so that a hostile peer cannot make
+//! a public server spend unbounded time or memory deserializing them into
+//! handler types. Install one on a server with
+//! [`ServerBuilder::request_guard`](crate::ServerBuilder::request_guard).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmcp::utils::validation::RequestGuard;
+//! use serde_json::json;
+//!
+//! let guard = RequestGuard::new()
+//!     .with_max_depth(4)
+//!     .with_max_string_length(16)
+//!     .with_max_array_length(3);
+//!
+//! assert!(guard.check(&json!({"tags": ["a", "b"]})).is_ok());
+//!
+//! let err = guard.check(&json!({"tags": ["a", "b", "c", "d"]})).unwrap_err();
+//! assert!(err.to_string().contains("params/tags"));
+//! ```
+
+use crate::error::{Error, Result};
+use serde_json::Value;
+use std::fmt::Write as _;
+
+/// Default maximum nesting depth of params.
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// Default maximum length of a string, in bytes.
+pub const DEFAULT_MAX_STRING_LENGTH: usize = 1024 * 1024;
+
+/// Default maximum number of elements in an array.
+pub const DEFAULT_MAX_ARRAY_LENGTH: usize = 10_000;
+
+/// Limits on the shape of incoming request params.
+///
+/// Violations are reported as `INVALID_PARAMS` errors naming the offending
+/// location, e.g. `params/items/3/name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestGuard {
+    max_depth: usize,
+    max_string_length: usize,
+    max_array_length: usize,
+}
+
+impl Default for RequestGuard {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_string_length: DEFAULT_MAX_STRING_LENGTH,
+            max_array_length: DEFAULT_MAX_ARRAY_LENGTH,
+        }
+    }
+}
+
+impl RequestGuard {
+    /// Create a guard with the default limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum nesting depth of arrays and objects.
+    ///
+    /// Params themselves are at depth 1, so `{"a": {"b": 1}}` has depth 2.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set the maximum length in bytes of any string, including object keys.
+    pub fn with_max_string_length(mut self, max_string_length: usize) -> Self {
+        self.max_string_length = max_string_length;
+        self
+    }
+
+    /// Set the maximum number of elements in any array.
+    pub fn with_max_array_length(mut self, max_array_length: usize) -> Self {
+        self.max_array_length = max_array_length;
+        self
+    }
+
+    /// Maximum nesting depth.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Maximum string length in bytes.
+    pub fn max_string_length(&self) -> usize {
+        self.max_string_length
+    }
+
+    /// Maximum array length.
+    pub fn max_array_length(&self) -> usize {
+        self.max_array_length
+    }
+
+    /// Check that `params` is within the limits.
+    pub fn check(&self, params: &Value) -> Result<()> {
+        let mut path = String::from("params");
+        self.check_value(params, 1, &mut path)
+    }
+
+    fn check_value(&self, value: &Value, depth: usize, path: &mut String) -> Result<()> {
+        match value {
+            Value::String(s) => self.check_string(s, path),
+            Value::Array(items) => {
+                self.check_depth(depth, path)?;
+                if items.len() > self.max_array_length {
+                    return Err(Self::violation(
+                        path,
+                        format!(
+                            "array has {} elements, limit is {}",
+                            items.len(),
+                            self.max_array_length
+                        ),
+                    ));
+                }
+                for (index, item) in items.iter().enumerate() {
+                    let len = path.len();
+                    let _ = write!(path, "/{}", index);
+                    self.check_value(item, depth + 1, path)?;
+                    path.truncate(len);
+                }
+                Ok(())
+            },
+            Value::Object(fields) => {
+                self.check_depth(depth, path)?;
+                for (key, item) in fields {
+                    let len = path.len();
+                    path.push('/');
+                    path.push_str(key);
+                    self.check_string(key, path)?;
+                    self.check_value(item, depth + 1, path)?;
+                    path.truncate(len);
+                }
+                Ok(())
+            },
+            Value::Null | Value::Bool(_) | Value::Number(_) => Ok(()),
+        }
+    }
+
+    fn check_depth(&self, depth: usize, path: &str) -> Result<()> {
+        if depth > self.max_depth {
+            return Err(Self::violation(
+                path,
+                format!("nesting depth exceeds limit of {}", self.max_depth),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_string(&self, s: &str, path: &str) -> Result<()> {
+        if s.len() > self.max_string_length {
+            return Err(Self::violation(
+                path,
+                format!(
+                    "string is {} bytes, limit is {}",
+                    s.len(),
+                    self.max_string_length
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn violation(path: &str, detail: String) -> Error {
+        Error::invalid_params(format!("Request rejected at {}: {}", path, detail))
+    }
+}
+
+/// List the ways `value` fails to match `schema`.
+///
+/// Each entry names the offending location, e.g. `/items/0: "x" is not of
+/// type "integer"`. Required top-level properties are always checked; with
+/// the `validation` feature the whole schema is enforced.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmcp::utils::validation::schema_violations;
+/// use serde_json::json;
+///
+/// let schema = json!({"type": "object", "required": ["sum"]});
+/// assert!(schema_violations(&schema, &json!({"sum": 3})).is_empty());
+/// assert_eq!(
+///     schema_violations(&schema, &json!({"total": 3})),
+///     vec!["missing required property 'sum'"]
+/// );
+/// ```
+pub fn schema_violations(schema: &Value, value: &Value) -> Vec<String> {
+    let violations: Vec<String> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter(|field| value.get(field).is_none())
+        .map(|field| format!("missing required property '{}'", field))
+        .collect();
+
+    #[cfg(feature = "validation")]
+    let violations = match jsonschema::validator_for(schema) {
+        Ok(validator) if violations.is_empty() => validator
+            .iter_errors(value)
+            .map(|e| format!("{}: {}", e.instance_path, e))
+            .collect(),
+        _ => violations,
+    };
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use serde_json::json;
+
+    #[test]
+    fn test_depth_limit() {
+        let guard = RequestGuard::new().with_max_depth(2);
+        assert!(guard.check(&json!({"a": {"b": 1}})).is_ok());
+        assert!(guard.check(&json!("scalar params")).is_ok());
+
+        let err = guard.check(&json!({"a": {"b": [1]}})).unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::INVALID_PARAMS));
+        assert!(err.to_string().contains("params/a/b"), "{}", err);
+    }
+
+    #[test]
+    fn test_string_and_array_limits() {
+        let guard = RequestGuard::new()
+            .with_max_string_length(3)
+            .with_max_array_length(2);
+        assert!(guard.check(&json!({"abc": ["xyz", 12345]})).is_ok());
+
+        let err = guard.check(&json!({"abc": ["x", "long"]})).unwrap_err();
+        assert!(err.to_string().contains("params/abc/1"), "{}", err);
+
+        let err = guard.check(&json!({"long": 1})).unwrap_err();
+        assert!(err.to_string().contains("params/long"), "{}", err);
+
+        let err = guard.check(&json!([1, 2, 3])).unwrap_err();
+        assert!(err.to_string().contains("3 elements"), "{}", err);
+    }
+}
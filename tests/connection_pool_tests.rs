@@ -190,6 +190,9 @@ mod pool_stats {
                 total_requests,
                 active_requests,
                 strategy: LoadBalanceStrategy::RoundRobin,
+                avg_latency: Duration::ZERO,
+                error_rate: 0.0,
+                connections: Vec::new(),
             };
 
             // Total connections should equal sum of health statuses
@@ -254,3 +254,56 @@ fn test_issue_38_json_rpc_compatibility() {
     println!("✓ Rust SDK is fully compatible with JSON-RPC 2.0");
     println!("✓ Compatible with Claude Code and TypeScript SDK");
 }
+
+#[test]
+fn test_tool_result_resource_content_typescript_sdk_format() {
+    use pmcp::types::{CallToolResult, Content, ResourceContents, ResourceLink};
+
+    // Tool result content blocks as serialized by the TypeScript SDK
+    let typescript_format = r#"{"content":[{"type":"text","text":"Found 2 files"},{"type":"resource_link","uri":"file:///project/README.md","name":"README.md","description":"Project readme","mimeType":"text/markdown"},{"type":"resource","resource":{"uri":"file:///project/main.rs","mimeType":"text/x-rust","text":"fn main() {}"}},{"type":"resource","resource":{"uri":"file:///project/logo.png","mimeType":"image/png","blob":"iVBORw0KGgo="}}],"isError":false}"#;
+
+    let result: CallToolResult = serde_json::from_str(typescript_format).unwrap();
+    assert_eq!(result.content.len(), 4);
+    match &result.content[1] {
+        Content::ResourceLink(link) => assert_eq!(
+            link,
+            &ResourceLink::new("file:///project/README.md", "README.md")
+                .with_description("Project readme")
+                .with_mime_type("text/markdown")
+        ),
+        other => panic!("Expected resource link, got {:?}", other),
+    }
+    match &result.content[2] {
+        Content::EmbeddedResource { resource } => assert_eq!(
+            resource,
+            &ResourceContents::text("file:///project/main.rs", "fn main() {}")
+                .with_mime_type("text/x-rust")
+        ),
+        other => panic!("Expected embedded resource, got {:?}", other),
+    }
+    match &result.content[3] {
+        Content::EmbeddedResource { resource } => {
+            assert_eq!(resource.blob.as_deref(), Some("iVBORw0KGgo="));
+            assert_eq!(resource.text, None);
+        },
+        other => panic!("Expected embedded resource, got {:?}", other),
+    }
+
+    // Serializing gives back the same JSON
+    let serialized: serde_json::Value = serde_json::to_value(&result).unwrap();
+    let expected: serde_json::Value = serde_json::from_str(typescript_format).unwrap();
+    assert_eq!(serialized, expected);
+}
+
+#[test]
+fn test_resource_contents_reference_still_parses() {
+    use pmcp::types::Content;
+
+    // The flat form used for `resources/read` contents
+    let flat = r#"{"type":"resource","uri":"file:///a.txt","mimeType":"text/plain","text":"hi"}"#;
+    let content: Content = serde_json::from_str(flat).unwrap();
+    assert!(matches!(
+        content,
+        Content::Resource { ref uri, text: Some(ref text), .. } if uri == "file:///a.txt" && text == "hi"
+    ));
+}
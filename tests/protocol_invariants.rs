@@ -96,6 +96,8 @@ prop_compose! {
             name,
             description: if has_desc { Some(description) } else { None },
             input_schema: schema,
+            output_schema: None,
+            annotations: None,
         }
     }
 }
@@ -222,6 +224,7 @@ proptest! {
             2 => ClientRequest::CallTool(CallToolParams {
                 name: tool_name,
                 arguments: args,
+                meta: None,
             }),
             3 => ClientRequest::ListPrompts(ListPromptsParams { cursor: cursor.clone() }),
             4 => ClientRequest::GetPrompt(GetPromptParams {
@@ -229,7 +232,7 @@ proptest! {
                 arguments: Default::default(),
             }),
             5 => ClientRequest::ListResources(ListResourcesParams { cursor }),
-            _ => ClientRequest::ReadResource(ReadResourceParams { uri: resource_uri }),
+            _ => ClientRequest::ReadResource(ReadResourceParams { uri: resource_uri, meta: None }),
         };
 
         let json = serde_json::to_value(&request).unwrap();
@@ -58,6 +58,7 @@ impl ResourceHandler for TestResourceHandler {
                     contents: vec![Content::Text {
                         text: content.clone(),
                     }],
+                    meta: None,
                 })
             },
         )
@@ -11,18 +11,17 @@ mod session_validation_tests {
     type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
     use std::net::{Ipv4Addr, SocketAddr};
     use std::sync::Arc;
-    use tokio::sync::Mutex;
     use url::Url;
 
     async fn create_test_server() -> Result<(SocketAddr, tokio::task::JoinHandle<()>)> {
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let http_server = StreamableHttpServer::new(addr, server);
         http_server
             .start()
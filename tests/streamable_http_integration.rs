@@ -19,7 +19,7 @@ use tokio::sync::Mutex;
 use url::Url;
 
 /// Create a test server with minimal capabilities
-async fn create_test_server() -> Arc<Mutex<Server>> {
+async fn create_test_server() -> Arc<Server> {
     let server = Server::builder()
         .name("test-server")
         .version("1.0.0")
@@ -27,7 +27,7 @@ async fn create_test_server() -> Arc<Mutex<Server>> {
         .build()
         .unwrap();
 
-    Arc::new(Mutex::new(server))
+    Arc::new(server)
 }
 
 #[tokio::test]
@@ -40,6 +40,7 @@ async fn test_streamable_http_stateless_mode() {
         event_store: None,
         on_session_initialized: None,
         on_session_closed: None,
+        ..Default::default()
     };
 
     let server_instance =
@@ -116,6 +117,7 @@ async fn test_streamable_http_stateful_mode() {
                 *closed.lock().await = true;
             });
         })),
+        ..Default::default()
     };
 
     let server_instance =
@@ -212,6 +214,7 @@ async fn test_transport_send_receive_multiple() {
         event_store: None,
         on_session_initialized: None,
         on_session_closed: None,
+        ..Default::default()
     };
 
     let server_instance =
@@ -11,20 +11,19 @@ mod streamable_http_server_tests {
     type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
     use std::net::{Ipv4Addr, SocketAddr};
     use std::sync::Arc;
-    use tokio::sync::Mutex;
     use url::Url;
 
     #[tokio::test]
     async fn test_initialization_generates_session_id() -> Result<()> {
         // Setup server with stateful mode (default)
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server
             .start()
@@ -80,14 +79,14 @@ mod streamable_http_server_tests {
     #[tokio::test]
     async fn test_non_init_requires_session_in_stateful_mode() -> Result<()> {
         // Setup server with stateful mode
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server
             .start()
@@ -127,14 +126,14 @@ mod streamable_http_server_tests {
     #[tokio::test]
     async fn test_stateless_mode_no_session_required() -> Result<()> {
         // Setup server in stateless mode
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
 
         let config = StreamableHttpServerConfig {
             session_id_generator: None, // Stateless mode
@@ -142,6 +141,7 @@ mod streamable_http_server_tests {
             event_store: None,
             on_session_initialized: None,
             on_session_closed: None,
+            ..Default::default()
         };
 
         let http_server = StreamableHttpServer::with_config(addr, server, config);
@@ -214,14 +214,14 @@ mod streamable_http_server_tests {
     #[tokio::test]
     async fn test_protocol_version_header_included() -> Result<()> {
         // Setup server
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server
             .start()
@@ -273,4 +273,623 @@ mod streamable_http_server_tests {
         server_task.abort();
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_serves_protected_resource_metadata() -> Result<()> {
+        use pmcp::server::auth::ProtectedResourceMetadata;
+
+        let server = Arc::new(
+            Server::builder()
+                .name("test-server")
+                .version("1.0.0")
+                .build()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+        );
+        let metadata = ProtectedResourceMetadata::new("https://mcp.example.com/mcp")
+            .authorization_server("https://auth.example.com")
+            .scopes_supported(["mcp:tools"]);
+        let config = StreamableHttpServerConfig {
+            protected_resource: Some(metadata.clone()),
+            ..Default::default()
+        };
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
+        let (server_addr, server_task) = StreamableHttpServer::with_config(addr, server, config)
+            .start()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        for path in [
+            "/.well-known/oauth-protected-resource",
+            "/.well-known/oauth-protected-resource/mcp",
+        ] {
+            let response = reqwest::get(format!("http://{}{}", server_addr, path)).await?;
+            assert_eq!(response.status(), reqwest::StatusCode::OK);
+            assert_eq!(response.headers()["access-control-allow-origin"], "*");
+            let served: ProtectedResourceMetadata = response.json().await?;
+            assert_eq!(served, metadata);
+        }
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_client_registration_endpoint() -> Result<()> {
+        use pmcp::server::auth::{InMemoryOAuthProvider, OAuthError, OAuthProvider};
+
+        let server = Arc::new(
+            Server::builder()
+                .name("test-server")
+                .version("1.0.0")
+                .build()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+        );
+        let provider = Arc::new(InMemoryOAuthProvider::new("http://localhost:8080"));
+        let config = StreamableHttpServerConfig {
+            client_registration: Some(provider.clone()),
+            ..Default::default()
+        };
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
+        let (server_addr, server_task) = StreamableHttpServer::with_config(addr, server, config)
+            .start()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+        let endpoint = format!("http://{}/oauth2/register", server_addr);
+        let http = reqwest::Client::new();
+
+        let response = http
+            .post(&endpoint)
+            .json(&serde_json::json!({
+                "redirect_uris": ["http://localhost:3000/callback"],
+                "client_name": "Test Client",
+                "token_endpoint_auth_method": "none",
+            }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+        let registered: serde_json::Value = response.json().await?;
+        let client_id = registered["client_id"].as_str().unwrap_or_default();
+        assert!(registered.get("client_secret").is_none());
+        assert!(provider.get_client(client_id).await?.is_some());
+
+        let response = http
+            .post(&endpoint)
+            .json(&serde_json::json!({"redirect_uris": ["not a uri"]}))
+            .send()
+            .await?;
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+        let error: OAuthError = response.json().await?;
+        assert_eq!(error.error, "invalid_redirect_uri");
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[tokio::test]
+    async fn test_serves_prometheus_metrics() -> Result<()> {
+        use pmcp::server::metrics::ServerMetrics;
+
+        let metrics = Arc::new(ServerMetrics::new());
+        let server = Arc::new(
+            Server::builder()
+                .name("test-server")
+                .version("1.0.0")
+                .metrics(metrics.clone())
+                .build()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+        );
+        let config = StreamableHttpServerConfig {
+            metrics: Some(metrics),
+            ..Default::default()
+        };
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
+        let (server_addr, server_task) = StreamableHttpServer::with_config(addr, server, config)
+            .start()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let mut client = StreamableHttpTransport::new(StreamableHttpTransportConfig {
+            url: Url::parse(&format!("http://{}", server_addr))?,
+            extra_headers: vec![],
+            auth_provider: None,
+            session_id: None,
+            enable_json_response: true,
+            on_resumption_token: None,
+        });
+        client
+            .send(TransportMessage::Request {
+                id: 1i64.into(),
+                request: Request::Client(Box::new(ClientRequest::Initialize(InitializeParams {
+                    protocol_version: pmcp::LATEST_PROTOCOL_VERSION.to_string(),
+                    capabilities: ClientCapabilities::default(),
+                    client_info: Implementation {
+                        name: "test-client".to_string(),
+                        version: "1.0.0".to_string(),
+                    },
+                }))),
+            })
+            .await?;
+        client.receive().await?;
+
+        let response = reqwest::get(format!("http://{}/metrics", server_addr)).await?;
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert!(response.headers()["content-type"]
+            .to_str()?
+            .starts_with("text/plain"));
+        let body = response.text().await?;
+        assert!(body.contains("mcp_requests_total{method=\"initialize\"} 1\n"));
+        assert!(body.contains("mcp_active_sessions 1\n"));
+        assert!(body.contains("mcp_event_store_events 1\n"));
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mounts_into_axum_app_with_shared_middleware() -> Result<()> {
+        use axum::extract::{Request as HttpRequest, State};
+        use axum::http::StatusCode;
+        use axum::middleware::{self, Next};
+        use axum::response::{IntoResponse, Response};
+        use axum::routing::get;
+        use axum::Router;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Clone, Default)]
+        struct AppState {
+            authorized: Arc<AtomicUsize>,
+        }
+
+        async fn require_key(
+            State(state): State<AppState>,
+            request: HttpRequest,
+            next: Next,
+        ) -> Response {
+            if request.headers().get("x-api-key").is_none() {
+                return StatusCode::UNAUTHORIZED.into_response();
+            }
+            state.authorized.fetch_add(1, Ordering::SeqCst);
+            next.run(request).await
+        }
+
+        let server = Arc::new(
+            Server::builder()
+                .name("embedded")
+                .version("1.0.0")
+                .build()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+        );
+        let mcp = StreamableHttpServer::new(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0), server);
+        let state = AppState::default();
+        let app = Router::new()
+            .route(
+                "/health",
+                get(|State(state): State<AppState>| async move {
+                    state.authorized.load(Ordering::SeqCst).to_string()
+                }),
+            )
+            .nest("/mcp", mcp.into_router())
+            .route_layer(middleware::from_fn_with_state(state.clone(), require_key))
+            .with_state(state.clone());
+
+        // Served without connect info
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let addr = listener.local_addr()?;
+        let server_task = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let http = reqwest::Client::new();
+        let initialize = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": pmcp::LATEST_PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {"name": "test-client", "version": "1.0.0"},
+            },
+        });
+        let post = || {
+            http.post(format!("http://{}/mcp", addr))
+                .header("Accept", "application/json, text/event-stream")
+                .json(&initialize)
+        };
+
+        let response = post().send().await?;
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let response = post().header("x-api-key", "secret").send().await?;
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert!(response.headers().contains_key("mcp-session-id"));
+        let body: serde_json::Value = response.json().await?;
+        assert_eq!(body["result"]["serverInfo"]["name"], "embedded");
+        assert_eq!(state.authorized.load(Ordering::SeqCst), 1);
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_requests_are_handled_concurrently() -> Result<()> {
+        use async_trait::async_trait;
+        use pmcp::{RequestHandlerExtra, ToolHandler};
+        use serde_json::{json, Value};
+        use tokio::sync::Notify;
+
+        /// Waits until `release` is called, holding its request open.
+        struct Wait(Arc<Notify>);
+
+        #[async_trait]
+        impl ToolHandler for Wait {
+            async fn handle(
+                &self,
+                _args: Value,
+                _extra: RequestHandlerExtra,
+            ) -> pmcp::Result<Value> {
+                self.0.notified().await;
+                Ok(json!("released"))
+            }
+        }
+
+        struct Release(Arc<Notify>);
+
+        #[async_trait]
+        impl ToolHandler for Release {
+            async fn handle(
+                &self,
+                _args: Value,
+                _extra: RequestHandlerExtra,
+            ) -> pmcp::Result<Value> {
+                self.0.notify_one();
+                Ok(json!("done"))
+            }
+        }
+
+        let notify = Arc::new(Notify::new());
+        let server = Arc::new(
+            Server::builder()
+                .name("concurrent")
+                .version("1.0.0")
+                .tool("wait", Wait(notify.clone()))
+                .tool("release", Release(notify))
+                .build()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+        );
+        let config = StreamableHttpServerConfig {
+            session_id_generator: None,
+            enable_json_response: true,
+            ..Default::default()
+        };
+        let (addr, server_task) = StreamableHttpServer::with_config(
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0),
+            server,
+            config,
+        )
+        .start()
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let http = reqwest::Client::new();
+        let post = move |body: Value| {
+            http.post(format!("http://{}", addr))
+                .header("Accept", "application/json, text/event-stream")
+                .json(&body)
+                .send()
+        };
+        let call = |id: i64, name: &str| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "tools/call",
+                "params": {"name": name, "arguments": {}},
+            })
+        };
+
+        post(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": pmcp::LATEST_PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {"name": "test-client", "version": "1.0.0"},
+            },
+        }))
+        .await?;
+
+        let waiting = tokio::spawn(post(call(2, "wait")));
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        // Served while the first call is still running
+        let released =
+            tokio::time::timeout(std::time::Duration::from_secs(5), post(call(3, "release")))
+                .await
+                .expect("second request waited for the first")?;
+        assert_eq!(released.status(), reqwest::StatusCode::OK);
+
+        let waited: Value = waiting.await??.json().await?;
+        assert_eq!(waited["id"], 2);
+        assert!(waited["result"].is_object());
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_handlers_sample_from_session_clients() -> Result<()> {
+        use async_trait::async_trait;
+        use pmcp::types::{Content, CreateMessageParams, Role, SamplingMessage};
+        use pmcp::{RequestHandlerExtra, ToolHandler};
+        use serde_json::{json, Value};
+        use std::time::Duration;
+
+        struct Ask;
+
+        #[async_trait]
+        impl ToolHandler for Ask {
+            async fn handle(
+                &self,
+                _args: Value,
+                extra: RequestHandlerExtra,
+            ) -> pmcp::Result<Value> {
+                let reply = extra
+                    .create_message(CreateMessageParams::new(vec![SamplingMessage {
+                        role: Role::User,
+                        content: Content::Text {
+                            text: "Say hi".to_string(),
+                        },
+                    }]))
+                    .await?;
+                Ok(json!({"model": reply.model}))
+            }
+        }
+
+        let server = Arc::new(
+            Server::builder()
+                .name("sampling")
+                .version("1.0.0")
+                .tool("ask", Ask)
+                .build()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+        );
+        let config = StreamableHttpServerConfig {
+            enable_json_response: true,
+            ..Default::default()
+        };
+        let (addr, server_task) = StreamableHttpServer::with_config(
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0),
+            server,
+            config,
+        )
+        .start()
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let http = reqwest::Client::new();
+        let url = format!("http://{}", addr);
+        let initialize = http
+            .post(&url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": {
+                    "protocolVersion": pmcp::LATEST_PROTOCOL_VERSION,
+                    "capabilities": {"sampling": {}},
+                    "clientInfo": {"name": "test-client", "version": "1.0.0"},
+                },
+            }))
+            .send()
+            .await?;
+        let session_id = initialize.headers()["mcp-session-id"].to_str()?.to_string();
+        let post = {
+            let http = http.clone();
+            let url = url.clone();
+            let session_id = session_id.clone();
+            move |body: Value| {
+                http.post(&url)
+                    .header("Accept", "application/json, text/event-stream")
+                    .header("mcp-session-id", &session_id)
+                    .json(&body)
+                    .send()
+            }
+        };
+        let ask = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {"name": "ask", "arguments": {}},
+        });
+
+        // Without a stream to send it on, the request fails right away
+        let failed: Value = post(ask.clone()).await?.json().await?;
+        assert!(failed.to_string().contains("SSE stream"), "{}", failed);
+
+        let mut stream = http
+            .get(&url)
+            .header("Accept", "text/event-stream")
+            .header("mcp-session-id", &session_id)
+            .send()
+            .await?;
+        assert_eq!(stream.status(), reqwest::StatusCode::OK);
+
+        let call = tokio::spawn(post(ask));
+
+        // The sampling request arrives on the session's stream
+        let mut events = String::new();
+        let request = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let chunk = stream.chunk().await?.ok_or("stream ended")?;
+                events.push_str(std::str::from_utf8(&chunk)?);
+                if let Some(request) = events
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("data:"))
+                    .filter_map(|data| serde_json::from_str::<Value>(data.trim()).ok())
+                    .find(|message| message["method"] == "sampling/createMessage")
+                {
+                    return Ok::<_, Box<dyn std::error::Error + Send + Sync>>(request);
+                }
+            }
+        })
+        .await??;
+
+        let answered = post(json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": {
+                "role": "assistant",
+                "content": {"type": "text", "text": "hi"},
+                "model": "test-model",
+            },
+        }))
+        .await?;
+        assert_eq!(answered.status(), reqwest::StatusCode::ACCEPTED);
+
+        let result: Value = call.await??.json().await?;
+        assert!(
+            result["result"].to_string().contains("test-model"),
+            "{}",
+            result
+        );
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cancellations_stay_within_their_session() -> Result<()> {
+        use async_trait::async_trait;
+        use pmcp::{RequestHandlerExtra, ToolHandler};
+        use serde_json::{json, Value};
+        use std::time::Duration;
+        use tokio::sync::{mpsc, Notify};
+
+        /// Runs until cancelled or released.
+        struct Block {
+            started: mpsc::UnboundedSender<()>,
+            release: Arc<Notify>,
+        }
+
+        #[async_trait]
+        impl ToolHandler for Block {
+            async fn handle(
+                &self,
+                _args: Value,
+                extra: RequestHandlerExtra,
+            ) -> pmcp::Result<Value> {
+                let released = self.release.notified();
+                let _ = self.started.send(());
+                tokio::select! {
+                    () = extra.cancelled() => Ok(json!("cancelled")),
+                    () = released => Ok(json!("released")),
+                }
+            }
+        }
+
+        let (started, mut started_rx) = mpsc::unbounded_channel();
+        let release = Arc::new(Notify::new());
+        let server = Arc::new(
+            Server::builder()
+                .name("cancellation")
+                .version("1.0.0")
+                .tool(
+                    "block",
+                    Block {
+                        started,
+                        release: release.clone(),
+                    },
+                )
+                .build()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+        );
+        let config = StreamableHttpServerConfig {
+            enable_json_response: true,
+            ..Default::default()
+        };
+        let (addr, server_task) = StreamableHttpServer::with_config(
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0),
+            server,
+            config,
+        )
+        .start()
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let http = reqwest::Client::new();
+        let url = format!("http://{}", addr);
+        let mut sessions = Vec::new();
+        for _ in 0..2 {
+            let initialize = http
+                .post(&url)
+                .header("Accept", "application/json, text/event-stream")
+                .json(&json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "initialize",
+                    "params": {
+                        "protocolVersion": pmcp::LATEST_PROTOCOL_VERSION,
+                        "capabilities": {},
+                        "clientInfo": {"name": "test-client", "version": "1.0.0"},
+                    },
+                }))
+                .send()
+                .await?;
+            sessions.push(initialize.headers()["mcp-session-id"].to_str()?.to_string());
+        }
+        let post = |session_id: &str, body: Value| {
+            http.post(&url)
+                .header("Accept", "application/json, text/event-stream")
+                .header("mcp-session-id", session_id)
+                .json(&body)
+                .send()
+        };
+
+        // Both clients use the same request ID
+        let block = json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": {"name": "block", "arguments": {}},
+        });
+        let first = tokio::spawn(post(&sessions[0], block.clone()));
+        let second = tokio::spawn(post(&sessions[1], block));
+        for _ in 0..2 {
+            tokio::time::timeout(Duration::from_secs(5), started_rx.recv()).await?;
+        }
+
+        let cancelled = post(
+            &sessions[0],
+            json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/cancelled",
+                "params": {"requestId": 7},
+            }),
+        )
+        .await?;
+        assert_eq!(cancelled.status(), reqwest::StatusCode::ACCEPTED);
+        let first: Value = tokio::time::timeout(Duration::from_secs(5), first)
+            .await???
+            .json()
+            .await?;
+        assert!(!first.to_string().contains("released"), "{}", first);
+
+        // The other session's request is still running
+        assert!(!second.is_finished());
+        release.notify_waiters();
+        let second: Value = tokio::time::timeout(Duration::from_secs(5), second)
+            .await???
+            .json()
+            .await?;
+        assert!(
+            second["result"].to_string().contains("released"),
+            "{}",
+            second
+        );
+
+        server_task.abort();
+        Ok(())
+    }
 }
@@ -1,16 +1,21 @@
 //! Comprehensive spec compliance tests for streamable HTTP transport
 #[cfg(feature = "streamable-http")]
 mod spec_compliance_tests {
-    use pmcp::server::streamable_http_server::{StreamableHttpServer, StreamableHttpServerConfig};
+    use pmcp::server::origin::OriginPolicy;
+    use pmcp::server::session_backend::SessionPolicy;
+    use pmcp::server::streamable_http_server::{
+        ProtocolVersionHeader, StreamableHttpServer, StreamableHttpServerConfig,
+    };
     use pmcp::server::Server;
+    use pmcp::shared::cors::CorsConfig;
     use pmcp::shared::streamable_http::{StreamableHttpTransport, StreamableHttpTransportConfig};
-    use pmcp::shared::{Transport, TransportMessage};
+    use pmcp::shared::{ProtocolOptions, Transport, TransportMessage};
     use pmcp::types::{
         ClientCapabilities, ClientRequest, Implementation, InitializeParams, Request,
     };
     use std::net::{Ipv4Addr, SocketAddr};
     use std::sync::Arc;
-    use tokio::sync::Mutex;
+    use std::time::Duration;
     use url::Url;
 
     // Use boxed error for tests to satisfy clippy's large_enum_variant warning
@@ -26,14 +31,14 @@ mod spec_compliance_tests {
     #[tokio::test]
     async fn test_baseline_accept_header_validation() -> Result<()> {
         // Test in stateful mode (but applies to both)
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(box_err)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
@@ -83,14 +88,14 @@ mod spec_compliance_tests {
 
     #[tokio::test]
     async fn test_baseline_content_type_validation() -> Result<()> {
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(box_err)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
@@ -141,14 +146,14 @@ mod spec_compliance_tests {
     #[tokio::test]
     async fn test_baseline_protocol_version_required_non_init() -> Result<()> {
         // Test that non-init requests MUST include protocol version header
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(box_err)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
@@ -221,14 +226,14 @@ mod spec_compliance_tests {
 
     #[tokio::test]
     async fn test_baseline_protocol_version_requirement() -> Result<()> {
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(box_err)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
@@ -298,14 +303,14 @@ mod spec_compliance_tests {
 
     #[tokio::test]
     async fn test_baseline_notifications_only_returns_202() -> Result<()> {
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(box_err)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
@@ -390,14 +395,14 @@ mod spec_compliance_tests {
 
     #[tokio::test]
     async fn test_stateful_initialize_creates_session() -> Result<()> {
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(box_err)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         // Explicitly use stateful mode (default)
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
@@ -441,14 +446,14 @@ mod spec_compliance_tests {
 
     #[tokio::test]
     async fn test_stateful_concurrent_sse_conflict() -> Result<()> {
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(box_err)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let http_server = StreamableHttpServer::new(addr, server);
         let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
 
@@ -517,20 +522,21 @@ mod spec_compliance_tests {
     // ==================== STATELESS MODE TESTS ====================
 
     async fn create_stateless_server() -> Result<(SocketAddr, tokio::task::JoinHandle<()>)> {
-        let server = Arc::new(Mutex::new(
+        let server = Arc::new(
             Server::builder()
                 .name("test-server")
                 .version("1.0.0")
                 .build()
                 .map_err(box_err)?,
-        ));
-        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
         let config = StreamableHttpServerConfig {
             session_id_generator: None, // Stateless mode
             enable_json_response: false,
             event_store: None,
             on_session_initialized: None,
             on_session_closed: None,
+            ..Default::default()
         };
         let http_server = StreamableHttpServer::with_config(addr, server, config);
         http_server.start().await.map_err(box_err)
@@ -738,4 +744,695 @@ mod spec_compliance_tests {
         server_task.abort();
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_strict_mode_rejects_malformed_envelopes() -> Result<()> {
+        let server = Arc::new(
+            Server::builder()
+                .name("test-server")
+                .version("1.0.0")
+                .build()
+                .map_err(box_err)?,
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
+        let config = StreamableHttpServerConfig {
+            session_id_generator: None,
+            protocol_options: ProtocolOptions::default().with_strict(true),
+            ..Default::default()
+        };
+        let http_server = StreamableHttpServer::with_config(addr, server, config);
+        let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
+
+        let client = reqwest::Client::new();
+        let url = format!("http://{}", server_addr);
+        let cases = [
+            (r#"{"jsonrpc":"1.0","id":1,"method":"ping"}"#, -32600),
+            (
+                r#"{"jsonrpc":"2.0","id":1,"method":"ping","extra":true}"#,
+                -32600,
+            ),
+            (r#"{"jsonrpc":"2.0","id":1,"method":"no/such"}"#, -32601),
+        ];
+
+        for (body, code) in cases {
+            let response = client
+                .post(&url)
+                .header("accept", "application/json, text/event-stream")
+                .header("content-type", "application/json")
+                .header("mcp-protocol-version", pmcp::LATEST_PROTOCOL_VERSION)
+                .body(body)
+                .send()
+                .await
+                .unwrap();
+
+            assert_eq!(response.status().as_u16(), 400, "{}", body);
+            let error_body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(error_body["error"]["code"], code, "{}", body);
+        }
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_oversized_messages_are_rejected() -> Result<()> {
+        let server = Arc::new(
+            Server::builder()
+                .name("test-server")
+                .version("1.0.0")
+                .build()
+                .map_err(box_err)?,
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
+        let config = StreamableHttpServerConfig {
+            session_id_generator: None,
+            protocol_options: ProtocolOptions::default()
+                .with_max_inbound_message_size(256)
+                .with_max_nesting_depth(4)
+                .with_max_array_length(8),
+            ..Default::default()
+        };
+        let http_server = StreamableHttpServer::with_config(addr, server, config);
+        let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
+
+        let client = reqwest::Client::new();
+        let url = format!("http://{}", server_addr);
+        let post = |body: String| {
+            client
+                .post(&url)
+                .header("accept", "application/json, text/event-stream")
+                .header("content-type", "application/json")
+                .header("mcp-protocol-version", pmcp::LATEST_PROTOCOL_VERSION)
+                .body(body)
+                .send()
+        };
+        let padded = |len: usize| {
+            format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"ping","params":{{"pad":"{}"}}}}"#,
+                "x".repeat(len)
+            )
+        };
+
+        // Slightly oversized: answered with a JSON-RPC error
+        let response = post(padded(300)).await.unwrap();
+        assert_eq!(response.status().as_u16(), 413);
+        let error_body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(error_body["error"]["code"], -32600);
+
+        // Far beyond the limit: refused without being buffered
+        let response = post(padded(4096)).await.unwrap();
+        assert_eq!(response.status().as_u16(), 413);
+
+        // Small but too deep or too wide: rejected before decoding
+        for params in [r#"{"a":{"b":{"c":{}}}}"#, "[1,2,3,4,5,6,7,8,9]"] {
+            let body = format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"ping","params":{}}}"#,
+                params
+            );
+            let response = post(body).await.unwrap();
+            assert_eq!(response.status().as_u16(), 400);
+            let error_body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(error_body["error"]["code"], -32600);
+        }
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stateful_sessions_shared_between_replicas() -> Result<()> {
+        use pmcp::server::session_backend::InMemorySessionBackend;
+
+        let backend = Arc::new(InMemorySessionBackend::new());
+        let mut urls = Vec::new();
+        let mut tasks = Vec::new();
+        for _ in 0..2 {
+            let server = Arc::new(
+                Server::builder()
+                    .name("test-server")
+                    .version("1.0.0")
+                    .build()
+                    .map_err(box_err)?,
+            );
+            let config = StreamableHttpServerConfig {
+                enable_json_response: true,
+                session_backend: backend.clone(),
+                ..Default::default()
+            };
+            let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
+            let http_server = StreamableHttpServer::with_config(addr, server, config);
+            let (server_addr, server_task) = http_server.start().await.map_err(box_err)?;
+            urls.push(format!("http://{}", server_addr));
+            tasks.push(server_task);
+        }
+
+        let client = reqwest::Client::new();
+
+        // Initialize on the first replica
+        let init = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"initialize","params":{{"protocolVersion":"{}","capabilities":{{}},"clientInfo":{{"name":"test-client","version":"1.0.0"}}}}}}"#,
+            pmcp::LATEST_PROTOCOL_VERSION
+        );
+        let response = client
+            .post(&urls[0])
+            .header("accept", "application/json, text/event-stream")
+            .header("content-type", "application/json")
+            .body(init)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let header = |name: &str| response.headers()[name].to_str().unwrap().to_string();
+        let session_id = header("mcp-session-id");
+        let protocol_version = header("mcp-protocol-version");
+
+        let post = |url: &str, session_id: &str| {
+            client
+                .post(url)
+                .header("accept", "application/json, text/event-stream")
+                .header("content-type", "application/json")
+                .header("mcp-protocol-version", &protocol_version)
+                .header("mcp-session-id", session_id)
+                .body(r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#)
+                .send()
+        };
+
+        // The second replica accepts the session
+        let response = post(&urls[1], &session_id).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let unknown = post(&urls[1], "unknown").await.unwrap();
+        assert_eq!(unknown.status().as_u16(), 404);
+
+        // Ending the session on one replica ends it everywhere
+        let response = client
+            .delete(&urls[1])
+            .header("mcp-session-id", &session_id)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let response = post(&urls[0], &session_id).await.unwrap();
+        assert_eq!(response.status().as_u16(), 404);
+
+        for task in tasks {
+            task.abort();
+        }
+        Ok(())
+    }
+
+    // ==================== PROTOCOL VERSION HEADER ====================
+
+    async fn create_version_checking_server() -> Result<(SocketAddr, tokio::task::JoinHandle<()>)> {
+        let server = Arc::new(
+            Server::builder()
+                .name("test-server")
+                .version("1.0.0")
+                .build()
+                .map_err(box_err)?,
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
+        let config = StreamableHttpServerConfig {
+            protocol_version_header: ProtocolVersionHeader::Required,
+            ..Default::default()
+        };
+        let http_server = StreamableHttpServer::with_config(addr, server, config);
+        http_server.start().await.map_err(box_err)
+    }
+
+    fn initialize_request(protocol_version: &str) -> TransportMessage {
+        TransportMessage::Request {
+            id: 1i64.into(),
+            request: Request::Client(Box::new(ClientRequest::Initialize(InitializeParams {
+                protocol_version: protocol_version.to_string(),
+                capabilities: ClientCapabilities::default(),
+                client_info: Implementation {
+                    name: "test-client".to_string(),
+                    version: "1.0.0".to_string(),
+                },
+            }))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transport_sends_negotiated_protocol_version() -> Result<()> {
+        let (server_addr, server_task) = create_version_checking_server().await?;
+
+        for version in pmcp::SUPPORTED_PROTOCOL_VERSIONS {
+            let client_config = StreamableHttpTransportConfig {
+                url: Url::parse(&format!("http://{}", server_addr))
+                    .map_err(|e| pmcp::Error::Internal(e.to_string()))
+                    .map_err(box_err)?,
+                extra_headers: vec![],
+                auth_provider: None,
+                session_id: None,
+                enable_json_response: true,
+                on_resumption_token: None,
+            };
+            let mut client = StreamableHttpTransport::new(client_config);
+
+            client
+                .send(initialize_request(version))
+                .await
+                .map_err(box_err)?;
+            client.receive().await.map_err(box_err)?;
+            assert_eq!(client.protocol_version().as_deref(), Some(*version));
+
+            // The server requires the header, so the ping only succeeds if
+            // the transport sends it
+            client
+                .send(TransportMessage::Request {
+                    id: 2i64.into(),
+                    request: Request::Client(Box::new(ClientRequest::Ping)),
+                })
+                .await
+                .map_err(box_err)?;
+            match client.receive().await.map_err(box_err)? {
+                TransportMessage::Response(response) => {
+                    assert!(response.result().is_some(), "ping failed on {}", version);
+                },
+                other => panic!("Expected a response, got {:?}", other),
+            }
+        }
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_required_protocol_version_header_is_enforced() -> Result<()> {
+        let (server_addr, server_task) = create_version_checking_server().await?;
+        let client = reqwest::Client::new();
+        let url = format!("http://{}", server_addr);
+
+        let negotiated = pmcp::SUPPORTED_PROTOCOL_VERSIONS[1];
+        let response = client
+            .post(&url)
+            .header("accept", "application/json, text/event-stream")
+            .header("content-type", "application/json")
+            .body(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "initialize",
+                    "params": {
+                        "protocolVersion": negotiated,
+                        "capabilities": {},
+                        "clientInfo": {"name": "test-client", "version": "1.0.0"}
+                    }
+                })
+                .to_string(),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let session_id = response.headers()["mcp-session-id"]
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let ping = |version: Option<&str>| {
+            let mut request = client
+                .post(&url)
+                .header("accept", "application/json, text/event-stream")
+                .header("content-type", "application/json")
+                .header("mcp-session-id", &session_id);
+            if let Some(version) = version {
+                request = request.header("mcp-protocol-version", version);
+            }
+            request
+                .body(r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#)
+                .send()
+        };
+
+        let response = ping(Some(negotiated)).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        let cases = [
+            (None, "Missing MCP-Protocol-Version"),
+            (Some(pmcp::LATEST_PROTOCOL_VERSION), "mismatch"),
+            (Some("1999-01-01"), "Unsupported"),
+        ];
+        for (version, message) in cases {
+            let response = ping(version).await.unwrap();
+            assert_eq!(response.status().as_u16(), 400, "{:?}", version);
+            let error_body: serde_json::Value = response.json().await.unwrap();
+            let text = error_body["error"]["message"].as_str().unwrap();
+            assert!(text.contains(message), "{:?}: {}", version, text);
+        }
+
+        server_task.abort();
+        Ok(())
+    }
+
+    // ==================== SESSION POLICY ====================
+
+    async fn start_with_config(
+        config: StreamableHttpServerConfig,
+    ) -> Result<(SocketAddr, tokio::task::JoinHandle<()>)> {
+        let server = Arc::new(
+            Server::builder()
+                .name("test-server")
+                .version("1.0.0")
+                .build()
+                .map_err(box_err)?,
+        );
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0);
+        let http_server = StreamableHttpServer::with_config(addr, server, config);
+        http_server.start().await.map_err(box_err)
+    }
+
+    /// Initialize a session with raw HTTP, returning its ID
+    async fn initialize_session(
+        client: &reqwest::Client,
+        url: &str,
+        authorization: &str,
+    ) -> Result<String> {
+        let response = client
+            .post(url)
+            .header("accept", "application/json, text/event-stream")
+            .header("content-type", "application/json")
+            .header("authorization", authorization)
+            .body(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "initialize",
+                    "params": {
+                        "protocolVersion": pmcp::LATEST_PROTOCOL_VERSION,
+                        "capabilities": {},
+                        "clientInfo": {"name": "test-client", "version": "1.0.0"}
+                    }
+                })
+                .to_string(),
+            )
+            .send()
+            .await?;
+        assert_eq!(response.status().as_u16(), 200);
+        Ok(response.headers()["mcp-session-id"].to_str()?.to_string())
+    }
+
+    async fn ping(
+        client: &reqwest::Client,
+        url: &str,
+        session_id: &str,
+        authorization: &str,
+    ) -> Result<reqwest::Response> {
+        Ok(client
+            .post(url)
+            .header("accept", "application/json, text/event-stream")
+            .header("content-type", "application/json")
+            .header("mcp-protocol-version", pmcp::LATEST_PROTOCOL_VERSION)
+            .header("mcp-session-id", session_id)
+            .header("authorization", authorization)
+            .body(r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#)
+            .send()
+            .await?)
+    }
+
+    #[tokio::test]
+    async fn test_idle_sessions_expire_with_404() -> Result<()> {
+        let closed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let closed_log = closed.clone();
+        let config = StreamableHttpServerConfig {
+            session_policy: SessionPolicy {
+                idle_timeout: Some(Duration::from_millis(600)),
+                ..Default::default()
+            },
+            on_session_closed: Some(Box::new(move |sid| {
+                closed_log.lock().unwrap().push(sid.to_string());
+            })),
+            ..Default::default()
+        };
+        let (server_addr, server_task) = start_with_config(config).await?;
+        let client = reqwest::Client::new();
+        let url = format!("http://{}", server_addr);
+
+        let session_id = initialize_session(&client, &url, "Bearer a").await?;
+        // Activity keeps the session alive past the idle timeout
+        for _ in 0..3 {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            let response = ping(&client, &url, &session_id, "Bearer a").await?;
+            assert_eq!(response.status().as_u16(), 200);
+        }
+
+        tokio::time::sleep(Duration::from_millis(800)).await;
+        let response = ping(&client, &url, &session_id, "Bearer a").await?;
+        assert_eq!(response.status().as_u16(), 404);
+        assert_eq!(*closed.lock().unwrap(), vec![session_id]);
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sessions_rotate_when_credentials_change() -> Result<()> {
+        let rotations = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let rotation_log = rotations.clone();
+        let config = StreamableHttpServerConfig {
+            session_policy: SessionPolicy {
+                rotate_on_auth_change: true,
+                ..Default::default()
+            },
+            on_session_rotated: Some(Box::new(move |old, new| {
+                rotation_log
+                    .lock()
+                    .unwrap()
+                    .push((old.to_string(), new.to_string()));
+            })),
+            ..Default::default()
+        };
+        let (server_addr, server_task) = start_with_config(config).await?;
+        let client = reqwest::Client::new();
+        let url = format!("http://{}", server_addr);
+
+        let session_id = initialize_session(&client, &url, "Bearer a").await?;
+        let response = ping(&client, &url, &session_id, "Bearer a").await?;
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(response.headers()["mcp-session-id"], session_id.as_str());
+
+        let response = ping(&client, &url, &session_id, "Bearer b").await?;
+        assert_eq!(response.status().as_u16(), 200);
+        let rotated = response.headers()["mcp-session-id"].to_str()?.to_string();
+        assert_ne!(rotated, session_id);
+        assert_eq!(
+            *rotations.lock().unwrap(),
+            vec![(session_id.clone(), rotated.clone())]
+        );
+
+        let response = ping(&client, &url, &session_id, "Bearer b").await?;
+        assert_eq!(response.status().as_u16(), 404);
+        let response = ping(&client, &url, &rotated, "Bearer b").await?;
+        assert_eq!(response.status().as_u16(), 200);
+
+        server_task.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transport_forgets_expired_session() -> Result<()> {
+        let config = StreamableHttpServerConfig {
+            session_policy: SessionPolicy {
+                max_lifetime: Some(Duration::from_millis(200)),
+                ..Default::default()
+            },
+            enable_json_response: true,
+            ..Default::default()
+        };
+        let (server_addr, server_task) = start_with_config(config).await?;
+        let client_config = StreamableHttpTransportConfig {
+            url: Url::parse(&format!("http://{}", server_addr))
+                .map_err(|e| pmcp::Error::Internal(e.to_string()))
+                .map_err(box_err)?,
+            extra_headers: vec![],
+            auth_provider: None,
+            session_id: None,
+            enable_json_response: true,
+            on_resumption_token: None,
+        };
+        let mut client = StreamableHttpTransport::new(client_config);
+        let ping = || TransportMessage::Request {
+            id: 2i64.into(),
+            request: Request::Client(Box::new(ClientRequest::Ping)),
+        };
+
+        client
+            .send(initialize_request(pmcp::LATEST_PROTOCOL_VERSION))
+            .await
+            .map_err(box_err)?;
+        client.receive().await.map_err(box_err)?;
+        let first_session = client.session_id();
+        assert!(first_session.is_some());
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let error = client.send(ping()).await.unwrap_err();
+        assert!(
+            matches!(
+                error,
+                pmcp::Error::Transport(pmcp::error::TransportError::SessionExpired)
+            ),
+            "{:?}",
+            error
+        );
+        assert_eq!(client.session_id(), None);
+        assert_eq!(client.protocol_version(), None);
+
+        // The next initialize starts a new session
+        client
+            .send(initialize_request(pmcp::LATEST_PROTOCOL_VERSION))
+            .await
+            .map_err(box_err)?;
+        client.receive().await.map_err(box_err)?;
+        assert!(client.session_id().is_some());
+        assert_ne!(client.session_id(), first_session);
+
+        server_task.abort();
+        Ok(())
+    }
+
+    // ==================== ORIGIN VALIDATION ====================
+
+    #[tokio::test]
+    async fn test_foreign_origins_and_hosts_are_forbidden() -> Result<()> {
+        let config = StreamableHttpServerConfig {
+            origin_policy: OriginPolicy::localhost(),
+            ..Default::default()
+        };
+        let (server_addr, server_task) = start_with_config(config).await?;
+        let client = reqwest::Client::new();
+        let url = format!("http://localhost:{}", server_addr.port());
+
+        let initialize = |url: &str, origin: &str| {
+            client
+                .post(url)
+                .header("accept", "application/json, text/event-stream")
+                .header("content-type", "application/json")
+                .header("origin", origin)
+                .body(
+                    serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "method": "initialize",
+                        "params": {
+                            "protocolVersion": pmcp::LATEST_PROTOCOL_VERSION,
+                            "capabilities": {},
+                            "clientInfo": {"name": "test-client", "version": "1.0.0"}
+                        }
+                    })
+                    .to_string(),
+                )
+                .send()
+        };
+
+        let response = initialize(&url, "http://evil.example.com").await?;
+        assert_eq!(response.status().as_u16(), 403);
+        let error_body: serde_json::Value = response.json().await?;
+        assert_eq!(error_body["error"]["message"], "Origin not allowed");
+
+        // A rebound page's requests to its own origin carry no `Origin`, but
+        // name its domain as their host
+        let response = client
+            .get(&url)
+            .header("accept", "text/event-stream")
+            .header(
+                "host",
+                format!("rebound.example.com:{}", server_addr.port()),
+            )
+            .send()
+            .await?;
+        assert_eq!(response.status().as_u16(), 403);
+        let error_body: serde_json::Value = response.json().await?;
+        assert_eq!(error_body["error"]["message"], "Host not allowed");
+
+        let response = initialize(&url, "http://localhost:3000").await?;
+        assert_eq!(response.status().as_u16(), 200);
+        let session_id = response.headers()["mcp-session-id"].to_str()?.to_string();
+
+        let response = client
+            .delete(&url)
+            .header("origin", "http://evil.example.com")
+            .header("mcp-session-id", &session_id)
+            .send()
+            .await?;
+        assert_eq!(response.status().as_u16(), 403);
+
+        server_task.abort();
+        Ok(())
+    }
+
+    // ==================== CORS ====================
+
+    #[tokio::test]
+    async fn test_cors_headers_follow_config() -> Result<()> {
+        let config = StreamableHttpServerConfig {
+            origin_policy: OriginPolicy::allow_any(),
+            cors: CorsConfig::new()
+                .allow_origin("https://app.example.com")
+                .allow_header("Authorization")
+                .allow_credentials(true)
+                .max_age(Duration::from_secs(600)),
+            ..Default::default()
+        };
+        let (server_addr, server_task) = start_with_config(config).await?;
+        let client = reqwest::Client::new();
+        let url = format!("http://{}", server_addr);
+        let header = |response: &reqwest::Response, name: &str| {
+            response
+                .headers()
+                .get(name)
+                .map(|value| value.to_str().unwrap().to_string())
+        };
+
+        let response = client
+            .request(reqwest::Method::OPTIONS, &url)
+            .header("origin", "https://app.example.com")
+            .header("access-control-request-method", "POST")
+            .send()
+            .await?;
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(
+            header(&response, "access-control-allow-origin").as_deref(),
+            Some("https://app.example.com")
+        );
+        assert_eq!(
+            header(&response, "access-control-allow-credentials").as_deref(),
+            Some("true")
+        );
+        assert!(header(&response, "access-control-allow-headers")
+            .unwrap()
+            .contains("Authorization"));
+        assert_eq!(
+            header(&response, "access-control-max-age").as_deref(),
+            Some("600")
+        );
+
+        let response = client
+            .request(reqwest::Method::OPTIONS, &url)
+            .header("origin", "https://other.example.com")
+            .send()
+            .await?;
+        assert_eq!(header(&response, "access-control-allow-origin"), None);
+
+        // Responses to actual requests, errors included, carry the headers
+        let response = client
+            .post(&url)
+            .header("origin", "https://app.example.com")
+            .header("content-type", "application/json")
+            .body("{}")
+            .send()
+            .await?;
+        assert_eq!(response.status().as_u16(), 406);
+        assert_eq!(
+            header(&response, "access-control-allow-origin").as_deref(),
+            Some("https://app.example.com")
+        );
+        assert_eq!(
+            header(&response, "access-control-expose-headers").as_deref(),
+            Some("mcp-session-id, mcp-protocol-version")
+        );
+        assert_eq!(header(&response, "access-control-allow-methods"), None);
+
+        server_task.abort();
+        Ok(())
+    }
 }
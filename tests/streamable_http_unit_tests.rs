@@ -258,3 +258,140 @@ fn test_send_options_debug() {
     assert!(debug_str.contains("related_request_id"));
     assert!(debug_str.contains("resumption_token"));
 }
+
+#[tokio::test]
+async fn test_auto_resume_replays_from_last_event_id() {
+    use pmcp::shared::reconnect::ReconnectConfig;
+    use std::time::Duration;
+
+    let event = |id: u32| {
+        format!(
+            "id: {}\ndata: {{\"jsonrpc\":\"2.0\",\"method\":\"notifications/initialized\"}}\n\n",
+            id
+        )
+    };
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("GET", "/")
+        .match_header("last-event-id", mockito::Matcher::Missing)
+        .with_header("content-type", "text/event-stream")
+        .with_body(event(1))
+        .create_async()
+        .await;
+    let resumed = server
+        .mock("GET", "/")
+        .match_header("last-event-id", "1")
+        .with_header("content-type", "text/event-stream")
+        .with_body(event(2))
+        .create_async()
+        .await;
+
+    let mut transport = StreamableHttpTransport::new(StreamableHttpTransportConfig {
+        url: Url::parse(&server.url()).unwrap(),
+        extra_headers: vec![],
+        auth_provider: None,
+        session_id: None,
+        enable_json_response: false,
+        on_resumption_token: None,
+    })
+    .with_auto_resume(ReconnectConfig {
+        initial_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(1),
+        max_retries: Some(2),
+        ..Default::default()
+    });
+
+    transport.start_sse(None).await.unwrap();
+    for _ in 0..2 {
+        tokio::time::timeout(Duration::from_secs(5), transport.receive())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    assert_eq!(transport.last_event_id().as_deref(), Some("2"));
+    first.assert_async().await;
+    resumed.assert_async().await;
+    transport.close().await.unwrap();
+}
+
+/// Hands out a stale token until told it was rejected.
+#[derive(Debug, Default)]
+struct RotatingAuthProvider {
+    rejected: std::sync::Mutex<Option<Option<String>>>,
+}
+
+#[async_trait::async_trait]
+impl AuthProvider for RotatingAuthProvider {
+    async fn get_access_token(&self) -> pmcp::error::Result<String> {
+        let token = match *self.rejected.lock().unwrap() {
+            Some(_) => "fresh",
+            None => "stale",
+        };
+        Ok(token.to_string())
+    }
+
+    async fn handle_unauthorized(
+        &self,
+        www_authenticate: Option<&str>,
+    ) -> pmcp::error::Result<bool> {
+        *self.rejected.lock().unwrap() = Some(www_authenticate.map(str::to_string));
+        Ok(true)
+    }
+}
+
+#[tokio::test]
+async fn test_unauthorized_request_is_retried_with_new_token() {
+    use std::time::Duration;
+
+    let mut server = mockito::Server::new_async().await;
+    let rejected = server
+        .mock("POST", "/")
+        .match_header("authorization", "Bearer stale")
+        .with_status(401)
+        .with_header("www-authenticate", r#"Bearer error="invalid_token""#)
+        .expect(1)
+        .create_async()
+        .await;
+    let accepted = server
+        .mock("POST", "/")
+        .match_header("authorization", "Bearer fresh")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"jsonrpc":"2.0","id":1,"result":{}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let provider = Arc::new(RotatingAuthProvider::default());
+    let mut transport = StreamableHttpTransport::new(StreamableHttpTransportConfig {
+        url: Url::parse(&server.url()).unwrap(),
+        extra_headers: vec![],
+        auth_provider: Some(provider.clone()),
+        session_id: None,
+        enable_json_response: true,
+        on_resumption_token: None,
+    });
+
+    transport
+        .send(pmcp::shared::TransportMessage::Request {
+            id: RequestId::from(1i64),
+            request: Request::Client(Box::new(ClientRequest::Ping)),
+        })
+        .await
+        .unwrap();
+    let response = tokio::time::timeout(Duration::from_secs(5), transport.receive())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        response,
+        pmcp::shared::TransportMessage::Response(_)
+    ));
+
+    let challenge = provider.rejected.lock().unwrap().clone();
+    assert_eq!(
+        challenge,
+        Some(Some(r#"Bearer error="invalid_token""#.to_string()))
+    );
+    rejected.assert_async().await;
+    accepted.assert_async().await;
+}
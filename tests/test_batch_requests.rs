@@ -36,6 +36,7 @@ impl ResourceHandler for TestResourceHandler {
             contents: vec![Content::Text {
                 text: format!("Content of {}", uri),
             }],
+            meta: None,
         })
     }
 
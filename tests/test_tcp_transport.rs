@@ -0,0 +1,191 @@
+//! Tests for the TCP transport and listener.
+
+#[cfg(feature = "tcp")]
+use pmcp::server::transport::TcpServerListener;
+#[cfg(feature = "tcp")]
+use pmcp::shared::{MessageSizeLimits, TcpTransport, Transport, TransportMessage};
+#[cfg(feature = "tcp")]
+use pmcp::types::{ClientNotification, Notification};
+#[cfg(feature = "tcp")]
+use pmcp::{Client, ClientCapabilities, Server};
+#[cfg(feature = "tcp")]
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+#[cfg(feature = "tcp")]
+#[tokio::test]
+async fn test_tcp_client_server_round_trip() {
+    let listener = TcpServerListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let transport = listener.accept().await.unwrap();
+        assert_eq!(transport.transport_type(), "tcp");
+        Server::builder()
+            .name("tcp-test")
+            .version("1.0.0")
+            .build()
+            .unwrap()
+            .run(transport)
+            .await
+    });
+
+    let transport = TcpTransport::connect(addr).await.unwrap();
+    assert_eq!(transport.peer_addr(), addr);
+    let mut client = Client::new(transport);
+    let result = client
+        .initialize(ClientCapabilities::default())
+        .await
+        .unwrap();
+    assert_eq!(result.server_info.name, "tcp-test");
+
+    server.abort();
+}
+
+#[cfg(feature = "tcp")]
+#[tokio::test]
+async fn test_tcp_framing_is_newline_delimited() {
+    let listener = TcpServerListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let peer = tokio::spawn(async move {
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (read, mut write) = stream.into_split();
+        // Split a message across writes and use a CRLF terminator.
+        write
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"notifications/")
+            .await
+            .unwrap();
+        write
+            .write_all(b"initialized\"}\r\n\n[{\"jsonrpc\":\"2.0\",\"method\":\"notifications/initialized\"}]\n")
+            .await
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(read).read_line(&mut line).await.unwrap();
+        line
+    });
+
+    let mut transport = listener.accept().await.unwrap();
+    for _ in 0..2 {
+        let message = transport.receive().await.unwrap();
+        assert!(matches!(
+            message,
+            TransportMessage::Notification(Notification::Client(ClientNotification::Initialized))
+        ));
+    }
+    transport
+        .send(TransportMessage::Notification(Notification::Client(
+            ClientNotification::Initialized,
+        )))
+        .await
+        .unwrap();
+    let line = peer.await.unwrap();
+    assert!(line.ends_with('\n'));
+    assert_eq!(line.matches('\n').count(), 1);
+    assert!(transport.receive().await.is_err());
+    assert!(!transport.is_connected());
+}
+
+#[cfg(feature = "tcp")]
+#[tokio::test]
+async fn test_tcp_rejects_oversized_requests() {
+    let listener = TcpServerListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .with_message_size_limits(MessageSizeLimits::default().with_inbound(64));
+    let addr = listener.local_addr().unwrap();
+
+    let peer = tokio::spawn(async move {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let padding = "x".repeat(100);
+        let request = format!(
+            "{{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\",\"params\":{{\"pad\":\"{}\"}}}}\n",
+            padding
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).await.unwrap();
+        line
+    });
+
+    let mut transport = listener.accept().await.unwrap();
+    let receive = tokio::spawn(async move { transport.receive().await });
+    let reply: serde_json::Value = serde_json::from_str(&peer.await.unwrap()).unwrap();
+    assert_eq!(reply["id"], 1);
+    assert!(reply["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("exceeds"));
+    assert!(receive.await.unwrap().is_err());
+}
+
+#[cfg(feature = "tls")]
+#[tokio::test]
+async fn test_tls_client_server_round_trip() {
+    use pmcp::shared::tcp::{tls_client_config, tls_server_config};
+
+    let server_config = tls_server_config(
+        include_bytes!("fixtures/tls/server.pem"),
+        include_bytes!("fixtures/tls/server.key"),
+    )
+    .unwrap();
+    let client_config = tls_client_config(include_bytes!("fixtures/tls/ca.pem")).unwrap();
+
+    let listener = TcpServerListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .with_tls(server_config);
+    let addr = listener.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let transport = listener.accept().await.unwrap();
+        assert!(transport.is_tls());
+        Server::builder()
+            .name("tls-test")
+            .version("1.0.0")
+            .build()
+            .unwrap()
+            .run(transport)
+            .await
+    });
+
+    let transport = TcpTransport::connect_tls(addr, "localhost", client_config.clone())
+        .await
+        .unwrap();
+    assert_eq!(transport.transport_type(), "tls");
+    let mut client = Client::new(transport);
+    let result = client
+        .initialize(ClientCapabilities::default())
+        .await
+        .unwrap();
+    assert_eq!(result.server_info.name, "tls-test");
+    server.abort();
+
+    // The certificate is not valid for other names.
+    let listener = TcpServerListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .with_tls(
+            tls_server_config(
+                include_bytes!("fixtures/tls/server.pem"),
+                include_bytes!("fixtures/tls/server.key"),
+            )
+            .unwrap(),
+        );
+    let addr = listener.local_addr().unwrap();
+    let accept = tokio::spawn(async move { listener.accept().await });
+    assert!(
+        TcpTransport::connect_tls(addr, "example.com", client_config)
+            .await
+            .is_err()
+    );
+    assert!(accept.await.unwrap().is_err());
+}
+
+#[cfg(feature = "tls")]
+#[test]
+fn test_tls_config_rejects_invalid_pem() {
+    use pmcp::shared::tcp::{tls_client_config, tls_server_config};
+
+    assert!(tls_client_config(b"not a certificate").is_err());
+    assert!(tls_server_config(include_bytes!("fixtures/tls/server.pem"), b"").is_err());
+}
@@ -177,3 +177,118 @@ async fn test_websocket_server_builder_options() {
 
     assert_eq!(transport.transport_type(), "websocket-server");
 }
+
+#[cfg(feature = "websocket")]
+#[tokio::test]
+async fn test_websocket_server_notifies_clients_individually() {
+    use futures::{SinkExt, StreamExt};
+    use pmcp::server::transport::{EnhancedWebSocketConfig, EnhancedWebSocketServer};
+    use pmcp::{ResourceCollection, Server, ServerNotification, StaticResource};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+    type Socket = tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >;
+
+    async fn next_json(socket: &mut Socket) -> Value {
+        loop {
+            let message = timeout(Duration::from_secs(5), socket.next())
+                .await
+                .expect("no message from server")
+                .unwrap()
+                .unwrap();
+            if let Message::Text(text) = message {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    async fn call(socket: &mut Socket, id: i64, method: &str, params: Value) -> Value {
+        let request = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+        socket
+            .send(Message::Text(request.to_string().into()))
+            .await
+            .unwrap();
+        next_json(socket).await
+    }
+
+    let server = Arc::new(
+        Server::builder()
+            .name("shared-server")
+            .version("1.0.0")
+            .resources(
+                ResourceCollection::new()
+                    .add_resource(StaticResource::new_text("file:///notes.txt", "hello")),
+            )
+            .build()
+            .unwrap(),
+    );
+    let transport = EnhancedWebSocketServer::new(EnhancedWebSocketConfig {
+        bind_addr: "127.0.0.1:9011".parse().unwrap(),
+        ..Default::default()
+    });
+    let clients = transport.connected_clients();
+    tokio::spawn(server.clone().run_websocket(transport));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let initialize = json!({
+        "protocolVersion": "2024-11-05",
+        "capabilities": {},
+        "clientInfo": {"name": "test-client", "version": "1.0.0"}
+    });
+    let (mut subscriber, _) = connect_async("ws://127.0.0.1:9011").await.unwrap();
+    let reply = call(&mut subscriber, 1, "initialize", initialize.clone()).await;
+    assert_eq!(reply["id"], 1);
+    let subscriber_id = clients.ids().await[0];
+
+    let (mut bystander, _) = connect_async("ws://127.0.0.1:9011").await.unwrap();
+    let reply = call(&mut bystander, 1, "initialize", initialize).await;
+    assert_eq!(reply["id"], 1);
+    assert_eq!(clients.ids().await.len(), 2);
+
+    let reply = call(
+        &mut subscriber,
+        2,
+        "resources/subscribe",
+        json!({"uri": "file:///notes.txt"}),
+    )
+    .await;
+    assert_eq!(reply["id"], 2);
+    assert!(reply.get("error").is_none(), "{}", reply);
+
+    // Only the subscriber hears about the update
+    assert_eq!(
+        server
+            .notify_resource_updated("file:///notes.txt".to_string())
+            .await
+            .unwrap(),
+        1
+    );
+    let update = next_json(&mut subscriber).await;
+    assert_eq!(update["method"], "notifications/resources/updated");
+    assert_eq!(update["params"]["uri"], "file:///notes.txt");
+
+    assert_eq!(
+        server
+            .broadcast_notification(ServerNotification::ToolsChanged)
+            .await,
+        2
+    );
+    for socket in [&mut subscriber, &mut bystander] {
+        let notification = next_json(socket).await;
+        assert_eq!(notification["method"], "notifications/tools/list_changed");
+    }
+
+    server
+        .notify_client(subscriber_id, ServerNotification::PromptsChanged)
+        .await
+        .unwrap();
+    let notification = next_json(&mut subscriber).await;
+    assert_eq!(notification["method"], "notifications/prompts/list_changed");
+    assert!(server
+        .notify_client(uuid::Uuid::new_v4(), ServerNotification::PromptsChanged)
+        .await
+        .is_err());
+}
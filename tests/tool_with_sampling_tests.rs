@@ -147,6 +147,8 @@ mod tests {
                 },
                 "required": ["text"]
             }),
+            output_schema: None,
+            annotations: None,
         };
 
         assert_eq!(tool_info.name, "summarize");
@@ -234,6 +236,8 @@ mod property_tests {
                     },
                     "required": ["text"]
                 }),
+                output_schema: None,
+                annotations: None,
             };
 
             // Property: Tool info should maintain its structure
@@ -217,6 +217,7 @@ proptest! {
         let options = ProtocolOptions {
             enforce_strict_capabilities: enforce_strict,
             debounced_notification_methods: methods.clone(),
+            ..Default::default()
         };
 
         let protocol = Protocol::new(options.clone());
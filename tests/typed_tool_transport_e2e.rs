@@ -209,6 +209,9 @@ mod tests {
             session_id: None,
             auth_info: None,
             auth_context: None,
+            progress: None,
+            client_requester: None,
+            logger: None,
         };
         let result = tool
             .handle(args, extra)
@@ -243,6 +246,9 @@ mod tests {
             session_id: None,
             auth_info: None,
             auth_context: None,
+            progress: None,
+            client_requester: None,
+            logger: None,
         };
         let result = tool.handle(invalid_args, extra).await;
 
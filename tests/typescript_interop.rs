@@ -52,6 +52,7 @@ impl ResourceHandler for TestResourceHandler {
                 contents: vec![pmcp::types::Content::Text {
                     text: "Hello from Rust server!".to_string(),
                 }],
+                meta: None,
             })
         } else {
             Err(Error::not_found(uri))